@@ -177,6 +177,17 @@ mod tests {
             "and",
             "from",
             "not",
+            "is",
+            "like",
+            "in",
+            "between",
+            "case",
+            "when",
+            "then",
+            "else",
+            "end",
+            "cast",
+            "null",
             "or",
             "select",
             "where",
@@ -4,7 +4,11 @@
 * https://docs.rs/vervolg/latest/vervolg/ast/enum.Statement.html
 ***/
 
-use crate::{intermediate_decimal::IntermediateDecimal, posql_time::PoSQLTimestamp, Identifier};
+use crate::{
+    intermediate_decimal::IntermediateDecimal,
+    posql_time::{PoSQLTimeUnit, PoSQLTimestamp},
+    Identifier,
+};
 use alloc::{boxed::Box, string::String, vec::Vec};
 use core::{
     fmt,
@@ -14,10 +18,12 @@ use core::{
 use serde::{Deserialize, Serialize};
 
 /// Representation of a `SetExpression`, a collection of rows, each having one or more columns.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum SetExpression {
     /// Query result as `SetExpression`
     Query {
+        /// Whether duplicate result rows are removed e.g. `true` in `SELECT DISTINCT a, b FROM table`
+        distinct: bool,
         /// Result expressions e.g. `a` and `b` in `SELECT a, b FROM table`
         result_exprs: Vec<SelectResultExpr>,
         /// Table expression e.g. `table` in `SELECT a, b FROM table`
@@ -26,11 +32,26 @@ pub enum SetExpression {
         /// If None, no filter is applied
         where_expr: Option<Box<Expression>>,
         /// Group by expressions e.g. `a` in `SELECT a, COUNT(*) FROM table GROUP BY a`
-        group_by: Vec<Identifier>,
+        /// or `a + b` in `SELECT a + b AS c, COUNT(*) FROM table GROUP BY a + b`
+        group_by: Vec<Expression>,
+        /// Filter expression on the groups e.g. `COUNT(*) > 10` in
+        /// `SELECT a, COUNT(*) FROM table GROUP BY a HAVING COUNT(*) > 10`
+        /// If None, no filter is applied to the groups
+        having: Option<Box<Expression>>,
+    },
+    /// Rows of both `SetExpression`s e.g. `SELECT a FROM t1 UNION ALL SELECT a FROM t2`
+    ///
+    /// Duplicate rows are kept. A chain of unions is left-associative, so that `right` is
+    /// always a `Query`.
+    UnionAll {
+        /// The rows that come first e.g. `SELECT a FROM t1`
+        left: Box<SetExpression>,
+        /// The rows that come next e.g. `SELECT a FROM t2`
+        right: Box<SetExpression>,
     },
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 /// What to select in a query
 pub enum SelectResultExpr {
     /// All columns in a table e.g. `SELECT * FROM table`
@@ -39,7 +60,7 @@ pub enum SelectResultExpr {
     AliasedResultExpr(AliasedResultExpr),
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 /// An expression with an alias e.g. `a + 1 AS b`
 pub struct AliasedResultExpr {
     /// The expression e.g. `a + 1`, `COUNT(*)`, etc.
@@ -70,7 +91,7 @@ impl AliasedResultExpr {
 }
 
 /// Representations of base queries
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub enum TableExpression {
     /// The row set of a given table; possibly providing an alias
     Named {
@@ -79,6 +100,33 @@ pub enum TableExpression {
         /// Namespace / schema for the table
         schema: Option<Identifier>,
     },
+    /// The inner join of two tables on a condition e.g. `a JOIN b ON a.id = b.a_id`
+    Join {
+        /// The left hand side of the join
+        left: Box<TableExpression>,
+        /// The right hand side of the join
+        right: Box<TableExpression>,
+        /// The join condition
+        on: Box<Expression>,
+    },
+    /// The rows of a subquery under an alias, i.e. a derived table
+    /// e.g. `(SELECT a FROM t) AS d` in `SELECT a FROM (SELECT a FROM t) AS d`
+    Subquery {
+        /// The query whose rows make up the derived table
+        query: Box<SetExpression>,
+        /// The name of the derived table
+        alias: Identifier,
+    },
+}
+
+/// A named query of a WITH clause, i.e. a common table expression
+/// e.g. `d AS (SELECT a FROM t)` in `WITH d AS (SELECT a FROM t) SELECT a FROM d`
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct CommonTableExpression {
+    /// The name the query can be referenced by as a table
+    pub alias: Identifier,
+    /// The query whose rows make up the table
+    pub query: Box<SetExpression>,
 }
 
 /// Binary operators for simple expressions
@@ -96,6 +144,9 @@ pub enum BinaryOperator {
     /// Numeric division
     Division,
 
+    /// Numeric remainder
+    Modulo,
+
     /// Logical And
     And,
 
@@ -110,6 +161,10 @@ pub enum BinaryOperator {
 
     /// Comparison >=
     GreaterThanOrEqual,
+
+    /// String pattern match `LIKE`, where `%` matches any sequence of characters and `_` matches
+    /// a single character
+    Like,
 }
 
 /// Possible unary operators for simple expressions
@@ -117,6 +172,10 @@ pub enum BinaryOperator {
 pub enum UnaryOperator {
     /// Logical inversion
     Not,
+    /// Null check `IS NULL`
+    IsNull,
+    /// Non-null check `IS NOT NULL`
+    IsNotNull,
 }
 
 // Aggregation operators
@@ -131,6 +190,8 @@ pub enum AggregationOperator {
     Sum,
     /// Count
     Count,
+    /// Count of the distinct values
+    CountDistinct,
     /// Return the first value
     First,
 }
@@ -142,6 +203,7 @@ impl Display for AggregationOperator {
             AggregationOperator::Min => write!(f, "min"),
             AggregationOperator::Sum => write!(f, "sum"),
             AggregationOperator::Count => write!(f, "count"),
+            AggregationOperator::CountDistinct => write!(f, "count distinct"),
             AggregationOperator::First => write!(f, "first"),
         }
     }
@@ -156,6 +218,14 @@ pub enum Expression {
     /// Column
     Column(Identifier),
 
+    /// Column qualified by its table e.g. `tab.a`
+    QualifiedColumn {
+        /// The table of the column
+        table: Identifier,
+        /// The column
+        column: Identifier,
+    },
+
     /// Unary operation
     Unary {
         /// The unary operator
@@ -174,6 +244,55 @@ pub enum Expression {
         right: Box<Expression>,
     },
 
+    /// Set membership `expr IN (list)`
+    InList {
+        /// The expression to look up
+        expr: Box<Expression>,
+        /// The values to look the expression up in
+        list: Vec<Expression>,
+    },
+
+    /// Set membership in the result of a subquery e.g. `a IN (SELECT b FROM t)`
+    InSubquery {
+        /// The expression to look up
+        expr: Box<Expression>,
+        /// The query whose single result column holds the values to look the expression up in
+        query: Box<SetExpression>,
+    },
+
+    /// The value of a subquery with a single result column and a single row
+    /// e.g. `(SELECT MAX(b) FROM t)` in `a > (SELECT MAX(b) FROM t)`
+    Subquery(Box<SetExpression>),
+
+    /// Range check `expr BETWEEN low AND high`, including both bounds
+    Between {
+        /// The expression to check
+        expr: Box<Expression>,
+        /// The lower bound
+        low: Box<Expression>,
+        /// The upper bound
+        high: Box<Expression>,
+    },
+
+    /// Conditional `CASE WHEN condition THEN result ... ELSE else_result END`
+    ///
+    /// The simple form `CASE operand WHEN value THEN result ... END` is parsed as the conditions
+    /// `operand = value`.
+    Case {
+        /// The conditions and their results, in the order they are checked
+        when_then: Vec<(Expression, Expression)>,
+        /// The result if no condition holds, which is `NULL` if there is no `ELSE`
+        else_result: Option<Box<Expression>>,
+    },
+
+    /// Type conversion `CAST(expr AS data_type)`, also written as `expr::data_type`
+    Cast {
+        /// The expression to convert
+        expr: Box<Expression>,
+        /// The type to convert the expression to
+        data_type: DataType,
+    },
+
     /// * expression
     Wildcard,
 
@@ -184,6 +303,31 @@ pub enum Expression {
         /// The expression to aggregate
         expr: Box<Expression>,
     },
+
+    /// Window function evaluated for each row over the rows of its partition
+    /// e.g. `SUM(a) OVER (PARTITION BY b ORDER BY c)`
+    Window {
+        /// The window function
+        function: WindowFunction,
+        /// The columns whose values partition the rows, where all rows form one partition if empty
+        partition_by: Vec<Identifier>,
+        /// The order of the rows within a partition
+        order_by: Vec<OrderBy>,
+    },
+}
+
+/// Functions that are evaluated over the rows of a window, see [`Expression::Window`]
+///
+/// Rows of a partition whose ORDER BY keys are equal are peers.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
+pub enum WindowFunction {
+    /// The number of the row within its partition, starting at one
+    RowNumber,
+    /// The number of rows of the partition before the peers of the row, plus one
+    Rank,
+    /// The sum of an expression over the rows of the partition up to the row and its peers,
+    /// i.e. a running sum if there is an ORDER BY and the sum over the partition otherwise
+    Sum(Box<Expression>),
 }
 
 impl Expression {
@@ -277,6 +421,17 @@ impl core::ops::Div<Box<Expression>> for Box<Expression> {
         })
     }
 }
+impl core::ops::Rem<Box<Expression>> for Box<Expression> {
+    type Output = Box<Expression>;
+
+    fn rem(self, rhs: Box<Expression>) -> Box<Expression> {
+        Box::new(Expression::Binary {
+            op: BinaryOperator::Modulo,
+            left: self,
+            right: rhs,
+        })
+    }
+}
 impl core::ops::Sub<Box<Expression>> for Box<Expression> {
     type Output = Box<Expression>;
 
@@ -289,8 +444,55 @@ impl core::ops::Sub<Box<Expression>> for Box<Expression> {
     }
 }
 
+/// Data types that expressions can be cast to
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum DataType {
+    /// `BOOLEAN`
+    Boolean,
+    /// `TINYINT`
+    TinyInt,
+    /// `SMALLINT`
+    SmallInt,
+    /// `INT` or `INTEGER`
+    Int,
+    /// `BIGINT`
+    BigInt,
+    /// `DECIMAL(precision, scale)` or `NUMERIC(precision, scale)`
+    Decimal {
+        /// The total number of digits
+        precision: u8,
+        /// The number of digits after the decimal point
+        scale: i8,
+    },
+    /// `VARCHAR` or `TEXT`
+    VarChar,
+    /// `TIMESTAMP(precision)`, where the precision is the number of fractional digits of the
+    /// seconds
+    Timestamp(PoSQLTimeUnit),
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DataType::Boolean => write!(f, "BOOLEAN"),
+            DataType::TinyInt => write!(f, "TINYINT"),
+            DataType::SmallInt => write!(f, "SMALLINT"),
+            DataType::Int => write!(f, "INT"),
+            DataType::BigInt => write!(f, "BIGINT"),
+            DataType::Decimal { precision, scale } => write!(f, "DECIMAL({precision}, {scale})"),
+            DataType::VarChar => write!(f, "VARCHAR"),
+            DataType::Timestamp(unit) => match unit {
+                PoSQLTimeUnit::Second => write!(f, "TIMESTAMP(0)"),
+                PoSQLTimeUnit::Millisecond => write!(f, "TIMESTAMP(3)"),
+                PoSQLTimeUnit::Microsecond => write!(f, "TIMESTAMP(6)"),
+                PoSQLTimeUnit::Nanosecond => write!(f, "TIMESTAMP(9)"),
+            },
+        }
+    }
+}
+
 /// `OrderBy`
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct OrderBy {
     /// which column to order by
     pub expr: Identifier,
@@ -299,7 +501,7 @@ pub struct OrderBy {
 }
 
 /// `OrderByDirection` values
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum OrderByDirection {
     /// Ascending
     Asc,
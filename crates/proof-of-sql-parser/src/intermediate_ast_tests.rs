@@ -1,12 +1,18 @@
 use crate::{
-    intermediate_ast::OrderByDirection::{Asc, Desc},
+    intermediate_ast::{
+        DataType,
+        OrderByDirection::{Asc, Desc},
+        SelectResultExpr, WindowFunction,
+    },
     intermediate_decimal::IntermediateDecimal,
+    posql_time::PoSQLTimeUnit,
     sql::*,
     utility::*,
     SelectStatement,
 };
 use alloc::{
     borrow::ToOwned,
+    format,
     string::{String, ToString},
     vec,
 };
@@ -625,6 +631,59 @@ fn we_can_parse_logical_not_with_less_precedence_priority_than_equal_operator()
     assert_eq!(parsed_ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_query_with_is_null_and_is_not_null_filter_expressions() {
+    let ast = "select a from sxt_tab where b is null or c IS NOT NULL"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            or(is_null(col("b")), is_not_null(col("c"))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_is_null_with_less_precedence_priority_than_equal_operator_and_more_than_not() {
+    let ast = "select a from sxt_tab where not b = c is not null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from sxt_tab where not ((b = c) is not null)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast, expected_ast);
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            not(is_not_null(equal(col("b"), col("c")))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_is_null_without_null() {
+    assert!("select a from sxt_tab where b is"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b is not"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select null from sxt_tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
 #[test]
 fn we_can_parse_logical_and_with_more_precedence_priority_than_logical_or() {
     let ast = "select a from sxt_tab where a = -1 or c = -3 and a = 3"
@@ -852,18 +911,32 @@ fn we_cannot_parse_a_query_with_select_tablename_followed_by_star() {
 
 #[test]
 fn we_cannot_parse_a_query_with_schemas_followed_by_column_and_table_names() {
-    assert!("select tab.a from tab".parse::<SelectStatement>().is_err());
-    assert!("select tab.a from eth.tab;"
-        .parse::<SelectStatement>()
-        .is_err());
     assert!("select eth.tab.a from eth.tab"
         .parse::<SelectStatement>()
         .is_err());
-    assert!("select a from eth.tab where tab.b = 3;"
+    assert!("select a from eth.tab where eth.tab.b = 3;"
         .parse::<SelectStatement>()
         .is_err());
 }
 
+#[test]
+fn we_can_parse_a_query_with_columns_qualified_by_their_table() {
+    let ast = "select tab.a from eth.tab where tab.b = 3;"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(qualified_col("tab", "a"), "a")],
+            tab(Some("eth"), "tab"),
+            equal(qualified_col("tab", "b"), lit(3)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_cannot_parse_a_query_with_a_subquery() {
     assert!("select a from (select a from tab where b = 4)"
@@ -1042,22 +1115,244 @@ fn we_can_parse_a_query_with_filter_gt() {
 }
 
 #[test]
-fn we_cannot_parse_a_query_with_inner_join_keyword() {
-    assert!(
-        "select tab1.a from tab1 join tab2 on tab1.c = tab2.c where tab2.b > 4;"
-            .parse::<SelectStatement>()
-            .is_err()
+fn we_can_parse_a_query_with_inner_join_keyword() {
+    let ast = "select tab1.a from tab1 join tab2 on tab1.c = tab2.c where tab2.b > 4;"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![col_res(qualified_col("tab1", "a"), "a")],
+            join(
+                tab(None, "tab1"),
+                tab(None, "tab2"),
+                equal(qualified_col("tab1", "c"), qualified_col("tab2", "c")),
+            ),
+            not(le(qualified_col("tab2", "b"), lit(4))),
+            vec![],
+        ),
+        vec![],
+        None,
     );
+    assert_eq!(ast, expected_ast);
 }
 
 // Case when
 #[test]
-fn we_cannot_parse_a_query_with_case_when_keyword() {
-    assert!(
-        "select case when a == 2 then 3 else 5 from tab where b <= 4;"
+fn we_can_parse_a_query_with_case_when_expressions() {
+    let ast = "select case when a = 2 then 3 when b > 1 then c else 5 end as x, \
+        sum(CASE WHEN a >= 0 THEN b END) as y from tab group by d"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(
+                    case_when(
+                        vec![
+                            (equal(col("a"), lit(2)), lit(3)),
+                            (not(le(col("b"), lit(1))), col("c")),
+                        ],
+                        Some(lit(5)),
+                    ),
+                    "x",
+                ),
+                sum_res(case_when(vec![(ge(col("a"), lit(0)), col("b"))], None), "y"),
+            ],
+            tab(None, "tab"),
+            group_by(&["d"]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_simple_case_expression_as_equalities() {
+    let ast = "select case a + 1 when 2 then 'two' when 3 then 'three' end as x from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select case when a + 1 = 2 then 'two' when a + 1 = 3 then 'three' end \
+        as x from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_nested_case_expressions_in_arithmetic() {
+    let ast = "select a from tab where 2 * case when b then case when c then 1 else 2 end end = 4"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            equal(
+                mul(
+                    lit(2),
+                    case_when(
+                        vec![(col("b"), case_when(vec![(col("c"), lit(1))], Some(lit(2))))],
+                        None,
+                    ),
+                ),
+                lit(4),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_malformed_case_expressions() {
+    for sql in [
+        "select case when a == 2 then 3 else 5 from tab where b <= 4;",
+        "select case when a = 2 then 3 else 5 from tab",
+        "select case else 5 end from tab",
+        "select case when a = 2 end from tab",
+        "select case when a = 2 then 3 else end from tab",
+        "select case a when then 3 end from tab",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err());
+    }
+}
+
+#[test]
+fn we_can_parse_a_query_with_cast_expressions() {
+    let ast = "select cast(a as decimal(20, 2)) as x, b::bigint, \
+        CAST(c + 1 AS Timestamp(3)) from tab where d::int >= -(a::smallint)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            vec![
+                col_res(
+                    cast(
+                        col("a"),
+                        DataType::Decimal {
+                            precision: 20,
+                            scale: 2,
+                        },
+                    ),
+                    "x",
+                ),
+                col_res(cast(col("b"), DataType::BigInt), "__expr__"),
+                col_res(
+                    cast(
+                        add(col("c"), lit(1)),
+                        DataType::Timestamp(PoSQLTimeUnit::Millisecond),
+                    ),
+                    "__expr__",
+                ),
+            ],
+            tab(None, "tab"),
+            ge(
+                cast(col("d"), DataType::Int),
+                mul(lit(-1), cast(col("a"), DataType::SmallInt)),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_all_data_types_in_cast_expressions() {
+    for (sql, data_type) in [
+        ("boolean", DataType::Boolean),
+        ("BOOL", DataType::Boolean),
+        ("tinyint", DataType::TinyInt),
+        ("smallint", DataType::SmallInt),
+        ("int", DataType::Int),
+        ("integer", DataType::Int),
+        ("bigint", DataType::BigInt),
+        ("varchar", DataType::VarChar),
+        ("text", DataType::VarChar),
+        (
+            "decimal(10)",
+            DataType::Decimal {
+                precision: 10,
+                scale: 0,
+            },
+        ),
+        (
+            "numeric(75, -3)",
+            DataType::Decimal {
+                precision: 75,
+                scale: -3,
+            },
+        ),
+        ("timestamp", DataType::Timestamp(PoSQLTimeUnit::Second)),
+        ("timestamp(0)", DataType::Timestamp(PoSQLTimeUnit::Second)),
+        (
+            "timestamp(6)",
+            DataType::Timestamp(PoSQLTimeUnit::Microsecond),
+        ),
+        (
+            "timestamp(9)",
+            DataType::Timestamp(PoSQLTimeUnit::Nanosecond),
+        ),
+    ] {
+        let ast = format!("select cast(a as {sql}) as x, a::{sql} as y from tab")
             .parse::<SelectStatement>()
-            .is_err()
+            .unwrap();
+        let expected_ast = select(
+            query_all(
+                vec![
+                    col_res(cast(col("a"), data_type), "x"),
+                    col_res(cast(col("a"), data_type), "y"),
+                ],
+                tab(None, "tab"),
+                vec![],
+            ),
+            vec![],
+            None,
+        );
+        assert_eq!(ast, expected_ast);
+    }
+}
+
+#[test]
+fn we_can_parse_chained_casts() {
+    let ast = "select a::int::varchar from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res(
+                cast(cast(col("a"), DataType::Int), DataType::VarChar),
+                "__expr__",
+            )],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
     );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_malformed_cast_expressions() {
+    for sql in [
+        "select cast(a) from tab",
+        "select cast(a as) from tab",
+        "select cast(a, int) from tab",
+        "select cast(a as float) from tab",
+        "select cast(a as decimal) from tab",
+        "select cast(a as int(3)) from tab",
+        "select cast(a as decimal(300, 2)) from tab",
+        "select cast(a as timestamp(2)) from tab",
+        "select a:: from tab",
+        "select a::float from tab",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err());
+    }
 }
 
 //////////////////////
@@ -1145,6 +1440,30 @@ fn we_can_parse_a_simple_group_by_clause_with_multiple_columns() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_parse_a_group_by_clause_with_expressions() {
+    let ast = "select a + b as c, count(*) as n from tab group by a + b, cast(d as bigint), e"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                SelectResultExpr::AliasedResultExpr(aliased_expr(add(col("a"), col("b")), "c")),
+                count_all_res("n"),
+            ],
+            tab(None, "tab"),
+            group_by_exprs(vec![
+                add(col("a"), col("b")),
+                cast(col("d"), DataType::BigInt),
+                col("e"),
+            ]),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_parse_a_simple_group_by_clause_using_the_wildcard() {
     let ast = "select * from tab group by a"
@@ -1206,35 +1525,35 @@ fn we_can_parse_a_group_by_clause_containing_multiple_aggregations_where_clause_
 }
 
 #[test]
-fn we_cannot_parse_a_group_by_clause_after_order_by() {
-    assert!("select a from tab order by a group by a"
-        .parse::<SelectStatement>()
-        .is_err());
-}
-
-#[test]
-fn we_cannot_parse_a_group_by_clause_before_where_expr() {
-    assert!("select a from tab group by a where a = 3;"
+fn we_can_parse_a_having_clause_with_a_group_by_order_by_and_limit() {
+    let ast = "select a, count(*) as n from tab where d = 3 group by a having count(*) >= 10 and sum(c) = 5 order by a limit 2"
         .parse::<SelectStatement>()
-        .is_err());
+        .unwrap();
+    let expected_ast = select(
+        having(
+            query(
+                vec![col_res(col("a"), "a"), count_all_res("n")],
+                tab(None, "tab"),
+                equal(col("d"), lit(3)),
+                group_by(&["a"]),
+            ),
+            and(ge(count_all(), lit(10)), equal(sum(col("c")), lit(5))),
+        ),
+        order("a", Asc),
+        slice(2, 0),
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_incorrect() {
-    let ast = "select f as f_col, min(a), max(a) as max_a, count(a), count(*) count_all from tab"
+fn we_can_parse_a_having_clause_without_a_group_by() {
+    let ast = "select count(*) as n from tab HAVING count(*) >= 10"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
-        query_all(
-            vec![
-                col_res(col("f"), "f_col"),
-                min_res(col("a"), "__min__"),
-                max_res(col("a"), "max_a"),
-                count_res(col("a"), "__count__"),
-                count_all_res("count_all"),
-            ],
-            tab(None, "tab"),
-            vec![],
+        having(
+            query_all(vec![count_all_res("n")], tab(None, "tab"), vec![]),
+            ge(count_all(), lit(10)),
         ),
         vec![],
         None,
@@ -1243,48 +1562,52 @@ fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_inco
 }
 
 #[test]
-fn we_cannot_parse_a_non_count_aggregations_with_wildcard() {
-    assert!("select min(*) from tab".parse::<SelectStatement>().is_err());
-    assert!("select max(*) from tab".parse::<SelectStatement>().is_err());
-    assert!("select sum(*) from tab".parse::<SelectStatement>().is_err());
+fn we_cannot_parse_a_having_clause_before_group_by_or_after_order_by() {
+    assert!("select a from tab having count(*) >= 1 group by a"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!(
+        "select a from tab group by a order by a having count(*) >= 1"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
 }
 
 #[test]
-fn we_can_parse_a_simple_add_mul_sub_arithmetic_expressions_in_the_result_expr() {
-    let ast = "select a + b, 2 * f, -77 - h, sum(a) / sum(b) from tab;"
+fn we_can_parse_a_select_distinct_query() {
+    let ast = "SELECT DISTINCT a, b FROM tab WHERE c = 3 ORDER BY a"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
-        query_all(
-            vec![
-                col_res(col("a") + col("b"), "__expr__"),
-                col_res(lit(2) * col("f"), "__expr__"),
-                col_res(lit(-77) - col("h"), "__expr__"),
-                col_res(col("a").sum() / col("b").sum(), "__expr__"),
-            ],
+        distinct(query(
+            cols_res(&["a", "b"]),
             tab(None, "tab"),
+            equal(col("c"), lit(3)),
             vec![],
-        ),
-        vec![],
+        )),
+        order("a", Asc),
         None,
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn mul_and_div_operators_have_the_same_precedence_and_left_expressions_are_always_parsed_first() {
-    let ast = "select a * b / c, (a * b) / c, a * (b / c) from tab"
+fn we_can_parse_count_distinct_with_and_without_an_alias() {
+    let ast = "select a, count(distinct b) as n, COUNT(DISTINCT b + c) from tab group by a"
         .parse::<SelectStatement>()
         .unwrap();
     let expected_ast = select(
         query_all(
             vec![
-                col_res(col("a") * col("b") / col("c"), "__expr__"),
-                col_res((col("a") * col("b")) / col("c"), "__expr__"),
-                col_res(col("a") * (col("b") / col("c")), "__expr__"),
+                col_res(col("a"), "a"),
+                SelectResultExpr::AliasedResultExpr(aliased_expr(count_distinct(col("b")), "n")),
+                SelectResultExpr::AliasedResultExpr(aliased_expr(
+                    count_distinct(add(col("b"), col("c"))),
+                    "__count__",
+                )),
             ],
             tab(None, "tab"),
-            vec![],
+            group_by(&["a"]),
         ),
         vec![],
         None,
@@ -1293,15 +1616,525 @@ fn mul_and_div_operators_have_the_same_precedence_and_left_expressions_are_alway
 }
 
 #[test]
-fn we_can_parse_a_single_literal_in_the_result_expr() {
-    let ast = "select -123 from tab".parse::<SelectStatement>().unwrap();
-    let expected_ast = select(
-        query_all(
-            vec![col_res(lit(-123), "__expr__")],
-            tab(None, "tab"),
-            vec![],
-        ),
-        vec![],
+fn we_cannot_parse_distinct_outside_of_the_select_list_or_count() {
+    assert!("select a, distinct b from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select sum(distinct a) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select count(distinct *) from tab"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_union_all_of_queries_with_order_by_and_limit_on_the_union() {
+    let ast = "select a, b from sxt.tx_2024_01 where c = 3 \
+        UNION ALL select a, b from sxt.tx_2024_02 \
+        union all select a, b from sxt.tx_2024_03 order by a limit 5"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        union_all(
+            union_all(
+                query(
+                    cols_res(&["a", "b"]),
+                    tab(Some("sxt"), "tx_2024_01"),
+                    equal(col("c"), lit(3)),
+                    vec![],
+                ),
+                query_all(
+                    cols_res(&["a", "b"]),
+                    tab(Some("sxt"), "tx_2024_02"),
+                    vec![],
+                ),
+            ),
+            query_all(
+                cols_res(&["a", "b"]),
+                tab(Some("sxt"), "tx_2024_03"),
+                vec![],
+            ),
+        ),
+        order("a", Asc),
+        slice(5, 0),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_union_all_of_aggregations() {
+    let ast = "select a, count(*) from tab_1 group by a having count(*) >= 1 \
+        union all select distinct a, b from tab_2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        union_all(
+            having(
+                query_all(
+                    vec![col_res(col("a"), "a"), count_all_res("__count__")],
+                    tab(None, "tab_1"),
+                    group_by(&["a"]),
+                ),
+                ge(count_all(), lit(1)),
+            ),
+            distinct(query_all(cols_res(&["a", "b"]), tab(None, "tab_2"), vec![])),
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_union_without_all_or_with_clauses_before_it() {
+    assert!("select a from tab_1 union select a from tab_2"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!(
+        "select a from tab_1 order by a union all select a from tab_2"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+    assert!("select a from tab_1 limit 1 union all select a from tab_2"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from tab_1 union all"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_subquery_in_the_from_clause() {
+    let ast = "select a, n from (select a, count(*) as n from sxt.tab group by a) as t \
+        where n >= 2 order by a"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a", "n"]),
+            subquery(
+                query_all(
+                    vec![col_res(col("a"), "a"), count_all_res("n")],
+                    tab(Some("sxt"), "tab"),
+                    group_by(&["a"]),
+                ),
+                "t",
+            ),
+            ge(col("n"), lit(2)),
+            vec![],
+        ),
+        order("a", Asc),
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_nested_subqueries_and_subqueries_of_unions_without_as() {
+    let ast =
+        "select * from (select * from (select a from tab_1 union all select a from tab_2) u) t"
+            .parse::<SelectStatement>()
+            .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res_all()],
+            subquery(
+                query_all(
+                    vec![col_res_all()],
+                    subquery(
+                        union_all(
+                            query_all(cols_res(&["a"]), tab(None, "tab_1"), vec![]),
+                            query_all(cols_res(&["a"]), tab(None, "tab_2"), vec![]),
+                        ),
+                        "u",
+                    ),
+                    vec![],
+                ),
+                "t",
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_subquery_without_an_alias_or_with_order_by() {
+    assert!("select a from (select a from tab)"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from (select a from tab order by a) as t"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from (select a from tab limit 1) as t"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_common_table_expressions() {
+    let ast = "WITH big AS (select a, b from sxt.tab where b >= 2), \
+        total As (select a, sum(b) as s from big group by a) \
+        select * from big union all select * from big order by a limit 3"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = with(
+        vec![
+            cte(
+                "big",
+                query(
+                    cols_res(&["a", "b"]),
+                    tab(Some("sxt"), "tab"),
+                    ge(col("b"), lit(2)),
+                    vec![],
+                ),
+            ),
+            cte(
+                "total",
+                query_all(
+                    vec![col_res(col("a"), "a"), sum_res(col("b"), "s")],
+                    tab(None, "big"),
+                    group_by(&["a"]),
+                ),
+            ),
+        ],
+        select(
+            union_all(
+                query_all(vec![col_res_all()], tab(None, "big"), vec![]),
+                query_all(vec![col_res_all()], tab(None, "big"), vec![]),
+            ),
+            order("a", Asc),
+            slice(3, 0),
+        ),
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_invalid_common_table_expressions() {
+    assert!("with select a from tab".parse::<SelectStatement>().is_err());
+    assert!("with d as select a from tab select a from d"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with d (select a from tab) select a from d"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with d as (select a from tab order by a) select a from d"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("with d as (select a from tab), select a from d"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!(
+        "select a from (with d as (select a from tab) select a from d) as t"
+            .parse::<SelectStatement>()
+            .is_err()
+    );
+}
+
+#[test]
+fn we_can_parse_window_functions() {
+    let ast = "select a, ROW_NUMBER() over (partition by a order by b desc) as r, \
+        rank() OVER (order by b, c desc) as k, sum(b + 1) over (partition by a, c) as s, \
+        sum(b) over () from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a"), "a"),
+                col_res(
+                    window(WindowFunction::RowNumber, &["a"], order("b", Desc)),
+                    "r",
+                ),
+                col_res(
+                    window(WindowFunction::Rank, &[], orders(&["b", "c"], &[Asc, Desc])),
+                    "k",
+                ),
+                col_res(
+                    window(
+                        WindowFunction::Sum(add(col("b"), lit(1))),
+                        &["a", "c"],
+                        vec![],
+                    ),
+                    "s",
+                ),
+                col_res(
+                    window(WindowFunction::Sum(col("b")), &[], vec![]),
+                    "__expr__",
+                ),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_window_functions_in_expressions_next_to_aggregations() {
+    let ast = "select sum(b) over (order by b) * 2 as s, sum(b) as t from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(
+                    mul(
+                        window(WindowFunction::Sum(col("b")), &[], order("b", Asc)),
+                        lit(2),
+                    ),
+                    "s",
+                ),
+                sum_res(col("b"), "t"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_invalid_window_functions() {
+    let invalid_queries = [
+        "select row_number() from tab",
+        "select row_number over (order by a) from tab",
+        "select rank(a) over (order by a) from tab",
+        "select max(a) over (order by a) from tab",
+        "select sum(a) over order by a from tab",
+        "select sum(a) over (order by a partition by b) from tab",
+        "select sum(a) over (partition by a + 1) from tab",
+        "select a as rank from tab",
+    ];
+    for query in invalid_queries {
+        assert!(query.parse::<SelectStatement>().is_err(), "{query}");
+    }
+}
+
+#[test]
+fn we_can_parse_in_subqueries_in_the_where_clause() {
+    let ast = "select a from tab where b in (select c from sxt.other where d = 1) \
+        and e not in (select c from other union all select c from another)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            and(
+                in_subquery(
+                    col("b"),
+                    query(
+                        cols_res(&["c"]),
+                        tab(Some("sxt"), "other"),
+                        equal(col("d"), lit(1)),
+                        vec![],
+                    ),
+                ),
+                not(in_subquery(
+                    col("e"),
+                    union_all(
+                        query_all(cols_res(&["c"]), tab(None, "other"), vec![]),
+                        query_all(cols_res(&["c"]), tab(None, "another"), vec![]),
+                    ),
+                )),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_scalar_subqueries_in_expressions() {
+    let ast = "select a from tab where b > (select max(b) from tab) - 1 and (c) = 2"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "tab"),
+            and(
+                not(le(
+                    col("b"),
+                    sub(
+                        scalar_subquery(query_all(
+                            vec![max_res(col("b"), "__max__")],
+                            tab(None, "tab"),
+                            vec![],
+                        )),
+                        lit(1),
+                    ),
+                )),
+                equal(col("c"), lit(2)),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_subqueries_with_order_by_or_without_parentheses() {
+    let invalid_queries = [
+        "select a from tab where b in select c from other",
+        "select a from tab where b in (select c from other order by c)",
+        "select a from tab where b > (select max(c) from other limit 1)",
+        "select a from tab where b in (select c from other), (select d from other)",
+    ];
+    for query in invalid_queries {
+        assert!(query.parse::<SelectStatement>().is_err(), "{query}");
+    }
+}
+
+#[test]
+fn we_cannot_parse_a_group_by_clause_after_order_by() {
+    assert!("select a from tab order by a group by a"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_cannot_parse_a_group_by_clause_before_where_expr() {
+    assert!("select a from tab group by a where a = 3;"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_aggregations_without_group_by_although_it_is_semantically_incorrect() {
+    let ast = "select f as f_col, min(a), max(a) as max_a, count(a), count(*) count_all from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("f"), "f_col"),
+                min_res(col("a"), "__min__"),
+                max_res(col("a"), "max_a"),
+                count_res(col("a"), "__count__"),
+                count_all_res("count_all"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_non_count_aggregations_with_wildcard() {
+    assert!("select min(*) from tab".parse::<SelectStatement>().is_err());
+    assert!("select max(*) from tab".parse::<SelectStatement>().is_err());
+    assert!("select sum(*) from tab".parse::<SelectStatement>().is_err());
+}
+
+#[test]
+fn we_can_parse_a_simple_add_mul_sub_arithmetic_expressions_in_the_result_expr() {
+    let ast = "select a + b, 2 * f, -77 - h, sum(a) / sum(b) from tab;"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a") + col("b"), "__expr__"),
+                col_res(lit(2) * col("f"), "__expr__"),
+                col_res(lit(-77) - col("h"), "__expr__"),
+                col_res(col("a").sum() / col("b").sum(), "__expr__"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn mul_and_div_operators_have_the_same_precedence_and_left_expressions_are_always_parsed_first() {
+    let ast = "select a * b / c, (a * b) / c, a * (b / c) from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a") * col("b") / col("c"), "__expr__"),
+                col_res((col("a") * col("b")) / col("c"), "__expr__"),
+                col_res(col("a") * (col("b") / col("c")), "__expr__"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn mod_has_the_same_precedence_as_mul_and_div() {
+    let ast = "select a % b * c, a / b % c, a + b % c from tab"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(col("a") % col("b") * col("c"), "__expr__"),
+                col_res(col("a") / col("b") % col("c"), "__expr__"),
+                col_res(col("a") + col("b") % col("c"), "__expr__"),
+            ],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_a_modulo_filter_expression() {
+    let ast = "select a from sxt_tab where a % 2 = 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            equal(modulo(col("a"), lit(2)), lit(1)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_single_literal_in_the_result_expr() {
+    let ast = "select -123 from tab".parse::<SelectStatement>().unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![col_res(lit(-123), "__expr__")],
+            tab(None, "tab"),
+            vec![],
+        ),
+        vec![],
         None,
     );
     assert_eq!(ast, expected_ast);
@@ -1448,3 +2281,190 @@ fn we_can_use_aggregation_inside_another_aggregation() {
     );
     assert_eq!(ast, expected_ast);
 }
+
+// Join tests
+#[test]
+fn we_can_parse_a_join_with_qualified_columns() {
+    let ast = "select a.x, b.y as z from a join sxt.b on a.id = b.a_id"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            vec![
+                col_res(qualified_col("a", "x"), "x"),
+                col_res(qualified_col("b", "y"), "z"),
+            ],
+            join(
+                tab(None, "a"),
+                tab(Some("sxt"), "b"),
+                equal(qualified_col("a", "id"), qualified_col("b", "a_id")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_an_inner_join_with_a_where_clause() {
+    let ast = "SELECT x FROM a INNER JOIN b ON id = a_id WHERE b.y = 3"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["x"]),
+            join(
+                tab(None, "a"),
+                tab(None, "b"),
+                equal(col("id"), col("a_id")),
+            ),
+            equal(qualified_col("b", "y"), lit(3)),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_join_of_more_than_two_tables() {
+    let ast = "select x from a join b on a.id = b.id join c on b.id = c.id"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query_all(
+            cols_res(&["x"]),
+            join(
+                join(
+                    tab(None, "a"),
+                    tab(None, "b"),
+                    equal(qualified_col("a", "id"), qualified_col("b", "id")),
+                ),
+                tab(None, "c"),
+                equal(qualified_col("b", "id"), qualified_col("c", "id")),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_a_join_without_a_condition() {
+    assert!("select x from a join b".parse::<SelectStatement>().is_err());
+    assert!("select x from a join b on"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_like_and_not_like_filter_expressions() {
+    let ast = "select a from sxt_tab where b like 'ab%' and c NOT LIKE '%a_c'"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(like(col("b"), lit("ab%")), not(like(col("c"), lit("%a_c")))),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_like_with_the_same_precedence_as_equal() {
+    let ast = "select a from sxt_tab where not b like 'x' is null"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = "select a from sxt_tab where not ((b like 'x') is null)"
+        .parse::<SelectStatement>()
+        .unwrap();
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_like_without_a_pattern() {
+    assert!("select a from sxt_tab where b like"
+        .parse::<SelectStatement>()
+        .is_err());
+    assert!("select a from sxt_tab where b not like"
+        .parse::<SelectStatement>()
+        .is_err());
+}
+
+#[test]
+fn we_can_parse_a_query_with_in_list_and_not_in_list_filter_expressions() {
+    let ast = "select a from sxt_tab where b in (1, -2, 3) and c NOT IN ('x')"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                in_list(col("b"), vec![lit(1), lit(-2), lit(3)]),
+                not(in_list(col("c"), vec![lit("x")])),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_with_between_and_not_between_filter_expressions() {
+    let ast = "select a from sxt_tab where b between 1 and c + 2 and c NOT BETWEEN -1 AND 1"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast = select(
+        query(
+            cols_res(&["a"]),
+            tab(None, "sxt_tab"),
+            and(
+                between(col("b"), lit(1), add(col("c"), lit(2))),
+                not(between(col("c"), lit(-1), lit(1))),
+            ),
+            vec![],
+        ),
+        vec![],
+        None,
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_between_and_in_with_the_same_precedence_as_equal() {
+    let ast = "select a from sxt_tab where not b between 1 and 2 is null or b in (1) = true"
+        .parse::<SelectStatement>()
+        .unwrap();
+    let expected_ast =
+        "select a from sxt_tab where (not ((b between 1 and 2) is null)) or ((b in (1)) = true)"
+            .parse::<SelectStatement>()
+            .unwrap();
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_parse_in_and_between_with_missing_operands() {
+    for sql in [
+        "select a from sxt_tab where b in ()",
+        "select a from sxt_tab where b in 1",
+        "select a from sxt_tab where b not in (1,)",
+        "select a from sxt_tab where b between 1",
+        "select a from sxt_tab where b between and 2",
+        "select a from sxt_tab where b not between 1 and",
+    ] {
+        assert!(sql.parse::<SelectStatement>().is_err());
+    }
+}
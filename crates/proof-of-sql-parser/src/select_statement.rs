@@ -1,4 +1,7 @@
-use super::intermediate_ast::{OrderBy, SetExpression, Slice, TableExpression};
+use super::intermediate_ast::{
+    CommonTableExpression, Expression, OrderBy, SetExpression, Slice, TableExpression,
+    WindowFunction,
+};
 use crate::{sql::SelectStatementParser, Identifier, ParseError, ParseResult, ResourceId};
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use core::{fmt, str::FromStr};
@@ -7,6 +10,10 @@ use serde::{Deserialize, Serialize};
 /// Representation of a select statement, that is, the only type of queries allowed.
 #[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct SelectStatement {
+    /// the common table expressions of a WITH clause, each of which can be referenced as a table
+    /// by the ones after it and by `expr`
+    pub ctes: Vec<CommonTableExpression>,
+
     /// the query expression
     pub expr: Box<SetExpression>,
 
@@ -22,8 +29,8 @@ impl fmt::Debug for SelectStatement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "SelectStatement \n[{:#?},\n{:#?},\n{:#?}\n]",
-            self.expr, self.order_by, self.slice
+            "SelectStatement \n[{:#?},\n{:#?},\n{:#?},\n{:#?}\n]",
+            self.ctes, self.expr, self.order_by, self.slice
         )
     }
 }
@@ -40,19 +47,126 @@ impl SelectStatement {
     /// as this would imply the caller to always know beforehand the referenced
     /// schemas.
     ///
+    /// The common table expressions of a WITH clause are not tables of the database,
+    /// so the references to them are left out, while the tables they reference are included.
+    ///
     /// Return:
     /// - The vector with all tables referenced by the intermediate ast, encoded as resource ids.
     #[must_use]
     pub fn get_table_references(&self, default_schema: Identifier) -> Vec<ResourceId> {
-        let set_expression: &SetExpression = &(self.expr);
-
-        match set_expression {
-            SetExpression::Query {
-                result_exprs: _,
-                from,
-                where_expr: _,
-                group_by: _,
-            } => convert_table_expr_to_resource_id_vector(&from[..], default_schema),
+        let mut tables = Vec::new();
+        let mut cte_tables = Vec::new();
+        for cte in &self.ctes {
+            push_query_table_references(&cte.query, default_schema, &cte_tables, &mut tables);
+            cte_tables.push(ResourceId::new(default_schema, cte.alias));
+        }
+        push_query_table_references(&self.expr, default_schema, &cte_tables, &mut tables);
+        tables
+    }
+}
+
+/// Appends the tables referenced by the queries of a set expression,
+/// except for the given tables of common table expressions.
+fn push_query_table_references(
+    set_expression: &SetExpression,
+    default_schema: Identifier,
+    cte_tables: &[ResourceId],
+    tables: &mut Vec<ResourceId>,
+) {
+    let mut query_tables = Vec::new();
+    push_set_expression_table_references(set_expression, default_schema, &mut query_tables);
+    tables.extend(
+        query_tables
+            .into_iter()
+            .filter(|table| !cte_tables.contains(table)),
+    );
+}
+
+/// Appends the tables referenced by the queries of a set expression, in the order they appear.
+fn push_set_expression_table_references(
+    set_expression: &SetExpression,
+    default_schema: Identifier,
+    tables: &mut Vec<ResourceId>,
+) {
+    match set_expression {
+        SetExpression::Query {
+            distinct: _,
+            result_exprs: _,
+            from,
+            where_expr,
+            group_by: _,
+            having: _,
+        } => {
+            tables.extend(convert_table_expr_to_resource_id_vector(
+                &from[..],
+                default_schema,
+            ));
+            if let Some(where_expr) = where_expr {
+                push_expression_table_references(where_expr, default_schema, tables);
+            }
+        }
+        SetExpression::UnionAll { left, right } => {
+            push_set_expression_table_references(left, default_schema, tables);
+            push_set_expression_table_references(right, default_schema, tables);
+        }
+    }
+}
+
+/// Appends the tables referenced by the subqueries of an expression, in the order they appear.
+fn push_expression_table_references(
+    expression: &Expression,
+    default_schema: Identifier,
+    tables: &mut Vec<ResourceId>,
+) {
+    match expression {
+        Expression::Literal(_)
+        | Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Wildcard
+        | Expression::Window {
+            function: WindowFunction::RowNumber | WindowFunction::Rank,
+            ..
+        } => {}
+        Expression::Unary { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Aggregation { expr, .. }
+        | Expression::Window {
+            function: WindowFunction::Sum(expr),
+            ..
+        } => push_expression_table_references(expr, default_schema, tables),
+        Expression::Binary { left, right, .. } => {
+            push_expression_table_references(left, default_schema, tables);
+            push_expression_table_references(right, default_schema, tables);
+        }
+        Expression::InList { expr, list } => {
+            push_expression_table_references(expr, default_schema, tables);
+            for item in list {
+                push_expression_table_references(item, default_schema, tables);
+            }
+        }
+        Expression::InSubquery { expr, query } => {
+            push_expression_table_references(expr, default_schema, tables);
+            push_set_expression_table_references(query, default_schema, tables);
+        }
+        Expression::Subquery(query) => {
+            push_set_expression_table_references(query, default_schema, tables);
+        }
+        Expression::Between { expr, low, high } => {
+            push_expression_table_references(expr, default_schema, tables);
+            push_expression_table_references(low, default_schema, tables);
+            push_expression_table_references(high, default_schema, tables);
+        }
+        Expression::Case {
+            when_then,
+            else_result,
+        } => {
+            for (when, then) in when_then {
+                push_expression_table_references(when, default_schema, tables);
+                push_expression_table_references(then, default_schema, tables);
+            }
+            if let Some(else_result) = else_result {
+                push_expression_table_references(else_result, default_schema, tables);
+            }
         }
     }
 }
@@ -81,21 +195,39 @@ fn convert_table_expr_to_resource_id_vector(
     let mut tables = Vec::new();
 
     for table_expression in table_expressions {
-        let table_ref: &TableExpression = table_expression;
+        push_table_references(table_expression, default_schema, &mut tables);
+    }
 
-        match table_ref {
-            TableExpression::Named { table, schema } => {
-                let schema = schema.as_ref().map_or_else(
-                    || default_schema.name(),
-                    super::identifier::Identifier::as_str,
-                );
+    tables
+}
 
-                tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
-            }
+/// Appends the tables referenced by a table expression, in the order they appear in the query.
+///
+/// # Panics
+///
+/// This function will panic if `ResourceId::try_new` fails to create a valid `ResourceId`.
+fn push_table_references(
+    table_expression: &TableExpression,
+    default_schema: Identifier,
+    tables: &mut Vec<ResourceId>,
+) {
+    match table_expression {
+        TableExpression::Named { table, schema } => {
+            let schema = schema.as_ref().map_or_else(
+                || default_schema.name(),
+                super::identifier::Identifier::as_str,
+            );
+
+            tables.push(ResourceId::try_new(schema, table.as_str()).unwrap());
+        }
+        TableExpression::Join { left, right, on: _ } => {
+            push_table_references(left, default_schema, tables);
+            push_table_references(right, default_schema, tables);
+        }
+        TableExpression::Subquery { query, alias: _ } => {
+            push_set_expression_table_references(query, default_schema, tables);
         }
     }
-
-    tables
 }
 
 #[cfg(test)]
@@ -138,4 +270,91 @@ mod tests {
 
         assert_eq!(ref_tables, [ResourceId::try_new("schema", "tab").unwrap()]);
     }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_a_join() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A.X FROM A JOIN SCHEMA.B ON A.ID = B.A_ID")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "a").unwrap(),
+                ResourceId::try_new("schema", "b").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_a_union() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM TAB_1 UNION ALL SELECT A FROM SCHEMA.TAB_2 UNION ALL SELECT A FROM TAB_1")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab_1").unwrap(),
+                ResourceId::try_new("schema", "tab_2").unwrap(),
+                ResourceId::try_new("eth", "tab_1").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_a_subquery() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse("SELECT A FROM (SELECT A FROM SCHEMA.TAB) AS T WHERE A = 3")
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(ref_tables, [ResourceId::try_new("schema", "tab").unwrap()]);
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_subqueries_in_the_where_clause() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse(
+                "SELECT A FROM TAB WHERE B IN (SELECT B FROM SCHEMA.OTHER) \
+                    AND C > (SELECT MAX(C) FROM TAB)",
+            )
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("eth", "tab").unwrap(),
+                ResourceId::try_new("schema", "other").unwrap(),
+                ResourceId::try_new("eth", "tab").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn we_can_get_the_correct_table_references_of_common_table_expressions() {
+        let parsed_query_ast = SelectStatementParser::new()
+            .parse(
+                "WITH T AS (SELECT A FROM SCHEMA.TAB), U AS (SELECT A FROM T UNION ALL SELECT A FROM TAB) \
+                    SELECT A FROM U UNION ALL SELECT A FROM T",
+            )
+            .unwrap();
+        let default_schema = Identifier::try_new("ETH").unwrap();
+        let ref_tables = parsed_query_ast.get_table_references(default_schema);
+
+        assert_eq!(
+            ref_tables,
+            [
+                ResourceId::try_new("schema", "tab").unwrap(),
+                ResourceId::try_new("eth", "tab").unwrap()
+            ]
+        );
+    }
 }
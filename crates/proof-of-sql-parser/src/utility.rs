@@ -1,7 +1,8 @@
 use crate::{
     intermediate_ast::{
-        AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
-        OrderByDirection, SelectResultExpr, SetExpression, Slice, TableExpression, UnaryOperator,
+        AggregationOperator, AliasedResultExpr, BinaryOperator, CommonTableExpression, DataType,
+        Expression, Literal, OrderBy, OrderByDirection, SelectResultExpr, SetExpression, Slice,
+        TableExpression, UnaryOperator, WindowFunction,
     },
     Identifier, SelectStatement,
 };
@@ -47,6 +48,68 @@ pub fn le(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A LIKE B
+#[must_use]
+pub fn like(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::Like,
+        left,
+        right,
+    })
+}
+
+/// Construct a new boxed `Expression` A IN (B, C, ...)
+#[must_use]
+pub fn in_list(expr: Box<Expression>, list: Vec<Box<Expression>>) -> Box<Expression> {
+    Box::new(Expression::InList {
+        expr,
+        list: list.into_iter().map(|item| *item).collect(),
+    })
+}
+
+/// Construct a new boxed `Expression` A IN (SELECT ...)
+#[must_use]
+pub fn in_subquery(expr: Box<Expression>, query: Box<SetExpression>) -> Box<Expression> {
+    Box::new(Expression::InSubquery { expr, query })
+}
+
+/// Construct a new boxed `Expression` (SELECT ...) that is the value of a subquery
+#[must_use]
+pub fn scalar_subquery(query: Box<SetExpression>) -> Box<Expression> {
+    Box::new(Expression::Subquery(query))
+}
+
+/// Construct a new boxed `Expression` A BETWEEN B AND C
+#[must_use]
+pub fn between(
+    expr: Box<Expression>,
+    low: Box<Expression>,
+    high: Box<Expression>,
+) -> Box<Expression> {
+    Box::new(Expression::Between { expr, low, high })
+}
+
+/// Construct a new boxed `Expression` CASE WHEN P THEN A ... ELSE B END
+#[must_use]
+pub fn case_when(
+    when_then: Vec<(Box<Expression>, Box<Expression>)>,
+    else_result: Option<Box<Expression>>,
+) -> Box<Expression> {
+    Box::new(Expression::Case {
+        when_then: when_then
+            .into_iter()
+            .map(|(when, then)| (*when, *then))
+            .collect(),
+        else_result,
+    })
+}
+
+/// Construct a new boxed `Expression` CAST(A AS T)
+#[must_use]
+pub fn cast(expr: Box<Expression>, data_type: DataType) -> Box<Expression> {
+    Box::new(Expression::Cast { expr, data_type })
+}
+
 /// Construct a new boxed `Expression` NOT P
 #[must_use]
 pub fn not(expr: Box<Expression>) -> Box<Expression> {
@@ -56,6 +119,24 @@ pub fn not(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A IS NULL
+#[must_use]
+pub fn is_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsNull,
+        expr,
+    })
+}
+
+/// Construct a new boxed `Expression` A IS NOT NULL
+#[must_use]
+pub fn is_not_null(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Unary {
+        op: UnaryOperator::IsNotNull,
+        expr,
+    })
+}
+
 /// Construct a new boxed `Expression` P AND Q
 #[must_use]
 pub fn and(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
@@ -116,6 +197,16 @@ pub fn div(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Construct a new boxed `Expression` A % B
+#[must_use]
+pub fn modulo(left: Box<Expression>, right: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Binary {
+        op: BinaryOperator::Modulo,
+        left,
+        right,
+    })
+}
+
 /// Get table from schema and name.
 ///
 /// If the schema is `None`, the table is assumed to be in the default schema.
@@ -130,6 +221,29 @@ pub fn tab(schema: Option<&str>, name: &str) -> Box<TableExpression> {
     })
 }
 
+/// Join two tables on a condition
+#[must_use]
+pub fn join(
+    left: Box<TableExpression>,
+    right: Box<TableExpression>,
+    on: Box<Expression>,
+) -> Box<TableExpression> {
+    Box::new(TableExpression::Join { left, right, on })
+}
+
+/// Use the rows of a query as a table with the given alias
+///
+/// # Panics
+///
+/// This function will panic if the `alias` cannot be parsed as a valid [Identifier].
+#[must_use]
+pub fn subquery(query: Box<SetExpression>, alias: &str) -> Box<TableExpression> {
+    Box::new(TableExpression::Subquery {
+        query,
+        alias: alias.parse().unwrap(),
+    })
+}
+
 /// Get column from name
 ///
 /// # Panics
@@ -140,6 +254,19 @@ pub fn col(name: &str) -> Box<Expression> {
     Box::new(Expression::Column(name.parse().unwrap()))
 }
 
+/// Get column qualified by its table from names
+///
+/// # Panics
+///
+/// This function will panic if the `table` or the `name` cannot be parsed as valid [Identifier]s.
+#[must_use]
+pub fn qualified_col(table: &str, name: &str) -> Box<Expression> {
+    Box::new(Expression::QualifiedColumn {
+        table: table.parse().unwrap(),
+        column: name.parse().unwrap(),
+    })
+}
+
 /// Get literal from value
 pub fn lit<L: Into<Literal>>(literal: L) -> Box<Expression> {
     Box::new(Expression::Literal(literal.into()))
@@ -181,12 +308,39 @@ pub fn count(expr: Box<Expression>) -> Box<Expression> {
     })
 }
 
+/// Count the amount of distinct non-null entries of expression
+#[must_use]
+pub fn count_distinct(expr: Box<Expression>) -> Box<Expression> {
+    Box::new(Expression::Aggregation {
+        op: AggregationOperator::CountDistinct,
+        expr,
+    })
+}
+
 /// Count the rows
 #[must_use]
 pub fn count_all() -> Box<Expression> {
     count(Box::new(Expression::Wildcard))
 }
 
+/// Evaluate a window function i.e. FUNCTION OVER (PARTITION BY ... ORDER BY ...)
+///
+/// # Panics
+///
+/// This function will panic if any of the `partition_by` columns cannot be parsed as a valid [Identifier].
+#[must_use]
+pub fn window(
+    function: WindowFunction,
+    partition_by: &[&str],
+    order_by: Vec<OrderBy>,
+) -> Box<Expression> {
+    Box::new(Expression::Window {
+        function,
+        partition_by: partition_by.iter().map(|id| id.parse().unwrap()).collect(),
+        order_by,
+    })
+}
+
 /// An expression with an alias i.e. EXPR AS ALIAS
 ///
 /// # Panics
@@ -300,13 +454,15 @@ pub fn query(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
     where_expr: Box<Expression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Expression>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
+        distinct: false,
         from: vec![tab],
         where_expr: Some(where_expr),
         group_by,
+        having: None,
     })
 }
 
@@ -317,16 +473,79 @@ pub fn query(
 pub fn query_all(
     result_exprs: Vec<SelectResultExpr>,
     tab: Box<TableExpression>,
-    group_by: Vec<Identifier>,
+    group_by: Vec<Expression>,
 ) -> Box<SetExpression> {
     Box::new(SetExpression::Query {
         result_exprs,
+        distinct: false,
         from: vec![tab],
         where_expr: None,
         group_by,
+        having: None,
     })
 }
 
+/// The last query of a `SetExpression` i.e. the one that a clause at the end of it belongs to
+fn last_query(mut query: &mut SetExpression) -> &mut SetExpression {
+    while let SetExpression::UnionAll { right, .. } = query {
+        query = right.as_mut();
+    }
+    query
+}
+
+/// Adds a HAVING clause to a `SetExpression` of the kind SELECT ... FROM TAB [WHERE EXPR] GROUP BY ...
+///
+/// For a union, the clause is added to its last query.
+#[must_use]
+pub fn having(mut query: Box<SetExpression>, having_expr: Box<Expression>) -> Box<SetExpression> {
+    if let SetExpression::Query { having, .. } = last_query(&mut query) {
+        *having = Some(having_expr);
+    }
+    query
+}
+
+/// Removes the duplicate rows of a `SetExpression` i.e. turns SELECT ... into SELECT DISTINCT ...
+///
+/// For a union, only the rows of its last query are deduplicated.
+#[must_use]
+pub fn distinct(mut query: Box<SetExpression>) -> Box<SetExpression> {
+    if let SetExpression::Query { distinct, .. } = last_query(&mut query) {
+        *distinct = true;
+    }
+    query
+}
+
+/// Generate a `SetExpression` of the kind QUERY1 UNION ALL QUERY2
+#[must_use]
+pub fn union_all(left: Box<SetExpression>, right: Box<SetExpression>) -> Box<SetExpression> {
+    Box::new(SetExpression::UnionAll { left, right })
+}
+
+/// Name a query as a common table expression of a WITH clause
+///
+/// # Panics
+///
+/// This function will panic if the `alias` cannot be parsed as a valid [Identifier].
+#[must_use]
+pub fn cte(alias: &str, query: Box<SetExpression>) -> CommonTableExpression {
+    CommonTableExpression {
+        alias: alias.parse().unwrap(),
+        query,
+    }
+}
+
+/// Generate a query of the kind WITH ... SELECT ... ORDER BY ... [LIMIT ... OFFSET ...]
+#[must_use]
+pub fn with(
+    ctes: Vec<CommonTableExpression>,
+    select_statement: SelectStatement,
+) -> SelectStatement {
+    SelectStatement {
+        ctes,
+        ..select_statement
+    }
+}
+
 /// Generate a query of the kind SELECT ... ORDER BY ... [LIMIT ... OFFSET ...]
 ///
 /// Note that `expr` is a boxed `SetExpression`
@@ -337,6 +556,7 @@ pub fn select(
     slice: Option<Slice>,
 ) -> SelectStatement {
     SelectStatement {
+        ctes: vec![],
         expr,
         order_by,
         slice,
@@ -389,6 +609,14 @@ pub fn slice(number_rows: u64, offset_value: i64) -> Option<Slice> {
 /// This function will panic if any of the `ids` cannot be parsed
 /// into an identifier.
 #[must_use]
-pub fn group_by(ids: &[&str]) -> Vec<Identifier> {
-    ids.iter().map(|id| id.parse().unwrap()).collect()
+pub fn group_by(ids: &[&str]) -> Vec<Expression> {
+    ids.iter()
+        .map(|id| Expression::Column(id.parse().unwrap()))
+        .collect()
+}
+
+/// Group by clause with multiple expressions i.e. GROUP BY EXPR0, EXPR1, ...
+#[must_use]
+pub fn group_by_exprs(exprs: Vec<Box<Expression>>) -> Vec<Expression> {
+    exprs.into_iter().map(|expr| *expr).collect()
 }
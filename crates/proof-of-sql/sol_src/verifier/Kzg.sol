@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {QueryProofAbi} from "./QueryProofAbi.sol";
+
+/// @title Multilinear KZG evaluation proofs over BN254
+/// @notice Mirrors `KzgEvaluationProof::verify_batched_proof` of the `proof-of-sql` crate, using the `ecAdd`,
+/// `ecMul` and `ecPairing` precompiles.
+library Kzg {
+    /// @notice The modulus of the base field of BN254
+    uint256 internal constant BASE_MODULUS = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47;
+    /// @notice The modulus of the scalar field of BN254
+    uint256 internal constant MODULUS = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001;
+
+    error PrecompileFailed();
+    error EvaluationProofFailed();
+
+    /// @notice The public parameters of the verifier, where G2 points are encoded like `ecPairing` expects them,
+    /// i.e. as `(x.c1, x.c0, y.c1, y.c0)`
+    struct Setup {
+        QueryProofAbi.G1Point g;
+        uint256[4] h;
+        uint256[4][] tauH;
+    }
+
+    /// @notice `a + b`
+    function ecAdd(QueryProofAbi.G1Point memory a, QueryProofAbi.G1Point memory b)
+        internal
+        view
+        returns (QueryProofAbi.G1Point memory result)
+    {
+        uint256[4] memory input = [a.x, a.y, b.x, b.y];
+        uint256[2] memory output;
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x06, input, 0x80, output, 0x40)
+        }
+        if (!success) revert PrecompileFailed();
+        result = QueryProofAbi.G1Point(output[0], output[1]);
+    }
+
+    /// @notice `scalar * point`
+    function ecMul(QueryProofAbi.G1Point memory point, uint256 scalar)
+        internal
+        view
+        returns (QueryProofAbi.G1Point memory result)
+    {
+        uint256[3] memory input = [point.x, point.y, scalar];
+        uint256[2] memory output;
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x07, input, 0x60, output, 0x40)
+        }
+        if (!success) revert PrecompileFailed();
+        result = QueryProofAbi.G1Point(output[0], output[1]);
+    }
+
+    /// @notice `-point`
+    function negate(QueryProofAbi.G1Point memory point) internal pure returns (QueryProofAbi.G1Point memory) {
+        if (point.x == 0 && point.y == 0) return point;
+        return QueryProofAbi.G1Point(point.x, BASE_MODULUS - (point.y % BASE_MODULUS));
+    }
+
+    /// @notice Check that the polynomial committed to by `commitment` evaluates to `product` at `point`, where
+    /// the point is padded with zeros to the number of variables of the setup
+    function verify(
+        QueryProofAbi.G1Point memory commitment,
+        uint256 product,
+        uint256[] memory point,
+        QueryProofAbi.G1Point[] memory quotients,
+        Setup memory setup
+    ) internal view {
+        uint256 maxNu = setup.tauH.length;
+        if (point.length > maxNu || quotients.length != maxNu) revert EvaluationProofFailed();
+        // lhs = C - product * G + sum_j z_j * P_j
+        QueryProofAbi.G1Point memory lhs = ecAdd(commitment, ecMul(setup.g, MODULUS - product));
+        for (uint256 j = 0; j < point.length; ++j) {
+            lhs = ecAdd(lhs, ecMul(quotients[j], point[j]));
+        }
+        // e(lhs, H) * prod_j e(-P_j, tau_j * H) == 1
+        uint256[] memory input = new uint256[](6 * (maxNu + 1));
+        input[0] = lhs.x;
+        input[1] = lhs.y;
+        for (uint256 k = 0; k < 4; ++k) {
+            input[2 + k] = setup.h[k];
+        }
+        for (uint256 j = 0; j < maxNu; ++j) {
+            QueryProofAbi.G1Point memory negatedQuotient = negate(quotients[j]);
+            uint256 start = 6 * (j + 1);
+            input[start] = negatedQuotient.x;
+            input[start + 1] = negatedQuotient.y;
+            for (uint256 k = 0; k < 4; ++k) {
+                input[start + 2 + k] = setup.tauH[j][k];
+            }
+        }
+        uint256[1] memory output;
+        bool success;
+        assembly {
+            success := staticcall(gas(), 0x08, add(input, 0x20), mul(mload(input), 0x20), output, 0x20)
+        }
+        if (!success || output[0] != 1) revert EvaluationProofFailed();
+    }
+}
@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+/// @title The ABI of query results and their proofs
+/// @notice Mirrors `ProvableQueryResult` and `QueryProof<KzgEvaluationProof>` of the `proof-of-sql` crate.
+/// Fields that the verifier only needs to add to the transcript are kept in their postcard serialization.
+library QueryProofAbi {
+    // A point of BN254 G1 in the encoding of the EVM precompiles, where the identity is (0, 0)
+    struct G1Point {
+        uint256 x;
+        uint256 y;
+    }
+
+    // An intermediate form of a query result
+    struct ProvableQueryResult {
+        uint64 numColumns;
+        uint64 tableLength;
+        bytes data;
+    }
+
+    // The proof for a query
+    struct QueryProof {
+        // postcard serialization of the bit distributions
+        bytes bitDistributions;
+        uint64[] oneEvaluationLengths;
+        // postcard serialization of the string dictionaries
+        bytes stringDictionaries;
+        // the commitments to the intermediate MLEs of the first round
+        G1Point[] firstRoundCommitments;
+        // the commitments to the intermediate MLEs
+        G1Point[] commitments;
+        // the coefficients of all sumcheck rounds, each round in reverse order
+        uint256[] sumcheckCoefficients;
+        uint256[] pcsProofEvaluations;
+        // the commitments to the quotients of the KZG evaluation proof
+        G1Point[] evaluationProof;
+    }
+
+    // A query result and its proof together with the metadata and commitments of the queried table
+    struct VerifierInput {
+        ProvableQueryResult result;
+        QueryProof proof;
+        uint64 tableLength;
+        uint64 generatorOffset;
+        // the commitments to the columns that the plan reads, in the order of `get_column_references`
+        G1Point[] columnCommitments;
+    }
+}
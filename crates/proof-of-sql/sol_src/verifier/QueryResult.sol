@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {QueryProofAbi} from "./QueryProofAbi.sol";
+
+/// @title Evaluation of query results
+/// @notice Mirrors `ProvableQueryResult::evaluate` of the `proof-of-sql` crate for results whose columns are
+/// booleans or integers of at most 64 bits.
+library QueryResult {
+    uint8 internal constant BOOLEAN = 0;
+    uint8 internal constant TINYINT = 1;
+    uint8 internal constant SMALLINT = 2;
+    uint8 internal constant INT = 3;
+    uint8 internal constant BIGINT = 4;
+
+    error InvalidColumnCount();
+    error InvalidResultData();
+
+    /// @notice Evaluate the MLEs of the columns of `result` at `point`, where the types of the columns are the
+    /// bytes of `columnTypes`
+    function evaluate(
+        QueryProofAbi.ProvableQueryResult memory result,
+        bytes memory columnTypes,
+        uint256[] memory point,
+        uint256 modulus
+    ) internal pure returns (uint256[] memory evaluations) {
+        if (result.numColumns != columnTypes.length) revert InvalidColumnCount();
+        uint256[] memory lagrangeBasis = lagrangeBasisEvaluations(result.tableLength, point, modulus);
+        evaluations = new uint256[](columnTypes.length);
+        uint256 offset = 0;
+        for (uint256 column = 0; column < columnTypes.length; ++column) {
+            uint8 columnType = uint8(columnTypes[column]);
+            for (uint256 row = 0; row < lagrangeBasis.length; ++row) {
+                uint256 value;
+                (value, offset) = readValue(result.data, offset, columnType, modulus);
+                evaluations[column] =
+                    addmod(evaluations[column], mulmod(lagrangeBasis[row], value, modulus), modulus);
+            }
+        }
+        if (offset != result.data.length) revert InvalidResultData();
+    }
+
+    /// @notice `eq(point, i)` for every `i < length`, like `compute_evaluation_vector`
+    function lagrangeBasisEvaluations(uint256 length, uint256[] memory point, uint256 modulus)
+        internal
+        pure
+        returns (uint256[] memory result)
+    {
+        if (length > (uint256(1) << point.length)) revert InvalidResultData();
+        result = new uint256[](length);
+        for (uint256 i = 0; i < length; ++i) {
+            result[i] = 1;
+            for (uint256 k = 0; k < point.length; ++k) {
+                uint256 factor = ((i >> k) & 1) == 1 ? point[k] : addmod(1, modulus - point[k], modulus);
+                result[i] = mulmod(result[i], factor, modulus);
+            }
+        }
+    }
+
+    /// @notice Decode a value of the given type at `offset` and return it as a scalar together with the offset
+    /// of the next value
+    function readValue(bytes memory data, uint256 offset, uint8 columnType, uint256 modulus)
+        internal
+        pure
+        returns (uint256 value, uint256 nextOffset)
+    {
+        uint256 encoded;
+        (encoded, nextOffset) = readVarint(data, offset);
+        if (columnType == BOOLEAN) {
+            if (encoded > 1) revert InvalidResultData();
+            return (encoded, nextOffset);
+        }
+        // Signed integers are zigzag encoded.
+        uint256 bound = uint256(1) << 63;
+        if (columnType == TINYINT) bound = uint256(1) << 7;
+        else if (columnType == SMALLINT) bound = uint256(1) << 15;
+        else if (columnType == INT) bound = uint256(1) << 31;
+        if ((encoded & 1) == 0) {
+            value = encoded >> 1;
+            if (value >= bound) revert InvalidResultData();
+        } else {
+            uint256 magnitude = (encoded >> 1) + 1;
+            if (magnitude > bound) revert InvalidResultData();
+            value = modulus - magnitude;
+        }
+    }
+
+    /// @notice Decode an unsigned LEB128 varint of at most 64 bits, like `u64::decode_var`
+    function readVarint(bytes memory data, uint256 offset) internal pure returns (uint256 value, uint256 nextOffset) {
+        uint256 shift = 0;
+        nextOffset = offset;
+        while (true) {
+            if (nextOffset >= data.length) revert InvalidResultData();
+            uint8 b = uint8(data[nextOffset++]);
+            value |= uint256(b & 0x7f) << shift;
+            shift += 7;
+            if (shift > 63) {
+                if (b >= 2) revert InvalidResultData();
+                break;
+            } else if ((b & 0x80) == 0) {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Transcript} from "./Transcript.sol";
+
+/// @title Sumcheck verification
+/// @notice Mirrors `SumcheckProof::verify_without_evaluation` of the `proof-of-sql` crate.
+library Sumcheck {
+    error InvalidSumcheckProofSize();
+    error InvalidScalar();
+    error RoundEvaluationMismatch();
+
+    /// @notice Verify the rounds of a sumcheck proof of a zero sum and return the evaluation point and
+    /// the evaluation that the polynomial is expected to have there
+    function verifyWithoutEvaluation(
+        Transcript.State memory transcript,
+        uint256[] memory coefficients,
+        uint64 maxMultiplicands,
+        uint64 numVariables,
+        uint256 modulus
+    ) internal pure returns (uint256[] memory evaluationPoint, uint256 expectedEvaluation) {
+        Transcript.append(transcript, abi.encodePacked(maxMultiplicands, numVariables));
+        // This challenge is in order to keep transcript messages grouped.
+        Transcript.challenge(transcript);
+        uint256 roundLength = uint256(maxMultiplicands) + 1;
+        if (coefficients.length != uint256(numVariables) * roundLength) revert InvalidSumcheckProofSize();
+        evaluationPoint = new uint256[](numVariables);
+        expectedEvaluation = 0;
+        for (uint256 round = 0; round < numVariables; ++round) {
+            uint256 start = round * roundLength;
+            bytes memory message = "";
+            for (uint256 i = 0; i < roundLength; ++i) {
+                if (coefficients[start + i] >= modulus) revert InvalidScalar();
+                message = bytes.concat(message, bytes32(coefficients[start + i]));
+            }
+            Transcript.append(transcript, message);
+            uint256 roundEvaluationPoint = Transcript.scalarChallenge(transcript, modulus);
+            evaluationPoint[round] = roundEvaluationPoint;
+            // The coefficients are in reverse order, so the first one is the leading coefficient and the
+            // last one is the evaluation at zero.
+            uint256 roundEvaluation = coefficients[start];
+            uint256 actualSum = addmod(roundEvaluation, coefficients[start + roundLength - 1], modulus);
+            for (uint256 i = 1; i < roundLength; ++i) {
+                roundEvaluation =
+                    addmod(mulmod(roundEvaluation, roundEvaluationPoint, modulus), coefficients[start + i], modulus);
+                actualSum = addmod(actualSum, coefficients[start + i], modulus);
+            }
+            if (actualSum != expectedEvaluation) revert RoundEvaluationMismatch();
+            expectedEvaluation = roundEvaluation;
+        }
+    }
+}
@@ -0,0 +1,78 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {QueryProofAbi} from "./QueryProofAbi.sol";
+
+/// @title Public coin transcript
+/// @notice Mirrors `Keccak256Transcript` of the `proof-of-sql` crate, where
+/// `challenge_(i+1) = keccak256(challenge_i, message_(i+1))`.
+library Transcript {
+    /// @notice The messages since the last challenge, prefixed with the last challenge
+    struct State {
+        bytes buffer;
+    }
+
+    /// @notice Append a message as raw bytes
+    function append(State memory state, bytes memory message) internal pure {
+        state.buffer = bytes.concat(state.buffer, message);
+    }
+
+    /// @notice Draw a challenge as raw bytes
+    function challenge(State memory state) internal pure returns (bytes32 result) {
+        result = keccak256(state.buffer);
+        state.buffer = abi.encodePacked(result);
+    }
+
+    /// @notice Draw a challenge and reduce it to a scalar, like `scalar_challenge_as_be`
+    function scalarChallenge(State memory state, uint256 modulus) internal pure returns (uint256) {
+        return uint256(challenge(state)) % modulus;
+    }
+
+    /// @notice Draw `count` scalar challenges
+    function scalarChallenges(State memory state, uint256 count, uint256 modulus)
+        internal
+        pure
+        returns (uint256[] memory result)
+    {
+        result = new uint256[](count);
+        for (uint256 i = 0; i < count; ++i) {
+            result[i] = scalarChallenge(state, modulus);
+        }
+    }
+
+    /// @notice The postcard serialization of an unsigned integer, i.e. its LEB128 varint encoding
+    function varint(uint256 value) internal pure returns (bytes memory result) {
+        result = "";
+        while (value > 0x7f) {
+            result = bytes.concat(result, bytes1(uint8((value & 0x7f) | 0x80)));
+            value >>= 7;
+        }
+        result = bytes.concat(result, bytes1(uint8(value)));
+    }
+
+    /// @notice The postcard serialization of a list of commitments, where every point is serialized as the
+    /// 64 bytes of its coordinates
+    function points(QueryProofAbi.G1Point[] memory values) internal pure returns (bytes memory result) {
+        result = varint(values.length);
+        for (uint256 i = 0; i < values.length; ++i) {
+            result = bytes.concat(result, hex"40", bytes32(values[i].x), bytes32(values[i].y));
+        }
+    }
+
+    /// @notice The canonical serialization of a list of scalars, i.e. the length as little endian `u64`
+    /// followed by the little endian scalars
+    function canonicalScalars(uint256[] memory scalars) internal pure returns (bytes memory result) {
+        result = abi.encodePacked(bytes8(uint64(reverseBytes(scalars.length) >> 192)));
+        for (uint256 i = 0; i < scalars.length; ++i) {
+            result = bytes.concat(result, bytes32(reverseBytes(scalars[i])));
+        }
+    }
+
+    /// @notice Reverse the byte order of a word
+    function reverseBytes(uint256 value) internal pure returns (uint256 result) {
+        for (uint256 i = 0; i < 32; ++i) {
+            result = (result << 8) | (value & 0xff);
+            value >>= 8;
+        }
+    }
+}
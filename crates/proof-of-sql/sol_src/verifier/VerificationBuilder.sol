@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {QueryProofAbi} from "./QueryProofAbi.sol";
+import {Kzg} from "./Kzg.sol";
+
+/// @title Verification builder
+/// @notice Mirrors `VerificationBuilder` and `SumcheckMleEvaluations` of the `proof-of-sql` crate. The generated
+/// verifier of a plan passes a builder through the evaluation of the plan, which tracks the MLE evaluations that
+/// the plan consumes and the sumcheck subpolynomials that it produces.
+library VerificationBuilder {
+    uint256 internal constant MODULUS = Kzg.MODULUS;
+
+    struct State {
+        uint256[] pcsProofEvaluations;
+        uint256[] innerProductMultipliers;
+        uint256[] subpolynomialMultipliers;
+        // used as a stack, so the last challenge is consumed first
+        uint256[] postResultChallenges;
+        QueryProofAbi.G1Point[] intermediateCommitments;
+        uint256 consumedPcsProofMles;
+        uint256 consumedIntermediateMles;
+        uint256 producedSubpolynomials;
+        uint256 consumedPostResultChallenges;
+        uint256 inputOneEvaluation;
+        uint256 outputOneEvaluation;
+        uint256 randomEvaluation;
+        uint256 sumcheckEvaluation;
+        uint256 foldedPcsProofEvaluation;
+        QueryProofAbi.G1Point foldedPcsProofCommitment;
+    }
+
+    /// @notice Consume the evaluation of an MLE whose commitment the verifier knows and fold it into the claim of
+    /// the evaluation proof
+    function consumeAnchoredMle(State memory state, QueryProofAbi.G1Point memory commitment)
+        internal
+        view
+        returns (uint256 result)
+    {
+        uint256 index = state.consumedPcsProofMles++;
+        uint256 multiplier = state.innerProductMultipliers[index];
+        result = state.pcsProofEvaluations[index];
+        state.foldedPcsProofCommitment =
+            Kzg.ecAdd(state.foldedPcsProofCommitment, Kzg.ecMul(commitment, multiplier));
+        state.foldedPcsProofEvaluation =
+            addmod(state.foldedPcsProofEvaluation, mulmod(multiplier, result, MODULUS), MODULUS);
+    }
+
+    /// @notice Consume the evaluation of an intermediate MLE, whose commitment is part of the proof
+    function consumeIntermediateMle(State memory state) internal view returns (uint256) {
+        return consumeAnchoredMle(state, state.intermediateCommitments[state.consumedIntermediateMles++]);
+    }
+
+    /// @notice Produce the evaluation of a subpolynomial that is zero on every row
+    function produceIdentity(State memory state, uint256 evaluation) internal pure {
+        produceZeroSum(state, mulmod(evaluation, state.randomEvaluation, MODULUS));
+    }
+
+    /// @notice Produce the evaluation of a subpolynomial that sums to zero over all rows
+    function produceZeroSum(State memory state, uint256 evaluation) internal pure {
+        uint256 multiplier = state.subpolynomialMultipliers[state.producedSubpolynomials++];
+        state.sumcheckEvaluation = addmod(state.sumcheckEvaluation, mulmod(multiplier, evaluation, MODULUS), MODULUS);
+    }
+
+    /// @notice Pop a challenge off the stack of post-result challenges
+    function consumePostResultChallenge(State memory state) internal pure returns (uint256) {
+        return state.postResultChallenges[state.postResultChallenges.length - ++state.consumedPostResultChallenges];
+    }
+
+    /// @notice Whether every MLE evaluation, subpolynomial and challenge of the proof has been used
+    function completed(State memory state) internal pure returns (bool) {
+        return state.consumedPcsProofMles == state.pcsProofEvaluations.length
+            && state.consumedIntermediateMles == state.intermediateCommitments.length
+            && state.producedSubpolynomials == state.subpolynomialMultipliers.length
+            && state.consumedPostResultChallenges == state.postResultChallenges.length;
+    }
+
+    /// @notice `sum_(i < length) eq(point, i)`, i.e. the evaluation of the MLE of `length` ones at `point`,
+    /// like `compute_truncated_lagrange_basis_sum`
+    function truncatedLagrangeBasisSum(uint256 length, uint256[] memory point)
+        internal
+        pure
+        returns (uint256 part)
+    {
+        // `part` is the truncated sum over the first `k` variables, and the full sum over them is one.
+        for (uint256 k = 0; k < point.length; ++k) {
+            uint256 firstHalfTerm = addmod(1, MODULUS - point[k], MODULUS);
+            if (((length >> k) & 1) == 1) {
+                part = addmod(firstHalfTerm, mulmod(part, point[k], MODULUS), MODULUS);
+            } else {
+                part = mulmod(part, firstHalfTerm, MODULUS);
+            }
+        }
+        if ((length >> point.length) != 0) part = 1;
+    }
+
+    /// @notice `sum_(i < length) eq(a, i) * eq(b, i)`, like `compute_truncated_lagrange_basis_inner_product`
+    function truncatedLagrangeBasisInnerProduct(uint256 length, uint256[] memory a, uint256[] memory b)
+        internal
+        pure
+        returns (uint256 part)
+    {
+        // `part` and `full` are the truncated and the full sums over the first `k` variables.
+        uint256 full = 1;
+        for (uint256 k = 0; k < a.length; ++k) {
+            uint256 firstHalfTerm = mulmod(addmod(1, MODULUS - a[k], MODULUS), addmod(1, MODULUS - b[k], MODULUS), MODULUS);
+            uint256 secondHalfTerm = mulmod(a[k], b[k], MODULUS);
+            if (((length >> k) & 1) == 1) {
+                part = addmod(mulmod(full, firstHalfTerm, MODULUS), mulmod(part, secondHalfTerm, MODULUS), MODULUS);
+            } else {
+                part = mulmod(part, firstHalfTerm, MODULUS);
+            }
+            full = mulmod(full, addmod(firstHalfTerm, secondHalfTerm, MODULUS), MODULUS);
+        }
+        if ((length >> a.length) != 0) part = full;
+    }
+}
@@ -1,5 +1,7 @@
 //! Types for creation and utilization of cryptographic commitments to proof-of-sql data.
 use crate::base::scalar::Scalar;
+#[cfg(all(not(feature = "blitzar"), feature = "std"))]
+pub use crate::proof_primitive::inner_product::InnerProductProof;
 use alloc::vec::Vec;
 #[cfg(feature = "blitzar")]
 pub use blitzar::{
@@ -33,6 +35,8 @@ pub use column_commitments::{
 };
 
 mod table_commitment;
+#[cfg(feature = "arrow")]
+pub use table_commitment::{AppendRecordBatchTableCommitmentError, RecordBatchToColumnsError};
 pub use table_commitment::{
     AppendTableCommitmentError, MixedLengthColumns, NegativeRange, TableCommitment,
     TableCommitmentArithmeticError, TableCommitmentFromColumnsError,
@@ -121,11 +125,14 @@ impl Commitment for RistrettoPoint {
     }
     #[cfg(not(feature = "blitzar"))]
     fn compute_commitments(
-        _committable_columns: &[CommittableColumn],
-        _offset: usize,
+        committable_columns: &[CommittableColumn],
+        offset: usize,
         _setup: &Self::PublicSetup<'_>,
     ) -> Vec<Self> {
-        unimplemented!()
+        crate::proof_primitive::inner_product::compute_curve25519_commitments(
+            committable_columns,
+            offset,
+        )
     }
 }
 
@@ -3,16 +3,24 @@ use super::{
     ColumnCommitmentsMismatch, Commitment, DuplicateIdentifiers,
 };
 #[cfg(feature = "arrow")]
-use crate::base::database::{ArrayRefExt, ArrowArrayToColumnConversionError};
+use crate::base::database::{
+    presence_column_id, ArrayRefExt, ArrowArrayToColumnConversionError, NullableColumn,
+};
 use crate::base::{
     database::{Column, ColumnField, CommitmentAccessor, OwnedTable, TableRef},
     scalar::Scalar,
 };
+#[cfg(feature = "arrow")]
+use alloc::string::String;
 use alloc::vec::Vec;
 #[cfg(feature = "arrow")]
-use arrow::record_batch::RecordBatch;
+use arrow::{array::ArrayRef, record_batch::RecordBatch};
 use bumpalo::Bump;
+#[cfg(feature = "arrow")]
+use core::iter;
 use core::ops::Range;
+#[cfg(feature = "arrow")]
+use itertools::Itertools;
 use proof_of_sql_parser::{Identifier, ParseError};
 use serde::{Deserialize, Serialize};
 use snafu::Snafu;
@@ -99,6 +107,12 @@ pub enum RecordBatchToColumnsError {
         /// The underlying source error
         source: ParseError,
     },
+    /// This error occurs when a nullable field has a name that is too long to have a presence column.
+    #[snafu(display("nullable field name is too long: {name}"))]
+    NullableFieldNameTooLong {
+        /// The name of the field
+        name: String,
+    },
 }
 
 /// Errors that can occur when attempting to append a record batch to a [`TableCommitment`].
@@ -403,6 +417,9 @@ impl<C: Commitment> TableCommitment<C> {
     ///
     /// The row offset is assumed to be the end of the [`TableCommitment`]'s current range.
     ///
+    /// A field is appended together with its presence column exactly if the [`TableCommitment`] already
+    /// has the presence column, so nulls can only be appended to fields whose presence is committed to.
+    ///
     /// Will error on a variety of mismatches, or if the provided columns have mixed length.
     #[cfg(feature = "arrow")]
     #[allow(clippy::missing_panics_doc)]
@@ -411,12 +428,13 @@ impl<C: Commitment> TableCommitment<C> {
         batch: &RecordBatch,
         setup: &C::PublicSetup<'_>,
     ) -> Result<(), AppendRecordBatchTableCommitmentError> {
-        match self.try_append_rows(
-            batch_to_columns::<C::Scalar>(batch, &Bump::new())?
-                .iter()
-                .map(|(a, b)| (a, b)),
-            setup,
-        ) {
+        let alloc = Bump::new();
+        let columns = batch_to_columns::<C::Scalar>(batch, &alloc, |identifier, _| {
+            presence_column_id(identifier).is_some_and(|presence_id| {
+                self.column_commitments.get_metadata(&presence_id).is_some()
+            })
+        })?;
+        match self.try_append_rows(columns.iter().map(|(a, b)| (a, b)), setup) {
             Ok(()) => Ok(()),
             Err(AppendTableCommitmentError::MixedLengthColumns { .. }) => {
                 panic!("RecordBatches cannot have columns of mixed length")
@@ -432,6 +450,10 @@ impl<C: Commitment> TableCommitment<C> {
         }
     }
     /// Returns a [`TableCommitment`] to the provided arrow [`RecordBatch`].
+    ///
+    /// Every field that contains nulls is committed to together with its presence column. See [`presence_column_id`].
+    /// Fields without nulls, including nullable ones, are committed to exactly like non-nullable fields,
+    /// so nulls can't be appended to them later.
     #[cfg(feature = "arrow")]
     pub fn try_from_record_batch(
         batch: &RecordBatch,
@@ -441,6 +463,8 @@ impl<C: Commitment> TableCommitment<C> {
     }
 
     /// Returns a [`TableCommitment`] to the provided arrow [`RecordBatch`] with the given row offset.
+    ///
+    /// Nullable fields are committed to like in [`TableCommitment::try_from_record_batch`].
     #[allow(clippy::missing_panics_doc)]
     #[cfg(feature = "arrow")]
     pub fn try_from_record_batch_with_offset(
@@ -449,7 +473,7 @@ impl<C: Commitment> TableCommitment<C> {
         setup: &C::PublicSetup<'_>,
     ) -> Result<TableCommitment<C>, RecordBatchToColumnsError> {
         match Self::try_from_columns_with_offset(
-            batch_to_columns::<C::Scalar>(batch, &Bump::new())?
+            batch_to_columns::<C::Scalar>(batch, &Bump::new(), |_, array| array.null_count() != 0)?
                 .iter()
                 .map(|(a, b)| (a, b)),
             offset,
@@ -466,10 +490,13 @@ impl<C: Commitment> TableCommitment<C> {
     }
 }
 
+/// Converts the fields of `batch` into columns, where the fields for which `has_presence_column` is `true`
+/// are followed by their presence columns.
 #[cfg(feature = "arrow")]
 fn batch_to_columns<'a, S: Scalar + 'a>(
     batch: &'a RecordBatch,
     alloc: &'a Bump,
+    has_presence_column: impl Fn(Identifier, &ArrayRef) -> bool,
 ) -> Result<Vec<(Identifier, Column<'a, S>)>, RecordBatchToColumnsError> {
     batch
         .schema()
@@ -478,9 +505,23 @@ fn batch_to_columns<'a, S: Scalar + 'a>(
         .zip(batch.columns())
         .map(|(field, array)| {
             let identifier: Identifier = field.name().parse()?;
-            let column: Column<S> = array.to_column(alloc, &(0..array.len()), None)?;
-            Ok((identifier, column))
+            let range = 0..array.len();
+            let (column, presence) = if has_presence_column(identifier, array) {
+                let presence_id = presence_column_id(identifier).ok_or_else(|| {
+                    RecordBatchToColumnsError::NullableFieldNameTooLong {
+                        name: field.name().clone(),
+                    }
+                })?;
+                let NullableColumn { values, presence } =
+                    array.to_nullable_column(alloc, &range, None)?;
+                let presence = presence.map(|presence| (presence_id, Column::Boolean(presence)));
+                (values, presence)
+            } else {
+                (array.to_column(alloc, &range, None)?, None)
+            };
+            Ok(iter::once((identifier, column)).chain(presence))
         })
+        .flatten_ok()
         .collect()
 }
 
@@ -1316,4 +1357,121 @@ mod tests {
 
         assert_eq!(commitment, expected_commitment);
     }
+
+    #[test]
+    fn we_can_create_table_commitments_with_nullable_record_batches() {
+        use alloc::sync::Arc;
+        use arrow::{
+            array::{ArrayRef, Int64Array},
+            datatypes::{DataType, Field, Schema},
+        };
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let array: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]));
+        let batch = RecordBatch::try_new(schema, vec![array]).unwrap();
+
+        let columns = [
+            (
+                &"a".parse().unwrap(),
+                &Column::<Curve25519Scalar>::BigInt(&[1, 0, 3]),
+            ),
+            (
+                &"__a_presence__".parse().unwrap(),
+                &Column::<Curve25519Scalar>::Boolean(&[true, false, true]),
+            ),
+        ];
+        let expected_commitment =
+            TableCommitment::<RistrettoPoint>::try_from_columns_with_offset(columns, 0, &())
+                .unwrap();
+
+        let commitment =
+            TableCommitment::<RistrettoPoint>::try_from_record_batch(&batch, &()).unwrap();
+
+        assert_eq!(commitment, expected_commitment);
+    }
+
+    #[test]
+    fn we_can_create_table_commitments_with_nullable_fields_without_nulls() {
+        use alloc::sync::Arc;
+        use arrow::{
+            array::{ArrayRef, Int64Array},
+            datatypes::{DataType, Field, Schema},
+        };
+
+        let array: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 3]));
+        let nullable_batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)])),
+            vec![array.clone()],
+        )
+        .unwrap();
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)])),
+            vec![array],
+        )
+        .unwrap();
+
+        let nullable_commitment =
+            TableCommitment::<RistrettoPoint>::try_from_record_batch(&nullable_batch, &()).unwrap();
+        let commitment =
+            TableCommitment::<RistrettoPoint>::try_from_record_batch(&batch, &()).unwrap();
+
+        assert_eq!(nullable_commitment, commitment);
+        assert_eq!(nullable_commitment.num_columns(), 1);
+    }
+
+    #[test]
+    fn we_can_only_append_nulls_to_table_commitments_with_presence_columns() {
+        use alloc::sync::Arc;
+        use arrow::{
+            array::{ArrayRef, Int64Array},
+            datatypes::{DataType, Field, Schema},
+        };
+
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+        let batch_with_nulls = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int64Array::from(vec![Some(1), None])) as ArrayRef],
+        )
+        .unwrap();
+        let batch_without_nulls = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from(vec![3, 4])) as ArrayRef],
+        )
+        .unwrap();
+
+        let mut commitment =
+            TableCommitment::<RistrettoPoint>::try_from_record_batch(&batch_with_nulls, &())
+                .unwrap();
+        commitment
+            .try_append_record_batch(&batch_without_nulls, &())
+            .unwrap();
+        let columns = [
+            (
+                &"a".parse().unwrap(),
+                &Column::<Curve25519Scalar>::BigInt(&[1, 0, 3, 4]),
+            ),
+            (
+                &"__a_presence__".parse().unwrap(),
+                &Column::<Curve25519Scalar>::Boolean(&[true, false, true, true]),
+            ),
+        ];
+        let expected_commitment =
+            TableCommitment::<RistrettoPoint>::try_from_columns_with_offset(columns, 0, &())
+                .unwrap();
+        assert_eq!(commitment, expected_commitment);
+
+        let mut commitment =
+            TableCommitment::<RistrettoPoint>::try_from_record_batch(&batch_without_nulls, &())
+                .unwrap();
+        assert!(matches!(
+            commitment.try_append_record_batch(&batch_with_nulls, &()),
+            Err(
+                AppendRecordBatchTableCommitmentError::ArrowBatchToColumnError {
+                    source: RecordBatchToColumnsError::ArrowArrayToColumnConversionError {
+                        source: ArrowArrayToColumnConversionError::ArrayContainsNulls
+                    }
+                }
+            )
+        ));
+    }
 }
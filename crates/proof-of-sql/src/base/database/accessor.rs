@@ -53,6 +53,31 @@ pub trait CommitmentAccessor<C: Commitment>: MetadataAccessor {
     fn get_commitment(&self, column: ColumnRef) -> C;
 }
 
+/// Access the blinding factors of hiding column commitments.
+///
+/// Prover uses this information to create a [`HidingQueryProof`](crate::sql::proof::HidingQueryProof),
+/// which only works if the commitments of the [`CommitmentAccessor`] are hiding commitments, i.e. if each
+/// commitment is the commitment to the column plus `blind * H` for a secret, uniformly random `blind`.
+///
+/// Note: we assume that the query has already been validated so that we
+/// will only be accessing information about columns that exist in the database.
+pub trait BlindingFactorAccessor<S: Scalar>: MetadataAccessor {
+    /// Return the blinding factor of the full table column commitment
+    fn get_blinding_factor(&self, column: ColumnRef) -> S;
+}
+
+/// Allows a [`CommitmentAccessor`] trait object to be used where a [`MetadataAccessor`] is
+/// expected, e.g. to get the length of the table of a subplan.
+impl<C: Commitment> MetadataAccessor for &dyn CommitmentAccessor<C> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        (**self).get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        (**self).get_offset(table_ref)
+    }
+}
+
 /// Access database columns of an in-memory table span.
 ///
 /// Prover uses this information to process a query.
@@ -87,6 +112,18 @@ pub trait DataAccessor<S: Scalar>: MetadataAccessor {
     fn get_column(&self, column: ColumnRef) -> Column<S>;
 }
 
+/// Allows a [`DataAccessor`] trait object to be used where a [`MetadataAccessor`] is expected,
+/// e.g. to get the length of the input of a subplan.
+impl<S: Scalar> MetadataAccessor for &dyn DataAccessor<S> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        (**self).get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        (**self).get_offset(table_ref)
+    }
+}
+
 /// Access tables and their schemas in a database.
 ///
 /// This accessor should be implemented by both the prover and verifier
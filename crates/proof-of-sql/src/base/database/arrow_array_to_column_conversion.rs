@@ -1,12 +1,16 @@
 use super::scalar_and_i256_conversions::convert_i256_to_scalar;
-use crate::base::{database::Column, math::decimal::Precision, scalar::Scalar};
+use crate::base::{
+    database::{Column, NullableColumn},
+    math::decimal::Precision,
+    scalar::Scalar,
+};
 use arrow::{
     array::{
         Array, ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array,
-        Int64Array, Int8Array, StringArray, TimestampMicrosecondArray, TimestampMillisecondArray,
-        TimestampNanosecondArray, TimestampSecondArray,
+        Int64Array, Int8Array, PrimitiveArray, StringArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
     },
-    datatypes::{i256, DataType, TimeUnit as ArrowTimeUnit},
+    datatypes::{i256, ArrowPrimitiveType, DataType, TimeUnit as ArrowTimeUnit},
 };
 use bumpalo::Bump;
 use core::ops::Range;
@@ -80,12 +84,235 @@ pub trait ArrayRefExt {
     ///    `scals` must be provided and have a length equal to `range.len()`.
     ///
     /// Note: this function must not be called from unsupported or nullable arrays as it will panic.
+    /// Use [`ArrayRefExt::to_nullable_column`] for arrays with nulls.
     fn to_column<'a, S: Scalar>(
         &'a self,
         alloc: &'a Bump,
         range: &Range<usize>,
         scals: Option<&'a [S]>,
     ) -> Result<Column<'a, S>, ArrowArrayToColumnConversionError>;
+
+    /// Convert an [`ArrayRef`], which may contain nulls, into a Proof of SQL Column type together with its presence.
+    ///
+    /// The values of null elements are replaced by the default value of their type, and the presence,
+    /// which is `true` exactly for the elements that are not null, is always returned, even if there are no nulls.
+    /// The parameters are the same as those of [`ArrayRefExt::to_column`].
+    fn to_nullable_column<'a, S: Scalar>(
+        &'a self,
+        alloc: &'a Bump,
+        range: &Range<usize>,
+        scals: Option<&'a [S]>,
+    ) -> Result<NullableColumn<'a, S>, ArrowArrayToColumnConversionError>;
+
+    /// Returns the presence of the elements of an [`ArrayRef`] in `range`,
+    /// i.e. `true` exactly for the elements that are not null.
+    fn to_presence<'a>(
+        &'a self,
+        alloc: &'a Bump,
+        range: &Range<usize>,
+    ) -> Result<&'a [bool], ArrowArrayToColumnConversionError>;
+}
+
+/// Returns the values of a [`PrimitiveArray`] in `range`, with the values of null elements replaced by the default value.
+///
+/// The values are only copied if the array contains nulls.
+fn values_with_defaults<'a, T: ArrowPrimitiveType>(
+    array: &'a PrimitiveArray<T>,
+    alloc: &'a Bump,
+    range: &Range<usize>,
+) -> &'a [T::Native] {
+    let values = &array.values()[range.start..range.end];
+    if array.null_count() == 0 {
+        values
+    } else {
+        alloc.alloc_slice_fill_with(range.len(), |i| {
+            if array.is_null(range.start + i) {
+                T::Native::default()
+            } else {
+                values[i]
+            }
+        })
+    }
+}
+
+/// Converts the elements of `array` in `range` into a [`Column`], with the values of null elements replaced by
+/// the default value of their type. See [`ArrayRefExt::to_column`].
+fn column_with_defaults<'a, S: Scalar>(
+    array: &'a ArrayRef,
+    alloc: &'a Bump,
+    range: &Range<usize>,
+    precomputed_scals: Option<&'a [S]>,
+) -> Result<Column<'a, S>, ArrowArrayToColumnConversionError> {
+    // Before performing any operations, check if the range is out of bounds
+    if range.end > array.len() {
+        return Err(ArrowArrayToColumnConversionError::IndexOutOfBounds {
+            len: array.len(),
+            index: range.end,
+        });
+    }
+    // Match supported types and attempt conversion
+    match array.data_type() {
+        DataType::Boolean => {
+            if let Some(array) = array.as_any().downcast_ref::<BooleanArray>() {
+                let values = alloc.alloc_slice_fill_with(range.len(), |i| {
+                    array.is_valid(range.start + i) && array.value(range.start + i)
+                });
+                Ok(Column::Boolean(values))
+            } else {
+                Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                    datatype: array.data_type().clone(),
+                })
+            }
+        }
+        DataType::Int8 => {
+            if let Some(array) = array.as_any().downcast_ref::<Int8Array>() {
+                Ok(Column::TinyInt(values_with_defaults(array, alloc, range)))
+            } else {
+                Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                    datatype: array.data_type().clone(),
+                })
+            }
+        }
+        DataType::Int16 => {
+            if let Some(array) = array.as_any().downcast_ref::<Int16Array>() {
+                Ok(Column::SmallInt(values_with_defaults(array, alloc, range)))
+            } else {
+                Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                    datatype: array.data_type().clone(),
+                })
+            }
+        }
+        DataType::Int32 => {
+            if let Some(array) = array.as_any().downcast_ref::<Int32Array>() {
+                Ok(Column::Int(values_with_defaults(array, alloc, range)))
+            } else {
+                Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                    datatype: array.data_type().clone(),
+                })
+            }
+        }
+        DataType::Int64 => {
+            if let Some(array) = array.as_any().downcast_ref::<Int64Array>() {
+                Ok(Column::BigInt(values_with_defaults(array, alloc, range)))
+            } else {
+                Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                    datatype: array.data_type().clone(),
+                })
+            }
+        }
+        DataType::Decimal128(38, 0) => {
+            if let Some(array) = array.as_any().downcast_ref::<Decimal128Array>() {
+                Ok(Column::Int128(values_with_defaults(array, alloc, range)))
+            } else {
+                Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                    datatype: array.data_type().clone(),
+                })
+            }
+        }
+        DataType::Decimal256(precision, scale) if *precision <= 75 => {
+            if let Some(array) = array.as_any().downcast_ref::<Decimal256Array>() {
+                let i256_slice = values_with_defaults(array, alloc, range);
+                let scalars = alloc.alloc_slice_fill_default(i256_slice.len());
+                for (scalar, value) in scalars.iter_mut().zip(i256_slice) {
+                    *scalar = convert_i256_to_scalar(value).ok_or(
+                        ArrowArrayToColumnConversionError::DecimalConversionFailed {
+                            number: *value,
+                        },
+                    )?;
+                }
+                Ok(Column::Decimal75(
+                    Precision::new(*precision)?,
+                    *scale,
+                    scalars,
+                ))
+            } else {
+                Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                    datatype: array.data_type().clone(),
+                })
+            }
+        }
+        // Handle all possible TimeStamp TimeUnit instances
+        DataType::Timestamp(time_unit, tz) => match time_unit {
+            ArrowTimeUnit::Second => {
+                if let Some(array) = array.as_any().downcast_ref::<TimestampSecondArray>() {
+                    Ok(Column::TimestampTZ(
+                        PoSQLTimeUnit::Second,
+                        PoSQLTimeZone::try_from(tz)?,
+                        values_with_defaults(array, alloc, range),
+                    ))
+                } else {
+                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                        datatype: array.data_type().clone(),
+                    })
+                }
+            }
+            ArrowTimeUnit::Millisecond => {
+                if let Some(array) = array.as_any().downcast_ref::<TimestampMillisecondArray>() {
+                    Ok(Column::TimestampTZ(
+                        PoSQLTimeUnit::Millisecond,
+                        PoSQLTimeZone::try_from(tz)?,
+                        values_with_defaults(array, alloc, range),
+                    ))
+                } else {
+                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                        datatype: array.data_type().clone(),
+                    })
+                }
+            }
+            ArrowTimeUnit::Microsecond => {
+                if let Some(array) = array.as_any().downcast_ref::<TimestampMicrosecondArray>() {
+                    Ok(Column::TimestampTZ(
+                        PoSQLTimeUnit::Microsecond,
+                        PoSQLTimeZone::try_from(tz)?,
+                        values_with_defaults(array, alloc, range),
+                    ))
+                } else {
+                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                        datatype: array.data_type().clone(),
+                    })
+                }
+            }
+            ArrowTimeUnit::Nanosecond => {
+                if let Some(array) = array.as_any().downcast_ref::<TimestampNanosecondArray>() {
+                    Ok(Column::TimestampTZ(
+                        PoSQLTimeUnit::Nanosecond,
+                        PoSQLTimeZone::try_from(tz)?,
+                        values_with_defaults(array, alloc, range),
+                    ))
+                } else {
+                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                        datatype: array.data_type().clone(),
+                    })
+                }
+            }
+        },
+        DataType::Utf8 => {
+            if let Some(array) = array.as_any().downcast_ref::<StringArray>() {
+                let vals = alloc.alloc_slice_fill_with(range.end - range.start, |i| -> &'a str {
+                    if array.is_null(range.start + i) {
+                        ""
+                    } else {
+                        array.value(range.start + i)
+                    }
+                });
+
+                let scals = if let Some(scals) = precomputed_scals {
+                    &scals[range.start..range.end]
+                } else {
+                    alloc.alloc_slice_fill_with(vals.len(), |i| -> S { vals[i].into() })
+                };
+
+                Ok(Column::VarChar((vals, scals)))
+            } else {
+                Err(ArrowArrayToColumnConversionError::UnsupportedType {
+                    datatype: array.data_type().clone(),
+                })
+            }
+        }
+        data_type => Err(ArrowArrayToColumnConversionError::UnsupportedType {
+            datatype: data_type.clone(),
+        }),
+    }
 }
 
 impl ArrayRefExt for ArrayRef {
@@ -206,178 +433,32 @@ impl ArrayRefExt for ArrayRef {
         if self.null_count() != 0 {
             return Err(ArrowArrayToColumnConversionError::ArrayContainsNulls);
         }
+        column_with_defaults(self, alloc, range, precomputed_scals)
+    }
+
+    fn to_nullable_column<'a, S: Scalar>(
+        &'a self,
+        alloc: &'a Bump,
+        range: &Range<usize>,
+        scals: Option<&'a [S]>,
+    ) -> Result<NullableColumn<'a, S>, ArrowArrayToColumnConversionError> {
+        let values = column_with_defaults(self, alloc, range, scals)?;
+        let presence = self.to_presence(alloc, range)?;
+        Ok(NullableColumn::new(values, Some(presence)))
+    }
 
-        // Before performing any operations, check if the range is out of bounds
+    fn to_presence<'a>(
+        &'a self,
+        alloc: &'a Bump,
+        range: &Range<usize>,
+    ) -> Result<&'a [bool], ArrowArrayToColumnConversionError> {
         if range.end > self.len() {
             return Err(ArrowArrayToColumnConversionError::IndexOutOfBounds {
                 len: self.len(),
                 index: range.end,
             });
         }
-        // Match supported types and attempt conversion
-        match self.data_type() {
-            DataType::Boolean => {
-                if let Some(array) = self.as_any().downcast_ref::<BooleanArray>() {
-                    let boolean_slice = array
-                        .iter()
-                        .skip(range.start)
-                        .take(range.len())
-                        .collect::<Option<Vec<bool>>>()
-                        .ok_or(ArrowArrayToColumnConversionError::ArrayContainsNulls)?;
-                    let values = alloc.alloc_slice_fill_with(range.len(), |i| boolean_slice[i]);
-                    Ok(Column::Boolean(values))
-                } else {
-                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                        datatype: self.data_type().clone(),
-                    })
-                }
-            }
-            DataType::Int8 => {
-                if let Some(array) = self.as_any().downcast_ref::<Int8Array>() {
-                    Ok(Column::TinyInt(&array.values()[range.start..range.end]))
-                } else {
-                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                        datatype: self.data_type().clone(),
-                    })
-                }
-            }
-            DataType::Int16 => {
-                if let Some(array) = self.as_any().downcast_ref::<Int16Array>() {
-                    Ok(Column::SmallInt(&array.values()[range.start..range.end]))
-                } else {
-                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                        datatype: self.data_type().clone(),
-                    })
-                }
-            }
-            DataType::Int32 => {
-                if let Some(array) = self.as_any().downcast_ref::<Int32Array>() {
-                    Ok(Column::Int(&array.values()[range.start..range.end]))
-                } else {
-                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                        datatype: self.data_type().clone(),
-                    })
-                }
-            }
-            DataType::Int64 => {
-                if let Some(array) = self.as_any().downcast_ref::<Int64Array>() {
-                    Ok(Column::BigInt(&array.values()[range.start..range.end]))
-                } else {
-                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                        datatype: self.data_type().clone(),
-                    })
-                }
-            }
-            DataType::Decimal128(38, 0) => {
-                if let Some(array) = self.as_any().downcast_ref::<Decimal128Array>() {
-                    Ok(Column::Int128(&array.values()[range.start..range.end]))
-                } else {
-                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                        datatype: self.data_type().clone(),
-                    })
-                }
-            }
-            DataType::Decimal256(precision, scale) if *precision <= 75 => {
-                if let Some(array) = self.as_any().downcast_ref::<Decimal256Array>() {
-                    let i256_slice = &array.values()[range.start..range.end];
-                    let scalars = alloc.alloc_slice_fill_default(i256_slice.len());
-                    for (scalar, value) in scalars.iter_mut().zip(i256_slice) {
-                        *scalar = convert_i256_to_scalar(value).ok_or(
-                            ArrowArrayToColumnConversionError::DecimalConversionFailed {
-                                number: *value,
-                            },
-                        )?;
-                    }
-                    Ok(Column::Decimal75(
-                        Precision::new(*precision)?,
-                        *scale,
-                        scalars,
-                    ))
-                } else {
-                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                        datatype: self.data_type().clone(),
-                    })
-                }
-            }
-            // Handle all possible TimeStamp TimeUnit instances
-            DataType::Timestamp(time_unit, tz) => match time_unit {
-                ArrowTimeUnit::Second => {
-                    if let Some(array) = self.as_any().downcast_ref::<TimestampSecondArray>() {
-                        Ok(Column::TimestampTZ(
-                            PoSQLTimeUnit::Second,
-                            PoSQLTimeZone::try_from(tz)?,
-                            &array.values()[range.start..range.end],
-                        ))
-                    } else {
-                        Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                            datatype: self.data_type().clone(),
-                        })
-                    }
-                }
-                ArrowTimeUnit::Millisecond => {
-                    if let Some(array) = self.as_any().downcast_ref::<TimestampMillisecondArray>() {
-                        Ok(Column::TimestampTZ(
-                            PoSQLTimeUnit::Millisecond,
-                            PoSQLTimeZone::try_from(tz)?,
-                            &array.values()[range.start..range.end],
-                        ))
-                    } else {
-                        Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                            datatype: self.data_type().clone(),
-                        })
-                    }
-                }
-                ArrowTimeUnit::Microsecond => {
-                    if let Some(array) = self.as_any().downcast_ref::<TimestampMicrosecondArray>() {
-                        Ok(Column::TimestampTZ(
-                            PoSQLTimeUnit::Microsecond,
-                            PoSQLTimeZone::try_from(tz)?,
-                            &array.values()[range.start..range.end],
-                        ))
-                    } else {
-                        Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                            datatype: self.data_type().clone(),
-                        })
-                    }
-                }
-                ArrowTimeUnit::Nanosecond => {
-                    if let Some(array) = self.as_any().downcast_ref::<TimestampNanosecondArray>() {
-                        Ok(Column::TimestampTZ(
-                            PoSQLTimeUnit::Nanosecond,
-                            PoSQLTimeZone::try_from(tz)?,
-                            &array.values()[range.start..range.end],
-                        ))
-                    } else {
-                        Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                            datatype: self.data_type().clone(),
-                        })
-                    }
-                }
-            },
-            DataType::Utf8 => {
-                if let Some(array) = self.as_any().downcast_ref::<StringArray>() {
-                    let vals = alloc
-                        .alloc_slice_fill_with(range.end - range.start, |i| -> &'a str {
-                            array.value(range.start + i)
-                        });
-
-                    let scals = if let Some(scals) = precomputed_scals {
-                        &scals[range.start..range.end]
-                    } else {
-                        alloc.alloc_slice_fill_with(vals.len(), |i| -> S { vals[i].into() })
-                    };
-
-                    Ok(Column::VarChar((vals, scals)))
-                } else {
-                    Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                        datatype: self.data_type().clone(),
-                    })
-                }
-            }
-            data_type => Err(ArrowArrayToColumnConversionError::UnsupportedType {
-                datatype: data_type.clone(),
-            }),
-        }
+        Ok(alloc.alloc_slice_fill_with(range.len(), |i| self.is_valid(range.start + i)))
     }
 }
 
@@ -1313,4 +1394,95 @@ mod tests {
                 .collect::<Vec<Curve25519Scalar>>())
         );
     }
+
+    #[test]
+    fn we_can_convert_arrays_with_nulls_into_nullable_columns() {
+        let alloc = Bump::new();
+        let array: ArrayRef = Arc::new(Int8Array::from(vec![Some(1), None, Some(42)]));
+        assert_eq!(
+            array
+                .to_nullable_column::<Curve25519Scalar>(&alloc, &(0..3), None)
+                .unwrap(),
+            NullableColumn::new(Column::TinyInt(&[1, 0, 42]), Some(&[true, false, true]))
+        );
+
+        let array: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None, Some(true)]));
+        assert_eq!(
+            array
+                .to_nullable_column::<Curve25519Scalar>(&alloc, &(1..3), None)
+                .unwrap(),
+            NullableColumn::new(Column::Boolean(&[false, true]), Some(&[false, true]))
+        );
+
+        let array: ArrayRef = Arc::new(StringArray::from(vec![Some("hello"), None, Some("test")]));
+        assert_eq!(
+            array
+                .to_nullable_column::<Curve25519Scalar>(&alloc, &(0..3), None)
+                .unwrap(),
+            NullableColumn::new(
+                Column::VarChar((
+                    &["hello", "", "test"],
+                    &["hello".into(), "".into(), "test".into()]
+                )),
+                Some(&[true, false, true])
+            )
+        );
+
+        let array: ArrayRef = Arc::new(TimestampSecondArray::with_timezone_opt(
+            vec![Some(1_625_072_400), None, Some(1_625_083_200)].into(),
+            Some("Z"),
+        ));
+        assert_eq!(
+            array
+                .to_nullable_column::<DoryScalar>(&alloc, &(0..3), None)
+                .unwrap(),
+            NullableColumn::new(
+                Column::TimestampTZ(
+                    PoSQLTimeUnit::Second,
+                    PoSQLTimeZone::Utc,
+                    &[1_625_072_400, 0, 1_625_083_200]
+                ),
+                Some(&[true, false, true])
+            )
+        );
+
+        let mut builder = Decimal256Builder::with_capacity(3);
+        builder.append_value(i256::from(100));
+        builder.append_null();
+        builder.append_value(i256::from(-42));
+        let array: ArrayRef = Arc::new(builder.finish().with_precision_and_scale(75, 0).unwrap());
+        assert_eq!(
+            array
+                .to_nullable_column::<Curve25519Scalar>(&alloc, &(0..3), None)
+                .unwrap(),
+            NullableColumn::new(
+                Column::Decimal75(
+                    Precision::new(75).unwrap(),
+                    0,
+                    &[
+                        Curve25519Scalar::from(100),
+                        Curve25519Scalar::ZERO,
+                        Curve25519Scalar::from(-42)
+                    ]
+                ),
+                Some(&[true, false, true])
+            )
+        );
+    }
+
+    #[test]
+    fn we_always_get_the_presence_of_nullable_columns() {
+        let alloc = Bump::new();
+        let array: ArrayRef = Arc::new(Int64Array::from(vec![1, -2, 3]));
+        assert_eq!(
+            array
+                .to_nullable_column::<Curve25519Scalar>(&alloc, &(0..3), None)
+                .unwrap(),
+            NullableColumn::new(Column::BigInt(&[1, -2, 3]), Some(&[true, true, true]))
+        );
+        assert_eq!(
+            array.to_nullable_column::<Curve25519Scalar>(&alloc, &(2..4), None),
+            Err(ArrowArrayToColumnConversionError::IndexOutOfBounds { len: 3, index: 4 })
+        );
+    }
 }
@@ -9,11 +9,14 @@ use alloc::{sync::Arc, vec::Vec};
 use arrow::datatypes::{DataType, Field, TimeUnit as ArrowTimeUnit};
 use bumpalo::Bump;
 use core::{
+    cmp::Ordering,
     fmt,
     fmt::{Display, Formatter},
     mem::size_of,
+    ops::Range,
 };
 use proof_of_sql_parser::{
+    intermediate_ast::OrderByDirection,
     posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
     Identifier,
 };
@@ -100,6 +103,28 @@ impl<'a, S: Scalar> Column<'a, S> {
         self.len() == 0
     }
 
+    /// Returns the rows of the column in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds.
+    pub(crate) fn slice(&self, range: Range<usize>) -> Self {
+        match *self {
+            Self::Boolean(col) => Self::Boolean(&col[range]),
+            Self::TinyInt(col) => Self::TinyInt(&col[range]),
+            Self::SmallInt(col) => Self::SmallInt(&col[range]),
+            Self::Int(col) => Self::Int(&col[range]),
+            Self::BigInt(col) => Self::BigInt(&col[range]),
+            Self::Int128(col) => Self::Int128(&col[range]),
+            Self::Decimal75(precision, scale, col) => {
+                Self::Decimal75(precision, scale, &col[range])
+            }
+            Self::Scalar(col) => Self::Scalar(&col[range]),
+            Self::VarChar((col, scals)) => Self::VarChar((&col[range.clone()], &scals[range])),
+            Self::TimestampTZ(tu, tz, col) => Self::TimestampTZ(tu, tz, &col[range]),
+        }
+    }
+
     /// Generate a constant column from a literal value with a given length
     pub fn from_literal_with_length(
         literal: &LiteralValue<S>,
@@ -581,6 +606,37 @@ impl From<&ColumnField> for Field {
     }
 }
 
+/// Compares the tuples `(order_by_pairs[0][i], order_by_pairs[1][i], ...)` and
+/// `(order_by_pairs[0][j], order_by_pairs[1][j], ...)` in lexicographic order.
+/// Note that direction flips the ordering.
+pub(crate) fn compare_indexes_by_columns_with_direction<S: Scalar>(
+    order_by_pairs: &[(Column<S>, OrderByDirection)],
+    i: usize,
+    j: usize,
+) -> Ordering {
+    order_by_pairs
+        .iter()
+        .map(|(col, direction)| {
+            let ordering = match col {
+                Column::Boolean(col) => col[i].cmp(&col[j]),
+                Column::TinyInt(col) => col[i].cmp(&col[j]),
+                Column::SmallInt(col) => col[i].cmp(&col[j]),
+                Column::Int(col) => col[i].cmp(&col[j]),
+                Column::BigInt(col) | Column::TimestampTZ(_, _, col) => col[i].cmp(&col[j]),
+                Column::Int128(col) => col[i].cmp(&col[j]),
+                Column::Decimal75(_, _, col) => col[i].signed_cmp(&col[j]),
+                Column::Scalar(col) => col[i].cmp(&col[j]),
+                Column::VarChar((col, _)) => col[i].cmp(col[j]),
+            };
+            match direction {
+                OrderByDirection::Asc => ordering,
+                OrderByDirection::Desc => ordering.reverse(),
+            }
+        })
+        .find(|&ord| ord != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
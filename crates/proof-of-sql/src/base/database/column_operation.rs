@@ -5,14 +5,23 @@ use crate::base::{
     math::decimal::{scale_scalar, DecimalError, Precision},
     scalar::Scalar,
 };
-use alloc::{format, string::ToString, vec::Vec};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use chrono::{DateTime, FixedOffset, SecondsFormat};
 use core::{cmp::Ordering, fmt::Debug};
 use num_bigint::BigInt;
 use num_traits::{
-    ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub},
-    Zero,
+    ops::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub},
+    Signed, Zero,
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{BinaryOperator, DataType},
+    intermediate_decimal::IntermediateDecimal,
+    posql_time::{PoSQLTimeZone, PoSQLTimestamp},
 };
-use proof_of_sql_parser::intermediate_ast::BinaryOperator;
 
 // For decimal type manipulation please refer to
 // https://learn.microsoft.com/en-us/sql/t-sql/data-types/precision-scale-and-length-transact-sql?view=sql-server-ver16
@@ -178,6 +187,127 @@ pub fn try_divide_column_types(
     Ok(ColumnType::Decimal75(precision, scale))
 }
 
+/// Determine the output type of a modulo operation if it is possible
+/// to take the remainder of the two input types. If the types are not compatible, return
+/// an error.
+///
+/// Only integers are supported and the result has the larger of the two integer types.
+///
+/// # Panics
+///
+/// - Panics if `lhs.max_integer_type(&rhs)` returns `None` for two integer types.
+pub fn try_modulo_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    if !lhs.is_integer() || !rhs.is_integer() {
+        return Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+            operator: BinaryOperator::Modulo,
+            left_type: lhs,
+            right_type: rhs,
+        });
+    }
+    // We can unwrap here because we know that both types are integers
+    Ok(lhs.max_integer_type(&rhs).unwrap())
+}
+
+/// Determine the output type of a `CASE` expression whose branches have the two input types if
+/// it is possible to choose between them. If the types are not compatible, return an error.
+///
+/// Equal types are compatible. Different types are compatible if they are numeric and neither
+/// of them is [`ColumnType::Scalar`]. Integers then result in the larger integer type and other
+/// numeric types in a decimal that can hold the values of both types.
+///
+/// # Panics
+///
+/// - Panics if `lhs` or `rhs` does not have a precision or scale when they are expected to be numeric types.
+/// - Panics if `lhs` or `rhs` is an integer, and `lhs.max_integer_type(&rhs)` returns `None`.
+pub fn try_case_column_types(
+    lhs: ColumnType,
+    rhs: ColumnType,
+) -> ColumnOperationResult<ColumnType> {
+    if lhs == rhs {
+        return Ok(lhs);
+    }
+    if !lhs.is_numeric()
+        || !rhs.is_numeric()
+        || lhs == ColumnType::Scalar
+        || rhs == ColumnType::Scalar
+    {
+        return Err(ColumnOperationError::CaseInvalidColumnTypes {
+            then_type: lhs,
+            else_type: rhs,
+        });
+    }
+    if lhs.is_integer() && rhs.is_integer() {
+        // We can unwrap here because we know that both types are integers
+        return Ok(lhs.max_integer_type(&rhs).unwrap());
+    }
+    let left_precision_value =
+        i16::from(lhs.precision_value().expect("Numeric types have precision"));
+    let right_precision_value =
+        i16::from(rhs.precision_value().expect("Numeric types have precision"));
+    let left_scale = lhs.scale().expect("Numeric types have scale");
+    let right_scale = rhs.scale().expect("Numeric types have scale");
+    let scale = left_scale.max(right_scale);
+    let precision_value: i16 = i16::from(scale)
+        + (left_precision_value - i16::from(left_scale))
+            .max(right_precision_value - i16::from(right_scale));
+    let precision = u8::try_from(precision_value)
+        .map_err(|_| ColumnOperationError::DecimalConversionError {
+            source: DecimalError::InvalidPrecision {
+                error: precision_value.to_string(),
+            },
+        })
+        .and_then(|p| {
+            Precision::new(p).map_err(|_| ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision {
+                    error: p.to_string(),
+                },
+            })
+        })?;
+    Ok(ColumnType::Decimal75(precision, scale))
+}
+
+/// Determine the output type of `CAST(expr AS to_type)` for an expression of type `from_type`
+/// if the cast is supported. If it is not, return an error.
+///
+/// Numeric types can be cast to each other and timestamps to timestamps, which keep their time
+/// zone. Every type other than [`ColumnType::Scalar`] can be cast to and from `VARCHAR`.
+pub fn try_cast_column_type(
+    from_type: ColumnType,
+    to_type: DataType,
+) -> ColumnOperationResult<ColumnType> {
+    let result_type = match to_type {
+        DataType::Boolean => ColumnType::Boolean,
+        DataType::TinyInt => ColumnType::TinyInt,
+        DataType::SmallInt => ColumnType::SmallInt,
+        DataType::Int => ColumnType::Int,
+        DataType::BigInt => ColumnType::BigInt,
+        DataType::Decimal { precision, scale } => {
+            ColumnType::Decimal75(Precision::new(precision)?, scale)
+        }
+        DataType::VarChar => ColumnType::VarChar,
+        DataType::Timestamp(time_unit) => match from_type {
+            ColumnType::TimestampTZ(_, time_zone) => ColumnType::TimestampTZ(time_unit, time_zone),
+            _ => ColumnType::TimestampTZ(time_unit, PoSQLTimeZone::Utc),
+        },
+    };
+    let is_supported = match (from_type, result_type) {
+        (ColumnType::Scalar, _) => false,
+        (ColumnType::VarChar, _)
+        | (_, ColumnType::VarChar)
+        | (ColumnType::Boolean, ColumnType::Boolean)
+        | (ColumnType::TimestampTZ(_, _), ColumnType::TimestampTZ(_, _)) => true,
+        (from_type, result_type) => from_type.is_numeric() && result_type.is_numeric(),
+    };
+    if is_supported {
+        Ok(result_type)
+    } else {
+        Err(ColumnOperationError::CastInvalidColumnType { from_type, to_type })
+    }
+}
+
 // Unary operations
 
 /// Negate a slice of boolean values.
@@ -246,6 +376,153 @@ where
         .collect::<Vec<_>>()
 }
 
+/// Choose element-wise from two slices, taking `lhs` where `selection` is true and `rhs` otherwise.
+///
+/// We do not check for length equality here.
+pub(super) fn slice_select<'a, T>(
+    selection: &'a [bool],
+    lhs: &'a [T],
+    rhs: &'a [T],
+) -> impl Iterator<Item = &'a T> {
+    selection
+        .iter()
+        .zip(lhs.iter().zip(rhs))
+        .map(|(&selected, (l, r))| if selected { l } else { r })
+}
+
+/// Multiply an integer by `10^scale`, rounding half away from zero if `scale` is negative.
+pub(super) fn rescale_bigint(value: &BigInt, scale: i8) -> BigInt {
+    let scale_factor = BigInt::from(10).pow(u32::from(scale.unsigned_abs()));
+    if scale >= 0 {
+        return value * scale_factor;
+    }
+    let quotient = value / &scale_factor;
+    let remainder = value % &scale_factor;
+    if remainder.abs() * 2 >= scale_factor {
+        quotient + value.signum()
+    } else {
+        quotient
+    }
+}
+
+/// Convert an integer to a scalar if it fits into `column_type`, which is numeric or a timestamp.
+///
+/// Decimals must not have more digits than their precision, other types must fit into their bits.
+///
+/// # Panics
+/// Panics if `column_type` is not numeric or a timestamp.
+pub(super) fn try_bigint_to_scalar<S: Scalar>(value: BigInt, column_type: ColumnType) -> Option<S> {
+    let fits = match column_type {
+        ColumnType::Decimal75(precision, _) => {
+            value.abs() < BigInt::from(10).pow(u32::from(precision.value()))
+        }
+        _ => {
+            let bound = BigInt::from(1) << (column_type.bit_size() - 1);
+            value >= -&bound && value < bound
+        }
+    };
+    fits.then(|| S::try_from(value).expect("values of column types fit into scalars"))
+}
+
+/// Format an integer that is scaled by `10^scale` as a decimal number, e.g. `-0.05` for `-5` with
+/// a scale of 2.
+pub(super) fn decimal_to_string(value: &BigInt, scale: i8) -> String {
+    if scale <= 0 {
+        return rescale_bigint(value, scale.saturating_neg()).to_string();
+    }
+    let scale = usize::from(scale.unsigned_abs());
+    let mut digits = value.magnitude().to_string();
+    if digits.len() <= scale {
+        digits.insert_str(0, &"0".repeat(scale + 1 - digits.len()));
+    }
+    let (integer_part, fractional_part) = digits.split_at(digits.len() - scale);
+    let sign = if value.is_negative() { "-" } else { "" };
+    format!("{sign}{integer_part}.{fractional_part}")
+}
+
+/// Parse a decimal number as an integer that is scaled by `10^scale`, rounding half away from zero.
+pub(super) fn parse_decimal(value: &str, scale: i8) -> Option<BigInt> {
+    let (digits, exponent) = value
+        .trim()
+        .parse::<IntermediateDecimal>()
+        .ok()?
+        .value()
+        .into_bigint_and_exponent();
+    let scale = i8::try_from(i64::from(scale) - exponent).ok()?;
+    Some(rescale_bigint(&digits, scale))
+}
+
+/// Format a timestamp with `10^scale` units per second as an RFC 3339 string in its time zone.
+pub(super) fn timestamp_to_string(
+    value: i64,
+    scale: i8,
+    time_zone: PoSQLTimeZone,
+) -> Option<String> {
+    let units_per_second = 10_i64.pow(u32::from(scale.unsigned_abs()));
+    let seconds = value.div_euclid(units_per_second);
+    let nanoseconds =
+        u32::try_from(value.rem_euclid(units_per_second) * (1_000_000_000 / units_per_second))
+            .ok()?;
+    let offset = match time_zone {
+        PoSQLTimeZone::Utc => 0,
+        PoSQLTimeZone::FixedOffset(offset) => offset,
+    };
+    let timestamp = DateTime::from_timestamp(seconds, nanoseconds)?
+        .with_timezone(&FixedOffset::east_opt(offset)?);
+    Some(timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+/// Parse an RFC 3339 string as a timestamp with `10^scale` units per second, rounding half away
+/// from zero.
+pub(super) fn parse_timestamp(value: &str, scale: i8) -> Option<BigInt> {
+    let timestamp = PoSQLTimestamp::try_from(value.trim()).ok()?.timestamp();
+    let nanoseconds =
+        BigInt::from(timestamp.timestamp()) * 1_000_000_000 + timestamp.timestamp_subsec_nanos();
+    Some(rescale_bigint(&nanoseconds, scale - 9))
+}
+
+/// Check whether each string of a slice matches the `LIKE` pattern at the same position of another.
+///
+/// We do not check for length equality here.
+pub(super) fn slice_like(lhs: &[String], rhs: &[String]) -> Vec<bool> {
+    lhs.iter()
+        .zip(rhs.iter())
+        .map(|(l, r)| -> bool { matches_like_pattern(l, r) })
+        .collect::<Vec<_>>()
+}
+
+/// Check whether `value` matches the `LIKE` pattern `pattern`, where `%` matches any sequence of
+/// characters, including the empty one, and `_` matches exactly one character.
+pub(crate) fn matches_like_pattern(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut v, mut p) = (0, 0);
+    // The position after the last `%` seen in the pattern and the position in the value it
+    // currently stretches to, so that the `%` can swallow one more character on a mismatch.
+    let mut backtrack = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                p += 1;
+                backtrack = Some((p, v));
+            }
+            Some(&c) if c == '_' || c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                Some((after_wildcard, matched_to)) => {
+                    p = after_wildcard;
+                    v = matched_to + 1;
+                    backtrack = Some((after_wildcard, v));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '%')
+}
+
 /// Try to add two slices of the same length.
 ///
 /// We do not check for length equality here. However, we do check for integer overflow.
@@ -300,21 +577,66 @@ where
         .collect::<ColumnOperationResult<Vec<T>>>()
 }
 
+/// Divide two integers, rounding toward zero.
+///
+/// Dividing by zero results in zero, like in the provable `DivideAndModuloExpr`,
+/// so that a query has the same result whether or not the division is proven.
+/// The only overflow is the smallest value of a type divided by -1.
+fn try_divide_integers<T>(l: T, r: T) -> ColumnOperationResult<T>
+where
+    T: CheckedDiv<Output = T> + Zero + Copy + Debug,
+{
+    if r.is_zero() {
+        return Ok(T::zero());
+    }
+    l.checked_div(&r)
+        .ok_or(ColumnOperationError::IntegerOverflow {
+            error: format!("Overflow in integer division {l:?} / {r:?}"),
+        })
+}
+
+/// Take the remainder of the division of two integers, which has the sign of `l`.
+///
+/// The remainder of a division by zero is `l`, like in the provable `DivideAndModuloExpr`.
+/// The remainder of the smallest value of a type divided by -1 is zero.
+fn modulo_integers<T>(l: T, r: T) -> T
+where
+    T: CheckedRem<Output = T> + Zero + Copy,
+{
+    if r.is_zero() {
+        l
+    } else {
+        l.checked_rem(&r).unwrap_or_else(T::zero)
+    }
+}
+
 /// Divide one slice by another of the same length.
 ///
-/// We do not check for length equality here. However, we do check for division by 0.
+/// We do not check for length equality here. Dividing by 0 results in 0, see [`try_divide_integers`].
 pub(super) fn try_divide_slices<T>(lhs: &[T], rhs: &[T]) -> ColumnOperationResult<Vec<T>>
 where
-    T: CheckedDiv<Output = T> + Debug,
+    T: CheckedDiv<Output = T> + Zero + Copy + Debug,
 {
     lhs.iter()
         .zip(rhs.iter())
-        .map(|(l, r)| -> ColumnOperationResult<T> {
-            l.checked_div(r).ok_or(ColumnOperationError::DivisionByZero)
-        })
+        .map(|(l, r)| try_divide_integers(*l, *r))
         .collect::<ColumnOperationResult<Vec<T>>>()
 }
 
+/// Take the remainder of one slice divided by another of the same length.
+///
+/// The remainder has the sign of the dividend, i.e. the quotient is rounded toward zero.
+/// We do not check for length equality here. The remainder of a division by 0 is the dividend.
+pub(super) fn modulo_slices<T>(lhs: &[T], rhs: &[T]) -> Vec<T>
+where
+    T: CheckedRem<Output = T> + Zero + Copy + Debug,
+{
+    lhs.iter()
+        .zip(rhs.iter())
+        .map(|(l, r)| modulo_integers(*l, *r))
+        .collect()
+}
+
 // Casting required for binary operations on different types
 
 /// Check whether two slices of the same length are equal element-wise.
@@ -470,42 +792,69 @@ where
 
 /// Divide one slice by another of the same length, casting the left slice to the type of the right slice.
 ///
-/// We do not check for length equality here
+/// We do not check for length equality here. Dividing by 0 results in 0.
 pub(super) fn try_divide_slices_left_upcast<SmallerType, LargerType>(
     lhs: &[SmallerType],
     rhs: &[LargerType],
 ) -> ColumnOperationResult<Vec<LargerType>>
 where
     SmallerType: Copy + Debug + Into<LargerType>,
-    LargerType: CheckedDiv<Output = LargerType> + Copy + Debug,
+    LargerType: CheckedDiv<Output = LargerType> + Zero + Copy + Debug,
 {
     lhs.iter()
         .zip(rhs.iter())
-        .map(|(l, r)| -> ColumnOperationResult<LargerType> {
-            Into::<LargerType>::into(*l)
-                .checked_div(r)
-                .ok_or(ColumnOperationError::DivisionByZero)
-        })
+        .map(|(l, r)| try_divide_integers(Into::<LargerType>::into(*l), *r))
         .collect()
 }
 
 /// Divide one slice by another of the same length, casting the right slice to the type of the left slice.
 ///
-/// We do not check for length equality here
+/// We do not check for length equality here. Dividing by 0 results in 0.
 pub(super) fn try_divide_slices_right_upcast<SmallerType, LargerType>(
     lhs: &[LargerType],
     rhs: &[SmallerType],
 ) -> ColumnOperationResult<Vec<LargerType>>
 where
     SmallerType: Copy + Debug + Into<LargerType>,
-    LargerType: CheckedDiv<Output = LargerType> + Copy + Debug,
+    LargerType: CheckedDiv<Output = LargerType> + Zero + Copy + Debug,
 {
     lhs.iter()
         .zip(rhs.iter())
-        .map(|(l, r)| -> ColumnOperationResult<LargerType> {
-            l.checked_div(&Into::<LargerType>::into(*r))
-                .ok_or(ColumnOperationError::DivisionByZero)
-        })
+        .map(|(l, r)| try_divide_integers(*l, Into::<LargerType>::into(*r)))
+        .collect()
+}
+
+/// Take the remainder of one slice divided by another of the same length, casting the left slice to the type of the right slice.
+///
+/// We do not check for length equality here. The remainder of a division by 0 is the dividend.
+pub(super) fn modulo_slices_left_upcast<SmallerType, LargerType>(
+    lhs: &[SmallerType],
+    rhs: &[LargerType],
+) -> Vec<LargerType>
+where
+    SmallerType: Copy + Debug + Into<LargerType>,
+    LargerType: CheckedRem<Output = LargerType> + Zero + Copy + Debug,
+{
+    lhs.iter()
+        .zip(rhs.iter())
+        .map(|(l, r)| modulo_integers(Into::<LargerType>::into(*l), *r))
+        .collect()
+}
+
+/// Take the remainder of one slice divided by another of the same length, casting the right slice to the type of the left slice.
+///
+/// We do not check for length equality here. The remainder of a division by 0 is the dividend.
+pub(super) fn modulo_slices_right_upcast<SmallerType, LargerType>(
+    lhs: &[LargerType],
+    rhs: &[SmallerType],
+) -> Vec<LargerType>
+where
+    SmallerType: Copy + Debug + Into<LargerType>,
+    LargerType: CheckedRem<Output = LargerType> + Zero + Copy + Debug,
+{
+    lhs.iter()
+        .zip(rhs.iter())
+        .map(|(l, r)| modulo_integers(*l, Into::<LargerType>::into(*r)))
         .collect()
 }
 
@@ -966,6 +1315,7 @@ where
 mod test {
     use super::*;
     use crate::base::scalar::Curve25519Scalar;
+    use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
     #[test]
     fn we_can_add_numeric_types() {
@@ -1513,6 +1863,36 @@ mod test {
         ));
     }
 
+    #[test]
+    fn we_can_take_the_remainder_of_integer_types() {
+        let actual = try_modulo_column_types(ColumnType::TinyInt, ColumnType::Int).unwrap();
+        assert_eq!(ColumnType::Int, actual);
+
+        let actual = try_modulo_column_types(ColumnType::BigInt, ColumnType::SmallInt).unwrap();
+        assert_eq!(ColumnType::BigInt, actual);
+    }
+
+    #[test]
+    fn we_cannot_take_the_remainder_of_non_integer_types() {
+        for (lhs, rhs) in [
+            (ColumnType::Int, ColumnType::VarChar),
+            (ColumnType::Boolean, ColumnType::Int),
+            (ColumnType::Scalar, ColumnType::BigInt),
+            (
+                ColumnType::BigInt,
+                ColumnType::Decimal75(Precision::new(10).unwrap(), 2),
+            ),
+        ] {
+            assert!(matches!(
+                try_modulo_column_types(lhs, rhs),
+                Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+                    operator: BinaryOperator::Modulo,
+                    ..
+                })
+            ));
+        }
+    }
+
     #[test]
     fn we_cannot_divide_some_numeric_types_due_to_decimal_issues() {
         // Invalid precision
@@ -1545,6 +1925,71 @@ mod test {
         ));
     }
 
+    #[test]
+    fn we_can_choose_between_compatible_types() {
+        // Equal types
+        for column_type in [
+            ColumnType::Boolean,
+            ColumnType::VarChar,
+            ColumnType::Scalar,
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc),
+        ] {
+            assert_eq!(
+                try_case_column_types(column_type, column_type).unwrap(),
+                column_type
+            );
+        }
+
+        // Integers
+        let actual = try_case_column_types(ColumnType::Int, ColumnType::TinyInt).unwrap();
+        assert_eq!(ColumnType::Int, actual);
+
+        let actual = try_case_column_types(ColumnType::SmallInt, ColumnType::BigInt).unwrap();
+        assert_eq!(ColumnType::BigInt, actual);
+
+        // lhs is a decimal with nonnegative scale and rhs is an integer
+        let lhs = ColumnType::Decimal75(Precision::new(10).unwrap(), 2);
+        let rhs = ColumnType::BigInt;
+        let actual = try_case_column_types(lhs, rhs).unwrap();
+        let expected = ColumnType::Decimal75(Precision::new(21).unwrap(), 2);
+        assert_eq!(expected, actual);
+
+        // lhs and rhs are both decimals one of which has negative scale
+        let lhs = ColumnType::Decimal75(Precision::new(40).unwrap(), -13);
+        let rhs = ColumnType::Decimal75(Precision::new(15).unwrap(), 5);
+        let actual = try_case_column_types(lhs, rhs).unwrap();
+        let expected = ColumnType::Decimal75(Precision::new(58).unwrap(), 5);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn we_cannot_choose_between_incompatible_types() {
+        for (lhs, rhs) in [
+            (ColumnType::BigInt, ColumnType::VarChar),
+            (ColumnType::Boolean, ColumnType::TinyInt),
+            (ColumnType::Scalar, ColumnType::BigInt),
+            (
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc),
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, PoSQLTimeZone::Utc),
+            ),
+        ] {
+            assert!(matches!(
+                try_case_column_types(lhs, rhs),
+                Err(ColumnOperationError::CaseInvalidColumnTypes { .. })
+            ));
+        }
+
+        // Invalid precision
+        let lhs = ColumnType::Decimal75(Precision::new(75).unwrap(), -10);
+        let rhs = ColumnType::Decimal75(Precision::new(75).unwrap(), 10);
+        assert!(matches!(
+            try_case_column_types(lhs, rhs),
+            Err(ColumnOperationError::DecimalConversionError {
+                source: DecimalError::InvalidPrecision { .. }
+            })
+        ));
+    }
+
     // NOT
     #[test]
     fn we_can_negate_boolean_slices() {
@@ -1714,6 +2159,34 @@ mod test {
     }
 
     // <=
+    #[test]
+    fn we_can_match_like_patterns() {
+        assert!(matches_like_pattern("abc", "abc"));
+        assert!(!matches_like_pattern("abc", "ab"));
+        assert!(!matches_like_pattern("ab", "abc"));
+        assert!(matches_like_pattern("abcdef", "abc%"));
+        assert!(matches_like_pattern("abc", "abc%"));
+        assert!(!matches_like_pattern("xabc", "abc%"));
+        assert!(matches_like_pattern("xxabcxx", "%abc%"));
+        assert!(matches_like_pattern("aXbYYc", "a_b%c"));
+        assert!(!matches_like_pattern("abc", "a_b%c"));
+        assert!(matches_like_pattern("abab", "%ab"));
+        assert!(matches_like_pattern("aaab", "%a%ab"));
+        assert!(!matches_like_pattern("aaa", "%a%ab"));
+        assert!(matches_like_pattern("", "%"));
+        assert!(matches_like_pattern("", ""));
+        assert!(!matches_like_pattern("", "_"));
+        assert!(matches_like_pattern("日本語", "_本%"));
+        assert!(!matches_like_pattern("ABC", "abc"));
+    }
+
+    #[test]
+    fn we_can_like_slices() {
+        let lhs = ["abc", "xabc", "ab"].map(String::from);
+        let rhs = ["a%", "a%", "_b"].map(String::from);
+        assert_eq!(slice_like(&lhs, &rhs), vec![true, false, true]);
+    }
+
     #[test]
     fn we_can_le_slices() {
         let lhs = [1_i32, 2, 3];
@@ -2488,12 +2961,31 @@ mod test {
     }
 
     #[test]
-    fn we_cannot_try_divide_slices_if_divide_by_zero() {
-        let lhs = [1_i32, 2, 3];
+    fn we_can_try_divide_slices_by_zero() {
+        let lhs = [1_i32, 2, 13];
         let rhs = [0_i32, -5, 6];
+        let actual = try_divide_slices(&lhs, &rhs).unwrap();
+        let expected = vec![0_i32, 0, 2];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn we_cannot_try_divide_slices_if_the_quotient_overflows() {
+        assert!(matches!(
+            try_divide_slices(&[1_i64, i64::MIN], &[1_i64, -1]),
+            Err(ColumnOperationError::IntegerOverflow { .. })
+        ));
         assert!(matches!(
-            try_divide_slices(&lhs, &rhs),
-            Err(ColumnOperationError::DivisionByZero)
+            try_divide_slices(&[i8::MIN], &[-1_i8]),
+            Err(ColumnOperationError::IntegerOverflow { .. })
+        ));
+        assert!(matches!(
+            try_divide_slices_left_upcast(&[1_i32], &[-1_i64]),
+            Ok(quotient) if quotient == vec![-1_i64]
+        ));
+        assert!(matches!(
+            try_divide_slices_right_upcast(&[i128::MIN], &[-1_i32]),
+            Err(ColumnOperationError::IntegerOverflow { .. })
         ));
     }
 
@@ -2507,13 +2999,12 @@ mod test {
     }
 
     #[test]
-    fn we_cannot_try_divide_slices_left_upcast_if_divide_by_zero() {
+    fn we_can_try_divide_slices_left_upcast_by_zero() {
         let lhs = [1_i16, 2];
         let rhs = [0_i32, 2];
-        assert!(matches!(
-            try_divide_slices_left_upcast(&lhs, &rhs),
-            Err(ColumnOperationError::DivisionByZero)
-        ));
+        let actual = try_divide_slices_left_upcast(&lhs, &rhs).unwrap();
+        let expected = vec![0_i32, 1];
+        assert_eq!(expected, actual);
     }
 
     #[test]
@@ -2526,13 +3017,58 @@ mod test {
     }
 
     #[test]
-    fn we_cannot_try_divide_slices_right_upcast_if_divide_by_zero() {
+    fn we_can_try_divide_slices_right_upcast_by_zero() {
         let lhs = [1_i32, 2];
         let rhs = [0_i16, 2];
-        assert!(matches!(
-            try_divide_slices_right_upcast(&lhs, &rhs),
-            Err(ColumnOperationError::DivisionByZero)
-        ));
+        let actual = try_divide_slices_right_upcast(&lhs, &rhs).unwrap();
+        let expected = vec![0_i32, 1];
+        assert_eq!(expected, actual);
+    }
+
+    // %
+    #[test]
+    fn we_can_modulo_slices() {
+        let lhs = [5_i16, -5, -7, 9];
+        let rhs = [-3_i16, 3, -4, 5];
+        let actual = modulo_slices(&lhs, &rhs);
+        let expected = vec![2_i16, -2, -3, 4];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn we_can_modulo_slices_by_zero_or_with_an_overflowing_quotient() {
+        let lhs = [1_i32, 2, 13, i32::MIN];
+        let rhs = [0_i32, -5, 6, -1];
+        let actual = modulo_slices(&lhs, &rhs);
+        let expected = vec![1_i32, 2, 1, 0];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn we_can_modulo_slices_with_upcasting() {
+        let lhs = [5_i16, -4, -9, 9];
+        let rhs = [-3_i32, 3, -4, 5];
+        let actual = modulo_slices_left_upcast(&lhs, &rhs);
+        let expected = vec![2_i32, -1, -1, 4];
+        assert_eq!(expected, actual);
+
+        let lhs = [15_i128, -82, -7, 9];
+        let rhs = [-4_i32, 3, -4, 5];
+        let actual = modulo_slices_right_upcast(&lhs, &rhs);
+        let expected = vec![3_i128, -1, -3, 4];
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn we_can_modulo_slices_with_upcasting_by_zero() {
+        assert_eq!(
+            modulo_slices_left_upcast(&[1_i16, 2], &[0_i32, 2]),
+            vec![1_i32, 0]
+        );
+        assert_eq!(
+            modulo_slices_right_upcast(&[1_i32, 2], &[2_i16, 0]),
+            vec![1_i32, 2]
+        );
     }
 
     #[test]
@@ -2670,4 +3206,100 @@ mod test {
         let expected = (Precision::new(9).unwrap(), 6, expected_scalars);
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn we_can_choose_the_type_of_a_cast() {
+        let decimal =
+            |precision, scale| ColumnType::Decimal75(Precision::new(precision).unwrap(), scale);
+        assert_eq!(
+            try_cast_column_type(ColumnType::Int, DataType::BigInt).unwrap(),
+            ColumnType::BigInt
+        );
+        assert_eq!(
+            try_cast_column_type(
+                ColumnType::BigInt,
+                DataType::Decimal {
+                    precision: 20,
+                    scale: 2
+                }
+            )
+            .unwrap(),
+            decimal(20, 2)
+        );
+        assert_eq!(
+            try_cast_column_type(decimal(5, 2), DataType::TinyInt).unwrap(),
+            ColumnType::TinyInt
+        );
+        assert_eq!(
+            try_cast_column_type(ColumnType::Boolean, DataType::VarChar).unwrap(),
+            ColumnType::VarChar
+        );
+        assert_eq!(
+            try_cast_column_type(ColumnType::VarChar, DataType::Boolean).unwrap(),
+            ColumnType::Boolean
+        );
+
+        // Timestamps keep their time zone
+        let time_zone = PoSQLTimeZone::FixedOffset(3600);
+        assert_eq!(
+            try_cast_column_type(
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Second, time_zone),
+                DataType::Timestamp(PoSQLTimeUnit::Millisecond)
+            )
+            .unwrap(),
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Millisecond, time_zone)
+        );
+        assert_eq!(
+            try_cast_column_type(
+                ColumnType::VarChar,
+                DataType::Timestamp(PoSQLTimeUnit::Microsecond)
+            )
+            .unwrap(),
+            ColumnType::TimestampTZ(PoSQLTimeUnit::Microsecond, PoSQLTimeZone::Utc)
+        );
+    }
+
+    #[test]
+    fn we_cannot_cast_between_incompatible_types() {
+        for (from_type, to_type) in [
+            (ColumnType::Boolean, DataType::BigInt),
+            (ColumnType::BigInt, DataType::Boolean),
+            (
+                ColumnType::BigInt,
+                DataType::Timestamp(PoSQLTimeUnit::Second),
+            ),
+            (
+                ColumnType::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc),
+                DataType::BigInt,
+            ),
+            (ColumnType::Scalar, DataType::VarChar),
+        ] {
+            assert!(matches!(
+                try_cast_column_type(from_type, to_type),
+                Err(ColumnOperationError::CastInvalidColumnType { .. })
+            ));
+        }
+        assert!(matches!(
+            try_cast_column_type(
+                ColumnType::BigInt,
+                DataType::Decimal {
+                    precision: 76,
+                    scale: 0
+                }
+            ),
+            Err(ColumnOperationError::DecimalConversionError { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_rescale_bigints_rounding_half_away_from_zero() {
+        let rescale = |value: i64, scale| rescale_bigint(&BigInt::from(value), scale);
+        assert_eq!(rescale(125, 2), BigInt::from(12_500));
+        assert_eq!(rescale(125, 0), BigInt::from(125));
+        assert_eq!(rescale(125, -1), BigInt::from(13));
+        assert_eq!(rescale(-125, -1), BigInt::from(-13));
+        assert_eq!(rescale(-124, -1), BigInt::from(-12));
+        assert_eq!(rescale(149, -2), BigInt::from(1));
+        assert_eq!(rescale(-150, -2), BigInt::from(-2));
+    }
 }
@@ -1,7 +1,7 @@
 use crate::base::{database::ColumnType, math::decimal::DecimalError};
 use alloc::string::String;
 use core::result::Result;
-use proof_of_sql_parser::intermediate_ast::{BinaryOperator, UnaryOperator};
+use proof_of_sql_parser::intermediate_ast::{BinaryOperator, DataType, UnaryOperator};
 use snafu::Snafu;
 
 /// Errors from operations on columns.
@@ -36,6 +36,35 @@ pub enum ColumnOperationError {
         operand_type: ColumnType,
     },
 
+    /// Incompatible `ColumnType`s of the branches of a `CASE` expression
+    #[snafu(display(
+        "CASE with branches of types {then_type:?} and {else_type:?} is not supported"
+    ))]
+    CaseInvalidColumnTypes {
+        /// `ColumnType` of the branch taken if the condition holds
+        then_type: ColumnType,
+        /// `ColumnType` of the branch taken otherwise
+        else_type: ColumnType,
+    },
+
+    /// Unsupported `CAST` between two types
+    #[snafu(display("CAST from {from_type:?} to {to_type} is not supported"))]
+    CastInvalidColumnType {
+        /// `ColumnType` of the value to convert
+        from_type: ColumnType,
+        /// `DataType` to convert the value to
+        to_type: DataType,
+    },
+
+    /// A value that can not be converted by a `CAST`, e.g. because it does not fit into the type
+    #[snafu(display("CAST of {value} to {to_type:?} failed"))]
+    CastInvalidValue {
+        /// The value that can not be converted
+        value: String,
+        /// `ColumnType` to convert the value to
+        to_type: ColumnType,
+    },
+
     /// Overflow in integer operations
     #[snafu(display("Overflow in integer operation: {error}"))]
     IntegerOverflow {
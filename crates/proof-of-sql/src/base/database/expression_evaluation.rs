@@ -4,9 +4,9 @@ use crate::base::{
     math::decimal::{try_into_to_scalar, Precision},
     scalar::Scalar,
 };
-use alloc::{format, string::ToString, vec};
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
 use proof_of_sql_parser::{
-    intermediate_ast::{BinaryOperator, Expression, Literal, UnaryOperator},
+    intermediate_ast::{BinaryOperator, DataType, Expression, Literal, UnaryOperator},
     Identifier,
 };
 
@@ -18,12 +18,191 @@ impl<S: Scalar> OwnedTable<S> {
             Expression::Literal(lit) => self.evaluate_literal(lit),
             Expression::Binary { op, left, right } => self.evaluate_binary_expr(*op, left, right),
             Expression::Unary { op, expr } => self.evaluate_unary_expr(*op, expr),
+            Expression::InList { expr, list } => self.evaluate(&expand_in_list(expr, list)),
+            Expression::Between { expr, low, high } => {
+                self.evaluate(&expand_between(expr, low, high))
+            }
+            Expression::Case {
+                when_then,
+                else_result,
+            } => self.evaluate_case(when_then, else_result.as_deref()),
+            Expression::Cast { expr, data_type } => self.evaluate_cast(expr, *data_type),
             _ => Err(ExpressionEvaluationError::Unsupported {
                 expression: format!("Expression {expr:?} is not supported yet"),
             }),
         }
     }
 
+    /// Evaluate which rows of an expression are not null on the table.
+    ///
+    /// Returns `None` if the expression can't be null. Logical operators follow three-valued logic,
+    /// e.g. `NULL AND FALSE` is `FALSE`, while all other operators are null if any operand is null.
+    pub fn evaluate_presence(
+        &self,
+        expr: &Expression,
+    ) -> ExpressionEvaluationResult<Option<Vec<bool>>> {
+        match expr {
+            Expression::Column(identifier) => Ok(self.presence(*identifier).map(<[bool]>::to_vec)),
+            Expression::Unary {
+                op: UnaryOperator::Not,
+                expr,
+            }
+            | Expression::Cast { expr, .. } => self.evaluate_presence(expr),
+            Expression::Binary {
+                op: op @ (BinaryOperator::And | BinaryOperator::Or),
+                left,
+                right,
+            } => {
+                let left_presence = self.evaluate_presence(left)?;
+                let right_presence = self.evaluate_presence(right)?;
+                if left_presence.is_none() && right_presence.is_none() {
+                    return Ok(None);
+                }
+                let (OwnedColumn::Boolean(left), OwnedColumn::Boolean(right)) =
+                    (self.evaluate(left)?, self.evaluate(right)?)
+                else {
+                    return Ok(and_presences(left_presence, right_presence));
+                };
+                // A row is not null if both sides are not null or if one side alone determines the result,
+                // i.e. it is `FALSE` for `AND` and `TRUE` for `OR`.
+                let decisive = |value: bool| value == (*op == BinaryOperator::Or);
+                let is_present = |presence: &Option<Vec<bool>>, i: usize| {
+                    presence.as_ref().map_or(true, |presence| presence[i])
+                };
+                Ok(Some(
+                    (0..self.num_rows())
+                        .map(|i| {
+                            let left_present = is_present(&left_presence, i);
+                            let right_present = is_present(&right_presence, i);
+                            (left_present && (right_present || decisive(left[i])))
+                                || (right_present && decisive(right[i]))
+                        })
+                        .collect(),
+                ))
+            }
+            Expression::InList { expr, list } => {
+                self.evaluate_presence(&expand_in_list(expr, list))
+            }
+            Expression::Between { expr, low, high } => {
+                self.evaluate_presence(&expand_between(expr, low, high))
+            }
+            Expression::Case {
+                when_then,
+                else_result,
+            } => self.evaluate_case_presence(when_then, else_result.as_deref()),
+            Expression::Binary { left, right, .. } => Ok(and_presences(
+                self.evaluate_presence(left)?,
+                self.evaluate_presence(right)?,
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    /// Evaluates `CASE WHEN ... END` starting from the last branch, so that the first condition
+    /// that holds determines the result.
+    fn evaluate_case(
+        &self,
+        when_then: &[(Expression, Expression)],
+        else_result: Option<&Expression>,
+    ) -> ExpressionEvaluationResult<OwnedColumn<S>> {
+        let ((_, last_result), _) =
+            when_then
+                .split_last()
+                .ok_or_else(|| ExpressionEvaluationError::Unsupported {
+                    expression: "CASE without WHEN".to_string(),
+                })?;
+        // Without `ELSE` the rows without a match are null, so any result of the right type will do
+        let mut result = self.evaluate(else_result.unwrap_or(last_result))?;
+        for (condition, then) in when_then.iter().rev() {
+            let selection = self.evaluate_condition(condition)?;
+            result = self
+                .evaluate(then)?
+                .element_wise_select(&selection, &result)?;
+        }
+        Ok(result)
+    }
+
+    /// Evaluates which rows of `CASE WHEN ... END` are not null, i.e. the rows whose branch is not
+    /// null. Rows without a match are null if there is no `ELSE`.
+    fn evaluate_case_presence(
+        &self,
+        when_then: &[(Expression, Expression)],
+        else_result: Option<&Expression>,
+    ) -> ExpressionEvaluationResult<Option<Vec<bool>>> {
+        let mut presence = match else_result {
+            Some(else_result) => self.evaluate_presence(else_result)?,
+            None => Some(vec![false; self.num_rows()]),
+        };
+        for (condition, then) in when_then.iter().rev() {
+            let then_presence = self.evaluate_presence(then)?;
+            if then_presence.is_none() && presence.is_none() {
+                continue;
+            }
+            let selection = self.evaluate_condition(condition)?;
+            let is_present = |presence: &Option<Vec<bool>>, i: usize| {
+                presence.as_ref().map_or(true, |presence| presence[i])
+            };
+            presence = Some(
+                (0..self.num_rows())
+                    .map(|i| {
+                        if selection[i] {
+                            is_present(&then_presence, i)
+                        } else {
+                            is_present(&presence, i)
+                        }
+                    })
+                    .collect(),
+            );
+        }
+        Ok(presence)
+    }
+
+    /// Evaluates a condition of `CASE`, which doesn't hold for the rows where it is null.
+    fn evaluate_condition(&self, condition: &Expression) -> ExpressionEvaluationResult<Vec<bool>> {
+        let OwnedColumn::Boolean(values) = self.evaluate(condition)? else {
+            return Err(ExpressionEvaluationError::Unsupported {
+                expression: format!("CASE with the non-boolean condition {condition:?}"),
+            });
+        };
+        Ok(match self.evaluate_presence(condition)? {
+            Some(presence) => values.iter().zip(presence).map(|(v, p)| *v && p).collect(),
+            None => values,
+        })
+    }
+
+    /// Evaluates `CAST(expr AS data_type)`. Null rows may hold strings that can't be converted, so
+    /// they are replaced by strings that can.
+    fn evaluate_cast(
+        &self,
+        expr: &Expression,
+        data_type: DataType,
+    ) -> ExpressionEvaluationResult<OwnedColumn<S>> {
+        let column = match (self.evaluate(expr)?, self.evaluate_presence(expr)?) {
+            (OwnedColumn::VarChar(values), Some(presence)) => {
+                let placeholder = match data_type {
+                    DataType::Boolean => "false",
+                    DataType::Timestamp(_) => "1970-01-01T00:00:00Z",
+                    _ => "0",
+                };
+                OwnedColumn::VarChar(
+                    values
+                        .into_iter()
+                        .zip(presence)
+                        .map(|(value, is_present)| {
+                            if is_present {
+                                value
+                            } else {
+                                placeholder.to_string()
+                            }
+                        })
+                        .collect(),
+                )
+            }
+            (column, _) => column,
+        };
+        Ok(column.try_cast(data_type)?)
+    }
+
     fn evaluate_column(
         &self,
         identifier: &Identifier,
@@ -66,6 +245,16 @@ impl<S: Scalar> OwnedTable<S> {
         let column = self.evaluate(expr)?;
         match op {
             UnaryOperator::Not => Ok(column.element_wise_not()?),
+            UnaryOperator::IsNull => Ok(OwnedColumn::Boolean(
+                self.evaluate_presence(expr)?.map_or_else(
+                    || vec![false; column.len()],
+                    |presence| presence.iter().map(|present| !present).collect(),
+                ),
+            )),
+            UnaryOperator::IsNotNull => Ok(OwnedColumn::Boolean(
+                self.evaluate_presence(expr)?
+                    .unwrap_or_else(|| vec![true; column.len()]),
+            )),
         }
     }
 
@@ -83,10 +272,56 @@ impl<S: Scalar> OwnedTable<S> {
             BinaryOperator::Equal => Ok(left.element_wise_eq(&right)?),
             BinaryOperator::GreaterThanOrEqual => Ok(left.element_wise_ge(&right)?),
             BinaryOperator::LessThanOrEqual => Ok(left.element_wise_le(&right)?),
+            BinaryOperator::Like => Ok(left.element_wise_like(&right)?),
             BinaryOperator::Add => Ok((left + right)?),
             BinaryOperator::Subtract => Ok((left - right)?),
             BinaryOperator::Multiply => Ok((left * right)?),
             BinaryOperator::Division => Ok((left / right)?),
+            BinaryOperator::Modulo => Ok((left % right)?),
         }
     }
 }
+
+/// Rewrites `expr IN (list)` as `expr = list[0] OR expr = list[1] OR ...`
+fn expand_in_list(expr: &Expression, list: &[Expression]) -> Expression {
+    list.iter()
+        .map(|item| Expression::Binary {
+            op: BinaryOperator::Equal,
+            left: Box::new(expr.clone()),
+            right: Box::new(item.clone()),
+        })
+        .reduce(|left, right| Expression::Binary {
+            op: BinaryOperator::Or,
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+        .unwrap_or(Expression::Literal(Literal::Boolean(false)))
+}
+
+/// Rewrites `expr BETWEEN low AND high` as `expr >= low AND expr <= high`
+fn expand_between(expr: &Expression, low: &Expression, high: &Expression) -> Expression {
+    Expression::Binary {
+        op: BinaryOperator::And,
+        left: Box::new(Expression::Binary {
+            op: BinaryOperator::GreaterThanOrEqual,
+            left: Box::new(expr.clone()),
+            right: Box::new(low.clone()),
+        }),
+        right: Box::new(Expression::Binary {
+            op: BinaryOperator::LessThanOrEqual,
+            left: Box::new(expr.clone()),
+            right: Box::new(high.clone()),
+        }),
+    }
+}
+
+/// Returns the presence of an expression that is null if either operand is null.
+fn and_presences(
+    left_presence: Option<Vec<bool>>,
+    right_presence: Option<Vec<bool>>,
+) -> Option<Vec<bool>> {
+    match (left_presence, right_presence) {
+        (Some(left), Some(right)) => Some(left.iter().zip(right).map(|(l, r)| *l && r).collect()),
+        (presence, None) | (None, presence) => presence,
+    }
+}
@@ -7,7 +7,7 @@ use crate::base::{
     scalar::Curve25519Scalar,
 };
 use proof_of_sql_parser::{
-    intermediate_ast::Literal,
+    intermediate_ast::{DataType, Literal},
     intermediate_decimal::IntermediateDecimal,
     posql_time::{PoSQLTimeUnit, PoSQLTimeZone, PoSQLTimestamp},
     utility::*,
@@ -140,6 +140,237 @@ fn we_can_evaluate_a_logical_expression() {
     assert_eq!(actual_column, expected_column);
 }
 
+#[test]
+fn we_can_evaluate_a_like_expression() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        varchar("name", ["abc", "abcd", "xabc", "ab", "", "ac"]),
+        varchar("pattern", ["a%", "%d", "_abc", "a_b", "%", "A%"]),
+    ]);
+
+    let expr = like(col("name"), lit("abc%"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![true, true, false, false, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = not(like(col("name"), lit("%b%")));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, false, false, false, true, true]);
+    assert_eq!(actual_column, expected_column);
+
+    // Patterns can be columns as well
+    let expr = like(col("name"), col("pattern"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![true, true, true, false, true, false]);
+    assert_eq!(actual_column, expected_column);
+}
+
+#[test]
+fn we_can_evaluate_in_list_and_between_expressions() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5]),
+        bigint("b", [2_i64, 2, 0, 5, 4]),
+        varchar("c", ["x", "y", "z", "x", "w"]),
+    ]);
+
+    let expr = in_list(col("a"), vec![lit(4_i64), lit(1_i64), col("b")]);
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![true, true, false, true, false]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = not(in_list(col("c"), vec![lit("x"), lit("w")]));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, true, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = between(col("a"), col("b"), lit(4_i64));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, true, false, false]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = between(col("a"), lit(4_i64), lit(2_i64));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false; 5]);
+    assert_eq!(actual_column, expected_column);
+}
+
+#[test]
+fn we_can_evaluate_case_expressions() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5]),
+        bigint("b", [10_i64, 20, 30, 40, 50]),
+        decimal75("d", 3, 1, [1_i64, 2, 3, 4, 5]),
+        bigint("e", [7_i64, 0, 9, 0, 11]),
+        boolean("__e_presence__", [true, false, true, false, true]),
+    ]);
+
+    // The branches are scaled to a common type
+    let expr = case_when(
+        vec![
+            (ge(col("a"), lit(4_i64)), col("b")),
+            (equal(col("a"), lit(1_i64)), lit(0_i64)),
+        ],
+        Some(col("d")),
+    );
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Decimal75(
+        Precision::new(20).unwrap(),
+        1,
+        vec![0.into(), 2.into(), 3.into(), 400.into(), 500.into()],
+    );
+    assert_eq!(actual_column, expected_column);
+    assert_eq!(table.evaluate_presence(&expr).unwrap(), None);
+
+    // Rows without a match are null if there is no ELSE
+    let expr = case_when(vec![(ge(col("a"), lit(4_i64)), col("b"))], None);
+    let expected_presence = vec![false, false, false, true, true];
+    assert_eq!(
+        table.evaluate_presence(&expr).unwrap(),
+        Some(expected_presence)
+    );
+
+    // A null condition doesn't hold, and the branch that is chosen decides whether a row is null
+    let expr = case_when(vec![(ge(col("e"), lit(8_i64)), col("b"))], Some(col("e")));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::BigInt(vec![7, 0, 30, 0, 50]);
+    assert_eq!(actual_column, expected_column);
+    let expected_presence = vec![true, false, true, false, true];
+    assert_eq!(
+        table.evaluate_presence(&expr).unwrap(),
+        Some(expected_presence)
+    );
+
+    // Conditions must be boolean
+    let expr = case_when(vec![(col("a"), col("b"))], None);
+    assert!(matches!(
+        table.evaluate(&expr),
+        Err(ExpressionEvaluationError::Unsupported { .. })
+    ));
+}
+
+#[test]
+fn we_can_evaluate_cast_expressions() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        int("a", [1_i32, -2, 3]),
+        decimal75("d", 5, 2, [125_i64, -250, 300]),
+        timestamptz(
+            "t",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            [0_i64, 1, 2],
+        ),
+        varchar("s", ["1.5", "abc", "-7"]),
+        boolean("__s_presence__", [true, false, true]),
+    ]);
+
+    let expr = cast(
+        col("a"),
+        DataType::Decimal {
+            precision: 20,
+            scale: 2,
+        },
+    );
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Decimal75(
+        Precision::new(20).unwrap(),
+        2,
+        vec![100.into(), (-200).into(), 300.into()],
+    );
+    assert_eq!(actual_column, expected_column);
+
+    // Lowering the scale rounds half away from zero
+    let expr = cast(col("d"), DataType::BigInt);
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::BigInt(vec![1, -3, 3]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = cast(col("t"), DataType::Timestamp(PoSQLTimeUnit::Millisecond));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::TimestampTZ(
+        PoSQLTimeUnit::Millisecond,
+        PoSQLTimeZone::Utc,
+        vec![0, 1000, 2000],
+    );
+    assert_eq!(actual_column, expected_column);
+
+    // Null rows can't fail to convert
+    let expr = cast(col("s"), DataType::BigInt);
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::BigInt(vec![2, 0, -7]);
+    assert_eq!(actual_column, expected_column);
+    assert_eq!(
+        table.evaluate_presence(&expr).unwrap(),
+        Some(vec![true, false, true])
+    );
+
+    // Values must fit into the type
+    let expr = cast(
+        col("a"),
+        DataType::Decimal {
+            precision: 2,
+            scale: 2,
+        },
+    );
+    assert!(matches!(
+        table.evaluate(&expr),
+        Err(ExpressionEvaluationError::ColumnOperationError {
+            source: ColumnOperationError::CastInvalidValue { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_can_evaluate_expressions_with_nulls() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1, 0, 3, 0]),
+        boolean("__a_presence__", [true, false, true, false]),
+        boolean("b", [true, false, false, true]),
+        boolean("__b_presence__", [true, true, false, true]),
+        boolean("c", [false, false, false, true]),
+    ]);
+
+    let expr = is_null(col("a"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, false, true]);
+    assert_eq!(actual_column, expected_column);
+
+    let expr = is_not_null(col("a"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![true, false, true, false]);
+    assert_eq!(actual_column, expected_column);
+
+    // Non-nullable columns are never null
+    let expr = is_null(col("c"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false; 4]);
+    assert_eq!(actual_column, expected_column);
+
+    // Arithmetic and comparisons are null if any operand is null
+    let expr = is_null(add(col("a"), lit(1)));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, true, false, true]);
+    assert_eq!(actual_column, expected_column);
+
+    // NULL AND FALSE is FALSE while NULL AND TRUE is NULL
+    let expr = and(equal(col("a"), lit(1)), col("b"));
+    let expected_presence = vec![true, true, true, false];
+    assert_eq!(
+        table.evaluate_presence(&expr).unwrap(),
+        Some(expected_presence)
+    );
+
+    // NULL OR TRUE is TRUE while NULL OR FALSE is NULL
+    let expr = or(col("b"), col("c"));
+    let expected_presence = vec![true, true, false, true];
+    assert_eq!(
+        table.evaluate_presence(&expr).unwrap(),
+        Some(expected_presence)
+    );
+    let expr = is_null(not(col("b")));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Boolean(vec![false, false, true, false]);
+    assert_eq!(actual_column, expected_column);
+}
+
 #[test]
 fn we_can_evaluate_an_arithmetic_expression() {
     let table: OwnedTable<Curve25519Scalar> = owned_table([
@@ -193,6 +424,22 @@ fn we_can_evaluate_an_arithmetic_expression() {
         .collect();
     let expected_column = OwnedColumn::Decimal75(Precision::new(46).unwrap(), 6, expected_scalars);
     assert_eq!(actual_column, expected_column);
+
+    // Remainder of the int128s divided by 3, which has the sign of the int128s
+    let expr = modulo(col("int128s"), lit(3));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Int128(vec![-1, -2, 0, 2, 1]);
+    assert_eq!(actual_column, expected_column);
+
+    // Dividing by zero results in zero with a remainder of the dividend, as in the proof
+    let expr = div(col("ints"), col("smallints"));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::Int(vec![2, 2, 0, 2, 2]);
+    assert_eq!(actual_column, expected_column);
+    let expr = modulo(col("bigints"), lit(0));
+    let actual_column = table.evaluate(&expr).unwrap();
+    let expected_column = OwnedColumn::BigInt(vec![-8, -4, 0, 4, 8]);
+    assert_eq!(actual_column, expected_column);
 }
 
 #[test]
@@ -239,12 +486,12 @@ fn we_cannot_evaluate_expressions_if_column_operation_errors_out() {
         })
     ));
 
-    // We can't divide by zero
-    let expr = div(col("bigints"), lit(0));
+    // i64::MIN / -1 overflows
+    let expr = div(col("bigints"), lit(-1));
     assert!(matches!(
         table.evaluate(&expr),
         Err(ExpressionEvaluationError::ColumnOperationError {
-            source: ColumnOperationError::DivisionByZero
+            source: ColumnOperationError::IntegerOverflow { .. }
         })
     ));
 }
@@ -1,11 +1,11 @@
 //! Contains the utility functions for the `GroupByExec` node.
 
 use crate::base::{
-    database::{filter_util::filter_column_by_index, Column, OwnedColumn},
+    database::{filter_util::filter_column_by_index, Column, NullableColumn, OwnedColumn},
     if_rayon,
     scalar::Scalar,
 };
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, vec, vec::Vec};
 use bumpalo::Bump;
 use core::cmp::Ordering;
 use itertools::Itertools;
@@ -45,22 +45,25 @@ pub enum AggregateColumnsError {
 ///
 /// This function takes a selection vector and a set of `group_by` and sum columns and returns
 /// the given columns aggregated by the `group_by` columns only for the selected rows.
+///
+/// Null values of the sum, max and min columns are ignored. In particular, the sum of a group
+/// that only contains nulls is zero, while its maximum and minimum are `None`.
 pub fn aggregate_columns<'a, S: Scalar>(
     alloc: &'a Bump,
     group_by_columns_in: &[Column<'a, S>],
-    sum_columns_in: &[Column<S>],
-    max_columns_in: &[Column<S>],
-    min_columns_in: &[Column<S>],
+    sum_columns_in: &[NullableColumn<S>],
+    max_columns_in: &[NullableColumn<S>],
+    min_columns_in: &[NullableColumn<S>],
     selection_column_in: &[bool],
 ) -> Result<AggregatedColumns<'a, S>, AggregateColumnsError> {
     // Check that all the columns have the same length
     let len = selection_column_in.len();
-    if group_by_columns_in
-        .iter()
-        .chain(sum_columns_in.iter())
-        .chain(max_columns_in.iter())
-        .chain(min_columns_in.iter())
-        .any(|col| col.len() != len)
+    if group_by_columns_in.iter().any(|col| col.len() != len)
+        || sum_columns_in
+            .iter()
+            .chain(max_columns_in.iter())
+            .chain(min_columns_in.iter())
+            .any(|col| col.len() != len)
     {
         return Err(AggregateColumnsError::ColumnLengthMismatch);
     }
@@ -105,21 +108,24 @@ pub fn aggregate_columns<'a, S: Scalar>(
     let sum_columns_out: Vec<_> = sum_columns_in
         .iter()
         .map(|column| {
-            sum_aggregate_column_by_index_counts(alloc, column, &counts, &filtered_indexes)
+            let (counts, indexes) = non_null_counts_and_indexes(column, &counts, &filtered_indexes);
+            sum_aggregate_column_by_index_counts(alloc, &column.values, &counts, &indexes)
         })
         .collect();
 
     let max_columns_out: Vec<_> = max_columns_in
         .iter()
         .map(|column| {
-            max_aggregate_column_by_index_counts(alloc, column, &counts, &filtered_indexes)
+            let (counts, indexes) = non_null_counts_and_indexes(column, &counts, &filtered_indexes);
+            max_aggregate_column_by_index_counts(alloc, &column.values, &counts, &indexes)
         })
         .collect();
 
     let min_columns_out: Vec<_> = min_columns_in
         .iter()
         .map(|column| {
-            min_aggregate_column_by_index_counts(alloc, column, &counts, &filtered_indexes)
+            let (counts, indexes) = non_null_counts_and_indexes(column, &counts, &filtered_indexes);
+            min_aggregate_column_by_index_counts(alloc, &column.values, &counts, &indexes)
         })
         .collect();
 
@@ -135,6 +141,72 @@ pub fn aggregate_columns<'a, S: Scalar>(
     })
 }
 
+/// Returns the index of the group of each selected row in the output of [`aggregate_columns`]
+/// and `None` for the rows that are not selected.
+pub(crate) fn group_indexes<S: Scalar>(
+    group_by_columns_in: &[Column<S>],
+    selection_column_in: &[bool],
+) -> Vec<Option<usize>> {
+    let mut filtered_indexes: Vec<_> = selection_column_in
+        .iter()
+        .enumerate()
+        .filter(|&(_, &b)| b)
+        .map(|(i, _)| i)
+        .collect();
+    if_rayon!(
+        filtered_indexes.par_sort_unstable_by(|&a, &b| compare_indexes_by_columns(
+            group_by_columns_in,
+            a,
+            b
+        )),
+        filtered_indexes.sort_unstable_by(|&a, &b| compare_indexes_by_columns(
+            group_by_columns_in,
+            a,
+            b
+        ))
+    );
+    let mut group_indexes = vec![None; selection_column_in.len()];
+    let mut group_index = 0;
+    for (k, &i) in filtered_indexes.iter().enumerate() {
+        if k > 0
+            && compare_indexes_by_columns(group_by_columns_in, filtered_indexes[k - 1], i)
+                != Ordering::Equal
+        {
+            group_index += 1;
+        }
+        group_indexes[i] = Some(group_index);
+    }
+    group_indexes
+}
+
+/// Returns the `counts` and `indexes` of the groups with the null rows of `column` removed.
+///
+/// The `counts` slice contains the number of elements in each group and the `indexes` slice
+/// contains the indexes of the elements in `column`.
+fn non_null_counts_and_indexes<'b, S: Scalar>(
+    column: &NullableColumn<S>,
+    counts: &'b [usize],
+    indexes: &'b [usize],
+) -> (Cow<'b, [usize]>, Cow<'b, [usize]>) {
+    let Some(presence) = column.presence else {
+        return (Cow::Borrowed(counts), Cow::Borrowed(indexes));
+    };
+    let mut index = 0;
+    let non_null_counts = counts
+        .iter()
+        .map(|&count| {
+            let start = index;
+            index += count;
+            indexes[start..index]
+                .iter()
+                .filter(|&&i| presence[i])
+                .count()
+        })
+        .collect();
+    let non_null_indexes = indexes.iter().copied().filter(|&i| presence[i]).collect();
+    (Cow::Owned(non_null_counts), Cow::Owned(non_null_indexes))
+}
+
 /// Returns a slice with the lifetime of `alloc` that contains the grouped sums of `column`.
 /// The `counts` slice contains the number of elements in each group and the `indexes` slice
 /// contains the indexes of the elements in `column`.
@@ -301,8 +373,7 @@ where
     alloc.alloc_slice_fill_iter(counts.iter().map(|&count| {
         let start = index;
         index += count;
-        // Note that currently we can't run this on empty slices
-        // In the future we have to support NULL values
+        // Empty groups, e.g. groups that only contain nulls, have no maximum
         indexes[start..index]
             .iter()
             .map(|i| S::from(&slice[*i]))
@@ -1,6 +1,6 @@
 use crate::{
     base::{
-        database::{group_by_util::*, Column, OwnedColumn},
+        database::{group_by_util::*, Column, NullableColumn, OwnedColumn},
         scalar::Curve25519Scalar,
     },
     proof_primitive::dory::DoryScalar,
@@ -15,7 +15,7 @@ fn we_can_aggregate_empty_columns() {
     let column_c = Column::Int128(&[]);
     let column_d = Column::Scalar(&[]);
     let group_by = &[column_a, column_b];
-    let sum_columns = &[column_c, column_d];
+    let sum_columns = &[column_c.into(), column_d.into()];
     let selection = &[];
     let alloc = Bump::new();
     let aggregate_result = aggregate_columns(&alloc, group_by, sum_columns, &[], &[], selection)
@@ -37,9 +37,9 @@ fn we_can_aggregate_columns_with_empty_group_by_and_no_rows_selected() {
     let column_c = Column::Int128(slice_c);
     let column_d = Column::Scalar(&scals_d);
     let group_by = &[];
-    let sum_columns = &[column_c, column_d];
-    let max_columns = &[column_c, column_d];
-    let min_columns = &[column_c, column_d];
+    let sum_columns = &[column_c.into(), column_d.into()];
+    let max_columns = &[column_c.into(), column_d.into()];
+    let min_columns = &[column_c.into(), column_d.into()];
     let alloc = Bump::new();
     let aggregate_result = aggregate_columns(
         &alloc,
@@ -73,9 +73,9 @@ fn we_can_aggregate_columns_with_empty_group_by() {
     let column_c = Column::Int128(slice_c);
     let column_d = Column::Scalar(&scals_d);
     let group_by = &[];
-    let sum_columns = &[column_c, column_d];
-    let max_columns = &[column_c, column_d];
-    let min_columns = &[column_c, column_d];
+    let sum_columns = &[column_c.into(), column_d.into()];
+    let max_columns = &[column_c.into(), column_d.into()];
+    let min_columns = &[column_c.into(), column_d.into()];
     let alloc = Bump::new();
     let aggregate_result = aggregate_columns(
         &alloc,
@@ -129,9 +129,9 @@ fn we_can_aggregate_columns() {
     let column_c = Column::Int128(slice_c);
     let column_d = Column::Scalar(&scals_d);
     let group_by = &[column_a, column_b];
-    let sum_columns = &[column_c, column_d];
-    let max_columns = &[column_c, column_d];
-    let min_columns = &[column_c, column_d];
+    let sum_columns = &[column_c.into(), column_d.into()];
+    let max_columns = &[column_c.into(), column_d.into()];
+    let min_columns = &[column_c.into(), column_d.into()];
     let alloc = Bump::new();
     let aggregate_result = aggregate_columns(
         &alloc,
@@ -216,6 +216,45 @@ fn we_can_aggregate_columns() {
     assert_eq!(aggregate_result.min_columns, expected_min_result);
 }
 
+#[test]
+fn we_can_aggregate_columns_with_nulls() {
+    let column_a = Column::BigInt::<Curve25519Scalar>(&[1, 1, 2, 2, 1]);
+    let column_b = NullableColumn::new(
+        Column::BigInt(&[10, 0, 0, 0, 30]),
+        Some(&[true, false, false, false, true]),
+    );
+    let group_by = &[column_a];
+    let selection = &[true; 5];
+    let alloc = Bump::new();
+    let aggregate_result = aggregate_columns(
+        &alloc,
+        group_by,
+        &[column_b],
+        &[column_b],
+        &[column_b],
+        selection,
+    )
+    .expect("Aggregation should succeed");
+    assert_eq!(
+        aggregate_result.group_by_columns,
+        vec![Column::BigInt(&[1, 2])]
+    );
+    assert_eq!(
+        aggregate_result.sum_columns,
+        vec![&[Curve25519Scalar::from(40), Curve25519Scalar::from(0)]]
+    );
+    assert_eq!(
+        aggregate_result.max_columns,
+        vec![&[Some(Curve25519Scalar::from(30)), None]]
+    );
+    assert_eq!(
+        aggregate_result.min_columns,
+        vec![&[Some(Curve25519Scalar::from(10)), None]]
+    );
+    // The count is the number of rows, including nulls
+    assert_eq!(aggregate_result.count_column, &[3, 2]);
+}
+
 #[test]
 fn we_can_compare_indexes_by_columns_with_no_columns() {
     let columns: &[Column<Curve25519Scalar>; 0] = &[];
@@ -752,3 +791,19 @@ fn we_can_min_aggregate_columns_by_counts() {
     let result = min_aggregate_column_by_index_counts(&alloc, &columns_c, counts, indexes);
     assert_eq!(result, expected);
 }
+
+#[test]
+fn we_can_get_the_group_indexes_of_selected_rows() {
+    let column_a = Column::BigInt::<Curve25519Scalar>(&[3, 1, 3, 2, 1, 3]);
+    let column_b = Column::Boolean(&[true, false, true, true, false, false]);
+    let selection = &[true, true, true, false, true, true];
+    assert_eq!(
+        group_indexes(&[column_a, column_b], selection),
+        vec![Some(2), Some(0), Some(2), None, Some(0), Some(1)]
+    );
+    assert_eq!(
+        group_indexes::<Curve25519Scalar>(&[], selection),
+        vec![Some(0), Some(0), Some(0), None, Some(0), Some(0)]
+    );
+    assert_eq!(group_indexes(&[column_a], &[false; 6]), vec![None; 6]);
+}
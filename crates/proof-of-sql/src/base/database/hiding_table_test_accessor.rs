@@ -0,0 +1,108 @@
+use super::{
+    BlindingFactorAccessor, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, OwnedTable, OwnedTableTestAccessor, SchemaAccessor, TableRef, TestAccessor,
+};
+use crate::{
+    base::{map::IndexMap, scalar::Curve25519Scalar},
+    proof_primitive::inner_product::{compute_curve25519_blinding_generator, InnerProductProof},
+};
+use alloc::vec::Vec;
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar};
+use proof_of_sql_parser::Identifier;
+
+/// A test accessor whose column commitments are hiding commitments, i.e. the commitment to the column plus
+/// `blind * H` for a uniformly random `blind` per column, see [`BlindingFactorAccessor`].
+/// Note: this is not optimized for performance, so should not be used for benchmarks.
+pub struct HidingTableTestAccessor {
+    inner: OwnedTableTestAccessor<'static, InnerProductProof>,
+    blinding_factors: IndexMap<ColumnRef, Curve25519Scalar>,
+}
+
+impl HidingTableTestAccessor {
+    /// Create a new empty test accessor.
+    #[must_use]
+    pub fn new_empty() -> Self {
+        Self {
+            inner: OwnedTableTestAccessor::new_empty_with_setup(()),
+            blinding_factors: IndexMap::default(),
+        }
+    }
+
+    /// Add a table and draw a blinding factor for each of its columns from `rng`.
+    pub fn add_table(
+        &mut self,
+        table_ref: TableRef,
+        data: OwnedTable<Curve25519Scalar>,
+        table_offset: usize,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) {
+        for &column_id in data.column_names() {
+            let column_type = data.inner_table()[&column_id].column_type();
+            self.blinding_factors.insert(
+                ColumnRef::new(table_ref, column_id, column_type),
+                Curve25519Scalar::rand(rng),
+            );
+        }
+        self.inner.add_table(table_ref, data, table_offset);
+    }
+
+    /// Create a new test accessor containing the provided table.
+    pub fn new_from_table(
+        table_ref: TableRef,
+        owned_table: OwnedTable<Curve25519Scalar>,
+        offset: usize,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Self {
+        let mut res = Self::new_empty();
+        res.add_table(table_ref, owned_table, offset, rng);
+        res
+    }
+}
+
+impl DataAccessor<Curve25519Scalar> for HidingTableTestAccessor {
+    fn get_column(&self, column: ColumnRef) -> Column<Curve25519Scalar> {
+        self.inner.get_column(column)
+    }
+}
+
+impl CommitmentAccessor<RistrettoPoint> for HidingTableTestAccessor {
+    fn get_commitment(&self, column: ColumnRef) -> RistrettoPoint {
+        self.inner.get_commitment(column)
+            + Scalar::from(&self.get_blinding_factor(column))
+                * compute_curve25519_blinding_generator()
+    }
+}
+
+///
+/// # Panics
+///
+/// Will panic if the column was not added with [`HidingTableTestAccessor::add_table`].
+impl BlindingFactorAccessor<Curve25519Scalar> for HidingTableTestAccessor {
+    fn get_blinding_factor(&self, column: ColumnRef) -> Curve25519Scalar {
+        self.blinding_factors[&column]
+    }
+}
+
+impl MetadataAccessor for HidingTableTestAccessor {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.inner.get_length(table_ref)
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.inner.get_offset(table_ref)
+    }
+}
+
+impl SchemaAccessor for HidingTableTestAccessor {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        self.inner.lookup_column(table_ref, column_id)
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        self.inner.lookup_schema(table_ref)
+    }
+}
@@ -0,0 +1,43 @@
+//! Contains the utility functions for the `JoinExec` node.
+
+use crate::base::{database::Column, scalar::Scalar};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// Returns the values of an integer-like column as `i128`s so that they can be used as join keys.
+///
+/// Returns `None` if the column can't be used as a join key.
+pub fn join_key_values<S: Scalar>(column: &Column<S>) -> Option<Vec<i128>> {
+    match column {
+        Column::TinyInt(col) => Some(col.iter().map(|&v| i128::from(v)).collect()),
+        Column::SmallInt(col) => Some(col.iter().map(|&v| i128::from(v)).collect()),
+        Column::Int(col) => Some(col.iter().map(|&v| i128::from(v)).collect()),
+        Column::BigInt(col) | Column::TimestampTZ(_, _, col) => {
+            Some(col.iter().map(|&v| i128::from(v)).collect())
+        }
+        Column::Int128(col) => Some(col.to_vec()),
+        Column::Boolean(_) | Column::Decimal75(..) | Column::Scalar(_) | Column::VarChar(_) => None,
+    }
+}
+
+/// Computes the rows of the inner join of two tables on a single key.
+///
+/// Returns the indexes of the joined rows in the left and the right table. The result is
+/// ordered by the index in the left table and then by the index in the right table, i.e. in
+/// the order that a nested loop join over the left table produces.
+pub fn compute_join_indexes(left_keys: &[i128], right_keys: &[i128]) -> (Vec<usize>, Vec<usize>) {
+    let mut right_rows_by_key: BTreeMap<i128, Vec<usize>> = BTreeMap::new();
+    for (j, key) in right_keys.iter().enumerate() {
+        right_rows_by_key.entry(*key).or_default().push(j);
+    }
+    left_keys
+        .iter()
+        .enumerate()
+        .flat_map(|(i, key)| {
+            right_rows_by_key
+                .get(key)
+                .into_iter()
+                .flatten()
+                .map(move |&j| (i, j))
+        })
+        .unzip()
+}
@@ -0,0 +1,72 @@
+use crate::base::{
+    database::{join_util::*, Column},
+    math::decimal::Precision,
+    scalar::Curve25519Scalar,
+};
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+
+#[test]
+fn we_can_get_join_key_values_of_integer_columns() {
+    assert_eq!(
+        join_key_values(&Column::<Curve25519Scalar>::TinyInt(&[1, -2])),
+        Some(vec![1, -2])
+    );
+    assert_eq!(
+        join_key_values(&Column::<Curve25519Scalar>::SmallInt(&[1, -2])),
+        Some(vec![1, -2])
+    );
+    assert_eq!(
+        join_key_values(&Column::<Curve25519Scalar>::Int(&[1, -2])),
+        Some(vec![1, -2])
+    );
+    assert_eq!(
+        join_key_values(&Column::<Curve25519Scalar>::BigInt(&[1, -2])),
+        Some(vec![1, -2])
+    );
+    assert_eq!(
+        join_key_values(&Column::<Curve25519Scalar>::Int128(&[i128::MAX, i128::MIN])),
+        Some(vec![i128::MAX, i128::MIN])
+    );
+    assert_eq!(
+        join_key_values(&Column::<Curve25519Scalar>::TimestampTZ(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            &[1, -2]
+        )),
+        Some(vec![1, -2])
+    );
+}
+
+#[test]
+fn we_cannot_get_join_key_values_of_non_integer_columns() {
+    let scalars = [Curve25519Scalar::from(1)];
+    assert_eq!(
+        join_key_values(&Column::<Curve25519Scalar>::Boolean(&[true])),
+        None
+    );
+    assert_eq!(join_key_values(&Column::Scalar(&scalars)), None);
+    assert_eq!(
+        join_key_values(&Column::Decimal75(Precision::new(5).unwrap(), 0, &scalars)),
+        None
+    );
+    assert_eq!(join_key_values(&Column::VarChar((&["1"], &scalars))), None);
+}
+
+#[test]
+fn we_can_compute_join_indexes() {
+    let left_keys = [3, 1, 2, 3, 5];
+    let right_keys = [3, 2, 3, 4, 1, 3];
+    let (left_indexes, right_indexes) = compute_join_indexes(&left_keys, &right_keys);
+    assert_eq!(left_indexes, vec![0, 0, 0, 1, 2, 3, 3, 3]);
+    assert_eq!(right_indexes, vec![0, 2, 5, 4, 1, 0, 2, 5]);
+}
+
+#[test]
+fn we_can_compute_join_indexes_without_matches() {
+    let (left_indexes, right_indexes) = compute_join_indexes(&[1, 2], &[3, 4]);
+    assert!(left_indexes.is_empty());
+    assert!(right_indexes.is_empty());
+    let (left_indexes, right_indexes) = compute_join_indexes(&[], &[3, 4]);
+    assert!(left_indexes.is_empty());
+    assert!(right_indexes.is_empty());
+}
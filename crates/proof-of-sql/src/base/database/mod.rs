@@ -2,14 +2,24 @@
 //! accessor traits and the `OwnedTable` type along with some utility functions to convert
 //! between Arrow and `OwnedTable`.
 mod accessor;
-pub use accessor::{CommitmentAccessor, DataAccessor, MetadataAccessor, SchemaAccessor};
+pub use accessor::{
+    BlindingFactorAccessor, CommitmentAccessor, DataAccessor, MetadataAccessor, SchemaAccessor,
+};
 
 mod column;
+pub(crate) use column::compare_indexes_by_columns_with_direction;
 pub use column::{Column, ColumnField, ColumnRef, ColumnType};
 
+mod nullable_column;
+pub use nullable_column::{
+    column_id_of_presence_column, presence_column_id, NullableColumn, NullableOwnedColumn,
+};
+
 mod column_operation;
+pub(crate) use column_operation::matches_like_pattern;
 pub use column_operation::{
-    try_add_subtract_column_types, try_divide_column_types, try_multiply_column_types,
+    try_add_subtract_column_types, try_case_column_types, try_cast_column_type,
+    try_divide_column_types, try_modulo_column_types, try_multiply_column_types,
 };
 
 mod column_operation_error;
@@ -31,6 +41,18 @@ mod record_batch_utility;
 #[cfg(feature = "arrow")]
 pub use record_batch_utility::ToArrow;
 
+#[cfg(feature = "arrow")]
+mod record_batch_accessor;
+#[cfg(feature = "arrow")]
+pub use record_batch_accessor::{RecordBatchAccessor, RecordBatchAccessorError};
+#[cfg(all(test, feature = "arrow"))]
+mod record_batch_accessor_test;
+
+mod snapshot_accessor;
+pub use snapshot_accessor::{SnapshotAccessor, SnapshotAccessorError};
+#[cfg(test)]
+mod snapshot_accessor_test;
+
 #[cfg(all(test, feature = "arrow", feature = "test"))]
 mod test_accessor_utility;
 #[cfg(all(test, feature = "arrow", feature = "test"))]
@@ -85,6 +107,11 @@ mod owned_table_test_accessor;
 pub use owned_table_test_accessor::OwnedTableTestAccessor;
 #[cfg(all(test, feature = "blitzar"))]
 mod owned_table_test_accessor_test;
+
+#[cfg(all(any(test, feature = "test"), feature = "std"))]
+mod hiding_table_test_accessor;
+#[cfg(all(any(test, feature = "test"), feature = "std"))]
+pub use hiding_table_test_accessor::HidingTableTestAccessor;
 /// Contains traits for scalar <-> i256 conversions
 #[cfg(feature = "arrow")]
 pub mod scalar_and_i256_conversions;
@@ -97,3 +124,11 @@ mod filter_util_test;
 pub(crate) mod group_by_util;
 #[cfg(test)]
 mod group_by_util_test;
+
+pub(crate) mod join_util;
+#[cfg(test)]
+mod join_util_test;
+
+pub(crate) mod union_util;
+#[cfg(test)]
+mod union_util_test;
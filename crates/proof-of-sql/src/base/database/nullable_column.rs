@@ -0,0 +1,194 @@
+use super::{Column, OwnedColumn};
+use crate::base::scalar::Scalar;
+use alloc::{format, vec::Vec};
+use proof_of_sql_parser::Identifier;
+
+/// The prefix of the identifier of a presence column.
+const PRESENCE_COLUMN_PREFIX: &str = "__";
+/// The suffix of the identifier of a presence column.
+const PRESENCE_COLUMN_SUFFIX: &str = "_presence__";
+
+/// Returns the identifier of the presence column of the column `column_id`.
+///
+/// A nullable column `c` is stored as two columns: `c` itself, which holds the value of each row
+/// (or the default value of its type for null rows), and the boolean column `__c_presence__`,
+/// which is `true` exactly for the non-null rows. Since the presence column is an ordinary column of the table,
+/// it is committed to alongside the values, so proofs cover the validity of every row.
+///
+/// Returns `None` if the identifier of the presence column would be too long, in which case `column_id` can't be nullable.
+#[must_use]
+pub fn presence_column_id(column_id: Identifier) -> Option<Identifier> {
+    let name = format!("{PRESENCE_COLUMN_PREFIX}{column_id}{PRESENCE_COLUMN_SUFFIX}");
+    // Identifiers are at most 64 bytes long
+    if name.len() > 64 {
+        return None;
+    }
+    Identifier::try_new(name).ok()
+}
+
+/// Returns the identifier of the column whose presence column is `presence_column_id`, if any.
+///
+/// This is the inverse of [`presence_column_id`].
+#[must_use]
+pub fn column_id_of_presence_column(presence_column_id: Identifier) -> Option<Identifier> {
+    presence_column_id
+        .as_str()
+        .strip_prefix(PRESENCE_COLUMN_PREFIX)?
+        .strip_suffix(PRESENCE_COLUMN_SUFFIX)
+        .and_then(|name| Identifier::try_new(name).ok())
+}
+
+/// A column together with its presence, i.e. which rows are not null.
+///
+/// The values of null rows are unspecified and must be ignored.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct NullableColumn<'a, S: Scalar> {
+    /// The values of the column
+    pub values: Column<'a, S>,
+    /// Which rows are not null. `None` if the column contains no nulls.
+    pub presence: Option<&'a [bool]>,
+}
+
+impl<'a, S: Scalar> NullableColumn<'a, S> {
+    /// Creates a new [`NullableColumn`].
+    ///
+    /// # Panics
+    /// Panics if `presence` and `values` have different lengths.
+    #[must_use]
+    pub fn new(values: Column<'a, S>, presence: Option<&'a [bool]>) -> Self {
+        if let Some(presence) = presence {
+            assert_eq!(values.len(), presence.len());
+        }
+        Self { values, presence }
+    }
+
+    /// Returns the length of the column.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the column has no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` if the row `index` is null.
+    #[must_use]
+    pub fn is_null(&self, index: usize) -> bool {
+        self.presence.is_some_and(|presence| !presence[index])
+    }
+}
+
+impl<'a, S: Scalar> From<Column<'a, S>> for NullableColumn<'a, S> {
+    fn from(values: Column<'a, S>) -> Self {
+        Self::new(values, None)
+    }
+}
+
+/// An owned column together with its presence, i.e. which rows are not null.
+///
+/// This is the owned counterpart of [`NullableColumn`]. The values of null rows are unspecified and must be ignored.
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct NullableOwnedColumn<S: Scalar> {
+    /// The values of the column
+    pub values: OwnedColumn<S>,
+    /// Which rows are not null. `None` if the column contains no nulls.
+    pub presence: Option<Vec<bool>>,
+}
+
+impl<S: Scalar> NullableOwnedColumn<S> {
+    /// Creates a new [`NullableOwnedColumn`].
+    ///
+    /// # Panics
+    /// Panics if `presence` and `values` have different lengths.
+    #[must_use]
+    pub fn new(values: OwnedColumn<S>, presence: Option<Vec<bool>>) -> Self {
+        if let Some(presence) = &presence {
+            assert_eq!(values.len(), presence.len());
+        }
+        Self { values, presence }
+    }
+
+    /// Returns the length of the column.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the column has no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` if the row `index` is null.
+    #[must_use]
+    pub fn is_null(&self, index: usize) -> bool {
+        self.presence
+            .as_ref()
+            .is_some_and(|presence| !presence[index])
+    }
+}
+
+impl<S: Scalar> From<OwnedColumn<S>> for NullableOwnedColumn<S> {
+    fn from(values: OwnedColumn<S>) -> Self {
+        Self::new(values, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base::scalar::test_scalar::TestScalar;
+    use alloc::{string::String, vec};
+
+    #[test]
+    fn we_can_convert_between_column_and_presence_column_ids() {
+        let column_id: Identifier = "a".parse().unwrap();
+        let presence_id = presence_column_id(column_id).unwrap();
+        assert_eq!(presence_id.as_str(), "__a_presence__");
+        assert_eq!(column_id_of_presence_column(presence_id), Some(column_id));
+        assert_eq!(column_id_of_presence_column(column_id), None);
+        assert_eq!(
+            column_id_of_presence_column("__presence__".parse().unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn we_cannot_get_a_presence_column_id_of_a_long_column_id() {
+        let column_id: Identifier = "a".repeat(64).parse().unwrap();
+        assert_eq!(presence_column_id(column_id), None);
+    }
+
+    #[test]
+    fn we_can_check_nulls_of_a_nullable_column() {
+        let column = NullableColumn::new(
+            Column::<TestScalar>::BigInt(&[1, 0, 3]),
+            Some(&[true, false, true]),
+        );
+        assert_eq!(column.len(), 3);
+        assert!(!column.is_empty());
+        assert!(!column.is_null(0));
+        assert!(column.is_null(1));
+        let column: NullableColumn<TestScalar> = Column::BigInt(&[1, 0, 3]).into();
+        assert!(!column.is_null(1));
+    }
+
+    #[test]
+    fn we_can_check_nulls_of_a_nullable_owned_column() {
+        let column = NullableOwnedColumn::new(
+            OwnedColumn::<TestScalar>::VarChar(vec!["a".into(), String::new()]),
+            Some(vec![true, false]),
+        );
+        assert_eq!(column.len(), 2);
+        assert!(!column.is_empty());
+        assert!(!column.is_null(0));
+        assert!(column.is_null(1));
+        let column: NullableOwnedColumn<TestScalar> = OwnedColumn::BigInt(vec![]).into();
+        assert!(column.is_empty());
+        assert_eq!(column.presence, None);
+    }
+}
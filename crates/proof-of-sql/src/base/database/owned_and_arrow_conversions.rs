@@ -8,6 +8,11 @@
 //! `Int128` <-> `Decimal128(38,0)`
 //! `Decimal75` <-> `S`
 //!
+//! An [`OwnedColumn`] can't hold nulls, so converting an array with nulls into one fails.
+//! Such arrays are converted into a [`NullableOwnedColumn`] instead, whose null values are replaced
+//! by the default value. Likewise, a field of a record batch that contains nulls is converted into a column
+//! together with its presence column, and vice versa. See [`presence_column_id`].
+//!
 //! Note: this converts `Int128` values to `Decimal128(38,0)`, which are backed by `i128`.
 //! This is because there is no `Int128` type in Arrow.
 //! This does not check that the values are less than 39 digits.
@@ -15,20 +20,26 @@
 use super::scalar_and_i256_conversions::convert_scalar_to_i256;
 use crate::base::{
     database::{
-        scalar_and_i256_conversions::convert_i256_to_scalar, OwnedColumn, OwnedTable,
-        OwnedTableError,
+        column_id_of_presence_column, presence_column_id,
+        scalar_and_i256_conversions::convert_i256_to_scalar, NullableOwnedColumn, OwnedColumn,
+        OwnedTable, OwnedTableError,
     },
     map::IndexMap,
     math::decimal::Precision,
     scalar::Scalar,
 };
-use alloc::sync::Arc;
+use alloc::{
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
 use arrow::{
     array::{
-        ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array, Int32Array,
-        Int64Array, Int8Array, StringArray, TimestampMicrosecondArray, TimestampMillisecondArray,
-        TimestampNanosecondArray, TimestampSecondArray,
+        make_array, Array, ArrayRef, BooleanArray, Decimal128Array, Decimal256Array, Int16Array,
+        Int32Array, Int64Array, Int8Array, StringArray, TimestampMicrosecondArray,
+        TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
     },
+    buffer::NullBuffer,
     datatypes::{i256, DataType, Schema, SchemaRef, TimeUnit as ArrowTimeUnit},
     error::ArrowError,
     record_batch::RecordBatch,
@@ -66,9 +77,17 @@ pub enum OwnedArrowConversionError {
         /// The underlying source error
         source: OwnedTableError,
     },
-    /// This error occurs when trying to convert from an Arrow array with nulls.
-    #[snafu(display("null values are not supported in OwnedColumn yet"))]
+    /// This error occurs when trying to convert from an Arrow array with nulls into an [`OwnedColumn`].
+    #[snafu(display(
+        "null values are not supported in OwnedColumn, use NullableOwnedColumn instead"
+    ))]
     NullNotSupportedYet,
+    /// This error occurs when a nullable field has a name that is too long to have a presence column.
+    #[snafu(display("nullable field name is too long: {name}"))]
+    NullableFieldNameTooLong {
+        /// The name of the field
+        name: String,
+    },
     /// Using `TimeError` to handle all time-related errors
     #[snafu(transparent)]
     TimestampConversionError {
@@ -116,17 +135,62 @@ impl<S: Scalar> From<OwnedColumn<S>> for ArrayRef {
     }
 }
 
+impl<S: Scalar> TryFrom<NullableOwnedColumn<S>> for ArrayRef {
+    type Error = ArrowError;
+    /// The presence of the column, if any, is converted into the validity of the array.
+    ///
+    /// # Panics
+    ///
+    /// Will panic in the same cases as the conversion of an [`OwnedColumn`] into an [`ArrayRef`].
+    fn try_from(value: NullableOwnedColumn<S>) -> Result<Self, Self::Error> {
+        let array = ArrayRef::from(value.values);
+        match value.presence {
+            Some(presence) => Ok(make_array(
+                array
+                    .into_data()
+                    .into_builder()
+                    .nulls(Some(NullBuffer::from(presence)))
+                    .build()?,
+            )),
+            None => Ok(array),
+        }
+    }
+}
+
 impl<S: Scalar> TryFrom<OwnedTable<S>> for RecordBatch {
     type Error = ArrowError;
+    /// Presence columns are converted into the validity of the column they belong to,
+    /// whose field is nullable. All other fields are not nullable.
     fn try_from(value: OwnedTable<S>) -> Result<Self, Self::Error> {
         if value.is_empty() {
             Ok(RecordBatch::new_empty(SchemaRef::new(Schema::empty())))
         } else {
-            RecordBatch::try_from_iter(
-                value
-                    .into_inner()
+            let mut columns = value.into_inner();
+            let presence_column_ids: Vec<_> = columns
+                .keys()
+                .filter(|&&id| {
+                    column_id_of_presence_column(id)
+                        .is_some_and(|column_id| columns.contains_key(&column_id))
+                })
+                .copied()
+                .collect();
+            let mut presences = IndexMap::default();
+            for id in presence_column_ids {
+                if let Some(OwnedColumn::Boolean(presence)) = columns.shift_remove(&id) {
+                    presences.insert(column_id_of_presence_column(id).unwrap(), presence);
+                }
+            }
+            RecordBatch::try_from_iter_with_nullable(
+                columns
                     .into_iter()
-                    .map(|(identifier, owned_column)| (identifier, ArrayRef::from(owned_column))),
+                    .map(|(identifier, owned_column)| {
+                        let presence = presences.swap_remove(&identifier);
+                        let is_nullable = presence.is_some();
+                        let array =
+                            ArrayRef::try_from(NullableOwnedColumn::new(owned_column, presence))?;
+                        Ok((identifier, array, is_nullable))
+                    })
+                    .collect::<Result<Vec<_>, ArrowError>>()?,
             )
         }
     }
@@ -151,161 +215,223 @@ impl<S: Scalar> TryFrom<&ArrayRef> for OwnedColumn<S> {
     /// - `Decimal256Array` when converting from `DataType::Decimal256` if precision is less than or equal to 75.
     /// - `StringArray` when converting from `DataType::Utf8`.
     fn try_from(value: &ArrayRef) -> Result<Self, Self::Error> {
-        match &value.data_type() {
-            // Arrow uses a bit-packed representation for booleans.
-            // Hence we need to unpack the bits to get the actual boolean values.
-            DataType::Boolean => Ok(Self::Boolean(
-                value
+        if value.null_count() != 0 {
+            return Err(OwnedArrowConversionError::NullNotSupportedYet);
+        }
+        owned_column_with_defaults(value)
+    }
+}
+
+impl<S: Scalar> TryFrom<ArrayRef> for NullableOwnedColumn<S> {
+    type Error = OwnedArrowConversionError;
+    fn try_from(value: ArrayRef) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+impl<S: Scalar> TryFrom<&ArrayRef> for NullableOwnedColumn<S> {
+    type Error = OwnedArrowConversionError;
+    /// The values of null elements are replaced by the default value of their type.
+    /// The presence is always returned, even if the array contains no nulls.
+    fn try_from(value: &ArrayRef) -> Result<Self, Self::Error> {
+        let presence = (0..value.len()).map(|i| value.is_valid(i)).collect();
+        Ok(Self::new(
+            owned_column_with_defaults(value)?,
+            Some(presence),
+        ))
+    }
+}
+
+/// Converts `value` into an [`OwnedColumn`], with the values of null elements replaced by the default value of their type.
+///
+/// # Panics
+///
+/// Will panic if downcasting fails for the following types:
+/// - `BooleanArray` when converting from `DataType::Boolean`.
+/// - `Int16Array` when converting from `DataType::Int16`.
+/// - `Int32Array` when converting from `DataType::Int32`.
+/// - `Int64Array` when converting from `DataType::Int64`.
+/// - `Decimal128Array` when converting from `DataType::Decimal128(38, 0)`.
+/// - `Decimal256Array` when converting from `DataType::Decimal256` if precision is less than or equal to 75.
+/// - `StringArray` when converting from `DataType::Utf8`.
+fn owned_column_with_defaults<S: Scalar>(
+    value: &ArrayRef,
+) -> Result<OwnedColumn<S>, OwnedArrowConversionError> {
+    match &value.data_type() {
+        // Arrow uses a bit-packed representation for booleans.
+        // Hence we need to unpack the bits to get the actual boolean values.
+        DataType::Boolean => Ok(OwnedColumn::Boolean(
+            value
+                .as_any()
+                .downcast_ref::<BooleanArray>()
+                .unwrap()
+                .iter()
+                .map(Option::unwrap_or_default)
+                .collect(),
+        )),
+        DataType::Int8 => Ok(OwnedColumn::TinyInt(
+            value
+                .as_any()
+                .downcast_ref::<Int8Array>()
+                .unwrap()
+                .iter()
+                .map(Option::unwrap_or_default)
+                .collect(),
+        )),
+        DataType::Int16 => Ok(OwnedColumn::SmallInt(
+            value
+                .as_any()
+                .downcast_ref::<Int16Array>()
+                .unwrap()
+                .iter()
+                .map(Option::unwrap_or_default)
+                .collect(),
+        )),
+        DataType::Int32 => Ok(OwnedColumn::Int(
+            value
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap()
+                .iter()
+                .map(Option::unwrap_or_default)
+                .collect(),
+        )),
+        DataType::Int64 => Ok(OwnedColumn::BigInt(
+            value
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap()
+                .iter()
+                .map(Option::unwrap_or_default)
+                .collect(),
+        )),
+        DataType::Decimal128(38, 0) => Ok(OwnedColumn::Int128(
+            value
+                .as_any()
+                .downcast_ref::<Decimal128Array>()
+                .unwrap()
+                .iter()
+                .map(Option::unwrap_or_default)
+                .collect(),
+        )),
+        DataType::Decimal256(precision, scale) if *precision <= 75 => Ok(OwnedColumn::Decimal75(
+            Precision::new(*precision).expect("precision is less than 76"),
+            *scale,
+            value
+                .as_any()
+                .downcast_ref::<Decimal256Array>()
+                .unwrap()
+                .iter()
+                .map(|v| convert_i256_to_scalar(&v.unwrap_or_default()))
+                .map(Option::unwrap)
+                .collect(),
+        )),
+        DataType::Utf8 => Ok(OwnedColumn::VarChar(
+            value
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap()
+                .iter()
+                .map(|s| s.unwrap_or_default().to_string())
+                .collect(),
+        )),
+        DataType::Timestamp(time_unit, timezone) => match time_unit {
+            ArrowTimeUnit::Second => {
+                let array = value
                     .as_any()
-                    .downcast_ref::<BooleanArray>()
-                    .unwrap()
+                    .downcast_ref::<TimestampSecondArray>()
+                    .expect("This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits");
+                let timestamps = array
                     .iter()
-                    .collect::<Option<Vec<bool>>>()
-                    .ok_or(OwnedArrowConversionError::NullNotSupportedYet)?,
-            )),
-            DataType::Int8 => Ok(Self::TinyInt(
-                value
-                    .as_any()
-                    .downcast_ref::<Int8Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Int16 => Ok(Self::SmallInt(
-                value
-                    .as_any()
-                    .downcast_ref::<Int16Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Int32 => Ok(Self::Int(
-                value
-                    .as_any()
-                    .downcast_ref::<Int32Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Int64 => Ok(Self::BigInt(
-                value
-                    .as_any()
-                    .downcast_ref::<Int64Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Decimal128(38, 0) => Ok(Self::Int128(
-                value
+                    .map(Option::unwrap_or_default)
+                    .collect::<Vec<i64>>();
+                Ok(OwnedColumn::TimestampTZ(
+                    PoSQLTimeUnit::Second,
+                    PoSQLTimeZone::try_from(timezone)?,
+                    timestamps,
+                ))
+            }
+            ArrowTimeUnit::Millisecond => {
+                let array = value
                     .as_any()
-                    .downcast_ref::<Decimal128Array>()
-                    .unwrap()
-                    .values()
-                    .to_vec(),
-            )),
-            DataType::Decimal256(precision, scale) if *precision <= 75 => Ok(Self::Decimal75(
-                Precision::new(*precision).expect("precision is less than 76"),
-                *scale,
-                value
+                    .downcast_ref::<TimestampMillisecondArray>()
+                    .expect("This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits");
+                let timestamps = array
+                    .iter()
+                    .map(Option::unwrap_or_default)
+                    .collect::<Vec<i64>>();
+                Ok(OwnedColumn::TimestampTZ(
+                    PoSQLTimeUnit::Millisecond,
+                    PoSQLTimeZone::try_from(timezone)?,
+                    timestamps,
+                ))
+            }
+            ArrowTimeUnit::Microsecond => {
+                let array = value
                     .as_any()
-                    .downcast_ref::<Decimal256Array>()
-                    .unwrap()
-                    .values()
+                    .downcast_ref::<TimestampMicrosecondArray>()
+                    .expect("This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits");
+                let timestamps = array
                     .iter()
-                    .map(convert_i256_to_scalar)
-                    .map(Option::unwrap)
-                    .collect(),
-            )),
-            DataType::Utf8 => Ok(Self::VarChar(
-                value
+                    .map(Option::unwrap_or_default)
+                    .collect::<Vec<i64>>();
+                Ok(OwnedColumn::TimestampTZ(
+                    PoSQLTimeUnit::Microsecond,
+                    PoSQLTimeZone::try_from(timezone)?,
+                    timestamps,
+                ))
+            }
+            ArrowTimeUnit::Nanosecond => {
+                let array = value
                     .as_any()
-                    .downcast_ref::<StringArray>()
-                    .unwrap()
+                    .downcast_ref::<TimestampNanosecondArray>()
+                    .expect("This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits");
+                let timestamps = array
                     .iter()
-                    .map(|s| s.unwrap().to_string())
-                    .collect(),
-            )),
-            DataType::Timestamp(time_unit, timezone) => match time_unit {
-                ArrowTimeUnit::Second => {
-                    let array = value
-                        .as_any()
-                        .downcast_ref::<TimestampSecondArray>()
-                        .expect(
-                            "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
-                        );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
-                    Ok(OwnedColumn::TimestampTZ(
-                        PoSQLTimeUnit::Second,
-                        PoSQLTimeZone::try_from(timezone)?,
-                        timestamps,
-                    ))
-                }
-                ArrowTimeUnit::Millisecond => {
-                    let array = value
-                        .as_any()
-                        .downcast_ref::<TimestampMillisecondArray>()
-                        .expect(
-                            "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
-                        );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
-                    Ok(OwnedColumn::TimestampTZ(
-                        PoSQLTimeUnit::Millisecond,
-                        PoSQLTimeZone::try_from(timezone)?,
-                        timestamps,
-                    ))
-                }
-                ArrowTimeUnit::Microsecond => {
-                    let array = value
-                        .as_any()
-                        .downcast_ref::<TimestampMicrosecondArray>()
-                        .expect(
-                            "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
-                        );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
-                    Ok(OwnedColumn::TimestampTZ(
-                        PoSQLTimeUnit::Microsecond,
-                        PoSQLTimeZone::try_from(timezone)?,
-                        timestamps,
-                    ))
-                }
-                ArrowTimeUnit::Nanosecond => {
-                    let array = value
-                        .as_any()
-                        .downcast_ref::<TimestampNanosecondArray>()
-                        .expect(
-                            "This cannot fail, all Arrow TimeUnits are mapped to PoSQL TimeUnits",
-                        );
-                    let timestamps = array.values().iter().copied().collect::<Vec<i64>>();
-                    Ok(OwnedColumn::TimestampTZ(
-                        PoSQLTimeUnit::Nanosecond,
-                        PoSQLTimeZone::try_from(timezone)?,
-                        timestamps,
-                    ))
-                }
-            },
-            &data_type => Err(OwnedArrowConversionError::UnsupportedType {
-                datatype: data_type.clone(),
-            }),
-        }
+                    .map(Option::unwrap_or_default)
+                    .collect::<Vec<i64>>();
+                Ok(OwnedColumn::TimestampTZ(
+                    PoSQLTimeUnit::Nanosecond,
+                    PoSQLTimeZone::try_from(timezone)?,
+                    timestamps,
+                ))
+            }
+        },
+        &data_type => Err(OwnedArrowConversionError::UnsupportedType {
+            datatype: data_type.clone(),
+        }),
     }
 }
 
 impl<S: Scalar> TryFrom<RecordBatch> for OwnedTable<S> {
     type Error = OwnedArrowConversionError;
+    /// Every field that contains nulls is converted into a column, with null values replaced by the default value,
+    /// and its presence column. See [`presence_column_id`].
+    ///
+    /// Fields without nulls, including nullable ones, are converted into a single column.
     fn try_from(value: RecordBatch) -> Result<Self, Self::Error> {
-        let num_columns = value.num_columns();
-        let table: Result<IndexMap<_, _>, Self::Error> = value
-            .schema()
-            .fields()
-            .iter()
-            .zip(value.columns())
-            .map(|(field, array_ref)| {
-                let owned_column = OwnedColumn::try_from(array_ref)?;
-                let identifier = Identifier::try_new(field.name())?; //This may always succeed.
-                Ok((identifier, owned_column))
-            })
-            .collect();
-        let owned_table = Self::try_new(table?)?;
+        let mut num_columns = 0;
+        let mut table = IndexMap::default();
+        for (field, array_ref) in value.schema().fields().iter().zip(value.columns()) {
+            let identifier = Identifier::try_new(field.name())?; //This may always succeed.
+            if array_ref.null_count() == 0 {
+                table.insert(identifier, OwnedColumn::try_from(array_ref)?);
+                num_columns += 1;
+            } else {
+                let presence_id = presence_column_id(identifier).ok_or_else(|| {
+                    OwnedArrowConversionError::NullableFieldNameTooLong {
+                        name: field.name().clone(),
+                    }
+                })?;
+                let column = NullableOwnedColumn::try_from(array_ref)?;
+                table.insert(identifier, column.values);
+                table.insert(
+                    presence_id,
+                    OwnedColumn::Boolean(column.presence.expect("the presence is always returned")),
+                );
+                num_columns += 2;
+            }
+        }
+        let owned_table = Self::try_new(table)?;
         if num_columns == owned_table.num_columns() {
             Ok(owned_table)
         } else {
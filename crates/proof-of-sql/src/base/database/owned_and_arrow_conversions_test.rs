@@ -1,4 +1,4 @@
-use super::{OwnedColumn, OwnedTable};
+use super::{NullableOwnedColumn, OwnedColumn, OwnedTable};
 use crate::{
     base::{
         database::{owned_table_utility::*, OwnedArrowConversionError},
@@ -7,12 +7,13 @@ use crate::{
     },
     record_batch,
 };
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec, vec::Vec};
 use arrow::{
     array::{ArrayRef, BooleanArray, Decimal128Array, Float32Array, Int64Array, StringArray},
     datatypes::Schema,
     record_batch::RecordBatch,
 };
+use proof_of_sql_parser::Identifier;
 
 fn we_can_convert_between_owned_column_and_array_ref_impl(
     owned_column: &OwnedColumn<Curve25519Scalar>,
@@ -129,6 +130,97 @@ fn we_can_convert_between_owned_table_and_record_batch() {
     );
 }
 
+#[test]
+fn we_can_convert_between_owned_table_and_record_batch_with_nulls() {
+    let owned_table = owned_table::<Curve25519Scalar>([
+        bigint("a", [1, 0, 3]),
+        boolean("__a_presence__", [true, false, true]),
+        varchar("b", ["x", "y", "z"]),
+    ]);
+    let record_batch = RecordBatch::try_from_iter_with_nullable([
+        (
+            "a",
+            Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef,
+            true,
+        ),
+        (
+            "b",
+            Arc::new(StringArray::from(vec!["x", "y", "z"])) as ArrayRef,
+            false,
+        ),
+    ])
+    .unwrap();
+    let it_to_rb = RecordBatch::try_from(owned_table.clone()).unwrap();
+    let rb_to_it = OwnedTable::try_from(record_batch.clone()).unwrap();
+    assert_eq!(it_to_rb, record_batch);
+    assert_eq!(
+        rb_to_it
+            .column_names()
+            .map(Identifier::as_str)
+            .collect::<Vec<_>>(),
+        ["a", "__a_presence__", "b"]
+    );
+    assert_eq!(rb_to_it, owned_table);
+}
+
+#[test]
+fn we_can_convert_a_record_batch_with_a_nullable_field_without_nulls() {
+    let record_batch = RecordBatch::try_from_iter_with_nullable([(
+        "a",
+        Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef,
+        true,
+    )])
+    .unwrap();
+    assert_eq!(
+        OwnedTable::<Curve25519Scalar>::try_from(record_batch).unwrap(),
+        owned_table([bigint("a", [1, 2, 3])])
+    );
+}
+
+#[test]
+fn we_cannot_convert_an_array_ref_with_nulls_to_an_owned_column() {
+    let array_ref: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None]));
+    assert!(matches!(
+        OwnedColumn::<Curve25519Scalar>::try_from(array_ref),
+        Err(OwnedArrowConversionError::NullNotSupportedYet)
+    ));
+    let array_ref: ArrayRef = Arc::new(StringArray::from(vec![None, Some("a")]));
+    assert!(matches!(
+        OwnedColumn::<Curve25519Scalar>::try_from(array_ref),
+        Err(OwnedArrowConversionError::NullNotSupportedYet)
+    ));
+}
+
+#[test]
+fn we_can_convert_between_nullable_owned_column_and_array_ref() {
+    let array_ref: ArrayRef = Arc::new(StringArray::from(vec![Some("a"), None, Some("c")]));
+    let nullable_column = NullableOwnedColumn::<Curve25519Scalar>::new(
+        OwnedColumn::VarChar(vec!["a".into(), String::new(), "c".into()]),
+        Some(vec![true, false, true]),
+    );
+    assert_eq!(
+        NullableOwnedColumn::try_from(array_ref.clone()).unwrap(),
+        nullable_column
+    );
+    assert_eq!(&ArrayRef::try_from(nullable_column).unwrap(), &array_ref);
+
+    let array_ref: ArrayRef = Arc::new(BooleanArray::from(vec![true, false]));
+    assert_eq!(
+        NullableOwnedColumn::<Curve25519Scalar>::try_from(&array_ref).unwrap(),
+        NullableOwnedColumn::new(
+            OwnedColumn::Boolean(vec![true, false]),
+            Some(vec![true, true])
+        )
+    );
+    assert_eq!(
+        &ArrayRef::try_from(NullableOwnedColumn::<Curve25519Scalar>::from(
+            OwnedColumn::Boolean(vec![true, false])
+        ))
+        .unwrap(),
+        &array_ref
+    );
+}
+
 #[test]
 fn we_cannot_convert_a_record_batch_if_it_has_repeated_column_names() {
     let record_batch = record_batch!(
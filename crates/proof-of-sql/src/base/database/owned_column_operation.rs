@@ -2,21 +2,28 @@ use super::{ColumnOperationError, ColumnOperationResult};
 use crate::base::{
     database::{
         column_operation::{
-            eq_decimal_columns, ge_decimal_columns, le_decimal_columns, slice_and, slice_eq,
-            slice_eq_with_casting, slice_ge, slice_ge_with_casting, slice_le,
-            slice_le_with_casting, slice_not, slice_or, try_add_decimal_columns, try_add_slices,
-            try_add_slices_with_casting, try_divide_decimal_columns, try_divide_slices,
-            try_divide_slices_left_upcast, try_divide_slices_right_upcast,
+            decimal_to_string, eq_decimal_columns, ge_decimal_columns, le_decimal_columns,
+            modulo_slices, modulo_slices_left_upcast, modulo_slices_right_upcast, parse_decimal,
+            parse_timestamp, rescale_bigint, slice_and, slice_eq, slice_eq_with_casting, slice_ge,
+            slice_ge_with_casting, slice_le, slice_le_with_casting, slice_like, slice_not,
+            slice_or, slice_select, timestamp_to_string, try_add_decimal_columns, try_add_slices,
+            try_add_slices_with_casting, try_bigint_to_scalar, try_divide_decimal_columns,
+            try_divide_slices, try_divide_slices_left_upcast, try_divide_slices_right_upcast,
             try_multiply_decimal_columns, try_multiply_slices, try_multiply_slices_with_casting,
             try_subtract_decimal_columns, try_subtract_slices, try_subtract_slices_left_upcast,
             try_subtract_slices_right_upcast,
         },
-        OwnedColumn,
+        try_case_column_types, try_cast_column_type, ColumnType, OwnedColumn,
     },
+    math::decimal::scale_scalar,
     scalar::Scalar,
 };
-use core::ops::{Add, Div, Mul, Sub};
-use proof_of_sql_parser::intermediate_ast::{BinaryOperator, UnaryOperator};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::{Add, Div, Mul, Rem, Sub};
+use proof_of_sql_parser::intermediate_ast::{BinaryOperator, DataType, UnaryOperator};
 
 impl<S: Scalar> OwnedColumn<S> {
     /// Element-wise NOT operation for a column
@@ -66,6 +73,24 @@ impl<S: Scalar> OwnedColumn<S> {
         }
     }
 
+    /// Element-wise `LIKE` check of a string column against a column of patterns
+    pub fn element_wise_like(&self, rhs: &Self) -> ColumnOperationResult<Self> {
+        if self.len() != rhs.len() {
+            return Err(ColumnOperationError::DifferentColumnLength {
+                len_a: self.len(),
+                len_b: rhs.len(),
+            });
+        }
+        match (self, rhs) {
+            (Self::VarChar(lhs), Self::VarChar(rhs)) => Ok(Self::Boolean(slice_like(lhs, rhs))),
+            _ => Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+                operator: BinaryOperator::Like,
+                left_type: self.column_type(),
+                right_type: rhs.column_type(),
+            }),
+        }
+    }
+
     /// Element-wise equality check for two columns
     pub fn element_wise_eq(&self, rhs: &Self) -> ColumnOperationResult<Self> {
         if self.len() != rhs.len() {
@@ -606,6 +631,170 @@ impl<S: Scalar> OwnedColumn<S> {
             }),
         }
     }
+
+    /// Element-wise choice between two columns, i.e. `CASE WHEN selection THEN self ELSE rhs END`
+    ///
+    /// The result has the type given by [`try_case_column_types`].
+    #[allow(clippy::missing_panics_doc)]
+    pub fn element_wise_select(
+        &self,
+        selection: &[bool],
+        rhs: &Self,
+    ) -> ColumnOperationResult<Self> {
+        if self.len() != rhs.len() {
+            return Err(ColumnOperationError::DifferentColumnLength {
+                len_a: self.len(),
+                len_b: rhs.len(),
+            });
+        }
+        if selection.len() != self.len() {
+            return Err(ColumnOperationError::DifferentColumnLength {
+                len_a: selection.len(),
+                len_b: self.len(),
+            });
+        }
+        let column_type = try_case_column_types(self.column_type(), rhs.column_type())?;
+        if let (Self::VarChar(lhs), Self::VarChar(rhs)) = (self, rhs) {
+            return Ok(Self::VarChar(
+                slice_select(selection, lhs, rhs).cloned().collect(),
+            ));
+        }
+        // Every other type can be chosen as scalars once both sides have the same scale
+        let scale = column_type.scale().unwrap_or(0);
+        let lhs = self.to_scalars_with_scaling(scale - self.column_type().scale().unwrap_or(0))?;
+        let rhs = rhs.to_scalars_with_scaling(scale - rhs.column_type().scale().unwrap_or(0))?;
+        let values: Vec<S> = slice_select(selection, &lhs, &rhs).copied().collect();
+        Ok(Self::try_from_scalars(&values, column_type)
+            .expect("values of either branch fit into the common type"))
+    }
+
+    /// Converts the column to another type, i.e. `CAST(self AS data_type)`
+    ///
+    /// The result has the type given by [`try_cast_column_type`]. Numbers and timestamps are
+    /// rescaled to the scale of the result, rounding half away from zero, and must fit into it.
+    /// Strings are converted the way literals are written.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn try_cast(&self, data_type: DataType) -> ColumnOperationResult<Self> {
+        let from_type = self.column_type();
+        let to_type = try_cast_column_type(from_type, data_type)?;
+        let invalid_value =
+            |value: String| ColumnOperationError::CastInvalidValue { value, to_type };
+        if from_type == to_type {
+            return Ok(self.clone());
+        }
+        let scalars: Vec<S> = match (self, to_type) {
+            (_, ColumnType::VarChar) => return self.try_to_strings().map(Self::VarChar),
+            (Self::VarChar(values), ColumnType::Boolean) => {
+                return values
+                    .iter()
+                    .map(|value| match value.trim().to_lowercase().as_str() {
+                        "true" => Ok(true),
+                        "false" => Ok(false),
+                        _ => Err(invalid_value(value.clone())),
+                    })
+                    .collect::<ColumnOperationResult<_>>()
+                    .map(Self::Boolean);
+            }
+            (Self::VarChar(values), _) => {
+                let scale = to_type
+                    .scale()
+                    .expect("numeric and timestamp types have scale");
+                values
+                    .iter()
+                    .map(|value| {
+                        let parsed = if let ColumnType::TimestampTZ(_, _) = to_type {
+                            parse_timestamp(value, scale)
+                        } else {
+                            parse_decimal(value, scale)
+                        };
+                        parsed
+                            .and_then(|parsed| try_bigint_to_scalar(parsed, to_type))
+                            .ok_or_else(|| invalid_value(value.clone()))
+                    })
+                    .collect::<ColumnOperationResult<_>>()?
+            }
+            _ => {
+                let from_scale = from_type
+                    .scale()
+                    .expect("numeric and timestamp types have scale");
+                let scale = to_type
+                    .scale()
+                    .expect("numeric and timestamp types have scale")
+                    - from_scale;
+                self.to_scalars_with_scaling(0)?
+                    .into_iter()
+                    .map(|value| {
+                        let value = value.into();
+                        try_bigint_to_scalar(rescale_bigint(&value, scale), to_type)
+                            .ok_or_else(|| invalid_value(decimal_to_string(&value, from_scale)))
+                    })
+                    .collect::<ColumnOperationResult<_>>()?
+            }
+        };
+        Ok(Self::try_from_scalars(&scalars, to_type).expect("the values fit into the type"))
+    }
+
+    /// Formats the values of the column the way they are written as literals
+    ///
+    /// # Panics
+    /// Panics if a timestamp column has no scale.
+    fn try_to_strings(&self) -> ColumnOperationResult<Vec<String>> {
+        Ok(match self {
+            Self::Boolean(col) => col.iter().map(ToString::to_string).collect(),
+            Self::TinyInt(col) => col.iter().map(ToString::to_string).collect(),
+            Self::SmallInt(col) => col.iter().map(ToString::to_string).collect(),
+            Self::Int(col) => col.iter().map(ToString::to_string).collect(),
+            Self::BigInt(col) => col.iter().map(ToString::to_string).collect(),
+            Self::Int128(col) => col.iter().map(ToString::to_string).collect(),
+            Self::Decimal75(_, scale, col) => col
+                .iter()
+                .map(|&value| decimal_to_string(&value.into(), *scale))
+                .collect(),
+            Self::Scalar(col) => col
+                .iter()
+                .map(|&value| decimal_to_string(&value.into(), 0))
+                .collect(),
+            Self::TimestampTZ(_, time_zone, col) => {
+                let scale = self
+                    .column_type()
+                    .scale()
+                    .expect("timestamp types have scale");
+                col.iter()
+                    .map(|&value| {
+                        timestamp_to_string(value, scale, *time_zone).ok_or_else(|| {
+                            ColumnOperationError::CastInvalidValue {
+                                value: value.to_string(),
+                                to_type: ColumnType::VarChar,
+                            }
+                        })
+                    })
+                    .collect::<ColumnOperationResult<_>>()?
+            }
+            Self::VarChar(col) => col.clone(),
+        })
+    }
+
+    /// Converts the column to scalars that are scaled up by `10^scale`
+    fn to_scalars_with_scaling(&self, scale: i8) -> ColumnOperationResult<Vec<S>> {
+        let scale_factor = scale_scalar(S::ONE, scale)?;
+        Ok(match self {
+            Self::Boolean(col) => col.iter().map(|&b| S::from(b) * scale_factor).collect(),
+            Self::TinyInt(col) => col.iter().map(|&i| S::from(i) * scale_factor).collect(),
+            Self::SmallInt(col) => col.iter().map(|&i| S::from(i) * scale_factor).collect(),
+            Self::Int(col) => col.iter().map(|&i| S::from(i) * scale_factor).collect(),
+            Self::BigInt(col) | Self::TimestampTZ(_, _, col) => {
+                col.iter().map(|&i| S::from(i) * scale_factor).collect()
+            }
+            Self::Int128(col) => col.iter().map(|&i| S::from(i) * scale_factor).collect(),
+            Self::Decimal75(_, _, col) | Self::Scalar(col) => {
+                col.iter().map(|&s| s * scale_factor).collect()
+            }
+            Self::VarChar(col) => col
+                .iter()
+                .map(|s| S::from(s.as_str()) * scale_factor)
+                .collect(),
+        })
+    }
 }
 
 impl<S: Scalar> Add for OwnedColumn<S> {
@@ -1392,10 +1581,98 @@ impl<S: Scalar> Div for OwnedColumn<S> {
     }
 }
 
+impl<S: Scalar> Rem for OwnedColumn<S> {
+    type Output = ColumnOperationResult<Self>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        if self.len() != rhs.len() {
+            return Err(ColumnOperationError::DifferentColumnLength {
+                len_a: self.len(),
+                len_b: rhs.len(),
+            });
+        }
+        match (&self, &rhs) {
+            (Self::TinyInt(lhs), Self::TinyInt(rhs)) => Ok(Self::TinyInt(modulo_slices(lhs, rhs))),
+            (Self::TinyInt(lhs), Self::SmallInt(rhs)) => {
+                Ok(Self::SmallInt(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::TinyInt(lhs), Self::Int(rhs)) => {
+                Ok(Self::Int(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::TinyInt(lhs), Self::BigInt(rhs)) => {
+                Ok(Self::BigInt(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::TinyInt(lhs), Self::Int128(rhs)) => {
+                Ok(Self::Int128(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::SmallInt(lhs), Self::TinyInt(rhs)) => {
+                Ok(Self::SmallInt(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::SmallInt(lhs), Self::SmallInt(rhs)) => {
+                Ok(Self::SmallInt(modulo_slices(lhs, rhs)))
+            }
+            (Self::SmallInt(lhs), Self::Int(rhs)) => {
+                Ok(Self::Int(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::SmallInt(lhs), Self::BigInt(rhs)) => {
+                Ok(Self::BigInt(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::SmallInt(lhs), Self::Int128(rhs)) => {
+                Ok(Self::Int128(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::Int(lhs), Self::TinyInt(rhs)) => {
+                Ok(Self::Int(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::Int(lhs), Self::SmallInt(rhs)) => {
+                Ok(Self::Int(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::Int(lhs), Self::Int(rhs)) => Ok(Self::Int(modulo_slices(lhs, rhs))),
+            (Self::Int(lhs), Self::BigInt(rhs)) => {
+                Ok(Self::BigInt(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::Int(lhs), Self::Int128(rhs)) => {
+                Ok(Self::Int128(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::BigInt(lhs), Self::TinyInt(rhs)) => {
+                Ok(Self::BigInt(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::BigInt(lhs), Self::SmallInt(rhs)) => {
+                Ok(Self::BigInt(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::BigInt(lhs), Self::Int(rhs)) => {
+                Ok(Self::BigInt(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::BigInt(lhs), Self::BigInt(rhs)) => Ok(Self::BigInt(modulo_slices(lhs, rhs))),
+            (Self::BigInt(lhs), Self::Int128(rhs)) => {
+                Ok(Self::Int128(modulo_slices_left_upcast(lhs, rhs)))
+            }
+            (Self::Int128(lhs), Self::TinyInt(rhs)) => {
+                Ok(Self::Int128(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::Int128(lhs), Self::SmallInt(rhs)) => {
+                Ok(Self::Int128(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::Int128(lhs), Self::Int(rhs)) => {
+                Ok(Self::Int128(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::Int128(lhs), Self::BigInt(rhs)) => {
+                Ok(Self::Int128(modulo_slices_right_upcast(lhs, rhs)))
+            }
+            (Self::Int128(lhs), Self::Int128(rhs)) => Ok(Self::Int128(modulo_slices(lhs, rhs))),
+            _ => Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+                operator: BinaryOperator::Modulo,
+                left_type: self.column_type(),
+                right_type: rhs.column_type(),
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::base::{math::decimal::Precision, scalar::Curve25519Scalar};
+    use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
 
     #[test]
     fn we_cannot_do_binary_operation_on_columns_with_different_lengths() {
@@ -1533,6 +1810,122 @@ mod test {
         );
     }
 
+    #[test]
+    fn we_can_do_like_operation() {
+        let lhs = OwnedColumn::<Curve25519Scalar>::VarChar(
+            ["Space", "Tea", "Spice", "spa"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        );
+        let rhs = OwnedColumn::<Curve25519Scalar>::VarChar(vec!["Sp%".to_string(); 4]);
+        let result = lhs.element_wise_like(&rhs);
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::<Curve25519Scalar>::Boolean(vec![
+                true, false, true, false
+            ]))
+        );
+
+        let rhs = OwnedColumn::<Curve25519Scalar>::BigInt(vec![1, 2, 3, 4]);
+        let result = lhs.element_wise_like(&rhs);
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::BinaryOperationInvalidColumnType { .. })
+        ));
+
+        let rhs = OwnedColumn::<Curve25519Scalar>::VarChar(vec!["Sp%".to_string(); 3]);
+        let result = lhs.element_wise_like(&rhs);
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::DifferentColumnLength { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_do_select_operation() {
+        let selection = [true, false, false, true];
+
+        // Equal types
+        let lhs = OwnedColumn::<Curve25519Scalar>::VarChar(
+            ["a", "b", "c", "d"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        );
+        let rhs = OwnedColumn::<Curve25519Scalar>::VarChar(
+            ["w", "x", "y", "z"]
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        );
+        let result = lhs.element_wise_select(&selection, &rhs);
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::<Curve25519Scalar>::VarChar(
+                ["a", "x", "y", "d"]
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            ))
+        );
+
+        let lhs = OwnedColumn::<Curve25519Scalar>::Boolean(vec![true; 4]);
+        let rhs = OwnedColumn::<Curve25519Scalar>::Boolean(vec![false; 4]);
+        let result = lhs.element_wise_select(&selection, &rhs);
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::<Curve25519Scalar>::Boolean(selection.to_vec()))
+        );
+
+        // Integers of different widths
+        let lhs = OwnedColumn::<Curve25519Scalar>::TinyInt(vec![1, 2, 3, -4]);
+        let rhs = OwnedColumn::<Curve25519Scalar>::BigInt(vec![0; 4]);
+        let result = lhs.element_wise_select(&selection, &rhs);
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::<Curve25519Scalar>::BigInt(vec![1, 0, 0, -4]))
+        );
+
+        // Integers and decimals are scaled to the larger scale
+        let lhs = OwnedColumn::<Curve25519Scalar>::Decimal75(
+            Precision::new(5).unwrap(),
+            2,
+            [125, 250, 375, -500].map(Curve25519Scalar::from).to_vec(),
+        );
+        let rhs = OwnedColumn::<Curve25519Scalar>::Int(vec![1, 2, 3, 4]);
+        let result = lhs.element_wise_select(&selection, &rhs);
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::<Curve25519Scalar>::Decimal75(
+                Precision::new(12).unwrap(),
+                2,
+                [125, 200, 300, -500].map(Curve25519Scalar::from).to_vec()
+            ))
+        );
+
+        // Incompatible types
+        let rhs = OwnedColumn::<Curve25519Scalar>::Boolean(vec![false; 4]);
+        let result = lhs.element_wise_select(&selection, &rhs);
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::CaseInvalidColumnTypes { .. })
+        ));
+
+        // Different lengths
+        let rhs = OwnedColumn::<Curve25519Scalar>::Int(vec![1, 2, 3]);
+        let result = lhs.element_wise_select(&selection, &rhs);
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::DifferentColumnLength { .. })
+        ));
+        let result = lhs.element_wise_select(&selection[1..], &lhs);
+        assert!(matches!(
+            result,
+            Err(ColumnOperationError::DifferentColumnLength { .. })
+        ));
+    }
+
     #[test]
     fn we_can_do_eq_operation() {
         // Integers
@@ -2241,6 +2634,46 @@ mod test {
         );
     }
 
+    #[test]
+    fn we_can_try_modulo_integer_columns() {
+        // The remainder has the sign of the dividend
+        let lhs = OwnedColumn::<Curve25519Scalar>::SmallInt(vec![7_i16, -7, 7, -7]);
+        let rhs = OwnedColumn::<Curve25519Scalar>::SmallInt(vec![3_i16, 3, -3, -3]);
+        let result = lhs % rhs;
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::<Curve25519Scalar>::SmallInt(vec![
+                1_i16, -1, 1, -1
+            ]))
+        );
+
+        // lhs and rhs have different precisions
+        let lhs = OwnedColumn::<Curve25519Scalar>::BigInt(vec![10_i64, -11, 12]);
+        let rhs = OwnedColumn::<Curve25519Scalar>::TinyInt(vec![4_i8, 4, 5]);
+        let result = lhs % rhs;
+        assert_eq!(
+            result,
+            Ok(OwnedColumn::<Curve25519Scalar>::BigInt(vec![2_i64, -3, 2]))
+        );
+
+        let lhs = OwnedColumn::<Curve25519Scalar>::Int(vec![3_i32, 2, 3]);
+        let rhs = OwnedColumn::<Curve25519Scalar>::Int(vec![1_i32, 0, 5]);
+        assert_eq!(
+            lhs % rhs,
+            Ok(OwnedColumn::<Curve25519Scalar>::Int(vec![0_i32, 2, 3]))
+        );
+
+        let lhs = OwnedColumn::<Curve25519Scalar>::Int(vec![3_i32]);
+        let rhs = decimal_column(10, 2, &[100]);
+        assert!(matches!(
+            lhs % rhs,
+            Err(ColumnOperationError::BinaryOperationInvalidColumnType {
+                operator: BinaryOperator::Modulo,
+                ..
+            })
+        ));
+    }
+
     #[test]
     fn we_can_try_divide_decimal_columns() {
         // lhs and rhs are both decimals
@@ -2301,4 +2734,171 @@ mod test {
             )
         );
     }
+
+    fn decimal_column(precision: u8, scale: i8, values: &[i64]) -> OwnedColumn<Curve25519Scalar> {
+        OwnedColumn::Decimal75(
+            Precision::new(precision).unwrap(),
+            scale,
+            values.iter().map(Curve25519Scalar::from).collect(),
+        )
+    }
+
+    #[test]
+    fn we_can_cast_numeric_columns() {
+        let column = OwnedColumn::<Curve25519Scalar>::Int(vec![1, -2, 300]);
+        assert_eq!(
+            column.try_cast(DataType::BigInt).unwrap(),
+            OwnedColumn::BigInt(vec![1, -2, 300])
+        );
+        assert_eq!(
+            column.try_cast(DataType::SmallInt).unwrap(),
+            OwnedColumn::SmallInt(vec![1, -2, 300])
+        );
+        assert_eq!(
+            OwnedColumn::<Curve25519Scalar>::BigInt(vec![1, -2, 300])
+                .try_cast(DataType::Decimal {
+                    precision: 20,
+                    scale: 2
+                })
+                .unwrap(),
+            decimal_column(20, 2, &[100, -200, 30_000])
+        );
+
+        // Lowering the scale rounds half away from zero
+        let column = decimal_column(5, 2, &[125, -125, -124, 150]);
+        assert_eq!(
+            column
+                .try_cast(DataType::Decimal {
+                    precision: 4,
+                    scale: 1
+                })
+                .unwrap(),
+            decimal_column(4, 1, &[13, -13, -12, 15])
+        );
+        assert_eq!(
+            column.try_cast(DataType::Int).unwrap(),
+            OwnedColumn::Int(vec![1, -1, -1, 2])
+        );
+    }
+
+    #[test]
+    fn we_cannot_cast_values_that_do_not_fit_into_the_type() {
+        let column = OwnedColumn::<Curve25519Scalar>::BigInt(vec![1, 1000]);
+        assert!(matches!(
+            column.try_cast(DataType::TinyInt),
+            Err(ColumnOperationError::CastInvalidValue { .. })
+        ));
+        assert!(matches!(
+            column.try_cast(DataType::Decimal {
+                precision: 4,
+                scale: 2
+            }),
+            Err(ColumnOperationError::CastInvalidValue { .. })
+        ));
+        assert!(matches!(
+            OwnedColumn::<Curve25519Scalar>::Boolean(vec![true]).try_cast(DataType::BigInt),
+            Err(ColumnOperationError::CastInvalidColumnType { .. })
+        ));
+    }
+
+    #[test]
+    fn we_can_cast_timestamp_columns() {
+        let column = OwnedColumn::<Curve25519Scalar>::TimestampTZ(
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            vec![1, -2],
+        );
+        assert_eq!(
+            column
+                .try_cast(DataType::Timestamp(PoSQLTimeUnit::Millisecond))
+                .unwrap(),
+            OwnedColumn::TimestampTZ(
+                PoSQLTimeUnit::Millisecond,
+                PoSQLTimeZone::Utc,
+                vec![1000, -2000]
+            )
+        );
+        let column = OwnedColumn::<Curve25519Scalar>::TimestampTZ(
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            vec![1499, 1500, -1500],
+        );
+        assert_eq!(
+            column
+                .try_cast(DataType::Timestamp(PoSQLTimeUnit::Second))
+                .unwrap(),
+            OwnedColumn::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, vec![1, 2, -2])
+        );
+    }
+
+    #[test]
+    fn we_can_cast_columns_to_and_from_varchar() {
+        let strings = |values: &[&str]| values.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            OwnedColumn::<Curve25519Scalar>::BigInt(vec![1, -2])
+                .try_cast(DataType::VarChar)
+                .unwrap(),
+            OwnedColumn::VarChar(strings(&["1", "-2"]))
+        );
+        assert_eq!(
+            decimal_column(5, 2, &[125, -5])
+                .try_cast(DataType::VarChar)
+                .unwrap(),
+            OwnedColumn::VarChar(strings(&["1.25", "-0.05"]))
+        );
+        assert_eq!(
+            OwnedColumn::<Curve25519Scalar>::Boolean(vec![true, false])
+                .try_cast(DataType::VarChar)
+                .unwrap(),
+            OwnedColumn::VarChar(strings(&["true", "false"]))
+        );
+        let timestamps = OwnedColumn::<Curve25519Scalar>::TimestampTZ(
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            vec![0, 1_500],
+        );
+        let timestamp_strings = timestamps.try_cast(DataType::VarChar).unwrap();
+        assert_eq!(
+            timestamp_strings,
+            OwnedColumn::VarChar(strings(&[
+                "1970-01-01T00:00:00Z",
+                "1970-01-01T00:00:01.500Z"
+            ]))
+        );
+        assert_eq!(
+            timestamp_strings
+                .try_cast(DataType::Timestamp(PoSQLTimeUnit::Millisecond))
+                .unwrap(),
+            timestamps
+        );
+
+        let column = OwnedColumn::<Curve25519Scalar>::VarChar(strings(&["1.25", "-3"]));
+        assert_eq!(
+            column
+                .try_cast(DataType::Decimal {
+                    precision: 5,
+                    scale: 2
+                })
+                .unwrap(),
+            decimal_column(5, 2, &[125, -300])
+        );
+        assert_eq!(
+            column.try_cast(DataType::BigInt).unwrap(),
+            OwnedColumn::BigInt(vec![1, -3])
+        );
+        assert!(matches!(
+            OwnedColumn::<Curve25519Scalar>::VarChar(strings(&["abc"])).try_cast(DataType::BigInt),
+            Err(ColumnOperationError::CastInvalidValue { .. })
+        ));
+        assert_eq!(
+            OwnedColumn::<Curve25519Scalar>::VarChar(strings(&["TRUE", "false"]))
+                .try_cast(DataType::Boolean)
+                .unwrap(),
+            OwnedColumn::Boolean(vec![true, false])
+        );
+        assert!(matches!(
+            OwnedColumn::<Curve25519Scalar>::VarChar(strings(&["yes"])).try_cast(DataType::Boolean),
+            Err(ColumnOperationError::CastInvalidValue { .. })
+        ));
+    }
 }
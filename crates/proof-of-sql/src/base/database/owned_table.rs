@@ -1,4 +1,4 @@
-use super::OwnedColumn;
+use super::{presence_column_id, OwnedColumn};
 use crate::base::{map::IndexMap, scalar::Scalar};
 use proof_of_sql_parser::Identifier;
 use snafu::Snafu;
@@ -71,6 +71,16 @@ impl<S: Scalar> OwnedTable<S> {
     pub fn column_names(&self) -> impl Iterator<Item = &Identifier> {
         self.table.keys()
     }
+    /// Returns which rows of the column `column_id` are not null, or `None` if the column is not nullable.
+    ///
+    /// This is the presence column of `column_id`. See [`presence_column_id`].
+    #[must_use]
+    pub fn presence(&self, column_id: Identifier) -> Option<&[bool]> {
+        match self.table.get(&presence_column_id(column_id)?)? {
+            OwnedColumn::Boolean(presence) => Some(presence),
+            _ => None,
+        }
+    }
 }
 
 // Note: we modify the default PartialEq for IndexMap to also check for column ordering.
@@ -0,0 +1,312 @@
+use super::{
+    column_id_of_presence_column, presence_column_id, ArrayRefExt,
+    ArrowArrayToColumnConversionError, Column, ColumnRef, ColumnType, CommitmentAccessor,
+    DataAccessor, MetadataAccessor, SchemaAccessor, TableRef,
+};
+use crate::base::{
+    commitment::{Commitment, RecordBatchToColumnsError, TableCommitment},
+    map::IndexMap,
+};
+use alloc::vec::Vec;
+use arrow::{
+    array::ArrayRef,
+    compute::concat_batches,
+    datatypes::SchemaRef,
+    error::ArrowError,
+    ipc::reader::FileReader,
+    record_batch::{RecordBatch, RecordBatchReader},
+};
+use bumpalo::Bump;
+#[cfg(feature = "parquet")]
+use parquet::{arrow::arrow_reader::ParquetRecordBatchReaderBuilder, errors::ParquetError};
+use proof_of_sql_parser::Identifier;
+use snafu::Snafu;
+use std::{fs::File, path::Path};
+
+/// Errors that can occur when inserting a table into a [`RecordBatchAccessor`].
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum RecordBatchAccessorError {
+    /// This error occurs when the file of a table can't be opened.
+    #[snafu(transparent)]
+    Io {
+        /// The underlying source error
+        source: std::io::Error,
+    },
+    /// This error occurs when an Arrow IPC file can't be read, or its batches can't be concatenated.
+    #[snafu(transparent)]
+    Arrow {
+        /// The underlying source error
+        source: ArrowError,
+    },
+    /// This error occurs when a Parquet file can't be read.
+    #[cfg(feature = "parquet")]
+    #[snafu(transparent)]
+    Parquet {
+        /// The underlying source error
+        source: ParquetError,
+    },
+    /// This error occurs when the table can't be committed to.
+    #[snafu(transparent)]
+    RecordBatchToColumns {
+        /// The underlying source error
+        source: RecordBatchToColumnsError,
+    },
+    /// This error occurs when a table is inserted together with a commitment to some other data.
+    #[snafu(display("the table commitment does not match the record batch"))]
+    TableCommitmentMismatch,
+}
+
+/// An accessor over tables stored as Arrow [`RecordBatch`]es, e.g. tables loaded from Arrow IPC or Parquet files.
+///
+/// Each table is kept as a single [`RecordBatch`] together with its [`TableCommitment`], which is computed once
+/// when the table is inserted. Fields are exposed exactly as they are committed to, i.e. a field whose presence
+/// column is committed to, e.g. by [`TableCommitment::try_from_record_batch_with_offset`] because it contains nulls,
+/// is exposed as a column and its presence column.
+///
+/// This type implements the [`DataAccessor`], [`MetadataAccessor`], [`SchemaAccessor`] and [`CommitmentAccessor`]
+/// traits, so it can be used by both the prover and the verifier.
+pub struct RecordBatchAccessor<C: Commitment> {
+    alloc: Bump,
+    tables: IndexMap<TableRef, (RecordBatch, TableCommitment<C>)>,
+}
+
+impl<C: Commitment> Default for RecordBatchAccessor<C> {
+    fn default() -> Self {
+        Self {
+            alloc: Bump::new(),
+            tables: IndexMap::default(),
+        }
+    }
+}
+
+impl<C: Commitment> RecordBatchAccessor<C> {
+    /// Inserts a table, committing to it with its first row at `offset`.
+    ///
+    /// Replaces any table that was already inserted as `table_ref`.
+    pub fn try_insert_table(
+        &mut self,
+        table_ref: TableRef,
+        batch: RecordBatch,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), RecordBatchAccessorError> {
+        let commitment = TableCommitment::try_from_record_batch_with_offset(&batch, offset, setup)?;
+        self.tables.insert(table_ref, (batch, commitment));
+        Ok(())
+    }
+
+    /// Inserts a table together with an already known commitment to it, e.g. a commitment that was
+    /// stored alongside the data.
+    ///
+    /// Only the shape of the commitment is checked against the batch, i.e. its length and the types of its columns.
+    /// The commitment itself is not recomputed, so the caller must ensure that it is a commitment to
+    /// `batch`. Otherwise, the proofs of queries against this table will not verify.
+    pub fn try_insert_table_with_commitment(
+        &mut self,
+        table_ref: TableRef,
+        batch: RecordBatch,
+        commitment: TableCommitment<C>,
+    ) -> Result<(), RecordBatchAccessorError> {
+        let column_metadata = commitment.column_commitments().column_metadata();
+        let matches_batch = commitment.num_rows() == batch.num_rows()
+            && batch
+                .schema()
+                .fields()
+                .iter()
+                .zip(batch.columns())
+                .all(|(field, array)| {
+                    field.name().parse().is_ok_and(|identifier| {
+                        column_metadata.contains_key(&identifier)
+                            && (array.null_count() == 0
+                                || has_presence_column(&commitment, identifier))
+                    })
+                })
+            && column_metadata.iter().all(|(&identifier, metadata)| {
+                column_type_of(&batch, identifier) == Some(*metadata.column_type())
+            });
+        if !matches_batch {
+            return Err(RecordBatchAccessorError::TableCommitmentMismatch);
+        }
+        self.tables.insert(table_ref, (batch, commitment));
+        Ok(())
+    }
+
+    /// Inserts the table stored in the Arrow IPC file at `path`, committing to it with its first row at `offset`.
+    ///
+    /// The batches of the file are concatenated into a single table.
+    pub fn try_insert_ipc_file(
+        &mut self,
+        table_ref: TableRef,
+        path: impl AsRef<Path>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), RecordBatchAccessorError> {
+        let batch = read_batches(FileReader::try_new(File::open(path)?, None)?)?;
+        self.try_insert_table(table_ref, batch, offset, setup)
+    }
+
+    /// Inserts the table stored in the Parquet file at `path`, committing to it with its first row at `offset`.
+    ///
+    /// The row groups of the file are concatenated into a single table.
+    #[cfg(feature = "parquet")]
+    pub fn try_insert_parquet_file(
+        &mut self,
+        table_ref: TableRef,
+        path: impl AsRef<Path>,
+        offset: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Result<(), RecordBatchAccessorError> {
+        let batch =
+            read_batches(ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?.build()?)?;
+        self.try_insert_table(table_ref, batch, offset, setup)
+    }
+
+    /// Returns the data of a table.
+    #[must_use]
+    pub fn get_record_batch(&self, table_ref: TableRef) -> Option<&RecordBatch> {
+        self.tables.get(&table_ref).map(|(batch, _)| batch)
+    }
+
+    /// Returns the commitment to a table.
+    #[must_use]
+    pub fn get_table_commitment(&self, table_ref: TableRef) -> Option<&TableCommitment<C>> {
+        self.tables
+            .get(&table_ref)
+            .map(|(_, commitment)| commitment)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the table is not found.
+    fn get_table(&self, table_ref: TableRef) -> &(RecordBatch, TableCommitment<C>) {
+        self.tables.get(&table_ref).expect("Table not found.")
+    }
+}
+
+/// Reads all the batches of `reader` into a single batch.
+fn read_batches(reader: impl RecordBatchReader) -> Result<RecordBatch, RecordBatchAccessorError> {
+    let schema: SchemaRef = reader.schema();
+    let batches = reader.collect::<Result<Vec<_>, _>>()?;
+    Ok(concat_batches(&schema, &batches)?)
+}
+
+/// Returns the array of the field of `batch` whose name is `identifier`.
+fn array_of(batch: &RecordBatch, identifier: Identifier) -> Option<&ArrayRef> {
+    batch
+        .schema()
+        .fields()
+        .iter()
+        .position(|field| field.name().parse::<Identifier>() == Ok(identifier))
+        .map(|index| batch.column(index))
+}
+
+/// Returns the type of the column `identifier`, which is either a field of `batch` or the presence column of a nullable field.
+fn column_type_of(batch: &RecordBatch, identifier: Identifier) -> Option<ColumnType> {
+    match array_of(batch, identifier) {
+        Some(array) => array.data_type().clone().try_into().ok(),
+        None => column_id_of_presence_column(identifier)
+            .and_then(|values_id| {
+                batch
+                    .schema()
+                    .fields()
+                    .iter()
+                    .find(|field| field.name().parse::<Identifier>() == Ok(values_id))
+                    .cloned()
+            })
+            .filter(|field| field.is_nullable())
+            .map(|_| ColumnType::Boolean),
+    }
+}
+
+/// Whether `commitment` commits to the presence column of the column `identifier`.
+fn has_presence_column<C: Commitment>(
+    commitment: &TableCommitment<C>,
+    identifier: Identifier,
+) -> bool {
+    presence_column_id(identifier).is_some_and(|presence_id| {
+        commitment
+            .column_commitments()
+            .get_metadata(&presence_id)
+            .is_some()
+    })
+}
+
+/// # Panics
+///
+/// Panics if the table or the column is not found, or if the column can't be converted.
+impl<C: Commitment> DataAccessor<C::Scalar> for RecordBatchAccessor<C> {
+    fn get_column(&self, column: ColumnRef) -> Column<C::Scalar> {
+        let (batch, commitment) = self.get_table(column.table_ref());
+        let range = 0..batch.num_rows();
+        let result: Result<_, ArrowArrayToColumnConversionError> =
+            match array_of(batch, column.column_id()) {
+                Some(array) if has_presence_column(commitment, column.column_id()) => array
+                    .to_nullable_column(&self.alloc, &range, None)
+                    .map(|nullable_column| nullable_column.values),
+                Some(array) => array.to_column(&self.alloc, &range, None),
+                None => {
+                    let array = column_id_of_presence_column(column.column_id())
+                        .and_then(|values_id| array_of(batch, values_id))
+                        .expect("Column not found.");
+                    array.to_presence(&self.alloc, &range).map(Column::Boolean)
+                }
+            };
+        let result = result.expect("Failed to convert arrow column.");
+        assert_eq!(
+            &result.column_type(),
+            column.column_type(),
+            "Type mismatch."
+        );
+        result
+    }
+}
+
+/// # Panics
+///
+/// Panics if the table or the column is not found.
+impl<C: Commitment> CommitmentAccessor<C> for RecordBatchAccessor<C> {
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        let (_, commitment) = self.get_table(column.table_ref());
+        commitment
+            .column_commitments()
+            .get_commitment(&column.column_id())
+            .expect("Column not found.")
+    }
+}
+
+/// # Panics
+///
+/// Panics if the table is not found.
+impl<C: Commitment> MetadataAccessor for RecordBatchAccessor<C> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        self.get_table(table_ref).1.num_rows()
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        self.get_table(table_ref).1.range().start
+    }
+}
+
+impl<C: Commitment> SchemaAccessor for RecordBatchAccessor<C> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        let (_, commitment) = self.tables.get(&table_ref)?;
+        commitment
+            .column_commitments()
+            .get_metadata(&column_id)
+            .map(|metadata| *metadata.column_type())
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the table is not found.
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        self.get_table(table_ref)
+            .1
+            .column_commitments()
+            .column_metadata()
+            .iter()
+            .map(|(&identifier, metadata)| (identifier, *metadata.column_type()))
+            .collect()
+    }
+}
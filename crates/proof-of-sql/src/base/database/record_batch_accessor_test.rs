@@ -0,0 +1,243 @@
+use super::{
+    owned_table_utility::*, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, RecordBatchAccessor, RecordBatchAccessorError, SchemaAccessor, TableRef,
+};
+use crate::{
+    base::commitment::{naive_commitment::NaiveCommitment, TableCommitment},
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryVerifierPublicSetup,
+        ProverSetup, PublicParameters, VerifierSetup,
+    },
+    record_batch,
+    sql::{
+        proof::VerifiableQueryResult,
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use alloc::sync::Arc;
+use ark_std::test_rng;
+use arrow::{
+    array::{ArrayRef, Int64Array},
+    datatypes::{DataType, Field, Schema},
+    ipc::writer::FileWriter,
+    record_batch::RecordBatch,
+};
+use proof_of_sql_parser::Identifier;
+use std::{fs::File, path::PathBuf};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("record_batch_accessor_test_{name}"))
+}
+
+#[test]
+fn we_can_access_the_columns_and_metadata_of_a_table() {
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let batch = record_batch!("a" => [1_i64, 2, 3], "b" => ["x", "y", "z"]);
+    let mut accessor = RecordBatchAccessor::<NaiveCommitment>::default();
+    accessor.try_insert_table(t, batch.clone(), 4, &()).unwrap();
+
+    assert_eq!(accessor.get_length(t), 3);
+    assert_eq!(accessor.get_offset(t), 4);
+    assert_eq!(accessor.get_record_batch(t), Some(&batch));
+
+    let a: Identifier = "a".parse().unwrap();
+    let b: Identifier = "b".parse().unwrap();
+    assert_eq!(
+        accessor.lookup_schema(t),
+        vec![(a, ColumnType::BigInt), (b, ColumnType::VarChar)]
+    );
+    assert_eq!(accessor.lookup_column(t, a), Some(ColumnType::BigInt));
+    assert_eq!(accessor.lookup_column(t, "c".parse().unwrap()), None);
+    assert_eq!(accessor.lookup_column("sxt.u".parse().unwrap(), a), None);
+
+    assert_eq!(
+        accessor.get_column(ColumnRef::new(t, a, ColumnType::BigInt)),
+        Column::BigInt(&[1, 2, 3])
+    );
+    let Column::VarChar((strings, _)) =
+        accessor.get_column(ColumnRef::new(t, b, ColumnType::VarChar))
+    else {
+        panic!("expected a varchar column");
+    };
+    assert_eq!(strings, ["x", "y", "z"]);
+
+    let expected_commitment =
+        TableCommitment::<NaiveCommitment>::try_from_record_batch_with_offset(&batch, 4, &())
+            .unwrap();
+    assert_eq!(accessor.get_table_commitment(t), Some(&expected_commitment));
+    assert_eq!(
+        accessor.get_commitment(ColumnRef::new(t, a, ColumnType::BigInt)),
+        expected_commitment
+            .column_commitments()
+            .get_commitment(&a)
+            .unwrap()
+    );
+}
+
+#[test]
+fn we_can_access_the_presence_of_nullable_columns() {
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)])),
+        vec![Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef],
+    )
+    .unwrap();
+    let mut accessor = RecordBatchAccessor::<NaiveCommitment>::default();
+    accessor.try_insert_table(t, batch, 0, &()).unwrap();
+
+    let a: Identifier = "a".parse().unwrap();
+    let presence: Identifier = "__a_presence__".parse().unwrap();
+    assert_eq!(
+        accessor.lookup_schema(t),
+        vec![(a, ColumnType::BigInt), (presence, ColumnType::Boolean)]
+    );
+    assert_eq!(
+        accessor.get_column(ColumnRef::new(t, a, ColumnType::BigInt)),
+        Column::BigInt(&[1, 0, 3])
+    );
+    assert_eq!(
+        accessor.get_column(ColumnRef::new(t, presence, ColumnType::Boolean)),
+        Column::Boolean(&[true, false, true])
+    );
+}
+
+#[test]
+fn we_can_only_access_the_presence_of_nullable_columns_with_nulls() {
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef],
+    )
+    .unwrap();
+    let mut accessor = RecordBatchAccessor::<NaiveCommitment>::default();
+    accessor.try_insert_table(t, batch.clone(), 0, &()).unwrap();
+
+    let a: Identifier = "a".parse().unwrap();
+    assert_eq!(accessor.lookup_schema(t), vec![(a, ColumnType::BigInt)]);
+
+    let commitment =
+        TableCommitment::<NaiveCommitment>::try_from_record_batch(&batch, &()).unwrap();
+    let batch_with_nulls = RecordBatch::try_new(
+        schema,
+        vec![Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])) as ArrayRef],
+    )
+    .unwrap();
+    assert!(matches!(
+        accessor.try_insert_table_with_commitment(t, batch_with_nulls, commitment),
+        Err(RecordBatchAccessorError::TableCommitmentMismatch)
+    ));
+}
+
+#[test]
+fn we_can_only_insert_tables_with_matching_commitments() {
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let batch = record_batch!("a" => [1_i64, 2, 3], "b" => [true, false, true]);
+    let commitment =
+        TableCommitment::<NaiveCommitment>::try_from_record_batch_with_offset(&batch, 2, &())
+            .unwrap();
+    let mut accessor = RecordBatchAccessor::<NaiveCommitment>::default();
+    accessor
+        .try_insert_table_with_commitment(t, batch, commitment.clone())
+        .unwrap();
+    assert_eq!(accessor.get_offset(t), 2);
+
+    for other_batch in [
+        record_batch!("a" => [1_i64, 2], "b" => [true, false]),
+        record_batch!("a" => [1_i64, 2, 3]),
+        record_batch!("a" => [1_i64, 2, 3], "c" => [true, false, true]),
+        record_batch!("a" => [1_i32, 2, 3], "b" => [true, false, true]),
+    ] {
+        assert!(matches!(
+            accessor.try_insert_table_with_commitment(t, other_batch, commitment.clone()),
+            Err(RecordBatchAccessorError::TableCommitmentMismatch)
+        ));
+    }
+}
+
+#[test]
+fn we_can_insert_a_table_from_an_ipc_file() {
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let batches = [
+        record_batch!("a" => [1_i64, 2], "b" => ["x", "y"]),
+        record_batch!("a" => [3_i64], "b" => ["z"]),
+    ];
+    let path = temp_path("ipc.arrow");
+    let mut writer =
+        FileWriter::try_new(File::create(&path).unwrap(), &batches[0].schema()).unwrap();
+    for batch in &batches {
+        writer.write(batch).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let mut accessor = RecordBatchAccessor::<NaiveCommitment>::default();
+    accessor.try_insert_ipc_file(t, &path, 0, &()).unwrap();
+    assert_eq!(
+        accessor.get_record_batch(t),
+        Some(&record_batch!("a" => [1_i64, 2, 3], "b" => ["x", "y", "z"]))
+    );
+
+    assert!(matches!(
+        accessor.try_insert_ipc_file(t, temp_path("missing.arrow"), 0, &()),
+        Err(RecordBatchAccessorError::Io { .. })
+    ));
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn we_can_insert_a_table_from_a_parquet_file() {
+    use parquet::arrow::ArrowWriter;
+
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let batch = record_batch!("a" => [1_i64, 2, 3], "b" => ["x", "y", "z"]);
+    let path = temp_path("parquet.parquet");
+    let mut writer =
+        ArrowWriter::try_new(File::create(&path).unwrap(), batch.schema(), None).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+
+    let mut accessor = RecordBatchAccessor::<NaiveCommitment>::default();
+    accessor.try_insert_parquet_file(t, &path, 0, &()).unwrap();
+    assert_eq!(accessor.get_record_batch(t), Some(&batch));
+
+    assert!(matches!(
+        accessor.try_insert_ipc_file(t, &path, 0, &()),
+        Err(RecordBatchAccessorError::Arrow { .. })
+    ));
+}
+
+#[test]
+fn we_can_prove_and_verify_a_query_with_a_record_batch_accessor() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let mut accessor = RecordBatchAccessor::<DoryCommitment>::default();
+    accessor
+        .try_insert_table(
+            t,
+            record_batch!("a" => [1_i64, 5, 3, 5], "b" => ["x", "y", "z", "w"]),
+            0,
+            &prover_setup,
+        )
+        .unwrap();
+    let plan: DynProofPlan<DoryCommitment> = filter(
+        cols_expr_plan(t, &["b"], &accessor),
+        tab(t),
+        equal(column(t, "a", &accessor), const_bigint(5)),
+    );
+    let verifiable_result =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(&plan, &accessor, &prover_setup);
+    let table = verifiable_result
+        .verify(
+            &plan,
+            &accessor,
+            &DoryVerifierPublicSetup::new(&verifier_setup, 3),
+        )
+        .unwrap()
+        .table;
+    assert_eq!(table, owned_table([varchar("b", ["y", "w"])]));
+}
@@ -0,0 +1,156 @@
+use super::{
+    Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
+    SchemaAccessor, TableRef,
+};
+use crate::base::{
+    commitment::{Commitment, TableCommitment},
+    map::IndexMap,
+};
+use alloc::vec::Vec;
+use core::ops::Range;
+use proof_of_sql_parser::Identifier;
+use snafu::Snafu;
+
+/// Errors that can occur when pinning a table of a [`SnapshotAccessor`].
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum SnapshotAccessorError {
+    /// This error occurs when the rows of a snapshot are not all available in the underlying table.
+    #[snafu(display(
+        "snapshot rows {snapshot_range:?} are not contained in the table rows {table_range:?}"
+    ))]
+    SnapshotOutOfRange {
+        /// The rows of the snapshot
+        snapshot_range: Range<usize>,
+        /// The rows of the underlying table
+        table_range: Range<usize>,
+    },
+}
+
+/// An accessor that pins tables of an underlying accessor to recorded versions of their [`TableCommitment`]s.
+///
+/// A table of an append-only database can be proven "as of" an earlier point in time by pinning it to the
+/// [`TableCommitment`] that was recorded at that time. Queries against a pinned table only see the rows in the
+/// range of that commitment, and its column commitments are taken from that commitment rather than from the
+/// underlying accessor. Tables that aren't pinned are passed through unchanged.
+///
+/// The prover wraps its [`DataAccessor`], and the verifier wraps its [`CommitmentAccessor`] and pins the same
+/// snapshots, so both agree on the table spans that the proof is about. The verifier only needs the recorded
+/// commitments of pinned tables, never their current ones.
+pub struct SnapshotAccessor<'a, C: Commitment, A> {
+    accessor: &'a A,
+    snapshots: IndexMap<TableRef, TableCommitment<C>>,
+}
+
+impl<'a, C: Commitment, A: MetadataAccessor> SnapshotAccessor<'a, C, A> {
+    /// Creates an accessor over `accessor` with no pinned tables.
+    pub fn new(accessor: &'a A) -> Self {
+        Self {
+            accessor,
+            snapshots: IndexMap::default(),
+        }
+    }
+
+    /// Pins a table to `snapshot`, replacing any snapshot it was already pinned to.
+    ///
+    /// The rows of the snapshot must be a subrange of the rows currently available in the underlying accessor.
+    pub fn try_pin_table(
+        &mut self,
+        table_ref: TableRef,
+        snapshot: TableCommitment<C>,
+    ) -> Result<(), SnapshotAccessorError> {
+        let table_range = self.underlying_range(table_ref);
+        let snapshot_range = snapshot.range().clone();
+        if snapshot_range.start < table_range.start || snapshot_range.end > table_range.end {
+            return Err(SnapshotAccessorError::SnapshotOutOfRange {
+                snapshot_range,
+                table_range,
+            });
+        }
+        self.snapshots.insert(table_ref, snapshot);
+        Ok(())
+    }
+
+    /// Returns the snapshot a table is pinned to, if any.
+    #[must_use]
+    pub fn get_snapshot(&self, table_ref: TableRef) -> Option<&TableCommitment<C>> {
+        self.snapshots.get(&table_ref)
+    }
+
+    /// The rows of a table in the underlying accessor.
+    fn underlying_range(&self, table_ref: TableRef) -> Range<usize> {
+        let offset = self.accessor.get_offset(table_ref);
+        offset..offset + self.accessor.get_length(table_ref)
+    }
+}
+
+impl<C: Commitment, A: MetadataAccessor> MetadataAccessor for SnapshotAccessor<'_, C, A> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        match self.snapshots.get(&table_ref) {
+            Some(snapshot) => snapshot.num_rows(),
+            None => self.accessor.get_length(table_ref),
+        }
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        match self.snapshots.get(&table_ref) {
+            Some(snapshot) => snapshot.range().start,
+            None => self.accessor.get_offset(table_ref),
+        }
+    }
+}
+
+impl<C: Commitment, A: DataAccessor<C::Scalar>> DataAccessor<C::Scalar>
+    for SnapshotAccessor<'_, C, A>
+{
+    fn get_column(&self, column: ColumnRef) -> Column<C::Scalar> {
+        let data = self.accessor.get_column(column);
+        match self.snapshots.get(&column.table_ref()) {
+            Some(snapshot) => {
+                let offset = self.accessor.get_offset(column.table_ref());
+                let range = snapshot.range();
+                data.slice(range.start - offset..range.end - offset)
+            }
+            None => data,
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if a column of a pinned table is not in its snapshot.
+impl<C: Commitment, A: CommitmentAccessor<C>> CommitmentAccessor<C> for SnapshotAccessor<'_, C, A> {
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        match self.snapshots.get(&column.table_ref()) {
+            Some(snapshot) => snapshot
+                .column_commitments()
+                .get_commitment(&column.column_id())
+                .expect("Column not found in snapshot."),
+            None => self.accessor.get_commitment(column),
+        }
+    }
+}
+
+impl<C: Commitment, A: SchemaAccessor> SchemaAccessor for SnapshotAccessor<'_, C, A> {
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        match self.snapshots.get(&table_ref) {
+            Some(snapshot) => snapshot
+                .column_commitments()
+                .get_metadata(&column_id)
+                .map(|metadata| *metadata.column_type()),
+            None => self.accessor.lookup_column(table_ref, column_id),
+        }
+    }
+
+    fn lookup_schema(&self, table_ref: TableRef) -> Vec<(Identifier, ColumnType)> {
+        match self.snapshots.get(&table_ref) {
+            Some(snapshot) => snapshot
+                .column_commitments()
+                .column_metadata()
+                .iter()
+                .map(|(&identifier, metadata)| (identifier, *metadata.column_type()))
+                .collect(),
+            None => self.accessor.lookup_schema(table_ref),
+        }
+    }
+}
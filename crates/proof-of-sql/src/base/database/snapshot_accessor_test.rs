@@ -0,0 +1,172 @@
+use super::{
+    owned_table_utility::*, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+    MetadataAccessor, OwnedTableTestAccessor, SchemaAccessor, SnapshotAccessor,
+    SnapshotAccessorError, TableRef, TestAccessor,
+};
+use crate::{
+    base::commitment::TableCommitment,
+    proof_primitive::dory::{
+        DoryCommitment, DoryEvaluationProof, DoryProverPublicSetup, DoryScalar,
+        DoryVerifierPublicSetup, ProverSetup, PublicParameters, VerifierSetup,
+    },
+    sql::{
+        proof::VerifiableQueryResult,
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use ark_std::test_rng;
+use proof_of_sql_parser::Identifier;
+
+#[test]
+fn we_can_access_a_pinned_table_as_of_its_snapshot() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+
+    let pinned: TableRef = "sxt.t".parse().unwrap();
+    let unpinned: TableRef = "sxt.u".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_from_table(
+        pinned,
+        owned_table([
+            bigint("a", [1_i64, 2, 3, 4, 5]),
+            varchar("b", ["v", "w", "x", "y", "z"]),
+        ]),
+        2,
+        prover_setup,
+    );
+    accessor.add_table(unpinned, owned_table([boolean("c", [true, false])]), 0);
+    let snapshot = TableCommitment::<DoryCommitment>::from_owned_table_with_offset(
+        &owned_table::<DoryScalar>([bigint("a", [2_i64, 3, 4]), varchar("b", ["w", "x", "y"])]),
+        3,
+        &prover_setup,
+    );
+
+    let mut snapshot_accessor = SnapshotAccessor::new(&accessor);
+    snapshot_accessor
+        .try_pin_table(pinned, snapshot.clone())
+        .unwrap();
+    assert_eq!(snapshot_accessor.get_snapshot(pinned), Some(&snapshot));
+    assert_eq!(snapshot_accessor.get_snapshot(unpinned), None);
+
+    let a: Identifier = "a".parse().unwrap();
+    let b: Identifier = "b".parse().unwrap();
+    let c: Identifier = "c".parse().unwrap();
+    assert_eq!(snapshot_accessor.get_length(pinned), 3);
+    assert_eq!(snapshot_accessor.get_offset(pinned), 3);
+    assert_eq!(
+        snapshot_accessor.get_column(ColumnRef::new(pinned, a, ColumnType::BigInt)),
+        Column::BigInt(&[2, 3, 4])
+    );
+    let Column::VarChar((strings, _)) =
+        snapshot_accessor.get_column(ColumnRef::new(pinned, b, ColumnType::VarChar))
+    else {
+        panic!("expected a varchar column");
+    };
+    assert_eq!(strings, ["w", "x", "y"]);
+    assert_eq!(
+        snapshot_accessor.get_commitment(ColumnRef::new(pinned, a, ColumnType::BigInt)),
+        snapshot.column_commitments().get_commitment(&a).unwrap()
+    );
+    assert_eq!(
+        snapshot_accessor.lookup_schema(pinned),
+        vec![(a, ColumnType::BigInt), (b, ColumnType::VarChar)]
+    );
+    assert_eq!(snapshot_accessor.lookup_column(pinned, c), None);
+
+    // tables that aren't pinned are passed through
+    let c_ref = ColumnRef::new(unpinned, c, ColumnType::Boolean);
+    assert_eq!(snapshot_accessor.get_length(unpinned), 2);
+    assert_eq!(snapshot_accessor.get_offset(unpinned), 0);
+    assert_eq!(
+        snapshot_accessor.get_column(c_ref),
+        Column::Boolean(&[true, false])
+    );
+    assert_eq!(
+        snapshot_accessor.get_commitment(c_ref),
+        accessor.get_commitment(c_ref)
+    );
+    assert_eq!(
+        snapshot_accessor.lookup_schema(unpinned),
+        vec![(c, ColumnType::Boolean)]
+    );
+}
+
+#[test]
+fn we_cannot_pin_a_table_to_rows_that_are_not_available() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_from_table(
+        t,
+        owned_table([bigint("a", [1_i64, 2, 3])]),
+        2,
+        prover_setup,
+    );
+    let mut snapshot_accessor = SnapshotAccessor::new(&accessor);
+    for (data, offset) in [([1_i64, 2, 3, 4], 2), ([1, 2, 3, 4], 0), ([1, 2, 3, 4], 1)] {
+        let snapshot = TableCommitment::<DoryCommitment>::from_owned_table_with_offset(
+            &owned_table::<DoryScalar>([bigint("a", data)]),
+            offset,
+            &prover_setup,
+        );
+        assert!(matches!(
+            snapshot_accessor.try_pin_table(t, snapshot),
+            Err(SnapshotAccessorError::SnapshotOutOfRange { .. })
+        ));
+    }
+    assert_eq!(snapshot_accessor.get_snapshot(t), None);
+}
+
+#[test]
+fn we_can_prove_and_verify_a_query_against_a_snapshot_of_a_grown_table() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    // The table had four rows when the snapshot was recorded, and has grown since.
+    let t: TableRef = "sxt.t".parse().unwrap();
+    let snapshot = TableCommitment::<DoryCommitment>::from_owned_table_with_offset(
+        &owned_table::<DoryScalar>([
+            bigint("a", [1_i64, 5, 3, 5]),
+            varchar("b", ["x", "y", "z", "w"]),
+        ]),
+        0,
+        &prover_setup,
+    );
+    let accessor = OwnedTableTestAccessor::<DoryEvaluationProof>::new_from_table(
+        t,
+        owned_table([
+            bigint("a", [1_i64, 5, 3, 5, 5, 2]),
+            varchar("b", ["x", "y", "z", "w", "v", "u"]),
+        ]),
+        0,
+        prover_setup,
+    );
+    let mut snapshot_accessor = SnapshotAccessor::new(&accessor);
+    snapshot_accessor
+        .try_pin_table(t, snapshot.clone())
+        .unwrap();
+
+    let plan: DynProofPlan<DoryCommitment> = filter(
+        cols_expr_plan(t, &["b"], &snapshot_accessor),
+        tab(t),
+        equal(column(t, "a", &snapshot_accessor), const_bigint(5)),
+    );
+    let verifiable_result =
+        VerifiableQueryResult::<DoryEvaluationProof>::new(&plan, &snapshot_accessor, &prover_setup);
+    let table = verifiable_result
+        .verify(&plan, &snapshot_accessor, &verifier_setup)
+        .unwrap()
+        .table;
+    assert_eq!(table, owned_table([varchar("b", ["y", "w"])]));
+
+    // The proof is about the snapshot, not the current state of the table.
+    assert!(verifiable_result
+        .verify(&plan, &accessor, &verifier_setup)
+        .is_err());
+}
@@ -0,0 +1,82 @@
+//! Contains the utility functions for the `UnionExec` node.
+
+use crate::base::{database::Column, scalar::Scalar};
+use alloc::vec::Vec;
+use bumpalo::Bump;
+
+/// Concatenates the slices of the columns into one slice allocated in `alloc`
+fn concat_slices<'a, T: Copy>(alloc: &'a Bump, slices: impl Iterator<Item = &'a [T]>) -> &'a [T] {
+    let values: Vec<T> = slices.flat_map(|slice| slice.iter().copied()).collect();
+    alloc.alloc_slice_copy(&values)
+}
+
+/// Concatenates columns of the same type, i.e. stacks their rows on top of each other.
+///
+/// # Panics
+///
+/// Panics if there are no columns or if the columns don't all have the type of the first one.
+pub fn concat_columns<'a, S: Scalar>(alloc: &'a Bump, columns: &[Column<'a, S>]) -> Column<'a, S> {
+    let first = columns.first().expect("at least one column is needed");
+    assert!(
+        columns
+            .iter()
+            .all(|column| column.column_type() == first.column_type()),
+        "columns of a union must have the same type"
+    );
+    // The types are checked above, so the other variants can't occur
+    macro_rules! concat_variant {
+        ($variant:ident) => {
+            concat_slices(
+                alloc,
+                columns.iter().map(|column| match column {
+                    Column::$variant(col) => *col,
+                    _ => unreachable!(),
+                }),
+            )
+        };
+    }
+    match first {
+        Column::Boolean(_) => Column::Boolean(concat_variant!(Boolean)),
+        Column::TinyInt(_) => Column::TinyInt(concat_variant!(TinyInt)),
+        Column::SmallInt(_) => Column::SmallInt(concat_variant!(SmallInt)),
+        Column::Int(_) => Column::Int(concat_variant!(Int)),
+        Column::BigInt(_) => Column::BigInt(concat_variant!(BigInt)),
+        Column::Int128(_) => Column::Int128(concat_variant!(Int128)),
+        Column::Scalar(_) => Column::Scalar(concat_variant!(Scalar)),
+        Column::Decimal75(precision, scale, _) => Column::Decimal75(
+            *precision,
+            *scale,
+            concat_slices(
+                alloc,
+                columns.iter().map(|column| match column {
+                    Column::Decimal75(_, _, col) => *col,
+                    _ => unreachable!(),
+                }),
+            ),
+        ),
+        Column::TimestampTZ(tu, tz, _) => Column::TimestampTZ(
+            *tu,
+            *tz,
+            concat_slices(
+                alloc,
+                columns.iter().map(|column| match column {
+                    Column::TimestampTZ(_, _, col) => *col,
+                    _ => unreachable!(),
+                }),
+            ),
+        ),
+        Column::VarChar(_) => {
+            let (strings, scalars): (Vec<_>, Vec<_>) = columns
+                .iter()
+                .map(|column| match column {
+                    Column::VarChar((strings, scalars)) => (*strings, *scalars),
+                    _ => unreachable!(),
+                })
+                .unzip();
+            Column::VarChar((
+                concat_slices(alloc, strings.into_iter()),
+                concat_slices(alloc, scalars.into_iter()),
+            ))
+        }
+    }
+}
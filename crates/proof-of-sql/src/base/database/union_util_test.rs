@@ -0,0 +1,79 @@
+use crate::base::{
+    database::{union_util::*, Column},
+    math::decimal::Precision,
+    scalar::Curve25519Scalar,
+};
+use bumpalo::Bump;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+
+#[test]
+fn we_can_concat_columns_of_the_same_type() {
+    let alloc = Bump::new();
+    assert_eq!(
+        concat_columns(
+            &alloc,
+            &[
+                Column::<Curve25519Scalar>::BigInt(&[1, 2]),
+                Column::BigInt(&[]),
+                Column::BigInt(&[3]),
+            ]
+        ),
+        Column::BigInt(&[1, 2, 3])
+    );
+    assert_eq!(
+        concat_columns(
+            &alloc,
+            &[
+                Column::<Curve25519Scalar>::Boolean(&[true]),
+                Column::Boolean(&[false, true]),
+            ]
+        ),
+        Column::Boolean(&[true, false, true])
+    );
+    let scalars: Vec<Curve25519Scalar> = ["a", "b", "c"].iter().map(|&s| s.into()).collect();
+    assert_eq!(
+        concat_columns(
+            &alloc,
+            &[
+                Column::VarChar((&["a"], &scalars[..1])),
+                Column::VarChar((&["b", "c"], &scalars[1..])),
+            ]
+        ),
+        Column::VarChar((&["a", "b", "c"], &scalars))
+    );
+    let precision = Precision::new(10).unwrap();
+    assert_eq!(
+        concat_columns(
+            &alloc,
+            &[
+                Column::Decimal75(precision, 2, &scalars[..2]),
+                Column::Decimal75(precision, 2, &scalars[2..]),
+            ]
+        ),
+        Column::Decimal75(precision, 2, &scalars)
+    );
+    assert_eq!(
+        concat_columns(
+            &alloc,
+            &[
+                Column::<Curve25519Scalar>::TimestampTZ(
+                    PoSQLTimeUnit::Second,
+                    PoSQLTimeZone::Utc,
+                    &[1]
+                ),
+                Column::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, &[2]),
+            ]
+        ),
+        Column::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, &[1, 2])
+    );
+}
+
+#[test]
+#[should_panic(expected = "columns of a union must have the same type")]
+fn we_cannot_concat_columns_of_different_types() {
+    let alloc = Bump::new();
+    concat_columns(
+        &alloc,
+        &[Column::<Curve25519Scalar>::BigInt(&[1]), Column::Int(&[2])],
+    );
+}
@@ -4,7 +4,7 @@ mod error;
 pub use error::ProofError;
 
 /// Contains an extension trait for `merlin::Transcript`, which is used to construct a proof.
-#[cfg(any(test, feature = "blitzar"))]
+#[cfg(any(test, feature = "std"))]
 mod merlin_transcript_core;
 
 mod transcript;
@@ -0,0 +1,272 @@
+use crate::{
+    base::{
+        polynomial::{CompositePolynomial, CompositePolynomialInfo},
+        proof::{ProofError, Transcript},
+        scalar::{Curve25519Scalar, Scalar as _},
+        slice_ops,
+    },
+    proof_primitive::{
+        inner_product::{
+            compute_curve25519_generator, compute_curve25519_hiding_commitment,
+            HidingInnerProductProof,
+        },
+        sumcheck::SumcheckProof,
+    },
+};
+use alloc::vec::Vec;
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::VartimeMultiscalarMul,
+};
+use serde::{Deserialize, Serialize};
+
+/// The claim that a [`MaskedSumcheckProof`] reduces the sum of a polynomial to.
+pub struct MaskedSumcheckSubclaim {
+    /// The point at which the polynomial has to be evaluated
+    pub evaluation_point: Vec<Curve25519Scalar>,
+    /// The hiding commitment `e * G[0] + blind * H` to the evaluation `e` that the polynomial is expected to have
+    /// at `evaluation_point`, whose blinding factor only the prover knows
+    pub expected_evaluation_commitment: RistrettoPoint,
+}
+
+/// A zero-knowledge sumcheck proof, whose round polynomials reveal nothing about the polynomial `f` beyond
+/// its evaluation at the final evaluation point.
+///
+/// The prover commits to a random mask polynomial `g(x) = g_0(x_0) + ... + g_{n-1}(x_{n-1})`, where each `g_i`
+/// has the same degree as the round polynomials and no constant term, and sends the sum `G` of `g` over the
+/// hypercube. After receiving the challenge `rho`, the prover runs the sumcheck protocol for `f + rho * g`, whose
+/// sum is `claimed_sum + rho * G`. Finally, the prover commits to `g(r)` at the evaluation point `r` and proves the
+/// commitment with a [`HidingInnerProductProof`]. Neither `g(r)` nor `f(r)` is revealed, so the verifier is left with
+/// a hiding commitment to `f(r) = expected_evaluation - rho * g(r)`, see [`MaskedSumcheckSubclaim`].
+///
+/// The coefficients of the mask are committed to with a hiding commitment with offset `0`, in the order
+/// `g_0[1..=d], g_1[1..=d], ...`, where `g_i[k]` is the coefficient of `x_i^k`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MaskedSumcheckProof {
+    pub(super) mask_commitment: CompressedRistretto,
+    pub(super) mask_sum: Curve25519Scalar,
+    pub(super) sumcheck_proof: SumcheckProof<Curve25519Scalar>,
+    pub(super) mask_evaluation_commitment: CompressedRistretto,
+    pub(super) mask_evaluation_proof: HidingInnerProductProof,
+}
+
+/// `2^exponent`
+fn power_of_two(exponent: usize) -> Curve25519Scalar {
+    (0..exponent).fold(Curve25519Scalar::ONE, |power, _| {
+        power * Curve25519Scalar::TWO
+    })
+}
+
+/// Evaluate the univariate polynomial `sum_k coefficients[k - 1] * x^k`.
+fn evaluate_without_constant(
+    coefficients: &[Curve25519Scalar],
+    x: Curve25519Scalar,
+) -> Curve25519Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(Curve25519Scalar::ZERO, |acc, &coefficient| {
+            (acc + coefficient) * x
+        })
+}
+
+/// The vector `[r_0, r_0^2, ..., r_0^d, r_1, ...]`, whose inner product with the coefficients of the mask is `g(r)`.
+fn mask_evaluation_vector(
+    evaluation_point: &[Curve25519Scalar],
+    degree: usize,
+) -> Vec<Curve25519Scalar> {
+    evaluation_point
+        .iter()
+        .flat_map(|&r| {
+            (0..degree).scan(Curve25519Scalar::ONE, move |power, _| {
+                *power *= r;
+                Some(*power)
+            })
+        })
+        .collect()
+}
+
+impl MaskedSumcheckProof {
+    /// Create a zero-knowledge proof of the sum of `polynomial` over the hypercube, and store the evaluation point in `evaluation_point`.
+    ///
+    /// Along with the proof, this returns the blinding factor of the commitment to the evaluation of `polynomial`
+    /// at the evaluation point, see [`MaskedSumcheckSubclaim::expected_evaluation_commitment`].
+    ///
+    /// `rng` must be a cryptographically secure source of randomness, since the mask is drawn from it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `evaluation_point` doesn't have one entry per variable, or if the polynomial has no variables or no multiplicands.
+    #[tracing::instrument(name = "MaskedSumcheckProof::create", level = "debug", skip_all)]
+    pub fn create(
+        transcript: &mut impl Transcript,
+        evaluation_point: &mut [Curve25519Scalar],
+        polynomial: &CompositePolynomial<Curve25519Scalar>,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> (Self, Curve25519Scalar) {
+        let num_variables = polynomial.num_variables;
+        let degree = polynomial.max_multiplicands;
+        assert!(num_variables > 0 && degree > 0);
+
+        let mask: Vec<_> = core::iter::repeat_with(|| Curve25519Scalar::rand(rng))
+            .take(num_variables * degree)
+            .collect();
+        let mask_blind = Scalar::random(rng);
+        let mask_commitment =
+            compute_curve25519_hiding_commitment(&slice_ops::slice_cast(&mask), mask_blind, 0)
+                .compress();
+        // g_i(0) = 0, so the sum of g over the hypercube is 2^(n - 1) * sum_i g_i(1).
+        let mask_at_one: Vec<_> = mask
+            .chunks(degree)
+            .map(|coefficients| coefficients.iter().copied().sum::<Curve25519Scalar>())
+            .collect();
+        let mask_sum =
+            power_of_two(num_variables - 1) * mask_at_one.iter().copied().sum::<Curve25519Scalar>();
+        transcript.extend_as_le_from_refs([mask_commitment.as_bytes()]);
+        transcript.extend_scalars_as_be([&mask_sum]);
+        let rho: Curve25519Scalar = transcript.scalar_challenge_as_be();
+
+        // In round j, the round polynomial of g is
+        // 2^(n - 1 - j) * (g_0(r_0) + ... + g_(j - 1)(r_(j - 1)) + g_j(t)) + 2^(n - 2 - j) * (g_(j + 1)(1) + ... + g_(n - 1)(1))
+        let sumcheck_proof = SumcheckProof::create_with_round_mask(
+            transcript,
+            evaluation_point,
+            polynomial,
+            |round, challenges| {
+                let fixed: Curve25519Scalar = challenges
+                    .iter()
+                    .zip(mask.chunks(degree))
+                    .map(|(&r, coefficients)| evaluate_without_constant(coefficients, r))
+                    .sum();
+                let free = if round + 1 < num_variables {
+                    power_of_two(num_variables - 2 - round)
+                        * mask_at_one[round + 1..]
+                            .iter()
+                            .copied()
+                            .sum::<Curve25519Scalar>()
+                } else {
+                    Curve25519Scalar::ZERO
+                };
+                let scale = power_of_two(num_variables - 1 - round);
+                let coefficients = &mask[round * degree..(round + 1) * degree];
+                Some(
+                    (0..=degree)
+                        .map(|t| {
+                            let t = Curve25519Scalar::from(t as u64);
+                            rho * (scale * (fixed + evaluate_without_constant(coefficients, t))
+                                + free)
+                        })
+                        .collect(),
+                )
+            },
+        );
+
+        let b = mask_evaluation_vector(evaluation_point, degree);
+        let mask_evaluation: Curve25519Scalar = mask
+            .iter()
+            .zip(&b)
+            .map(|(&coefficient, &b)| coefficient * b)
+            .sum();
+        let mask_evaluation_blind = Curve25519Scalar::rand(rng);
+        let mask_evaluation_commitment = compute_curve25519_hiding_commitment(
+            &[mask_evaluation.into()],
+            mask_evaluation_blind.into(),
+            0,
+        )
+        .compress();
+        transcript.extend_as_le_from_refs([mask_evaluation_commitment.as_bytes()]);
+        let mask_evaluation_proof = transcript.wrap_transcript(|transcript| {
+            HidingInnerProductProof::create(
+                transcript,
+                &slice_ops::slice_cast(&mask),
+                mask_blind,
+                &slice_ops::slice_cast(&b),
+                mask_evaluation_blind.into(),
+                0,
+                rng,
+            )
+        });
+
+        let proof = Self {
+            mask_commitment,
+            mask_sum,
+            sumcheck_proof,
+            mask_evaluation_commitment,
+            mask_evaluation_proof,
+        };
+        (proof, -rho * mask_evaluation_blind)
+    }
+
+    /// Verify the proof up to the evaluation of the polynomial, i.e. reduce the claim that the polynomial sums to `claimed_sum`
+    /// to a claim about its evaluation at a single point.
+    #[tracing::instrument(
+        name = "MaskedSumcheckProof::verify_without_evaluation",
+        level = "debug",
+        skip_all
+    )]
+    pub fn verify_without_evaluation(
+        &self,
+        transcript: &mut impl Transcript,
+        polynomial_info: CompositePolynomialInfo,
+        claimed_sum: &Curve25519Scalar,
+    ) -> Result<MaskedSumcheckSubclaim, ProofError> {
+        let degree = polynomial_info.max_multiplicands;
+        if polynomial_info.num_variables == 0 || degree == 0 {
+            return Err(ProofError::VerificationError {
+                error: "invalid proof size",
+            });
+        }
+        let mask_commitment =
+            self.mask_commitment
+                .decompress()
+                .ok_or(ProofError::VerificationError {
+                    error: "invalid mask commitment",
+                })?;
+        transcript.extend_as_le_from_refs([self.mask_commitment.as_bytes()]);
+        transcript.extend_scalars_as_be([&self.mask_sum]);
+        let rho: Curve25519Scalar = transcript.scalar_challenge_as_be();
+
+        let subclaim = self.sumcheck_proof.verify_without_evaluation(
+            transcript,
+            polynomial_info,
+            &(*claimed_sum + rho * self.mask_sum),
+        )?;
+
+        let b = mask_evaluation_vector(&subclaim.evaluation_point, degree);
+        let mask_evaluation_commitment =
+            self.mask_evaluation_commitment
+                .decompress()
+                .ok_or(ProofError::VerificationError {
+                    error: "invalid mask evaluation commitment",
+                })?;
+        transcript.extend_as_le_from_refs([self.mask_evaluation_commitment.as_bytes()]);
+        transcript
+            .wrap_transcript(|transcript| {
+                self.mask_evaluation_proof.verify(
+                    transcript,
+                    &mask_commitment,
+                    &mask_evaluation_commitment,
+                    &slice_ops::slice_cast(&b),
+                    0,
+                )
+            })
+            .map_err(|_| ProofError::VerificationError {
+                error: "mask evaluation proof failed",
+            })?;
+
+        // e * G[0] - rho * (g(r) * G[0] + blind * H)
+        let expected_evaluation_commitment = RistrettoPoint::vartime_multiscalar_mul(
+            [(&subclaim.expected_evaluation).into(), -Scalar::from(&rho)],
+            [compute_curve25519_generator(0), mask_evaluation_commitment],
+        );
+        Ok(MaskedSumcheckSubclaim {
+            evaluation_point: subclaim.evaluation_point,
+            expected_evaluation_commitment,
+        })
+    }
+}
@@ -0,0 +1,145 @@
+use super::MaskedSumcheckProof;
+use crate::{
+    base::{
+        polynomial::CompositePolynomialInfo,
+        scalar::{Curve25519Scalar, Scalar},
+    },
+    proof_primitive::{
+        inner_product::{compute_curve25519_generator, compute_curve25519_hiding_commitment},
+        sumcheck::{test_cases::sumcheck_test_cases, SumcheckProof},
+    },
+};
+use curve25519_dalek::ristretto::CompressedRistretto;
+use merlin::Transcript;
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn we_can_verify_many_random_masked_test_cases() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let test_cases: Vec<_> = sumcheck_test_cases::<Curve25519Scalar>(&mut rng)
+        .filter(|test_case| test_case.max_multiplicands > 0)
+        .collect();
+    for test_case in test_cases {
+        let info = || CompositePolynomialInfo {
+            max_multiplicands: test_case.max_multiplicands,
+            num_variables: test_case.num_vars,
+        };
+        let mut transcript = Transcript::new(b"sumchecktest");
+        let mut evaluation_point = vec![Curve25519Scalar::ZERO; test_case.num_vars];
+        let (proof, evaluation_blind) = MaskedSumcheckProof::create(
+            &mut transcript,
+            &mut evaluation_point,
+            &test_case.polynomial,
+            &mut rng,
+        );
+
+        let mut transcript = Transcript::new(b"sumchecktest");
+        let subclaim = proof
+            .verify_without_evaluation(&mut transcript, info(), &test_case.sum)
+            .expect("verification should succeed with the correct setup");
+        assert_eq!(
+            subclaim.evaluation_point, evaluation_point,
+            "the prover's evaluation point should match the verifier's"
+        );
+        assert_eq!(
+            compute_curve25519_hiding_commitment(
+                &[test_case.polynomial.evaluate(&evaluation_point).into()],
+                evaluation_blind.into(),
+                0
+            ),
+            subclaim.expected_evaluation_commitment,
+            "the claimed evaluation should be committed to with the returned blinding factor"
+        );
+
+        let mut transcript = Transcript::new(b"sumchecktest");
+        assert!(
+            proof
+                .verify_without_evaluation(
+                    &mut transcript,
+                    info(),
+                    &(test_case.sum + Curve25519Scalar::ONE)
+                )
+                .is_err(),
+            "verification should fail when the sum is wrong"
+        );
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_tampered_masked_sumcheck_proof() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let test_case = sumcheck_test_cases::<Curve25519Scalar>(&mut rng)
+        .find(|test_case| test_case.num_vars == 3 && test_case.max_multiplicands == 3)
+        .unwrap();
+    let info = || CompositePolynomialInfo {
+        max_multiplicands: test_case.max_multiplicands,
+        num_variables: test_case.num_vars,
+    };
+    let mut evaluation_point = vec![Curve25519Scalar::ZERO; test_case.num_vars];
+    let (proof, _) = MaskedSumcheckProof::create(
+        &mut Transcript::new(b"sumchecktest"),
+        &mut evaluation_point,
+        &test_case.polynomial,
+        &mut rng,
+    );
+    let verify = |proof: &MaskedSumcheckProof| {
+        proof.verify_without_evaluation(
+            &mut Transcript::new(b"sumchecktest"),
+            info(),
+            &test_case.sum,
+        )
+    };
+    assert!(verify(&proof).is_ok());
+
+    let mut tampered_proof = proof.clone();
+    tampered_proof.mask_sum += Curve25519Scalar::ONE;
+    assert!(verify(&tampered_proof).is_err());
+
+    // Changing the evaluation of the mask is caught by the evaluation proof, even though it still
+    // shifts the final claim consistently.
+    let mut tampered_proof = proof.clone();
+    tampered_proof.mask_evaluation_commitment =
+        (proof.mask_evaluation_commitment.decompress().unwrap() + compute_curve25519_generator(0))
+            .compress();
+    assert!(verify(&tampered_proof).is_err());
+
+    let mut tampered_proof = proof;
+    tampered_proof.mask_commitment = CompressedRistretto::default();
+    assert!(verify(&tampered_proof).is_err());
+}
+
+#[test]
+fn the_round_polynomials_of_a_masked_sumcheck_proof_differ_from_the_unmasked_ones() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let test_case = sumcheck_test_cases::<Curve25519Scalar>(&mut rng)
+        .find(|test_case| test_case.num_vars == 4 && test_case.max_multiplicands == 2)
+        .unwrap();
+    let mut evaluation_point = vec![Curve25519Scalar::ZERO; test_case.num_vars];
+    let unmasked_proof = SumcheckProof::create(
+        &mut Transcript::new(b"sumchecktest"),
+        &mut evaluation_point,
+        &test_case.polynomial,
+    );
+    let (masked_proof, _) = MaskedSumcheckProof::create(
+        &mut Transcript::new(b"sumchecktest"),
+        &mut evaluation_point,
+        &test_case.polynomial,
+        &mut rng,
+    );
+    let (other_masked_proof, _) = MaskedSumcheckProof::create(
+        &mut Transcript::new(b"sumchecktest"),
+        &mut evaluation_point,
+        &test_case.polynomial,
+        &mut rng,
+    );
+    let round_bytes =
+        |proof: &SumcheckProof<Curve25519Scalar>| postcard::to_allocvec(proof).unwrap();
+    assert_ne!(
+        round_bytes(&masked_proof.sumcheck_proof),
+        round_bytes(&unmasked_proof)
+    );
+    assert_ne!(
+        round_bytes(&masked_proof.sumcheck_proof),
+        round_bytes(&other_masked_proof.sumcheck_proof)
+    );
+}
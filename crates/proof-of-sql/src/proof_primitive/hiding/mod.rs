@@ -0,0 +1,22 @@
+//! This module contains zero-knowledge building blocks for query proofs that hide the data of the queried tables.
+//!
+//! The building blocks work over Curve25519 and use the hiding commitments and the
+//! [`HidingInnerProductProof`](crate::proof_primitive::inner_product::HidingInnerProductProof) of the
+//! [`inner_product`](crate::proof_primitive::inner_product) module:
+//! - [`MaskedSumcheckProof`] is a sumcheck proof whose round polynomials are masked, so they only reveal a
+//!   commitment to the evaluation of the polynomial at the final evaluation point.
+//! - [`PolynomialRelationProof`] proves that a polynomial in committed values is a committed value, e.g. that
+//!   committed MLE evaluations pass the evaluation checks of a verifier.
+//!
+//! [`HidingQueryProof`](crate::sql::proof::HidingQueryProof) combines these into a query proof that only
+//! reveals the query result.
+
+mod masked_sumcheck_proof;
+pub use masked_sumcheck_proof::{MaskedSumcheckProof, MaskedSumcheckSubclaim};
+#[cfg(test)]
+mod masked_sumcheck_proof_test;
+
+mod polynomial_relation_proof;
+pub use polynomial_relation_proof::PolynomialRelationProof;
+#[cfg(test)]
+mod polynomial_relation_proof_test;
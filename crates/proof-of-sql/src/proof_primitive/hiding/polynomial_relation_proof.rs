@@ -0,0 +1,248 @@
+use crate::{
+    base::{
+        polynomial::{interpolate_evaluations_to_reverse_coefficients, interpolate_uni_poly},
+        proof::{ProofError, Transcript},
+        scalar::{Curve25519Scalar, Scalar as _},
+    },
+    proof_primitive::inner_product::{
+        compute_curve25519_blinding_generator, compute_curve25519_generator,
+        compute_curve25519_hiding_commitment,
+    },
+};
+use alloc::vec::Vec;
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::{IsIdentity, VartimeMultiscalarMul},
+};
+use serde::{Deserialize, Serialize};
+
+/// The hiding commitment `value * G[0] + blind * H`.
+fn commit(value: Curve25519Scalar, blind: Curve25519Scalar) -> CompressedRistretto {
+    compute_curve25519_hiding_commitment(&[value.into()], blind.into(), 0).compress()
+}
+
+/// Decompress all of `commitments`, or fail with `error` if any of them is invalid.
+fn decompress(
+    commitments: &[CompressedRistretto],
+    error: &'static str,
+) -> Result<Vec<RistrettoPoint>, ProofError> {
+    commitments
+        .iter()
+        .map(|commitment| {
+            commitment
+                .decompress()
+                .ok_or(ProofError::VerificationError { error })
+        })
+        .collect()
+}
+
+/// A zero-knowledge proof that `relation(values) = target`, where `relation` is a polynomial of bounded degree and
+/// the values and the target are only known through their hiding commitments `value * G[0] + blind * H`.
+///
+/// The verifier only needs to be able to evaluate `relation`, so it can be any computation that is a polynomial
+/// in its inputs, e.g. the evaluation check that a verifier would otherwise perform on revealed values.
+///
+/// The prover commits to a uniformly random shift `u` of the values and to the coefficients `p_1, ..., p_d` of
+/// ```text
+/// p(x) = relation(values + x * u) = target + p_1 * x + ... + p_d * x^d
+/// ```
+/// After receiving the challenge `c`, the prover reveals the shifted values `w = values + c * u`, which are
+/// uniformly random, together with the blinding factors that open `V_i + c * U_i` to `w_i` and
+/// `T + c * P_1 + ... + c^d * P_d` to `p(c) = relation(w)`, where `V_i`, `U_i`, `T` and `P_j` are the commitments
+/// to `values[i]`, `u[i]`, `target` and `p_j`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PolynomialRelationProof {
+    pub(crate) shift_commitments: Vec<CompressedRistretto>,
+    pub(crate) coefficient_commitments: Vec<CompressedRistretto>,
+    pub(crate) shifted_values: Vec<Curve25519Scalar>,
+    pub(crate) shifted_value_blinds: Vec<Curve25519Scalar>,
+    pub(crate) shifted_relation_blind: Curve25519Scalar,
+}
+
+impl PolynomialRelationProof {
+    /// Create a proof that `relation(values)` is the value committed to with the blinding factor `target_blind`,
+    /// where `values[i]` is committed to with the blinding factor `value_blinds[i]`.
+    ///
+    /// Returns `None` if `relation` fails to evaluate, or if it is not a polynomial of degree at most `degree`.
+    ///
+    /// `rng` must be a cryptographically secure source of randomness, since the shift is drawn from it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` and `value_blinds` have different lengths.
+    #[tracing::instrument(name = "PolynomialRelationProof::create", level = "debug", skip_all)]
+    pub fn create(
+        transcript: &mut impl Transcript,
+        values: &[Curve25519Scalar],
+        value_blinds: &[Curve25519Scalar],
+        target_blind: Curve25519Scalar,
+        degree: usize,
+        mut relation: impl FnMut(&[Curve25519Scalar]) -> Option<Curve25519Scalar>,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Option<Self> {
+        assert_eq!(values.len(), value_blinds.len());
+        let mut random_scalars = |len| -> Vec<_> {
+            core::iter::repeat_with(|| Curve25519Scalar::rand(rng))
+                .take(len)
+                .collect()
+        };
+        let shift = random_scalars(values.len());
+        let shift_blinds = random_scalars(values.len());
+        let coefficient_blinds = random_scalars(degree);
+        let shifted = |x: Curve25519Scalar| -> Vec<_> {
+            values
+                .iter()
+                .zip(&shift)
+                .map(|(&value, &shift)| value + x * shift)
+                .collect()
+        };
+
+        // p has degree at most `degree` iff the interpolation of its first `degree + 1` evaluations matches the next one.
+        let evaluations = (0..=degree as u64 + 1)
+            .map(|x| relation(&shifted(Curve25519Scalar::from(x))))
+            .collect::<Option<Vec<_>>>()?;
+        if interpolate_uni_poly(
+            &evaluations[..=degree],
+            Curve25519Scalar::from(degree as u64 + 1),
+        ) != evaluations[degree + 1]
+        {
+            return None;
+        }
+        // The coefficients are `[p_d, ..., p_1, p(0)]`.
+        let coefficients = interpolate_evaluations_to_reverse_coefficients(&evaluations[..=degree]);
+
+        let shift_commitments: Vec<_> = shift
+            .iter()
+            .zip(&shift_blinds)
+            .map(|(&shift, &blind)| commit(shift, blind))
+            .collect();
+        let coefficient_commitments: Vec<_> = coefficients[..degree]
+            .iter()
+            .rev()
+            .zip(&coefficient_blinds)
+            .map(|(&coefficient, &blind)| commit(coefficient, blind))
+            .collect();
+        transcript.extend_as_le_from_refs(
+            shift_commitments
+                .iter()
+                .chain(&coefficient_commitments)
+                .map(CompressedRistretto::as_bytes),
+        );
+        let c: Curve25519Scalar = transcript.scalar_challenge_as_be();
+
+        let shifted_values = shifted(c);
+        let shifted_value_blinds = value_blinds
+            .iter()
+            .zip(&shift_blinds)
+            .map(|(&blind, &shift_blind)| blind + c * shift_blind)
+            .collect();
+        let shifted_relation_blind = coefficient_blinds
+            .iter()
+            .rev()
+            .fold(Curve25519Scalar::ZERO, |acc, &blind| (acc + blind) * c)
+            + target_blind;
+        transcript.extend_scalars_as_be(&shifted_values);
+        Some(Self {
+            shift_commitments,
+            coefficient_commitments,
+            shifted_values,
+            shifted_value_blinds,
+            shifted_relation_blind,
+        })
+    }
+
+    /// Verify that `relation` evaluates to the opening of `target_commitment` at the openings of `value_commitments`.
+    ///
+    /// `relation` has to be a polynomial of degree at most `degree` for the proof to be sound.
+    #[tracing::instrument(name = "PolynomialRelationProof::verify", level = "debug", skip_all)]
+    pub fn verify(
+        &self,
+        transcript: &mut impl Transcript,
+        value_commitments: &[RistrettoPoint],
+        target_commitment: &RistrettoPoint,
+        degree: usize,
+        relation: impl FnOnce(&[Curve25519Scalar]) -> Result<Curve25519Scalar, ProofError>,
+    ) -> Result<(), ProofError> {
+        let num_values = value_commitments.len();
+        if self.shift_commitments.len() != num_values
+            || self.coefficient_commitments.len() != degree
+            || self.shifted_values.len() != num_values
+            || self.shifted_value_blinds.len() != num_values
+        {
+            Err(ProofError::VerificationError {
+                error: "invalid proof size",
+            })?;
+        }
+        let shift_commitments = decompress(&self.shift_commitments, "invalid shift commitment")?;
+        let coefficient_commitments = decompress(
+            &self.coefficient_commitments,
+            "invalid coefficient commitment",
+        )?;
+        transcript.extend_as_le_from_refs(
+            self.shift_commitments
+                .iter()
+                .chain(&self.coefficient_commitments)
+                .map(CompressedRistretto::as_bytes),
+        );
+        let c: Curve25519Scalar = transcript.scalar_challenge_as_be();
+        transcript.extend_scalars_as_be(&self.shifted_values);
+
+        let g = compute_curve25519_generator(0);
+        let h = compute_curve25519_blinding_generator();
+        let c_scalar = Scalar::from(&c);
+        // V_i + c * U_i - w_i * G[0] - t_i * H
+        let opens_shifted_value = |(((value_commitment, shift_commitment), value), blind): (
+            ((&RistrettoPoint, &RistrettoPoint), &Curve25519Scalar),
+            &Curve25519Scalar,
+        )| {
+            RistrettoPoint::vartime_multiscalar_mul(
+                [
+                    Scalar::ONE,
+                    c_scalar,
+                    -Scalar::from(value),
+                    -Scalar::from(blind),
+                ],
+                [*value_commitment, *shift_commitment, g, h],
+            )
+            .is_identity()
+        };
+        if !value_commitments
+            .iter()
+            .zip(&shift_commitments)
+            .zip(&self.shifted_values)
+            .zip(&self.shifted_value_blinds)
+            .all(opens_shifted_value)
+        {
+            Err(ProofError::VerificationError {
+                error: "shifted value does not match its commitment",
+            })?;
+        }
+
+        // T + c * P_1 + ... + c^d * P_d - relation(w) * G[0] - tau * H
+        let shifted_relation = relation(&self.shifted_values)?;
+        let powers = core::iter::successors(Some(c), |&power| Some(power * c));
+        let scalars = [
+            Curve25519Scalar::ONE,
+            -shifted_relation,
+            -self.shifted_relation_blind,
+        ]
+        .into_iter()
+        .chain(powers.take(degree))
+        .map(|scalar| Scalar::from(&scalar))
+        .collect::<Vec<_>>();
+        let points = [*target_commitment, g, h]
+            .into_iter()
+            .chain(coefficient_commitments);
+        if !RistrettoPoint::vartime_multiscalar_mul(scalars, points).is_identity() {
+            Err(ProofError::VerificationError {
+                error: "polynomial relation check failed",
+            })?;
+        }
+        Ok(())
+    }
+}
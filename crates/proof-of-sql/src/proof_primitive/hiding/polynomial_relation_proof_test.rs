@@ -0,0 +1,209 @@
+use super::PolynomialRelationProof;
+use crate::{
+    base::{
+        proof::ProofError,
+        scalar::{Curve25519Scalar, Scalar},
+    },
+    proof_primitive::inner_product::{
+        compute_curve25519_generator, compute_curve25519_hiding_commitment,
+    },
+};
+use ark_std::UniformRand;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use merlin::Transcript;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// `a * b * c + 3 * a + 5`
+fn relation(values: &[Curve25519Scalar]) -> Curve25519Scalar {
+    values[0] * values[1] * values[2]
+        + Curve25519Scalar::from(3) * values[0]
+        + Curve25519Scalar::from(5)
+}
+
+fn commit(value: Curve25519Scalar, blind: Curve25519Scalar) -> RistrettoPoint {
+    compute_curve25519_hiding_commitment(&[value.into()], blind.into(), 0)
+}
+
+struct TestCase {
+    values: Vec<Curve25519Scalar>,
+    value_blinds: Vec<Curve25519Scalar>,
+    target_blind: Curve25519Scalar,
+    value_commitments: Vec<RistrettoPoint>,
+    target_commitment: RistrettoPoint,
+}
+
+fn random_test_case(rng: &mut StdRng) -> TestCase {
+    let values: Vec<_> = (0..3).map(|_| Curve25519Scalar::rand(rng)).collect();
+    let value_blinds: Vec<_> = (0..3).map(|_| Curve25519Scalar::rand(rng)).collect();
+    let target_blind = Curve25519Scalar::rand(rng);
+    let value_commitments = values
+        .iter()
+        .zip(&value_blinds)
+        .map(|(&value, &blind)| commit(value, blind))
+        .collect();
+    let target_commitment = commit(relation(&values), target_blind);
+    TestCase {
+        values,
+        value_blinds,
+        target_blind,
+        value_commitments,
+        target_commitment,
+    }
+}
+
+#[test]
+fn we_can_create_and_verify_a_polynomial_relation_proof() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let test_case = random_test_case(&mut rng);
+    let proof = PolynomialRelationProof::create(
+        &mut Transcript::new(b"test"),
+        &test_case.values,
+        &test_case.value_blinds,
+        test_case.target_blind,
+        3,
+        |values| Some(relation(values)),
+        &mut rng,
+    )
+    .unwrap();
+    assert!(proof
+        .verify(
+            &mut Transcript::new(b"test"),
+            &test_case.value_commitments,
+            &test_case.target_commitment,
+            3,
+            |values| Ok(relation(values)),
+        )
+        .is_ok());
+    assert!(!proof.shifted_values.contains(&test_case.values[0]));
+}
+
+#[test]
+fn we_cannot_create_a_polynomial_relation_proof_if_the_relation_has_a_larger_degree_or_fails() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let test_case = random_test_case(&mut rng);
+    assert!(PolynomialRelationProof::create(
+        &mut Transcript::new(b"test"),
+        &test_case.values,
+        &test_case.value_blinds,
+        test_case.target_blind,
+        2,
+        |values| Some(relation(values)),
+        &mut rng,
+    )
+    .is_none());
+    assert!(PolynomialRelationProof::create(
+        &mut Transcript::new(b"test"),
+        &test_case.values,
+        &test_case.value_blinds,
+        test_case.target_blind,
+        3,
+        |_| None,
+        &mut rng,
+    )
+    .is_none());
+}
+
+#[test]
+fn we_cannot_verify_a_polynomial_relation_proof_with_the_wrong_inputs() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let test_case = random_test_case(&mut rng);
+    let proof = PolynomialRelationProof::create(
+        &mut Transcript::new(b"test"),
+        &test_case.values,
+        &test_case.value_blinds,
+        test_case.target_blind,
+        3,
+        |values| Some(relation(values)),
+        &mut rng,
+    )
+    .unwrap();
+    let verify = |proof: &PolynomialRelationProof,
+                  value_commitments: &[RistrettoPoint],
+                  target_commitment: &RistrettoPoint,
+                  label: &'static [u8]| {
+        proof.verify(
+            &mut Transcript::new(label),
+            value_commitments,
+            target_commitment,
+            3,
+            |values| Ok(relation(values)),
+        )
+    };
+    let g = compute_curve25519_generator(0);
+
+    // wrong target
+    assert!(verify(
+        &proof,
+        &test_case.value_commitments,
+        &(test_case.target_commitment + g),
+        b"test"
+    )
+    .is_err());
+    // wrong value
+    let mut value_commitments = test_case.value_commitments.clone();
+    value_commitments[1] += g;
+    assert!(verify(
+        &proof,
+        &value_commitments,
+        &test_case.target_commitment,
+        b"test"
+    )
+    .is_err());
+    // wrong number of values
+    assert!(verify(
+        &proof,
+        &test_case.value_commitments[..2],
+        &test_case.target_commitment,
+        b"test"
+    )
+    .is_err());
+    // wrong transcript
+    assert!(verify(
+        &proof,
+        &test_case.value_commitments,
+        &test_case.target_commitment,
+        b"other"
+    )
+    .is_err());
+    // failing relation
+    assert!(matches!(
+        proof.verify(
+            &mut Transcript::new(b"test"),
+            &test_case.value_commitments,
+            &test_case.target_commitment,
+            3,
+            |_| Err(ProofError::VerificationError { error: "failed" }),
+        ),
+        Err(ProofError::VerificationError { error: "failed" })
+    ));
+
+    // tampered proof
+    let mut tampered = proof.clone();
+    tampered.shifted_values[0] += Curve25519Scalar::ONE;
+    assert!(verify(
+        &tampered,
+        &test_case.value_commitments,
+        &test_case.target_commitment,
+        b"test"
+    )
+    .is_err());
+    let mut tampered = proof.clone();
+    tampered.shifted_relation_blind += Curve25519Scalar::ONE;
+    assert!(verify(
+        &tampered,
+        &test_case.value_commitments,
+        &test_case.target_commitment,
+        b"test"
+    )
+    .is_err());
+    let mut tampered = proof;
+    tampered.coefficient_commitments[0] =
+        (tampered.coefficient_commitments[0].decompress().unwrap() + g).compress();
+    assert!(verify(
+        &tampered,
+        &test_case.value_commitments,
+        &test_case.target_commitment,
+        b"test"
+    )
+    .is_err());
+}
@@ -0,0 +1,76 @@
+use super::compute_curve25519_generators;
+use crate::base::{commitment::CommittableColumn, if_rayon, scalar::Curve25519Scalar};
+use alloc::vec::Vec;
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::VartimeMultiscalarMul};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+fn compute_curve25519_commitment_impl<'a, T>(
+    column: &'a [T],
+    generators: &[RistrettoPoint],
+) -> RistrettoPoint
+where
+    &'a T: Into<Curve25519Scalar>,
+{
+    RistrettoPoint::vartime_multiscalar_mul(
+        column
+            .iter()
+            .map(|value| Scalar::from(Into::<Curve25519Scalar>::into(value))),
+        &generators[..column.len()],
+    )
+}
+
+fn compute_curve25519_commitment(
+    committable_column: &CommittableColumn,
+    generators: &[RistrettoPoint],
+) -> RistrettoPoint {
+    match committable_column {
+        CommittableColumn::Scalar(column)
+        | CommittableColumn::Decimal75(_, _, column)
+        | CommittableColumn::VarChar(column) => {
+            compute_curve25519_commitment_impl(column, generators)
+        }
+        CommittableColumn::TinyInt(column) => {
+            compute_curve25519_commitment_impl(column, generators)
+        }
+        CommittableColumn::SmallInt(column) => {
+            compute_curve25519_commitment_impl(column, generators)
+        }
+        CommittableColumn::Int(column) => compute_curve25519_commitment_impl(column, generators),
+        CommittableColumn::BigInt(column) | CommittableColumn::TimestampTZ(_, _, column) => {
+            compute_curve25519_commitment_impl(column, generators)
+        }
+        CommittableColumn::Int128(column) => compute_curve25519_commitment_impl(column, generators),
+        CommittableColumn::Boolean(column) => {
+            compute_curve25519_commitment_impl(column, generators)
+        }
+        CommittableColumn::RangeCheckWord(column) => {
+            compute_curve25519_commitment_impl(column, generators)
+        }
+    }
+}
+
+/// Compute the Pedersen commitments of the columns over Curve25519 without blitzar.
+///
+/// The commitment of a column `a` is `sum_i a[i] * G_{offset + i}`, where the generators are the
+/// ones computed by [`compute_curve25519_generators`]. Signed integers are committed to as their
+/// signed value, so the result matches the commitments computed by blitzar.
+#[tracing::instrument(
+    name = "compute_curve25519_commitments (cpu)",
+    level = "debug",
+    skip_all
+)]
+pub fn compute_curve25519_commitments(
+    committable_columns: &[CommittableColumn],
+    offset: usize,
+) -> Vec<RistrettoPoint> {
+    let max_length = committable_columns
+        .iter()
+        .map(CommittableColumn::len)
+        .max()
+        .unwrap_or(0);
+    let generators = compute_curve25519_generators(max_length, offset as u64);
+    if_rayon!(committable_columns.par_iter(), committable_columns.iter())
+        .map(|column| compute_curve25519_commitment(column, &generators))
+        .collect()
+}
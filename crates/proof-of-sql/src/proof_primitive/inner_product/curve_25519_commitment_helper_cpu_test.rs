@@ -0,0 +1,91 @@
+use super::{compute_curve25519_commitments, compute_curve25519_generators};
+use crate::base::{
+    commitment::CommittableColumn,
+    math::decimal::Precision,
+    scalar::{Curve25519Scalar, Scalar},
+};
+use curve25519_dalek::ristretto::RistrettoPoint;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+
+fn naive_commitment(values: &[Curve25519Scalar], offset: u64) -> RistrettoPoint {
+    values
+        .iter()
+        .zip(compute_curve25519_generators(values.len(), offset))
+        .map(|(value, generator)| curve25519_dalek::scalar::Scalar::from(*value) * generator)
+        .sum()
+}
+
+#[test]
+fn we_can_compute_curve25519_commitments_of_integer_columns() {
+    let res = compute_curve25519_commitments(
+        &[
+            CommittableColumn::TinyInt(&[0, -1, 2]),
+            CommittableColumn::SmallInt(&[3, -4]),
+            CommittableColumn::Int(&[-5]),
+            CommittableColumn::BigInt(&[6, 7, -8, 9]),
+            CommittableColumn::Int128(&[i128::MIN, i128::MAX]),
+        ],
+        2,
+    );
+    assert_eq!(
+        res,
+        vec![
+            naive_commitment(&[0.into(), (-1).into(), 2.into()], 2),
+            naive_commitment(&[3.into(), (-4).into()], 2),
+            naive_commitment(&[(-5).into()], 2),
+            naive_commitment(&[6.into(), 7.into(), (-8).into(), 9.into()], 2),
+            naive_commitment(&[i128::MIN.into(), i128::MAX.into()], 2),
+        ]
+    );
+}
+
+#[test]
+fn we_can_compute_curve25519_commitments_of_other_columns() {
+    let scalars = [Curve25519Scalar::from(5), Curve25519Scalar::MAX_SIGNED];
+    let res = compute_curve25519_commitments(
+        &[
+            CommittableColumn::Boolean(&[true, false, true]),
+            CommittableColumn::Scalar(vec![scalars[0].into(), scalars[1].into()]),
+            CommittableColumn::Decimal75(
+                Precision::new(10).unwrap(),
+                0,
+                vec![scalars[0].into(), scalars[1].into()],
+            ),
+            CommittableColumn::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, &[-1, 1]),
+        ],
+        0,
+    );
+    assert_eq!(
+        res,
+        vec![
+            naive_commitment(&[true.into(), false.into(), true.into()], 0),
+            naive_commitment(&scalars, 0),
+            naive_commitment(&scalars, 0),
+            naive_commitment(&[(-1).into(), 1.into()], 0),
+        ]
+    );
+}
+
+#[test]
+fn we_can_compute_empty_curve25519_commitments() {
+    assert!(compute_curve25519_commitments(&[], 0).is_empty());
+    assert_eq!(
+        compute_curve25519_commitments(&[CommittableColumn::BigInt(&[])], 3),
+        vec![RistrettoPoint::default()]
+    );
+}
+
+#[cfg(feature = "blitzar")]
+#[test]
+fn we_compute_the_same_commitments_as_blitzar() {
+    use crate::base::commitment::Commitment;
+    let columns = [
+        CommittableColumn::BigInt(&[1, -2, 3, i64::MIN]),
+        CommittableColumn::Boolean(&[true, false]),
+        CommittableColumn::Int128(&[i128::MAX]),
+    ];
+    assert_eq!(
+        compute_curve25519_commitments(&columns, 5),
+        RistrettoPoint::compute_commitments(&columns, 5, &())
+    );
+}
@@ -0,0 +1,65 @@
+use alloc::vec::Vec;
+use curve25519_dalek::ristretto::RistrettoPoint;
+use tiny_keccak::{Hasher, Keccak};
+
+/// The xorshift128+ pseudo-random number generator that blitzar seeds with the index of a generator.
+struct FastRandomNumberGenerator {
+    state: [u64; 2],
+}
+
+impl FastRandomNumberGenerator {
+    fn new(seed1: u64, seed2: u64) -> Self {
+        Self {
+            state: [seed1, seed2],
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut s1 = self.state[0];
+        let s0 = self.state[1];
+        self.state[0] = s0;
+        s1 ^= s1 << 23;
+        self.state[1] = s1 ^ s0 ^ (s1 >> 18) ^ (s0 >> 5);
+        self.state[1].wrapping_add(s0)
+    }
+}
+
+/// Compute the generator with the given index.
+///
+/// The generator is the ristretto point formed from 64 pseudo-random bytes, which are drawn from a
+/// [`FastRandomNumberGenerator`] seeded with `(index + 1, index + 2)`.
+/// This matches the generators used by blitzar.
+#[must_use]
+pub fn compute_curve25519_generator(index: u64) -> RistrettoPoint {
+    let mut rng = FastRandomNumberGenerator::new(index + 1, index + 2);
+    let mut bytes = [0u8; 64];
+    for chunk in bytes.chunks_exact_mut(8) {
+        chunk.copy_from_slice(&rng.next_u64().to_le_bytes());
+    }
+    RistrettoPoint::from_uniform_bytes(&bytes)
+}
+
+/// Compute the generators `G_{offset}, ..., G_{offset + length - 1}` that are used for commitments and
+/// inner product proofs over Curve25519.
+pub fn compute_curve25519_generators(length: usize, offset: u64) -> Vec<RistrettoPoint> {
+    (offset..offset + length as u64)
+        .map(compute_curve25519_generator)
+        .collect()
+}
+
+/// Compute the generator `H` that blinds hiding commitments over Curve25519.
+///
+/// `H` is the ristretto point formed from the 64 bytes `keccak256(label || 0) || keccak256(label || 1)`.
+/// Because it is derived by hashing rather than from the index of a generator, nobody knows its discrete
+/// logarithm with respect to the generators of [`compute_curve25519_generators`].
+#[must_use]
+pub fn compute_curve25519_blinding_generator() -> RistrettoPoint {
+    let mut bytes = [0u8; 64];
+    for (counter, chunk) in bytes.chunks_exact_mut(32).enumerate() {
+        let mut hasher = Keccak::v256();
+        hasher.update(b"proof-of-sql curve25519 blinding generator");
+        hasher.update(&[counter as u8]);
+        hasher.finalize(chunk);
+    }
+    RistrettoPoint::from_uniform_bytes(&bytes)
+}
@@ -0,0 +1,19 @@
+use super::{compute_curve25519_generator, compute_curve25519_generators};
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+#[test]
+fn we_compute_the_same_generators_as_blitzar() {
+    for (length, offset) in [(1, 0), (10, 0), (10, 5), (3, 1000)] {
+        let mut expected = vec![RistrettoPoint::default(); length];
+        blitzar::compute::get_curve25519_generators(&mut expected, offset);
+        assert_eq!(compute_curve25519_generators(length, offset), expected);
+    }
+}
+
+#[test]
+fn we_compute_each_generator_independently_of_the_others() {
+    let generators = compute_curve25519_generators(8, 4);
+    for (index, generator) in (4..).zip(generators) {
+        assert_eq!(compute_curve25519_generator(index), generator);
+    }
+}
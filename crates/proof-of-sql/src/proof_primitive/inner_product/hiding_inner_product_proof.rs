@@ -0,0 +1,199 @@
+use super::{
+    compute_curve25519_blinding_generator, compute_curve25519_generator,
+    compute_curve25519_generators,
+    inner_product_proof::{challenge_value, inner_product},
+    InnerProductProof, InnerProductProofError,
+};
+use alloc::vec::Vec;
+use ark_std::rand::{CryptoRng, RngCore};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::{IsIdentity, VartimeMultiscalarMul},
+};
+use serde::{Deserialize, Serialize};
+
+/// Compute the hiding commitment `<a, G> + blind * H` to `a`, where `G = compute_curve25519_generators(a.len(), generators_offset)`
+/// and `H = compute_curve25519_blinding_generator()`.
+///
+/// Unlike the commitments of [`compute_curve25519_commitments`](super::compute_curve25519_commitments), a hiding
+/// commitment reveals nothing about `a` as long as `blind` is uniformly random and kept secret.
+#[must_use]
+pub fn compute_curve25519_hiding_commitment(
+    a: &[Scalar],
+    blind: Scalar,
+    generators_offset: u64,
+) -> RistrettoPoint {
+    RistrettoPoint::vartime_multiscalar_mul(
+        a.iter().chain([blind].iter()),
+        compute_curve25519_generators(a.len(), generators_offset)
+            .into_iter()
+            .chain([compute_curve25519_blinding_generator()]),
+    )
+}
+
+/// Add the domain separator and the length of the vectors to the transcript.
+fn init_transcript(transcript: &mut merlin::Transcript, n: usize) {
+    transcript.append_message(b"dom-sep", b"hiding inner product proof v1");
+    transcript.append_u64(b"n", n as u64);
+}
+
+/// A zero-knowledge proof that `product = <a, b>`, where `a_commit = <a, G> + blind * H` is a hiding commitment
+/// (see [`compute_curve25519_hiding_commitment`]) and `product` is only known through its hiding commitment
+/// `product_commit = product * G[0] + product_blind * H`, i.e. the hiding commitment to `[product]` with offset `0`.
+///
+/// The proof reveals nothing about `a`, `blind`, `product` or `product_blind`. It wraps an [`InnerProductProof`] in a
+/// Schnorr-style masking step: the prover samples a uniformly random vector `d` and blinding factors `r_d` and `r_y`,
+/// and sends
+/// ```text
+/// mask_commit = <d, G> + r_d * H
+/// mask_product_commit = <d, b> * G[0] + r_y * H
+/// ```
+/// After receiving the challenge `c`, the prover sends
+/// ```text
+/// folded_product = c * product + <d, b>
+/// folded_blind = c * blind + r_d
+/// folded_product_blind = c * product_blind + r_y
+/// ```
+/// and both parties run the inner product proof for the folded vector `c * a + d`, which is uniformly random and
+/// independent of `a`, against
+/// ```text
+/// c * a_commit + mask_commit - folded_blind * H = <c * a + d, G>
+/// folded_product = <c * a + d, b>
+/// ```
+/// The verifier additionally checks that the folded product is the opening of the folded product commitment
+/// ```text
+/// c * product_commit + mask_product_commit = folded_product * G[0] + folded_product_blind * H
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HidingInnerProductProof {
+    pub(super) mask_commit: CompressedRistretto,
+    pub(super) mask_product_commit: CompressedRistretto,
+    pub(super) folded_product: Scalar,
+    pub(super) folded_blind: Scalar,
+    pub(super) folded_product_blind: Scalar,
+    pub(super) inner_product_proof: InnerProductProof,
+}
+
+impl HidingInnerProductProof {
+    /// Creates a zero-knowledge proof that `<a, b>` is the evaluation of the hiding commitment `<a, G> + blind * H`,
+    /// where the evaluation is committed to with the blinding factor `product_blind`.
+    ///
+    /// `rng` must be a cryptographically secure source of randomness, since the masks are drawn from it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` is empty or if `a` and `b` have different lengths.
+    #[allow(clippy::many_single_char_names)]
+    pub fn create(
+        transcript: &mut merlin::Transcript,
+        a: &[Scalar],
+        blind: Scalar,
+        b: &[Scalar],
+        product_blind: Scalar,
+        generators_offset: u64,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Self {
+        let n = a.len();
+        assert!(n > 0);
+        assert_eq!(n, b.len());
+        init_transcript(transcript, n);
+
+        let d: Vec<_> = core::iter::repeat_with(|| Scalar::random(rng))
+            .take(n)
+            .collect();
+        let mask_blind = Scalar::random(rng);
+        let mask_product_blind = Scalar::random(rng);
+        let mask_commit =
+            compute_curve25519_hiding_commitment(&d, mask_blind, generators_offset).compress();
+        let mask_product = inner_product(&d, b);
+        let mask_product_commit =
+            compute_curve25519_hiding_commitment(&[mask_product], mask_product_blind, 0).compress();
+        transcript.append_message(b"D", mask_commit.as_bytes());
+        transcript.append_message(b"Y_d", mask_product_commit.as_bytes());
+
+        let c = challenge_value(transcript, b"c");
+        let folded_product = c * inner_product(a, b) + mask_product;
+        let folded_blind = c * blind + mask_blind;
+        let folded_product_blind = c * product_blind + mask_product_blind;
+        transcript.append_message(b"y", folded_product.as_bytes());
+        transcript.append_message(b"r", folded_blind.as_bytes());
+        transcript.append_message(b"r_y", folded_product_blind.as_bytes());
+        let folded_a: Vec<_> = a.iter().zip(&d).map(|(a, d)| c * a + d).collect();
+        let inner_product_proof =
+            InnerProductProof::create(transcript, &folded_a, b, generators_offset);
+
+        Self {
+            mask_commit,
+            mask_product_commit,
+            folded_product,
+            folded_blind,
+            folded_product_blind,
+            inner_product_proof,
+        }
+    }
+
+    /// Verifies a zero-knowledge proof that the opening of `product_commit` is `<a, b>`, where `a_commit = <a, G> + blind * H`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is empty.
+    pub fn verify(
+        &self,
+        transcript: &mut merlin::Transcript,
+        a_commit: &RistrettoPoint,
+        product_commit: &RistrettoPoint,
+        b: &[Scalar],
+        generators_offset: u64,
+    ) -> Result<(), InnerProductProofError> {
+        let n = b.len();
+        assert!(n > 0);
+        init_transcript(transcript, n);
+
+        let mask_commit = self
+            .mask_commit
+            .decompress()
+            .ok_or(InnerProductProofError::VerificationError)?;
+        let mask_product_commit = self
+            .mask_product_commit
+            .decompress()
+            .ok_or(InnerProductProofError::VerificationError)?;
+        transcript.append_message(b"D", self.mask_commit.as_bytes());
+        transcript.append_message(b"Y_d", self.mask_product_commit.as_bytes());
+
+        let c = challenge_value(transcript, b"c");
+        transcript.append_message(b"y", self.folded_product.as_bytes());
+        transcript.append_message(b"r", self.folded_blind.as_bytes());
+        transcript.append_message(b"r_y", self.folded_product_blind.as_bytes());
+        let blinding_generator = compute_curve25519_blinding_generator();
+        // c * product_commit + mask_product_commit - folded_product * G[0] - folded_product_blind * H = 0
+        let folded_product_check = RistrettoPoint::vartime_multiscalar_mul(
+            [
+                c,
+                Scalar::ONE,
+                -self.folded_product,
+                -self.folded_product_blind,
+            ],
+            [
+                *product_commit,
+                mask_product_commit,
+                compute_curve25519_generator(0),
+                blinding_generator,
+            ],
+        );
+        if !folded_product_check.is_identity() {
+            return Err(InnerProductProofError::VerificationError);
+        }
+        let folded_commit = RistrettoPoint::vartime_multiscalar_mul(
+            [c, Scalar::ONE, -self.folded_blind],
+            [*a_commit, mask_commit, blinding_generator],
+        );
+        self.inner_product_proof.verify(
+            transcript,
+            &folded_commit,
+            &self.folded_product,
+            b,
+            generators_offset,
+        )
+    }
+}
@@ -0,0 +1,169 @@
+use super::{
+    compute_curve25519_blinding_generator, compute_curve25519_generators,
+    compute_curve25519_hiding_commitment, HidingInnerProductProof,
+};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::VartimeMultiscalarMul};
+use merlin::Transcript;
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+
+fn random_scalars(length: usize, rng: &mut impl RngCore) -> Vec<Scalar> {
+    core::iter::repeat_with(|| Scalar::from(rng.next_u64()))
+        .take(length)
+        .collect()
+}
+
+#[test]
+fn we_can_compute_hiding_commitments() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let a = random_scalars(5, &mut rng);
+    let blind = Scalar::from(rng.next_u64());
+    assert_eq!(
+        compute_curve25519_hiding_commitment(&a, blind, 3),
+        RistrettoPoint::vartime_multiscalar_mul(&a, compute_curve25519_generators(5, 3))
+            + blind * compute_curve25519_blinding_generator()
+    );
+    assert_ne!(
+        compute_curve25519_hiding_commitment(&a, blind, 3),
+        compute_curve25519_hiding_commitment(&a, blind + Scalar::ONE, 3)
+    );
+}
+
+#[test]
+fn the_blinding_generator_is_not_one_of_the_generators() {
+    let generators = compute_curve25519_generators(1000, 0);
+    assert!(!generators.contains(&compute_curve25519_blinding_generator()));
+    assert_eq!(
+        compute_curve25519_blinding_generator(),
+        compute_curve25519_blinding_generator()
+    );
+}
+
+#[test]
+fn we_can_create_and_verify_a_hiding_inner_product_proof() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for n in [1, 2, 3, 4, 5, 8, 13, 16, 33] {
+        for offset in [0, 7] {
+            let a = random_scalars(n, &mut rng);
+            let b = random_scalars(n, &mut rng);
+            let blind = Scalar::from(rng.next_u64());
+            let product_blind = Scalar::from(rng.next_u64());
+            let product: Scalar = a.iter().zip(&b).map(|(a, b)| a * b).sum();
+            let a_commit = compute_curve25519_hiding_commitment(&a, blind, offset);
+            let product_commit = compute_curve25519_hiding_commitment(&[product], product_blind, 0);
+
+            let proof = HidingInnerProductProof::create(
+                &mut Transcript::new(b"zk-ipa"),
+                &a,
+                blind,
+                &b,
+                product_blind,
+                offset,
+                &mut rng,
+            );
+            assert!(proof
+                .verify(
+                    &mut Transcript::new(b"zk-ipa"),
+                    &a_commit,
+                    &product_commit,
+                    &b,
+                    offset
+                )
+                .is_ok());
+        }
+    }
+}
+
+#[test]
+fn proofs_of_the_same_vector_do_not_repeat_the_folded_vector_or_product() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let a = random_scalars(4, &mut rng);
+    let b = random_scalars(4, &mut rng);
+    let mut create = || {
+        HidingInnerProductProof::create(
+            &mut Transcript::new(b"zk-ipa"),
+            &a,
+            Scalar::ONE,
+            &b,
+            Scalar::ONE,
+            0,
+            &mut rng,
+        )
+    };
+    let proof = create();
+    let other_proof = create();
+    assert_ne!(proof.mask_commit, other_proof.mask_commit);
+    assert_ne!(proof.folded_product, other_proof.folded_product);
+    assert_ne!(proof.folded_blind, other_proof.folded_blind);
+    assert_ne!(
+        proof.inner_product_proof.ap_value,
+        other_proof.inner_product_proof.ap_value
+    );
+}
+
+#[test]
+fn we_cannot_verify_a_hiding_inner_product_proof_with_the_wrong_inputs() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let a = random_scalars(6, &mut rng);
+    let b = random_scalars(6, &mut rng);
+    let blind = Scalar::from(rng.next_u64());
+    let product_blind = Scalar::from(rng.next_u64());
+    let product: Scalar = a.iter().zip(&b).map(|(a, b)| a * b).sum();
+    let a_commit = compute_curve25519_hiding_commitment(&a, blind, 2);
+    let product_commit = compute_curve25519_hiding_commitment(&[product], product_blind, 0);
+    let proof = HidingInnerProductProof::create(
+        &mut Transcript::new(b"zk-ipa"),
+        &a,
+        blind,
+        &b,
+        product_blind,
+        2,
+        &mut rng,
+    );
+    let verify = |proof: &HidingInnerProductProof,
+                  a_commit: &RistrettoPoint,
+                  product_commit: &RistrettoPoint,
+                  offset: u64| {
+        proof.verify(
+            &mut Transcript::new(b"zk-ipa"),
+            a_commit,
+            product_commit,
+            &b,
+            offset,
+        )
+    };
+    assert!(verify(&proof, &a_commit, &product_commit, 2).is_ok());
+
+    // wrong product
+    let wrong_product_commit =
+        compute_curve25519_hiding_commitment(&[product + Scalar::ONE], product_blind, 0);
+    assert!(verify(&proof, &a_commit, &wrong_product_commit, 2).is_err());
+    // wrong blinding factor of the product
+    let wrong_product_commit =
+        compute_curve25519_hiding_commitment(&[product], product_blind + Scalar::ONE, 0);
+    assert!(verify(&proof, &a_commit, &wrong_product_commit, 2).is_err());
+    // wrong blinding factor of the commitment
+    let wrong_a_commit = compute_curve25519_hiding_commitment(&a, blind + Scalar::ONE, 2);
+    assert!(verify(&proof, &wrong_a_commit, &product_commit, 2).is_err());
+    // wrong offset
+    assert!(verify(&proof, &a_commit, &product_commit, 3).is_err());
+    // wrong transcript
+    assert!(proof
+        .verify(
+            &mut Transcript::new(b"other"),
+            &a_commit,
+            &product_commit,
+            &b,
+            2
+        )
+        .is_err());
+    // tampered proof
+    let mut tampered_proof = proof.clone();
+    tampered_proof.folded_product += Scalar::ONE;
+    assert!(verify(&tampered_proof, &a_commit, &product_commit, 2).is_err());
+    let mut tampered_proof = proof.clone();
+    tampered_proof.folded_product_blind += Scalar::ONE;
+    assert!(verify(&tampered_proof, &a_commit, &product_commit, 2).is_err());
+    let mut tampered_proof = proof;
+    tampered_proof.folded_blind += Scalar::ONE;
+    assert!(verify(&tampered_proof, &a_commit, &product_commit, 2).is_err());
+}
@@ -0,0 +1,307 @@
+use super::compute_curve25519_generators;
+use crate::base::{
+    commitment::CommitmentEvaluationProof,
+    proof::Transcript,
+    scalar::{Curve25519Scalar, MontScalar, Scalar as _},
+    slice_ops,
+};
+use alloc::{vec, vec::Vec};
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::{Identity, IsIdentity, VartimeMultiscalarMul},
+};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+/// The error type for the inner product proof.
+#[derive(Snafu, Debug)]
+pub enum InnerProductProofError {
+    /// This error occurs when the proof fails to verify.
+    #[snafu(display("verification error"))]
+    VerificationError,
+}
+
+/// A pure-Rust implementation of the Bulletproofs-style inner product proof of blitzar.
+///
+/// The proof is created with respect to the generators `G = compute_curve25519_generators(np + 1, generators_offset)`,
+/// where `np` is the length of the vectors rounded up to a power of two, and `Q = G[np]`.
+/// Both the transcript and the layout of the proof follow blitzar, so a proof created by either
+/// implementation can be verified by the other.
+///
+/// In the `j`-th round, where `j` goes from `ceil(log2(n)) - 1` down to `0`, the prover sends
+/// ```text
+/// l_vector[j] = <a_lo, G_hi> + <a_lo, b_hi> * Q
+/// r_vector[j] = <a_hi, G_lo> + <a_hi, b_lo> * Q
+/// ```
+/// receives the challenge `u[j]` and folds the vectors with
+/// ```text
+/// a = a_lo * u[j] + u[j]^(-1) * a_hi
+/// b = b_lo * u[j]^(-1) + u[j] * b_hi
+/// G = G_lo * u[j]^(-1) + u[j] * G_hi
+/// ```
+/// After the last round, the prover sends `ap_value = a[0]`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InnerProductProof {
+    pub(super) l_vector: Vec<CompressedRistretto>,
+    pub(super) r_vector: Vec<CompressedRistretto>,
+    pub(super) ap_value: Scalar,
+}
+
+/// Add the domain separator and the length of the vectors to the transcript.
+fn init_transcript(transcript: &mut merlin::Transcript, n: usize) {
+    transcript.append_message(b"dom-sep", b"inner product proof v1");
+    transcript.append_u64(b"n", n as u64);
+}
+
+/// Draw a challenge from 64 bytes of the transcript reduced modulo the group order.
+pub(super) fn challenge_value(transcript: &mut merlin::Transcript, label: &'static [u8]) -> Scalar {
+    let mut bytes = [0u8; 64];
+    transcript.challenge_bytes(label, &mut bytes);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+pub(super) fn inner_product(a: &[Scalar], b: &[Scalar]) -> Scalar {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Compute `lo * lo_factor + hi * hi_factor` elementwise.
+fn fold_scalars(lo: &[Scalar], hi: &[Scalar], lo_factor: Scalar, hi_factor: Scalar) -> Vec<Scalar> {
+    lo.iter()
+        .zip(hi)
+        .map(|(lo, hi)| lo * lo_factor + hi * hi_factor)
+        .collect()
+}
+
+impl InnerProductProof {
+    /// Creates an inner product proof that `<a, b>` is the evaluation of the commitment `<a, G>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` is empty or if `a` and `b` have different lengths.
+    #[allow(clippy::many_single_char_names)]
+    pub fn create(
+        transcript: &mut merlin::Transcript,
+        a: &[Scalar],
+        b: &[Scalar],
+        generators_offset: u64,
+    ) -> Self {
+        let n = a.len();
+        assert!(n > 0);
+        assert_eq!(n, b.len());
+        let np = n.next_power_of_two();
+        let num_rounds = np.trailing_zeros() as usize;
+        init_transcript(transcript, n);
+
+        let mut g = compute_curve25519_generators(np + 1, generators_offset);
+        let q = g.pop().unwrap();
+        let mut a = [a, &vec![Scalar::ZERO; np - n]].concat();
+        let mut b = [b, &vec![Scalar::ZERO; np - n]].concat();
+
+        let mut l_vector = vec![CompressedRistretto::default(); num_rounds];
+        let mut r_vector = vec![CompressedRistretto::default(); num_rounds];
+        for j in (0..num_rounds).rev() {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = g.split_at(half);
+
+            let l_value = RistrettoPoint::vartime_multiscalar_mul(
+                a_lo.iter().chain([inner_product(a_lo, b_hi)].iter()),
+                g_hi.iter().chain([q].iter()),
+            )
+            .compress();
+            let r_value = RistrettoPoint::vartime_multiscalar_mul(
+                a_hi.iter().chain([inner_product(a_hi, b_lo)].iter()),
+                g_lo.iter().chain([q].iter()),
+            )
+            .compress();
+            transcript.append_message(b"L", l_value.as_bytes());
+            transcript.append_message(b"R", r_value.as_bytes());
+            l_vector[j] = l_value;
+            r_vector[j] = r_value;
+
+            let u = challenge_value(transcript, b"x");
+            let u_inv = u.invert();
+            let g_next = g_lo
+                .iter()
+                .zip(g_hi)
+                .map(|(lo, hi)| RistrettoPoint::vartime_multiscalar_mul([u_inv, u], [lo, hi]))
+                .collect();
+            a = fold_scalars(a_lo, a_hi, u, u_inv);
+            b = fold_scalars(b_lo, b_hi, u_inv, u);
+            g = g_next;
+        }
+
+        Self {
+            l_vector,
+            r_vector,
+            ap_value: a[0],
+        }
+    }
+
+    /// Verifies an inner product proof that `product` is `<a, b>`, where `a_commit = <a, G>`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is empty.
+    #[allow(clippy::many_single_char_names)]
+    pub fn verify(
+        &self,
+        transcript: &mut merlin::Transcript,
+        a_commit: &RistrettoPoint,
+        product: &Scalar,
+        b: &[Scalar],
+        generators_offset: u64,
+    ) -> Result<(), InnerProductProofError> {
+        let n = b.len();
+        assert!(n > 0);
+        let np = n.next_power_of_two();
+        let num_rounds = np.trailing_zeros() as usize;
+        if self.l_vector.len() != num_rounds || self.r_vector.len() != num_rounds {
+            return Err(InnerProductProofError::VerificationError);
+        }
+        init_transcript(transcript, n);
+
+        // The challenges in the order of the rounds, i.e. `u[k - 1], ..., u[0]`.
+        let challenges: Vec<_> = (0..num_rounds)
+            .rev()
+            .map(|j| {
+                transcript.append_message(b"L", self.l_vector[j].as_bytes());
+                transcript.append_message(b"R", self.r_vector[j].as_bytes());
+                challenge_value(transcript, b"x")
+            })
+            .collect();
+        let mut challenge_inverses = challenges.clone();
+        Scalar::batch_invert(&mut challenge_inverses);
+
+        // The coefficient of `G[i]` in the folded generator, which is also the coefficient of `b[i]`
+        // in the folded `b`. The first round corresponds to the most significant bit of `i`.
+        let s_vector: Vec<_> = (0..np)
+            .map(|i| {
+                challenges
+                    .iter()
+                    .zip(&challenge_inverses)
+                    .enumerate()
+                    .map(|(round, (u, u_inv))| {
+                        if (i >> (num_rounds - 1 - round)) & 1 == 1 {
+                            *u
+                        } else {
+                            *u_inv
+                        }
+                    })
+                    .product()
+            })
+            .collect();
+        let b_final = inner_product(&s_vector, b);
+
+        let l_points = self
+            .l_vector
+            .iter()
+            .rev()
+            .map(CompressedRistretto::decompress)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(InnerProductProofError::VerificationError)?;
+        let r_points = self
+            .r_vector
+            .iter()
+            .rev()
+            .map(CompressedRistretto::decompress)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(InnerProductProofError::VerificationError)?;
+        let mut g = compute_curve25519_generators(np + 1, generators_offset);
+        let q = g.pop().unwrap();
+
+        // a_commit + product * Q + sum(u^2 * L + u^(-2) * R) = ap * G_final + ap * b_final * Q
+        let scalars = [Scalar::ONE, product - self.ap_value * b_final]
+            .into_iter()
+            .chain(challenges.iter().map(|u| u * u))
+            .chain(challenge_inverses.iter().map(|u_inv| u_inv * u_inv))
+            .chain(s_vector.iter().map(|s| -self.ap_value * s));
+        let points = [*a_commit, q]
+            .into_iter()
+            .chain(l_points)
+            .chain(r_points)
+            .chain(g);
+        if RistrettoPoint::vartime_multiscalar_mul(scalars, points).is_identity() {
+            Ok(())
+        } else {
+            Err(InnerProductProofError::VerificationError)
+        }
+    }
+}
+
+impl CommitmentEvaluationProof for InnerProductProof {
+    type Scalar = Curve25519Scalar;
+    type Commitment = RistrettoPoint;
+    type Error = InnerProductProofError;
+    type ProverPublicSetup<'a> = ();
+    type VerifierPublicSetup<'a> = ();
+    #[tracing::instrument(name = "InnerProductProof::new (cpu)", level = "debug", skip_all)]
+    fn new(
+        transcript: &mut impl Transcript,
+        a: &[Self::Scalar],
+        b_point: &[Self::Scalar],
+        generators_offset: u64,
+        _setup: &Self::ProverPublicSetup<'_>,
+    ) -> Self {
+        assert!(!a.is_empty());
+        let b = &mut vec![MontScalar::default(); a.len()];
+        if b_point.is_empty() {
+            assert_eq!(b.len(), 1);
+            b[0] = Self::Scalar::ONE;
+        } else {
+            crate::base::polynomial::compute_evaluation_vector(b, b_point);
+        }
+        // The transcript of the inner product proof is a merlin transcript, so that it matches blitzar.
+        transcript.wrap_transcript(|transcript| {
+            Self::create(
+                transcript,
+                &slice_ops::slice_cast(a),
+                &slice_ops::slice_cast(b),
+                generators_offset,
+            )
+        })
+    }
+
+    #[tracing::instrument(
+        name = "InnerProductProof::verify_batched_proof (cpu)",
+        level = "debug",
+        skip_all
+    )]
+    fn verify_batched_proof(
+        &self,
+        transcript: &mut impl Transcript,
+        commit_batch: &[Self::Commitment],
+        batching_factors: &[Self::Scalar],
+        product: &Self::Scalar,
+        b_point: &[Self::Scalar],
+        generators_offset: u64,
+        table_length: usize,
+        _setup: &Self::VerifierPublicSetup<'_>,
+    ) -> Result<(), Self::Error> {
+        assert!(table_length > 0);
+        let b = &mut vec![MontScalar::default(); table_length];
+        if b_point.is_empty() {
+            assert_eq!(b.len(), 1);
+            b[0] = Self::Scalar::ONE;
+        } else {
+            crate::base::polynomial::compute_evaluation_vector(b, b_point);
+        }
+        let a_commit = commit_batch
+            .iter()
+            .zip(batching_factors)
+            .map(|(c, m)| *m * c)
+            .fold(RistrettoPoint::identity(), |a, c| a + c);
+        // The transcript of the inner product proof is a merlin transcript, so that it matches blitzar.
+        transcript.wrap_transcript(|transcript| {
+            self.verify(
+                transcript,
+                &a_commit,
+                &product.into(),
+                &slice_ops::slice_cast(b),
+                generators_offset,
+            )
+        })
+    }
+}
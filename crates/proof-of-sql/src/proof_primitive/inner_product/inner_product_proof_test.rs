@@ -0,0 +1,143 @@
+use super::{compute_curve25519_generators, InnerProductProof};
+use crate::base::commitment::commitment_evaluation_proof_test::{
+    test_commitment_evaluation_proof_with_length_1, test_random_commitment_evaluation_proof,
+    test_simple_commitment_evaluation_proof,
+};
+use curve25519_dalek::{ristretto::RistrettoPoint, scalar::Scalar, traits::VartimeMultiscalarMul};
+use merlin::Transcript;
+
+fn random_scalars(length: usize, rng: &mut impl rand::RngCore) -> Vec<Scalar> {
+    core::iter::repeat_with(|| Scalar::from(rng.next_u64()))
+        .take(length)
+        .collect()
+}
+
+#[test]
+fn we_can_create_and_verify_an_inner_product_proof() {
+    let mut rng = ark_std::test_rng();
+    for n in [1, 2, 3, 4, 5, 8, 13, 16, 33] {
+        for offset in [0, 7] {
+            let a = random_scalars(n, &mut rng);
+            let b = random_scalars(n, &mut rng);
+            let product: Scalar = a.iter().zip(&b).map(|(a, b)| a * b).sum();
+            let a_commit = RistrettoPoint::vartime_multiscalar_mul(
+                &a,
+                compute_curve25519_generators(n, offset),
+            );
+
+            let proof = InnerProductProof::create(&mut Transcript::new(b"ipa"), &a, &b, offset);
+            assert_eq!(
+                proof.l_vector.len(),
+                n.next_power_of_two().trailing_zeros() as usize
+            );
+            assert!(proof
+                .verify(
+                    &mut Transcript::new(b"ipa"),
+                    &a_commit,
+                    &product,
+                    &b,
+                    offset
+                )
+                .is_ok());
+        }
+    }
+}
+
+#[test]
+fn we_cannot_verify_an_inner_product_proof_with_the_wrong_inputs() {
+    let mut rng = ark_std::test_rng();
+    let a = random_scalars(6, &mut rng);
+    let b = random_scalars(6, &mut rng);
+    let product: Scalar = a.iter().zip(&b).map(|(a, b)| a * b).sum();
+    let a_commit = RistrettoPoint::vartime_multiscalar_mul(&a, compute_curve25519_generators(6, 2));
+    let proof = InnerProductProof::create(&mut Transcript::new(b"ipa"), &a, &b, 2);
+
+    // wrong product
+    assert!(proof
+        .verify(
+            &mut Transcript::new(b"ipa"),
+            &a_commit,
+            &(product + Scalar::ONE),
+            &b,
+            2
+        )
+        .is_err());
+    // wrong offset
+    assert!(proof
+        .verify(&mut Transcript::new(b"ipa"), &a_commit, &product, &b, 3)
+        .is_err());
+    // wrong transcript
+    assert!(proof
+        .verify(&mut Transcript::new(b"other"), &a_commit, &product, &b, 2)
+        .is_err());
+    // wrong length
+    assert!(proof
+        .verify(
+            &mut Transcript::new(b"ipa"),
+            &a_commit,
+            &product,
+            &[b.clone(), vec![Scalar::ZERO; 4]].concat(),
+            2
+        )
+        .is_err());
+    // tampered proof
+    let mut tampered_proof = proof.clone();
+    tampered_proof.ap_value += Scalar::ONE;
+    assert!(tampered_proof
+        .verify(&mut Transcript::new(b"ipa"), &a_commit, &product, &b, 2)
+        .is_err());
+    let mut tampered_proof = proof;
+    tampered_proof.l_vector.swap(0, 1);
+    assert!(tampered_proof
+        .verify(&mut Transcript::new(b"ipa"), &a_commit, &product, &b, 2)
+        .is_err());
+}
+
+#[test]
+fn test_simple_cpu_ipa() {
+    test_simple_commitment_evaluation_proof::<InnerProductProof>(&(), &());
+}
+
+#[test]
+fn test_random_cpu_ipa_with_length_1() {
+    test_commitment_evaluation_proof_with_length_1::<InnerProductProof>(&(), &());
+}
+
+#[test]
+fn test_random_cpu_ipa_with_various_lengths() {
+    for length in [2, 3, 4, 5, 8, 10, 16, 20, 32, 50, 64, 100, 128] {
+        test_random_commitment_evaluation_proof::<InnerProductProof>(length, 0, &(), &());
+        test_random_commitment_evaluation_proof::<InnerProductProof>(length, 1, &(), &());
+        test_random_commitment_evaluation_proof::<InnerProductProof>(length, 10, &(), &());
+    }
+}
+
+#[cfg(feature = "blitzar")]
+#[test]
+fn we_can_verify_blitzar_proofs_with_the_cpu_verifier_and_vice_versa() {
+    let mut rng = ark_std::test_rng();
+    for n in [1, 2, 3, 4, 7, 16, 33] {
+        let a = random_scalars(n, &mut rng);
+        let b = random_scalars(n, &mut rng);
+        let product: Scalar = a.iter().zip(&b).map(|(a, b)| a * b).sum();
+        let a_commit =
+            RistrettoPoint::vartime_multiscalar_mul(&a, compute_curve25519_generators(n, 3));
+
+        let blitzar_proof =
+            blitzar::proof::InnerProductProof::create(&mut Transcript::new(b"ipa"), &a, &b, 3);
+        let cpu_proof = InnerProductProof::create(&mut Transcript::new(b"ipa"), &a, &b, 3);
+        let blitzar_bytes = postcard::to_allocvec(&blitzar_proof).unwrap();
+        let cpu_bytes = postcard::to_allocvec(&cpu_proof).unwrap();
+        assert_eq!(cpu_bytes, blitzar_bytes);
+
+        let cpu_proof: InnerProductProof = postcard::from_bytes(&blitzar_bytes).unwrap();
+        assert!(cpu_proof
+            .verify(&mut Transcript::new(b"ipa"), &a_commit, &product, &b, 3)
+            .is_ok());
+        let blitzar_proof: blitzar::proof::InnerProductProof =
+            postcard::from_bytes(&cpu_bytes).unwrap();
+        assert!(blitzar_proof
+            .verify(&mut Transcript::new(b"ipa"), &a_commit, &product, &b, 3)
+            .is_ok());
+    }
+}
@@ -0,0 +1,33 @@
+//! This module contains a pure-Rust implementation of the Curve25519 commitments and the inner product proof of blitzar.
+//!
+//! The generators, the commitments, and the proofs are compatible with the ones computed by blitzar,
+//! so they can be used when blitzar is unavailable, e.g. in `no_std` environments or on the verifier side.
+
+mod curve_25519_generators;
+pub use curve_25519_generators::{
+    compute_curve25519_blinding_generator, compute_curve25519_generator,
+    compute_curve25519_generators,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod curve_25519_generators_test;
+
+mod curve_25519_commitment_helper_cpu;
+pub use curve_25519_commitment_helper_cpu::compute_curve25519_commitments;
+#[cfg(test)]
+mod curve_25519_commitment_helper_cpu_test;
+
+#[cfg(feature = "std")]
+mod inner_product_proof;
+#[cfg(feature = "std")]
+pub use inner_product_proof::{InnerProductProof, InnerProductProofError};
+#[cfg(all(test, feature = "std"))]
+mod inner_product_proof_test;
+
+#[cfg(feature = "std")]
+mod hiding_inner_product_proof;
+#[cfg(feature = "std")]
+pub use hiding_inner_product_proof::{
+    compute_curve25519_hiding_commitment, HidingInnerProductProof,
+};
+#[cfg(all(test, feature = "std"))]
+mod hiding_inner_product_proof_test;
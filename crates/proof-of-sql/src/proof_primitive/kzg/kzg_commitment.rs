@@ -0,0 +1,157 @@
+//! Module containing the `KzgCommitment` type and its implementation.
+//!
+//! The commitment to a column with offset `o` is the commitment to the column padded with `o` zeros at the
+//! front, i.e. `sum_i a[i] * eq(tau, i + o) * G`. This allows for the updateability of the commitments, see
+//! [`VecCommitmentExt`](crate::base::commitment::VecCommitmentExt).
+
+use super::{G1Affine, G1Projective, KzgPublicParameters};
+use crate::base::{
+    commitment::{Commitment, CommittableColumn},
+    scalar::{MontScalar, Scalar},
+};
+use alloc::vec::Vec;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, PrimeField};
+use core::ops::Mul;
+use derive_more::{AddAssign, Neg, Sub, SubAssign};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The BN254 scalar type. (alias for `MontScalar<ark_bn254::FrConfig>`)
+pub type BNScalar = MontScalar<ark_bn254::FrConfig>;
+
+impl Scalar for BNScalar {
+    const MAX_SIGNED: Self = Self(ark_ff::MontFp!(
+        "10944121435919637611123202872628637544274182200208017171849102093287904247808"
+    ));
+    const ZERO: Self = Self(ark_ff::MontFp!("0"));
+    const ONE: Self = Self(ark_ff::MontFp!("1"));
+    const TWO: Self = Self(ark_ff::MontFp!("2"));
+}
+
+#[derive(Debug, Sub, Eq, PartialEq, Neg, Copy, Clone, Default, AddAssign, SubAssign)]
+/// The multilinear KZG commitment type, a point of BN254 G1.
+pub struct KzgCommitment(pub(super) G1Projective);
+
+impl KzgCommitment {
+    /// The affine coordinates `(x, y)` of the commitment as big endian words, where the identity is `(0, 0)`.
+    ///
+    /// This is the encoding of G1 points of the EVM precompiles.
+    #[must_use]
+    pub fn to_be_words(&self) -> [[u8; 32]; 2] {
+        let point = self.0.into_affine();
+        let mut words = [[0; 32]; 2];
+        if let Some((x, y)) = point.xy() {
+            words[0].copy_from_slice(&x.into_bigint().to_bytes_be());
+            words[1].copy_from_slice(&y.into_bigint().to_bytes_be());
+        }
+        words
+    }
+
+    /// The inverse of [`Self::to_be_words`], which fails for coordinates that aren't a point of G1.
+    #[must_use]
+    pub fn try_from_be_words(words: &[[u8; 32]; 2]) -> Option<Self> {
+        if words == &[[0; 32]; 2] {
+            return Some(Self::default());
+        }
+        let [x, y] = words.map(|word| ark_bn254::Fq::from_be_bytes_mod_order(&word));
+        let point = G1Affine::new_unchecked(x, y);
+        // G1 of BN254 has cofactor one, so every point on the curve is in the group.
+        (words == &Self(point.into()).to_be_words() && point.is_on_curve())
+            .then_some(Self(point.into()))
+    }
+}
+
+/// Commitments are serialized as the 64 bytes of [`KzgCommitment::to_be_words`], so that they are cheap to
+/// handle on the EVM.
+impl Serialize for KzgCommitment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_be_words().concat().serialize(serializer)
+    }
+}
+impl<'de> Deserialize<'de> for KzgCommitment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        let words: [[u8; 32]; 2] = [
+            bytes
+                .get(..32)
+                .and_then(|word| word.try_into().ok())
+                .ok_or_else(|| serde::de::Error::custom("invalid length"))?,
+            bytes
+                .get(32..)
+                .and_then(|word| word.try_into().ok())
+                .ok_or_else(|| serde::de::Error::custom("invalid length"))?,
+        ];
+        Self::try_from_be_words(&words).ok_or_else(|| serde::de::Error::custom("invalid point"))
+    }
+}
+
+// Traits required for `KzgCommitment` to impl `Commitment`.
+impl Mul<KzgCommitment> for BNScalar {
+    type Output = KzgCommitment;
+    fn mul(self, rhs: KzgCommitment) -> Self::Output {
+        KzgCommitment(rhs.0 * self.0)
+    }
+}
+impl<'a> Mul<&'a KzgCommitment> for BNScalar {
+    type Output = KzgCommitment;
+    fn mul(self, rhs: &'a KzgCommitment) -> Self::Output {
+        KzgCommitment(rhs.0 * self.0)
+    }
+}
+
+fn compute_kzg_commitment_impl<'a, T>(
+    column: &'a [T],
+    offset: usize,
+    setup: &KzgPublicParameters,
+) -> KzgCommitment
+where
+    &'a T: Into<BNScalar>,
+{
+    let bases = &setup.lagrange_bases[setup.max_nu()][offset..offset + column.len()];
+    let scalars: Vec<_> = column.iter().map(|v| v.into().0).collect();
+    KzgCommitment(G1Projective::msm_unchecked(bases, &scalars))
+}
+
+fn compute_kzg_commitment(
+    committable_column: &CommittableColumn,
+    offset: usize,
+    setup: &KzgPublicParameters,
+) -> KzgCommitment {
+    match committable_column {
+        CommittableColumn::Scalar(column)
+        | CommittableColumn::VarChar(column)
+        | CommittableColumn::Decimal75(_, _, column) => {
+            compute_kzg_commitment_impl(column, offset, setup)
+        }
+        CommittableColumn::TinyInt(column) => compute_kzg_commitment_impl(column, offset, setup),
+        CommittableColumn::SmallInt(column) => compute_kzg_commitment_impl(column, offset, setup),
+        CommittableColumn::Int(column) => compute_kzg_commitment_impl(column, offset, setup),
+        CommittableColumn::BigInt(column) | CommittableColumn::TimestampTZ(_, _, column) => {
+            compute_kzg_commitment_impl(column, offset, setup)
+        }
+        CommittableColumn::Int128(column) => compute_kzg_commitment_impl(column, offset, setup),
+        CommittableColumn::Boolean(column) => compute_kzg_commitment_impl(column, offset, setup),
+        CommittableColumn::RangeCheckWord(column) => {
+            compute_kzg_commitment_impl(column, offset, setup)
+        }
+    }
+}
+
+impl Commitment for KzgCommitment {
+    type Scalar = BNScalar;
+    type PublicSetup<'a> = &'a KzgPublicParameters;
+
+    /// # Panics
+    ///
+    /// Panics if a column doesn't fit in the setup, i.e. if `offset` plus its length is more than `2^max_nu`.
+    fn compute_commitments(
+        committable_columns: &[CommittableColumn],
+        offset: usize,
+        setup: &Self::PublicSetup<'_>,
+    ) -> Vec<Self> {
+        committable_columns
+            .iter()
+            .map(|column| compute_kzg_commitment(column, offset, setup))
+            .collect()
+    }
+}
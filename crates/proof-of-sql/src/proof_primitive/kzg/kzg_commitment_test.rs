@@ -0,0 +1,146 @@
+use super::{BNScalar, KzgCommitment, KzgPublicParameters, F};
+use crate::base::{
+    commitment::{Commitment, CommittableColumn},
+    math::decimal::Precision,
+};
+use ark_std::test_rng;
+use proof_of_sql_parser::posql_time::{PoSQLTimeUnit, PoSQLTimeZone};
+
+fn expected_commitment(
+    public_parameters: &KzgPublicParameters,
+    values: &[F],
+    offset: usize,
+) -> KzgCommitment {
+    let bases = &public_parameters.lagrange_bases[public_parameters.max_nu()];
+    KzgCommitment(
+        values
+            .iter()
+            .zip(&bases[offset..])
+            .map(|(v, g)| *g * v)
+            .sum(),
+    )
+}
+
+#[test]
+fn we_can_compute_kzg_commitments_of_all_column_types() {
+    let public_parameters = KzgPublicParameters::test_rand(3, &mut test_rng());
+    let res = KzgCommitment::compute_commitments(
+        &[
+            CommittableColumn::Boolean(&[true, false, true]),
+            CommittableColumn::TinyInt(&[0, -1, 2]),
+            CommittableColumn::SmallInt(&[0, -1, 2]),
+            CommittableColumn::Int(&[0, -1, 2]),
+            CommittableColumn::BigInt(&[0, -1, 2]),
+            CommittableColumn::Int128(&[0, -1, 2]),
+            CommittableColumn::Decimal75(
+                Precision::new(1).unwrap(),
+                0,
+                vec![[0, 0, 0, 0], [1, 0, 0, 0], [2, 0, 0, 0]],
+            ),
+            CommittableColumn::Scalar(vec![[0, 0, 0, 0], [1, 0, 0, 0], [2, 0, 0, 0]]),
+            CommittableColumn::TimestampTZ(PoSQLTimeUnit::Second, PoSQLTimeZone::Utc, &[0, -1, 2]),
+            CommittableColumn::RangeCheckWord(&[0, 1, 2]),
+        ],
+        0,
+        &&public_parameters,
+    );
+    let booleans = expected_commitment(&public_parameters, &[1.into(), 0.into(), 1.into()], 0);
+    let signed = expected_commitment(&public_parameters, &[0.into(), (-1).into(), 2.into()], 0);
+    let unsigned = expected_commitment(&public_parameters, &[0.into(), 1.into(), 2.into()], 0);
+    assert_eq!(
+        res,
+        vec![
+            booleans, signed, signed, signed, signed, signed, unsigned, unsigned, signed, unsigned
+        ]
+    );
+}
+
+#[test]
+fn we_can_compute_kzg_commitments_with_an_offset() {
+    let public_parameters = KzgPublicParameters::test_rand(3, &mut test_rng());
+    let res = KzgCommitment::compute_commitments(
+        &[CommittableColumn::BigInt(&[3, -4, 5])],
+        4,
+        &&public_parameters,
+    );
+    let expected = expected_commitment(&public_parameters, &[3.into(), (-4).into(), 5.into()], 4);
+    assert_eq!(res, vec![expected]);
+}
+
+#[test]
+fn we_can_compute_kzg_commitments_of_empty_columns() {
+    let public_parameters = KzgPublicParameters::test_rand(3, &mut test_rng());
+    let res = KzgCommitment::compute_commitments(
+        &[CommittableColumn::BigInt(&[])],
+        2,
+        &&public_parameters,
+    );
+    assert_eq!(res, vec![KzgCommitment::default()]);
+}
+
+#[test]
+fn we_can_append_to_kzg_commitments() {
+    let public_parameters = KzgPublicParameters::test_rand(3, &mut test_rng());
+    let full = KzgCommitment::compute_commitments(
+        &[CommittableColumn::BigInt(&[3, -4, 5, 6])],
+        0,
+        &&public_parameters,
+    );
+    let head = KzgCommitment::compute_commitments(
+        &[CommittableColumn::BigInt(&[3, -4])],
+        0,
+        &&public_parameters,
+    );
+    let tail = KzgCommitment::compute_commitments(
+        &[CommittableColumn::BigInt(&[5, 6])],
+        2,
+        &&public_parameters,
+    );
+    let mut appended = head[0];
+    appended += tail[0];
+    assert_eq!(appended, full[0]);
+    assert_eq!(
+        BNScalar::from(2) * full[0] - full[0],
+        full[0],
+        "scalar multiplication and subtraction should be consistent"
+    );
+}
+
+#[test]
+fn we_can_serialize_and_deserialize_kzg_commitments() {
+    let public_parameters = KzgPublicParameters::test_rand(3, &mut test_rng());
+    let commitments = KzgCommitment::compute_commitments(
+        &[
+            CommittableColumn::BigInt(&[3, -4, 5]),
+            CommittableColumn::BigInt(&[]),
+        ],
+        0,
+        &&public_parameters,
+    );
+    for commitment in commitments {
+        let bytes = postcard::to_allocvec(&commitment).unwrap();
+        assert_eq!(bytes.len(), 65);
+        assert_eq!(bytes[0], 64);
+        assert_eq!(bytes[1..33], commitment.to_be_words()[0]);
+        assert_eq!(bytes[33..], commitment.to_be_words()[1]);
+        let deserialized: KzgCommitment = postcard::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, commitment);
+    }
+}
+
+#[test]
+fn we_cannot_deserialize_kzg_commitments_that_are_not_on_the_curve() {
+    let mut bytes = vec![64_u8];
+    bytes.extend([0; 63]);
+    bytes.push(1);
+    assert!(postcard::from_bytes::<KzgCommitment>(&bytes).is_err());
+
+    let mut bytes = vec![63_u8];
+    bytes.extend([0; 63]);
+    assert!(postcard::from_bytes::<KzgCommitment>(&bytes).is_err());
+
+    // The x coordinate is not reduced modulo the base field.
+    let mut bytes = vec![64_u8];
+    bytes.extend([0xff; 64]);
+    assert!(postcard::from_bytes::<KzgCommitment>(&bytes).is_err());
+}
@@ -0,0 +1,142 @@
+use super::{
+    BNScalar, G1Affine, G1Projective, KzgCommitment, KzgPublicParameters, KzgVerifierSetup, F,
+};
+use crate::base::{commitment::CommitmentEvaluationProof, proof::Transcript};
+use alloc::{vec, vec::Vec};
+use ark_bn254::Bn254;
+use ark_ec::{pairing::Pairing, CurveGroup, VariableBaseMSM};
+use ark_ff::Zero;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+/// The `CommitmentEvaluationProof` for the multilinear KZG PCS.
+///
+/// The proof consists of the commitments to the quotients `q_j`, one for each variable of the setup.
+#[derive(Default, Clone, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct KzgEvaluationProof {
+    pub(crate) quotient_commitments: Vec<KzgCommitment>,
+}
+
+/// The error type for the KZG PCS.
+#[derive(Snafu, Debug)]
+pub enum KzgError {
+    /// This error occurs when the generators offset is invalid.
+    #[snafu(display("invalid generators offset: {offset}"))]
+    InvalidGeneratorsOffset {
+        /// The invalid offset.
+        offset: u64,
+    },
+    /// This error occurs when the proof fails to verify.
+    #[snafu(display("verification error"))]
+    VerificationError,
+    /// This error occurs when the setup is too small.
+    #[snafu(display("setup is too small: the setup is {actual}, but the proof requires a setup of size {required}"))]
+    SmallSetup {
+        /// The number of variables of the setup.
+        actual: usize,
+        /// The number of variables of the proven evaluation.
+        required: usize,
+    },
+}
+
+impl CommitmentEvaluationProof for KzgEvaluationProof {
+    type Scalar = BNScalar;
+    type Commitment = KzgCommitment;
+    type Error = KzgError;
+    type ProverPublicSetup<'a> = &'a KzgPublicParameters;
+    type VerifierPublicSetup<'a> = &'a KzgVerifierSetup;
+
+    #[tracing::instrument(name = "KzgEvaluationProof::new", level = "debug", skip_all)]
+    fn new(
+        _transcript: &mut impl Transcript,
+        a: &[Self::Scalar],
+        b_point: &[Self::Scalar],
+        generators_offset: u64,
+        setup: &Self::ProverPublicSetup<'_>,
+    ) -> Self {
+        let max_nu = setup.max_nu();
+        if generators_offset != 0 || b_point.len() > max_nu || a.len() > 1 << b_point.len() {
+            // TODO: support offsets other than 0.
+            // Note: this will always result in a verification error.
+            return Self::default();
+        }
+        let mut table: Vec<F> = a.iter().map(|v| v.0).collect();
+        table.resize(1 << max_nu, F::zero());
+        let quotient_commitments = (0..max_nu)
+            .map(|j| {
+                let z = b_point.get(j).map_or(F::zero(), |z| z.0);
+                let quotient: Vec<F> = table.chunks_exact(2).map(|t| t[1] - t[0]).collect();
+                table = table
+                    .chunks_exact(2)
+                    .zip(&quotient)
+                    .map(|(t, q)| t[0] + z * q)
+                    .collect();
+                KzgCommitment(G1Projective::msm_unchecked(
+                    &setup.lagrange_bases[max_nu - j - 1],
+                    &quotient,
+                ))
+            })
+            .collect();
+        Self {
+            quotient_commitments,
+        }
+    }
+
+    #[tracing::instrument(
+        name = "KzgEvaluationProof::verify_batched_proof",
+        level = "debug",
+        skip_all
+    )]
+    fn verify_batched_proof(
+        &self,
+        _transcript: &mut impl Transcript,
+        commit_batch: &[Self::Commitment],
+        batching_factors: &[Self::Scalar],
+        product: &Self::Scalar,
+        b_point: &[Self::Scalar],
+        generators_offset: u64,
+        _table_length: usize,
+        setup: &Self::VerifierPublicSetup<'_>,
+    ) -> Result<(), Self::Error> {
+        if generators_offset != 0 {
+            return Err(KzgError::InvalidGeneratorsOffset {
+                offset: generators_offset,
+            });
+        }
+        let max_nu = setup.max_nu();
+        if b_point.len() > max_nu {
+            return Err(KzgError::SmallSetup {
+                actual: max_nu,
+                required: b_point.len(),
+            });
+        }
+        if self.quotient_commitments.len() != max_nu {
+            return Err(KzgError::VerificationError);
+        }
+        let quotients: Vec<G1Affine> = G1Projective::normalize_batch(
+            &self
+                .quotient_commitments
+                .iter()
+                .map(|c| c.0)
+                .collect::<Vec<_>>(),
+        );
+        let mut z: Vec<F> = b_point.iter().map(|z| z.0).collect();
+        z.resize(max_nu, F::zero());
+        let lhs = commit_batch
+            .iter()
+            .zip(batching_factors)
+            .map(|(c, m)| c.0 * m.0)
+            .sum::<G1Projective>()
+            - setup.g * product.0
+            + G1Projective::msm_unchecked(&quotients, &z);
+        let mut g1_points = vec![lhs.into_affine()];
+        g1_points.extend(quotients.iter().map(|q| -*q));
+        let mut g2_points = vec![setup.h];
+        g2_points.extend(&setup.tau_h);
+        if Bn254::multi_pairing(g1_points, g2_points).is_zero() {
+            Ok(())
+        } else {
+            Err(KzgError::VerificationError)
+        }
+    }
+}
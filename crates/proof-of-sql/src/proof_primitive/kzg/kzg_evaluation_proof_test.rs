@@ -0,0 +1,193 @@
+use super::{BNScalar, KzgError, KzgEvaluationProof, KzgPublicParameters, KzgVerifierSetup};
+use crate::base::{
+    commitment::{
+        commitment_evaluation_proof_test::*, CommitmentEvaluationProof, VecCommitmentExt,
+    },
+    database::Column,
+    polynomial::compute_evaluation_vector,
+};
+use ark_std::{
+    rand::{rngs::StdRng, SeedableRng},
+    test_rng, UniformRand,
+};
+use merlin::Transcript;
+
+#[test]
+fn test_simple_kzg_proof() {
+    let public_parameters = KzgPublicParameters::test_rand(4, &mut test_rng());
+    let verifier_setup = KzgVerifierSetup::from(&public_parameters);
+    test_simple_commitment_evaluation_proof::<KzgEvaluationProof>(
+        &&public_parameters,
+        &&verifier_setup,
+    );
+}
+
+#[test]
+fn test_random_kzg_proof_with_length_1() {
+    let public_parameters = KzgPublicParameters::test_rand(4, &mut test_rng());
+    let verifier_setup = KzgVerifierSetup::from(&public_parameters);
+    test_commitment_evaluation_proof_with_length_1::<KzgEvaluationProof>(
+        &&public_parameters,
+        &&verifier_setup,
+    );
+}
+
+/// Proves the evaluation of a random column of length `table_length` at a random point and checks it.
+///
+/// If the proof verifies, this also asserts that it fails to verify with a wrong product or offset.
+///
+/// Unlike `test_random_commitment_evaluation_proof`, this doesn't check that the proof is bound to the transcript,
+/// since KZG proofs are deterministic.
+fn prove_and_verify_random_evaluation(
+    table_length: usize,
+    public_parameters: &KzgPublicParameters,
+    verifier_setup: &KzgVerifierSetup,
+) -> Result<(), KzgError> {
+    let nu = table_length.next_power_of_two().trailing_zeros() as usize;
+    let mut rng = test_rng();
+    let a = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+        .take(table_length)
+        .collect::<Vec<_>>();
+    let b_point = core::iter::repeat_with(|| BNScalar::rand(&mut rng))
+        .take(nu)
+        .collect::<Vec<_>>();
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let proof = KzgEvaluationProof::new(&mut transcript, &a, &b_point, 0, &public_parameters);
+    let commits = Vec::from_columns_with_offset([Column::Scalar(&a)], 0, &public_parameters);
+    let mut b = vec![BNScalar::default(); table_length];
+    compute_evaluation_vector(&mut b, &b_point);
+    let product: BNScalar = a.iter().zip(&b).map(|(a, b)| *a * *b).sum();
+
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let result = proof.verify_proof(
+        &mut transcript,
+        &commits[0],
+        &product,
+        &b_point,
+        0,
+        table_length,
+        &verifier_setup,
+    );
+    if result.is_ok() {
+        // Invalid Product
+        let mut transcript = Transcript::new(b"evaluation_proof");
+        assert!(matches!(
+            proof.verify_proof(
+                &mut transcript,
+                &commits[0],
+                &(product + BNScalar::from(1)),
+                &b_point,
+                0,
+                table_length,
+                &verifier_setup,
+            ),
+            Err(KzgError::VerificationError)
+        ));
+        // Invalid offset
+        let mut transcript = Transcript::new(b"evaluation_proof");
+        assert!(matches!(
+            proof.verify_proof(
+                &mut transcript,
+                &commits[0],
+                &product,
+                &b_point,
+                1,
+                table_length,
+                &verifier_setup,
+            ),
+            Err(KzgError::InvalidGeneratorsOffset { offset: 1 })
+        ));
+    }
+    result
+}
+
+#[test]
+fn test_random_kzg_proof_with_various_lengths() {
+    let lengths = [128, 100, 64, 50, 32, 20, 16, 10, 8, 5, 4, 3, 2];
+    let public_parameters = KzgPublicParameters::test_rand(7, &mut test_rng());
+    let verifier_setup = KzgVerifierSetup::from(&public_parameters);
+    for length in lengths {
+        assert!(
+            prove_and_verify_random_evaluation(length, &public_parameters, &verifier_setup).is_ok()
+        );
+    }
+}
+
+#[test]
+fn test_random_kzg_proof_fails_with_a_different_setup() {
+    let public_parameters = KzgPublicParameters::test_rand(6, &mut test_rng());
+    let other_public_parameters = KzgPublicParameters::test_rand(6, &mut StdRng::seed_from_u64(42));
+    let verifier_setup = KzgVerifierSetup::from(&other_public_parameters);
+    assert!(matches!(
+        prove_and_verify_random_evaluation(50, &public_parameters, &verifier_setup),
+        Err(KzgError::VerificationError)
+    ));
+}
+
+#[test]
+fn test_random_kzg_proof_fails_with_too_small_of_verifier_setup() {
+    let public_parameters = KzgPublicParameters::test_rand(6, &mut test_rng());
+    let verifier_setup =
+        KzgVerifierSetup::from(&KzgPublicParameters::test_rand(2, &mut test_rng()));
+    assert!(matches!(
+        prove_and_verify_random_evaluation(50, &public_parameters, &verifier_setup),
+        Err(KzgError::SmallSetup {
+            actual: 2,
+            required: 6
+        })
+    ));
+}
+
+#[test]
+fn test_kzg_proof_fails_with_a_tampered_quotient() {
+    let public_parameters = KzgPublicParameters::test_rand(4, &mut test_rng());
+    let verifier_setup = KzgVerifierSetup::from(&public_parameters);
+    let a = [BNScalar::from(3), BNScalar::from(-4), BNScalar::from(5)];
+    let b_point = [BNScalar::from(7), BNScalar::from(11)];
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    let mut proof = KzgEvaluationProof::new(&mut transcript, &a, &b_point, 0, &&public_parameters);
+    let commits = Vec::from_columns_with_offset([Column::Scalar(&a)], 0, &&public_parameters);
+    let mut b = vec![BNScalar::default(); 3];
+    compute_evaluation_vector(&mut b, &b_point);
+    let product: BNScalar = a.iter().zip(&b).map(|(a, b)| *a * *b).sum();
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    assert!(proof
+        .verify_proof(
+            &mut transcript,
+            &commits[0],
+            &product,
+            &b_point,
+            0,
+            3,
+            &&verifier_setup
+        )
+        .is_ok());
+
+    proof.quotient_commitments[1] = proof.quotient_commitments[0];
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    assert!(proof
+        .verify_proof(
+            &mut transcript,
+            &commits[0],
+            &product,
+            &b_point,
+            0,
+            3,
+            &&verifier_setup
+        )
+        .is_err());
+
+    proof.quotient_commitments.pop();
+    let mut transcript = Transcript::new(b"evaluation_proof");
+    assert!(proof
+        .verify_proof(
+            &mut transcript,
+            &commits[0],
+            &product,
+            &b_point,
+            0,
+            3,
+            &&verifier_setup
+        )
+        .is_err());
+}
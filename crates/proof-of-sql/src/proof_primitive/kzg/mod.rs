@@ -0,0 +1,33 @@
+//! This module contains the multilinear KZG commitment scheme of <https://eprint.iacr.org/2011/587.pdf> (PST13) over BN254.
+//!
+//! A column `a` is committed to as the multilinear polynomial `f(x) = sum_i a[i] * eq(x, i)` in `max_nu` variables,
+//! where the bits of `i` are the variables, least significant bit first, so
+//! ```text
+//! C = sum_i a[i] * eq(tau, i) * G
+//! ```
+//! for the secret point `tau` of the setup. The evaluation of `f` at `z` is proven with the commitments `P_j` to the
+//! quotients `q_j` of
+//! ```text
+//! f(x) - f(z) = sum_j (x_j - z_j) * q_j(x_(j+1), ..., x_(max_nu - 1))
+//! ```
+//! and checked with the pairing equation
+//! ```text
+//! e(C - f(z) * G + sum_j z_j * P_j, H) * prod_j e(-P_j, tau_j * H) = 1.
+//! ```
+//! Since this only needs additions, scalar multiplications and a pairing check on BN254, the proofs can be verified
+//! on the EVM with the `ecAdd`, `ecMul` and `ecPairing` precompiles.
+
+use ark_bn254::{Fr as F, G1Affine, G1Projective, G2Affine};
+
+mod public_parameters;
+pub use public_parameters::{KzgPublicParameters, KzgVerifierSetup};
+
+mod kzg_commitment;
+pub use kzg_commitment::{BNScalar, KzgCommitment};
+#[cfg(test)]
+mod kzg_commitment_test;
+
+mod kzg_evaluation_proof;
+pub use kzg_evaluation_proof::{KzgError, KzgEvaluationProof};
+#[cfg(test)]
+mod kzg_evaluation_proof_test;
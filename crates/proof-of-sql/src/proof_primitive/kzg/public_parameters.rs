@@ -0,0 +1,91 @@
+use super::{G1Affine, G1Projective, G2Affine, F};
+use crate::base::polynomial::compute_evaluation_vector;
+use alloc::{vec, vec::Vec};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{One, UniformRand};
+use ark_std::rand::{CryptoRng, Rng};
+
+/// The public parameters of the multilinear KZG commitment scheme for polynomials in up to `max_nu` variables.
+///
+/// The parameters are derived from a secret point `tau`, which must be discarded after the setup.
+/// These are the parameters the prover needs to commit to columns and to create evaluation proofs.
+pub struct KzgPublicParameters {
+    /// `lagrange_bases[k]` is the Lagrange basis of the polynomials in the last `k` variables, i.e.
+    /// `eq(tau[max_nu - k..], i) * G` for every `i < 2^k`.
+    pub(super) lagrange_bases: Vec<Vec<G1Affine>>,
+    /// The generator `H` of G2.
+    pub(super) h: G2Affine,
+    /// `tau_h[j] = tau[j] * H`.
+    pub(super) tau_h: Vec<G2Affine>,
+}
+
+impl KzgPublicParameters {
+    /// Generate cryptographically secure random public parameters.
+    ///
+    /// Note: whoever runs this learns `tau` and can forge proofs, so real deployments need a trusted setup ceremony.
+    pub fn rand<R: CryptoRng + Rng + ?Sized>(max_nu: usize, rng: &mut R) -> Self {
+        Self::rand_impl(max_nu, rng)
+    }
+    #[cfg(any(test, feature = "test"))]
+    /// Generate random public parameters for testing.
+    pub fn test_rand<R: Rng + ?Sized>(max_nu: usize, rng: &mut R) -> Self {
+        Self::rand_impl(max_nu, rng)
+    }
+    fn rand_impl<R: Rng + ?Sized>(max_nu: usize, rng: &mut R) -> Self {
+        let tau: Vec<_> = core::iter::repeat_with(|| F::rand(rng))
+            .take(max_nu)
+            .collect();
+        let g = G1Affine::generator();
+        let h = G2Affine::generator();
+        let lagrange_bases = (0..=max_nu)
+            .map(|k| {
+                let mut eq = vec![F::one(); 1 << k];
+                compute_evaluation_vector(&mut eq, &tau[max_nu - k..]);
+                G1Projective::normalize_batch(&eq.iter().map(|e| g * e).collect::<Vec<_>>())
+            })
+            .collect();
+        let tau_h = tau.iter().map(|t| (h * t).into_affine()).collect();
+        Self {
+            lagrange_bases,
+            h,
+            tau_h,
+        }
+    }
+
+    /// The maximum number of variables of the committed polynomials, i.e. columns have at most `2^max_nu` rows.
+    #[must_use]
+    pub fn max_nu(&self) -> usize {
+        self.tau_h.len()
+    }
+}
+
+/// The public parameters that the verifier needs to check evaluation proofs.
+///
+/// These have to match the [`KzgPublicParameters`] of the prover.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KzgVerifierSetup {
+    /// The generator `G` of G1.
+    pub(crate) g: G1Affine,
+    /// The generator `H` of G2.
+    pub(crate) h: G2Affine,
+    /// `tau_h[j] = tau[j] * H`.
+    pub(crate) tau_h: Vec<G2Affine>,
+}
+
+impl From<&KzgPublicParameters> for KzgVerifierSetup {
+    fn from(public_parameters: &KzgPublicParameters) -> Self {
+        Self {
+            g: public_parameters.lagrange_bases[0][0],
+            h: public_parameters.h,
+            tau_h: public_parameters.tau_h.clone(),
+        }
+    }
+}
+
+impl KzgVerifierSetup {
+    /// The maximum number of variables of the committed polynomials, i.e. columns have at most `2^max_nu` rows.
+    #[must_use]
+    pub fn max_nu(&self) -> usize {
+        self.tau_h.len()
+    }
+}
@@ -1,4 +1,8 @@
 //! TODO: add docs
 pub mod dory;
+#[cfg(feature = "std")]
+pub mod hiding;
+pub mod inner_product;
+pub mod kzg;
 /// TODO: add docs
 pub(crate) mod sumcheck;
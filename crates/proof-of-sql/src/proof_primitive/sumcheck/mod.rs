@@ -10,4 +10,4 @@ mod prover_round;
 use prover_round::prove_round;
 
 #[cfg(test)]
-mod test_cases;
+pub(crate) mod test_cases;
@@ -27,11 +27,34 @@ pub struct Subclaim<S: Scalar> {
 }
 
 impl<S: Scalar> SumcheckProof<S> {
+    /// The coefficients of the round polynomials, each in reverse order
+    #[cfg(feature = "evm")]
+    pub(crate) fn coefficients(&self) -> &[S] {
+        &self.coefficients
+    }
+
     #[tracing::instrument(name = "SumcheckProof::create", level = "debug", skip_all)]
     pub fn create(
         transcript: &mut impl Transcript,
         evaluation_point: &mut [S],
         polynomial: &CompositePolynomial<S>,
+    ) -> Self {
+        Self::create_with_round_mask(transcript, evaluation_point, polynomial, |_, _| None)
+    }
+
+    /// Create a proof for the sum of `polynomial` plus a mask polynomial, which is only known through its round polynomials.
+    ///
+    /// `round_mask(round, challenges)` returns the evaluations of the round polynomial of the mask at `0..=max_multiplicands`,
+    /// where `challenges` are the challenges of the previous rounds, or `None` if there is no mask.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `evaluation_point` doesn't have one entry per variable.
+    pub(crate) fn create_with_round_mask(
+        transcript: &mut impl Transcript,
+        evaluation_point: &mut [S],
+        polynomial: &CompositePolynomial<S>,
+        mut round_mask: impl FnMut(usize, &[S]) -> Option<Vec<S>>,
     ) -> Self {
         assert_eq!(evaluation_point.len(), polynomial.num_variables);
         transcript.extend_as_be([
@@ -43,14 +66,21 @@ impl<S: Scalar> SumcheckProof<S> {
         let mut r = None;
         let mut state = ProverState::create(polynomial);
         let mut coefficients = Vec::with_capacity(polynomial.num_variables);
-        for scalar in evaluation_point.iter_mut().take(polynomial.num_variables) {
-            let round_evaluations = prove_round(&mut state, &r);
+        for round in 0..polynomial.num_variables {
+            let mut round_evaluations = prove_round(&mut state, &r);
+            if let Some(mask_evaluations) = round_mask(round, &evaluation_point[..round]) {
+                for (evaluation, mask_evaluation) in
+                    round_evaluations.iter_mut().zip(mask_evaluations)
+                {
+                    *evaluation += mask_evaluation;
+                }
+            }
             let round_coefficients =
                 interpolate_evaluations_to_reverse_coefficients(&round_evaluations);
             transcript.extend_scalars_as_be(&round_coefficients);
             coefficients.extend(round_coefficients);
-            *scalar = transcript.scalar_challenge_as_be();
-            r = Some(*scalar);
+            evaluation_point[round] = transcript.scalar_challenge_as_be();
+            r = Some(evaluation_point[round]);
         }
 
         SumcheckProof { coefficients }
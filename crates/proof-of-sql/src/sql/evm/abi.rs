@@ -0,0 +1,98 @@
+use crate::{
+    base::{database::CommitmentAccessor, scalar::Scalar},
+    proof_primitive::kzg::{KzgCommitment, KzgEvaluationProof},
+    sql::proof::{ProofPlan, ProvableQueryResult, QueryProof},
+};
+use alloc::vec::Vec;
+use alloy_sol_types::{
+    private::primitives::{Bytes, U256},
+    sol,
+};
+use serde::Serialize;
+
+sol!(
+    #[sol(all_derives)]
+    "./sol_src/verifier/QueryProofAbi.sol"
+);
+
+/// The postcard serialization of a proof component that the verifier only adds to the transcript.
+///
+/// # Panics
+///
+/// Panics if the component cannot be serialized, which can't happen for the components of a proof.
+fn postcard_bytes(message: &(impl Serialize + ?Sized)) -> Bytes {
+    Bytes::from(postcard::to_allocvec(message).unwrap())
+}
+
+fn scalars_to_words<S: Scalar>(scalars: &[S]) -> Vec<U256> {
+    scalars
+        .iter()
+        .map(|&scalar| U256::from_limbs(scalar.into()))
+        .collect()
+}
+
+impl From<&ProvableQueryResult> for QueryProofAbi::ProvableQueryResult {
+    fn from(result: &ProvableQueryResult) -> Self {
+        Self {
+            numColumns: result.num_columns() as u64,
+            tableLength: result.table_length() as u64,
+            data: Bytes::copy_from_slice(result.data()),
+        }
+    }
+}
+
+impl From<&KzgCommitment> for QueryProofAbi::G1Point {
+    fn from(commitment: &KzgCommitment) -> Self {
+        let [x, y] = commitment.to_be_words();
+        Self {
+            x: U256::from_be_bytes(x),
+            y: U256::from_be_bytes(y),
+        }
+    }
+}
+
+fn points(commitments: &[KzgCommitment]) -> Vec<QueryProofAbi::G1Point> {
+    commitments.iter().map(Into::into).collect()
+}
+
+impl From<&QueryProof<KzgEvaluationProof>> for QueryProofAbi::QueryProof {
+    fn from(proof: &QueryProof<KzgEvaluationProof>) -> Self {
+        Self {
+            bitDistributions: postcard_bytes(&proof.bit_distributions),
+            oneEvaluationLengths: proof
+                .one_evaluation_lengths
+                .iter()
+                .map(|&length| length as u64)
+                .collect(),
+            stringDictionaries: postcard_bytes(&proof.string_dictionaries),
+            firstRoundCommitments: points(&proof.first_round_commitments),
+            commitments: points(&proof.commitments),
+            sumcheckCoefficients: scalars_to_words(proof.sumcheck_proof.coefficients()),
+            pcsProofEvaluations: scalars_to_words(&proof.pcs_proof_evaluations),
+            evaluationProof: points(&proof.evaluation_proof.quotient_commitments),
+        }
+    }
+}
+
+impl QueryProofAbi::VerifierInput {
+    /// Collect everything that the generated Solidity verifier needs to check a proof of `plan`,
+    /// including the length, the offset and the column commitments of the queried table.
+    pub fn new(
+        plan: &impl ProofPlan<KzgCommitment>,
+        accessor: &impl CommitmentAccessor<KzgCommitment>,
+        result: &ProvableQueryResult,
+        proof: &QueryProof<KzgEvaluationProof>,
+    ) -> Self {
+        Self {
+            result: result.into(),
+            proof: proof.into(),
+            tableLength: plan.get_length(accessor) as u64,
+            generatorOffset: plan.get_offset(accessor) as u64,
+            columnCommitments: plan
+                .get_column_references()
+                .into_iter()
+                .map(|column| (&accessor.get_commitment(column)).into())
+                .collect(),
+        }
+    }
+}
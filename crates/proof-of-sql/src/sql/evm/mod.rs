@@ -0,0 +1,15 @@
+//! This module contains support for checking query proofs on the EVM.
+//!
+//! The generated verifiers check proofs over the multilinear KZG commitment scheme over BN254 in
+//! [`kzg`](crate::proof_primitive::kzg), and not proofs over Dory or Dynamic Dory. The Dory
+//! verifier multiplies and exponentiates elements of the target group of the pairing and compares
+//! them to pairings, which the EVM has no precompiles for: its pairing precompiles only check that a
+//! product of pairings is one. So tables whose query results are checked on the EVM need to be
+//! committed to with [`KzgCommitment`](crate::proof_primitive::kzg::KzgCommitment).
+mod abi;
+pub use abi::QueryProofAbi;
+
+mod solidity_verifier;
+pub use solidity_verifier::{SolidityVerifier, SolidityVerifierError};
+#[cfg(test)]
+mod solidity_verifier_test;
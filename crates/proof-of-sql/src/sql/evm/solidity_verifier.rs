@@ -0,0 +1,686 @@
+use crate::{
+    base::{
+        database::{ColumnRef, ColumnType, MetadataAccessor},
+        map::IndexSet,
+        math::decimal::scale_scalar,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    proof_primitive::kzg::{BNScalar, KzgCommitment, KzgVerifierSetup},
+    sql::{
+        proof::{CountBuilder, ProofPlan},
+        proof_exprs::{DynProofExpr, ProofExpr},
+        proof_plans::DynProofPlan,
+    },
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use alloy_sol_types::private::primitives::U256;
+use ark_bn254::{Fq, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use core::{cmp, fmt::Write};
+use snafu::Snafu;
+
+/// The Solidity libraries that the generated verifier is built on, in dependency order.
+const LIBRARY_SOURCES: [&str; 6] = [
+    include_str!("../../../sol_src/verifier/QueryProofAbi.sol"),
+    include_str!("../../../sol_src/verifier/Transcript.sol"),
+    include_str!("../../../sol_src/verifier/Sumcheck.sol"),
+    include_str!("../../../sol_src/verifier/Kzg.sol"),
+    include_str!("../../../sol_src/verifier/VerificationBuilder.sol"),
+    include_str!("../../../sol_src/verifier/QueryResult.sol"),
+];
+
+/// The plan-specific part of the generated verifier. The `{{...}}` placeholders are replaced by the
+/// constants of the plan and the setup.
+const VERIFIER_TEMPLATE: &str = r#"
+/// @title Verifier of the proofs of a single query plan
+/// @notice Generated by `SolidityVerifier` of the `proof-of-sql` crate.
+/// Mirrors `QueryProof::verify` for a single plan and a single multilinear KZG setup.
+library QueryVerifier {
+    uint256 internal constant MODULUS = Kzg.MODULUS;
+    bytes internal constant PLAN = hex"{{PLAN}}";
+    bytes internal constant RESULT_COLUMN_TYPES = hex"{{RESULT_COLUMN_TYPES}}";
+    uint64 internal constant MAX_MULTIPLICANDS = {{MAX_MULTIPLICANDS}};
+    uint256 internal constant NUM_SUMCHECK_SUBPOLYNOMIALS = {{NUM_SUMCHECK_SUBPOLYNOMIALS}};
+    uint256 internal constant NUM_POST_RESULT_CHALLENGES = {{NUM_POST_RESULT_CHALLENGES}};
+    uint256 internal constant NUM_INTERMEDIATE_MLES = {{NUM_INTERMEDIATE_MLES}};
+    uint256 internal constant NUM_PCS_PROOF_EVALUATIONS = {{NUM_PCS_PROOF_EVALUATIONS}};
+    uint256 internal constant NUM_COLUMN_COMMITMENTS = {{NUM_COLUMN_COMMITMENTS}};
+
+    error InvalidProofSize();
+    error InvalidGeneratorOffset();
+    error ResultEvaluationMismatch();
+    error SumcheckEvaluationMismatch();
+
+    // Everything that the transcript and the sumcheck of a proof yield
+    struct Subclaim {
+        uint256[] postResultChallenges;
+        uint256 rangeLength;
+        // the entrywise point followed by the subpolynomial multipliers
+        uint256[] randomScalars;
+        uint256[] evaluationPoint;
+        uint256 expectedEvaluation;
+        uint256[] evaluationRandomScalars;
+    }
+
+    /// @notice Verify a proof of the plan, which reverts unless the result is correct
+    function verify(QueryProofAbi.VerifierInput memory input) internal view {
+        checkSizes(input);
+        Subclaim memory subclaim = verifyTranscriptAndSumcheck(input);
+        VerificationBuilder.State memory builder = newBuilder(input, subclaim);
+        uint256[] memory evaluations = evaluatePlan(builder, input.columnCommitments);
+        assert(VerificationBuilder.completed(builder));
+        uint256[] memory resultEvaluations =
+            QueryResult.evaluate(input.result, RESULT_COLUMN_TYPES, subclaim.evaluationPoint, MODULUS);
+        if (keccak256(abi.encodePacked(evaluations)) != keccak256(abi.encodePacked(resultEvaluations))) {
+            revert ResultEvaluationMismatch();
+        }
+        if (builder.sumcheckEvaluation != subclaim.expectedEvaluation) revert SumcheckEvaluationMismatch();
+        Kzg.verify(
+            builder.foldedPcsProofCommitment,
+            builder.foldedPcsProofEvaluation,
+            subclaim.evaluationPoint,
+            input.proof.evaluationProof,
+            setup()
+        );
+    }
+
+    /// @notice Entry point for scripts, which takes the ABI encoding of a `VerifierInput`
+    function verifyEncoded(bytes memory encodedInput) public view {
+        verify(abi.decode(encodedInput, (QueryProofAbi.VerifierInput)));
+    }
+
+    /// @notice Check that the proof has the shape that the plan expects
+    function checkSizes(QueryProofAbi.VerifierInput memory input) internal pure {
+        QueryProofAbi.QueryProof memory proof = input.proof;
+        if (
+            proof.oneEvaluationLengths.length != 0 || keccak256(proof.stringDictionaries) != keccak256(hex"00")
+                || keccak256(proof.bitDistributions) != keccak256(hex"00") || proof.firstRoundCommitments.length != 0
+                || proof.commitments.length != NUM_INTERMEDIATE_MLES
+                || proof.pcsProofEvaluations.length != NUM_PCS_PROOF_EVALUATIONS
+                || input.columnCommitments.length != NUM_COLUMN_COMMITMENTS
+        ) revert InvalidProofSize();
+        // Like `KzgEvaluationProof`, only tables at offset zero are supported.
+        if (input.generatorOffset != 0) revert InvalidGeneratorOffset();
+    }
+
+    /// @notice Replay the transcript of the proof and verify the rounds of its sumcheck
+    function verifyTranscriptAndSumcheck(QueryProofAbi.VerifierInput memory input)
+        internal
+        pure
+        returns (Subclaim memory subclaim)
+    {
+        QueryProofAbi.QueryProof memory proof = input.proof;
+        Transcript.State memory transcript = Transcript.State("");
+        Transcript.append(transcript, publicInputs(input));
+        Transcript.append(
+            transcript,
+            bytes.concat(
+                oneEvaluationLengths(proof.oneEvaluationLengths),
+                proof.stringDictionaries,
+                Transcript.points(proof.firstRoundCommitments)
+            )
+        );
+        subclaim.postResultChallenges =
+            Transcript.scalarChallenges(transcript, NUM_POST_RESULT_CHALLENGES, MODULUS);
+        Transcript.append(transcript, bytes.concat(Transcript.points(proof.commitments), proof.bitDistributions));
+
+        subclaim.rangeLength = rangeLength(input);
+        uint64 numSumcheckVariables = numVariables(subclaim.rangeLength);
+        subclaim.randomScalars =
+            Transcript.scalarChallenges(transcript, numSumcheckVariables + NUM_SUMCHECK_SUBPOLYNOMIALS, MODULUS);
+        (subclaim.evaluationPoint, subclaim.expectedEvaluation) = Sumcheck.verifyWithoutEvaluation(
+            transcript, proof.sumcheckCoefficients, MAX_MULTIPLICANDS, numSumcheckVariables, MODULUS
+        );
+
+        for (uint256 i = 0; i < NUM_PCS_PROOF_EVALUATIONS; ++i) {
+            if (proof.pcsProofEvaluations[i] >= MODULUS) revert Sumcheck.InvalidScalar();
+        }
+        Transcript.append(transcript, Transcript.canonicalScalars(proof.pcsProofEvaluations));
+        subclaim.evaluationRandomScalars =
+            Transcript.scalarChallenges(transcript, NUM_PCS_PROOF_EVALUATIONS, MODULUS);
+    }
+
+    /// @notice Set up the verification builder like `QueryProof::verify` does
+    function newBuilder(QueryProofAbi.VerifierInput memory input, Subclaim memory subclaim)
+        internal
+        pure
+        returns (VerificationBuilder.State memory builder)
+    {
+        uint256 numSumcheckVariables = subclaim.evaluationPoint.length;
+        uint256[] memory entrywisePoint = new uint256[](numSumcheckVariables);
+        for (uint256 i = 0; i < numSumcheckVariables; ++i) {
+            entrywisePoint[i] = subclaim.randomScalars[i];
+        }
+        builder.subpolynomialMultipliers = new uint256[](NUM_SUMCHECK_SUBPOLYNOMIALS);
+        for (uint256 i = 0; i < NUM_SUMCHECK_SUBPOLYNOMIALS; ++i) {
+            builder.subpolynomialMultipliers[i] = subclaim.randomScalars[numSumcheckVariables + i];
+        }
+        builder.pcsProofEvaluations = input.proof.pcsProofEvaluations;
+        builder.innerProductMultipliers = subclaim.evaluationRandomScalars;
+        builder.postResultChallenges = subclaim.postResultChallenges;
+        builder.intermediateCommitments = input.proof.commitments;
+        builder.inputOneEvaluation =
+            VerificationBuilder.truncatedLagrangeBasisSum(input.tableLength, subclaim.evaluationPoint);
+        builder.outputOneEvaluation =
+            VerificationBuilder.truncatedLagrangeBasisSum(input.result.tableLength, subclaim.evaluationPoint);
+        builder.randomEvaluation = VerificationBuilder.truncatedLagrangeBasisInnerProduct(
+            subclaim.rangeLength, subclaim.evaluationPoint, entrywisePoint
+        );
+    }
+
+    /// @notice Evaluate the plan at the evaluation point of the sumcheck, like `ProofPlan::verifier_evaluate`,
+    /// and return the evaluations of the result columns
+    function evaluatePlan(VerificationBuilder.State memory builder, QueryProofAbi.G1Point[] memory columns)
+        internal
+        view
+        returns (uint256[] memory results)
+    {
+        uint256[] memory v = new uint256[]({{NUM_VALUES}});
+        results = new uint256[]({{NUM_RESULT_COLUMNS}});
+{{PLAN_EVALUATION}}
+    }
+
+    /// @notice The public parameters of the evaluation proofs
+    function setup() internal pure returns (Kzg.Setup memory result) {
+{{SETUP}}
+    }
+
+    /// @notice The postcard serialization of the result, the plan, the table length and the generator offset
+    function publicInputs(QueryProofAbi.VerifierInput memory input) internal pure returns (bytes memory) {
+        return bytes.concat(
+            Transcript.varint(input.result.numColumns),
+            Transcript.varint(input.result.tableLength),
+            Transcript.varint(input.result.data.length),
+            input.result.data,
+            PLAN,
+            Transcript.varint(input.tableLength),
+            Transcript.varint(input.generatorOffset)
+        );
+    }
+
+    /// @notice The postcard serialization of the lengths of the intermediate results
+    function oneEvaluationLengths(uint64[] memory lengths) internal pure returns (bytes memory result) {
+        result = Transcript.varint(lengths.length);
+        for (uint256 i = 0; i < lengths.length; ++i) {
+            result = bytes.concat(result, Transcript.varint(lengths[i]));
+        }
+    }
+
+    /// @notice The length of the sumcheck domain, which fits the input, the output and the intermediate results
+    function rangeLength(QueryProofAbi.VerifierInput memory input) internal pure returns (uint256 result) {
+        result = input.tableLength;
+        if (input.result.tableLength > result) result = input.result.tableLength;
+        for (uint256 i = 0; i < input.proof.oneEvaluationLengths.length; ++i) {
+            if (input.proof.oneEvaluationLengths[i] > result) result = input.proof.oneEvaluationLengths[i];
+        }
+    }
+
+    /// @notice The number of sumcheck variables, i.e. `max(log2_up(length), 1)`
+    function numVariables(uint256 length) internal pure returns (uint64 result) {
+        result = 1;
+        while ((uint256(1) << result) < length) ++result;
+    }
+}
+"#;
+
+/// Errors from generating a Solidity verifier.
+#[derive(Snafu, Debug)]
+pub enum SolidityVerifierError {
+    /// The shape of the proofs of the plan is not known before the data is, e.g. because the plan
+    /// uses range checks whose size depends on bit distributions.
+    #[snafu(display("the plan is not supported by the Solidity verifier: {source}"))]
+    UnsupportedPlan {
+        /// The underlying source error
+        source: ProofError,
+    },
+    /// The plan contains a node or a result type that the Solidity verifier can't evaluate.
+    #[snafu(display("the Solidity verifier does not support {feature}"))]
+    UnsupportedFeature {
+        /// The unsupported part of the plan
+        feature: String,
+    },
+}
+
+/// The body of the generated `evaluatePlan`, which mirrors [`ProofPlan::verifier_evaluate`].
+///
+/// Every evaluation is assigned to a new slot of the array `v`, so that each statement is a single
+/// call to the verification builder, a single `addmod` or `mulmod`, or the assignment of a result.
+struct PlanEvaluation<'a> {
+    column_references: &'a IndexSet<ColumnRef>,
+    statements: Vec<String>,
+    num_values: usize,
+}
+
+impl PlanEvaluation<'_> {
+    fn assign(&mut self, expression: &str) -> String {
+        let index = self.num_values;
+        self.num_values += 1;
+        self.statements.push(format!("v[{index}] = {expression};"));
+        format!("v[{index}]")
+    }
+
+    fn add(&mut self, lhs: &str, rhs: &str) -> String {
+        self.assign(&format!("addmod({lhs}, {rhs}, MODULUS)"))
+    }
+
+    fn subtract(&mut self, lhs: &str, rhs: &str) -> String {
+        self.assign(&format!("addmod({lhs}, MODULUS - {rhs}, MODULUS)"))
+    }
+
+    fn multiply(&mut self, lhs: &str, rhs: &str) -> String {
+        self.assign(&format!("mulmod({lhs}, {rhs}, MODULUS)"))
+    }
+
+    fn consume_intermediate_mle(&mut self) -> String {
+        self.assign("VerificationBuilder.consumeIntermediateMle(builder)")
+    }
+
+    fn consume_post_result_challenge(&mut self) -> String {
+        self.assign("VerificationBuilder.consumePostResultChallenge(builder)")
+    }
+
+    fn produce_identity(&mut self, evaluation: &str) {
+        self.statements.push(format!(
+            "VerificationBuilder.produceIdentity(builder, {evaluation});"
+        ));
+    }
+
+    fn produce_zero_sum(&mut self, evaluation: &str) {
+        self.statements.push(format!(
+            "VerificationBuilder.produceZeroSum(builder, {evaluation});"
+        ));
+    }
+
+    /// `lhs + rhs` or `lhs - rhs` after scaling both sides to the same scale, like
+    /// `scale_and_add_subtract_eval`
+    fn scale_and_add_subtract(
+        &mut self,
+        lhs: &DynProofExpr<KzgCommitment>,
+        rhs: &DynProofExpr<KzgCommitment>,
+        is_subtract: bool,
+    ) -> Result<String, SolidityVerifierError> {
+        let lhs_scale = lhs.data_type().scale().unwrap_or(0);
+        let rhs_scale = rhs.data_type().scale().unwrap_or(0);
+        let max_scale = cmp::max(lhs_scale, rhs_scale);
+        let mut lhs = self.evaluate_expr(lhs)?;
+        let mut rhs = self.evaluate_expr(rhs)?;
+        if lhs_scale < max_scale {
+            lhs = self.multiply(&lhs, &power_of_ten(max_scale - lhs_scale));
+        }
+        if rhs_scale < max_scale {
+            rhs = self.multiply(&rhs, &power_of_ten(max_scale - rhs_scale));
+        }
+        Ok(if is_subtract {
+            self.subtract(&lhs, &rhs)
+        } else {
+            self.add(&lhs, &rhs)
+        })
+    }
+
+    /// Mirrors `verifier_evaluate_equals_zero`.
+    fn equals_zero(&mut self, evaluation: &str) -> String {
+        let pseudo_inverse = self.consume_intermediate_mle();
+        let selection_not = self.consume_intermediate_mle();
+        let selection = self.subtract("builder.inputOneEvaluation", &selection_not);
+        let product = self.multiply(&selection, evaluation);
+        self.produce_identity(&product);
+        let product = self.multiply(evaluation, &pseudo_inverse);
+        let difference = self.subtract(&selection_not, &product);
+        self.produce_identity(&difference);
+        selection
+    }
+
+    /// Consume an intermediate MLE `x` and prove that `x = lhs * rhs`.
+    fn product(&mut self, lhs: &str, rhs: &str) -> String {
+        let lhs_times_rhs = self.consume_intermediate_mle();
+        let product = self.multiply(lhs, rhs);
+        let difference = self.subtract(&lhs_times_rhs, &product);
+        self.produce_identity(&difference);
+        lhs_times_rhs
+    }
+
+    /// Mirrors `verifier_evaluate` of the expression and returns its evaluation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the expression reads a column that the plan doesn't reference.
+    fn evaluate_expr(
+        &mut self,
+        expr: &DynProofExpr<KzgCommitment>,
+    ) -> Result<String, SolidityVerifierError> {
+        Ok(match expr {
+            DynProofExpr::Column(column) => {
+                let index = self
+                    .column_references
+                    .get_index_of(&column.get_column_reference())
+                    .expect("the plan should reference every column it reads");
+                self.assign(&format!(
+                    "VerificationBuilder.consumeAnchoredMle(builder, columns[{index}])"
+                ))
+            }
+            DynProofExpr::Literal(literal) => self.multiply(
+                "builder.inputOneEvaluation",
+                &scalar_literal(literal.value().to_scalar()),
+            ),
+            DynProofExpr::Equals(equals) => {
+                let difference = self.scale_and_add_subtract(equals.lhs(), equals.rhs(), true)?;
+                self.equals_zero(&difference)
+            }
+            DynProofExpr::And(and) => {
+                let lhs = self.evaluate_expr(and.lhs())?;
+                let rhs = self.evaluate_expr(and.rhs())?;
+                self.product(&lhs, &rhs)
+            }
+            DynProofExpr::Or(or) => {
+                let lhs = self.evaluate_expr(or.lhs())?;
+                let rhs = self.evaluate_expr(or.rhs())?;
+                let lhs_and_rhs = self.product(&lhs, &rhs);
+                let sum = self.add(&lhs, &rhs);
+                self.subtract(&sum, &lhs_and_rhs)
+            }
+            DynProofExpr::Not(not) => {
+                let evaluation = self.evaluate_expr(not.expr())?;
+                let presence = match not.presence() {
+                    Some(presence) => self.evaluate_expr(presence)?,
+                    None => "builder.inputOneEvaluation".to_string(),
+                };
+                self.subtract(&presence, &evaluation)
+            }
+            DynProofExpr::AddSubtract(add_subtract) => self.scale_and_add_subtract(
+                add_subtract.lhs(),
+                add_subtract.rhs(),
+                add_subtract.is_subtract(),
+            )?,
+            DynProofExpr::Multiply(multiply) => {
+                let lhs = self.evaluate_expr(multiply.lhs())?;
+                let rhs = self.evaluate_expr(multiply.rhs())?;
+                self.product(&lhs, &rhs)
+            }
+            _ => Err(SolidityVerifierError::UnsupportedFeature {
+                feature: "expressions other than columns, literals, equality and arithmetic or logical operators".to_string(),
+            })?,
+        })
+    }
+
+    /// Mirrors `verifier_evaluate` of the plan and returns the evaluations of the result columns.
+    fn evaluate_plan(
+        &mut self,
+        plan: &DynProofPlan<KzgCommitment>,
+    ) -> Result<Vec<String>, SolidityVerifierError> {
+        match plan {
+            DynProofPlan::Projection(projection) => {
+                for aliased_expr in projection.aliased_results() {
+                    self.evaluate_expr(&aliased_expr.expr)?;
+                }
+                Ok(projection
+                    .aliased_results()
+                    .iter()
+                    .map(|_| self.consume_intermediate_mle())
+                    .collect())
+            }
+            DynProofPlan::Filter(filter) => {
+                let selection = self.evaluate_expr(&filter.where_clause)?;
+                let columns = filter
+                    .aliased_results()
+                    .iter()
+                    .map(|aliased_expr| self.evaluate_expr(&aliased_expr.expr))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let filtered_columns: Vec<_> = columns
+                    .iter()
+                    .map(|_| self.consume_intermediate_mle())
+                    .collect();
+                let alpha = self.consume_post_result_challenge();
+                let beta = self.consume_post_result_challenge();
+
+                // Mirrors `verify_filter`.
+                let alpha_one = self.multiply(&alpha, "builder.inputOneEvaluation");
+                let c_fold = self.fold(&alpha_one, &beta, &columns);
+                let d_bar_fold = self.fold(&alpha_one, &beta, &filtered_columns);
+                let c_star = self.consume_intermediate_mle();
+                let d_star = self.consume_intermediate_mle();
+                let product = self.multiply(&c_star, &selection);
+                let difference = self.subtract(&product, &d_star);
+                self.produce_zero_sum(&difference);
+                let product = self.multiply(&c_fold, &c_star);
+                let difference = self.subtract(&product, "builder.inputOneEvaluation");
+                self.produce_identity(&difference);
+                let product = self.multiply(&d_bar_fold, &d_star);
+                let difference = self.subtract(&product, "builder.outputOneEvaluation");
+                self.produce_identity(&difference);
+                Ok(filtered_columns)
+            }
+            _ => Err(SolidityVerifierError::UnsupportedFeature {
+                feature: "plans other than projections and filters".to_string(),
+            }),
+        }
+    }
+
+    /// `init + fold_vals(beta, values)`, which is evaluated with Horner's method.
+    fn fold(&mut self, init: &str, beta: &str, values: &[String]) -> String {
+        match values.split_last() {
+            None => init.to_string(),
+            Some((last, rest)) => {
+                let folded = rest.iter().rev().fold(last.clone(), |folded, value| {
+                    let product = self.multiply(&folded, beta);
+                    self.add(&product, value)
+                });
+                self.add(init, &folded)
+            }
+        }
+    }
+}
+
+/// A scalar as a Solidity literal
+fn scalar_literal(scalar: BNScalar) -> String {
+    format!("{:#x}", U256::from_limbs(scalar.into()))
+}
+
+/// `10^scale` as a Solidity literal
+///
+/// # Panics
+///
+/// Panics if `scale` is negative.
+fn power_of_ten(scale: i8) -> String {
+    scalar_literal(scale_scalar(BNScalar::ONE, scale).expect("scales should not be negative"))
+}
+
+/// An element of the base field of BN254 as a Solidity literal
+fn base_field_literal(element: &Fq) -> String {
+    format!("{:#x}", U256::from_limbs(element.into_bigint().0))
+}
+
+/// A point of G2 in the encoding of the `ecPairing` precompile, i.e. `(x.c1, x.c0, y.c1, y.c0)`
+///
+/// # Panics
+///
+/// Panics if `point` is the identity.
+fn g2_literal(point: &G2Affine) -> String {
+    let (x, y) = point
+        .xy()
+        .expect("the setup should not contain the identity");
+    format!(
+        "[uint256({}), {}, {}, {}]",
+        base_field_literal(&x.c1),
+        base_field_literal(&x.c0),
+        base_field_literal(&y.c1),
+        base_field_literal(&y.c0)
+    )
+}
+
+/// The type code of a result column in `QueryResult`
+fn result_column_type(column_type: ColumnType) -> Result<u8, SolidityVerifierError> {
+    match column_type {
+        ColumnType::Boolean => Ok(0),
+        ColumnType::TinyInt => Ok(1),
+        ColumnType::SmallInt => Ok(2),
+        ColumnType::Int => Ok(3),
+        ColumnType::BigInt | ColumnType::TimestampTZ(..) => Ok(4),
+        _ => Err(SolidityVerifierError::UnsupportedFeature {
+            feature: format!("result columns of type {column_type}"),
+        }),
+    }
+}
+
+fn hex_literal(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        write!(hex, "{byte:02x}").unwrap();
+        hex
+    })
+}
+
+/// A Solidity verifier for the proofs of a single [`DynProofPlan`] over the multilinear KZG commitment
+/// scheme.
+///
+/// The generated source is self-contained and defines the `QueryVerifier` library along with the
+/// [`QueryProofAbi`](super::QueryProofAbi), `Transcript`, `Sumcheck`, `Kzg`, `VerificationBuilder` and
+/// `QueryResult` libraries it is built on. `QueryVerifier.verify` takes a
+/// [`QueryProofAbi::VerifierInput`](super::QueryProofAbi::VerifierInput) and reverts unless the result is
+/// correct, i.e. it replays the [`Keccak256Transcript`](crate::base::proof::Keccak256Transcript) of the
+/// proof, checks every round of its sumcheck, evaluates the plan at the evaluation point of the sumcheck,
+/// compares the evaluations of the result columns and checks the
+/// [`KzgEvaluationProof`](crate::proof_primitive::kzg::KzgEvaluationProof) against the embedded setup.
+pub struct SolidityVerifier {
+    source: String,
+}
+
+impl SolidityVerifier {
+    /// Generate the verifier for the proofs of `plan` with the given setup.
+    ///
+    /// Only projections and filters of columns, literals, equality and arithmetic or logical operators
+    /// whose results are booleans or integers of at most 64 bits are supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `plan` cannot be serialized or if the setup contains the identity.
+    pub fn try_new(
+        plan: &DynProofPlan<KzgCommitment>,
+        accessor: &dyn MetadataAccessor,
+        setup: &KzgVerifierSetup,
+    ) -> Result<Self, SolidityVerifierError> {
+        let counts = {
+            let mut builder = CountBuilder::new(&[]);
+            plan.count(&mut builder, accessor)
+                .and_then(|()| builder.counts())
+                .map_err(|source| SolidityVerifierError::UnsupportedPlan { source })?
+        };
+        let result_column_types = plan
+            .get_column_result_fields()
+            .iter()
+            .map(|field| result_column_type(field.data_type()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let column_references = plan.get_column_references();
+        let mut evaluation = PlanEvaluation {
+            column_references: &column_references,
+            statements: Vec::new(),
+            num_values: 0,
+        };
+        let results = evaluation.evaluate_plan(plan)?;
+        let plan_evaluation = evaluation
+            .statements
+            .iter()
+            .cloned()
+            .chain(
+                results
+                    .iter()
+                    .enumerate()
+                    .map(|(i, result)| format!("results[{i}] = {result};")),
+            )
+            .map(|statement| format!("        {statement}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let (g_x, g_y) = setup
+            .g
+            .xy()
+            .expect("the setup should not contain the identity");
+        let mut setup_statements = vec![
+            format!(
+                "result.g = QueryProofAbi.G1Point({}, {});",
+                base_field_literal(g_x),
+                base_field_literal(g_y)
+            ),
+            format!("result.h = {};", g2_literal(&setup.h)),
+            format!("result.tauH = new uint256[4][]({});", setup.max_nu()),
+        ];
+        setup_statements.extend(
+            setup
+                .tau_h
+                .iter()
+                .enumerate()
+                .map(|(j, tau_h)| format!("result.tauH[{j}] = {};", g2_literal(tau_h))),
+        );
+        let setup_statements = setup_statements
+            .iter()
+            .map(|statement| format!("        {statement}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let verifier = VERIFIER_TEMPLATE
+            .replace(
+                "{{PLAN}}",
+                &hex_literal(&postcard::to_allocvec(plan).unwrap()),
+            )
+            .replace(
+                "{{RESULT_COLUMN_TYPES}}",
+                &hex_literal(&result_column_types),
+            )
+            .replace(
+                "{{MAX_MULTIPLICANDS}}",
+                &cmp::max(counts.sumcheck_max_multiplicands, 2).to_string(),
+            )
+            .replace(
+                "{{NUM_SUMCHECK_SUBPOLYNOMIALS}}",
+                &counts.sumcheck_subpolynomials.to_string(),
+            )
+            .replace(
+                "{{NUM_POST_RESULT_CHALLENGES}}",
+                &counts.post_result_challenges.to_string(),
+            )
+            .replace(
+                "{{NUM_INTERMEDIATE_MLES}}",
+                &counts.intermediate_mles.to_string(),
+            )
+            .replace(
+                "{{NUM_PCS_PROOF_EVALUATIONS}}",
+                &(counts.first_round_mles + counts.intermediate_mles + counts.anchored_mles)
+                    .to_string(),
+            )
+            .replace(
+                "{{NUM_COLUMN_COMMITMENTS}}",
+                &column_references.len().to_string(),
+            )
+            .replace("{{NUM_VALUES}}", &evaluation.num_values.to_string())
+            .replace("{{NUM_RESULT_COLUMNS}}", &results.len().to_string())
+            .replace("{{PLAN_EVALUATION}}", &plan_evaluation)
+            .replace("{{SETUP}}", &setup_statements);
+
+        // The libraries are merged into one source unit, so their headers and imports are dropped.
+        let libraries: Vec<_> = LIBRARY_SOURCES
+            .iter()
+            .flat_map(|source| source.lines())
+            .filter(|line| {
+                !line.starts_with("// SPDX")
+                    && !line.starts_with("pragma")
+                    && !line.starts_with("import")
+            })
+            .collect();
+        let source = format!(
+            "// SPDX-License-Identifier: UNLICENSED\npragma solidity ^0.8.13;\n{}\n{verifier}",
+            libraries.join("\n")
+        );
+        Ok(Self { source })
+    }
+
+    /// The Solidity source of the verifier
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
@@ -0,0 +1,1054 @@
+use super::{QueryProofAbi, SolidityVerifier, SolidityVerifierError};
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor},
+        scalar::Scalar,
+    },
+    proof_primitive::kzg::{
+        BNScalar, KzgCommitment, KzgEvaluationProof, KzgPublicParameters, KzgVerifierSetup,
+    },
+    sql::{
+        proof::{ProvableQueryResult, QueryProof},
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+    tests::{ForgeScript, ForgeScriptError},
+};
+use alloy_sol_types::private::primitives::{keccak256, U256};
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup};
+use ark_ff::{BigInt, PrimeField, Zero};
+use ark_serialize::CanonicalSerialize;
+use ark_std::test_rng;
+use std::{collections::HashMap, fs, path::PathBuf};
+
+type G1Point = QueryProofAbi::G1Point;
+
+/// A port of the generated Solidity verifier to Rust, which stands in for the EVM in tests that
+/// can't run `forge`.
+///
+/// The constants, the plan evaluation and the setup are read from the generated source, and every
+/// function mirrors the Solidity function of the same name, with `U256` in place of `uint256` and
+/// `ark_bn254` in place of the precompiles. Errors are the names of the Solidity errors.
+struct EvmStandIn {
+    constants: HashMap<String, U256>,
+    byte_constants: HashMap<String, Vec<u8>>,
+    num_values: usize,
+    num_result_columns: usize,
+    plan_evaluation: Vec<String>,
+    setup: Setup,
+}
+
+struct Setup {
+    g: G1Point,
+    h: [U256; 4],
+    tau_h: Vec<[U256; 4]>,
+}
+
+struct Subclaim {
+    post_result_challenges: Vec<U256>,
+    range_length: u64,
+    random_scalars: Vec<U256>,
+    evaluation_point: Vec<U256>,
+    expected_evaluation: U256,
+    evaluation_random_scalars: Vec<U256>,
+}
+
+#[derive(Default)]
+struct Builder {
+    pcs_proof_evaluations: Vec<U256>,
+    inner_product_multipliers: Vec<U256>,
+    subpolynomial_multipliers: Vec<U256>,
+    post_result_challenges: Vec<U256>,
+    intermediate_commitments: Vec<G1Point>,
+    consumed_pcs_proof_mles: usize,
+    consumed_intermediate_mles: usize,
+    produced_subpolynomials: usize,
+    consumed_post_result_challenges: usize,
+    input_one_evaluation: U256,
+    output_one_evaluation: U256,
+    random_evaluation: U256,
+    sumcheck_evaluation: U256,
+    folded_pcs_proof_evaluation: U256,
+    folded_pcs_proof_commitment: G1Point,
+}
+
+fn modulus() -> U256 {
+    let max_signed = U256::from_limbs(BNScalar::MAX_SIGNED.into());
+    max_signed + max_signed + U256::from(1)
+}
+
+/// The `0x...` literals of a line of Solidity
+fn hex_words(line: &str) -> Vec<U256> {
+    line.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter_map(|token| token.strip_prefix("0x"))
+        .map(|hex| U256::from_str_radix(hex, 16).unwrap())
+        .collect()
+}
+
+fn g1(point: &G1Point) -> Result<G1Affine, &'static str> {
+    if point.x.is_zero() && point.y.is_zero() {
+        return Ok(G1Affine::identity());
+    }
+    let coordinate =
+        |word: U256| Fq::from_bigint(BigInt(word.into_limbs())).ok_or("PrecompileFailed");
+    let point = G1Affine::new_unchecked(coordinate(point.x)?, coordinate(point.y)?);
+    point
+        .is_on_curve()
+        .then_some(point)
+        .ok_or("PrecompileFailed")
+}
+
+fn g1_point(point: G1Projective) -> G1Point {
+    let to_word = |coordinate: &Fq| U256::from_limbs(coordinate.into_bigint().0);
+    point
+        .into_affine()
+        .xy()
+        .map_or(G1Point::default(), |(x, y)| G1Point {
+            x: to_word(x),
+            y: to_word(y),
+        })
+}
+
+fn g2(words: &[U256; 4]) -> Result<G2Affine, &'static str> {
+    let coordinate =
+        |word: U256| Fq::from_bigint(BigInt(word.into_limbs())).ok_or("PrecompileFailed");
+    let point = G2Affine::new_unchecked(
+        Fq2::new(coordinate(words[1])?, coordinate(words[0])?),
+        Fq2::new(coordinate(words[3])?, coordinate(words[2])?),
+    );
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve())
+        .then_some(point)
+        .ok_or("PrecompileFailed")
+}
+
+// Kzg
+fn ec_add(a: &G1Point, b: &G1Point) -> Result<G1Point, &'static str> {
+    Ok(g1_point(g1(a)? + g1(b)?))
+}
+
+fn ec_mul(point: &G1Point, scalar: U256) -> Result<G1Point, &'static str> {
+    let scalar = Fr::from_be_bytes_mod_order(&scalar.to_be_bytes::<32>());
+    Ok(g1_point(g1(point)? * scalar))
+}
+
+fn kzg_verify(
+    commitment: &G1Point,
+    product: U256,
+    point: &[U256],
+    quotients: &[G1Point],
+    setup: &Setup,
+) -> Result<(), &'static str> {
+    let max_nu = setup.tau_h.len();
+    if point.len() > max_nu || quotients.len() != max_nu {
+        return Err("EvaluationProofFailed");
+    }
+    let mut lhs = ec_add(commitment, &ec_mul(&setup.g, modulus() - product)?)?;
+    for (quotient, &z) in quotients.iter().zip(point) {
+        lhs = ec_add(&lhs, &ec_mul(quotient, z)?)?;
+    }
+    let mut g1_points = vec![g1(&lhs)?];
+    let mut g2_points = vec![g2(&setup.h)?];
+    for (quotient, tau_h) in quotients.iter().zip(&setup.tau_h) {
+        g1_points.push(-g1(quotient)?);
+        g2_points.push(g2(tau_h)?);
+    }
+    if Bn254::multi_pairing(g1_points, g2_points).is_zero() {
+        Ok(())
+    } else {
+        Err("EvaluationProofFailed")
+    }
+}
+
+// VerificationBuilder
+impl Builder {
+    fn consume_anchored_mle(&mut self, commitment: &G1Point) -> Result<U256, &'static str> {
+        let index = self.consumed_pcs_proof_mles;
+        self.consumed_pcs_proof_mles += 1;
+        let multiplier = self.inner_product_multipliers[index];
+        let result = self.pcs_proof_evaluations[index];
+        self.folded_pcs_proof_commitment = ec_add(
+            &self.folded_pcs_proof_commitment,
+            &ec_mul(commitment, multiplier)?,
+        )?;
+        self.folded_pcs_proof_evaluation = self
+            .folded_pcs_proof_evaluation
+            .add_mod(multiplier.mul_mod(result, modulus()), modulus());
+        Ok(result)
+    }
+
+    fn consume_intermediate_mle(&mut self) -> Result<U256, &'static str> {
+        let commitment = self.intermediate_commitments[self.consumed_intermediate_mles].clone();
+        self.consumed_intermediate_mles += 1;
+        self.consume_anchored_mle(&commitment)
+    }
+
+    fn produce_identity(&mut self, evaluation: U256) {
+        self.produce_zero_sum(evaluation.mul_mod(self.random_evaluation, modulus()));
+    }
+
+    fn produce_zero_sum(&mut self, evaluation: U256) {
+        let multiplier = self.subpolynomial_multipliers[self.produced_subpolynomials];
+        self.produced_subpolynomials += 1;
+        self.sumcheck_evaluation = self
+            .sumcheck_evaluation
+            .add_mod(multiplier.mul_mod(evaluation, modulus()), modulus());
+    }
+
+    fn consume_post_result_challenge(&mut self) -> U256 {
+        self.consumed_post_result_challenges += 1;
+        self.post_result_challenges
+            [self.post_result_challenges.len() - self.consumed_post_result_challenges]
+    }
+
+    fn completed(&self) -> bool {
+        self.consumed_pcs_proof_mles == self.pcs_proof_evaluations.len()
+            && self.consumed_intermediate_mles == self.intermediate_commitments.len()
+            && self.produced_subpolynomials == self.subpolynomial_multipliers.len()
+            && self.consumed_post_result_challenges == self.post_result_challenges.len()
+    }
+}
+
+fn truncated_lagrange_basis_sum(length: u64, point: &[U256]) -> U256 {
+    let m = modulus();
+    let mut part = U256::ZERO;
+    for (k, &z) in point.iter().enumerate() {
+        let first_half_term = U256::from(1).add_mod(m - z, m);
+        part = if (length >> k) & 1 == 1 {
+            first_half_term.add_mod(part.mul_mod(z, m), m)
+        } else {
+            part.mul_mod(first_half_term, m)
+        };
+    }
+    if point.len() < 64 && length >> point.len() != 0 {
+        part = U256::from(1);
+    }
+    part
+}
+
+fn truncated_lagrange_basis_inner_product(length: u64, a: &[U256], b: &[U256]) -> U256 {
+    let m = modulus();
+    let mut part = U256::ZERO;
+    let mut full = U256::from(1);
+    for (k, (&a, &b)) in a.iter().zip(b).enumerate() {
+        let first_half_term = U256::from(1)
+            .add_mod(m - a, m)
+            .mul_mod(U256::from(1).add_mod(m - b, m), m);
+        let second_half_term = a.mul_mod(b, m);
+        part = if (length >> k) & 1 == 1 {
+            full.mul_mod(first_half_term, m)
+                .add_mod(part.mul_mod(second_half_term, m), m)
+        } else {
+            part.mul_mod(first_half_term, m)
+        };
+        full = full.mul_mod(first_half_term.add_mod(second_half_term, m), m);
+    }
+    if a.len() < 64 && length >> a.len() != 0 {
+        part = full;
+    }
+    part
+}
+
+// QueryResult
+fn query_result_evaluate(
+    result: &QueryProofAbi::ProvableQueryResult,
+    column_types: &[u8],
+    point: &[U256],
+) -> Result<Vec<U256>, &'static str> {
+    let m = modulus();
+    if result.numColumns as usize != column_types.len() {
+        return Err("InvalidColumnCount");
+    }
+    let lagrange_basis = lagrange_basis_evaluations(result.tableLength, point)?;
+    let mut evaluations = vec![U256::ZERO; column_types.len()];
+    let mut offset = 0;
+    for (evaluation, &column_type) in evaluations.iter_mut().zip(column_types) {
+        for basis in &lagrange_basis {
+            let value;
+            (value, offset) = read_value(&result.data, offset, column_type)?;
+            *evaluation = evaluation.add_mod(basis.mul_mod(value, m), m);
+        }
+    }
+    if offset != result.data.len() {
+        return Err("InvalidResultData");
+    }
+    Ok(evaluations)
+}
+
+fn lagrange_basis_evaluations(length: u64, point: &[U256]) -> Result<Vec<U256>, &'static str> {
+    let m = modulus();
+    if point.len() < 64 && length > 1 << point.len() {
+        return Err("InvalidResultData");
+    }
+    Ok((0..length)
+        .map(|i| {
+            point
+                .iter()
+                .enumerate()
+                .fold(U256::from(1), |acc, (k, &z)| {
+                    let factor = if (i >> k) & 1 == 1 {
+                        z
+                    } else {
+                        U256::from(1).add_mod(m - z, m)
+                    };
+                    acc.mul_mod(factor, m)
+                })
+        })
+        .collect())
+}
+
+fn read_value(data: &[u8], offset: usize, column_type: u8) -> Result<(U256, usize), &'static str> {
+    let (encoded, next_offset) = read_varint(data, offset)?;
+    if column_type == 0 {
+        return if encoded > 1 {
+            Err("InvalidResultData")
+        } else {
+            Ok((U256::from(encoded), next_offset))
+        };
+    }
+    let bound: u128 = match column_type {
+        1 => 1 << 7,
+        2 => 1 << 15,
+        3 => 1 << 31,
+        _ => 1 << 63,
+    };
+    let value = if encoded & 1 == 0 {
+        let value = u128::from(encoded >> 1);
+        if value >= bound {
+            return Err("InvalidResultData");
+        }
+        U256::from(value)
+    } else {
+        let magnitude = u128::from(encoded >> 1) + 1;
+        if magnitude > bound {
+            return Err("InvalidResultData");
+        }
+        modulus() - U256::from(magnitude)
+    };
+    Ok((value, next_offset))
+}
+
+fn read_varint(data: &[u8], offset: usize) -> Result<(u64, usize), &'static str> {
+    let mut value = 0_u64;
+    let mut shift = 0;
+    let mut next_offset = offset;
+    loop {
+        let byte = *data.get(next_offset).ok_or("InvalidResultData")?;
+        next_offset += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        shift += 7;
+        if shift > 63 {
+            if byte >= 2 {
+                return Err("InvalidResultData");
+            }
+            break;
+        } else if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    Ok((value, next_offset))
+}
+
+impl EvmStandIn {
+    fn new(verifier: &SolidityVerifier) -> Self {
+        let mut constants = HashMap::new();
+        let mut byte_constants = HashMap::new();
+        let mut num_values = 0;
+        let mut num_result_columns = 0;
+        let mut plan_evaluation = Vec::new();
+        let mut setup = Setup {
+            g: G1Point::default(),
+            h: [U256::ZERO; 4],
+            tau_h: Vec::new(),
+        };
+        for line in verifier.source().lines().map(str::trim) {
+            if let Some((name, hex)) = line
+                .strip_prefix("bytes internal constant ")
+                .and_then(|rest| rest.strip_suffix("\";"))
+                .and_then(|rest| rest.split_once(" = hex\""))
+            {
+                let bytes = (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+                    .collect();
+                byte_constants.insert(name.to_string(), bytes);
+            } else if let Some((name, value)) = line
+                .strip_prefix("uint256 internal constant ")
+                .or_else(|| line.strip_prefix("uint64 internal constant "))
+                .and_then(|rest| rest.strip_suffix(';'))
+                .and_then(|rest| rest.split_once(" = "))
+            {
+                if let Ok(value) = value.parse() {
+                    constants.insert(name.to_string(), value);
+                }
+            } else if let Some(length) = line
+                .strip_prefix("uint256[] memory v = new uint256[](")
+                .and_then(|rest| rest.strip_suffix(");"))
+            {
+                num_values = length.parse().unwrap();
+            } else if let Some(length) = line
+                .strip_prefix("results = new uint256[](")
+                .and_then(|rest| rest.strip_suffix(");"))
+            {
+                num_result_columns = length.parse().unwrap();
+            } else if line.starts_with("v[")
+                || line.starts_with("results[")
+                || line.starts_with("VerificationBuilder.produce")
+            {
+                plan_evaluation.push(line.to_string());
+            } else if line.starts_with("result.g = ") {
+                let words = hex_words(line);
+                setup.g = G1Point {
+                    x: words[0],
+                    y: words[1],
+                };
+            } else if line.starts_with("result.h = ") {
+                setup.h = hex_words(line).try_into().unwrap();
+            } else if line.starts_with("result.tauH[") {
+                setup.tau_h.push(hex_words(line).try_into().unwrap());
+            }
+        }
+        Self {
+            constants,
+            byte_constants,
+            num_values,
+            num_result_columns,
+            plan_evaluation,
+            setup,
+        }
+    }
+
+    fn constant(&self, name: &str) -> U256 {
+        self.constants[name]
+    }
+
+    fn constant_usize(&self, name: &str) -> usize {
+        self.constant(name).to::<usize>()
+    }
+
+    // Transcript
+    fn scalar_challenge(buffer: &mut Vec<u8>) -> U256 {
+        let challenge = keccak256(&buffer);
+        *buffer = challenge.to_vec();
+        U256::from_be_bytes(challenge.0).reduce_mod(modulus())
+    }
+
+    fn scalar_challenges(buffer: &mut Vec<u8>, count: usize) -> Vec<U256> {
+        (0..count).map(|_| Self::scalar_challenge(buffer)).collect()
+    }
+
+    fn varint(mut value: u64) -> Vec<u8> {
+        let mut result = Vec::new();
+        while value > 0x7f {
+            result.push((value & 0x7f) as u8 | 0x80);
+            value >>= 7;
+        }
+        result.push(value as u8);
+        result
+    }
+
+    fn points(values: &[G1Point]) -> Vec<u8> {
+        let mut result = Self::varint(values.len() as u64);
+        for value in values {
+            result.push(0x40);
+            result.extend(value.x.to_be_bytes::<32>());
+            result.extend(value.y.to_be_bytes::<32>());
+        }
+        result
+    }
+
+    fn canonical_scalars(scalars: &[U256]) -> Vec<u8> {
+        let mut result = (scalars.len() as u64).to_le_bytes().to_vec();
+        for scalar in scalars {
+            result.extend(scalar.to_le_bytes::<32>());
+        }
+        result
+    }
+
+    // Sumcheck
+    fn verify_without_evaluation(
+        buffer: &mut Vec<u8>,
+        coefficients: &[U256],
+        max_multiplicands: u64,
+        num_variables: u64,
+    ) -> Result<(Vec<U256>, U256), &'static str> {
+        let m = modulus();
+        buffer.extend(max_multiplicands.to_be_bytes());
+        buffer.extend(num_variables.to_be_bytes());
+        Self::scalar_challenge(buffer);
+        let round_length = max_multiplicands as usize + 1;
+        if coefficients.len() != num_variables as usize * round_length {
+            return Err("InvalidSumcheckProofSize");
+        }
+        let mut evaluation_point = Vec::new();
+        let mut expected_evaluation = U256::ZERO;
+        for round in coefficients.chunks(round_length) {
+            for coefficient in round {
+                if *coefficient >= m {
+                    return Err("InvalidScalar");
+                }
+                buffer.extend(coefficient.to_be_bytes::<32>());
+            }
+            let round_evaluation_point = Self::scalar_challenge(buffer);
+            evaluation_point.push(round_evaluation_point);
+            let mut round_evaluation = round[0];
+            let mut actual_sum = round[0].add_mod(round[round_length - 1], m);
+            for &coefficient in &round[1..] {
+                round_evaluation = round_evaluation
+                    .mul_mod(round_evaluation_point, m)
+                    .add_mod(coefficient, m);
+                actual_sum = actual_sum.add_mod(coefficient, m);
+            }
+            if actual_sum != expected_evaluation {
+                return Err("RoundEvaluationMismatch");
+            }
+            expected_evaluation = round_evaluation;
+        }
+        Ok((evaluation_point, expected_evaluation))
+    }
+
+    // QueryVerifier
+    fn verify(&self, input: &QueryProofAbi::VerifierInput) -> Result<(), &'static str> {
+        self.check_sizes(input)?;
+        let subclaim = self.verify_transcript_and_sumcheck(input)?;
+        let mut builder = Self::new_builder(input, &subclaim);
+        let evaluations = self.evaluate_plan(&mut builder, &input.columnCommitments)?;
+        assert!(builder.completed());
+        let result_evaluations = query_result_evaluate(
+            &input.result,
+            &self.byte_constants["RESULT_COLUMN_TYPES"],
+            &subclaim.evaluation_point,
+        )?;
+        if evaluations != result_evaluations {
+            return Err("ResultEvaluationMismatch");
+        }
+        if builder.sumcheck_evaluation != subclaim.expected_evaluation {
+            return Err("SumcheckEvaluationMismatch");
+        }
+        kzg_verify(
+            &builder.folded_pcs_proof_commitment,
+            builder.folded_pcs_proof_evaluation,
+            &subclaim.evaluation_point,
+            &input.proof.evaluationProof,
+            &self.setup,
+        )
+    }
+
+    fn check_sizes(&self, input: &QueryProofAbi::VerifierInput) -> Result<(), &'static str> {
+        let proof = &input.proof;
+        if !proof.oneEvaluationLengths.is_empty()
+            || proof.stringDictionaries[..] != [0]
+            || proof.bitDistributions[..] != [0]
+            || !proof.firstRoundCommitments.is_empty()
+            || proof.commitments.len() != self.constant_usize("NUM_INTERMEDIATE_MLES")
+            || proof.pcsProofEvaluations.len() != self.constant_usize("NUM_PCS_PROOF_EVALUATIONS")
+            || input.columnCommitments.len() != self.constant_usize("NUM_COLUMN_COMMITMENTS")
+        {
+            return Err("InvalidProofSize");
+        }
+        if input.generatorOffset != 0 {
+            return Err("InvalidGeneratorOffset");
+        }
+        Ok(())
+    }
+
+    fn verify_transcript_and_sumcheck(
+        &self,
+        input: &QueryProofAbi::VerifierInput,
+    ) -> Result<Subclaim, &'static str> {
+        let proof = &input.proof;
+        let mut buffer = self.public_inputs(input);
+        buffer.extend(Self::one_evaluation_lengths(&proof.oneEvaluationLengths));
+        buffer.extend(proof.stringDictionaries.iter());
+        buffer.extend(Self::points(&proof.firstRoundCommitments));
+        let post_result_challenges = Self::scalar_challenges(
+            &mut buffer,
+            self.constant_usize("NUM_POST_RESULT_CHALLENGES"),
+        );
+        buffer.extend(Self::points(&proof.commitments));
+        buffer.extend(proof.bitDistributions.iter());
+
+        let range_length = Self::range_length(input);
+        let num_sumcheck_variables = Self::num_variables(range_length);
+        let random_scalars = Self::scalar_challenges(
+            &mut buffer,
+            num_sumcheck_variables as usize + self.constant_usize("NUM_SUMCHECK_SUBPOLYNOMIALS"),
+        );
+        let (evaluation_point, expected_evaluation) = Self::verify_without_evaluation(
+            &mut buffer,
+            &proof.sumcheckCoefficients,
+            self.constant("MAX_MULTIPLICANDS").to::<u64>(),
+            num_sumcheck_variables,
+        )?;
+
+        if proof
+            .pcsProofEvaluations
+            .iter()
+            .any(|evaluation| *evaluation >= modulus())
+        {
+            return Err("InvalidScalar");
+        }
+        buffer.extend(Self::canonical_scalars(&proof.pcsProofEvaluations));
+        let evaluation_random_scalars = Self::scalar_challenges(
+            &mut buffer,
+            self.constant_usize("NUM_PCS_PROOF_EVALUATIONS"),
+        );
+        Ok(Subclaim {
+            post_result_challenges,
+            range_length,
+            random_scalars,
+            evaluation_point,
+            expected_evaluation,
+            evaluation_random_scalars,
+        })
+    }
+
+    fn new_builder(input: &QueryProofAbi::VerifierInput, subclaim: &Subclaim) -> Builder {
+        let (entrywise_point, subpolynomial_multipliers) = subclaim
+            .random_scalars
+            .split_at(subclaim.evaluation_point.len());
+        Builder {
+            pcs_proof_evaluations: input.proof.pcsProofEvaluations.clone(),
+            inner_product_multipliers: subclaim.evaluation_random_scalars.clone(),
+            subpolynomial_multipliers: subpolynomial_multipliers.to_vec(),
+            post_result_challenges: subclaim.post_result_challenges.clone(),
+            intermediate_commitments: input.proof.commitments.clone(),
+            input_one_evaluation: truncated_lagrange_basis_sum(
+                input.tableLength,
+                &subclaim.evaluation_point,
+            ),
+            output_one_evaluation: truncated_lagrange_basis_sum(
+                input.result.tableLength,
+                &subclaim.evaluation_point,
+            ),
+            random_evaluation: truncated_lagrange_basis_inner_product(
+                subclaim.range_length,
+                &subclaim.evaluation_point,
+                entrywise_point,
+            ),
+            ..Builder::default()
+        }
+    }
+
+    /// Interprets the statements of the generated `evaluatePlan`.
+    fn evaluate_plan(
+        &self,
+        builder: &mut Builder,
+        columns: &[G1Point],
+    ) -> Result<Vec<U256>, &'static str> {
+        fn index(slot: &str, name: &str) -> usize {
+            slot.strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix('['))
+                .and_then(|rest| rest.strip_suffix(']'))
+                .unwrap()
+                .parse()
+                .unwrap()
+        }
+        fn operand(builder: &Builder, v: &[U256], operand: &str) -> U256 {
+            if let Some(rest) = operand.strip_prefix("MODULUS - ") {
+                modulus() - operand_atom(builder, v, rest)
+            } else {
+                operand_atom(builder, v, operand)
+            }
+        }
+        fn operand_atom(builder: &Builder, v: &[U256], atom: &str) -> U256 {
+            match atom {
+                "builder.inputOneEvaluation" => builder.input_one_evaluation,
+                "builder.outputOneEvaluation" => builder.output_one_evaluation,
+                _ if atom.starts_with("0x") => U256::from_str_radix(&atom[2..], 16).unwrap(),
+                _ => v[index(atom, "v")],
+            }
+        }
+        fn binary_operands<'a>(expression: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+            let arguments = expression
+                .strip_prefix(name)?
+                .strip_prefix('(')?
+                .strip_suffix(", MODULUS)")?;
+            arguments.split_once(", ")
+        }
+
+        let m = modulus();
+        let mut v = vec![U256::ZERO; self.num_values];
+        let mut results = vec![U256::ZERO; self.num_result_columns];
+        for statement in &self.plan_evaluation {
+            let statement = statement.strip_suffix(';').unwrap();
+            if let Some(evaluation) = statement
+                .strip_prefix("VerificationBuilder.produceIdentity(builder, ")
+                .and_then(|rest| rest.strip_suffix(')'))
+            {
+                builder.produce_identity(operand(builder, &v, evaluation));
+            } else if let Some(evaluation) = statement
+                .strip_prefix("VerificationBuilder.produceZeroSum(builder, ")
+                .and_then(|rest| rest.strip_suffix(')'))
+            {
+                builder.produce_zero_sum(operand(builder, &v, evaluation));
+            } else if let Some(slot) = statement
+                .strip_prefix("results")
+                .and_then(|rest| rest.split_once(" = "))
+            {
+                results[index(slot.0, "")] = operand(builder, &v, slot.1);
+            } else {
+                let (slot, expression) = statement.split_once(" = ").unwrap();
+                let value = if expression == "VerificationBuilder.consumeIntermediateMle(builder)" {
+                    builder.consume_intermediate_mle()?
+                } else if expression == "VerificationBuilder.consumePostResultChallenge(builder)" {
+                    builder.consume_post_result_challenge()
+                } else if let Some(column) = expression
+                    .strip_prefix("VerificationBuilder.consumeAnchoredMle(builder, ")
+                    .and_then(|rest| rest.strip_suffix(')'))
+                {
+                    builder.consume_anchored_mle(&columns[index(column, "columns")])?
+                } else if let Some((lhs, rhs)) = binary_operands(expression, "addmod") {
+                    operand(builder, &v, lhs).add_mod(operand(builder, &v, rhs), m)
+                } else if let Some((lhs, rhs)) = binary_operands(expression, "mulmod") {
+                    operand(builder, &v, lhs).mul_mod(operand(builder, &v, rhs), m)
+                } else {
+                    panic!("unexpected expression: {expression}");
+                };
+                v[index(slot, "v")] = value;
+            }
+        }
+        Ok(results)
+    }
+
+    fn public_inputs(&self, input: &QueryProofAbi::VerifierInput) -> Vec<u8> {
+        let mut result = Self::varint(input.result.numColumns);
+        result.extend(Self::varint(input.result.tableLength));
+        result.extend(Self::varint(input.result.data.len() as u64));
+        result.extend(input.result.data.iter());
+        result.extend(&self.byte_constants["PLAN"]);
+        result.extend(Self::varint(input.tableLength));
+        result.extend(Self::varint(input.generatorOffset));
+        result
+    }
+
+    fn one_evaluation_lengths(lengths: &[u64]) -> Vec<u8> {
+        let mut result = Self::varint(lengths.len() as u64);
+        for &length in lengths {
+            result.extend(Self::varint(length));
+        }
+        result
+    }
+
+    fn range_length(input: &QueryProofAbi::VerifierInput) -> u64 {
+        input.proof.oneEvaluationLengths.iter().fold(
+            input.tableLength.max(input.result.tableLength),
+            |acc, &length| acc.max(length),
+        )
+    }
+
+    fn num_variables(length: u64) -> u64 {
+        let mut result = 1;
+        while (1_u64 << result) < length {
+            result += 1;
+        }
+        result
+    }
+}
+
+fn test_table() -> OwnedTable<BNScalar> {
+    owned_table([
+        bigint("a", [1_i64, 5, 3, 5, 7, 5]),
+        int("b", [2_i32, -3, 4, 3, 0, -1]),
+        smallint("c", [0_i16, 1, 2, 3, 4, 5]),
+        tinyint("d", [-1_i8, 1, -1, 1, -1, 1]),
+        boolean("e", [true, false, true, true, false, false]),
+    ])
+}
+
+fn prove_and_generate_verifier(
+    public_parameters: &KzgPublicParameters,
+    make_plan: impl Fn(&OwnedTableTestAccessor<KzgEvaluationProof>) -> DynProofPlan<KzgCommitment>,
+) -> (
+    SolidityVerifier,
+    QueryProofAbi::VerifierInput,
+    QueryProof<KzgEvaluationProof>,
+    ProvableQueryResult,
+) {
+    let verifier_setup = KzgVerifierSetup::from(public_parameters);
+    let accessor = OwnedTableTestAccessor::<KzgEvaluationProof>::new_from_table(
+        "sxt.t".parse().unwrap(),
+        test_table(),
+        0,
+        public_parameters,
+    );
+    let plan = make_plan(&accessor);
+    let (proof, result) =
+        QueryProof::<KzgEvaluationProof>::new(&plan, &accessor, &public_parameters);
+    proof
+        .verify(&plan, &accessor, &result, &&verifier_setup)
+        .unwrap();
+    let verifier = SolidityVerifier::try_new(&plan, &accessor, &verifier_setup).unwrap();
+    let input = QueryProofAbi::VerifierInput::new(&plan, &accessor, &result, &proof);
+    (verifier, input, proof, result)
+}
+
+// select a, b from sxt.t where a = 5
+fn filter_plan(
+    accessor: &OwnedTableTestAccessor<KzgEvaluationProof>,
+) -> DynProofPlan<KzgCommitment> {
+    let t = "sxt.t".parse().unwrap();
+    filter(
+        cols_expr_plan(t, &["a", "b"], accessor),
+        tab(t),
+        equal(column(t, "a", accessor), const_bigint(5)),
+    )
+}
+
+// select a * b as f, c - d as g, e or b = 4 as h from sxt.t
+// where not (a = 5.0) or (e and c + 1 = 5)
+fn filter_with_operators_plan(
+    accessor: &OwnedTableTestAccessor<KzgEvaluationProof>,
+) -> DynProofPlan<KzgCommitment> {
+    let t = "sxt.t".parse().unwrap();
+    filter(
+        vec![
+            aliased_plan(
+                multiply(column(t, "a", accessor), column(t, "b", accessor)),
+                "f",
+            ),
+            aliased_plan(
+                subtract(column(t, "c", accessor), column(t, "d", accessor)),
+                "g",
+            ),
+            aliased_plan(
+                or(
+                    column(t, "e", accessor),
+                    equal(column(t, "b", accessor), const_int(4)),
+                ),
+                "h",
+            ),
+        ],
+        tab(t),
+        or(
+            not(equal(column(t, "a", accessor), const_decimal75(2, 1, 50))),
+            and(
+                column(t, "e", accessor),
+                equal(
+                    add(column(t, "c", accessor), const_smallint(1)),
+                    const_smallint(5),
+                ),
+            ),
+        ),
+    )
+}
+
+// select a + b as f, d = -1 as g from sxt.t
+fn projection_plan(
+    accessor: &OwnedTableTestAccessor<KzgEvaluationProof>,
+) -> DynProofPlan<KzgCommitment> {
+    let t = "sxt.t".parse().unwrap();
+    projection(
+        vec![
+            aliased_plan(add(column(t, "a", accessor), column(t, "b", accessor)), "f"),
+            aliased_plan(equal(column(t, "d", accessor), const_bigint(-1)), "g"),
+        ],
+        tab(t),
+    )
+}
+
+#[test]
+fn we_can_verify_proofs_with_the_generated_verifier() {
+    let public_parameters = KzgPublicParameters::test_rand(4, &mut test_rng());
+    for make_plan in [filter_plan, filter_with_operators_plan, projection_plan] {
+        let (verifier, input, _, _) = prove_and_generate_verifier(&public_parameters, make_plan);
+        EvmStandIn::new(&verifier).verify(&input).unwrap();
+    }
+}
+
+#[test]
+fn we_can_encode_proofs_like_the_transcript_of_the_prover() {
+    let public_parameters = KzgPublicParameters::test_rand(4, &mut test_rng());
+    let (verifier, input, proof, result) =
+        prove_and_generate_verifier(&public_parameters, filter_plan);
+    let stand_in = EvmStandIn::new(&verifier);
+
+    let mut expected_public_inputs = postcard::to_allocvec(&result).unwrap();
+    expected_public_inputs.extend(stand_in.byte_constants["PLAN"].iter());
+    expected_public_inputs.extend(postcard::to_allocvec(&6_usize).unwrap());
+    expected_public_inputs.extend(postcard::to_allocvec(&0_usize).unwrap());
+    assert_eq!(stand_in.public_inputs(&input), expected_public_inputs);
+
+    for length in [0_usize, 1, 127, 128, 300, usize::MAX] {
+        assert_eq!(
+            EvmStandIn::varint(length as u64),
+            postcard::to_allocvec(&length).unwrap()
+        );
+    }
+
+    assert_eq!(
+        EvmStandIn::points(&input.proof.commitments),
+        postcard::to_allocvec(&proof.commitments).unwrap()
+    );
+
+    let mut expected_evaluations = Vec::new();
+    proof
+        .pcs_proof_evaluations
+        .serialize_compressed(&mut expected_evaluations)
+        .unwrap();
+    assert_eq!(
+        EvmStandIn::canonical_scalars(&input.proof.pcsProofEvaluations),
+        expected_evaluations
+    );
+}
+
+#[test]
+fn we_cannot_verify_tampered_proofs_with_the_generated_verifier() {
+    let public_parameters = KzgPublicParameters::test_rand(4, &mut test_rng());
+    let (verifier, input, _, _) = prove_and_generate_verifier(&public_parameters, filter_plan);
+    let stand_in = EvmStandIn::new(&verifier);
+
+    let mut tampered_input = input.clone();
+    tampered_input.proof.sumcheckCoefficients[4] += U256::from(1);
+    assert_eq!(
+        stand_in.verify(&tampered_input),
+        Err("RoundEvaluationMismatch")
+    );
+
+    let mut tampered_input = input.clone();
+    tampered_input.result.data = {
+        let mut data = tampered_input.result.data.to_vec();
+        data[0] ^= 1;
+        data.into()
+    };
+    assert_eq!(
+        stand_in.verify(&tampered_input),
+        Err("RoundEvaluationMismatch")
+    );
+
+    // The evaluations are consumed in the order `a`, the two intermediate MLEs of `a = 5`, `a`, `b`,
+    // the filtered `a` and `b`, and the two intermediate MLEs of the filter.
+    let mut tampered_input = input.clone();
+    tampered_input.proof.pcsProofEvaluations[5] += U256::from(1);
+    assert_eq!(
+        stand_in.verify(&tampered_input),
+        Err("ResultEvaluationMismatch")
+    );
+
+    let mut tampered_input = input.clone();
+    tampered_input.proof.pcsProofEvaluations[0] += U256::from(1);
+    assert_eq!(
+        stand_in.verify(&tampered_input),
+        Err("SumcheckEvaluationMismatch")
+    );
+
+    // The commitments of the table are not part of the transcript, so only the evaluation proof
+    // catches them.
+    let mut tampered_input = input.clone();
+    tampered_input.columnCommitments.swap(0, 1);
+    assert_eq!(
+        stand_in.verify(&tampered_input),
+        Err("EvaluationProofFailed")
+    );
+
+    let mut tampered_input = input.clone();
+    tampered_input.proof.evaluationProof.swap(0, 1);
+    assert_eq!(
+        stand_in.verify(&tampered_input),
+        Err("EvaluationProofFailed")
+    );
+
+    let mut tampered_input = input.clone();
+    tampered_input.generatorOffset += 1;
+    assert_eq!(
+        stand_in.verify(&tampered_input),
+        Err("InvalidGeneratorOffset")
+    );
+
+    let mut tampered_input = input;
+    tampered_input.proof.pcsProofEvaluations.pop();
+    assert_eq!(stand_in.verify(&tampered_input), Err("InvalidProofSize"));
+}
+
+#[test]
+fn we_can_only_generate_verifiers_for_supported_plans() {
+    let public_parameters = KzgPublicParameters::test_rand(4, &mut test_rng());
+    let verifier_setup = KzgVerifierSetup::from(&public_parameters);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<KzgEvaluationProof>::new_from_table(
+        t,
+        owned_table([bigint("a", [1_i64, 2, 3]), varchar("b", ["x", "y", "z"])]),
+        0,
+        &public_parameters,
+    );
+
+    // range checks
+    let plan = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        gte(column(t, "a", &accessor), const_bigint(2)),
+    );
+    assert!(matches!(
+        SolidityVerifier::try_new(&plan, &accessor, &verifier_setup),
+        Err(SolidityVerifierError::UnsupportedPlan { .. })
+    ));
+
+    let plan = group_by(
+        cols_expr_plan(t, &["b"], &accessor),
+        vec![sum_expr(column(t, "a", &accessor), "s")],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    assert!(matches!(
+        SolidityVerifier::try_new(&plan, &accessor, &verifier_setup),
+        Err(SolidityVerifierError::UnsupportedFeature { .. })
+    ));
+
+    let plan = filter(
+        cols_expr_plan(t, &["b"], &accessor),
+        tab(t),
+        equal(column(t, "a", &accessor), const_bigint(2)),
+    );
+    assert!(matches!(
+        SolidityVerifier::try_new(&plan, &accessor, &verifier_setup),
+        Err(SolidityVerifierError::UnsupportedFeature { .. })
+    ));
+
+    let plan = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        equal(column(t, "a", &accessor), const_bigint(2)),
+    );
+    let verifier = SolidityVerifier::try_new(&plan, &accessor, &verifier_setup).unwrap();
+    assert!(verifier.source().contains("library QueryVerifier"));
+    assert!(!verifier.source().contains("{{"));
+    assert_eq!(
+        verifier
+            .source()
+            .matches("// SPDX-License-Identifier")
+            .count(),
+        1
+    );
+}
+
+#[test]
+#[ignore = "Because forge needs to be installed, we are ignoring this test by default. They will still be run from within the ci."]
+fn we_can_verify_a_proof_with_the_generated_verifier_on_the_evm() {
+    let public_parameters = KzgPublicParameters::test_rand(4, &mut test_rng());
+    let (verifier, input, _, _) =
+        prove_and_generate_verifier(&public_parameters, filter_with_operators_plan);
+    let path: PathBuf = std::env::temp_dir().join("QueryVerifier.t.sol");
+    fs::write(&path, verifier.source()).unwrap();
+
+    ForgeScript::new(&path, "verifyEncoded(bytes)")
+        .arg(&input)
+        .execute()
+        .unwrap();
+
+    let tampers: [fn(&mut QueryProofAbi::VerifierInput); 3] = [
+        |input| input.proof.sumcheckCoefficients[4] += U256::from(1),
+        |input| input.proof.pcsProofEvaluations[0] += U256::from(1),
+        |input| input.columnCommitments.swap(0, 1),
+    ];
+    for tamper in tampers {
+        let mut tampered_input = input.clone();
+        tamper(&mut tampered_input);
+        assert!(matches!(
+            ForgeScript::new(&path, "verifyEncoded(bytes)")
+                .arg(&tampered_input)
+                .execute(),
+            Err(ForgeScriptError::SolidityError { .. })
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 //! This module contains the main logic for Proof of SQL.
+#[cfg(feature = "evm")]
+pub mod evm;
 pub mod parse;
 pub mod postprocessing;
 pub mod proof;
 pub mod proof_exprs;
+pub(crate) mod proof_gadgets;
 pub mod proof_plans;
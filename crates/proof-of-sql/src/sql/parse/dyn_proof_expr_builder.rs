@@ -2,7 +2,7 @@ use super::ConversionError;
 use crate::{
     base::{
         commitment::Commitment,
-        database::{ColumnRef, LiteralValue},
+        database::{presence_column_id, ColumnRef, ColumnType, LiteralValue},
         map::IndexMap,
         math::decimal::{try_into_to_scalar, DecimalError::InvalidPrecision, Precision},
     },
@@ -11,7 +11,7 @@ use crate::{
         proof_exprs::{ColumnExpr, DynProofExpr, ProofExpr},
     },
 };
-use alloc::{borrow::ToOwned, boxed::Box, format, string::ToString};
+use alloc::{borrow::ToOwned, boxed::Box, format, string::ToString, vec::Vec};
 use proof_of_sql_parser::{
     intermediate_ast::{AggregationOperator, BinaryOperator, Expression, Literal, UnaryOperator},
     posql_time::{PoSQLTimeUnit, PoSQLTimestampError},
@@ -41,27 +41,95 @@ impl<'a> DynProofExprBuilder<'a> {
         }
     }
     /// Builds a `proofs::sql::proof_exprs::DynProofExpr` from a `proof_of_sql_parser::intermediate_ast::Expression`
+    ///
+    /// Boolean expressions are `false` for the rows where they are null.
     pub fn build<C: Commitment>(
         &self,
         expr: &Expression,
     ) -> Result<DynProofExpr<C>, ConversionError> {
+        Ok(self.visit_expr(expr)?.0)
+    }
+    /// Builds a `proofs::sql::proof_exprs::DynProofExpr` from a `proof_of_sql_parser::intermediate_ast::Expression`
+    /// together with the presence of the expression, i.e. which rows are not null.
+    ///
+    /// The presence is `None` if the expression is not nullable.
+    pub(crate) fn build_nullable<C: Commitment>(
+        &self,
+        expr: &Expression,
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
         self.visit_expr(expr)
     }
 }
 
+/// A `DynProofExpr` together with its presence, if it is nullable.
+///
+/// Nullable boolean expressions are always `false` for null rows.
+pub(crate) type NullableDynProofExpr<C> = (DynProofExpr<C>, Option<DynProofExpr<C>>);
+
+/// Returns the presence of an expression whose operands have the presences `lhs` and `rhs`,
+/// i.e. which is null exactly when either operand is null.
+fn and_presences<C: Commitment>(
+    lhs: Option<DynProofExpr<C>>,
+    rhs: Option<DynProofExpr<C>>,
+) -> Result<Option<DynProofExpr<C>>, ConversionError> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Ok(Some(DynProofExpr::try_new_and(lhs, rhs)?)),
+        (presence, None) | (None, presence) => Ok(presence),
+    }
+}
+
+/// Returns the value of an integer literal that fits into 64 bits.
+fn integer_literal(expr: &Expression) -> Option<i64> {
+    match expr {
+        Expression::Literal(Literal::BigInt(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Whether the values of the type are integers of at most 64 bits.
+fn is_small_integer(data_type: ColumnType) -> bool {
+    matches!(
+        data_type,
+        ColumnType::TinyInt | ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt
+    )
+}
+
+/// Returns `NOT expr`, which is `false` for the null rows of `expr`.
+fn not_nullable<C: Commitment>(
+    expr: DynProofExpr<C>,
+    presence: Option<DynProofExpr<C>>,
+) -> Result<DynProofExpr<C>, ConversionError> {
+    match presence {
+        Some(presence) => DynProofExpr::try_new_nullable_not(expr, presence),
+        None => DynProofExpr::try_new_not(expr),
+    }
+}
+
 #[allow(clippy::match_wildcard_for_single_variants)]
 // Private interface
 impl DynProofExprBuilder<'_> {
     fn visit_expr<C: Commitment>(
         &self,
         expr: &Expression,
-    ) -> Result<DynProofExpr<C>, ConversionError> {
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
         match expr {
             Expression::Column(identifier) => self.visit_column(*identifier),
-            Expression::Literal(lit) => self.visit_literal(lit),
+            Expression::Literal(lit) => Ok((self.visit_literal(lit)?, None)),
             Expression::Binary { op, left, right } => self.visit_binary_expr(*op, left, right),
             Expression::Unary { op, expr } => self.visit_unary_expr(*op, expr),
-            Expression::Aggregation { op, expr } => self.visit_aggregate_expr(*op, expr),
+            Expression::InList { expr, list } => self.visit_in_list_expr(expr, list),
+            Expression::Between { expr, low, high } => self.visit_between_expr(expr, low, high),
+            Expression::Case {
+                when_then,
+                else_result,
+            } => self.visit_case_expr(when_then, else_result.as_deref()),
+            Expression::Cast { expr, data_type } => {
+                let (expr, presence) = self.visit_expr(expr)?;
+                Ok((DynProofExpr::try_new_cast(expr, *data_type)?, presence))
+            }
+            Expression::Aggregation { op, expr } => {
+                Ok((self.visit_aggregate_expr(*op, expr)?, None))
+            }
             _ => Err(ConversionError::Unprovable {
                 error: format!("Expression {expr:?} is not supported yet"),
             }),
@@ -71,14 +139,18 @@ impl DynProofExprBuilder<'_> {
     fn visit_column<C: Commitment>(
         &self,
         identifier: Identifier,
-    ) -> Result<DynProofExpr<C>, ConversionError> {
-        Ok(DynProofExpr::Column(ColumnExpr::new(
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
+        let column = DynProofExpr::Column(ColumnExpr::new(
             *self.column_mapping.get(&identifier).ok_or(
                 ConversionError::MissingColumnWithoutTable {
                     identifier: Box::new(identifier),
                 },
             )?,
-        )))
+        ));
+        let presence = presence_column_id(identifier)
+            .and_then(|presence_id| self.column_mapping.get(&presence_id))
+            .map(|presence_ref| DynProofExpr::Column(ColumnExpr::new(*presence_ref)));
+        Ok((column, presence))
     }
 
     #[allow(clippy::unused_self)]
@@ -136,64 +208,283 @@ impl DynProofExprBuilder<'_> {
         &self,
         op: UnaryOperator,
         expr: &Expression,
-    ) -> Result<DynProofExpr<C>, ConversionError> {
-        let expr = self.visit_expr(expr);
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
+        let (expr, presence) = self.visit_expr(expr)?;
         match op {
-            UnaryOperator::Not => DynProofExpr::try_new_not(expr?),
+            UnaryOperator::Not => {
+                let expr = not_nullable(expr, presence.clone())?;
+                Ok((expr, presence))
+            }
+            UnaryOperator::IsNull => {
+                let expr = match presence {
+                    Some(presence) => DynProofExpr::try_new_not(presence)?,
+                    None => DynProofExpr::new_literal(LiteralValue::Boolean(false)),
+                };
+                Ok((expr, None))
+            }
+            UnaryOperator::IsNotNull => Ok((
+                presence.unwrap_or_else(|| DynProofExpr::new_literal(LiteralValue::Boolean(true))),
+                None,
+            )),
         }
     }
 
+    /// Builds `lhs AND rhs` with three-valued logic.
+    ///
+    /// The result is not null if both operands are not null or if either operand is `false`.
+    fn and_nullable<C: Commitment>(
+        (lhs, lhs_presence): NullableDynProofExpr<C>,
+        (rhs, rhs_presence): NullableDynProofExpr<C>,
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
+        let presence = match (lhs_presence, rhs_presence) {
+            (None, None) => None,
+            (None, Some(rhs_presence)) => Some(DynProofExpr::try_new_or(
+                rhs_presence,
+                DynProofExpr::try_new_not(lhs.clone())?,
+            )?),
+            (Some(lhs_presence), None) => Some(DynProofExpr::try_new_or(
+                lhs_presence,
+                DynProofExpr::try_new_not(rhs.clone())?,
+            )?),
+            (Some(lhs_presence), Some(rhs_presence)) => {
+                let both_present =
+                    DynProofExpr::try_new_and(lhs_presence.clone(), rhs_presence.clone())?;
+                let lhs_false = not_nullable(lhs.clone(), Some(lhs_presence))?;
+                let rhs_false = not_nullable(rhs.clone(), Some(rhs_presence))?;
+                Some(DynProofExpr::try_new_or(
+                    DynProofExpr::try_new_or(both_present, lhs_false)?,
+                    rhs_false,
+                )?)
+            }
+        };
+        Ok((DynProofExpr::try_new_and(lhs, rhs)?, presence))
+    }
+
+    /// Builds `lhs OR rhs` with three-valued logic.
+    ///
+    /// The result is not null if both operands are not null or if either operand is `true`.
+    fn or_nullable<C: Commitment>(
+        (lhs, lhs_presence): NullableDynProofExpr<C>,
+        (rhs, rhs_presence): NullableDynProofExpr<C>,
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
+        let presence = match (lhs_presence, rhs_presence) {
+            (None, None) => None,
+            (None, Some(rhs_presence)) => {
+                Some(DynProofExpr::try_new_or(rhs_presence, lhs.clone())?)
+            }
+            (Some(lhs_presence), None) => {
+                Some(DynProofExpr::try_new_or(lhs_presence, rhs.clone())?)
+            }
+            (Some(lhs_presence), Some(rhs_presence)) => {
+                let both_present = DynProofExpr::try_new_and(lhs_presence, rhs_presence)?;
+                Some(DynProofExpr::try_new_or(
+                    DynProofExpr::try_new_or(both_present, lhs.clone())?,
+                    rhs.clone(),
+                )?)
+            }
+        };
+        Ok((DynProofExpr::try_new_or(lhs, rhs)?, presence))
+    }
+
+    /// Visits both operands of a binary expression, reporting the error of the left one first.
+    fn visit_operands<C: Commitment>(
+        &self,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<(NullableDynProofExpr<C>, NullableDynProofExpr<C>), ConversionError> {
+        let left = self.visit_expr(left);
+        let right = self.visit_expr(right);
+        Ok((left?, right?))
+    }
+
     fn visit_binary_expr<C: Commitment>(
         &self,
         op: BinaryOperator,
         left: &Expression,
         right: &Expression,
-    ) -> Result<DynProofExpr<C>, ConversionError> {
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
         match op {
             BinaryOperator::And => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_and(left?, right?)
+                let (left, right) = self.visit_operands(left, right)?;
+                Self::and_nullable(left, right)
             }
             BinaryOperator::Or => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_or(left?, right?)
-            }
-            BinaryOperator::Equal => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_equals(left?, right?)
-            }
-            BinaryOperator::GreaterThanOrEqual => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_inequality(left?, right?, false)
-            }
-            BinaryOperator::LessThanOrEqual => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_inequality(left?, right?, true)
-            }
-            BinaryOperator::Add => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_add(left?, right?)
-            }
-            BinaryOperator::Subtract => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_subtract(left?, right?)
-            }
-            BinaryOperator::Multiply => {
-                let left = self.visit_expr(left);
-                let right = self.visit_expr(right);
-                DynProofExpr::try_new_multiply(left?, right?)
-            }
-            BinaryOperator::Division => Err(ConversionError::Unprovable {
-                error: format!("Binary operator {op:?} is not supported at this location"),
-            }),
+                let (left, right) = self.visit_operands(left, right)?;
+                Self::or_nullable(left, right)
+            }
+            BinaryOperator::Equal
+            | BinaryOperator::GreaterThanOrEqual
+            | BinaryOperator::LessThanOrEqual => {
+                let ((left, left_presence), (right, right_presence)) =
+                    self.visit_operands(left, right)?;
+                let expr = match op {
+                    BinaryOperator::Equal => DynProofExpr::try_new_equals(left, right)?,
+                    BinaryOperator::GreaterThanOrEqual => {
+                        DynProofExpr::try_new_inequality(left, right, false)?
+                    }
+                    _ => DynProofExpr::try_new_inequality(left, right, true)?,
+                };
+                // Comparisons are `false` for null rows
+                match and_presences(left_presence, right_presence)? {
+                    Some(presence) => Ok((
+                        DynProofExpr::try_new_and(expr, presence.clone())?,
+                        Some(presence),
+                    )),
+                    None => Ok((expr, None)),
+                }
+            }
+            BinaryOperator::Like => {
+                let Expression::Literal(Literal::VarChar(pattern)) = right else {
+                    return Err(ConversionError::Unprovable {
+                        error: "LIKE is only supported with a string literal as pattern"
+                            .to_string(),
+                    });
+                };
+                let (expr, presence) = self.visit_expr(left)?;
+                let expr = DynProofExpr::try_new_like(expr, pattern.clone())?;
+                // LIKE is `false` for null rows
+                match presence {
+                    Some(presence) => Ok((
+                        DynProofExpr::try_new_and(expr, presence.clone())?,
+                        Some(presence),
+                    )),
+                    None => Ok((expr, None)),
+                }
+            }
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply => {
+                let ((left, left_presence), (right, right_presence)) =
+                    self.visit_operands(left, right)?;
+                let expr = match op {
+                    BinaryOperator::Add => DynProofExpr::try_new_add(left, right)?,
+                    BinaryOperator::Subtract => DynProofExpr::try_new_subtract(left, right)?,
+                    _ => DynProofExpr::try_new_multiply(left, right)?,
+                };
+                Ok((expr, and_presences(left_presence, right_presence)?))
+            }
+            BinaryOperator::Division | BinaryOperator::Modulo => {
+                let ((left, left_presence), (right, right_presence)) =
+                    self.visit_operands(left, right)?;
+                // Only integer division is provable, other divisions fall back to postprocessing
+                if !is_small_integer(left.data_type()) || !is_small_integer(right.data_type()) {
+                    return Err(ConversionError::Unprovable {
+                        error: format!(
+                            "Binary operator {op:?} is only supported for integers of at most 64 bits"
+                        ),
+                    });
+                }
+                let expr = if op == BinaryOperator::Division {
+                    DynProofExpr::try_new_divide(left, right)?
+                } else {
+                    DynProofExpr::try_new_modulo(left, right)?
+                };
+                Ok((expr, and_presences(left_presence, right_presence)?))
+            }
+        }
+    }
+
+    /// Builds `expr IN (list)`.
+    ///
+    /// Integer expressions and integer literals get a dedicated proof. Otherwise, this is
+    /// `expr = list[0] OR expr = list[1] OR ...`.
+    fn visit_in_list_expr<C: Commitment>(
+        &self,
+        expr: &Expression,
+        list: &[Expression],
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
+        if let Some(values) = list.iter().map(integer_literal).collect::<Option<Vec<_>>>() {
+            let (expr, presence) = self.visit_expr(expr)?;
+            if is_small_integer(expr.data_type()) {
+                let expr = DynProofExpr::try_new_in_list(expr, values)?;
+                // IN is `false` for null rows
+                return match presence {
+                    Some(presence) => Ok((
+                        DynProofExpr::try_new_and(expr, presence.clone())?,
+                        Some(presence),
+                    )),
+                    None => Ok((expr, None)),
+                };
+            }
         }
+        list.iter()
+            .map(|item| self.visit_binary_expr(BinaryOperator::Equal, expr, item))
+            .reduce(|lhs, rhs| Self::or_nullable(lhs?, rhs?))
+            .ok_or_else(|| ConversionError::InvalidExpression {
+                expression: "IN requires a non-empty list".to_string(),
+            })?
+    }
+
+    /// Builds `expr BETWEEN low AND high`.
+    ///
+    /// Integer expressions with integer literals as bounds get a dedicated proof. Otherwise, this
+    /// is `expr >= low AND expr <= high`.
+    fn visit_between_expr<C: Commitment>(
+        &self,
+        expr: &Expression,
+        low: &Expression,
+        high: &Expression,
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
+        if let (Some(low), Some(high)) = (integer_literal(low), integer_literal(high)) {
+            let (expr, presence) = self.visit_expr(expr)?;
+            if is_small_integer(expr.data_type()) && low <= high {
+                let expr = DynProofExpr::try_new_between(expr, low, high)?;
+                // BETWEEN is `false` for null rows
+                return match presence {
+                    Some(presence) => Ok((
+                        DynProofExpr::try_new_and(expr, presence.clone())?,
+                        Some(presence),
+                    )),
+                    None => Ok((expr, None)),
+                };
+            }
+        }
+        Self::and_nullable(
+            self.visit_binary_expr(BinaryOperator::GreaterThanOrEqual, expr, low)?,
+            self.visit_binary_expr(BinaryOperator::LessThanOrEqual, expr, high)?,
+        )
+    }
+
+    /// Builds `CASE WHEN ... END` by nesting a `CaseExpr` for every condition, starting from the
+    /// last one.
+    ///
+    /// The result is null if the branch it takes is null or, without `ELSE`, if no condition holds.
+    fn visit_case_expr<C: Commitment>(
+        &self,
+        when_then: &[(Expression, Expression)],
+        else_result: Option<&Expression>,
+    ) -> Result<NullableDynProofExpr<C>, ConversionError> {
+        let when_then = when_then
+            .iter()
+            .map(|(when, then)| Ok((self.visit_expr(when)?.0, self.visit_expr(then)?)))
+            .collect::<Result<Vec<_>, ConversionError>>()?;
+        let ((_, (last_result, _)), _) =
+            when_then
+                .split_last()
+                .ok_or_else(|| ConversionError::InvalidExpression {
+                    expression: "CASE requires at least one WHEN".to_string(),
+                })?;
+        let (mut result, mut presence) = match else_result {
+            Some(else_result) => self.visit_expr(else_result)?,
+            // Without `ELSE` the rows without a match are null, so any result of the right type
+            // will do
+            None => (
+                last_result.clone(),
+                Some(DynProofExpr::new_literal(LiteralValue::Boolean(false))),
+            ),
+        };
+        let present = || DynProofExpr::new_literal(LiteralValue::Boolean(true));
+        // Conditions are `false` for null rows, so they select the branch that SQL selects
+        for (condition, (then, then_presence)) in when_then.into_iter().rev() {
+            presence = match (then_presence, presence) {
+                (None, None) => None,
+                (then_presence, presence) => Some(DynProofExpr::try_new_case(
+                    condition.clone(),
+                    then_presence.unwrap_or_else(present),
+                    presence.unwrap_or_else(present),
+                )?),
+            };
+            result = DynProofExpr::try_new_case(condition, then, result)?;
+        }
+        Ok((result, presence))
     }
 
     fn visit_aggregate_expr<C: Commitment>(
@@ -206,12 +497,25 @@ impl DynProofExprBuilder<'_> {
                 expression: "nested aggregations are invalid".to_string(),
             });
         }
-        let expr = DynProofExprBuilder::new_agg(self.column_mapping).visit_expr(expr)?;
-        match (op, expr.data_type().is_numeric()) {
-            (AggregationOperator::Count, _) | (AggregationOperator::Sum, true) => {
+        let (expr, presence) =
+            DynProofExprBuilder::new_agg(self.column_mapping).visit_expr(expr)?;
+        match (op, expr.data_type().is_numeric(), presence) {
+            (AggregationOperator::Count, _, None) | (AggregationOperator::Sum, true, None) => {
                 Ok(DynProofExpr::new_aggregate(op, expr))
             }
-            (AggregationOperator::Sum, false) => Err(ConversionError::InvalidExpression {
+            // Null rows are ignored by the sum
+            (AggregationOperator::Sum, true, Some(presence)) => {
+                DynProofExpr::try_new_nullable_aggregate(op, expr, presence)
+            }
+            // `COUNT(expr)` counts the rows where `expr` is not null, i.e. it is the sum of the presence
+            (AggregationOperator::Count, _, Some(presence)) => {
+                DynProofExpr::try_new_nullable_aggregate(
+                    AggregationOperator::Sum,
+                    DynProofExpr::new_literal(LiteralValue::BigInt(1)),
+                    presence,
+                )
+            }
+            (AggregationOperator::Sum, false, _) => Err(ConversionError::InvalidExpression {
                 expression: format!(
                     "Aggregation operator {op:?} doesn't work with non-numeric types"
                 ),
@@ -1,6 +1,10 @@
 use super::DynProofExprBuilder;
 use crate::{
-    base::{commitment::Commitment, database::ColumnRef, map::IndexMap},
+    base::{
+        commitment::Commitment,
+        database::{presence_column_id, ColumnRef},
+        map::IndexMap,
+    },
     sql::proof_exprs::DynProofExpr,
 };
 use alloc::boxed::Box;
@@ -18,6 +22,8 @@ pub struct EnrichedExpr<C: Commitment> {
     pub residue_expression: AliasedResultExpr,
     /// The extracted provable expression plan if it exists.
     pub dyn_proof_expr: Option<DynProofExpr<C>>,
+    /// The presence of the provable expression plan if it is nullable.
+    pub presence_expr: Option<DynProofExpr<C>>,
 }
 
 impl<C: Commitment> EnrichedExpr<C> {
@@ -33,9 +39,15 @@ impl<C: Commitment> EnrichedExpr<C> {
         // TODO: Using new_agg (ironically) disables aggregations in `QueryExpr` for now.
         // Re-enable aggregations when we add `GroupByExec` generalizations.
         let res_dyn_proof_expr =
-            DynProofExprBuilder::new_agg(column_mapping).build(&expression.expr);
+            DynProofExprBuilder::new_agg(column_mapping).build_nullable(&expression.expr);
         match res_dyn_proof_expr {
-            Ok(dyn_proof_expr) => {
+            // The presence of a nullable expression has to be a result column as well
+            Ok((_, Some(_))) if presence_column_id(expression.alias).is_none() => Self {
+                residue_expression: expression,
+                dyn_proof_expr: None,
+                presence_expr: None,
+            },
+            Ok((dyn_proof_expr, presence_expr)) => {
                 let alias = expression.alias;
                 Self {
                     residue_expression: AliasedResultExpr {
@@ -43,11 +55,13 @@ impl<C: Commitment> EnrichedExpr<C> {
                         alias,
                     },
                     dyn_proof_expr: Some(dyn_proof_expr),
+                    presence_expr,
                 }
             }
             Err(_) => Self {
                 residue_expression: expression,
                 dyn_proof_expr: None,
+                presence_expr: None,
             },
         }
     }
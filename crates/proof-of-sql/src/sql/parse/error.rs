@@ -33,6 +33,20 @@ pub enum ConversionError {
         identifier: Box<Identifier>,
     },
 
+    #[snafu(display("Column '{identifier}' is ambiguous"))]
+    /// The column is present in more than one of the tables of the query
+    AmbiguousColumn {
+        /// The ambiguous column identifier
+        identifier: Box<Identifier>,
+    },
+
+    #[snafu(display("Table '{table}' is not referenced in the FROM clause"))]
+    /// A column is qualified by a table that is not in the query
+    InvalidTableReference {
+        /// The table qualifying the column
+        table: Box<Identifier>,
+    },
+
     #[snafu(display("Expected '{expected}' but found '{actual}'"))]
     /// Invalid data type received
     InvalidDataType {
@@ -51,6 +65,17 @@ pub enum ConversionError {
         right_type: String,
     },
 
+    #[snafu(display(
+        "The queries of a union have different result types: ({left_types}) and ({right_types})"
+    ))]
+    /// The queries of a union do not have the same number of result columns of the same types
+    UnionTypeMismatch {
+        /// The result types of the queries before
+        left_types: String,
+        /// The result types of the query that does not match them
+        right_types: String,
+    },
+
     #[snafu(display("Columns have different lengths: {len_a} != {len_b}"))]
     /// Two columns do not have the same length
     DifferentColumnLength {
@@ -67,6 +92,15 @@ pub enum ConversionError {
         alias: String,
     },
 
+    #[snafu(display(
+        "Multiple common table expressions with the same name '{alias}' have been found."
+    ))]
+    /// Duplicate name of common table expressions in a WITH clause
+    DuplicateCommonTableExpression {
+        /// The duplicate name
+        alias: String,
+    },
+
     #[snafu(display(
         "A WHERE clause must has boolean type. It is currently of type '{datatype}'."
     ))]
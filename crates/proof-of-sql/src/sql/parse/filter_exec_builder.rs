@@ -2,7 +2,7 @@ use super::{where_expr_builder::WhereExprBuilder, ConversionError, EnrichedExpr}
 use crate::{
     base::{
         commitment::Commitment,
-        database::{ColumnRef, LiteralValue, TableRef},
+        database::{presence_column_id, ColumnRef, LiteralValue, TableRef},
         map::IndexMap,
     },
     sql::{
@@ -49,16 +49,26 @@ impl<C: Commitment> FilterExecBuilder<C> {
     ///
     /// Will panic if:
     /// - `self.column_mapping.get(alias)` returns `None`, which can occur if the alias is not found in the column mapping.
+    /// - The alias of a nullable column has no presence column id, which `EnrichedExpr` rules out.
     pub fn add_result_columns(mut self, columns: &[EnrichedExpr<C>]) -> Self {
         // If a column is provable, add it to the filter result expression list
         // If at least one column is non-provable, add all columns from the column mapping to the filter result expression list
         let mut has_nonprovable_column = false;
         for enriched_expr in columns {
             if let Some(plan) = &enriched_expr.dyn_proof_expr {
+                let alias = enriched_expr.residue_expression.alias;
                 self.filter_result_expr_list.push(AliasedDynProofExpr {
                     expr: plan.clone(),
-                    alias: enriched_expr.residue_expression.alias,
+                    alias,
                 });
+                // The presence of a nullable column is a result column as well
+                if let Some(presence) = &enriched_expr.presence_expr {
+                    self.filter_result_expr_list.push(AliasedDynProofExpr {
+                        expr: presence.clone(),
+                        alias: presence_column_id(alias)
+                            .expect("the presence column id is checked by `EnrichedExpr`"),
+                    });
+                }
             } else {
                 has_nonprovable_column = true;
             }
@@ -67,6 +77,14 @@ impl<C: Commitment> FilterExecBuilder<C> {
         if has_nonprovable_column {
             // Has to keep them sorted to have deterministic order for tests
             for alias in self.column_mapping.keys().sorted() {
+                // Columns that are results already must not be repeated
+                if self
+                    .filter_result_expr_list
+                    .iter()
+                    .any(|aliased_expr| aliased_expr.alias == *alias)
+                {
+                    continue;
+                }
                 let column_ref = self.column_mapping.get(alias).unwrap();
                 self.filter_result_expr_list.push(AliasedDynProofExpr {
                     expr: DynProofExpr::new_column(*column_ref),
@@ -0,0 +1,217 @@
+use super::{ConversionError, ConversionResult};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{ColumnRef, ColumnType, SchemaAccessor, TableRef},
+        map::IndexSet,
+    },
+    sql::{
+        proof_exprs::{ColumnExpr, TableExpr},
+        proof_plans::JoinExec,
+    },
+};
+use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec::Vec};
+use proof_of_sql_parser::{
+    intermediate_ast::{BinaryOperator, Expression, SelectResultExpr, TableExpression},
+    Identifier, ResourceId,
+};
+
+/// Builder of a `JoinExec` from a query of the form
+/// ```ignore
+///     SELECT <column1>, ..., <columnN>
+///     FROM <left_table> JOIN <right_table> ON <left_key> = <right_key>
+/// ```
+pub struct JoinExecBuilder<'a> {
+    schema_accessor: &'a dyn SchemaAccessor,
+    left_table: TableRef,
+    right_table: TableRef,
+}
+
+// Public interface
+impl<'a> JoinExecBuilder<'a> {
+    pub fn try_new(
+        schema_accessor: &'a dyn SchemaAccessor,
+        left: &TableExpression,
+        right: &TableExpression,
+        default_schema: Identifier,
+    ) -> ConversionResult<Self> {
+        let left_table = named_table_ref(left, default_schema)?;
+        let right_table = named_table_ref(right, default_schema)?;
+        // Columns are qualified by the table name, so the two tables must have different names
+        if left_table.table_id() == right_table.table_id() {
+            return Err(ConversionError::Unprovable {
+                error: "a table can't be joined with a table of the same name".to_owned(),
+            });
+        }
+        Ok(Self {
+            schema_accessor,
+            left_table,
+            right_table,
+        })
+    }
+
+    /// The tables of the join, in the order they are joined
+    pub fn table_refs(&self) -> [TableRef; 2] {
+        [self.left_table, self.right_table]
+    }
+
+    pub fn build<C: Commitment>(
+        &self,
+        on: &Expression,
+        result_exprs: &[SelectResultExpr],
+    ) -> ConversionResult<JoinExec<C>> {
+        let (left_key, right_key) = self.visit_join_condition(on)?;
+        let mut aliased_results = Vec::new();
+        for result_expr in result_exprs {
+            match result_expr {
+                SelectResultExpr::ALL => {
+                    for table_ref in [self.left_table, self.right_table] {
+                        for (column_id, column_type) in
+                            self.schema_accessor.lookup_schema(table_ref)
+                        {
+                            aliased_results.push((
+                                ColumnExpr::new(ColumnRef::new(table_ref, column_id, column_type)),
+                                column_id,
+                            ));
+                        }
+                    }
+                }
+                SelectResultExpr::AliasedResultExpr(aliased_expr) => {
+                    let column_ref = self.visit_column_expr(&aliased_expr.expr)?;
+                    aliased_results.push((ColumnExpr::new(column_ref), aliased_expr.alias));
+                }
+            }
+        }
+        let mut aliases = IndexSet::default();
+        for (_, alias) in &aliased_results {
+            if !aliases.insert(*alias) {
+                return Err(ConversionError::DuplicateResultAlias {
+                    alias: alias.to_string(),
+                });
+            }
+        }
+        Ok(JoinExec::new(
+            TableExpr {
+                table_ref: self.left_table,
+            },
+            TableExpr {
+                table_ref: self.right_table,
+            },
+            ColumnExpr::new(left_key),
+            ColumnExpr::new(right_key),
+            aliased_results,
+        ))
+    }
+}
+
+// Private interface
+impl JoinExecBuilder<'_> {
+    /// Returns the keys of the left and the right table from a condition of the form
+    /// `<left_key> = <right_key>` or `<right_key> = <left_key>`.
+    fn visit_join_condition(&self, on: &Expression) -> ConversionResult<(ColumnRef, ColumnRef)> {
+        let Expression::Binary {
+            op: BinaryOperator::Equal,
+            left,
+            right,
+        } = on
+        else {
+            return Err(ConversionError::Unprovable {
+                error: "the join condition must be an equality of two columns".to_owned(),
+            });
+        };
+        let (left_key, right_key) = match (
+            self.visit_column_expr(left)?,
+            self.visit_column_expr(right)?,
+        ) {
+            (a, b) if a.table_ref() == self.left_table && b.table_ref() == self.right_table => {
+                (a, b)
+            }
+            (a, b) if a.table_ref() == self.right_table && b.table_ref() == self.left_table => {
+                (b, a)
+            }
+            _ => {
+                return Err(ConversionError::Unprovable {
+                    error: "the join condition must compare a column of each table".to_owned(),
+                })
+            }
+        };
+        let (left_type, right_type) = (*left_key.column_type(), *right_key.column_type());
+        if left_type != right_type {
+            return Err(ConversionError::DataTypeMismatch {
+                left_type: left_type.to_string(),
+                right_type: right_type.to_string(),
+            });
+        }
+        if !(left_type.is_integer() || matches!(left_type, ColumnType::TimestampTZ(_, _))) {
+            return Err(ConversionError::Unprovable {
+                error: "join keys must be integers or timestamps".to_owned(),
+            });
+        }
+        Ok((left_key, right_key))
+    }
+
+    /// Resolves a possibly qualified column against the two tables of the join
+    fn visit_column_expr(&self, expr: &Expression) -> ConversionResult<ColumnRef> {
+        match expr {
+            Expression::Column(column_id) => {
+                let mut matches =
+                    [self.left_table, self.right_table]
+                        .into_iter()
+                        .filter_map(|table_ref| {
+                            self.schema_accessor
+                                .lookup_column(table_ref, *column_id)
+                                .map(|column_type| {
+                                    ColumnRef::new(table_ref, *column_id, column_type)
+                                })
+                        });
+                match (matches.next(), matches.next()) {
+                    (Some(column_ref), None) => Ok(column_ref),
+                    (Some(_), Some(_)) => Err(ConversionError::AmbiguousColumn {
+                        identifier: Box::new(*column_id),
+                    }),
+                    (None, _) => Err(ConversionError::MissingColumnWithoutTable {
+                        identifier: Box::new(*column_id),
+                    }),
+                }
+            }
+            Expression::QualifiedColumn { table, column } => {
+                let table_ref = [self.left_table, self.right_table]
+                    .into_iter()
+                    .find(|table_ref| table_ref.table_id() == *table)
+                    .ok_or_else(|| ConversionError::InvalidTableReference {
+                        table: Box::new(*table),
+                    })?;
+                let column_type = self
+                    .schema_accessor
+                    .lookup_column(table_ref, *column)
+                    .ok_or_else(|| ConversionError::MissingColumn {
+                        identifier: Box::new(*column),
+                        resource_id: Box::new(table_ref.resource_id()),
+                    })?;
+                Ok(ColumnRef::new(table_ref, *column, column_type))
+            }
+            _ => Err(ConversionError::Unprovable {
+                error: "only columns are supported in the join condition and the results of a join"
+                    .to_owned(),
+            }),
+        }
+    }
+}
+
+fn named_table_ref(
+    table_expr: &TableExpression,
+    default_schema: Identifier,
+) -> ConversionResult<TableRef> {
+    match table_expr {
+        TableExpression::Named { table, schema } => Ok(TableRef::new(ResourceId::new(
+            schema.unwrap_or(default_schema),
+            *table,
+        ))),
+        TableExpression::Join { .. } => Err(ConversionError::Unprovable {
+            error: "joins of more than two tables are not supported".to_owned(),
+        }),
+        TableExpression::Subquery { .. } => Err(ConversionError::Unprovable {
+            error: "joins of subqueries are not supported".to_owned(),
+        }),
+    }
+}
@@ -16,6 +16,9 @@ pub use query_expr::QueryExpr;
 mod filter_exec_builder;
 pub(crate) use filter_exec_builder::FilterExecBuilder;
 
+mod join_exec_builder;
+pub(crate) use join_exec_builder::JoinExecBuilder;
+
 /// TODO: add docs
 pub(crate) mod query_context;
 pub(crate) use query_context::QueryContext;
@@ -1,13 +1,14 @@
 use crate::{
     base::{
         commitment::Commitment,
-        database::{ColumnRef, LiteralValue, TableRef},
+        database::{presence_column_id, ColumnRef, LiteralValue, TableRef},
         map::{IndexMap, IndexSet},
     },
     sql::{
         parse::{ConversionError, ConversionResult, DynProofExprBuilder, WhereExprBuilder},
-        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
-        proof_plans::GroupByExec,
+        postprocessing::{get_free_identifiers_from_expr, GroupByPostprocessing},
+        proof_exprs::{AliasedDynProofExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_plans::{is_sortable_column_type, GroupByExec},
     },
 };
 use alloc::{borrow::ToOwned, boxed::Box, string::ToString, vec::Vec};
@@ -16,18 +17,23 @@ use proof_of_sql_parser::{
     Identifier,
 };
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct QueryContext {
     in_agg_scope: bool,
     agg_counter: usize,
+    in_window_scope: bool,
+    window_counter: usize,
     slice_expr: Option<Slice>,
     col_ref_counter: usize,
     table: Option<TableRef>,
     in_result_scope: bool,
     has_visited_group_by: bool,
     order_by_exprs: Vec<OrderBy>,
-    group_by_exprs: Vec<Identifier>,
+    group_by_exprs: Vec<Expression>,
     where_expr: Option<Box<Expression>>,
+    having_expr: Option<Box<Expression>>,
+    distinct: bool,
+    has_like: bool,
     result_column_set: IndexSet<Identifier>,
     res_aliased_exprs: Vec<AliasedResultExpr>,
     column_mapping: IndexMap<Identifier, ColumnRef>,
@@ -56,6 +62,35 @@ impl QueryContext {
         &self.where_expr
     }
 
+    pub fn set_having_expr(&mut self, having_expr: Option<Box<Expression>>) {
+        self.having_expr = having_expr;
+    }
+
+    pub fn get_having_expr(&self) -> &Option<Box<Expression>> {
+        &self.having_expr
+    }
+
+    /// Sets whether duplicate result rows are removed.
+    ///
+    /// A query without aggregations is then grouped by its result expressions,
+    /// so that only queries with aggregations have to remove duplicates afterwards.
+    pub fn set_distinct(&mut self, distinct: bool) {
+        if distinct && !self.has_agg() {
+            let group_by_exprs = self
+                .res_aliased_exprs
+                .iter()
+                .map(|aliased_expr| (*aliased_expr.expr).clone())
+                .collect();
+            self.set_group_by_exprs(group_by_exprs);
+        } else {
+            self.distinct = distinct;
+        }
+    }
+
+    pub fn is_distinct(&self) -> bool {
+        self.distinct
+    }
+
     pub fn set_slice_expr(&mut self, slice_expr: Option<Slice>) {
         self.slice_expr = slice_expr;
     }
@@ -96,6 +131,47 @@ impl QueryContext {
         self.in_agg_scope
     }
 
+    /// Enters or exits a window function, which can't be nested in aggregations or other window functions.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn set_in_window_scope(&mut self, in_window_scope: bool) -> ConversionResult<()> {
+        if !in_window_scope {
+            assert!(
+                self.in_window_scope,
+                "window context needs to be set before exiting"
+            );
+            self.in_window_scope = false;
+            return Ok(());
+        }
+
+        if self.in_window_scope || self.in_agg_scope {
+            return Err(ConversionError::InvalidExpression {
+                expression: "window functions can't be nested in aggregations or window functions"
+                    .to_string(),
+            });
+        }
+
+        self.window_counter += 1;
+        self.in_window_scope = true;
+
+        Ok(())
+    }
+
+    /// Marks that the query contains a `LIKE` expression
+    pub(crate) fn set_has_like(&mut self) {
+        self.has_like = true;
+    }
+
+    /// Whether the query contains a `LIKE` expression, whose proof reveals the distinct values
+    /// of the matched expression
+    pub(crate) fn has_like(&self) -> bool {
+        self.has_like
+    }
+
+    /// Whether the result expressions contain window functions
+    pub(crate) fn has_window(&self) -> bool {
+        self.window_counter > 0
+    }
+
     /// TODO: add docs
     pub(crate) fn has_agg(&self) -> bool {
         self.agg_counter > 0 || !self.group_by_exprs.is_empty()
@@ -107,6 +183,13 @@ impl QueryContext {
         self.column_mapping.insert(column, column_ref);
     }
 
+    /// Adds the presence column of a nullable column to the column mapping.
+    ///
+    /// Unlike [`Self::push_column_ref`], this doesn't count as a reference to a result column.
+    pub fn push_presence_column_ref(&mut self, presence: Identifier, presence_ref: ColumnRef) {
+        self.column_mapping.insert(presence, presence_ref);
+    }
+
     fn push_result_column_ref(&mut self, column: Identifier) {
         if self.is_in_result_scope() {
             self.result_column_set.insert(column);
@@ -125,13 +208,14 @@ impl QueryContext {
         Ok(())
     }
 
-    pub fn set_group_by_exprs(&mut self, exprs: Vec<Identifier>) {
+    pub fn set_group_by_exprs(&mut self, exprs: Vec<Expression>) {
         self.group_by_exprs = exprs;
 
-        // Add the group by columns to the result column set
+        // Add the columns of the group by expressions to the result column set
         // to ensure their integrity in the filter expression.
-        for group_column in &self.group_by_exprs {
-            self.result_column_set.insert(*group_column);
+        for group_by_expr in &self.group_by_exprs {
+            self.result_column_set
+                .extend(get_free_identifiers_from_expr(group_by_expr));
         }
 
         self.has_visited_group_by = true;
@@ -150,7 +234,7 @@ impl QueryContext {
         // Result column references outside aggregation must appear in the group by
         self.group_by_exprs
             .iter()
-            .find(|group_column| *group_column == column)
+            .find(|group_by_expr| get_free_identifiers_from_expr(group_by_expr).contains(column))
             .map(|_| true)
             .ok_or(ConversionError::InvalidGroupByColumnRef {
                 column: column.to_string(),
@@ -179,6 +263,14 @@ impl QueryContext {
             }
         }
 
+        // Window functions are evaluated on the rows of the table, not on groups
+        if self.has_window() && self.has_agg() {
+            return Err(ConversionError::InvalidExpression {
+                expression: "window functions can't be used with aggregations or GROUP BY"
+                    .to_string(),
+            });
+        }
+
         // We cannot have column references outside aggregations when there is no group by expressions
         if self.group_by_exprs.is_empty()
             && self.agg_counter > 0
@@ -210,7 +302,7 @@ impl QueryContext {
         &self.slice_expr
     }
 
-    pub fn get_group_by_exprs(&self) -> &[Identifier] {
+    pub fn get_group_by_exprs(&self) -> &[Expression] {
         &self.group_by_exprs
     }
 
@@ -221,6 +313,76 @@ impl QueryContext {
     pub fn get_column_mapping(&self) -> IndexMap<Identifier, ColumnRef> {
         self.column_mapping.clone()
     }
+
+    /// Splits a query whose results are its group by expressions followed by a single `COUNT(DISTINCT expr)`
+    /// into the query that is grouped by `expr` as well, and the postprocessing that counts the groups of `expr`.
+    ///
+    /// This allows the distinct values of `expr` to be proven with a `GroupByExec`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the identifier of the hidden column of the distinct values cannot be parsed.
+    pub fn try_split_count_distinct(
+        &self,
+    ) -> ConversionResult<Option<(QueryContext, GroupByPostprocessing)>> {
+        let Some((count_distinct, res_group_by_exprs)) = self.res_aliased_exprs.split_last() else {
+            return Ok(None);
+        };
+        let Expression::Aggregation {
+            op: AggregationOperator::CountDistinct,
+            expr,
+        } = &*count_distinct.expr
+        else {
+            return Ok(None);
+        };
+        if res_group_by_exprs.len() != self.group_by_exprs.len()
+            || res_group_by_exprs
+                .iter()
+                .zip(&self.group_by_exprs)
+                .any(|(res, group_by_expr)| *res.expr != *group_by_expr)
+        {
+            return Ok(None);
+        }
+        let distinct_alias: Identifier = "__count_distinct__".parse().expect("valid identifier");
+        let context = QueryContext {
+            group_by_exprs: self
+                .group_by_exprs
+                .iter()
+                .cloned()
+                .chain([(**expr).clone()])
+                .collect(),
+            res_aliased_exprs: res_group_by_exprs
+                .iter()
+                .cloned()
+                .chain([AliasedResultExpr {
+                    expr: expr.clone(),
+                    alias: distinct_alias,
+                }])
+                .collect(),
+            ..self.clone()
+        };
+        let count_postprocessing = GroupByPostprocessing::try_new(
+            res_group_by_exprs
+                .iter()
+                .map(|res| Expression::Column(res.alias))
+                .collect(),
+            res_group_by_exprs
+                .iter()
+                .map(|res| AliasedResultExpr {
+                    expr: Box::new(Expression::Column(res.alias)),
+                    alias: res.alias,
+                })
+                .chain([AliasedResultExpr {
+                    expr: Box::new(Expression::Aggregation {
+                        op: AggregationOperator::Count,
+                        expr: Box::new(Expression::Column(distinct_alias)),
+                    }),
+                    alias: count_distinct.alias,
+                }])
+                .collect(),
+        )?;
+        Ok(Some((context, count_postprocessing)))
+    }
 }
 
 /// Converts a `QueryContext` into a `Option<GroupByExec>`.
@@ -239,86 +401,148 @@ impl<C: Commitment> TryFrom<&QueryContext> for Option<GroupByExec<C>> {
                 expression: "QueryContext has no table_ref".to_owned(),
             },
         )?;
-        let resource_id = table.table_ref.resource_id();
-        let group_by_exprs = value
-            .group_by_exprs
-            .iter()
-            .map(|expr| -> Result<ColumnExpr<C>, ConversionError> {
-                value
-                    .column_mapping
-                    .get(expr)
-                    .ok_or(ConversionError::MissingColumn {
-                        identifier: Box::new(*expr),
-                        resource_id: Box::new(resource_id),
-                    })
-                    .map(|column_ref| ColumnExpr::<C>::new(*column_ref))
-            })
-            .collect::<Result<Vec<ColumnExpr<C>>, ConversionError>>()?;
-        // For a query to be provable the result columns must be of one of three kinds below:
-        // 1. Group by columns (it is mandatory to have all of them in the correct order)
-        // 2. Sum(expr) expressions (it is optional to have any)
-        // 3. count(*) with an alias (it is mandatory to have one and only one)
-        let num_group_by_columns = group_by_exprs.len();
+        // For a query to be provable the result columns must be of one of four kinds below:
+        // 1. Group by expressions (it is mandatory to have all of them in the correct order)
+        // 2. Sum(expr) expressions and count(expr) expressions of nullable expressions,
+        //    which are sums of the presence of expr (it is optional to have any)
+        // 3. Max(expr) and Min(expr) expressions of non-nullable sortable expressions
+        //    (it is optional to have any)
+        // 4. count(*) with an alias (it is optional to have one as the last column)
+        let num_group_by_columns = value.group_by_exprs.len();
         let num_result_columns = value.res_aliased_exprs.len();
-        if num_result_columns < num_group_by_columns + 1 {
+        if num_result_columns < num_group_by_columns {
             return Ok(None);
         }
-        let res_group_by_columns = &value.res_aliased_exprs[..num_group_by_columns].to_vec();
-        let sum_expr_columns =
-            &value.res_aliased_exprs[num_group_by_columns..num_result_columns - 1].to_vec();
-        // Check group by columns
-        let group_by_compliance = value
+        let res_group_by_columns = &value.res_aliased_exprs[..num_group_by_columns];
+        // Check group by expressions
+        // Null rows of a nullable group by column form their own group. The values of computed
+        // expressions on null rows are arbitrary though, so those are left to postprocessing.
+        let group_by_exprs = value
             .group_by_exprs
             .iter()
-            .zip(res_group_by_columns.iter())
-            .all(|(ident, res)| {
-                if let Expression::Column(res_ident) = *res.expr {
-                    res_ident == *ident
-                } else {
-                    false
+            .zip(res_group_by_columns)
+            .map(|(group_by_expr, res)| {
+                if *res.expr != *group_by_expr {
+                    return None;
                 }
-            });
+                let (expr, presence) = DynProofExprBuilder::new(&value.column_mapping)
+                    .build_nullable::<C>(group_by_expr)
+                    .ok()?;
+                let presence = match (group_by_expr, presence) {
+                    (_, None) => None,
+                    (Expression::Column(_), Some(presence)) => Some(AliasedDynProofExpr {
+                        alias: presence_column_id(res.alias)?,
+                        expr: presence,
+                    }),
+                    _ => return None,
+                };
+                Some((
+                    AliasedDynProofExpr {
+                        alias: res.alias,
+                        expr,
+                    },
+                    presence,
+                ))
+            })
+            .collect::<Option<Vec<_>>>();
+
+        // Check count(*)
+        // Counts of nullable expressions skip the null rows, so they are proven as sums of the presence instead
+        let is_nullable_count = |expr: &Expression| match expr {
+            Expression::Aggregation {
+                op: AggregationOperator::Count,
+                expr,
+            } => matches!(
+                DynProofExprBuilder::new(&value.column_mapping).build_nullable::<C>(expr),
+                Ok((_, Some(_)))
+            ),
+            _ => false,
+        };
+        let count_alias = value.res_aliased_exprs[num_group_by_columns..]
+            .last()
+            .filter(|count_column| {
+                matches!(
+                    *count_column.expr,
+                    Expression::Aggregation {
+                        op: AggregationOperator::Count,
+                        ..
+                    }
+                ) && !is_nullable_count(&count_column.expr)
+            })
+            .map(|count_column| count_column.alias);
+        let aggregate_columns = &value.res_aliased_exprs
+            [num_group_by_columns..num_result_columns - usize::from(count_alias.is_some())];
+        let num_sum_columns = aggregate_columns
+            .iter()
+            .take_while(|res| {
+                matches!(
+                    *res.expr,
+                    Expression::Aggregation {
+                        op: AggregationOperator::Sum,
+                        ..
+                    }
+                ) || is_nullable_count(&res.expr)
+            })
+            .count();
+        let (sum_expr_columns, min_max_expr_columns) = aggregate_columns.split_at(num_sum_columns);
 
         // Check sums
         let sum_expr = sum_expr_columns
             .iter()
             .map(|res| {
-                if let Expression::Aggregation {
-                    op: AggregationOperator::Sum,
-                    ..
-                } = (*res.expr).clone()
-                {
-                    let res_dyn_proof_expr =
-                        DynProofExprBuilder::new(&value.column_mapping).build(&res.expr);
-                    res_dyn_proof_expr
-                        .ok()
-                        .map(|dyn_proof_expr| AliasedDynProofExpr {
-                            alias: res.alias,
-                            expr: dyn_proof_expr,
-                        })
-                } else {
-                    None
-                }
+                let res_dyn_proof_expr =
+                    DynProofExprBuilder::new(&value.column_mapping).build(&res.expr);
+                res_dyn_proof_expr
+                    .ok()
+                    .map(|dyn_proof_expr| AliasedDynProofExpr {
+                        alias: res.alias,
+                        expr: dyn_proof_expr,
+                    })
             })
             .collect::<Option<Vec<AliasedDynProofExpr<C>>>>();
 
-        // Check count(*)
-        let count_column = &value.res_aliased_exprs[num_result_columns - 1];
-        let count_column_compliant = matches!(
-            *count_column.expr,
-            Expression::Aggregation {
-                op: AggregationOperator::Count,
-                ..
-            }
-        );
+        // Check maxes and mins
+        let min_max_expr = min_max_expr_columns
+            .iter()
+            .map(|res| match &*res.expr {
+                Expression::Aggregation {
+                    op: op @ (AggregationOperator::Max | AggregationOperator::Min),
+                    expr,
+                } => match DynProofExprBuilder::new_agg(&value.column_mapping)
+                    .build_nullable::<C>(expr)
+                {
+                    Ok((dyn_proof_expr, None))
+                        if is_sortable_column_type(&dyn_proof_expr.data_type()) =>
+                    {
+                        Some((
+                            *op,
+                            AliasedDynProofExpr {
+                                alias: res.alias,
+                                expr: dyn_proof_expr,
+                            },
+                        ))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
 
-        if !group_by_compliance || sum_expr.is_none() || !count_column_compliant {
+        let (Some(group_by_exprs), Some(sum_expr), Some(min_max_expr)) =
+            (group_by_exprs, sum_expr, min_max_expr)
+        else {
             return Ok(None);
-        }
+        };
+        let (group_by_exprs, presence_group_by_exprs): (Vec<_>, Vec<_>) =
+            group_by_exprs.into_iter().unzip();
         Ok(Some(GroupByExec::new(
-            group_by_exprs,
-            sum_expr.expect("the none case was just checked"),
-            count_column.alias,
+            group_by_exprs
+                .into_iter()
+                .chain(presence_group_by_exprs.into_iter().flatten())
+                .collect(),
+            sum_expr,
+            min_max_expr,
+            count_alias,
             table,
             where_clause,
         )))
@@ -1,16 +1,22 @@
 use super::{ConversionError, ConversionResult, QueryContext};
-use crate::base::{
-    database::{
-        try_add_subtract_column_types, try_multiply_column_types, ColumnRef, ColumnType,
-        SchemaAccessor, TableRef,
+use crate::{
+    base::{
+        database::{
+            column_id_of_presence_column, presence_column_id, try_add_subtract_column_types,
+            try_case_column_types, try_cast_column_type, try_modulo_column_types,
+            try_multiply_column_types, ColumnField, ColumnRef, ColumnType, SchemaAccessor,
+            TableRef,
+        },
+        map::IndexMap,
+        math::decimal::Precision,
     },
-    math::decimal::Precision,
+    sql::postprocessing::contains_nested_aggregation,
 };
 use alloc::{boxed::Box, string::ToString, vec::Vec};
 use proof_of_sql_parser::{
     intermediate_ast::{
         AggregationOperator, AliasedResultExpr, BinaryOperator, Expression, Literal, OrderBy,
-        SelectResultExpr, Slice, TableExpression, UnaryOperator,
+        SelectResultExpr, Slice, TableExpression, UnaryOperator, WindowFunction,
     },
     Identifier, ResourceId,
 };
@@ -18,6 +24,12 @@ use proof_of_sql_parser::{
 pub struct QueryContextBuilder<'a> {
     context: QueryContext,
     schema_accessor: &'a dyn SchemaAccessor,
+    /// The columns of the table if it is a derived table, i.e. the result of a subquery
+    derived_schema: Option<Vec<(Identifier, ColumnType)>>,
+    /// The result fields of the common table expressions that can be referenced as tables
+    common_table_exprs: IndexMap<TableRef, Vec<ColumnField>>,
+    /// The hidden columns of the table that hold the values of the subqueries in the WHERE clause
+    subquery_columns: Vec<(Identifier, ColumnType)>,
 }
 
 // Public interface
@@ -26,10 +38,30 @@ impl<'a> QueryContextBuilder<'a> {
         Self {
             context: QueryContext::default(),
             schema_accessor,
+            derived_schema: None,
+            common_table_exprs: IndexMap::default(),
+            subquery_columns: Vec::new(),
         }
     }
 
-    #[allow(clippy::vec_box, clippy::missing_panics_doc)]
+    /// Visits the common table expressions of a WITH clause, given by the result fields of each of them.
+    ///
+    /// This has to be done before visiting the table expression,
+    /// since a common table expression referenced by it is visited as a derived table.
+    pub fn visit_common_table_exprs(
+        mut self,
+        common_table_exprs: &IndexMap<TableRef, Vec<ColumnField>>,
+    ) -> Self {
+        self.common_table_exprs.clone_from(common_table_exprs);
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the table expression is a join, since joins are built by the `JoinExecBuilder`,
+    /// or a subquery, since its schema is only known once the subquery is built.
+    /// Subqueries are visited with [`Self::visit_derived_table`] instead.
+    #[allow(clippy::vec_box)]
     pub fn visit_table_expr(
         mut self,
         table_expr: &[Box<TableExpression>],
@@ -38,20 +70,105 @@ impl<'a> QueryContextBuilder<'a> {
         assert_eq!(table_expr.len(), 1);
         match *table_expr[0] {
             TableExpression::Named { table, schema } => {
-                self.context.set_table_ref(TableRef::new(ResourceId::new(
-                    schema.unwrap_or(default_schema),
-                    table,
-                )));
+                let table_ref =
+                    TableRef::new(ResourceId::new(schema.unwrap_or(default_schema), table));
+                if let Some(result_fields) = self.common_table_exprs.get(&table_ref).cloned() {
+                    return self.visit_derived_table(table_ref, &result_fields);
+                }
+                self.context.set_table_ref(table_ref);
+            }
+            TableExpression::Join { .. } => panic!("Joins are not built from a query context"),
+            TableExpression::Subquery { .. } => {
+                panic!("Subqueries are visited as derived tables")
             }
         }
         self
     }
 
+    /// Visits a derived table, i.e. a subquery in the FROM clause, whose columns are the result
+    /// columns of the subquery.
+    pub fn visit_derived_table(
+        mut self,
+        table_ref: TableRef,
+        result_fields: &[ColumnField],
+    ) -> Self {
+        self.context.set_table_ref(table_ref);
+        self.derived_schema = Some(
+            result_fields
+                .iter()
+                .map(|field| (field.name(), field.data_type()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Visits the subqueries of the WHERE clause, given by the hidden column that replaces each of
+    /// them, the key of an IN subquery or `None` for a scalar subquery, and the type of its result.
+    ///
+    /// An IN subquery is replaced by a boolean column, which is true for the rows whose key is in
+    /// its result. The key has to be a non-nullable integer or timestamp column of the table.
+    /// A scalar subquery is replaced by a nullable column, which is its value.
+    ///
+    /// This has to be done after visiting the table expression and before visiting the WHERE expression.
+    pub fn visit_where_subqueries(
+        mut self,
+        subqueries: &mut [(Identifier, Option<Expression>, ColumnType)],
+    ) -> ConversionResult<Self> {
+        let table_ref = *self.context.get_table_ref();
+        for (alias, key, subquery_type) in subqueries {
+            match key {
+                Some(key) => {
+                    self.visit_qualified_columns(key)?;
+                    let Expression::Column(key_id) = *key else {
+                        return Err(ConversionError::Unprovable {
+                            error: "the key of an IN subquery must be a column".to_string(),
+                        });
+                    };
+                    let key_type = self.visit_column_identifier(key_id)?;
+                    let is_nullable = presence_column_id(key_id)
+                        .and_then(|presence_id| self.lookup_column(table_ref, presence_id))
+                        .is_some();
+                    if is_nullable {
+                        return Err(ConversionError::Unprovable {
+                            error: "the key of an IN subquery must not be nullable".to_string(),
+                        });
+                    }
+                    check_dtypes(key_type, *subquery_type, BinaryOperator::Equal)?;
+                    if key_type != *subquery_type
+                        || !(key_type.is_integer()
+                            || matches!(key_type, ColumnType::TimestampTZ(..)))
+                    {
+                        return Err(ConversionError::Unprovable {
+                            error: "IN subqueries are only supported on keys of the same integer or timestamp type".to_string(),
+                        });
+                    }
+                    self.subquery_columns.push((*alias, ColumnType::Boolean));
+                }
+                None => {
+                    if *subquery_type == ColumnType::VarChar {
+                        return Err(ConversionError::Unprovable {
+                            error: "scalar subqueries of strings are not supported".to_string(),
+                        });
+                    }
+                    let presence_id =
+                        presence_column_id(*alias).ok_or_else(|| ConversionError::Unprovable {
+                            error: "the alias of a scalar subquery is too long".to_string(),
+                        })?;
+                    self.subquery_columns.push((*alias, *subquery_type));
+                    self.subquery_columns
+                        .push((presence_id, ColumnType::Boolean));
+                }
+            }
+        }
+        Ok(self)
+    }
+
     pub fn visit_where_expr(
         mut self,
         mut where_expr: Option<Box<Expression>>,
     ) -> ConversionResult<Self> {
         if let Some(expr) = where_expr.as_deref_mut() {
+            self.visit_qualified_columns(expr)?;
             self.visit_expr(expr)?;
         }
         self.context.set_where_expr(where_expr);
@@ -74,6 +191,42 @@ impl<'a> QueryContextBuilder<'a> {
         Ok(self)
     }
 
+    /// Visits the HAVING expression, which has to be boolean.
+    ///
+    /// This has to be done after visiting the result expressions, since the HAVING expression can only be used
+    /// on queries with a group by or aggregations in the results or in the HAVING expression itself.
+    pub fn visit_having_expr(
+        mut self,
+        mut having_expr: Option<Box<Expression>>,
+    ) -> ConversionResult<Self> {
+        if let Some(expr) = having_expr.as_deref_mut() {
+            self.visit_qualified_columns(expr)?;
+            let dtype = self.visit_expr(expr)?;
+            if dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: dtype,
+                });
+            }
+            if !self.context.has_agg() {
+                return Err(ConversionError::InvalidExpression {
+                    expression: "HAVING requires a GROUP BY or aggregations".to_string(),
+                });
+            }
+        }
+        self.context.set_having_expr(having_expr);
+        Ok(self)
+    }
+
+    /// Visits the DISTINCT of the query.
+    ///
+    /// This has to be done after visiting the result expressions, since those are grouped by
+    /// if the query has no aggregations.
+    pub fn visit_distinct(mut self, distinct: bool) -> Self {
+        self.context.set_distinct(distinct);
+        self
+    }
+
     pub fn visit_order_by_exprs(mut self, order_by_exprs: Vec<OrderBy>) -> Self {
         self.context.set_order_by_exprs(order_by_exprs);
         self
@@ -86,10 +239,17 @@ impl<'a> QueryContextBuilder<'a> {
 
     pub fn visit_group_by_exprs(
         mut self,
-        group_by_exprs: Vec<Identifier>,
+        mut group_by_exprs: Vec<Expression>,
     ) -> ConversionResult<Self> {
-        for id in &group_by_exprs {
-            self.visit_column_identifier(*id)?;
+        for expr in &mut group_by_exprs {
+            self.visit_qualified_columns(expr)?;
+            // Aggregations can't be grouped by, so any aggregation in a group by expression is treated as nested
+            if contains_nested_aggregation(expr, true) {
+                return Err(ConversionError::InvalidExpression {
+                    expression: "aggregations are not allowed in group by expressions".to_string(),
+                });
+            }
+            self.visit_expr(expr)?;
         }
         self.context.set_group_by_exprs(group_by_exprs);
         Ok(self)
@@ -109,34 +269,144 @@ impl<'a> QueryContextBuilder<'a> {
     )]
     fn lookup_schema(&self) -> Vec<(Identifier, ColumnType)> {
         let table_ref = self.context.get_table_ref();
-        let columns = self.schema_accessor.lookup_schema(*table_ref);
+        let columns = self
+            .derived_schema
+            .clone()
+            .unwrap_or_else(|| self.schema_accessor.lookup_schema(*table_ref));
         assert!(!columns.is_empty(), "At least one column must exist");
         columns
     }
 
+    fn lookup_column(&self, table_ref: TableRef, column_id: Identifier) -> Option<ColumnType> {
+        let subquery_column = self
+            .subquery_columns
+            .iter()
+            .find(|(id, _)| *id == column_id && table_ref == *self.context.get_table_ref());
+        if let Some((_, column_type)) = subquery_column {
+            return Some(*column_type);
+        }
+        match &self.derived_schema {
+            Some(columns) => columns
+                .iter()
+                .find(|(id, _)| *id == column_id)
+                .map(|(_, column_type)| *column_type),
+            None => self.schema_accessor.lookup_column(table_ref, column_id),
+        }
+    }
+
     fn visit_select_all_expr(&mut self) -> ConversionResult<()> {
-        for (column_name, _) in self.lookup_schema() {
+        let schema = self.lookup_schema();
+        for (column_name, _) in &schema {
+            // Presence columns are selected along with the column they belong to
+            let is_presence_column = column_id_of_presence_column(*column_name)
+                .is_some_and(|column_id| schema.iter().any(|(id, _)| *id == column_id));
+            if is_presence_column {
+                continue;
+            }
+            let column_name = *column_name;
             let col_expr = Expression::Column(column_name);
             self.visit_aliased_expr(AliasedResultExpr::new(col_expr, column_name))?;
         }
         Ok(())
     }
 
-    fn visit_aliased_expr(&mut self, aliased_expr: AliasedResultExpr) -> ConversionResult<()> {
+    fn visit_aliased_expr(&mut self, mut aliased_expr: AliasedResultExpr) -> ConversionResult<()> {
+        self.visit_qualified_columns(&mut aliased_expr.expr)?;
         self.visit_expr(&aliased_expr.expr)?;
         self.context.push_aliased_result_expr(aliased_expr)?;
         Ok(())
     }
 
+    /// Replaces the columns qualified by the table of the query with unqualified columns.
+    fn visit_qualified_columns(&self, expr: &mut Expression) -> ConversionResult<()> {
+        match expr {
+            Expression::QualifiedColumn { table, column } => {
+                if *table != self.context.get_table_ref().table_id() {
+                    return Err(ConversionError::InvalidTableReference {
+                        table: Box::new(*table),
+                    });
+                }
+                *expr = Expression::Column(*column);
+            }
+            Expression::Unary { expr, .. }
+            | Expression::Aggregation { expr, .. }
+            | Expression::Cast { expr, .. }
+            | Expression::Window {
+                function: WindowFunction::Sum(expr),
+                ..
+            } => {
+                self.visit_qualified_columns(expr)?;
+            }
+            Expression::Binary { left, right, .. } => {
+                self.visit_qualified_columns(left)?;
+                self.visit_qualified_columns(right)?;
+            }
+            Expression::InList { expr, list } => {
+                self.visit_qualified_columns(expr)?;
+                for item in list {
+                    self.visit_qualified_columns(item)?;
+                }
+            }
+            Expression::Between { expr, low, high } => {
+                self.visit_qualified_columns(expr)?;
+                self.visit_qualified_columns(low)?;
+                self.visit_qualified_columns(high)?;
+            }
+            Expression::Case {
+                when_then,
+                else_result,
+            } => {
+                for (when, then) in when_then {
+                    self.visit_qualified_columns(when)?;
+                    self.visit_qualified_columns(then)?;
+                }
+                if let Some(else_result) = else_result {
+                    self.visit_qualified_columns(else_result)?;
+                }
+            }
+            // The subqueries of a WHERE clause are replaced by columns before they are visited
+            Expression::Column(_)
+            | Expression::Literal(_)
+            | Expression::Wildcard
+            | Expression::InSubquery { .. }
+            | Expression::Subquery(_)
+            | Expression::Window {
+                function: WindowFunction::RowNumber | WindowFunction::Rank,
+                ..
+            } => {}
+        }
+        Ok(())
+    }
+
     /// Visits the expression and returns its data type.
     fn visit_expr(&mut self, expr: &Expression) -> ConversionResult<ColumnType> {
         match expr {
             Expression::Wildcard => Ok(ColumnType::BigInt), // Since COUNT(*) = COUNT(1)
             Expression::Literal(literal) => self.visit_literal(literal),
             Expression::Column(_) => self.visit_column_expr(expr),
+            Expression::QualifiedColumn { column, .. } => self.visit_column_identifier(*column),
             Expression::Unary { op, expr } => self.visit_unary_expr(*op, expr),
             Expression::Binary { op, left, right } => self.visit_binary_expr(*op, left, right),
+            Expression::InList { expr, list } => self.visit_in_list_expr(expr, list),
+            Expression::Between { expr, low, high } => self.visit_between_expr(expr, low, high),
+            Expression::Case {
+                when_then,
+                else_result,
+            } => self.visit_case_expr(when_then, else_result.as_deref()),
+            Expression::Cast { expr, data_type } => {
+                Ok(try_cast_column_type(self.visit_expr(expr)?, *data_type)?)
+            }
             Expression::Aggregation { op, expr } => self.visit_agg_expr(*op, expr),
+            Expression::Window {
+                function,
+                partition_by,
+                order_by,
+            } => self.visit_window_expr(function, partition_by, order_by),
+            Expression::InSubquery { .. } | Expression::Subquery(_) => {
+                Err(ConversionError::InvalidExpression {
+                    expression: "subqueries are only supported in WHERE clauses".to_string(),
+                })
+            }
         }
     }
 
@@ -160,19 +430,83 @@ impl<'a> QueryContextBuilder<'a> {
         let left_dtype = self.visit_expr(left)?;
         let right_dtype = self.visit_expr(right)?;
         check_dtypes(left_dtype, right_dtype, op)?;
+        if op == BinaryOperator::Like {
+            self.context.set_has_like();
+        }
         match op {
             BinaryOperator::And
             | BinaryOperator::Or
             | BinaryOperator::Equal
             | BinaryOperator::GreaterThanOrEqual
-            | BinaryOperator::LessThanOrEqual => Ok(ColumnType::Boolean),
+            | BinaryOperator::LessThanOrEqual
+            | BinaryOperator::Like => Ok(ColumnType::Boolean),
             BinaryOperator::Multiply
             | BinaryOperator::Division
+            | BinaryOperator::Modulo
             | BinaryOperator::Subtract
             | BinaryOperator::Add => Ok(left_dtype),
         }
     }
 
+    fn visit_in_list_expr(
+        &mut self,
+        expr: &Expression,
+        list: &[Expression],
+    ) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        for item in list {
+            let item_dtype = self.visit_expr(item)?;
+            check_dtypes(dtype, item_dtype, BinaryOperator::Equal)?;
+        }
+        Ok(ColumnType::Boolean)
+    }
+
+    fn visit_between_expr(
+        &mut self,
+        expr: &Expression,
+        low: &Expression,
+        high: &Expression,
+    ) -> ConversionResult<ColumnType> {
+        let dtype = self.visit_expr(expr)?;
+        let low_dtype = self.visit_expr(low)?;
+        let high_dtype = self.visit_expr(high)?;
+        check_dtypes(dtype, low_dtype, BinaryOperator::GreaterThanOrEqual)?;
+        check_dtypes(dtype, high_dtype, BinaryOperator::LessThanOrEqual)?;
+        Ok(ColumnType::Boolean)
+    }
+
+    fn visit_case_expr(
+        &mut self,
+        when_then: &[(Expression, Expression)],
+        else_result: Option<&Expression>,
+    ) -> ConversionResult<ColumnType> {
+        let mut dtype = None;
+        for (when, then) in when_then {
+            let when_dtype = self.visit_expr(when)?;
+            if when_dtype != ColumnType::Boolean {
+                return Err(ConversionError::InvalidDataType {
+                    expected: ColumnType::Boolean,
+                    actual: when_dtype,
+                });
+            }
+            let then_dtype = self.visit_expr(then)?;
+            dtype = Some(match dtype {
+                Some(dtype) => try_case_column_types(dtype, then_dtype)?,
+                None => then_dtype,
+            });
+        }
+        let dtype = dtype.ok_or_else(|| ConversionError::InvalidExpression {
+            expression: "CASE requires at least one WHEN".to_string(),
+        })?;
+        match else_result {
+            Some(else_result) => {
+                let else_dtype = self.visit_expr(else_result)?;
+                Ok(try_case_column_types(dtype, else_dtype)?)
+            }
+            None => Ok(dtype),
+        }
+    }
+
     fn visit_unary_expr(
         &mut self,
         op: UnaryOperator,
@@ -189,6 +523,10 @@ impl<'a> QueryContextBuilder<'a> {
                 }
                 Ok(ColumnType::Boolean)
             }
+            UnaryOperator::IsNull | UnaryOperator::IsNotNull => {
+                self.visit_expr(expr)?;
+                Ok(ColumnType::Boolean)
+            }
         }
     }
 
@@ -201,8 +539,12 @@ impl<'a> QueryContextBuilder<'a> {
 
         let expr_dtype = self.visit_expr(expr)?;
 
+        let is_count = matches!(
+            op,
+            AggregationOperator::Count | AggregationOperator::CountDistinct
+        );
         // We only support sum/max/min aggregations on numeric columns.
-        if op != AggregationOperator::Count && expr_dtype == ColumnType::VarChar {
+        if !is_count && expr_dtype == ColumnType::VarChar {
             return Err(ConversionError::non_numeric_expr_in_agg(
                 expr_dtype.to_string(),
                 op.to_string(),
@@ -212,13 +554,61 @@ impl<'a> QueryContextBuilder<'a> {
         self.context.set_in_agg_scope(false)?;
 
         // Count aggregation always results in an integer type
-        if op == AggregationOperator::Count {
+        if is_count {
             Ok(ColumnType::BigInt)
         } else {
             Ok(expr_dtype)
         }
     }
 
+    fn visit_window_expr(
+        &mut self,
+        function: &WindowFunction,
+        partition_by: &[Identifier],
+        order_by: &[OrderBy],
+    ) -> ConversionResult<ColumnType> {
+        if !self.context.is_in_result_scope() {
+            return Err(ConversionError::InvalidExpression {
+                expression: "window functions are only allowed in result expressions".to_string(),
+            });
+        }
+        self.context.set_in_window_scope(true)?;
+
+        let table_ref = *self.context.get_table_ref();
+        for &key in partition_by
+            .iter()
+            .chain(order_by.iter().map(|order_by| &order_by.expr))
+        {
+            self.visit_column_identifier(key)?;
+            // The values of null rows are arbitrary, so they can't be compared
+            let is_nullable = presence_column_id(key)
+                .and_then(|presence_id| self.lookup_column(table_ref, presence_id))
+                .is_some();
+            if is_nullable {
+                return Err(ConversionError::InvalidExpression {
+                    expression: "window functions can't partition or order by nullable columns"
+                        .to_string(),
+                });
+            }
+        }
+        let dtype = match function {
+            WindowFunction::RowNumber | WindowFunction::Rank => ColumnType::BigInt,
+            WindowFunction::Sum(expr) => {
+                let expr_dtype = self.visit_expr(expr)?;
+                if !expr_dtype.is_numeric() {
+                    return Err(ConversionError::non_numeric_expr_in_agg(
+                        expr_dtype.to_string(),
+                        AggregationOperator::Sum.to_string(),
+                    ));
+                }
+                expr_dtype
+            }
+        };
+
+        self.context.set_in_window_scope(false)?;
+        Ok(dtype)
+    }
+
     #[allow(clippy::unused_self)]
     fn visit_literal(&self, literal: &Literal) -> Result<ColumnType, ConversionError> {
         match literal {
@@ -235,18 +625,26 @@ impl<'a> QueryContextBuilder<'a> {
     }
 
     fn visit_column_identifier(&mut self, column_name: Identifier) -> ConversionResult<ColumnType> {
-        let table_ref = self.context.get_table_ref();
-        let column_type = self.schema_accessor.lookup_column(*table_ref, column_name);
+        let table_ref = *self.context.get_table_ref();
+        let column_type = self.lookup_column(table_ref, column_name);
 
         let column_type = column_type.ok_or_else(|| ConversionError::MissingColumn {
             identifier: Box::new(column_name),
             resource_id: Box::new(table_ref.resource_id()),
         })?;
 
-        let column = ColumnRef::new(*table_ref, column_name, column_type);
+        let column = ColumnRef::new(table_ref, column_name, column_type);
 
         self.context.push_column_ref(column_name, column);
 
+        // Nullable columns need their presence column as well
+        if let Some(presence_id) = presence_column_id(column_name) {
+            if let Some(ColumnType::Boolean) = self.lookup_column(table_ref, presence_id) {
+                let presence = ColumnRef::new(table_ref, presence_id, ColumnType::Boolean);
+                self.context.push_presence_column_ref(presence_id, presence);
+            }
+        }
+
         Ok(column_type)
     }
 }
@@ -274,6 +672,12 @@ pub(crate) fn type_check_binary_operation(
                     | (ColumnType::Scalar, _)
             ) || (left_dtype.is_numeric() && right_dtype.is_numeric())
         }
+        BinaryOperator::Like => {
+            matches!(
+                (left_dtype, right_dtype),
+                (ColumnType::VarChar, ColumnType::VarChar)
+            )
+        }
         BinaryOperator::GreaterThanOrEqual | BinaryOperator::LessThanOrEqual => {
             if left_dtype == &ColumnType::VarChar || right_dtype == &ColumnType::VarChar {
                 return false;
@@ -305,6 +709,7 @@ pub(crate) fn type_check_binary_operation(
         }
         BinaryOperator::Multiply => try_multiply_column_types(*left_dtype, *right_dtype).is_ok(),
         BinaryOperator::Division => left_dtype.is_numeric() && right_dtype.is_numeric(),
+        BinaryOperator::Modulo => try_modulo_column_types(*left_dtype, *right_dtype).is_ok(),
     }
 }
 
@@ -1,17 +1,36 @@
-use super::{EnrichedExpr, FilterExecBuilder, QueryContextBuilder};
+use super::{EnrichedExpr, FilterExecBuilder, JoinExecBuilder, QueryContext, QueryContextBuilder};
 use crate::{
-    base::{commitment::Commitment, database::SchemaAccessor},
+    base::{
+        commitment::Commitment,
+        database::{
+            presence_column_id, ColumnField, ColumnRef, ColumnType, SchemaAccessor, TableRef,
+        },
+        map::IndexMap,
+    },
     sql::{
-        parse::ConversionResult,
+        parse::{ConversionError, ConversionResult},
         postprocessing::{
-            GroupByPostprocessing, OrderByPostprocessing, OwnedTablePostprocessing,
-            SelectPostprocessing, SlicePostprocessing,
+            get_expression_over_results, FilterPostprocessing, GroupByPostprocessing,
+            OrderByPostprocessing, OwnedTablePostprocessing, SelectPostprocessing,
+            SlicePostprocessing, WindowPostprocessing,
+        },
+        proof::ProofPlan,
+        proof_exprs::ColumnExpr,
+        proof_plans::{
+            is_sortable_column_type, is_supported_subquery, DerivedTableExec, DynProofPlan,
+            FilterExec, GroupByExec, RunningSumExec, ScalarSubqueryExec, SemiJoinExec, SliceExec,
+            SortExec, UnionExec,
         },
-        proof_plans::{DynProofPlan, GroupByExec},
     },
 };
-use alloc::{fmt, vec, vec::Vec};
-use proof_of_sql_parser::{intermediate_ast::SetExpression, Identifier, SelectStatement};
+use alloc::{borrow::ToOwned, boxed::Box, fmt, format, string::ToString, vec, vec::Vec};
+use proof_of_sql_parser::{
+    intermediate_ast::{
+        AliasedResultExpr, Expression, OrderBy, OrderByDirection, SetExpression, Slice,
+        TableExpression, WindowFunction,
+    },
+    Identifier, ResourceId, SelectStatement,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Serialize, Deserialize)]
@@ -44,46 +63,343 @@ impl<C: Commitment> QueryExpr<C> {
     }
 
     /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`.
+    ///
+    /// Queries with `LIKE` are rejected as unprovable, since proving `LIKE` reveals data outside
+    /// the query result, see [`Self::try_new_revealing_like_dictionaries`].
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the identifier of the hidden column of a HAVING expression cannot be parsed.
     pub fn try_new(
         ast: SelectStatement,
         default_schema: Identifier,
         schema_accessor: &dyn SchemaAccessor,
     ) -> ConversionResult<Self> {
-        let context = match *ast.expr {
+        Self::try_new_with_options(ast, default_schema, schema_accessor, false)
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`, where `LIKE` is provable.
+    ///
+    /// # Security
+    ///
+    /// The dictionary of every `VARCHAR` column referenced by a `LIKE` becomes public. Strings are
+    /// only committed to by their hashes, so the proof of `<expr> LIKE <pattern>` contains every
+    /// distinct value of `expr` in the whole table and not only in the rows of the result. Anyone
+    /// with the proof learns these values, and the size of the proof grows with their number. Only
+    /// use this for columns whose values may be revealed to the verifier.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the identifier of the hidden column of a HAVING expression cannot be parsed.
+    pub fn try_new_revealing_like_dictionaries(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+    ) -> ConversionResult<Self> {
+        Self::try_new_with_options(ast, default_schema, schema_accessor, true)
+    }
+
+    /// Parse an intermediate AST `SelectStatement` into a `QueryExpr`,
+    /// where `LIKE` is only provable if `reveal_like_dictionaries` is set.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the identifier of the hidden column of a HAVING expression cannot be parsed.
+    fn try_new_with_options(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        reveal_like_dictionaries: bool,
+    ) -> ConversionResult<Self> {
+        // Each common table expression is proven once, as a derived table of the queries after it
+        let mut common_table_exprs = IndexMap::default();
+        let mut cte_plans = Vec::new();
+        for cte in &ast.ctes {
+            let table_ref = TableRef::new(ResourceId::new(default_schema, cte.alias));
+            if common_table_exprs.contains_key(&table_ref) {
+                return Err(ConversionError::DuplicateCommonTableExpression {
+                    alias: cte.alias.to_string(),
+                });
+            }
+            let plan = Self::try_new_subquery(
+                &cte.query,
+                default_schema,
+                schema_accessor,
+                &common_table_exprs,
+                reveal_like_dictionaries,
+            )?;
+            common_table_exprs.insert(table_ref, plan.get_column_result_fields());
+            cte_plans.push((plan, table_ref));
+        }
+        let query_expr = Self::try_new_with_ctes(
+            ast,
+            default_schema,
+            schema_accessor,
+            &common_table_exprs,
+            reveal_like_dictionaries,
+        )?;
+        Ok(cte_plans
+            .into_iter()
+            .rev()
+            .fold(query_expr, |query_expr, (plan, table_ref)| {
+                query_expr.over_derived_table(plan, table_ref)
+            }))
+    }
+
+    /// Creates a `QueryExpr` from a `SelectStatement` that can reference the given
+    /// common table expressions, whose WITH clause is already converted.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the identifier of the hidden column of a HAVING expression cannot be parsed.
+    fn try_new_with_ctes(
+        ast: SelectStatement,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        common_table_exprs: &IndexMap<TableRef, Vec<ColumnField>>,
+        reveal_like_dictionaries: bool,
+    ) -> ConversionResult<Self> {
+        let (context, subquery, where_subqueries) = match *ast.expr {
             SetExpression::Query {
+                distinct,
                 result_exprs,
                 from,
-                where_expr,
+                mut where_expr,
                 group_by,
-            } => QueryContextBuilder::new(schema_accessor)
-                .visit_table_expr(&from, default_schema)
-                .visit_group_by_exprs(group_by)?
-                .visit_result_exprs(result_exprs)?
-                .visit_where_expr(where_expr)?
-                .visit_order_by_exprs(ast.order_by)
-                .visit_slice_expr(ast.slice)
-                .build()?,
+                having,
+            } => {
+                if let TableExpression::Join { left, right, on } = from[0].as_ref() {
+                    if where_expr.is_some() || !group_by.is_empty() || having.is_some() {
+                        return Err(ConversionError::Unprovable {
+                            error: "WHERE, GROUP BY and HAVING clauses are not supported in joins"
+                                .to_owned(),
+                        });
+                    }
+                    let join_builder =
+                        JoinExecBuilder::try_new(schema_accessor, left, right, default_schema)?;
+                    if join_builder
+                        .table_refs()
+                        .iter()
+                        .any(|table_ref| common_table_exprs.contains_key(table_ref))
+                    {
+                        return Err(ConversionError::Unprovable {
+                            error: "joins of common table expressions are not supported".to_owned(),
+                        });
+                    }
+                    let join = join_builder.build(on, &result_exprs)?;
+                    return Self::try_sort_and_slice(
+                        DynProofPlan::Join(Box::new(join)),
+                        ast.order_by,
+                        ast.slice,
+                    );
+                }
+                let (builder, subquery) =
+                    if let TableExpression::Subquery { query, alias } = from[0].as_ref() {
+                        let subquery = Self::try_new_subquery(
+                            query,
+                            default_schema,
+                            schema_accessor,
+                            common_table_exprs,
+                            reveal_like_dictionaries,
+                        )?;
+                        let table_ref = TableRef::new(ResourceId::new(default_schema, *alias));
+                        let builder = QueryContextBuilder::new(schema_accessor)
+                            .visit_derived_table(table_ref, &subquery.get_column_result_fields());
+                        (builder, Some((subquery, table_ref)))
+                    } else {
+                        let builder = QueryContextBuilder::new(schema_accessor)
+                            .visit_common_table_exprs(common_table_exprs)
+                            .visit_table_expr(&from, default_schema);
+                        (builder, None)
+                    };
+                // Each subquery of the WHERE clause is proven once and read as a hidden column
+                let mut subqueries = Vec::new();
+                if let Some(expr) = where_expr.as_deref_mut() {
+                    extract_where_subqueries(expr, &mut subqueries);
+                }
+                let mut subquery_columns = Vec::with_capacity(subqueries.len());
+                let mut subquery_plans = Vec::with_capacity(subqueries.len());
+                for (alias, key, query) in subqueries {
+                    let plan = Self::try_new_subquery(
+                        &query,
+                        default_schema,
+                        schema_accessor,
+                        common_table_exprs,
+                        reveal_like_dictionaries,
+                    )?;
+                    let [field] = plan.get_column_result_fields()[..] else {
+                        return Err(ConversionError::InvalidExpression {
+                            expression:
+                                "a subquery in a WHERE clause must have exactly one result column"
+                                    .to_owned(),
+                        });
+                    };
+                    subquery_columns.push((alias, key, field.data_type()));
+                    subquery_plans.push(plan);
+                }
+                let context = builder
+                    .visit_where_subqueries(&mut subquery_columns)?
+                    .visit_group_by_exprs(group_by)?
+                    .visit_result_exprs(result_exprs)?
+                    .visit_where_expr(where_expr)?
+                    .visit_having_expr(having)?
+                    .visit_distinct(distinct)
+                    .visit_order_by_exprs(ast.order_by)
+                    .visit_slice_expr(ast.slice)
+                    .build()?;
+                if context.has_like() && !reveal_like_dictionaries {
+                    return Err(ConversionError::Unprovable {
+                        error: "proving LIKE reveals the distinct values of the matched \
+                            expression, see QueryExpr::try_new_revealing_like_dictionaries"
+                            .to_owned(),
+                    });
+                }
+                let where_subqueries = subquery_columns
+                    .into_iter()
+                    .zip(subquery_plans)
+                    .map(|((alias, key, _), plan)| (alias, key, plan))
+                    .collect::<Vec<_>>();
+                (context, subquery, where_subqueries)
+            }
+            union @ SetExpression::UnionAll { .. } => {
+                return Self::try_new_union(
+                    union,
+                    ast.order_by,
+                    ast.slice,
+                    default_schema,
+                    schema_accessor,
+                    common_table_exprs,
+                    reveal_like_dictionaries,
+                );
+            }
+        };
+        let query_expr = Self::try_new_from_context(&context)?;
+        let column_mapping = context.get_column_mapping();
+        let table_ref = *context.get_table_ref();
+        let query_expr = where_subqueries.into_iter().rev().fold(
+            query_expr,
+            |query_expr, (alias, key, plan)| {
+                let key = match key {
+                    Some(Expression::Column(key_id)) => Some(column_mapping[&key_id]),
+                    _ => None,
+                };
+                query_expr.over_where_subquery(plan, table_ref, alias, key)
+            },
+        );
+        Ok(match subquery {
+            Some((subquery, table_ref)) => query_expr.over_derived_table(subquery, table_ref),
+            None => query_expr,
+        })
+    }
+
+    /// Makes the provable part of the query read `table_ref` as the derived table that is
+    /// the result of `subquery`.
+    fn over_derived_table(self, subquery: DynProofPlan<C>, table_ref: TableRef) -> Self {
+        Self {
+            proof_expr: DynProofPlan::DerivedTable(Box::new(DerivedTableExec::new(
+                Box::new(subquery),
+                table_ref,
+                Box::new(self.proof_expr),
+            ))),
+            postprocessing: self.postprocessing,
+        }
+    }
+
+    /// Makes the provable part of the query read the hidden column `alias` of `table_ref` as
+    /// the result of `subquery` in its WHERE clause.
+    ///
+    /// With a `key`, the subquery is an IN subquery, see [`SemiJoinExec`],
+    /// and otherwise a scalar subquery, see [`ScalarSubqueryExec`].
+    fn over_where_subquery(
+        self,
+        subquery: DynProofPlan<C>,
+        table_ref: TableRef,
+        alias: Identifier,
+        key: Option<ColumnRef>,
+    ) -> Self {
+        let plan = Box::new(self.proof_expr);
+        let proof_expr = match key {
+            Some(key) => DynProofPlan::SemiJoin(Box::new(SemiJoinExec::new(
+                Box::new(subquery),
+                ColumnExpr::new(key),
+                alias,
+                plan,
+            ))),
+            None => DynProofPlan::ScalarSubquery(Box::new(ScalarSubqueryExec::new(
+                Box::new(subquery),
+                table_ref,
+                alias,
+                plan,
+            ))),
         };
+        Self {
+            proof_expr,
+            postprocessing: self.postprocessing,
+        }
+    }
+
+    /// Creates a `QueryExpr` from the context of a query that reads a single table.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the identifier of the hidden column of a HAVING expression cannot be parsed.
+    fn try_new_from_context(context: &QueryContext) -> ConversionResult<Self> {
         let result_aliased_exprs = context.get_aliased_result_exprs()?.to_vec();
         let group_by = context.get_group_by_exprs();
 
-        // Figure out the basic postprocessing steps.
-        let mut postprocessing = vec![];
         let order_bys = context.get_order_by_exprs()?;
-        if !order_bys.is_empty() {
-            postprocessing.push(OwnedTablePostprocessing::new_order_by(
-                OrderByPostprocessing::new(order_bys.clone()),
-            ));
-        }
-        if let Some(slice) = context.get_slice_expr() {
-            postprocessing.push(OwnedTablePostprocessing::new_slice(
-                SlicePostprocessing::new(Some(slice.number_rows), Some(slice.offset_value)),
-            ));
-        }
+        let slice = context.get_slice_expr().clone();
         if context.has_agg() {
-            if let Some(group_by_expr) = Option::<GroupByExec<C>>::try_from(&context)? {
+            // Figure out the basic postprocessing steps.
+            let mut postprocessing = Self::order_by_and_slice_postprocessing(order_bys, slice);
+            // A HAVING expression that only depends on the results filters them after the aggregation.
+            // Otherwise it is computed for each group as a hidden result column, which isn't provable.
+            let having = context.get_having_expr().as_deref();
+            let having_over_results = having
+                .and_then(|having| get_expression_over_results(having, &result_aliased_exprs));
+            let hidden_having = having.filter(|_| having_over_results.is_none());
+            let having_filter = |having| {
+                OwnedTablePostprocessing::new_filter(FilterPostprocessing::new(Box::new(having)))
+            };
+            // A single COUNT(DISTINCT expr) is proven by also grouping by `expr` and then counting its groups.
+            let group_by_exec = if hidden_having.is_some() {
+                None
+            } else if let Some((distinct_context, count_distinct)) =
+                context.try_split_count_distinct()?
+            {
+                Option::<GroupByExec<C>>::try_from(&distinct_context)?
+                    .map(|group_by_exec| (group_by_exec, Some(count_distinct)))
+            } else {
+                Option::<GroupByExec<C>>::try_from(context)?
+                    .map(|group_by_exec| (group_by_exec, None))
+            };
+            if let Some((group_by_expr, count_distinct)) = group_by_exec {
+                let group_by_plan = DynProofPlan::GroupBy(group_by_expr);
+                // The HAVING filter over the groups is proven unless they are counted afterwards
+                let having_plan = having_over_results
+                    .as_ref()
+                    .filter(|_| count_distinct.is_none())
+                    .and_then(|having| {
+                        Self::try_new_having_filter(&group_by_plan, having, context.get_table_ref())
+                    });
+                let proof_expr = if let Some((derived_table_ref, having_plan)) = having_plan {
+                    DynProofPlan::DerivedTable(Box::new(DerivedTableExec::new(
+                        Box::new(group_by_plan),
+                        derived_table_ref,
+                        Box::new(DynProofPlan::Filter(having_plan)),
+                    )))
+                } else {
+                    if let Some(having) = having_over_results {
+                        postprocessing.insert(0, having_filter(having));
+                    }
+                    group_by_plan
+                };
+                if let Some(count_distinct) = count_distinct {
+                    postprocessing
+                        .insert(0, OwnedTablePostprocessing::new_group_by(count_distinct));
+                }
                 Ok(Self {
-                    proof_expr: DynProofPlan::GroupBy(group_by_expr),
+                    proof_expr,
                     postprocessing,
                 })
             } else {
@@ -92,6 +408,7 @@ impl<C: Commitment> QueryExpr<C> {
                     .map(|aliased_expr| EnrichedExpr {
                         residue_expression: aliased_expr.clone(),
                         dyn_proof_expr: None,
+                        presence_expr: None,
                     })
                     .collect::<Vec<_>>();
                 let filter = FilterExecBuilder::new(context.get_column_mapping())
@@ -100,32 +417,70 @@ impl<C: Commitment> QueryExpr<C> {
                     .add_result_columns(&raw_enriched_exprs)
                     .build();
 
-                let group_by_postprocessing =
-                    GroupByPostprocessing::try_new(group_by.to_vec(), result_aliased_exprs)?;
-                postprocessing.insert(
-                    0,
-                    OwnedTablePostprocessing::new_group_by(group_by_postprocessing.clone()),
-                );
-                let remainder_exprs = group_by_postprocessing.remainder_exprs();
-                // Check whether we need to do select postprocessing.
-                // That is, if any of them is not simply a column reference.
-                if remainder_exprs
+                let having_alias: Identifier = "__having__".parse().expect("valid identifier");
+                let group_by_result_exprs = result_aliased_exprs
                     .iter()
-                    .any(|expr| expr.try_as_identifier().is_none())
-                {
-                    postprocessing.insert(
-                        1,
-                        OwnedTablePostprocessing::new_select(SelectPostprocessing::new(
-                            remainder_exprs.to_vec(),
-                        )),
-                    );
+                    .cloned()
+                    .chain(hidden_having.map(|having| AliasedResultExpr {
+                        expr: Box::new(having.clone()),
+                        alias: having_alias,
+                    }))
+                    .collect();
+                let group_by_postprocessing =
+                    GroupByPostprocessing::try_new(group_by.to_vec(), group_by_result_exprs)?;
+                // The group by postprocessing already evaluates the remainder expressions
+                let mut group_by_steps = vec![OwnedTablePostprocessing::new_group_by(
+                    group_by_postprocessing,
+                )];
+                if let Some(having) = having_over_results {
+                    group_by_steps.push(having_filter(having));
+                }
+                if hidden_having.is_some() {
+                    // Filter on the hidden column and then remove it
+                    group_by_steps.push(having_filter(Expression::Column(having_alias)));
+                    group_by_steps.push(OwnedTablePostprocessing::new_select(
+                        SelectPostprocessing::new(
+                            result_aliased_exprs
+                                .iter()
+                                .map(|aliased_expr| AliasedResultExpr {
+                                    expr: Box::new(Expression::Column(aliased_expr.alias)),
+                                    alias: aliased_expr.alias,
+                                })
+                                .collect(),
+                        ),
+                    ));
+                }
+                // Results of a `GroupByExec` are distinct already, since it has every group by expression as a result
+                if context.is_distinct() {
+                    let aliases = result_aliased_exprs
+                        .iter()
+                        .map(|aliased_expr| aliased_expr.alias);
+                    group_by_steps.push(OwnedTablePostprocessing::new_group_by(
+                        GroupByPostprocessing::try_new(
+                            aliases.clone().map(Expression::Column).collect(),
+                            aliases
+                                .map(|alias| AliasedResultExpr {
+                                    expr: Box::new(Expression::Column(alias)),
+                                    alias,
+                                })
+                                .collect(),
+                        )?,
+                    ));
                 }
+                postprocessing.splice(0..0, group_by_steps);
                 Ok(Self {
                     proof_expr: DynProofPlan::Filter(filter),
                     postprocessing,
                 })
             }
         } else {
+            if context.has_window() {
+                if let Some(query_expr) =
+                    Self::try_new_running_sums(context, &result_aliased_exprs, &order_bys, &slice)
+                {
+                    return Ok(query_expr);
+                }
+            }
             // No group by, so we need to do a filter.
             let column_mapping = context.get_column_mapping();
             let enriched_exprs = result_aliased_exprs
@@ -146,18 +501,461 @@ impl<C: Commitment> QueryExpr<C> {
                 .iter()
                 .any(|expr| expr.try_as_identifier().is_none())
             {
-                postprocessing.insert(
-                    0,
-                    OwnedTablePostprocessing::new_select(SelectPostprocessing::new(select_exprs)),
-                );
+                let mut postprocessing = Self::order_by_and_slice_postprocessing(order_bys, slice);
+                // Window functions are evaluated along with the remaining result expressions
+                let select = if context.has_window() {
+                    OwnedTablePostprocessing::new_window(WindowPostprocessing::new(select_exprs))
+                } else {
+                    OwnedTablePostprocessing::new_select(SelectPostprocessing::new(select_exprs))
+                };
+                postprocessing.insert(0, select);
+                Ok(Self {
+                    proof_expr: DynProofPlan::Filter(filter),
+                    postprocessing,
+                })
+            } else {
+                Ok(Self::sort_and_slice(
+                    DynProofPlan::Filter(filter),
+                    order_bys,
+                    slice,
+                ))
             }
-            Ok(Self {
-                proof_expr: DynProofPlan::Filter(filter),
+        }
+    }
+
+    /// Creates the plan that filters the groups of `group_by` by a HAVING expression over its results.
+    ///
+    /// The groups are read as the returned derived table, see [`DerivedTableExec`], so that the
+    /// filter is proven. Returns `None` if the filter has to be applied in postprocessing instead.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the name of the derived table cannot be parsed as a valid identifier.
+    fn try_new_having_filter(
+        group_by: &DynProofPlan<C>,
+        having: &Expression,
+        table_ref: &TableRef,
+    ) -> Option<(TableRef, FilterExec<C>)> {
+        if !is_supported_subquery(group_by) {
+            return None;
+        }
+        let derived_table_ref = TableRef::new(ResourceId::new(
+            table_ref.schema_id(),
+            "__having__".parse().expect("valid identifier"),
+        ));
+        let column_mapping: IndexMap<_, _> = group_by
+            .get_column_result_fields()
+            .iter()
+            .map(|field| {
+                (
+                    field.name(),
+                    ColumnRef::new(derived_table_ref, field.name(), field.data_type()),
+                )
+            })
+            .collect();
+        let result_exprs = column_mapping
+            .keys()
+            .map(|alias| {
+                EnrichedExpr::new(
+                    AliasedResultExpr {
+                        expr: Box::new(Expression::Column(*alias)),
+                        alias: *alias,
+                    },
+                    &column_mapping,
+                )
+            })
+            .collect::<Vec<_>>();
+        let filter = FilterExecBuilder::new(column_mapping.clone())
+            .add_table_expr(derived_table_ref)
+            .add_where_expr(Some(Box::new(having.clone())))
+            .ok()?
+            .add_result_columns(&result_exprs)
+            .build();
+        Some((derived_table_ref, filter))
+    }
+
+    /// Creates a `QueryExpr` that proves the window functions of a query that reads a single table,
+    /// which is possible if they are all sums of columns over the same window and the other results are columns.
+    ///
+    /// The rows of the filter are sorted by the window and then the running sums are proven, see [`RunningSumExec`].
+    /// Returns `None` if the window functions have to be evaluated in postprocessing instead.
+    fn try_new_running_sums(
+        context: &QueryContext,
+        result_aliased_exprs: &[AliasedResultExpr],
+        order_by: &[OrderBy],
+        slice: &Option<Slice>,
+    ) -> Option<Self> {
+        let column_mapping = context.get_column_mapping();
+        let mut window = None;
+        let mut columns = vec![];
+        let mut sums = vec![];
+        for aliased_expr in result_aliased_exprs {
+            match aliased_expr.expr.as_ref() {
+                Expression::Column(id) => columns.push((*id, aliased_expr.alias)),
+                Expression::Window {
+                    function: WindowFunction::Sum(expr),
+                    partition_by,
+                    order_by,
+                } => {
+                    let Expression::Column(id) = expr.as_ref() else {
+                        return None;
+                    };
+                    if *window.get_or_insert((partition_by, order_by)) != (partition_by, order_by) {
+                        return None;
+                    }
+                    sums.push((*id, aliased_expr.alias));
+                }
+                _ => return None,
+            }
+        }
+        let (partition_by, window_order_by) = window?;
+        let num_results = columns.len();
+        // The results have to be in the order of the result of the `RunningSumExec`
+        let is_in_order = result_aliased_exprs[num_results..]
+            .iter()
+            .all(|aliased_expr| matches!(*aliased_expr.expr, Expression::Window { .. }));
+
+        // Columns of the window that aren't results are hidden columns of the filter
+        let mut column_index = |id: Identifier| {
+            columns
+                .iter()
+                .position(|&(column_id, _)| column_id == id)
+                .or_else(|| {
+                    let is_alias = result_aliased_exprs
+                        .iter()
+                        .any(|aliased_expr| aliased_expr.alias == id);
+                    (!is_alias).then(|| {
+                        columns.push((id, id));
+                        columns.len() - 1
+                    })
+                })
+        };
+        let partition_by = partition_by
+            .iter()
+            .map(|&id| column_index(id))
+            .collect::<Option<Vec<_>>>()?;
+        let window_order_by = window_order_by
+            .iter()
+            .map(|by_expr| Some((column_index(by_expr.expr)?, by_expr.direction)))
+            .collect::<Option<Vec<_>>>()?;
+        let sums = sums
+            .into_iter()
+            .map(|(id, alias)| Some((column_index(id)?, alias)))
+            .collect::<Option<Vec<_>>>()?;
+
+        // Nullable columns need their presence columns, and the keys have to be sortable
+        let is_nullable = |id: Identifier| {
+            presence_column_id(id)
+                .is_some_and(|presence_id| column_mapping.contains_key(&presence_id))
+        };
+        if columns.iter().any(|&(id, _)| is_nullable(id))
+            || partition_by
+                .iter()
+                .chain(window_order_by.iter().map(|(index, _)| index))
+                .any(|&index| {
+                    !is_sortable_column_type(column_mapping[&columns[index].0].column_type())
+                })
+        {
+            return None;
+        }
+
+        let enriched_exprs = columns
+            .iter()
+            .map(|&(id, alias)| {
+                EnrichedExpr::new(
+                    AliasedResultExpr {
+                        expr: Box::new(Expression::Column(id)),
+                        alias,
+                    },
+                    &column_mapping,
+                )
+            })
+            .collect::<Vec<_>>();
+        let filter = FilterExecBuilder::new(column_mapping.clone())
+            .add_table_expr(*context.get_table_ref())
+            .add_where_expr(context.get_where_expr().clone())
+            .ok()?
+            .add_result_columns(&enriched_exprs)
+            .build();
+        let sort_keys = partition_by
+            .iter()
+            .map(|&index| (index, OrderByDirection::Asc))
+            .chain(window_order_by.iter().copied())
+            .collect::<Vec<_>>();
+        let input = if sort_keys.is_empty() {
+            DynProofPlan::Filter(filter)
+        } else {
+            DynProofPlan::Sort(Box::new(SortExec::new(
+                Box::new(DynProofPlan::Filter(filter)),
+                sort_keys,
+            )))
+        };
+        let running_sums = DynProofPlan::RunningSum(Box::new(RunningSumExec::new(
+            Box::new(input),
+            partition_by,
+            window_order_by.iter().map(|&(index, _)| index).collect(),
+            sums,
+        )));
+        if is_in_order && columns.len() == num_results {
+            Some(Self::sort_and_slice(
+                running_sums,
+                order_by.to_vec(),
+                slice.clone(),
+            ))
+        } else {
+            // Select the results in order, without the hidden columns
+            let mut postprocessing =
+                Self::order_by_and_slice_postprocessing(order_by.to_vec(), slice.clone());
+            postprocessing.insert(
+                0,
+                OwnedTablePostprocessing::new_select(SelectPostprocessing::new(
+                    result_aliased_exprs
+                        .iter()
+                        .map(|aliased_expr| AliasedResultExpr {
+                            expr: Box::new(Expression::Column(aliased_expr.alias)),
+                            alias: aliased_expr.alias,
+                        })
+                        .collect(),
+                )),
+            );
+            Some(Self {
+                proof_expr: running_sums,
                 postprocessing,
             })
         }
     }
 
+    /// Creates the plan of a subquery in the FROM clause, i.e. of a derived table.
+    ///
+    /// The subquery has to be provable without postprocessing, so that its result is proven.
+    /// Since the derived table is not the query result, the plan must either be a `GroupByExec`
+    /// or not contain one, see [`DerivedTableExec`].
+    fn try_new_subquery(
+        query: &SetExpression,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        common_table_exprs: &IndexMap<TableRef, Vec<ColumnField>>,
+        reveal_like_dictionaries: bool,
+    ) -> ConversionResult<DynProofPlan<C>> {
+        let query_expr = Self::try_new_with_ctes(
+            SelectStatement {
+                ctes: vec![],
+                expr: Box::new(query.clone()),
+                order_by: vec![],
+                slice: None,
+            },
+            default_schema,
+            schema_accessor,
+            common_table_exprs,
+            reveal_like_dictionaries,
+        )?;
+        if !query_expr.postprocessing.is_empty() {
+            return Err(ConversionError::Unprovable {
+                error: "a subquery must be provable without postprocessing, \
+                    so it can't apply arithmetic to its aggregates"
+                    .to_owned(),
+            });
+        }
+        if is_supported_subquery(&query_expr.proof_expr) {
+            Ok(query_expr.proof_expr)
+        } else {
+            Err(ConversionError::Unprovable {
+                error: "subqueries that group by unsortable expressions or that contain \
+                    a grouping that is not the subquery itself are not supported"
+                    .to_owned(),
+            })
+        }
+    }
+
+    /// Creates a `QueryExpr` that proves a union and then sorts and slices its result.
+    ///
+    /// Each query of the union has to be provable without postprocessing, so that the union of
+    /// their results is proven as well. The results are named after the ones of the first query.
+    fn try_new_union(
+        union: SetExpression,
+        order_by: Vec<OrderBy>,
+        slice: Option<Slice>,
+        default_schema: Identifier,
+        schema_accessor: &dyn SchemaAccessor,
+        common_table_exprs: &IndexMap<TableRef, Vec<ColumnField>>,
+        reveal_like_dictionaries: bool,
+    ) -> ConversionResult<Self> {
+        let mut queries = vec![];
+        push_union_queries(union, &mut queries);
+        let inputs = queries
+            .into_iter()
+            .map(|query| {
+                let query_expr = Self::try_new_with_ctes(
+                    SelectStatement {
+                        ctes: vec![],
+                        expr: Box::new(query),
+                        order_by: vec![],
+                        slice: None,
+                    },
+                    default_schema,
+                    schema_accessor,
+                    common_table_exprs,
+                    reveal_like_dictionaries,
+                )?;
+                if query_expr.postprocessing.is_empty() {
+                    Ok(query_expr.proof_expr)
+                } else {
+                    Err(ConversionError::Unprovable {
+                        error: "every query of a union must be provable without postprocessing"
+                            .to_owned(),
+                    })
+                }
+            })
+            .collect::<ConversionResult<Vec<_>>>()?;
+        let result_types = |input: &DynProofPlan<C>| {
+            input
+                .get_column_result_fields()
+                .iter()
+                .map(ColumnField::data_type)
+                .collect::<Vec<_>>()
+        };
+        let left_types = result_types(&inputs[0]);
+        for input in &inputs[1..] {
+            let right_types = result_types(input);
+            if right_types != left_types {
+                let join_types = |types: &[ColumnType]| {
+                    types
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+                return Err(ConversionError::UnionTypeMismatch {
+                    left_types: join_types(&left_types),
+                    right_types: join_types(&right_types),
+                });
+            }
+        }
+        // A `GroupByExec` needs the query result to be verified, so a union of one has to be
+        // the provable part of the query.
+        let has_group_by = inputs
+            .iter()
+            .any(|input| matches!(input, DynProofPlan::GroupBy(_)));
+        let union = DynProofPlan::Union(UnionExec::new(inputs));
+        if has_group_by {
+            Self::check_order_by(&union, &order_by)?;
+            Ok(Self {
+                proof_expr: union,
+                postprocessing: Self::order_by_and_slice_postprocessing(order_by, slice),
+            })
+        } else {
+            Self::try_sort_and_slice(union, order_by, slice)
+        }
+    }
+
+    /// Creates a `QueryExpr` that sorts and slices the result of `proof_expr`
+    /// after checking that the order by only references its result columns.
+    fn try_sort_and_slice(
+        proof_expr: DynProofPlan<C>,
+        order_by: Vec<OrderBy>,
+        slice: Option<Slice>,
+    ) -> ConversionResult<Self> {
+        Self::check_order_by(&proof_expr, &order_by)?;
+        Ok(Self::sort_and_slice(proof_expr, order_by, slice))
+    }
+
+    /// Checks that the order by only references result columns of `proof_expr`.
+    fn check_order_by(proof_expr: &DynProofPlan<C>, order_by: &[OrderBy]) -> ConversionResult<()> {
+        let result_fields = proof_expr.get_column_result_fields();
+        // Order by must reference only aliases in the result schema
+        for by_expr in order_by {
+            if !result_fields
+                .iter()
+                .any(|field| field.name() == by_expr.expr)
+            {
+                return Err(ConversionError::InvalidOrderBy {
+                    alias: by_expr.expr.as_str().to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Creates a `QueryExpr` that sorts and slices the result of `proof_expr`.
+    ///
+    /// The sort is proven if every key is a non-nullable column of the result of `proof_expr`
+    /// whose type can be sorted provably. Otherwise it is done in postprocessing.
+    /// The slice is proven if the sort is and its offset is not negative.
+    fn sort_and_slice(
+        proof_expr: DynProofPlan<C>,
+        order_by: Vec<OrderBy>,
+        slice: Option<Slice>,
+    ) -> Self {
+        let result_fields = proof_expr.get_column_result_fields();
+        let is_nullable = |id: Identifier| {
+            presence_column_id(id)
+                .is_some_and(|presence_id| result_fields.iter().any(|f| f.name() == presence_id))
+        };
+        let sort_keys = order_by
+            .iter()
+            .map(|by_expr| {
+                result_fields
+                    .iter()
+                    .position(|field| {
+                        field.name() == by_expr.expr
+                            && is_sortable_column_type(&field.data_type())
+                            && !is_nullable(field.name())
+                    })
+                    .map(|index| (index, by_expr.direction))
+            })
+            .collect::<Option<Vec<_>>>();
+        let proof_expr = match sort_keys {
+            Some(sort_keys) if !sort_keys.is_empty() => {
+                DynProofPlan::Sort(Box::new(SortExec::new(Box::new(proof_expr), sort_keys)))
+            }
+            Some(_) => proof_expr,
+            None => {
+                return Self {
+                    proof_expr,
+                    postprocessing: Self::order_by_and_slice_postprocessing(order_by, slice),
+                }
+            }
+        };
+        // Slices with a negative offset count from the end of the result, which isn't provable
+        match slice {
+            Some(Slice {
+                number_rows,
+                offset_value,
+            }) if offset_value >= 0 => Self {
+                proof_expr: DynProofPlan::Slice(Box::new(SliceExec::new(
+                    Box::new(proof_expr),
+                    usize::try_from(offset_value).unwrap_or(usize::MAX),
+                    (number_rows != u64::MAX)
+                        .then(|| usize::try_from(number_rows).unwrap_or(usize::MAX)),
+                ))),
+                postprocessing: vec![],
+            },
+            _ => Self {
+                proof_expr,
+                postprocessing: Self::order_by_and_slice_postprocessing(vec![], slice),
+            },
+        }
+    }
+
+    /// The postprocessing steps that sort and slice a result.
+    fn order_by_and_slice_postprocessing(
+        order_by: Vec<OrderBy>,
+        slice: Option<Slice>,
+    ) -> Vec<OwnedTablePostprocessing> {
+        let mut postprocessing = vec![];
+        if !order_by.is_empty() {
+            postprocessing.push(OwnedTablePostprocessing::new_order_by(
+                OrderByPostprocessing::new(order_by),
+            ));
+        }
+        if let Some(slice) = slice {
+            postprocessing.push(OwnedTablePostprocessing::new_slice(
+                SlicePostprocessing::new(Some(slice.number_rows), Some(slice.offset_value)),
+            ));
+        }
+        postprocessing
+    }
+
     /// Immutable access to this query's provable filter expression.
     pub fn proof_expr(&self) -> &DynProofPlan<C> {
         &self.proof_expr
@@ -168,3 +966,85 @@ impl<C: Commitment> QueryExpr<C> {
         &self.postprocessing
     }
 }
+
+/// Replaces the subqueries of a WHERE expression with hidden columns, in the order they appear.
+///
+/// Each subquery is appended along with the identifier of its hidden column and,
+/// for an IN subquery, the expression that is looked up in its result.
+///
+/// # Panics
+///
+/// Will panic if the identifier of a hidden column cannot be parsed.
+fn extract_where_subqueries(
+    expr: &mut Expression,
+    subqueries: &mut Vec<(Identifier, Option<Expression>, SetExpression)>,
+) {
+    let hidden_column = |subqueries: &mut Vec<_>, key, query| {
+        let alias: Identifier = format!("__col_subquery_{}", subqueries.len())
+            .parse()
+            .expect("valid identifier");
+        subqueries.push((alias, key, query));
+        Expression::Column(alias)
+    };
+    match expr {
+        Expression::InSubquery { expr: key, query } => {
+            *expr = hidden_column(subqueries, Some((**key).clone()), (**query).clone());
+        }
+        Expression::Subquery(query) => {
+            *expr = hidden_column(subqueries, None, (**query).clone());
+        }
+        Expression::Unary { expr, .. }
+        | Expression::Aggregation { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Window {
+            function: WindowFunction::Sum(expr),
+            ..
+        } => extract_where_subqueries(expr, subqueries),
+        Expression::Binary { left, right, .. } => {
+            extract_where_subqueries(left, subqueries);
+            extract_where_subqueries(right, subqueries);
+        }
+        Expression::InList { expr, list } => {
+            extract_where_subqueries(expr, subqueries);
+            for item in list {
+                extract_where_subqueries(item, subqueries);
+            }
+        }
+        Expression::Between { expr, low, high } => {
+            extract_where_subqueries(expr, subqueries);
+            extract_where_subqueries(low, subqueries);
+            extract_where_subqueries(high, subqueries);
+        }
+        Expression::Case {
+            when_then,
+            else_result,
+        } => {
+            for (when, then) in when_then {
+                extract_where_subqueries(when, subqueries);
+                extract_where_subqueries(then, subqueries);
+            }
+            if let Some(else_result) = else_result {
+                extract_where_subqueries(else_result, subqueries);
+            }
+        }
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::Window {
+            function: WindowFunction::RowNumber | WindowFunction::Rank,
+            ..
+        } => {}
+    }
+}
+
+/// Appends the queries of a set expression in the order of their results in a union.
+fn push_union_queries(set_expression: SetExpression, queries: &mut Vec<SetExpression>) {
+    match set_expression {
+        SetExpression::UnionAll { left, right } => {
+            push_union_queries(*left, queries);
+            push_union_queries(*right, queries);
+        }
+        query @ SetExpression::Query { .. } => queries.push(query),
+    }
+}
@@ -1,26 +1,30 @@
 use super::ConversionError;
 use crate::{
     base::{
-        database::{ColumnType, TableRef, TestSchemaAccessor},
+        database::{ColumnRef, ColumnType, TableRef, TestSchemaAccessor},
         map::{indexmap, IndexMap, IndexSet},
+        math::decimal::Precision,
     },
     sql::{
         parse::QueryExpr,
-        postprocessing::{test_utility::*, PostprocessingError},
-        proof_exprs::test_utility::*,
+        postprocessing::{
+            test_utility::*, GroupByPostprocessing, OwnedTablePostprocessing, PostprocessingError,
+            WindowPostprocessing,
+        },
+        proof_exprs::{test_utility::*, ColumnExpr, DynProofExpr},
         proof_plans::{test_utility::*, DynProofPlan},
     },
 };
 use curve25519_dalek::RistrettoPoint;
 use itertools::Itertools;
 use proof_of_sql_parser::{
-    intermediate_ast::OrderByDirection::*,
+    intermediate_ast::{AggregationOperator, DataType, OrderByDirection::*, WindowFunction},
     sql::SelectStatementParser,
     utility::{
-        add as padd, aliased_expr, col, count, count_all, lit, max, min, mul as pmul, sub as psub,
-        sum,
+        add as padd, aliased_expr, col, count, count_all, count_distinct, ge as pge, lit, max, min,
+        mul as pmul, order, sub as psub, sum, window,
     },
-    Identifier,
+    Identifier, ParseError, SelectStatement,
 };
 
 /// # Panics
@@ -151,6 +155,74 @@ fn we_can_convert_an_ast_with_one_column_and_i128_data() {
     assert_eq!(ast, expected_ast);
 }
 
+fn get_nullable_test_accessor() -> (TableRef, TestSchemaAccessor) {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::BigInt,
+            "__b_presence__".parse().unwrap() => ColumnType::Boolean,
+        },
+    );
+    (t, accessor)
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_is_null_filter() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select a from sxt_tab where b is null", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            not(column(t, "__b_presence__", &accessor)),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    let ast = query_to_provable_ast(t, "select a from sxt_tab where a is not null", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_nullable_column() {
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select b from sxt_tab where b = 3", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["b", "__b_presence__"], &accessor),
+            tab(t),
+            and(
+                equal(column(t, "b", &accessor), const_bigint(3)),
+                column(t, "__b_presence__", &accessor),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    let ast = query_to_provable_ast(t, "select * from sxt_tab", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a", "b", "__b_presence__"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
 #[test]
 fn we_can_convert_an_ast_with_one_column_and_a_filter_by_a_string_literal() {
     let t = "sxt.sxt_tab".parse().unwrap();
@@ -172,6 +244,177 @@ fn we_can_convert_an_ast_with_one_column_and_a_filter_by_a_string_literal() {
     assert_eq!(ast, expected_ast);
 }
 
+#[test]
+fn we_can_convert_an_ast_with_a_filter_by_a_like_pattern() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::VarChar,
+            "b".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let intermediate_ast = SelectStatementParser::new()
+        .parse("select b from sxt_tab where a like 'abc%' and a not like '%xyz%'")
+        .unwrap();
+    let ast = QueryExpr::<RistrettoPoint>::try_new_revealing_like_dictionaries(
+        intermediate_ast,
+        t.schema_id(),
+        &accessor,
+    )
+    .unwrap();
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            and(
+                like(column(t, "a", &accessor), "abc%"),
+                not(like(column(t, "a", &accessor), "%xyz%")),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_prove_a_like_pattern_without_revealing_the_dictionary() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::VarChar,
+            "b".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    for query in [
+        "select b from sxt_tab where a like 'abc%'",
+        "select a like 'abc%' as c from sxt_tab",
+        "select b from sxt_tab where b in (select b from sxt_tab where a like 'abc%')",
+    ] {
+        let intermediate_ast = SelectStatementParser::new().parse(query).unwrap();
+        assert!(matches!(
+            QueryExpr::<RistrettoPoint>::try_new(intermediate_ast, t.schema_id(), &accessor),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_filter_by_in_lists_and_ranges() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::Int,
+            "b".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select b from sxt_tab where a in (1, 5, -3) and b not between -10 and 10",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["b"], &accessor),
+            tab(t),
+            and(
+                in_list(column(t, "a", &accessor), &[1, 5, -3]),
+                not(between(column(t, "b", &accessor), -10, 10)),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_casts() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::Int,
+            "b".parse().unwrap() => ColumnType::Decimal75(Precision::new(5).unwrap(), 2),
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select cast(a as bigint) as c from sxt_tab where a::decimal(12, 2) >= b",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![aliased_plan(
+                cast(column(t, "a", &accessor), DataType::BigInt),
+                "c",
+            )],
+            tab(t),
+            gte(
+                cast(
+                    column(t, "a", &accessor),
+                    DataType::Decimal {
+                        precision: 12,
+                        scale: 2,
+                    },
+                ),
+                column(t, "b", &accessor),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // Casts that may lose values are not provable
+    invalid_query_to_provable_ast(
+        t,
+        "select a from sxt_tab where cast(a as tinyint) = 1",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where b::int = 1", &accessor);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_integer_division_and_modulo() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::Int,
+            "d".parse().unwrap() => ColumnType::Decimal75(Precision::new(5).unwrap(), 2),
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select a / b as q, a % 3 as r from sxt_tab where b % 2 = 0",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    "q",
+                ),
+                aliased_plan(modulo(column(t, "a", &accessor), const_bigint(3)), "r"),
+            ],
+            tab(t),
+            equal(
+                modulo(column(t, "b", &accessor), const_bigint(2)),
+                const_bigint(0),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+
+    // Only integer division is provable
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where d / 2 >= 1", &accessor);
+    invalid_query_to_provable_ast(t, "select a from sxt_tab where d % 2 = 0", &accessor);
+}
+
 #[test]
 fn we_cannot_convert_an_ast_with_duplicate_aliases() {
     let t = "sxt.sxt_tab".parse().unwrap();
@@ -679,12 +922,15 @@ fn we_can_parse_order_by_with_a_single_column() {
     );
     let ast = query_to_provable_ast(t, "select * from sxt_tab where a = 3 order by b", &accessor);
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["b", "a"], &accessor),
-            tab(t),
-            equal(column(t, "a", &accessor), const_bigint(3)),
+        sort(
+            filter(
+                cols_expr_plan(t, &["b", "a"], &accessor),
+                tab(t),
+                equal(column(t, "a", &accessor), const_bigint(3)),
+            ),
+            vec![(0, Asc)],
         ),
-        vec![orders(&["b"], &[Asc])],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -704,16 +950,66 @@ fn we_can_parse_order_by_with_multiple_columns() {
         "select a, b from sxt_tab where a = b + 3 order by b desc, a asc",
         &accessor,
     );
+    let expected_ast = QueryExpr::new(
+        sort(
+            filter(
+                cols_expr_plan(t, &["a", "b"], &accessor),
+                tab(t),
+                equal(
+                    column(t, "a", &accessor),
+                    add(column(t, "b", &accessor), const_bigint(3)),
+                ),
+            ),
+            vec![(1, Desc), (0, Asc)],
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_order_by_with_keys_that_cannot_be_sorted_provably() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "s".parse().unwrap() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(t, "select a, s from sxt_tab order by a, s", &accessor);
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["a", "b"], &accessor),
+            cols_expr_plan(t, &["a", "s"], &accessor),
             tab(t),
-            equal(
-                column(t, "a", &accessor),
-                add(column(t, "b", &accessor), const_bigint(3)),
+            const_bool(true),
+        ),
+        vec![orders(&["a", "s"], &[Asc, Asc])],
+    );
+    assert_eq!(ast, expected_ast);
+
+    let (t, accessor) = get_nullable_test_accessor();
+    let ast = query_to_provable_ast(t, "select a, b from sxt_tab order by b desc", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a", "b", "__b_presence__"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![orders(&["b"], &[Desc])],
+    );
+    assert_eq!(ast, expected_ast);
+    let ast = query_to_provable_ast(t, "select a, b from sxt_tab order by a desc", &accessor);
+    let expected_ast = QueryExpr::new(
+        sort(
+            filter(
+                cols_expr_plan(t, &["a", "b", "__b_presence__"], &accessor),
+                tab(t),
+                const_bool(true),
             ),
+            vec![(0, Desc)],
         ),
-        vec![orders(&["b", "a"], &[Desc, Asc])],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -830,23 +1126,26 @@ fn we_can_parse_order_by_queries_with_the_same_column_name_appearing_more_than_o
             "name".parse().unwrap() => ColumnType::VarChar,
         },
     );
-    for order_by in ["s", "d"] {
+    for (order_by, index) in [("s", 0), ("d", 2)] {
         let ast = query_to_provable_ast(
             t,
             &("select salary as s, name, salary as d from sxt_tab order by ".to_owned() + order_by),
             &accessor,
         );
         let expected_ast = QueryExpr::new(
-            filter(
-                vec![
-                    aliased_col_expr_plan(t, "salary", "s", &accessor),
-                    col_expr_plan(t, "name", &accessor),
-                    aliased_col_expr_plan(t, "salary", "d", &accessor),
-                ],
-                tab(t),
-                const_bool(true),
+            sort(
+                filter(
+                    vec![
+                        aliased_col_expr_plan(t, "salary", "s", &accessor),
+                        col_expr_plan(t, "name", &accessor),
+                        aliased_col_expr_plan(t, "salary", "d", &accessor),
+                    ],
+                    tab(t),
+                    const_bool(true),
+                ),
+                vec![(index, Asc)],
             ),
-            vec![orders(&[order_by], &[Asc])],
+            vec![],
         );
         assert_eq!(ast, expected_ast);
     }
@@ -867,12 +1166,16 @@ fn we_can_parse_a_query_having_a_simple_limit_clause() {
     );
     let ast = query_to_provable_ast(t, "select a from sxt_tab limit 3", &accessor);
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["a"], &accessor),
-            tab(t),
-            const_bool(true),
+        slice_exec(
+            filter(
+                cols_expr_plan(t, &["a"], &accessor),
+                tab(t),
+                const_bool(true),
+            ),
+            0,
+            Some(3),
         ),
-        vec![slice(Some(3), Some(0))],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -888,12 +1191,16 @@ fn slice_is_still_applied_when_limit_is_u64_max_and_offset_is_zero() {
     );
     let ast = query_to_provable_ast(t, "select a from sxt_tab offset 0", &accessor);
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["a"], &accessor),
-            tab(t),
-            const_bool(true),
+        slice_exec(
+            filter(
+                cols_expr_plan(t, &["a"], &accessor),
+                tab(t),
+                const_bool(true),
+            ),
+            0,
+            None,
         ),
-        vec![slice(Some(u64::MAX), Some(0))],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -909,12 +1216,16 @@ fn we_can_parse_a_query_having_a_simple_positive_offset_clause() {
     );
     let ast = query_to_provable_ast(t, "select a from sxt_tab offset 7", &accessor);
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["a"], &accessor),
-            tab(t),
-            const_bool(true),
+        slice_exec(
+            filter(
+                cols_expr_plan(t, &["a"], &accessor),
+                tab(t),
+                const_bool(true),
+            ),
+            7,
+            None,
         ),
-        vec![slice(Some(u64::MAX), Some(7))],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -951,12 +1262,38 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause() {
     );
     let ast = query_to_provable_ast(t, "select a from sxt_tab limit 55 offset 3", &accessor);
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["a"], &accessor),
-            tab(t),
-            const_bool(true),
+        slice_exec(
+            filter(
+                cols_expr_plan(t, &["a"], &accessor),
+                tab(t),
+                const_bool(true),
+            ),
+            3,
+            Some(55),
         ),
-        vec![slice(Some(55), Some(3))],
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_having_a_limit_clause_after_an_order_by_that_cannot_be_proven() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "s".parse().unwrap() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(t, "select a, s from sxt_tab order by s limit 2", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["a", "s"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![orders(&["s"], &[Asc]), slice(Some(2), Some(0))],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -981,21 +1318,28 @@ fn we_can_parse_a_query_having_a_simple_limit_and_offset_clause_preceded_by_wher
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            vec![
-                col_expr_plan(t, "a", &accessor),
-                aliased_plan(
-                    and(
-                        column(t, "boolean", &accessor),
-                        gte(column(t, "a", &accessor), const_bigint(4)),
-                    ),
-                    "res",
+        slice_exec(
+            sort(
+                filter(
+                    vec![
+                        col_expr_plan(t, "a", &accessor),
+                        aliased_plan(
+                            and(
+                                column(t, "boolean", &accessor),
+                                gte(column(t, "a", &accessor), const_bigint(4)),
+                            ),
+                            "res",
+                        ),
+                    ],
+                    tab(t),
+                    equal(column(t, "a", &accessor), const_bigint(-3)),
                 ),
-            ],
-            tab(t),
-            equal(column(t, "a", &accessor), const_bigint(-3)),
+                vec![(0, Desc)],
+            ),
+            3,
+            Some(55),
         ),
-        vec![orders(&["a"], &[Desc]), slice(Some(55), Some(3))],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
@@ -1020,7 +1364,7 @@ fn we_can_do_provable_group_by() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
             "num_employee",
             tab(t),
@@ -1048,7 +1392,7 @@ fn we_can_do_provable_group_by_without_sum() {
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
+            cols_expr_plan(t, &["department"], &accessor),
             vec![],
             "num_employee",
             tab(t),
@@ -1060,25 +1404,31 @@ fn we_can_do_provable_group_by_without_sum() {
 }
 
 #[test]
-fn we_can_do_provable_group_by_with_two_group_by_columns() {
+fn we_can_do_provable_group_by_with_a_conditional_sum() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "state".parse().unwrap() => ColumnType::VarChar,
             "salary".parse().unwrap() => ColumnType::BigInt,
             "department".parse().unwrap() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select state, department, sum(salary) as total_salary, count(*) as num_employee from employees group by state, department",
+        "select department, sum(case when salary >= 100 then 1 else 0 end) as num_rich, count(*) as num_employee from employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["state", "department"], &accessor),
-            vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
+            cols_expr_plan(t, &["department"], &accessor),
+            vec![sum_expr(
+                case_when(
+                    gte(column(t, "salary", &accessor), const_bigint(100)),
+                    const_bigint(1),
+                    const_bigint(0),
+                ),
+                "num_rich",
+            )],
             "num_employee",
             tab(t),
             const_bool(true),
@@ -1089,42 +1439,36 @@ fn we_can_do_provable_group_by_with_two_group_by_columns() {
 }
 
 #[test]
-fn we_can_do_provable_group_by_with_two_sums_and_filter() {
+fn we_can_do_provable_group_by_with_two_group_by_columns() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "tax".parse().unwrap() => ColumnType::BigInt,
+            "state".parse().unwrap() => ColumnType::VarChar,
             "salary".parse().unwrap() => ColumnType::BigInt,
             "department".parse().unwrap() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select department, sum(salary) as total_salary, sum(tax) as total_tax, count(*) as num_employee from employees where tax <= 1 group by department",
+        "select state, department, sum(salary) as total_salary, count(*) as num_employee from employees group by state, department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         group_by(
-            cols_expr(t, &["department"], &accessor),
-            vec![
-                sum_expr(column(t, "salary", &accessor), "total_salary"),
-                sum_expr(column(t, "tax", &accessor), "total_tax"),
-            ],
+            cols_expr_plan(t, &["state", "department"], &accessor),
+            vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
             "num_employee",
             tab(t),
-            lte(column(t, "tax", &accessor), const_bigint(1)),
+            const_bool(true),
         ),
         vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
-///////////////////////////
-// Group By Expressions - Postprocessing
-///////////////////////////
 #[test]
-fn we_can_group_by_without_using_aggregate_functions() {
+fn we_can_do_provable_group_by_on_an_expression() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
@@ -1135,106 +1479,59 @@ fn we_can_group_by_without_using_aggregate_functions() {
     );
     let ast = query_to_provable_ast(
         t,
-        "select department, true as is_remote from employees group by department",
+        "select department + 1 as next_department, sum(salary) as total_salary, count(*) as num_employee from employees group by department + 1",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            vec![col_expr_plan(t, "department", &accessor)],
+        group_by(
+            vec![aliased_plan(
+                add(column(t, "department", &accessor), const_bigint(1)),
+                "next_department",
+            )],
+            vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
+            "num_employee",
             tab(t),
             const_bool(true),
         ),
-        vec![
-            group_by_postprocessing(
-                &["department"],
-                &[
-                    aliased_expr(col("department"), "department"),
-                    aliased_expr(lit(true), "is_remote"),
-                ],
-            ),
-            select_expr(&[
-                aliased_expr(col("department"), "department"),
-                aliased_expr(lit(true), "is_remote"),
-            ]),
-        ],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_expressions_are_parsed_before_an_order_by_referencing_an_aggregate_alias_result() {
-    let query = query!(
-        select: ["max(i) max_sal", "i0 d", "count(i0)"],
-        group: ["i0", "i1"],
-        order: ["max_sal"]
-    );
-    let (t, accessor) = get_test_accessor();
-    let expected_query = QueryExpr::new(
-        filter(
-            vec![
-                col_expr_plan(t, "i", &accessor),
-                col_expr_plan(t, "i0", &accessor),
-                col_expr_plan(t, "i1", &accessor),
-            ],
-            tab(t),
-            const_bool(true),
-        ),
-        vec![
-            group_by_postprocessing(
-                &["i0", "i1"],
-                &[
-                    aliased_expr(max(col("i")), "max_sal"),
-                    aliased_expr(col("i0"), "d"),
-                    aliased_expr(count(col("i0")), "__count__"),
-                ],
-            ),
-            orders(&["max_sal"], &[Asc]),
-        ],
-    );
-    assert_eq!(query, expected_query);
-}
-
-#[test]
-fn we_cannot_parse_non_aggregated_or_non_group_by_columns_in_the_select_clause() {
-    let t = "sxt.employees".parse().unwrap();
-    let accessor = schema_accessor_from_table_ref_with_schema(
-        t,
-        indexmap! {
-            "salary".parse().unwrap() => ColumnType::BigInt,
-            "department".parse().unwrap() => ColumnType::BigInt,
-        },
-    );
-    invalid_query_to_provable_ast(
-        t,
-        "select department, salary from sxt.employees group by department",
-        &accessor,
-    );
-}
-
-#[test]
-fn alias_references_are_not_allowed_in_the_group_by() {
+fn we_can_do_provable_group_by_with_two_sums_and_filter() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
+            "tax".parse().unwrap() => ColumnType::BigInt,
             "salary".parse().unwrap() => ColumnType::BigInt,
             "department".parse().unwrap() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         t,
-        "select department, min(salary) as min_salary from employees group by min_salary",
+        "select department, sum(salary) as total_salary, sum(tax) as total_tax, count(*) as num_employee from employees where tax <= 1 group by department",
         &accessor,
     );
-    invalid_query_to_provable_ast(
-        t,
-        "select salary as min_salary from employees group by min_salary",
-        &accessor,
+    let expected_ast = QueryExpr::new(
+        group_by(
+            cols_expr_plan(t, &["department"], &accessor),
+            vec![
+                sum_expr(column(t, "salary", &accessor), "total_salary"),
+                sum_expr(column(t, "tax", &accessor), "total_tax"),
+            ],
+            "num_employee",
+            tab(t),
+            lte(column(t, "tax", &accessor), const_bigint(1)),
+        ),
+        vec![],
     );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn order_by_cannot_reference_an_invalid_group_by_column() {
+fn we_can_do_provable_group_by_with_a_max_and_without_count() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
@@ -1243,97 +1540,148 @@ fn order_by_cannot_reference_an_invalid_group_by_column() {
             "department".parse().unwrap() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         t,
-        "select department as d from sxt.employees group by department order by department",
+        "select department, max(salary) as max_salary from employees group by department",
         &accessor,
     );
-    invalid_query_to_provable_ast(
-        t,
-        "select department, min(salary) from sxt.employees group by department order by salary",
-        &accessor,
+    let expected_ast = QueryExpr::new(
+        group_by_with_min_max(
+            cols_expr_plan(t, &["department"], &accessor),
+            vec![],
+            vec![(
+                AggregationOperator::Max,
+                aliased_plan(column(t, "salary", &accessor), "max_salary"),
+            )],
+            None,
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
     );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_column_cannot_be_a_column_result_alias() {
+fn we_can_do_provable_group_by_with_sums_mins_maxes_and_filter() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
+            "tax".parse().unwrap() => ColumnType::BigInt,
             "salary".parse().unwrap() => ColumnType::BigInt,
             "department".parse().unwrap() => ColumnType::BigInt,
         },
     );
-    invalid_query_to_provable_ast(
+    let ast = query_to_provable_ast(
         t,
-        "select min(salary) as min_sal from sxt.employees group by min_sal",
+        "select department, sum(salary) as total_salary, min(salary) as min_salary, max(salary - tax) as max_net, count(*) as num_employee from employees where tax <= 1 group by department",
         &accessor,
     );
-}
-
-#[test]
-fn we_can_have_aggregate_functions_without_a_group_by_clause() {
-    let ast = query!(
-        select: ["count(s)"],
-    );
-    let (t, _accessor) = get_test_accessor();
     let expected_ast = QueryExpr::new(
-        group_by(vec![], vec![], "__count__", tab(t), const_bool(true)),
+        group_by_with_min_max(
+            cols_expr_plan(t, &["department"], &accessor),
+            vec![sum_expr(column(t, "salary", &accessor), "total_salary")],
+            vec![
+                (
+                    AggregationOperator::Min,
+                    aliased_plan(column(t, "salary", &accessor), "min_salary"),
+                ),
+                (
+                    AggregationOperator::Max,
+                    aliased_plan(
+                        subtract(column(t, "salary", &accessor), column(t, "tax", &accessor)),
+                        "max_net",
+                    ),
+                ),
+            ],
+            Some("num_employee"),
+            tab(t),
+            lte(column(t, "tax", &accessor), const_bigint(1)),
+        ),
         vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_expression() {
-    let t = "sxt.employees".parse().unwrap();
+fn we_cannot_do_provable_group_by_with_a_min_of_a_nullable_column_or_a_sum_after_a_max() {
+    let t = "sxt.sxt_tab".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "salary".parse().unwrap() => ColumnType::BigInt,
-            "department".parse().unwrap() => ColumnType::BigInt,
-            "bonus".parse().unwrap() => ColumnType::VarChar,
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::BigInt,
+            "__b_presence__".parse().unwrap() => ColumnType::Boolean,
         },
     );
+    for query in [
+        "select a, min(b) as min_b from sxt_tab group by a",
+        "select a, max(a) as max_a, sum(b) as sum_b from sxt_tab group by a",
+    ] {
+        let ast = query_to_provable_ast(t, query, &accessor);
+        assert!(matches!(ast.proof_expr(), DynProofPlan::Filter(_)));
+        assert!(!ast.postprocessing().is_empty());
+    }
+}
+
+#[test]
+fn we_can_do_provable_group_by_with_a_count_of_a_nullable_column() {
+    let (t, accessor) = get_nullable_test_accessor();
     let ast = query_to_provable_ast(
         t,
-        "select count(bonus) department from sxt.employees group by department",
+        "select a, count(b) as count_b, sum(b) as sum_b, count(*) as n from sxt_tab group by a",
         &accessor,
     );
+    let count_b = DynProofExpr::try_new_nullable_aggregate(
+        AggregationOperator::Sum,
+        const_bigint(1),
+        column(t, "__b_presence__", &accessor),
+    )
+    .unwrap();
+    let sum_b = DynProofExpr::try_new_nullable_aggregate(
+        AggregationOperator::Sum,
+        column(t, "b", &accessor),
+        column(t, "__b_presence__", &accessor),
+    )
+    .unwrap();
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["bonus", "department"], &accessor),
+        group_by(
+            cols_expr_plan(t, &["a"], &accessor),
+            vec![
+                aliased_plan(count_b, "count_b"),
+                aliased_plan(sum_b, "sum_b"),
+            ],
+            "n",
             tab(t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[aliased_expr(count(col("bonus")), "department")],
-        )],
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
+///////////////////////////
+// Group By Expressions - Postprocessing
+///////////////////////////
 #[test]
-fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
+fn we_can_group_by_without_using_aggregate_functions() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".parse().unwrap() => ColumnType::BigInt,
             "department".parse().unwrap() => ColumnType::BigInt,
-            "bonus".parse().unwrap() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select department, count(bonus), count(department) as dep from sxt.employees group by department",
+        "select department, true as is_remote from employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["bonus", "department"], &accessor),
+            vec![col_expr_plan(t, "department", &accessor)],
             tab(t),
             const_bool(true),
         ),
@@ -1341,8 +1689,7 @@ fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
             &["department"],
             &[
                 aliased_expr(col("department"), "department"),
-                aliased_expr(count(col("bonus")), "__count__"),
-                aliased_expr(count(col("department")), "dep"),
+                aliased_expr(lit(true), "is_remote"),
             ],
         )],
     );
@@ -1350,151 +1697,208 @@ fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
 }
 
 #[test]
-fn count_all_uses_the_first_group_by_identifier_as_default_result_column() {
+fn we_can_group_by_an_expression_that_is_used_inside_a_result_expression() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".parse().unwrap() => ColumnType::BigInt,
             "department".parse().unwrap() => ColumnType::BigInt,
-            "bonus".parse().unwrap() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select count(*) from sxt.employees where salary = 4 group by department",
+        "select 2 * (department + 1) as d, count(*) as num_employee from employees group by department + 1",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["department", "salary"], &accessor),
+            vec![col_expr_plan(t, "department", &accessor)],
             tab(t),
-            equal(column(t, "salary", &accessor), const_bigint(4)),
+            const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[aliased_expr(count_all(), "__count__")],
+        vec![OwnedTablePostprocessing::new_group_by(
+            GroupByPostprocessing::try_new(
+                vec![*padd(col("department"), lit(1))],
+                vec![
+                    aliased_expr(pmul(lit(2), padd(col("department"), lit(1))), "d"),
+                    aliased_expr(count_all(), "num_employee"),
+                ],
+            )
+            .unwrap(),
         )],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn aggregate_result_columns_cannot_reference_invalid_columns() {
+fn we_cannot_group_by_an_aggregation_or_use_columns_of_group_by_expressions_outside_them() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".parse().unwrap() => ColumnType::BigInt,
             "department".parse().unwrap() => ColumnType::BigInt,
-            "bonus".parse().unwrap() => ColumnType::VarChar,
         },
     );
     invalid_query_to_provable_ast(
         t,
-        "select department, max(non_existent) from sxt.employees group by department",
+        "select count(*) as n from sxt.employees group by sum(salary)",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department, count(*) as n from sxt.employees group by department + 1",
         &accessor,
     );
 }
 
 #[test]
-fn we_can_use_the_same_result_columns_with_different_aliases_and_associate_it_with_group_by() {
+fn we_can_do_provable_group_by_with_a_having_clause_on_the_results() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
             "salary".parse().unwrap() => ColumnType::BigInt,
             "department".parse().unwrap() => ColumnType::BigInt,
-            "bonus".parse().unwrap() => ColumnType::VarChar,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "SELECT department as d1, department as d2 from employees group by department",
+        "select department, count(*) as num_employee from employees group by department having count(*) >= 2 order by department",
         &accessor,
     );
+    // The groups are filtered as a derived table
+    let d = "sxt.__having__".parse().unwrap();
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["department"], &accessor),
-            tab(t),
-            const_bool(true),
-        ),
-        vec![group_by_postprocessing(
-            &["department"],
-            &[
-                aliased_expr(col("department"), "d1"),
-                aliased_expr(col("department"), "d2"),
-            ],
-        )],
+        derived_table(
+            group_by(
+                cols_expr_plan(t, &["department"], &accessor),
+                vec![],
+                "num_employee",
+                tab(t),
+                const_bool(true),
+            ),
+            d,
+            filter(
+                vec![
+                    aliased_plan(
+                        derived_column(d, "department", ColumnType::BigInt),
+                        "department",
+                    ),
+                    aliased_plan(
+                        derived_column(d, "num_employee", ColumnType::BigInt),
+                        "num_employee",
+                    ),
+                ],
+                tab(d),
+                gte(
+                    derived_column(d, "num_employee", ColumnType::BigInt),
+                    const_bigint(2),
+                ),
+            ),
+        ),
+        vec![orders(&["department"], &[Asc])],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_use_multiple_group_by_clauses_with_multiple_agg_and_non_agg_exprs() {
-    let (t, accessor) = get_test_accessor();
-    let ast = query!(
-        select: ["i d1", "max(i1)", "i d2", "sum(i0) sum_bonus", "count(s) count_s"],
-        group: ["i", "i0", "i"]
+fn we_can_group_by_with_a_having_clause_on_an_aggregation_that_is_not_a_result() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select department, count(*) as num_employee from employees group by department having sum(salary) >= 100",
+        &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["i", "i0", "i1", "s"], &accessor),
+            vec![
+                col_expr_plan(t, "department", &accessor),
+                col_expr_plan(t, "salary", &accessor),
+            ],
             tab(t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["i", "i0", "i"],
-            &[
-                aliased_expr(col("i"), "d1"),
-                aliased_expr(max(col("i1")), "__max__"),
-                aliased_expr(col("i"), "d2"),
-                aliased_expr(sum(col("i0")), "sum_bonus"),
-                aliased_expr(count(col("s")), "count_s"),
-            ],
-        )],
+        vec![
+            OwnedTablePostprocessing::new_group_by(
+                GroupByPostprocessing::try_new(
+                    vec![*col("department")],
+                    vec![
+                        aliased_expr(col("department"), "department"),
+                        aliased_expr(count_all(), "num_employee"),
+                        aliased_expr(pge(sum(col("salary")), lit(100)), "__having__"),
+                    ],
+                )
+                .unwrap(),
+            ),
+            filter_expr(col("__having__")),
+            select_expr(&[
+                aliased_expr(col("department"), "department"),
+                aliased_expr(col("num_employee"), "num_employee"),
+            ]),
+        ],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_expr() {
+fn we_cannot_have_a_having_clause_that_is_not_boolean_or_without_aggregations() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "a".parse().unwrap() => ColumnType::BigInt,
-            "f".parse().unwrap() => ColumnType::Int128,
-            "b".parse().unwrap() => ColumnType::BigInt,
-            "h".parse().unwrap() => ColumnType::Int128,
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department, count(*) as n from sxt.employees group by department having count(*)",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department from sxt.employees having department >= 2",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department, count(*) as n from sxt.employees group by department having salary >= 2",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_prove_a_select_distinct_query_as_a_group_by() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
         },
     );
-    // TODO: add `a / b as a_div_b` result expr once polars properly
-    // supports decimal division without panicking in production
     let ast = query_to_provable_ast(
         t,
-        "select a + b, 2 * f as f2, -77 - h as col, a + f as af from employees",
+        "select distinct department, salary from employees where salary >= 5",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            vec![
-                aliased_plan(
-                    add(column(t, "a", &accessor), column(t, "b", &accessor)),
-                    "__expr__",
-                ),
-                aliased_plan(multiply(const_bigint(2), column(t, "f", &accessor)), "f2"),
-                aliased_plan(
-                    subtract(const_bigint(-77), column(t, "h", &accessor)),
-                    "col",
-                ),
-                aliased_plan(
-                    add(column(t, "a", &accessor), column(t, "f", &accessor)),
-                    "af",
-                ),
-            ],
+        group_by_with_min_max(
+            cols_expr_plan(t, &["department", "salary"], &accessor),
+            vec![],
+            vec![],
+            None,
             tab(t),
-            const_bool(true),
+            gte(column(t, "salary", &accessor), const_bigint(5)),
         ),
         vec![],
     );
@@ -1502,92 +1906,73 @@ fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_ex
 }
 
 #[test]
-fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
-) {
+fn we_can_prove_the_distinct_values_of_a_count_distinct() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "c".parse().unwrap() => ColumnType::BigInt,
-            "f".parse().unwrap() => ColumnType::BigInt,
-            "g".parse().unwrap() => ColumnType::BigInt,
-            "h".parse().unwrap() => ColumnType::BigInt,
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select (2 + f) * (c + g + 2 * h), ((h - g) * 2 + c + g) * (f + 2) as d from employees",
+        "select department, count(distinct salary) as n from employees group by department order by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
+        group_by_with_min_max(
             vec![
-                aliased_plan(
-                    multiply(
-                        add(const_bigint(2), column(t, "f", &accessor)),
-                        add(
-                            add(column(t, "c", &accessor), column(t, "g", &accessor)),
-                            multiply(const_bigint(2), column(t, "h", &accessor)),
-                        ),
-                    ),
-                    "__expr__",
-                ),
-                aliased_plan(
-                    multiply(
-                        add(
-                            add(
-                                multiply(
-                                    subtract(column(t, "h", &accessor), column(t, "g", &accessor)),
-                                    const_bigint(2),
-                                ),
-                                column(t, "c", &accessor),
-                            ),
-                            column(t, "g", &accessor),
-                        ),
-                        add(column(t, "f", &accessor), const_bigint(2)),
-                    ),
-                    "d",
-                ),
+                col_expr_plan(t, "department", &accessor),
+                aliased_col_expr_plan(t, "salary", "__count_distinct__", &accessor),
             ],
+            vec![],
+            vec![],
+            None,
             tab(t),
             const_bool(true),
         ),
-        vec![],
+        vec![
+            group_by_postprocessing(
+                &["department"],
+                &[
+                    aliased_expr(col("department"), "department"),
+                    aliased_expr(count(col("__count_distinct__")), "n"),
+                ],
+            ),
+            orders(&["department"], &[Asc]),
+        ],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
+fn we_can_count_distinct_values_along_with_other_aggregations_in_postprocessing() {
     let t = "sxt.employees".parse().unwrap();
     let accessor = schema_accessor_from_table_ref_with_schema(
         t,
         indexmap! {
-            "c".parse().unwrap() => ColumnType::BigInt,
-            "f".parse().unwrap() => ColumnType::BigInt,
-            "g".parse().unwrap() => ColumnType::BigInt,
-            "k".parse().unwrap() => ColumnType::BigInt,
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
         },
     );
     let ast = query_to_provable_ast(
         t,
-        "select c, sum(2 * f + c - -7) as d from employees group by c",
+        "select department, count(distinct salary) as n, sum(salary) as s from employees group by department",
         &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["c", "f"], &accessor),
+            cols_expr_plan(t, &["department", "salary"], &accessor),
             tab(t),
             const_bool(true),
         ),
         vec![group_by_postprocessing(
-            &["c"],
+            &["department"],
             &[
-                aliased_expr(col("c"), "c"),
-                aliased_expr(
-                    sum(psub(padd(pmul(lit(2), col("f")), col("c")), lit(-7))),
-                    "d",
-                ),
+                aliased_expr(col("department"), "department"),
+                aliased_expr(count_distinct(col("salary")), "n"),
+                aliased_expr(sum(col("salary")), "s"),
             ],
         )],
     );
@@ -1595,401 +1980,1712 @@ fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
 }
 
 #[test]
-fn we_cannot_use_non_grouped_columns_outside_agg() {
-    assert!(matches!(
-        query!(select: ["i"], group: ["s"], should_err: true),
-        ConversionError::PostprocessingError {
-            source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
-        }
-    ));
-    assert!(matches!(
-        query!(select: ["sum(i)", "i"], group: ["s"], should_err: true),
-        ConversionError::PostprocessingError {
-            source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
-        }
-    ));
-    assert!(matches!(
-        query!(select: ["min(i) + i"], group: ["s"], should_err: true),
-        ConversionError::PostprocessingError {
-            source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
-        }
-    ));
-    assert!(matches!(
-        query!(select: ["2 * i", "min(i)"], group: ["s"], should_err: true),
-        ConversionError::PostprocessingError {
-            source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
-        }
-    ));
-    assert!(matches!(
-        query!(select: ["2 * i", "min(i)"], should_err: true),
-        ConversionError::InvalidGroupByColumnRef { .. }
-    ));
-    assert!(matches!(
-        query!(select: ["sum(i)", "i"], should_err: true),
-        ConversionError::InvalidGroupByColumnRef { .. }
-    ));
-    assert!(matches!(
-        query!(select: ["max(i) + 2 * i"], should_err: true),
-        ConversionError::InvalidGroupByColumnRef { .. }
-    ));
-}
-
-#[test]
-fn varchar_column_is_not_compatible_with_integer_column() {
-    assert_eq!(
-        query!(select: ["-123 * s"], should_err: true),
-        ConversionError::DataTypeMismatch {
-            left_type: ColumnType::BigInt.to_string(),
-            right_type: ColumnType::VarChar.to_string()
-        }
-    );
-    assert_eq!(
-        query!(select: ["i - s"], should_err: true),
-        ConversionError::DataTypeMismatch {
-            left_type: ColumnType::BigInt.to_string(),
-            right_type: ColumnType::VarChar.to_string()
-        }
+fn we_can_remove_duplicate_groups_of_a_select_distinct_query_in_postprocessing() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+        },
     );
-    assert_eq!(
-        query!(select: ["s"], filter: "'abc' = i", should_err: true),
-        ConversionError::DataTypeMismatch {
-            left_type: ColumnType::VarChar.to_string(),
-            right_type: ColumnType::BigInt.to_string(),
-        }
+    let ast = query_to_provable_ast(
+        t,
+        "select distinct count(*) as n from employees group by department",
+        &accessor,
     );
-    assert_eq!(
-        query!(select: ["s"], filter: "'abc' != i", should_err: true),
-        ConversionError::DataTypeMismatch {
-            left_type: ColumnType::VarChar.to_string(),
-            right_type: ColumnType::BigInt.to_string(),
-        }
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![col_expr_plan(t, "department", &accessor)],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(&["department"], &[aliased_expr(count_all(), "n")]),
+            group_by_postprocessing(&["n"], &[aliased_expr(col("n"), "n")]),
+        ],
     );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn arithmetic_operations_are_not_allowed_with_varchar_column() {
-    assert_eq!(
-        query!(select: ["s - s1"], should_err: true),
-        ConversionError::DataTypeMismatch {
-            left_type: ColumnType::VarChar.to_string(),
-            right_type: ColumnType::VarChar.to_string()
-        }
+fn group_by_expressions_are_parsed_before_an_order_by_referencing_an_aggregate_alias_result() {
+    let query = query!(
+        select: ["max(i) max_sal", "i0 d", "count(i0)"],
+        group: ["i0", "i1"],
+        order: ["max_sal"]
+    );
+    let (t, accessor) = get_test_accessor();
+    let expected_query = QueryExpr::new(
+        filter(
+            vec![
+                col_expr_plan(t, "i", &accessor),
+                col_expr_plan(t, "i0", &accessor),
+                col_expr_plan(t, "i1", &accessor),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![
+            group_by_postprocessing(
+                &["i0", "i1"],
+                &[
+                    aliased_expr(max(col("i")), "max_sal"),
+                    aliased_expr(col("i0"), "d"),
+                    aliased_expr(count(col("i0")), "__count__"),
+                ],
+            ),
+            orders(&["max_sal"], &[Asc]),
+        ],
     );
+    assert_eq!(query, expected_query);
 }
 
 #[test]
-fn varchar_column_is_not_allowed_within_numeric_aggregations() {
-    assert_eq!(
-        query!(select: ["sum(s)"], should_err: true),
-        ConversionError::non_numeric_expr_in_agg("varchar", "sum")
+fn we_cannot_parse_non_aggregated_or_non_group_by_columns_in_the_select_clause() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+        },
     );
-    assert_eq!(
-        query!(select: ["max(s)"], should_err: true),
-        ConversionError::non_numeric_expr_in_agg("varchar", "max")
+    invalid_query_to_provable_ast(
+        t,
+        "select department, salary from sxt.employees group by department",
+        &accessor,
     );
-    assert_eq!(
-        query!(select: ["min(s)"], should_err: true),
-        ConversionError::non_numeric_expr_in_agg("varchar", "min")
+}
+
+#[test]
+fn alias_references_are_not_allowed_in_the_group_by() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department, min(salary) as min_salary from employees group by min_salary",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select salary as min_salary from employees group by min_salary",
+        &accessor,
     );
 }
 
 #[test]
-fn group_by_with_bigint_column_is_valid() {
-    let (t, accessor) = get_test_accessor();
-    let query = query!(select: ["i"], group: ["i"]);
-    let expected_query = QueryExpr::new(
+fn order_by_cannot_reference_an_invalid_group_by_column() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department as d from sxt.employees group by department order by department",
+        &accessor,
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department, min(salary) from sxt.employees group by department order by salary",
+        &accessor,
+    );
+}
+
+#[test]
+fn group_by_column_cannot_be_a_column_result_alias() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select min(salary) as min_sal from sxt.employees group by min_sal",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_have_aggregate_functions_without_a_group_by_clause() {
+    let ast = query!(
+        select: ["count(s)"],
+    );
+    let (t, _accessor) = get_test_accessor();
+    let expected_ast = QueryExpr::new(
+        group_by(vec![], vec![], "__count__", tab(t), const_bool(true)),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_query_having_group_by_with_the_same_name_as_the_aggregation_expression() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+            "bonus".parse().unwrap() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select count(bonus) department from sxt.employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["i"], &accessor),
+            cols_expr_plan(t, &["bonus", "department"], &accessor),
             tab(t),
             const_bool(true),
         ),
         vec![group_by_postprocessing(
-            &["i"],
-            &[aliased_expr(col("i"), "i")],
+            &["department"],
+            &[aliased_expr(count(col("bonus")), "department")],
         )],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_with_decimal_column_is_valid() {
-    let (t, accessor) = get_test_accessor();
-    let query = query!(select: ["d"], group: ["d"]);
-    let expected_query = QueryExpr::new(
+fn count_aggregate_functions_can_be_used_with_non_numeric_columns() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+            "bonus".parse().unwrap() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select department, count(bonus), count(department) as dep from sxt.employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["d"], &accessor),
+            cols_expr_plan(t, &["bonus", "department"], &accessor),
             tab(t),
             const_bool(true),
         ),
         vec![group_by_postprocessing(
-            &["d"],
-            &[aliased_expr(col("d"), "d")],
+            &["department"],
+            &[
+                aliased_expr(col("department"), "department"),
+                aliased_expr(count(col("bonus")), "__count__"),
+                aliased_expr(count(col("department")), "dep"),
+            ],
         )],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn group_by_with_varchar_column_is_valid() {
-    let (t, accessor) = get_test_accessor();
-    let query = query!(select: ["s"], group: ["s"]);
-    let expected_query = QueryExpr::new(
+fn count_all_uses_the_first_group_by_identifier_as_default_result_column() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+            "bonus".parse().unwrap() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select count(*) from sxt.employees where salary = 4 group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
         filter(
-            cols_expr_plan(t, &["s"], &accessor),
+            cols_expr_plan(t, &["department", "salary"], &accessor),
+            tab(t),
+            equal(column(t, "salary", &accessor), const_bigint(4)),
+        ),
+        vec![group_by_postprocessing(
+            &["department"],
+            &[aliased_expr(count_all(), "__count__")],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn aggregate_result_columns_cannot_reference_invalid_columns() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+            "bonus".parse().unwrap() => ColumnType::VarChar,
+        },
+    );
+    invalid_query_to_provable_ast(
+        t,
+        "select department, max(non_existent) from sxt.employees group by department",
+        &accessor,
+    );
+}
+
+#[test]
+fn we_can_use_the_same_result_columns_with_different_aliases_and_associate_it_with_group_by() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "salary".parse().unwrap() => ColumnType::BigInt,
+            "department".parse().unwrap() => ColumnType::BigInt,
+            "bonus".parse().unwrap() => ColumnType::VarChar,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "SELECT department as d1, department as d2 from employees group by department",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["department"], &accessor),
             tab(t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(
-            &["s"],
-            &[aliased_expr(col("s"), "s")],
-        )],
+        vec![group_by_postprocessing(
+            &["department"],
+            &[
+                aliased_expr(col("department"), "d1"),
+                aliased_expr(col("department"), "d2"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_use_multiple_group_by_clauses_with_multiple_agg_and_non_agg_exprs() {
+    let (t, accessor) = get_test_accessor();
+    let ast = query!(
+        select: ["i d1", "max(i1)", "i d2", "sum(i0) sum_bonus", "count(s) count_s"],
+        group: ["i", "i0", "i"]
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["i", "i0", "i1", "s"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["i", "i0", "i"],
+            &[
+                aliased_expr(col("i"), "d1"),
+                aliased_expr(max(col("i1")), "__max__"),
+                aliased_expr(col("i"), "d2"),
+                aliased_expr(sum(col("i0")), "sum_bonus"),
+                aliased_expr(count(col("s")), "count_s"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_a_simple_add_mul_sub_div_arithmetic_expressions_in_the_result_expr() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "f".parse().unwrap() => ColumnType::Int128,
+            "b".parse().unwrap() => ColumnType::BigInt,
+            "h".parse().unwrap() => ColumnType::Int128,
+        },
+    );
+    // TODO: add `a / b as a_div_b` result expr once polars properly
+    // supports decimal division without panicking in production
+    let ast = query_to_provable_ast(
+        t,
+        "select a + b, 2 * f as f2, -77 - h as col, a + f as af from employees",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    add(column(t, "a", &accessor), column(t, "b", &accessor)),
+                    "__expr__",
+                ),
+                aliased_plan(multiply(const_bigint(2), column(t, "f", &accessor)), "f2"),
+                aliased_plan(
+                    subtract(const_bigint(-77), column(t, "h", &accessor)),
+                    "col",
+                ),
+                aliased_plan(
+                    add(column(t, "a", &accessor), column(t, "f", &accessor)),
+                    "af",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_multiple_arithmetic_expression_where_multiplication_has_precedence_in_the_result_expr(
+) {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "c".parse().unwrap() => ColumnType::BigInt,
+            "f".parse().unwrap() => ColumnType::BigInt,
+            "g".parse().unwrap() => ColumnType::BigInt,
+            "h".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select (2 + f) * (c + g + 2 * h), ((h - g) * 2 + c + g) * (f + 2) as d from employees",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(
+                    multiply(
+                        add(const_bigint(2), column(t, "f", &accessor)),
+                        add(
+                            add(column(t, "c", &accessor), column(t, "g", &accessor)),
+                            multiply(const_bigint(2), column(t, "h", &accessor)),
+                        ),
+                    ),
+                    "__expr__",
+                ),
+                aliased_plan(
+                    multiply(
+                        add(
+                            add(
+                                multiply(
+                                    subtract(column(t, "h", &accessor), column(t, "g", &accessor)),
+                                    const_bigint(2),
+                                ),
+                                column(t, "c", &accessor),
+                            ),
+                            column(t, "g", &accessor),
+                        ),
+                        add(column(t, "f", &accessor), const_bigint(2)),
+                    ),
+                    "d",
+                ),
+            ],
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_parse_arithmetic_expression_within_aggregations_in_the_result_expr() {
+    let t = "sxt.employees".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "c".parse().unwrap() => ColumnType::BigInt,
+            "f".parse().unwrap() => ColumnType::BigInt,
+            "g".parse().unwrap() => ColumnType::BigInt,
+            "k".parse().unwrap() => ColumnType::BigInt,
+        },
+    );
+    let ast = query_to_provable_ast(
+        t,
+        "select c, sum(2 * f + c - -7) as d from employees group by c",
+        &accessor,
+    );
+    let expected_ast = QueryExpr::new(
+        group_by_with_min_max(
+            cols_expr_plan(t, &["c"], &accessor),
+            vec![sum_expr(
+                subtract(
+                    add(
+                        multiply(const_bigint(2), column(t, "f", &accessor)),
+                        column(t, "c", &accessor),
+                    ),
+                    const_bigint(-7),
+                ),
+                "d",
+            )],
+            vec![],
+            None,
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_use_non_grouped_columns_outside_agg() {
+    assert!(matches!(
+        query!(select: ["i"], group: ["s"], should_err: true),
+        ConversionError::PostprocessingError {
+            source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
+        }
+    ));
+    assert!(matches!(
+        query!(select: ["sum(i)", "i"], group: ["s"], should_err: true),
+        ConversionError::PostprocessingError {
+            source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
+        }
+    ));
+    assert!(matches!(
+        query!(select: ["min(i) + i"], group: ["s"], should_err: true),
+        ConversionError::PostprocessingError {
+            source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
+        }
+    ));
+    assert!(matches!(
+        query!(select: ["2 * i", "min(i)"], group: ["s"], should_err: true),
+        ConversionError::PostprocessingError {
+            source: PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. }
+        }
+    ));
+    assert!(matches!(
+        query!(select: ["2 * i", "min(i)"], should_err: true),
+        ConversionError::InvalidGroupByColumnRef { .. }
+    ));
+    assert!(matches!(
+        query!(select: ["sum(i)", "i"], should_err: true),
+        ConversionError::InvalidGroupByColumnRef { .. }
+    ));
+    assert!(matches!(
+        query!(select: ["max(i) + 2 * i"], should_err: true),
+        ConversionError::InvalidGroupByColumnRef { .. }
+    ));
+}
+
+#[test]
+fn varchar_column_is_not_compatible_with_integer_column() {
+    assert_eq!(
+        query!(select: ["-123 * s"], should_err: true),
+        ConversionError::DataTypeMismatch {
+            left_type: ColumnType::BigInt.to_string(),
+            right_type: ColumnType::VarChar.to_string()
+        }
+    );
+    assert_eq!(
+        query!(select: ["i - s"], should_err: true),
+        ConversionError::DataTypeMismatch {
+            left_type: ColumnType::BigInt.to_string(),
+            right_type: ColumnType::VarChar.to_string()
+        }
+    );
+    assert_eq!(
+        query!(select: ["s"], filter: "'abc' = i", should_err: true),
+        ConversionError::DataTypeMismatch {
+            left_type: ColumnType::VarChar.to_string(),
+            right_type: ColumnType::BigInt.to_string(),
+        }
+    );
+    assert_eq!(
+        query!(select: ["s"], filter: "'abc' != i", should_err: true),
+        ConversionError::DataTypeMismatch {
+            left_type: ColumnType::VarChar.to_string(),
+            right_type: ColumnType::BigInt.to_string(),
+        }
+    );
+}
+
+#[test]
+fn arithmetic_operations_are_not_allowed_with_varchar_column() {
+    assert_eq!(
+        query!(select: ["s - s1"], should_err: true),
+        ConversionError::DataTypeMismatch {
+            left_type: ColumnType::VarChar.to_string(),
+            right_type: ColumnType::VarChar.to_string()
+        }
+    );
+}
+
+#[test]
+fn varchar_column_is_not_allowed_within_numeric_aggregations() {
+    assert_eq!(
+        query!(select: ["sum(s)"], should_err: true),
+        ConversionError::non_numeric_expr_in_agg("varchar", "sum")
+    );
+    assert_eq!(
+        query!(select: ["max(s)"], should_err: true),
+        ConversionError::non_numeric_expr_in_agg("varchar", "max")
+    );
+    assert_eq!(
+        query!(select: ["min(s)"], should_err: true),
+        ConversionError::non_numeric_expr_in_agg("varchar", "min")
+    );
+}
+
+#[test]
+fn group_by_with_bigint_column_is_valid() {
+    let (t, accessor) = get_test_accessor();
+    let query = query!(select: ["i"], group: ["i"]);
+    let expected_query = QueryExpr::new(
+        group_by_with_min_max(
+            cols_expr_plan(t, &["i"], &accessor),
+            vec![],
+            vec![],
+            None,
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn group_by_with_decimal_column_is_valid() {
+    let (t, accessor) = get_test_accessor();
+    let query = query!(select: ["d"], group: ["d"]);
+    let expected_query = QueryExpr::new(
+        group_by_with_min_max(
+            cols_expr_plan(t, &["d"], &accessor),
+            vec![],
+            vec![],
+            None,
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn group_by_with_varchar_column_is_valid() {
+    let (t, accessor) = get_test_accessor();
+    let query = query!(select: ["s"], group: ["s"]);
+    let expected_query = QueryExpr::new(
+        group_by_with_min_max(
+            cols_expr_plan(t, &["s"], &accessor),
+            vec![],
+            vec![],
+            None,
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn we_can_use_arithmetic_outside_agg_expressions_while_using_group_by() {
+    let (t, accessor) = get_test_accessor();
+    let query = query!(
+        select: ["2 * i + sum(i) - i1"],
+        group: ["i", "i1"]
+    );
+    let expected_query = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["i", "i1"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &["i", "i1"],
+            &[aliased_expr(
+                psub(padd(pmul(lit(2), col("i")), sum(col("i"))), col("i1")),
+                "__expr__",
+            )],
+        )],
+    );
+    assert_eq!(query, expected_query);
+}
+
+#[test]
+fn we_can_use_arithmetic_outside_agg_expressions_without_using_group_by() {
+    let (t, accessor) = get_test_accessor();
+    let ast = query!(
+        select: ["7 + max(i) as max_i", "min(i + 777 * d) * -5 as min_d"],
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["d", "i"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &[],
+            &[
+                aliased_expr(padd(lit(7), max(col("i"))), "max_i"),
+                aliased_expr(
+                    pmul(min(padd(col("i"), pmul(lit(777), col("d")))), lit(-5)),
+                    "min_d",
+                ),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn count_aggregation_always_have_integer_type() {
+    let (t, accessor) = get_test_accessor();
+    let ast = query!(
+        select: ["7 + count(s) as cs", "count(i) * -5 as ci", "count(d)"]
+    );
+    let expected_ast = QueryExpr::new(
+        filter(
+            cols_expr_plan(t, &["d", "i", "s"], &accessor),
+            tab(t),
+            const_bool(true),
+        ),
+        vec![group_by_postprocessing(
+            &[],
+            &[
+                aliased_expr(padd(lit(7), count(col("s"))), "cs"),
+                aliased_expr(pmul(count(col("i")), lit(-5)), "ci"),
+                aliased_expr(count(col("d")), "__count__"),
+            ],
+        )],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn select_wildcard_is_valid_with_group_by_exprs() {
+    let columns = ["s", "i", "d", "s0", "i0", "d0", "s1", "i1", "d1"];
+    let (t, accessor) = get_test_accessor();
+    let ast = query!(
+        select: ["*"],
+        group: columns.clone()
+    );
+    let expected_ast = QueryExpr::new(
+        group_by_with_min_max(
+            cols_expr_plan(t, &columns, &accessor),
+            vec![],
+            vec![],
+            None,
+            tab(t),
+            const_bool(true),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn nested_aggregations_are_not_supported() {
+    let supported_agg = ["max", "min", "sum", "count"];
+    for perm_aggs in supported_agg.iter().permutations(2) {
+        assert_eq!(
+            query!(select: [format!("{}({}(i))", perm_aggs[0], perm_aggs[1])], should_err: true),
+            ConversionError::InvalidExpression {
+                expression: "nested aggregations are not supported".to_string()
+            }
+        );
+    }
+}
+
+#[test]
+fn select_group_and_order_by_preserve_the_column_order_reference() {
+    const N: usize = 4;
+    let (t, accessor) = get_test_accessor();
+    let base_cols: [&str; N] = ["i", "i0", "i1", "s"]; // sorted because of `select: [cols = ... ]`
+    let base_ordering = [Asc, Desc, Asc, Desc];
+    for (idx, perm_cols) in base_cols
+        .into_iter()
+        .permutations(N)
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .enumerate()
+    {
+        let perm_col_plans = perm_cols
+            .iter()
+            .sorted()
+            .map(|c| col_expr_plan(t, c, &accessor))
+            .collect();
+        let aliased_perm_cols = perm_cols
+            .iter()
+            .map(|c| aliased_expr(col(c), c))
+            .collect::<Vec<_>>();
+        let group_cols = perm_cols.clone().into_iter().cycle().skip(1).take(N);
+        let group_cols_vec = group_cols.clone().collect::<Vec<_>>();
+        let order_cols = perm_cols.clone().into_iter().cycle().skip(2).take(N);
+        let order_cols_vec = order_cols.clone().collect::<Vec<_>>();
+        let ordering = base_ordering.into_iter().cycle().skip(idx).take(N);
+        let ordering_vec = ordering.clone().collect::<Vec<_>>();
+        let query = query!(
+            select: perm_cols,
+            group: group_cols.clone(),
+            order: order_cols.clone().zip(ordering.clone()).map(|(c, o)| format!("{c} {o}"))
+        );
+        let expected_query = QueryExpr::new(
+            filter(perm_col_plans, tab(t), const_bool(true)),
+            vec![
+                group_by_postprocessing(&group_cols_vec, &aliased_perm_cols),
+                orders(&order_cols_vec, &ordering_vec),
+            ],
+        );
+        assert_eq!(query, expected_query);
+    }
+}
+
+/// Creates a new [`QueryExpr`], with the given select statement and a sample schema accessor.
+fn query_expr_for_test_table(sql_text: &str) -> QueryExpr<RistrettoPoint> {
+    let schema_accessor = schema_accessor_from_table_ref_with_schema(
+        "test.table".parse().unwrap(),
+        indexmap! {
+            "bigint_column".parse().unwrap() => ColumnType::BigInt,
+            "varchar_column".parse().unwrap() => ColumnType::VarChar,
+            "int128_column".parse().unwrap() => ColumnType::Int128,
+        },
+    );
+    let default_schema = "test".parse().unwrap();
+    let select_statement = SelectStatementParser::new().parse(sql_text).unwrap();
+    QueryExpr::try_new(select_statement, default_schema, &schema_accessor).unwrap()
+}
+
+/// Serializes and deserializes [`QueryExpr`] with flexbuffers and asserts that it remains the same.
+fn assert_query_expr_serializes_to_and_from_flex_buffers(query_expr: &QueryExpr<RistrettoPoint>) {
+    let serialized = flexbuffers::to_vec(query_expr).unwrap();
+    let deserialized: QueryExpr<RistrettoPoint> =
+        flexbuffers::from_slice(serialized.as_slice()).unwrap();
+    assert_eq!(deserialized, *query_expr);
+}
+
+#[test]
+fn basic_query_expr_can_serialize_to_and_from_flex_buffers() {
+    let query_expr = query_expr_for_test_table("select * from table");
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn query_expr_with_selected_columns_can_serialize_to_and_from_flex_buffers() {
+    let query_expr =
+        query_expr_for_test_table("select bigint_column, varchar_column, int128_column from table");
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn query_expr_with_aggregation_can_serialize_to_and_from_flex_buffers() {
+    let query_expr = query_expr_for_test_table("select count(*) from table group by bigint_column");
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn query_expr_with_filters_can_serialize_to_and_from_flex_buffers() {
+    let query_expr = query_expr_for_test_table(
+        "select * from table where bigint_column != 5 and varchar_column = 'example' or int128_column = 10",
+    );
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn query_expr_with_order_and_limits_can_serialize_to_and_from_flex_buffers() {
+    let query_expr = query_expr_for_test_table(
+        "select * from table order by int128_column desc limit 1 offset 1",
+    );
+    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+}
+
+#[test]
+fn we_can_serialize_list_of_filters_from_query_expr() {
+    let query_expr = query_expr_for_test_table("select * from table");
+    let filter_execs = vec![query_expr.proof_expr()];
+    let serialized = flexbuffers::to_vec(&filter_execs).unwrap();
+    let deserialized: Vec<DynProofPlan<RistrettoPoint>> =
+        flexbuffers::from_slice(serialized.as_slice()).unwrap();
+    let deserialized_as_ref: Vec<&DynProofPlan<RistrettoPoint>> = deserialized.iter().collect();
+    assert_eq!(filter_execs.len(), deserialized_as_ref.len());
+    assert_eq!(filter_execs[0], deserialized_as_ref[0]);
+}
+
+/////////////////////////
+/// Join
+/////////////////////////
+fn get_join_test_accessor() -> (TableRef, TableRef, TestSchemaAccessor) {
+    let left = "sxt.users".parse().unwrap();
+    let right = "sxt.orders".parse().unwrap();
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        left => indexmap! {
+            "id".parse().unwrap() => ColumnType::BigInt,
+            "name".parse().unwrap() => ColumnType::VarChar,
+        },
+        right => indexmap! {
+            "id".parse().unwrap() => ColumnType::BigInt,
+            "user_id".parse().unwrap() => ColumnType::BigInt,
+            "amount".parse().unwrap() => ColumnType::Int128,
+            "note".parse().unwrap() => ColumnType::VarChar,
+            "code".parse().unwrap() => ColumnType::Int,
+        },
+    });
+    (left, right, accessor)
+}
+
+fn try_join_query(query: &str) -> Result<QueryExpr<RistrettoPoint>, ConversionError> {
+    let (left, _, accessor) = get_join_test_accessor();
+    let intermediate_ast = SelectStatementParser::new().parse(query).unwrap();
+    QueryExpr::try_new(intermediate_ast, left.schema_id(), &accessor)
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_join() {
+    let (l, r, accessor) = get_join_test_accessor();
+    let expected_ast = QueryExpr::new(
+        join(
+            tab(l),
+            tab(r),
+            ColumnExpr::new(col_ref(l, "id", &accessor)),
+            ColumnExpr::new(col_ref(r, "user_id", &accessor)),
+            vec![
+                aliased_col(l, "name", "name", &accessor),
+                aliased_col(r, "amount", "total", &accessor),
+                aliased_col(r, "id", "order_id", &accessor),
+            ],
+        ),
+        vec![],
+    );
+    let queries = [
+        "select name, amount as total, orders.id as order_id from users join orders on users.id = user_id",
+        "select users.name, orders.amount total, orders.id order_id from sxt.users inner join sxt.orders on orders.user_id = users.id",
+    ];
+    for query in queries {
+        assert_eq!(try_join_query(query).unwrap(), expected_ast);
+    }
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_join_order_by_and_slice() {
+    let (l, r, accessor) = get_join_test_accessor();
+    let ast = try_join_query(
+        "select name, amount from users join orders on users.id = orders.user_id order by amount desc limit 2 offset 1",
+    )
+    .unwrap();
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort(
+                join(
+                    tab(l),
+                    tab(r),
+                    ColumnExpr::new(col_ref(l, "id", &accessor)),
+                    ColumnExpr::new(col_ref(r, "user_id", &accessor)),
+                    vec![
+                        aliased_col(l, "name", "name", &accessor),
+                        aliased_col(r, "amount", "amount", &accessor),
+                    ],
+                ),
+                vec![(1, Desc)],
+            ),
+            1,
+            Some(2),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_ambiguous_or_missing_join_column() {
+    assert!(matches!(
+        try_join_query("select id from users join orders on users.id = orders.user_id"),
+        Err(ConversionError::AmbiguousColumn { .. })
+    ));
+    assert!(matches!(
+        try_join_query("select name from users join orders on id = user_id"),
+        Err(ConversionError::AmbiguousColumn { .. })
+    ));
+    assert!(matches!(
+        try_join_query("select missing from users join orders on users.id = orders.user_id"),
+        Err(ConversionError::MissingColumnWithoutTable { .. })
+    ));
+    assert!(matches!(
+        try_join_query("select users.amount from users join orders on users.id = orders.user_id"),
+        Err(ConversionError::MissingColumn { .. })
+    ));
+    assert!(matches!(
+        try_join_query("select other.name from users join orders on users.id = orders.user_id"),
+        Err(ConversionError::InvalidTableReference { .. })
+    ));
+    assert!(matches!(
+        try_join_query("select * from users join orders on users.id = orders.user_id"),
+        Err(ConversionError::DuplicateResultAlias { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_invalid_join_condition() {
+    assert!(matches!(
+        try_join_query("select name from users join orders on users.id = orders.code"),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    let unprovable_queries = [
+        "select name from users join orders on users.name = orders.note",
+        "select name from users join orders on users.id = users.id",
+        "select name from users join orders on not users.id = orders.user_id",
+        "select name from users join orders on users.id = 1",
+    ];
+    for query in unprovable_queries {
+        assert!(matches!(
+            try_join_query(query),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_unsupported_join() {
+    let unprovable_queries = [
+        "select name from users join orders on users.id = orders.user_id where amount = 1",
+        "select name, count(*) from users join orders on users.id = orders.user_id group by name",
+        "select sum(amount) from users join orders on users.id = orders.user_id",
+        "select amount + 1 from users join orders on users.id = orders.user_id",
+        "select name from users join users on users.id = users.id",
+        "select name from users join orders on users.id = orders.user_id join t on t.a = users.id",
+    ];
+    for query in unprovable_queries {
+        assert!(matches!(
+            try_join_query(query),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+    assert!(matches!(
+        try_join_query(
+            "select name from users join orders on users.id = orders.user_id order by id"
+        ),
+        Err(ConversionError::InvalidOrderBy { .. })
+    ));
+}
+
+#[test]
+fn we_can_convert_an_ast_with_columns_qualified_by_the_table() {
+    let (t, accessor) = get_test_accessor();
+    let ast = query_to_provable_ast(t, "select t.i, s as x from sxt.t where t.d = 3", &accessor);
+    let expected_ast = QueryExpr::new(
+        filter(
+            vec![
+                aliased_plan(column(t, "i", &accessor), "i"),
+                aliased_plan(column(t, "s", &accessor), "x"),
+            ],
+            tab(t),
+            equal(column(t, "d", &accessor), const_bigint(3_i64)),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+    invalid_query_to_provable_ast(t, "select other.i from t", &accessor);
+}
+
+/////////////////////////
+/// Union
+/////////////////////////
+fn get_union_test_accessor() -> (TableRef, TableRef, TestSchemaAccessor) {
+    let t1 = "sxt.tx_2024_01".parse().unwrap();
+    let t2 = "sxt.tx_2024_02".parse().unwrap();
+    let schema = indexmap! {
+        "day".parse().unwrap() => ColumnType::BigInt,
+        "wallet".parse().unwrap() => ColumnType::VarChar,
+        "amount".parse().unwrap() => ColumnType::BigInt,
+    };
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        t1 => schema.clone(),
+        t2 => schema,
+    });
+    (t1, t2, accessor)
+}
+
+fn try_union_query(query: &str) -> Result<QueryExpr<RistrettoPoint>, ConversionError> {
+    let (t1, _, accessor) = get_union_test_accessor();
+    let intermediate_ast = SelectStatementParser::new().parse(query).unwrap();
+    QueryExpr::try_new(intermediate_ast, t1.schema_id(), &accessor)
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_union_order_by_and_slice() {
+    let (t1, t2, accessor) = get_union_test_accessor();
+    let ast = try_union_query(
+        "select wallet, amount from tx_2024_01 where day = 1 \
+            union all select wallet, amount from tx_2024_02 \
+            union all select wallet, day as amount from tx_2024_01 \
+            order by amount desc limit 2 offset 1",
+    )
+    .unwrap();
+    let expected_ast = QueryExpr::new(
+        slice_exec(
+            sort(
+                union_exec(vec![
+                    filter(
+                        cols_expr_plan(t1, &["wallet", "amount"], &accessor),
+                        tab(t1),
+                        equal(column(t1, "day", &accessor), const_bigint(1)),
+                    ),
+                    filter(
+                        cols_expr_plan(t2, &["wallet", "amount"], &accessor),
+                        tab(t2),
+                        const_bool(true),
+                    ),
+                    filter(
+                        vec![
+                            col_expr_plan(t1, "wallet", &accessor),
+                            aliased_col_expr_plan(t1, "day", "amount", &accessor),
+                        ],
+                        tab(t1),
+                        const_bool(true),
+                    ),
+                ]),
+                vec![(1, Desc)],
+            ),
+            1,
+            Some(2),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_union_of_group_bys() {
+    let (t1, t2, accessor) = get_union_test_accessor();
+    let ast = try_union_query(
+        "select wallet, sum(amount) as total, count(*) as num from tx_2024_01 group by wallet \
+            union all select wallet, sum(amount) as total, count(*) as num from tx_2024_02 \
+            group by wallet order by total desc limit 3",
+    )
+    .unwrap();
+    let group_by_wallet = |t| {
+        group_by(
+            cols_expr_plan(t, &["wallet"], &accessor),
+            vec![sum_expr(column(t, "amount", &accessor), "total")],
+            "num",
+            tab(t),
+            const_bool(true),
+        )
+    };
+    // The groups of a `GroupByExec` are verified with the query result, so the union is not sorted provably
+    let expected_ast = QueryExpr::new(
+        union_exec(vec![group_by_wallet(t1), group_by_wallet(t2)]),
+        vec![orders(&["total"], &[Desc]), slice(Some(3), Some(0))],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_a_union_of_different_result_types() {
+    let mismatched_queries = [
+        "select wallet, amount from tx_2024_01 union all select amount, wallet from tx_2024_02",
+        "select wallet from tx_2024_01 union all select wallet, amount from tx_2024_02",
+        "select amount from tx_2024_01 union all select amount from tx_2024_02 \
+            union all select wallet from tx_2024_01",
+    ];
+    for query in mismatched_queries {
+        assert!(matches!(
+            try_union_query(query),
+            Err(ConversionError::UnionTypeMismatch { .. })
+        ));
+    }
+    assert!(matches!(
+        try_union_query(
+            "select wallet as w from tx_2024_01 union all select wallet from tx_2024_02 order by wallet"
+        ),
+        Err(ConversionError::InvalidOrderBy { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_a_union_of_queries_that_need_postprocessing() {
+    let unprovable_queries = [
+        "select amount from tx_2024_01 union all select distinct count(*) as amount from tx_2024_02 group by wallet",
+        "select amount from tx_2024_01 union all select sum(amount) * 2 as amount from tx_2024_02 group by wallet",
+        "select amount from tx_2024_01 union all select count(distinct wallet) as amount from tx_2024_02",
+        "select wallet, count(*) as n from tx_2024_01 group by wallet having count(*) > 1 \
+            union all select wallet, count(*) as n from tx_2024_02 group by wallet",
+    ];
+    for query in unprovable_queries {
+        assert!(matches!(
+            try_union_query(query),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+}
+
+/////////////////////////
+/// Subqueries
+/////////////////////////
+fn derived_column(
+    tab: TableRef,
+    name: &str,
+    column_type: ColumnType,
+) -> DynProofExpr<RistrettoPoint> {
+    DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+        tab,
+        name.parse().unwrap(),
+        column_type,
+    )))
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_filter_over_a_group_by_subquery() {
+    let (t1, _, accessor) = get_union_test_accessor();
+    let d = "sxt.d".parse().unwrap();
+    let ast = try_union_query(
+        "select day, total from (select day, sum(amount) as total, count(*) as n \
+            from tx_2024_01 group by day) as d where n >= 2 order by total desc",
+    )
+    .unwrap();
+    // The derived table is sorted by its outer plan
+    let expected_ast = QueryExpr::new(
+        derived_table(
+            group_by(
+                cols_expr_plan(t1, &["day"], &accessor),
+                vec![sum_expr(column(t1, "amount", &accessor), "total")],
+                "n",
+                tab(t1),
+                const_bool(true),
+            ),
+            d,
+            sort(
+                filter(
+                    vec![
+                        aliased_plan(derived_column(d, "day", ColumnType::BigInt), "day"),
+                        aliased_plan(derived_column(d, "total", ColumnType::BigInt), "total"),
+                    ],
+                    tab(d),
+                    gte(derived_column(d, "n", ColumnType::BigInt), const_bigint(2)),
+                ),
+                vec![(1, Desc)],
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_group_by_over_a_subquery_of_a_union() {
+    let (t1, t2, accessor) = get_union_test_accessor();
+    let d = "sxt.d".parse().unwrap();
+    let ast = try_union_query(
+        "select wallet, count(*) as n from (select wallet from tx_2024_01 \
+            union all select wallet from tx_2024_02) d group by wallet",
+    )
+    .unwrap();
+    let expected_ast = QueryExpr::new(
+        derived_table(
+            union_exec(vec![
+                filter(
+                    cols_expr_plan(t1, &["wallet"], &accessor),
+                    tab(t1),
+                    const_bool(true),
+                ),
+                filter(
+                    cols_expr_plan(t2, &["wallet"], &accessor),
+                    tab(t2),
+                    const_bool(true),
+                ),
+            ]),
+            d,
+            group_by(
+                vec![aliased_plan(
+                    derived_column(d, "wallet", ColumnType::VarChar),
+                    "wallet",
+                )],
+                vec![],
+                "n",
+                tab(d),
+                const_bool(true),
+            ),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_cannot_convert_an_ast_with_an_unsupported_subquery() {
+    let unprovable_queries = [
+        // The groups of a string key can't be proven to be distinct
+        "select n from (select wallet, count(*) as n from tx_2024_01 group by wallet) as d",
+        "select w from (select wallet as w, count(*) as n from tx_2024_01 group by wallet \
+            union all select wallet as w, count(*) as n from tx_2024_02 group by wallet) as d",
+        "select total from (select day, sum(amount) * 2 as total from tx_2024_01 group by day) as d",
+    ];
+    for query in unprovable_queries {
+        assert!(matches!(
+            try_union_query(query),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+    assert!(try_union_query("select amount from (select day from tx_2024_01) as d").is_err());
+}
+
+/////////////////////////
+/// Common table expressions
+/////////////////////////
+#[test]
+fn we_can_convert_an_ast_with_a_common_table_expression_referenced_twice() {
+    let (t1, _, accessor) = get_union_test_accessor();
+    let big = "sxt.big".parse().unwrap();
+    let ast = try_union_query(
+        "with big as (select wallet, amount from tx_2024_01 where amount >= 10) \
+            select wallet, amount from big where amount = 20 union all select * from big",
+    )
+    .unwrap();
+    let big_cols = || {
+        vec![
+            aliased_plan(derived_column(big, "wallet", ColumnType::VarChar), "wallet"),
+            aliased_plan(derived_column(big, "amount", ColumnType::BigInt), "amount"),
+        ]
+    };
+    // The common table expression is proven once
+    let expected_ast = QueryExpr::new(
+        derived_table(
+            filter(
+                cols_expr_plan(t1, &["wallet", "amount"], &accessor),
+                tab(t1),
+                gte(column(t1, "amount", &accessor), const_bigint(10)),
+            ),
+            big,
+            union_exec(vec![
+                filter(
+                    big_cols(),
+                    tab(big),
+                    equal(
+                        derived_column(big, "amount", ColumnType::BigInt),
+                        const_bigint(20),
+                    ),
+                ),
+                filter(big_cols(), tab(big), const_bool(true)),
+            ]),
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
+}
+
+#[test]
+fn we_can_convert_an_ast_with_a_common_table_expression_referencing_another_one() {
+    let (t1, _, accessor) = get_union_test_accessor();
+    let daily = "sxt.daily".parse().unwrap();
+    let busy = "sxt.busy".parse().unwrap();
+    let ast = try_union_query(
+        "with daily as (select day, sum(amount) as total, count(*) as n from tx_2024_01 group by day), \
+            busy as (select day, total from daily where n >= 2) \
+            select total from busy order by total desc limit 1",
+    )
+    .unwrap();
+    let expected_ast = QueryExpr::new(
+        derived_table(
+            group_by(
+                cols_expr_plan(t1, &["day"], &accessor),
+                vec![sum_expr(column(t1, "amount", &accessor), "total")],
+                "n",
+                tab(t1),
+                const_bool(true),
+            ),
+            daily,
+            derived_table(
+                filter(
+                    vec![
+                        aliased_plan(derived_column(daily, "day", ColumnType::BigInt), "day"),
+                        aliased_plan(derived_column(daily, "total", ColumnType::BigInt), "total"),
+                    ],
+                    tab(daily),
+                    gte(
+                        derived_column(daily, "n", ColumnType::BigInt),
+                        const_bigint(2),
+                    ),
+                ),
+                busy,
+                slice_exec(
+                    sort(
+                        filter(
+                            vec![aliased_plan(
+                                derived_column(busy, "total", ColumnType::BigInt),
+                                "total",
+                            )],
+                            tab(busy),
+                            const_bool(true),
+                        ),
+                        vec![(0, Desc)],
+                    ),
+                    0,
+                    Some(1),
+                ),
+            ),
+        ),
+        vec![],
     );
-    assert_eq!(query, expected_query);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_use_arithmetic_outside_agg_expressions_while_using_group_by() {
-    let (t, accessor) = get_test_accessor();
-    let query = query!(
-        select: ["2 * i + sum(i) - i1"],
-        group: ["i", "i1"]
-    );
-    let expected_query = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["i", "i1"], &accessor),
-            tab(t),
-            const_bool(true),
+fn we_cannot_convert_an_ast_with_invalid_common_table_expressions() {
+    assert!(matches!(
+        try_union_query(
+            "with d as (select amount from tx_2024_01), d as (select amount from tx_2024_02) \
+                select amount from d"
         ),
-        vec![
-            group_by_postprocessing(
-                &["i", "i1"],
-                &[aliased_expr(
-                    psub(padd(pmul(lit(2), col("i")), sum(col("i"))), col("i1")),
-                    "__expr__",
-                )],
-            ),
-            select_expr(&[aliased_expr(
-                psub(padd(pmul(lit(2), col("i")), col("__col_agg_0")), col("i1")),
-                "__expr__",
-            )]),
-        ],
+        Err(ConversionError::DuplicateCommonTableExpression { .. })
+    ));
+    let unprovable_queries = [
+        "with d as (select wallet, count(*) as n from tx_2024_01 group by wallet) select n from d",
+        "with d as (select day, amount from tx_2024_01) \
+            select d.amount from d join tx_2024_02 on d.day = tx_2024_02.day",
+    ];
+    for query in unprovable_queries {
+        assert!(matches!(
+            try_union_query(query),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+}
+
+fn get_window_test_accessor() -> (TableRef, TestSchemaAccessor) {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = schema_accessor_from_table_ref_with_schema(
+        t,
+        indexmap! {
+            "a".parse().unwrap() => ColumnType::BigInt,
+            "b".parse().unwrap() => ColumnType::BigInt,
+            "c".parse().unwrap() => ColumnType::BigInt,
+            "s".parse().unwrap() => ColumnType::VarChar,
+        },
     );
-    assert_eq!(query, expected_query);
+    (t, accessor)
 }
 
 #[test]
-fn we_can_use_arithmetic_outside_agg_expressions_without_using_group_by() {
-    let (t, accessor) = get_test_accessor();
-    let ast = query!(
-        select: ["7 + max(i) as max_i", "min(i + 777 * d) * -5 as min_d"],
+fn we_can_convert_an_ast_with_a_running_sum() {
+    let (t, accessor) = get_window_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select c, a, b, sum(b) over (partition by c order by a desc) as total from sxt_tab",
+        &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["d", "i"], &accessor),
-            tab(t),
-            const_bool(true),
-        ),
-        vec![
-            group_by_postprocessing(
-                &[],
-                &[
-                    aliased_expr(padd(lit(7), max(col("i"))), "max_i"),
-                    aliased_expr(
-                        pmul(min(padd(col("i"), pmul(lit(777), col("d")))), lit(-5)),
-                        "min_d",
-                    ),
-                ],
+        running_sum(
+            sort(
+                filter(
+                    cols_expr_plan(t, &["c", "a", "b"], &accessor),
+                    tab(t),
+                    const_bool(true),
+                ),
+                vec![(0, Asc), (1, Desc)],
             ),
-            select_expr(&[
-                aliased_expr(padd(lit(7), col("__col_agg_0")), "max_i"),
-                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "min_d"),
-            ]),
-        ],
+            vec![0],
+            vec![1],
+            &[(2, "total")],
+        ),
+        vec![],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn count_aggregation_always_have_integer_type() {
-    let (t, accessor) = get_test_accessor();
-    let ast = query!(
-        select: ["7 + count(s) as cs", "count(i) * -5 as ci", "count(d)"]
+fn we_can_convert_an_ast_with_running_sums_of_columns_that_are_not_results() {
+    let (t, accessor) = get_window_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select sum(b) over (partition by c order by a) as total, a from sxt_tab \
+            where a >= 0 order by total limit 2",
+        &accessor,
     );
     let expected_ast = QueryExpr::new(
-        filter(
-            cols_expr_plan(t, &["d", "i", "s"], &accessor),
-            tab(t),
-            const_bool(true),
+        running_sum(
+            sort(
+                filter(
+                    cols_expr_plan(t, &["a", "c", "b"], &accessor),
+                    tab(t),
+                    gte(column(t, "a", &accessor), const_bigint(0)),
+                ),
+                vec![(1, Asc), (0, Asc)],
+            ),
+            vec![1],
+            vec![0],
+            &[(2, "total")],
         ),
         vec![
-            group_by_postprocessing(
-                &[],
-                &[
-                    aliased_expr(padd(lit(7), count(col("s"))), "cs"),
-                    aliased_expr(pmul(count(col("i")), lit(-5)), "ci"),
-                    aliased_expr(count(col("d")), "__count__"),
-                ],
-            ),
             select_expr(&[
-                aliased_expr(padd(lit(7), col("__col_agg_0")), "cs"),
-                aliased_expr(pmul(col("__col_agg_1"), lit(-5)), "ci"),
-                aliased_expr(col("__col_agg_2"), "__count__"),
+                aliased_expr(col("total"), "total"),
+                aliased_expr(col("a"), "a"),
             ]),
+            orders(&["total"], &[Asc]),
+            slice(Some(2), Some(0)),
         ],
     );
     assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn select_wildcard_is_valid_with_group_by_exprs() {
-    let columns = ["s", "i", "d", "s0", "i0", "d0", "s1", "i1", "d1"];
-    let sorted_columns = columns.iter().sorted().collect::<Vec<_>>();
-    let aliased_exprs = columns
-        .iter()
-        .map(|c| aliased_expr(col(c), c))
-        .collect::<Vec<_>>();
-    let (t, accessor) = get_test_accessor();
-    let ast = query!(
-        select: ["*"],
-        group: columns.clone()
+fn we_can_convert_an_ast_with_window_functions_that_are_not_provable() {
+    let (t, accessor) = get_window_test_accessor();
+    let ast = query_to_provable_ast(
+        t,
+        "select s, row_number() over (order by a) + 1 as r from sxt_tab",
+        &accessor,
     );
     let expected_ast = QueryExpr::new(
         filter(
-            sorted_columns
-                .iter()
-                .map(|c| col_expr_plan(t, c, &accessor))
-                .collect(),
+            cols_expr_plan(t, &["s", "a"], &accessor),
             tab(t),
             const_bool(true),
         ),
-        vec![group_by_postprocessing(&columns, &aliased_exprs)],
+        vec![OwnedTablePostprocessing::new_window(
+            WindowPostprocessing::new(vec![
+                aliased_expr(col("s"), "s"),
+                aliased_expr(
+                    padd(
+                        window(WindowFunction::RowNumber, &[], order("a", Asc)),
+                        lit(1),
+                    ),
+                    "r",
+                ),
+            ]),
+        )],
     );
     assert_eq!(ast, expected_ast);
-}
 
-#[test]
-fn nested_aggregations_are_not_supported() {
-    let supported_agg = ["max", "min", "sum", "count"];
-    for perm_aggs in supported_agg.iter().permutations(2) {
-        assert_eq!(
-            query!(select: [format!("{}({}(i))", perm_aggs[0], perm_aggs[1])], should_err: true),
-            ConversionError::InvalidExpression {
-                expression: "nested aggregations are not supported".to_string()
-            }
-        );
+    // Sums of different windows, sums of expressions and unsortable keys are not provable
+    for query in [
+        "select sum(b) over (order by a) as x, sum(b) over (order by c) as y from sxt_tab",
+        "select sum(a + b) over (order by c) as x from sxt_tab",
+        "select sum(b) over (partition by s) as x from sxt_tab",
+    ] {
+        let ast = query_to_provable_ast(t, query, &accessor);
+        assert!(matches!(
+            ast.postprocessing()[..],
+            [OwnedTablePostprocessing::Window(_)]
+        ));
     }
 }
 
 #[test]
-fn select_group_and_order_by_preserve_the_column_order_reference() {
-    const N: usize = 4;
-    let (t, accessor) = get_test_accessor();
-    let base_cols: [&str; N] = ["i", "i0", "i1", "s"]; // sorted because of `select: [cols = ... ]`
-    let base_ordering = [Asc, Desc, Asc, Desc];
-    for (idx, perm_cols) in base_cols
-        .into_iter()
-        .permutations(N)
-        .collect::<IndexSet<_>>()
-        .into_iter()
-        .enumerate()
-    {
-        let perm_col_plans = perm_cols
-            .iter()
-            .sorted()
-            .map(|c| col_expr_plan(t, c, &accessor))
-            .collect();
-        let aliased_perm_cols = perm_cols
-            .iter()
-            .map(|c| aliased_expr(col(c), c))
-            .collect::<Vec<_>>();
-        let group_cols = perm_cols.clone().into_iter().cycle().skip(1).take(N);
-        let group_cols_vec = group_cols.clone().collect::<Vec<_>>();
-        let order_cols = perm_cols.clone().into_iter().cycle().skip(2).take(N);
-        let order_cols_vec = order_cols.clone().collect::<Vec<_>>();
-        let ordering = base_ordering.into_iter().cycle().skip(idx).take(N);
-        let ordering_vec = ordering.clone().collect::<Vec<_>>();
-        let query = query!(
-            select: perm_cols,
-            group: group_cols.clone(),
-            order: order_cols.clone().zip(ordering.clone()).map(|(c, o)| format!("{c} {o}"))
-        );
-        let expected_query = QueryExpr::new(
-            filter(perm_col_plans, tab(t), const_bool(true)),
-            vec![
-                group_by_postprocessing(&group_cols_vec, &aliased_perm_cols),
-                orders(&order_cols_vec, &ordering_vec),
-            ],
-        );
-        assert_eq!(query, expected_query);
+fn we_cannot_convert_an_ast_with_invalid_window_functions() {
+    let (t, accessor) = get_window_test_accessor();
+    for query in [
+        "select a from sxt_tab where row_number() over (order by a) = 1",
+        "select sum(row_number() over (order by a)) as x from sxt_tab",
+        "select sum(sum(b) over (order by a)) over (order by a) as x from sxt_tab",
+        "select a, rank() over (order by b) as r from sxt_tab group by a",
+        "select sum(s) over (order by a) as x from sxt_tab",
+        "select rank() over (order by d) as r from sxt_tab",
+    ] {
+        invalid_query_to_provable_ast(t, query, &accessor);
     }
-}
-
-/// Creates a new [`QueryExpr`], with the given select statement and a sample schema accessor.
-fn query_expr_for_test_table(sql_text: &str) -> QueryExpr<RistrettoPoint> {
-    let schema_accessor = schema_accessor_from_table_ref_with_schema(
-        "test.table".parse().unwrap(),
-        indexmap! {
-            "bigint_column".parse().unwrap() => ColumnType::BigInt,
-            "varchar_column".parse().unwrap() => ColumnType::VarChar,
-            "int128_column".parse().unwrap() => ColumnType::Int128,
-        },
+    let (t, accessor) = get_nullable_test_accessor();
+    invalid_query_to_provable_ast(
+        t,
+        "select row_number() over (partition by b) as r from sxt_tab",
+        &accessor,
     );
-    let default_schema = "test".parse().unwrap();
-    let select_statement = SelectStatementParser::new().parse(sql_text).unwrap();
-    QueryExpr::try_new(select_statement, default_schema, &schema_accessor).unwrap()
-}
-
-/// Serializes and deserializes [`QueryExpr`] with flexbuffers and asserts that it remains the same.
-fn assert_query_expr_serializes_to_and_from_flex_buffers(query_expr: &QueryExpr<RistrettoPoint>) {
-    let serialized = flexbuffers::to_vec(query_expr).unwrap();
-    let deserialized: QueryExpr<RistrettoPoint> =
-        flexbuffers::from_slice(serialized.as_slice()).unwrap();
-    assert_eq!(deserialized, *query_expr);
 }
 
-#[test]
-fn basic_query_expr_can_serialize_to_and_from_flex_buffers() {
-    let query_expr = query_expr_for_test_table("select * from table");
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
-}
-
-#[test]
-fn query_expr_with_selected_columns_can_serialize_to_and_from_flex_buffers() {
-    let query_expr =
-        query_expr_for_test_table("select bigint_column, varchar_column, int128_column from table");
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
-}
-
-#[test]
-fn query_expr_with_aggregation_can_serialize_to_and_from_flex_buffers() {
-    let query_expr = query_expr_for_test_table("select count(*) from table group by bigint_column");
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+/////////////////////////
+/// Subqueries in WHERE clauses
+/////////////////////////
+fn subquery_column(tab: TableRef, index: usize) -> DynProofExpr<RistrettoPoint> {
+    derived_column(tab, &format!("__col_subquery_{index}"), ColumnType::Boolean)
+}
+
+/// The value of a scalar subquery, which is false when compared to the subquery if it is null
+fn scalar_subquery_column(
+    tab: TableRef,
+    index: usize,
+) -> (DynProofExpr<RistrettoPoint>, DynProofExpr<RistrettoPoint>) {
+    let alias = format!("__col_subquery_{index}");
+    (
+        derived_column(tab, &alias, ColumnType::BigInt),
+        derived_column(tab, &format!("__{alias}_presence__"), ColumnType::Boolean),
+    )
+}
+
+#[test]
+fn we_can_convert_an_ast_with_an_in_subquery() {
+    let (t1, t2, accessor) = get_union_test_accessor();
+    let ast = try_union_query(
+        "select wallet from tx_2024_01 \
+            where day in (select day from tx_2024_02 where amount >= 10)",
+    )
+    .unwrap();
+    let expected_ast = QueryExpr::new(
+        semi_join(
+            filter(
+                cols_expr_plan(t2, &["day"], &accessor),
+                tab(t2),
+                gte(column(t2, "amount", &accessor), const_bigint(10)),
+            ),
+            "day",
+            t1,
+            "__col_subquery_0",
+            filter(
+                cols_expr_plan(t1, &["wallet"], &accessor),
+                tab(t1),
+                subquery_column(t1, 0),
+            ),
+            &accessor,
+        ),
+        vec![],
+    );
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn query_expr_with_filters_can_serialize_to_and_from_flex_buffers() {
-    let query_expr = query_expr_for_test_table(
-        "select * from table where bigint_column != 5 and varchar_column = 'example' or int128_column = 10",
+fn we_can_convert_an_ast_with_a_scalar_subquery() {
+    let (t1, t2, accessor) = get_union_test_accessor();
+    let ast = try_union_query(
+        "select wallet from tx_2024_01 \
+            where amount >= (select max(amount) as m from tx_2024_02)",
+    )
+    .unwrap();
+    let (value, presence) = scalar_subquery_column(t1, 0);
+    let expected_ast = QueryExpr::new(
+        scalar_subquery(
+            group_by_with_min_max(
+                vec![],
+                vec![],
+                vec![(
+                    AggregationOperator::Max,
+                    aliased_plan(column(t2, "amount", &accessor), "m"),
+                )],
+                None,
+                tab(t2),
+                const_bool(true),
+            ),
+            t1,
+            "__col_subquery_0",
+            filter(
+                cols_expr_plan(t1, &["wallet"], &accessor),
+                tab(t1),
+                and(gte(column(t1, "amount", &accessor), value), presence),
+            ),
+        ),
+        vec![],
     );
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn query_expr_with_order_and_limits_can_serialize_to_and_from_flex_buffers() {
-    let query_expr = query_expr_for_test_table(
-        "select * from table order by int128_column desc limit 1 offset 1",
+fn we_can_convert_an_ast_with_several_subqueries_in_the_where_clause() {
+    let (t1, t2, accessor) = get_union_test_accessor();
+    let ast = try_union_query(
+        "select day, count(*) as n from tx_2024_01 \
+            where tx_2024_01.day not in (select day from tx_2024_02) \
+            and amount >= (select sum(amount) as total from tx_2024_02) group by day",
+    )
+    .unwrap();
+    let (value, presence) = scalar_subquery_column(t1, 1);
+    // The first subquery is the outermost one
+    let expected_ast = QueryExpr::new(
+        semi_join(
+            filter(
+                cols_expr_plan(t2, &["day"], &accessor),
+                tab(t2),
+                const_bool(true),
+            ),
+            "day",
+            t1,
+            "__col_subquery_0",
+            scalar_subquery(
+                group_by_with_min_max(
+                    vec![],
+                    vec![sum_expr(column(t2, "amount", &accessor), "total")],
+                    vec![],
+                    None,
+                    tab(t2),
+                    const_bool(true),
+                ),
+                t1,
+                "__col_subquery_1",
+                group_by(
+                    cols_expr_plan(t1, &["day"], &accessor),
+                    vec![],
+                    "n",
+                    tab(t1),
+                    and(
+                        not(subquery_column(t1, 0)),
+                        and(gte(column(t1, "amount", &accessor), value), presence),
+                    ),
+                ),
+            ),
+            &accessor,
+        ),
+        vec![],
     );
-    assert_query_expr_serializes_to_and_from_flex_buffers(&query_expr);
+    assert_eq!(ast, expected_ast);
 }
 
 #[test]
-fn we_can_serialize_list_of_filters_from_query_expr() {
-    let query_expr = query_expr_for_test_table("select * from table");
-    let filter_execs = vec![query_expr.proof_expr()];
-    let serialized = flexbuffers::to_vec(&filter_execs).unwrap();
-    let deserialized: Vec<DynProofPlan<RistrettoPoint>> =
-        flexbuffers::from_slice(serialized.as_slice()).unwrap();
-    let deserialized_as_ref: Vec<&DynProofPlan<RistrettoPoint>> = deserialized.iter().collect();
-    assert_eq!(filter_execs.len(), deserialized_as_ref.len());
-    assert_eq!(filter_execs[0], deserialized_as_ref[0]);
+fn we_cannot_convert_an_ast_with_invalid_subqueries_in_the_where_clause() {
+    let invalid_queries = [
+        "select day from tx_2024_01 where day in (select day, amount from tx_2024_02)",
+        "select day, (select max(amount) as m from tx_2024_02) as m from tx_2024_01",
+        "select day from tx_2024_01 group by day \
+            having count(*) >= (select count(*) as n from tx_2024_02)",
+    ];
+    for query in invalid_queries {
+        assert!(matches!(
+            try_union_query(query),
+            Err(ConversionError::InvalidExpression { .. })
+        ));
+    }
+    assert!(matches!(
+        try_union_query("select day from tx_2024_01 where day in (select wallet from tx_2024_02)"),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+    let unprovable_queries = [
+        (
+            "select day from tx_2024_01 where wallet in (select wallet from tx_2024_02)",
+            "IN subqueries are only supported on keys of the same integer or timestamp type",
+        ),
+        (
+            "select day from tx_2024_01 where day + 1 in (select day from tx_2024_02)",
+            "the key of an IN subquery must be a column",
+        ),
+        (
+            "select day from tx_2024_01 where wallet = (select wallet from tx_2024_02)",
+            "scalar subqueries of strings are not supported",
+        ),
+        (
+            "select day from tx_2024_01 \
+                where amount >= (select sum(amount) * 2 as total from tx_2024_02)",
+            "a subquery must be provable without postprocessing, \
+                so it can't apply arithmetic to its aggregates",
+        ),
+    ];
+    for (query, expected_error) in unprovable_queries {
+        match try_union_query(query) {
+            Err(ConversionError::Unprovable { error }) => assert_eq!(error, expected_error),
+            result => panic!("{query} should be unprovable, got {result:?}"),
+        }
+    }
+
+    // AVG is not supported, so the average has to be compared through its sum and count
+    let error = "select day from tx_2024_01 where amount > (select avg(amount) from tx_2024_02)"
+        .parse::<SelectStatement>()
+        .unwrap_err();
+    assert!(matches!(error, ParseError::QueryParseError { .. }));
+    assert!(try_union_query(
+        "select day from tx_2024_01 where amount * (select count(amount) as n from tx_2024_02) \
+            > (select sum(amount) as total from tx_2024_02)"
+    )
+    .is_ok());
 }
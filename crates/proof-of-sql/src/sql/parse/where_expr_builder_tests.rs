@@ -5,8 +5,8 @@ use crate::{
         math::decimal::Precision,
     },
     sql::{
-        parse::{ConversionError, QueryExpr, WhereExprBuilder},
-        proof_exprs::{ColumnExpr, DynProofExpr, LiteralExpr},
+        parse::{ConversionError, DynProofExprBuilder, QueryExpr, WhereExprBuilder},
+        proof_exprs::{test_utility::const_varchar, ColumnExpr, DynProofExpr, LiteralExpr},
     },
 };
 use core::str::FromStr;
@@ -192,6 +192,148 @@ fn we_can_directly_check_whether_varchar_columns_eq_varchar() {
     assert!(result.is_ok());
 }
 
+#[test]
+fn we_can_directly_check_whether_varchar_columns_are_like_a_pattern() {
+    let column_mapping = get_column_mappings_for_testing();
+    let expr = not(like(col("varchar_column"), lit("test%")));
+    let builder = WhereExprBuilder::new(&column_mapping);
+    let actual = builder
+        .build::<RistrettoPoint>(Some(expr))
+        .unwrap()
+        .unwrap();
+    let expected = DynProofExpr::try_new_not(
+        DynProofExpr::try_new_like(
+            DynProofExpr::Column(ColumnExpr::new(
+                *column_mapping.get(&ident("varchar_column")).unwrap(),
+            )),
+            "test%".into(),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn we_cannot_prove_like_with_a_pattern_that_is_not_a_literal() {
+    let column_mapping = get_column_mappings_for_testing();
+    let expr = like(col("varchar_column"), col("varchar_column"));
+    let builder = WhereExprBuilder::new(&column_mapping);
+    assert!(matches!(
+        builder.build::<RistrettoPoint>(Some(expr)),
+        Err(ConversionError::Unprovable { .. })
+    ));
+}
+
+#[test]
+fn we_can_directly_check_whether_bigint_columns_are_in_a_list_or_between_bounds() {
+    let column_mapping = get_column_mappings_for_testing();
+    let column = || {
+        DynProofExpr::Column(ColumnExpr::new(
+            *column_mapping.get(&ident("bigint_column")).unwrap(),
+        ))
+    };
+    let builder = WhereExprBuilder::new(&column_mapping);
+    let actual = builder
+        .build::<RistrettoPoint>(Some(in_list(
+            col("bigint_column"),
+            vec![lit(3_i64), lit(-1_i64)],
+        )))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        actual,
+        DynProofExpr::try_new_in_list(column(), vec![3, -1]).unwrap()
+    );
+    let builder = WhereExprBuilder::new(&column_mapping);
+    let actual = builder
+        .build::<RistrettoPoint>(Some(between(col("bigint_column"), lit(-1_i64), lit(3_i64))))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        actual,
+        DynProofExpr::try_new_between(column(), -1, 3).unwrap()
+    );
+}
+
+#[test]
+fn we_can_check_in_and_between_of_other_types_with_comparisons() {
+    let column_mapping = get_column_mappings_for_testing();
+    let column = |name: &str| {
+        DynProofExpr::Column(ColumnExpr::new(*column_mapping.get(&ident(name)).unwrap()))
+    };
+    let builder = WhereExprBuilder::new(&column_mapping);
+    let actual = builder
+        .build::<RistrettoPoint>(Some(in_list(
+            col("varchar_column"),
+            vec![lit("a"), lit("b")],
+        )))
+        .unwrap()
+        .unwrap();
+    let expected = DynProofExpr::try_new_or(
+        DynProofExpr::try_new_equals(column("varchar_column"), const_varchar("a")).unwrap(),
+        DynProofExpr::try_new_equals(column("varchar_column"), const_varchar("b")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(actual, expected);
+
+    // Bounds that are not literals, or an empty range
+    for (low, high) in [
+        (col("bigint_column"), lit(3_i64)),
+        (lit(3_i64), lit(-1_i64)),
+    ] {
+        let builder = WhereExprBuilder::new(&column_mapping);
+        let actual = builder
+            .build::<RistrettoPoint>(Some(between(
+                col("bigint_column"),
+                low.clone(),
+                high.clone(),
+            )))
+            .unwrap()
+            .unwrap();
+        let dyn_builder = DynProofExprBuilder::new(&column_mapping);
+        let expected = DynProofExpr::try_new_and(
+            DynProofExpr::try_new_inequality(
+                column("bigint_column"),
+                dyn_builder.build(&low).unwrap(),
+                false,
+            )
+            .unwrap(),
+            DynProofExpr::try_new_inequality(
+                column("bigint_column"),
+                dyn_builder.build(&high).unwrap(),
+                true,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn we_expect_an_error_while_trying_to_check_a_varchar_column_in_or_between_integers() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        t => indexmap! {
+            "b".parse().unwrap() => ColumnType::VarChar,
+        },
+    });
+    for query in [
+        "select * from sxt_tab where b in ('a', 1)",
+        "select * from sxt_tab where b between 'a' and 1",
+    ] {
+        assert!(matches!(
+            QueryExpr::<RistrettoPoint>::try_new(
+                SelectStatement::from_str(query).unwrap(),
+                t.schema_id(),
+                &accessor,
+            ),
+            Err(ConversionError::DataTypeMismatch { .. })
+        ));
+    }
+}
+
 #[test]
 fn we_can_check_non_decimal_columns_eq_integer_literals() {
     let column_mapping = get_column_mappings_for_testing();
@@ -340,6 +482,25 @@ fn we_expect_an_error_while_trying_to_check_varchar_column_ge_decimal() {
     ));
 }
 
+#[test]
+fn we_expect_an_error_while_trying_to_check_bigint_column_like_varchar() {
+    let t = "sxt.sxt_tab".parse().unwrap();
+    let accessor = TestSchemaAccessor::new(indexmap! {
+        t => indexmap! {
+            "b".parse().unwrap() => ColumnType::BigInt,
+        },
+    });
+
+    assert!(matches!(
+        QueryExpr::<RistrettoPoint>::try_new(
+            SelectStatement::from_str("select * from sxt_tab where b like '1%'").unwrap(),
+            t.schema_id(),
+            &accessor,
+        ),
+        Err(ConversionError::DataTypeMismatch { .. })
+    ));
+}
+
 #[test]
 fn we_do_not_expect_an_error_while_trying_to_check_int128_column_eq_decimal_with_zero_scale() {
     let t = "sxt.sxt_tab".parse().unwrap();
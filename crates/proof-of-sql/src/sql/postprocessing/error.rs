@@ -1,3 +1,4 @@
+use crate::base::database::ColumnType;
 use alloc::string::String;
 use proof_of_sql_parser::Identifier;
 use snafu::Snafu;
@@ -47,6 +48,12 @@ pub enum PostprocessingError {
         /// The underlying source error
         source: crate::base::database::OwnedColumnError,
     },
+    /// The expression of a filter such as `HAVING` is not boolean
+    #[snafu(display("Filter expression must be boolean, not {column_type}"))]
+    NonBooleanFilter {
+        /// The type of the filter expression
+        column_type: ColumnType,
+    },
     /// Nested aggregation in `GROUP BY` clause
     #[snafu(display("Nested aggregation in `GROUP BY` clause: {error}"))]
     NestedAggregationInGroupByClause {
@@ -0,0 +1,66 @@
+use super::{PostprocessingError, PostprocessingResult, PostprocessingStep};
+use crate::base::{
+    database::{filter_util::filter_columns, Column, OwnedColumn, OwnedTable},
+    scalar::Scalar,
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use proof_of_sql_parser::intermediate_ast::Expression;
+use serde::{Deserialize, Serialize};
+
+/// A `FilterPostprocessing` keeps the rows of an `OwnedTable` on which a boolean expression is true.
+///
+/// This is used for the `HAVING` clause, whose expression is evaluated on the aggregated table.
+/// Rows on which the expression is null are removed as well.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterPostprocessing {
+    /// The expression that the kept rows satisfy
+    expr: Box<Expression>,
+}
+
+impl FilterPostprocessing {
+    /// Create a new `FilterPostprocessing` node.
+    #[must_use]
+    pub fn new(expr: Box<Expression>) -> Self {
+        Self { expr }
+    }
+
+    /// Get the expression that the kept rows satisfy
+    #[must_use]
+    pub fn expr(&self) -> &Expression {
+        &self.expr
+    }
+}
+
+impl<S: Scalar> PostprocessingStep<S> for FilterPostprocessing {
+    /// Apply the filter transformation to the given `OwnedTable`.
+    fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
+        let selection = match owned_table.evaluate(&self.expr)? {
+            OwnedColumn::Boolean(selection) => selection,
+            column => {
+                return Err(PostprocessingError::NonBooleanFilter {
+                    column_type: column.column_type(),
+                })
+            }
+        };
+        let selection: Vec<bool> = match owned_table.evaluate_presence(&self.expr)? {
+            Some(presence) => selection
+                .iter()
+                .zip(presence)
+                .map(|(&selected, present)| selected && present)
+                .collect(),
+            None => selection,
+        };
+        let alloc = Bump::new();
+        let (ids, columns): (Vec<_>, Vec<_>) = owned_table
+            .inner_table()
+            .iter()
+            .map(|(id, column)| (*id, Column::<S>::from_owned_column(column, &alloc)))
+            .unzip();
+        let (filtered_columns, _) = filter_columns(&alloc, &columns, &selection);
+        Ok(OwnedTable::try_from_iter(
+            ids.into_iter()
+                .zip(filtered_columns.iter().map(OwnedColumn::from)),
+        )?)
+    }
+}
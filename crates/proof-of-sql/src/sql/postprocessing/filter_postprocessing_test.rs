@@ -0,0 +1,58 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, ColumnType, OwnedTable},
+        scalar::Curve25519Scalar,
+    },
+    sql::postprocessing::{
+        apply_postprocessing_steps, test_utility::*, OwnedTablePostprocessing, PostprocessingError,
+    },
+};
+use proof_of_sql_parser::utility::*;
+
+#[test]
+fn we_can_filter_owned_table_rows() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 5, 3, 7]),
+        varchar("b", ["w", "x", "y", "z"]),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [filter_expr(ge(col("a"), lit(3_i64)))];
+    let expected_table = owned_table([bigint("a", [5_i64, 3, 7]), varchar("b", ["x", "y", "z"])]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_filter_out_all_rows_of_an_owned_table() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [1_i64, 5, 3])]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [filter_expr(lit(false))];
+    let expected_table = owned_table([bigint("a", [0_i64; 0])]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_filter_out_rows_on_which_the_filter_is_null() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 5, 3, 7]),
+        boolean("__a_presence__", [true, false, true, true]),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [filter_expr(ge(col("a"), lit(3_i64)))];
+    let expected_table = owned_table([
+        bigint("a", [3_i64, 7]),
+        boolean("__a_presence__", [true, true]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_cannot_filter_owned_table_rows_with_a_non_boolean_expression() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [1_i64, 5, 3])]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [filter_expr(col("a"))];
+    assert_eq!(
+        apply_postprocessing_steps(table, &postprocessing),
+        Err(PostprocessingError::NonBooleanFilter {
+            column_type: ColumnType::BigInt
+        })
+    );
+}
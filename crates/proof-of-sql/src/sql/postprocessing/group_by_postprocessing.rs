@@ -1,14 +1,20 @@
-use super::{PostprocessingError, PostprocessingResult, PostprocessingStep};
+use super::{
+    select_postprocessing::evaluate_aliased_result_expr, PostprocessingError, PostprocessingResult,
+    PostprocessingStep,
+};
 use crate::base::{
-    database::{group_by_util::aggregate_columns, Column, OwnedColumn, OwnedTable},
+    database::{
+        filter_util::filter_column_by_index, group_by_util::aggregate_columns, presence_column_id,
+        Column, ColumnType, NullableColumn, OwnedColumn, OwnedTable,
+    },
     map::{indexmap, IndexMap, IndexSet},
     scalar::Scalar,
 };
-use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use alloc::{boxed::Box, collections::BTreeSet, format, vec, vec::Vec};
 use bumpalo::Bump;
 use itertools::{izip, Itertools};
 use proof_of_sql_parser::{
-    intermediate_ast::{AggregationOperator, AliasedResultExpr, Expression},
+    intermediate_ast::{AggregationOperator, AliasedResultExpr, Expression, WindowFunction},
     Identifier,
 };
 use serde::{Deserialize, Serialize};
@@ -19,8 +25,10 @@ pub struct GroupByPostprocessing {
     /// A list of `AliasedResultExpr` that exclusively use identifiers in the group by clause or results of aggregation expressions
     remainder_exprs: Vec<AliasedResultExpr>,
 
-    /// A list of identifiers in the group by clause
-    group_by_identifiers: Vec<Identifier>,
+    /// A list of expressions in the group by clause, each with the identifier of its column after the aggregation
+    ///
+    /// Columns keep their own identifier, while other expressions are given new ones.
+    group_by_exprs: Vec<(Expression, Identifier)>,
 
     /// A list of aggregation expressions
     aggregation_exprs: Vec<(AggregationOperator, Expression, Identifier)>,
@@ -31,31 +39,109 @@ pub struct GroupByPostprocessing {
 ///
 /// If the context is within an aggregation function, then any aggregation function is considered nested.
 /// Otherwise we need two layers of aggregation functions to be nested.
-fn contains_nested_aggregation(expr: &Expression, is_agg: bool) -> bool {
+pub(crate) fn contains_nested_aggregation(expr: &Expression, is_agg: bool) -> bool {
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => false,
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::Subquery(_) => false,
         Expression::Aggregation { expr, .. } => is_agg || contains_nested_aggregation(expr, true),
+        Expression::Window { function, .. } => match function {
+            WindowFunction::Sum(expr) => contains_nested_aggregation(expr, is_agg),
+            WindowFunction::RowNumber | WindowFunction::Rank => false,
+        },
         Expression::Binary { left, right, .. } => {
             contains_nested_aggregation(left, is_agg) || contains_nested_aggregation(right, is_agg)
         }
-        Expression::Unary { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::Unary { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::InSubquery { expr, .. } => contains_nested_aggregation(expr, is_agg),
+        Expression::InList { expr, list } => {
+            contains_nested_aggregation(expr, is_agg)
+                || list
+                    .iter()
+                    .any(|item| contains_nested_aggregation(item, is_agg))
+        }
+        Expression::Between { expr, low, high } => {
+            contains_nested_aggregation(expr, is_agg)
+                || contains_nested_aggregation(low, is_agg)
+                || contains_nested_aggregation(high, is_agg)
+        }
+        Expression::Case {
+            when_then,
+            else_result,
+        } => {
+            when_then.iter().any(|(when, then)| {
+                contains_nested_aggregation(when, is_agg)
+                    || contains_nested_aggregation(then, is_agg)
+            }) || else_result
+                .as_deref()
+                .is_some_and(|else_result| contains_nested_aggregation(else_result, is_agg))
+        }
     }
 }
 
 /// Get identifiers NOT in aggregate functions
-fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Identifier> {
+pub(crate) fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Identifier> {
     match expr {
-        Expression::Column(identifier) => IndexSet::from_iter([*identifier]),
-        Expression::Literal(_) | Expression::Aggregation { .. } | Expression::Wildcard => {
-            IndexSet::default()
-        }
+        Expression::Column(identifier)
+        | Expression::QualifiedColumn {
+            column: identifier, ..
+        } => IndexSet::from_iter([*identifier]),
+        Expression::Literal(_)
+        | Expression::Aggregation { .. }
+        | Expression::Wildcard
+        | Expression::Subquery(_) => IndexSet::default(),
         Expression::Binary { left, right, .. } => {
             let mut left_identifiers = get_free_identifiers_from_expr(left);
             let right_identifiers = get_free_identifiers_from_expr(right);
             left_identifiers.extend(right_identifiers);
             left_identifiers
         }
-        Expression::Unary { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::Unary { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::InSubquery { expr, .. } => get_free_identifiers_from_expr(expr),
+        Expression::Window {
+            function,
+            partition_by,
+            order_by,
+        } => {
+            let mut identifiers = match function {
+                WindowFunction::Sum(expr) => get_free_identifiers_from_expr(expr),
+                WindowFunction::RowNumber | WindowFunction::Rank => IndexSet::default(),
+            };
+            identifiers.extend(partition_by.iter().copied());
+            identifiers.extend(order_by.iter().map(|order_by| order_by.expr));
+            identifiers
+        }
+        Expression::InList { expr, list } => {
+            let mut identifiers = get_free_identifiers_from_expr(expr);
+            for item in list {
+                identifiers.extend(get_free_identifiers_from_expr(item));
+            }
+            identifiers
+        }
+        Expression::Between { expr, low, high } => {
+            let mut identifiers = get_free_identifiers_from_expr(expr);
+            identifiers.extend(get_free_identifiers_from_expr(low));
+            identifiers.extend(get_free_identifiers_from_expr(high));
+            identifiers
+        }
+        Expression::Case {
+            when_then,
+            else_result,
+        } => {
+            let mut identifiers = IndexSet::default();
+            for (when, then) in when_then {
+                identifiers.extend(get_free_identifiers_from_expr(when));
+                identifiers.extend(get_free_identifiers_from_expr(then));
+            }
+            if let Some(else_result) = else_result {
+                identifiers.extend(get_free_identifiers_from_expr(else_result));
+            }
+            identifiers
+        }
     }
 }
 
@@ -64,16 +150,31 @@ fn get_free_identifiers_from_expr(expr: &Expression) -> IndexSet<Identifier> {
 /// The idea here is to recursively traverse the expression tree and collect all the aggregation expressions
 /// and then label them as new columns post-aggregation and replace them with these new columns so that
 /// the post-aggregation expression tree doesn't contain any aggregation expressions and can be simply evaluated.
+/// Subexpressions outside aggregations that are group by expressions are replaced by the columns of their groups.
+/// Window functions and subqueries are left as they are unless they are group by expressions.
 /// # Panics
 ///
 /// Will panic if the key for an aggregation expression cannot be parsed as a valid identifier
 /// or if there are issues retrieving an identifier from the map.
-fn get_aggregate_and_remainder_expressions(
+pub(super) fn get_aggregate_and_remainder_expressions(
     expr: Expression,
+    group_by_exprs: &[(Expression, Identifier)],
     aggregation_expr_map: &mut IndexMap<(AggregationOperator, Expression), Identifier>,
 ) -> Expression {
+    if let Some((_, id)) = group_by_exprs
+        .iter()
+        .find(|(group_by_expr, _)| *group_by_expr == expr)
+    {
+        return Expression::Column(*id);
+    }
     match expr {
-        Expression::Column(_) | Expression::Literal(_) | Expression::Wildcard => expr,
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::Window { .. }
+        | Expression::InSubquery { .. }
+        | Expression::Subquery(_) => expr,
         Expression::Aggregation { op, expr } => {
             let key = (op, (*expr));
             if aggregation_expr_map.contains_key(&key) {
@@ -87,10 +188,16 @@ fn get_aggregate_and_remainder_expressions(
             }
         }
         Expression::Binary { op, left, right } => {
-            let left_remainder =
-                get_aggregate_and_remainder_expressions(*left, aggregation_expr_map);
-            let right_remainder =
-                get_aggregate_and_remainder_expressions(*right, aggregation_expr_map);
+            let left_remainder = get_aggregate_and_remainder_expressions(
+                *left,
+                group_by_exprs,
+                aggregation_expr_map,
+            );
+            let right_remainder = get_aggregate_and_remainder_expressions(
+                *right,
+                group_by_exprs,
+                aggregation_expr_map,
+            );
             Expression::Binary {
                 op,
                 left: Box::new(left_remainder),
@@ -98,43 +205,164 @@ fn get_aggregate_and_remainder_expressions(
             }
         }
         Expression::Unary { op, expr } => {
-            let remainder = get_aggregate_and_remainder_expressions(*expr, aggregation_expr_map);
+            let remainder = get_aggregate_and_remainder_expressions(
+                *expr,
+                group_by_exprs,
+                aggregation_expr_map,
+            );
             Expression::Unary {
                 op,
                 expr: Box::new(remainder),
             }
         }
+        Expression::Cast { expr, data_type } => {
+            let remainder = get_aggregate_and_remainder_expressions(
+                *expr,
+                group_by_exprs,
+                aggregation_expr_map,
+            );
+            Expression::Cast {
+                expr: Box::new(remainder),
+                data_type,
+            }
+        }
+        Expression::InList { expr, list } => Expression::InList {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                group_by_exprs,
+                aggregation_expr_map,
+            )),
+            list: list
+                .into_iter()
+                .map(|item| {
+                    get_aggregate_and_remainder_expressions(
+                        item,
+                        group_by_exprs,
+                        aggregation_expr_map,
+                    )
+                })
+                .collect(),
+        },
+        Expression::Between { expr, low, high } => Expression::Between {
+            expr: Box::new(get_aggregate_and_remainder_expressions(
+                *expr,
+                group_by_exprs,
+                aggregation_expr_map,
+            )),
+            low: Box::new(get_aggregate_and_remainder_expressions(
+                *low,
+                group_by_exprs,
+                aggregation_expr_map,
+            )),
+            high: Box::new(get_aggregate_and_remainder_expressions(
+                *high,
+                group_by_exprs,
+                aggregation_expr_map,
+            )),
+        },
+        Expression::Case {
+            when_then,
+            else_result,
+        } => Expression::Case {
+            when_then: when_then
+                .into_iter()
+                .map(|(when, then)| {
+                    (
+                        get_aggregate_and_remainder_expressions(
+                            when,
+                            group_by_exprs,
+                            aggregation_expr_map,
+                        ),
+                        get_aggregate_and_remainder_expressions(
+                            then,
+                            group_by_exprs,
+                            aggregation_expr_map,
+                        ),
+                    )
+                })
+                .collect(),
+            else_result: else_result.map(|else_result| {
+                Box::new(get_aggregate_and_remainder_expressions(
+                    *else_result,
+                    group_by_exprs,
+                    aggregation_expr_map,
+                ))
+            }),
+        },
     }
 }
 
+/// Rewrite an expression on the groups, such as a HAVING expression, in terms of the result columns
+///
+/// Subexpressions that are result expressions are replaced by the columns of their aliases.
+/// Returns `None` if the expression needs anything else, i.e. aggregations that aren't results or columns
+/// outside the results. Column references whose names are aliases of other result expressions are ambiguous,
+/// so those return `None` as well.
+pub(crate) fn get_expression_over_results(
+    expr: &Expression,
+    result_exprs: &[AliasedResultExpr],
+) -> Option<Expression> {
+    let is_ambiguous = |id: &Identifier| {
+        result_exprs
+            .iter()
+            .any(|res| res.alias == *id && *res.expr != Expression::Column(*id))
+    };
+    if get_free_identifiers_from_expr(expr)
+        .iter()
+        .any(is_ambiguous)
+    {
+        return None;
+    }
+    let replacements = result_exprs
+        .iter()
+        .map(|res| ((*res.expr).clone(), res.alias))
+        .collect::<Vec<_>>();
+    let mut aggregation_expr_map = IndexMap::default();
+    let remainder = get_aggregate_and_remainder_expressions(
+        expr.clone(),
+        &replacements,
+        &mut aggregation_expr_map,
+    );
+    let aliases = result_exprs
+        .iter()
+        .map(|res| res.alias)
+        .collect::<IndexSet<_>>();
+    (aggregation_expr_map.is_empty()
+        && get_free_identifiers_from_expr(&remainder).is_subset(&aliases))
+    .then_some(remainder)
+}
+
 /// Given an `AliasedResultExpr`, check if it is legitimate and if so grab the relevant aggregation expression
 /// # Panics
 ///
 /// Will panic if there is an issue retrieving the first element from the difference of free identifiers and group-by identifiers, indicating a logical inconsistency in the identifiers.
 fn check_and_get_aggregation_and_remainder(
     expr: AliasedResultExpr,
-    group_by_identifiers: &[Identifier],
+    group_by_exprs: &[(Expression, Identifier)],
     aggregation_expr_map: &mut IndexMap<(AggregationOperator, Expression), Identifier>,
 ) -> PostprocessingResult<AliasedResultExpr> {
-    let free_identifiers = get_free_identifiers_from_expr(&expr.expr);
-    let group_by_identifier_set = group_by_identifiers
-        .iter()
-        .copied()
-        .collect::<IndexSet<_>>();
     if contains_nested_aggregation(&expr.expr, false) {
         return Err(PostprocessingError::NestedAggregationInGroupByClause {
             error: format!("Nested aggregations found {:?}", expr.expr),
         });
     }
-    if free_identifiers.is_subset(&group_by_identifier_set) {
-        let remainder = get_aggregate_and_remainder_expressions(*expr.expr, aggregation_expr_map);
+    let remainder =
+        get_aggregate_and_remainder_expressions(*expr.expr, group_by_exprs, aggregation_expr_map);
+    // Outside aggregations, only the columns of the groups and the aggregation results may be left
+    let available_identifiers = group_by_exprs
+        .iter()
+        .map(|(_, id)| *id)
+        .chain(aggregation_expr_map.values().copied())
+        .collect::<IndexSet<_>>();
+    let free_identifiers = get_free_identifiers_from_expr(&remainder);
+    if free_identifiers.is_subset(&available_identifiers) {
         Ok(AliasedResultExpr {
             alias: expr.alias,
             expr: Box::new(remainder),
         })
     } else {
         let diff = free_identifiers
-            .difference(&group_by_identifier_set)
+            .difference(&available_identifiers)
             .next()
             .unwrap();
         Err(
@@ -147,10 +375,25 @@ fn check_and_get_aggregation_and_remainder(
 
 impl GroupByPostprocessing {
     /// Create a new group by expression containing the group by and aggregation expressions
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the identifier of a group by expression that is not a column cannot be parsed.
     pub fn try_new(
-        by_ids: Vec<Identifier>,
+        by_exprs: Vec<Expression>,
         aliased_exprs: Vec<AliasedResultExpr>,
     ) -> PostprocessingResult<Self> {
+        let group_by_exprs: Vec<(Expression, Identifier)> = IndexSet::from_iter(by_exprs)
+            .into_iter()
+            .enumerate()
+            .map(|(i, expr)| {
+                let id = match expr {
+                    Expression::Column(id) => id,
+                    _ => format!("__col_group_by_{i}").parse().unwrap(),
+                };
+                (expr, id)
+            })
+            .collect();
         let mut aggregation_expr_map: IndexMap<(AggregationOperator, Expression), Identifier> =
             IndexMap::default();
         // Look for aggregation expressions and check for non-aggregation expressions that contain identifiers not in the group by clause
@@ -159,15 +402,14 @@ impl GroupByPostprocessing {
             .map(|aliased_expr| -> PostprocessingResult<_> {
                 check_and_get_aggregation_and_remainder(
                     aliased_expr,
-                    &by_ids,
+                    &group_by_exprs,
                     &mut aggregation_expr_map,
                 )
             })
             .collect::<PostprocessingResult<Vec<AliasedResultExpr>>>()?;
-        let group_by_identifiers = Vec::from_iter(IndexSet::from_iter(by_ids));
         Ok(Self {
             remainder_exprs,
-            group_by_identifiers,
+            group_by_exprs,
             aggregation_exprs: aggregation_expr_map
                 .into_iter()
                 .map(|((op, expr), id)| (op, expr, id))
@@ -175,10 +417,10 @@ impl GroupByPostprocessing {
         })
     }
 
-    /// Get group by identifiers
+    /// Get group by expressions together with the identifiers of their columns
     #[must_use]
-    pub fn group_by(&self) -> &[Identifier] {
-        &self.group_by_identifiers
+    pub fn group_by(&self) -> &[(Expression, Identifier)] {
+        &self.group_by_exprs
     }
 
     /// Get remainder expressions for SELECT
@@ -196,15 +438,19 @@ impl GroupByPostprocessing {
 
 impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
     /// Apply the group by transformation to the given `OwnedTable`.
+    ///
+    /// Null values are ignored by the aggregations, and `COUNT(expr)` counts the rows where `expr` is not null.
+    /// Likewise `COUNT(DISTINCT expr)` counts the distinct values of `expr` that are not null.
     fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
-        // First evaluate all the aggregated columns
+        // First evaluate all the aggregated columns, together with which of their rows are not null
         let alloc = Bump::new();
         let evaluated_columns = self
             .aggregation_exprs
             .iter()
             .map(|(agg_op, expr, id)| -> PostprocessingResult<_> {
                 let evaluated_owned_column = owned_table.evaluate(expr)?;
-                Ok((*agg_op, (*id, evaluated_owned_column)))
+                let presence = owned_table.evaluate_presence(expr)?;
+                Ok((*agg_op, (*id, evaluated_owned_column, presence)))
             })
             .process_results(|iter| {
                 iter.fold(
@@ -216,48 +462,103 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
                 )
             })?;
         // Next actually do the GROUP BY
-        let group_by_ins = self
-            .group_by_identifiers
+        // Nullable expressions are also grouped by their presence so that nulls are not grouped with other values.
+        // Since the values of null rows are arbitrary, they are all replaced by the value of the first null row.
+        let group_by_evaluations = self
+            .group_by_exprs
             .iter()
-            .map(|id| {
-                let column = owned_table.inner_table().get(id).ok_or(
-                    PostprocessingError::ColumnNotFound {
-                        column: id.to_string(),
-                    },
-                )?;
-                Ok(Column::<S>::from_owned_column(column, &alloc))
+            .map(|(expr, id)| -> PostprocessingResult<_> {
+                Ok((
+                    *id,
+                    owned_table.evaluate(expr)?,
+                    owned_table.evaluate_presence(expr)?,
+                ))
             })
             .collect::<PostprocessingResult<Vec<_>>>()?;
+        let mut group_by_identifiers = Vec::new();
+        let mut group_by_ins = Vec::new();
+        for (id, column, presence) in &group_by_evaluations {
+            let column = Column::<S>::from_owned_column(column, &alloc);
+            group_by_identifiers.push(*id);
+            match presence {
+                Some(presence) => {
+                    let first_null = presence.iter().position(|present| !present);
+                    let indexes = presence
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &present)| if present { i } else { first_null.unwrap_or(i) })
+                        .collect::<Vec<_>>();
+                    group_by_ins.push(filter_column_by_index(&alloc, &column, &indexes));
+                    group_by_identifiers
+                        .push(presence_column_id(*id).expect("presence column exists"));
+                    group_by_ins.push(Column::Boolean(presence));
+                }
+                None => group_by_ins.push(column),
+            }
+        }
         // TODO: Allow a filter
         let selection_in = vec![true; owned_table.num_rows()];
-        let (sum_identifiers, sum_columns): (Vec<_>, Vec<_>) = evaluated_columns
-            .get(&AggregationOperator::Sum)
-            .map_or((vec![], vec![]), |tuple| {
-                tuple
-                    .iter()
-                    .map(|(id, c)| (*id, Column::<S>::from_owned_column(c, &alloc)))
-                    .unzip()
-            });
-        let (max_identifiers, max_columns): (Vec<_>, Vec<_>) = evaluated_columns
-            .get(&AggregationOperator::Max)
-            .map_or((vec![], vec![]), |tuple| {
-                tuple
-                    .iter()
-                    .map(|(id, c)| (*id, Column::<S>::from_owned_column(c, &alloc)))
-                    .unzip()
-            });
-        let (min_identifiers, min_columns): (Vec<_>, Vec<_>) = evaluated_columns
-            .get(&AggregationOperator::Min)
-            .map_or((vec![], vec![]), |tuple| {
-                tuple
-                    .iter()
-                    .map(|(id, c)| (*id, Column::<S>::from_owned_column(c, &alloc)))
-                    .unzip()
-            });
+        let nullable_columns_of = |op| -> (Vec<_>, Vec<_>) {
+            evaluated_columns
+                .get(&op)
+                .map_or((vec![], vec![]), |tuple| {
+                    tuple
+                        .iter()
+                        .map(|(id, c, presence)| {
+                            let column = Column::<S>::from_owned_column(c, &alloc);
+                            (*id, NullableColumn::new(column, presence.as_deref()))
+                        })
+                        .unzip()
+                })
+        };
+        let (sum_identifiers, sum_columns) = nullable_columns_of(AggregationOperator::Sum);
+        let (max_identifiers, max_columns) = nullable_columns_of(AggregationOperator::Max);
+        let (min_identifiers, min_columns) = nullable_columns_of(AggregationOperator::Min);
+        // `COUNT(expr)` of a nullable `expr` is the sum of its presence
+        let (count_identifiers, count_presence_columns): (Vec<_>, Vec<_>) = evaluated_columns
+            .get(&AggregationOperator::Count)
+            .into_iter()
+            .flatten()
+            .filter_map(|(id, _, presence)| {
+                let presence = presence.as_ref()?;
+                let presence = alloc.alloc_slice_fill_iter(presence.iter().map(|&p| i64::from(p)));
+                Some((*id, NullableColumn::from(Column::BigInt(presence))))
+            })
+            .unzip();
+        // `COUNT(DISTINCT expr)` is the sum of whether a row is the first one of its group with its non-null value of `expr`
+        let group_by_scalars = group_by_ins
+            .iter()
+            .map(|column| column.as_scalar(&alloc))
+            .collect::<Vec<_>>();
+        let (count_distinct_identifiers, count_distinct_columns): (Vec<_>, Vec<_>) =
+            evaluated_columns
+                .get(&AggregationOperator::CountDistinct)
+                .into_iter()
+                .flatten()
+                .map(|(id, column, presence)| {
+                    let values = Column::<S>::from_owned_column(column, &alloc).as_scalar(&alloc);
+                    let mut seen = BTreeSet::new();
+                    let is_first = alloc.alloc_slice_fill_iter((0..values.len()).map(|i| {
+                        let key: Vec<S> = group_by_scalars
+                            .iter()
+                            .map(|scalars| scalars[i])
+                            .chain([values[i]])
+                            .collect();
+                        let present = presence.as_ref().map_or(true, |presence| presence[i]);
+                        i64::from(present && seen.insert(key))
+                    }));
+                    (*id, NullableColumn::from(Column::BigInt(is_first)))
+                })
+                .unzip();
         let aggregation_results = aggregate_columns(
             &alloc,
             &group_by_ins,
-            &sum_columns,
+            &[
+                sum_columns.as_slice(),
+                &count_presence_columns,
+                &count_distinct_columns,
+            ]
+            .concat(),
             &max_columns,
             &min_columns,
             &selection_in,
@@ -267,54 +568,61 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
         let group_by_outs = aggregation_results
             .group_by_columns
             .iter()
-            .zip(self.group_by_identifiers.iter())
-            .map(|(column, id)| Ok((*id, OwnedColumn::from(column))));
-        let sum_outs = izip!(
-            aggregation_results.sum_columns,
-            sum_identifiers,
-            sum_columns,
-        )
-        .map(|(c_out, id, c_in)| {
-            Ok((
-                id,
-                OwnedColumn::try_from_scalars(c_out, c_in.column_type())?,
-            ))
-        });
+            .zip(group_by_identifiers)
+            .map(|(column, id)| Ok((id, OwnedColumn::from(column))));
+        let (sum_results, count_results) =
+            aggregation_results.sum_columns.split_at(sum_columns.len());
+        let (count_results, count_distinct_results) =
+            count_results.split_at(count_presence_columns.len());
+        let sum_outs =
+            izip!(sum_results, sum_identifiers, &sum_columns).map(|(c_out, id, c_in)| {
+                Ok((
+                    id,
+                    OwnedColumn::try_from_scalars(c_out, c_in.values.column_type())?,
+                ))
+            });
         let max_outs = izip!(
             aggregation_results.max_columns,
             max_identifiers,
-            max_columns,
+            &max_columns,
         )
-        .map(|(c_out, id, c_in)| {
-            Ok((
-                id,
-                OwnedColumn::try_from_option_scalars(c_out, c_in.column_type())?,
-            ))
-        });
+        .map(|(c_out, id, c_in)| option_scalars_to_columns(c_out, id, c_in))
+        .flatten_ok();
         let min_outs = izip!(
             aggregation_results.min_columns,
             min_identifiers,
-            min_columns,
+            &min_columns,
         )
-        .map(|(c_out, id, c_in)| {
-            Ok((
-                id,
-                OwnedColumn::try_from_option_scalars(c_out, c_in.column_type())?,
-            ))
-        });
-        //TODO: When we have NULLs we need to differentiate between count(1) and count(expression)
+        .map(|(c_out, id, c_in)| option_scalars_to_columns(c_out, id, c_in))
+        .flatten_ok();
         let count_column = OwnedColumn::BigInt(aggregation_results.count_column.to_vec());
         let count_outs = evaluated_columns
             .get(&AggregationOperator::Count)
             .into_iter()
             .flatten()
-            .map(|(id, _)| -> PostprocessingResult<_> { Ok((*id, count_column.clone())) });
+            .map(|(id, _, _)| -> PostprocessingResult<_> {
+                match count_identifiers.iter().position(|count_id| count_id == id) {
+                    Some(index) => Ok((
+                        *id,
+                        OwnedColumn::try_from_scalars(count_results[index], ColumnType::BigInt)?,
+                    )),
+                    None => Ok((*id, count_column.clone())),
+                }
+            });
+        let count_distinct_outs =
+            izip!(count_distinct_results, count_distinct_identifiers).map(|(c_out, id)| {
+                Ok((
+                    id,
+                    OwnedColumn::try_from_scalars(c_out, ColumnType::BigInt)?,
+                ))
+            });
         let new_owned_table: OwnedTable<S> = group_by_outs
             .into_iter()
             .chain(sum_outs)
             .chain(max_outs)
             .chain(min_outs)
             .chain(count_outs)
+            .chain(count_distinct_outs)
             .process_results(|iter| OwnedTable::try_from_iter(iter))??;
         // If there are no columns at all we need to have the count column so that we can handle
         // queries such as `SELECT 1 FROM table`
@@ -326,15 +634,41 @@ impl<S: Scalar> PostprocessingStep<S> for GroupByPostprocessing {
         let result = self
             .remainder_exprs
             .iter()
-            .map(|aliased_expr| -> PostprocessingResult<_> {
-                let column = target_table.evaluate(&aliased_expr.expr)?;
-                Ok((aliased_expr.alias, column))
-            })
+            .map(|aliased_expr| evaluate_aliased_result_expr(&target_table, aliased_expr))
+            .flatten_ok()
             .process_results(|iter| OwnedTable::try_from_iter(iter))??;
         Ok(result)
     }
 }
 
+/// Converts the maxima or minima of a column into a column and, if the column is nullable, its presence column.
+///
+/// Groups without a maximum or minimum, i.e. groups that only contain nulls, are null.
+fn option_scalars_to_columns<S: Scalar>(
+    option_scalars: &[Option<S>],
+    id: Identifier,
+    column_in: &NullableColumn<S>,
+) -> PostprocessingResult<Vec<(Identifier, OwnedColumn<S>)>> {
+    let column_type = column_in.values.column_type();
+    match column_in.presence.zip(presence_column_id(id)) {
+        Some((_, presence_id)) => {
+            let scalars: Vec<_> = option_scalars
+                .iter()
+                .map(|scalar| scalar.unwrap_or_default())
+                .collect();
+            let presence = option_scalars.iter().map(Option::is_some).collect();
+            Ok(vec![
+                (id, OwnedColumn::try_from_scalars(&scalars, column_type)?),
+                (presence_id, OwnedColumn::Boolean(presence)),
+            ])
+        }
+        None => Ok(vec![(
+            id,
+            OwnedColumn::try_from_option_scalars(option_scalars, column_type)?,
+        )]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -416,7 +750,7 @@ mod tests {
         // SUM(a) + b
         let expr = add(sum(col("a")), col("b"));
         let remainder_expr =
-            get_aggregate_and_remainder_expressions(*expr, &mut aggregation_expr_map);
+            get_aggregate_and_remainder_expressions(*expr, &[], &mut aggregation_expr_map);
         assert_eq!(
             aggregation_expr_map[&(AggregationOperator::Sum, *col("a"))],
             ident("__col_agg_0")
@@ -427,7 +761,7 @@ mod tests {
         // SUM(a) + SUM(b)
         let expr = add(sum(col("a")), sum(col("b")));
         let remainder_expr =
-            get_aggregate_and_remainder_expressions(*expr, &mut aggregation_expr_map);
+            get_aggregate_and_remainder_expressions(*expr, &[], &mut aggregation_expr_map);
         assert_eq!(
             aggregation_expr_map[&(AggregationOperator::Sum, *col("a"))],
             ident("__col_agg_0")
@@ -448,7 +782,7 @@ mod tests {
             col("c"),
         );
         let remainder_expr =
-            get_aggregate_and_remainder_expressions(*expr, &mut aggregation_expr_map);
+            get_aggregate_and_remainder_expressions(*expr, &[], &mut aggregation_expr_map);
         assert_eq!(
             aggregation_expr_map[&(AggregationOperator::Max, *add(col("a"), lit(1)))],
             ident("__col_agg_2")
@@ -472,7 +806,7 @@ mod tests {
             lit(1),
         );
         let remainder_expr =
-            get_aggregate_and_remainder_expressions(*expr, &mut aggregation_expr_map);
+            get_aggregate_and_remainder_expressions(*expr, &[], &mut aggregation_expr_map);
         assert_eq!(
             aggregation_expr_map[&(AggregationOperator::Count, *mul(lit(2), col("a")))],
             ident("__col_agg_4")
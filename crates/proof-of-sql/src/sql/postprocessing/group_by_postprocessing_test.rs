@@ -1,6 +1,6 @@
 use crate::{
     base::{
-        database::{owned_table_utility::*, OwnedTable},
+        database::{owned_table_utility::*, OwnedColumn, OwnedTable},
         scalar::Curve25519Scalar,
     },
     sql::postprocessing::{
@@ -16,7 +16,7 @@ use proof_of_sql_parser::{
 fn we_cannot_have_invalid_group_bys() {
     // Column in result but not in group by or aggregation
     let expr = add(sum(col("a")), col("b")); // b is not in group by or aggregation
-    let res = GroupByPostprocessing::try_new(vec![ident("a")], vec![aliased_expr(expr, "res")]);
+    let res = GroupByPostprocessing::try_new(vec![*col("a")], vec![aliased_expr(expr, "res")]);
     assert!(matches!(
         res,
         Err(PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. })
@@ -24,7 +24,7 @@ fn we_cannot_have_invalid_group_bys() {
 
     // Nested aggregation
     let expr = sum(max(col("a"))); // Nested aggregation
-    let res = GroupByPostprocessing::try_new(vec![ident("a")], vec![aliased_expr(expr, "res")]);
+    let res = GroupByPostprocessing::try_new(vec![*col("a")], vec![aliased_expr(expr, "res")]);
     assert!(matches!(
         res,
         Err(PostprocessingError::NestedAggregationInGroupByClause { .. })
@@ -35,14 +35,17 @@ fn we_cannot_have_invalid_group_bys() {
 fn we_can_make_group_by_postprocessing() {
     // SELECT SUM(a) + 2 as c0, SUM(b + a) as c1 FROM tab GROUP BY a, b
     let res = GroupByPostprocessing::try_new(
-        vec![ident("a"), ident("b")],
+        vec![*col("a"), *col("b")],
         vec![
             aliased_expr(add(sum(col("a")), lit(2)), "c0"),
             aliased_expr(sum(add(col("b"), col("a"))), "c1"),
         ],
     )
     .unwrap();
-    assert_eq!(res.group_by(), &[ident("a"), ident("b")]);
+    assert_eq!(
+        res.group_by(),
+        &[(*col("a"), ident("a")), (*col("b"), ident("b"))]
+    );
     assert_eq!(
         res.remainder_exprs(),
         &[
@@ -312,3 +315,140 @@ fn we_can_do_complex_group_bys() {
     let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
     assert_eq!(actual_table, expected_table);
 }
+
+#[test]
+fn we_can_do_group_bys_on_expressions() {
+    // SELECT a + b AS c, 2 * (a + b) + 1 AS d, SUM(b) AS sum_b FROM tab GROUP BY a + b
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 0, 3, 1]),
+        bigint("b", [2_i64, 0, 3, 0, 1]),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [OwnedTablePostprocessing::new_group_by(
+        GroupByPostprocessing::try_new(
+            vec![*add(col("a"), col("b"))],
+            vec![
+                aliased_expr(add(col("a"), col("b")), "c"),
+                aliased_expr(add(mul(lit(2), add(col("a"), col("b"))), lit(1)), "d"),
+                aliased_expr(sum(col("b")), "sum_b"),
+            ],
+        )
+        .unwrap(),
+    )];
+    let expected_table = owned_table([
+        bigint("c", [2_i64, 3]),
+        bigint("d", [5_i64, 7]),
+        bigint("sum_b", [1_i64, 5]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_cannot_use_columns_of_group_by_expressions_outside_them() {
+    // SELECT a FROM tab GROUP BY a + b
+    let res = GroupByPostprocessing::try_new(
+        vec![*add(col("a"), col("b"))],
+        vec![aliased_expr(col("a"), "a")],
+    );
+    assert!(matches!(
+        res,
+        Err(PostprocessingError::IdentifierNotInAggregationOperatorOrGroupByClause { .. })
+    ));
+}
+
+#[test]
+fn we_can_do_group_bys_on_nullable_expressions() {
+    // SELECT a + 1 AS c, COUNT(*) AS n FROM tab GROUP BY a + 1
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 5, 1, 9, 2]),
+        boolean("__a_presence__", [true, false, true, false, true]),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [OwnedTablePostprocessing::new_group_by(
+        GroupByPostprocessing::try_new(
+            vec![*add(col("a"), lit(1))],
+            vec![
+                aliased_expr(add(col("a"), lit(1)), "c"),
+                aliased_expr(count(lit(1)), "n"),
+            ],
+        )
+        .unwrap(),
+    )];
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table.num_rows(), 3);
+    assert_eq!(
+        actual_table.presence(ident("c")),
+        Some(&[true, true, false][..])
+    );
+    assert_eq!(
+        actual_table.inner_table()[&ident("n")],
+        OwnedColumn::BigInt(vec![2, 1, 2])
+    );
+}
+
+#[test]
+fn we_can_do_group_bys_with_nulls() {
+    // SELECT a, SUM(b) as sum_b, COUNT(b) as count_b, MAX(b) as max_b FROM tab GROUP BY a
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        int128("a", [1_i128, 1, 0, 2]),
+        boolean("__a_presence__", [true, true, false, true]),
+        bigint("b", [0_i64, 0, 7, 8]),
+        boolean("__b_presence__", [false, false, true, true]),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing(
+        &["a"],
+        &[
+            aliased_expr(col("a"), "a"),
+            aliased_expr(sum(col("b")), "sum_b"),
+            aliased_expr(count(col("b")), "count_b"),
+            aliased_expr(max(col("b")), "max_b"),
+        ],
+    )];
+    let expected_table = owned_table([
+        int128("a", [0_i128, 1, 2]),
+        boolean("__a_presence__", [false, true, true]),
+        bigint("sum_b", [7_i64, 0, 8]),
+        bigint("count_b", [1_i64, 0, 1]),
+        bigint("max_b", [7_i64, 0, 8]),
+        boolean("__max_b_presence__", [true, false, true]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_count_distinct_values_with_nulls() {
+    // SELECT a, COUNT(DISTINCT b) as n, COUNT(b) as count_b FROM tab GROUP BY a
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 1, 1, 2, 1, 3]),
+        varchar("b", ["x", "y", "x", "z", "y", "w", "v"]),
+        boolean(
+            "__b_presence__",
+            [true, true, true, true, true, false, false],
+        ),
+    ]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing(
+        &["a"],
+        &[
+            aliased_expr(col("a"), "a"),
+            aliased_expr(count_distinct(col("b")), "n"),
+            aliased_expr(count(col("b")), "count_b"),
+        ],
+    )];
+    let expected_table = owned_table([
+        bigint("a", [1_i64, 2, 3]),
+        bigint("n", [2_i64, 1, 0]),
+        bigint("count_b", [3_i64, 2, 0]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+
+    // SELECT COUNT(DISTINCT a + 1) as n FROM tab
+    let table: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [1_i64, 2, 1, 1, 2])]);
+    let postprocessing: [OwnedTablePostprocessing; 1] = [group_by_postprocessing(
+        &[],
+        &[aliased_expr(count_distinct(add(col("a"), lit(1))), "n")],
+    )];
+    let expected_table = owned_table([bigint("n", [2_i64])]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
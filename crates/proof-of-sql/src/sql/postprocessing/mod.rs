@@ -12,9 +12,17 @@ pub mod test_utility;
 
 mod group_by_postprocessing;
 pub use group_by_postprocessing::GroupByPostprocessing;
+pub(crate) use group_by_postprocessing::{
+    contains_nested_aggregation, get_expression_over_results, get_free_identifiers_from_expr,
+};
 #[cfg(test)]
 mod group_by_postprocessing_test;
 
+mod filter_postprocessing;
+pub use filter_postprocessing::FilterPostprocessing;
+#[cfg(test)]
+mod filter_postprocessing_test;
+
 mod order_by_postprocessing;
 pub use order_by_postprocessing::OrderByPostprocessing;
 #[cfg(test)]
@@ -29,3 +37,8 @@ mod slice_postprocessing;
 pub use slice_postprocessing::SlicePostprocessing;
 #[cfg(test)]
 mod slice_postprocessing_test;
+
+mod window_postprocessing;
+pub use window_postprocessing::WindowPostprocessing;
+#[cfg(test)]
+mod window_postprocessing_test;
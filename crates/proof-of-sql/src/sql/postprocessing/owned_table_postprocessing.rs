@@ -1,6 +1,6 @@
 use super::{
-    GroupByPostprocessing, OrderByPostprocessing, PostprocessingResult, PostprocessingStep,
-    SelectPostprocessing, SlicePostprocessing,
+    FilterPostprocessing, GroupByPostprocessing, OrderByPostprocessing, PostprocessingResult,
+    PostprocessingStep, SelectPostprocessing, SlicePostprocessing, WindowPostprocessing,
 };
 use crate::base::{database::OwnedTable, scalar::Scalar};
 use serde::{Deserialize, Serialize};
@@ -16,6 +16,10 @@ pub enum OwnedTablePostprocessing {
     Select(SelectPostprocessing),
     /// Aggregate the `OwnedTable` with the given `GroupByPostprocessing`.
     GroupBy(GroupByPostprocessing),
+    /// Filter the `OwnedTable` with the given `FilterPostprocessing`.
+    Filter(FilterPostprocessing),
+    /// Evaluate window functions on the `OwnedTable` with the given `WindowPostprocessing`.
+    Window(WindowPostprocessing),
 }
 
 impl<S: Scalar> PostprocessingStep<S> for OwnedTablePostprocessing {
@@ -26,6 +30,8 @@ impl<S: Scalar> PostprocessingStep<S> for OwnedTablePostprocessing {
             OwnedTablePostprocessing::OrderBy(order_by_expr) => order_by_expr.apply(owned_table),
             OwnedTablePostprocessing::Select(select_expr) => select_expr.apply(owned_table),
             OwnedTablePostprocessing::GroupBy(group_by_expr) => group_by_expr.apply(owned_table),
+            OwnedTablePostprocessing::Filter(filter_expr) => filter_expr.apply(owned_table),
+            OwnedTablePostprocessing::Window(window_expr) => window_expr.apply(owned_table),
         }
     }
 }
@@ -51,6 +57,16 @@ impl OwnedTablePostprocessing {
     pub fn new_group_by(group_by_postprocessing: GroupByPostprocessing) -> Self {
         Self::GroupBy(group_by_postprocessing)
     }
+    /// Create a new `OwnedTablePostprocessing` with the given `FilterPostprocessing`.
+    #[must_use]
+    pub fn new_filter(filter_expr: FilterPostprocessing) -> Self {
+        Self::Filter(filter_expr)
+    }
+    /// Create a new `OwnedTablePostprocessing` with the given `WindowPostprocessing`.
+    #[must_use]
+    pub fn new_window(window_expr: WindowPostprocessing) -> Self {
+        Self::Window(window_expr)
+    }
 }
 
 /// Apply a list of postprocessing steps to an `OwnedTable`.
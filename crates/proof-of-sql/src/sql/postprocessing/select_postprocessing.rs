@@ -1,10 +1,12 @@
 use super::{PostprocessingResult, PostprocessingStep};
 use crate::base::{
-    database::{OwnedColumn, OwnedTable},
+    database::{presence_column_id, OwnedColumn, OwnedTable},
     map::IndexMap,
     scalar::Scalar,
 };
 use alloc::vec::Vec;
+use core::iter;
+use itertools::Itertools;
 use proof_of_sql_parser::{intermediate_ast::AliasedResultExpr, Identifier};
 use serde::{Deserialize, Serialize};
 
@@ -31,13 +33,28 @@ impl<S: Scalar> PostprocessingStep<S> for SelectPostprocessing {
         let cols: IndexMap<Identifier, OwnedColumn<S>> = self
             .aliased_result_exprs
             .iter()
-            .map(
-                |aliased_result_expr| -> PostprocessingResult<(Identifier, OwnedColumn<S>)> {
-                    let result_column = owned_table.evaluate(&aliased_result_expr.expr)?;
-                    Ok((aliased_result_expr.alias, result_column))
-                },
-            )
+            .map(|aliased_result_expr| {
+                evaluate_aliased_result_expr(&owned_table, aliased_result_expr)
+            })
+            .flatten_ok()
             .collect::<PostprocessingResult<_>>()?;
         Ok(OwnedTable::try_new(cols)?)
     }
 }
+
+/// Evaluates an aliased result expression on a table.
+///
+/// If the expression is nullable, its presence column is returned as well. See [`presence_column_id`].
+pub(super) fn evaluate_aliased_result_expr<S: Scalar>(
+    owned_table: &OwnedTable<S>,
+    aliased_result_expr: &AliasedResultExpr,
+) -> PostprocessingResult<Vec<(Identifier, OwnedColumn<S>)>> {
+    let result_column = owned_table.evaluate(&aliased_result_expr.expr)?;
+    let presence_column = owned_table
+        .evaluate_presence(&aliased_result_expr.expr)?
+        .zip(presence_column_id(aliased_result_expr.alias))
+        .map(|(presence, presence_id)| (presence_id, OwnedColumn::Boolean(presence)));
+    Ok(iter::once((aliased_result_expr.alias, result_column))
+        .chain(presence_column)
+        .collect())
+}
@@ -1,8 +1,7 @@
 use super::*;
 use proof_of_sql_parser::{
-    intermediate_ast::{AliasedResultExpr, OrderBy, OrderByDirection},
+    intermediate_ast::{AliasedResultExpr, Expression, OrderBy, OrderByDirection},
     utility::ident,
-    Identifier,
 };
 
 #[must_use]
@@ -10,9 +9,12 @@ pub fn group_by_postprocessing(
     cols: &[&str],
     result_exprs: &[AliasedResultExpr],
 ) -> OwnedTablePostprocessing {
-    let ids: Vec<Identifier> = cols.iter().map(|col| ident(col)).collect();
+    let exprs: Vec<Expression> = cols
+        .iter()
+        .map(|col| Expression::Column(ident(col)))
+        .collect();
     OwnedTablePostprocessing::new_group_by(
-        GroupByPostprocessing::try_new(ids, result_exprs.to_vec()).unwrap(),
+        GroupByPostprocessing::try_new(exprs, result_exprs.to_vec()).unwrap(),
     )
 }
 
@@ -42,3 +44,8 @@ pub fn orders(cols: &[&str], directions: &[OrderByDirection]) -> OwnedTablePostp
         .collect();
     OwnedTablePostprocessing::new_order_by(OrderByPostprocessing::new(by_exprs))
 }
+
+#[must_use]
+pub fn filter_expr(expr: Box<Expression>) -> OwnedTablePostprocessing {
+    OwnedTablePostprocessing::new_filter(FilterPostprocessing::new(expr))
+}
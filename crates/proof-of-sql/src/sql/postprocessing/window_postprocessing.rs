@@ -0,0 +1,268 @@
+use super::{
+    group_by_postprocessing::get_aggregate_and_remainder_expressions,
+    select_postprocessing::evaluate_aliased_result_expr, PostprocessingError, PostprocessingResult,
+    PostprocessingStep,
+};
+use crate::base::{
+    database::{compare_indexes_by_owned_columns_with_direction, Column, OwnedColumn, OwnedTable},
+    map::{IndexMap, IndexSet},
+    scalar::Scalar,
+};
+use alloc::{boxed::Box, format, string::ToString, vec, vec::Vec};
+use bumpalo::Bump;
+use core::cmp::Ordering;
+use itertools::Itertools;
+use proof_of_sql_parser::{
+    intermediate_ast::{AliasedResultExpr, Expression, OrderBy, OrderByDirection, WindowFunction},
+    Identifier,
+};
+use serde::{Deserialize, Serialize};
+
+/// A node that evaluates window functions, such as `ROW_NUMBER() OVER (PARTITION BY a ORDER BY b)`,
+/// and then the result expressions that contain them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowPostprocessing {
+    /// A list of `AliasedResultExpr` where each window function is replaced by the column of its values
+    remainder_exprs: Vec<AliasedResultExpr>,
+
+    /// A list of window functions, i.e. `Expression::Window`s, each with the identifier of its column
+    window_exprs: Vec<(Expression, Identifier)>,
+}
+
+/// Collect the window functions of an expression
+fn collect_window_expressions(expr: &Expression, window_exprs: &mut IndexSet<Expression>) {
+    match expr {
+        Expression::Column(_)
+        | Expression::QualifiedColumn { .. }
+        | Expression::Literal(_)
+        | Expression::Wildcard
+        | Expression::Subquery(_) => {}
+        Expression::Window { .. } => {
+            window_exprs.insert(expr.clone());
+        }
+        Expression::Unary { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Aggregation { expr, .. }
+        | Expression::InSubquery { expr, .. } => collect_window_expressions(expr, window_exprs),
+        Expression::Binary { left, right, .. } => {
+            collect_window_expressions(left, window_exprs);
+            collect_window_expressions(right, window_exprs);
+        }
+        Expression::InList { expr, list } => {
+            collect_window_expressions(expr, window_exprs);
+            for item in list {
+                collect_window_expressions(item, window_exprs);
+            }
+        }
+        Expression::Between { expr, low, high } => {
+            collect_window_expressions(expr, window_exprs);
+            collect_window_expressions(low, window_exprs);
+            collect_window_expressions(high, window_exprs);
+        }
+        Expression::Case {
+            when_then,
+            else_result,
+        } => {
+            for (when, then) in when_then {
+                collect_window_expressions(when, window_exprs);
+                collect_window_expressions(then, window_exprs);
+            }
+            if let Some(else_result) = else_result {
+                collect_window_expressions(else_result, window_exprs);
+            }
+        }
+    }
+}
+
+impl WindowPostprocessing {
+    /// Create a new window postprocessing node from the result expressions
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the identifier of the column of a window function cannot be parsed.
+    #[must_use]
+    pub fn new(aliased_exprs: Vec<AliasedResultExpr>) -> Self {
+        let mut window_exprs = IndexSet::default();
+        for aliased_expr in &aliased_exprs {
+            collect_window_expressions(&aliased_expr.expr, &mut window_exprs);
+        }
+        let window_exprs: Vec<(Expression, Identifier)> = window_exprs
+            .into_iter()
+            .enumerate()
+            .map(|(i, expr)| (expr, format!("__col_window_{i}").parse().unwrap()))
+            .collect();
+        // Window functions are replaced just like group by expressions, and there are no aggregations
+        let remainder_exprs = aliased_exprs
+            .into_iter()
+            .map(|aliased_expr| AliasedResultExpr {
+                expr: Box::new(get_aggregate_and_remainder_expressions(
+                    *aliased_expr.expr,
+                    &window_exprs,
+                    &mut IndexMap::default(),
+                )),
+                alias: aliased_expr.alias,
+            })
+            .collect();
+        Self {
+            remainder_exprs,
+            window_exprs,
+        }
+    }
+
+    /// Get remainder expressions for SELECT
+    #[must_use]
+    pub fn remainder_exprs(&self) -> &[AliasedResultExpr] {
+        &self.remainder_exprs
+    }
+
+    /// Get window functions together with the identifiers of their columns
+    #[must_use]
+    pub fn window_exprs(&self) -> &[(Expression, Identifier)] {
+        &self.window_exprs
+    }
+}
+
+/// Get the columns of the given identifiers in the given directions
+fn get_key_columns<S: Scalar>(
+    owned_table: &OwnedTable<S>,
+    keys: impl IntoIterator<Item = (Identifier, OrderByDirection)>,
+) -> PostprocessingResult<Vec<(OwnedColumn<S>, OrderByDirection)>> {
+    keys.into_iter()
+        .map(|(id, direction)| {
+            let column =
+                owned_table
+                    .inner_table()
+                    .get(&id)
+                    .ok_or(PostprocessingError::ColumnNotFound {
+                        column: id.to_string(),
+                    })?;
+            Ok((column.clone(), direction))
+        })
+        .collect()
+}
+
+/// Evaluate a window function
+///
+/// The rows are ordered by the partition keys and then the order keys, keeping their order
+/// in the table on ties. Partitions are the runs of equal partition keys, and rows of a partition
+/// whose order keys are equal are peers.
+fn evaluate_window<S: Scalar>(
+    owned_table: &OwnedTable<S>,
+    function: &WindowFunction,
+    partition_by: &[Identifier],
+    order_by: &[OrderBy],
+) -> PostprocessingResult<OwnedColumn<S>> {
+    let partition_keys = get_key_columns(
+        owned_table,
+        partition_by.iter().map(|&id| (id, OrderByDirection::Asc)),
+    )?;
+    let keys = partition_keys
+        .iter()
+        .cloned()
+        .chain(get_key_columns(
+            owned_table,
+            order_by
+                .iter()
+                .map(|order_by| (order_by.expr, order_by.direction)),
+        )?)
+        .collect::<Vec<_>>();
+    let num_rows = owned_table.num_rows();
+    let mut indexes = (0..num_rows).collect::<Vec<_>>();
+    indexes.sort_by(|&a, &b| compare_indexes_by_owned_columns_with_direction(&keys, a, b));
+
+    // The values of `SUM`, where null values are ignored
+    let alloc = Bump::new();
+    let summands = match function {
+        WindowFunction::Sum(expr) => {
+            let values = owned_table.evaluate(expr)?;
+            let presence = owned_table.evaluate_presence(expr)?;
+            let scalars = Column::<S>::from_owned_column(&values, &alloc).as_scalar(&alloc);
+            let summands = alloc.alloc_slice_fill_with(scalars.len(), |i| {
+                if presence.as_ref().map_or(true, |presence| presence[i]) {
+                    scalars[i]
+                } else {
+                    S::ZERO
+                }
+            });
+            Some((values.column_type(), &*summands))
+        }
+        WindowFunction::RowNumber | WindowFunction::Rank => None,
+    };
+
+    let mut numbers = vec![0; num_rows];
+    let mut sums = vec![S::ZERO; num_rows];
+    let mut partition_start = 0;
+    let mut running_sum = S::ZERO;
+    let mut peers_start = 0;
+    while peers_start < num_rows {
+        let is_new_partition = peers_start == 0
+            || compare_indexes_by_owned_columns_with_direction(
+                &partition_keys,
+                indexes[peers_start - 1],
+                indexes[peers_start],
+            ) != Ordering::Equal;
+        if is_new_partition {
+            partition_start = peers_start;
+            running_sum = S::ZERO;
+        }
+        let peers_end = (peers_start + 1..num_rows)
+            .find(|&k| {
+                compare_indexes_by_owned_columns_with_direction(&keys, indexes[k - 1], indexes[k])
+                    != Ordering::Equal
+            })
+            .unwrap_or(num_rows);
+        if let Some((_, summands)) = summands {
+            for &index in &indexes[peers_start..peers_end] {
+                running_sum += summands[index];
+            }
+        }
+        for (k, &index) in indexes.iter().enumerate().take(peers_end).skip(peers_start) {
+            numbers[index] = match function {
+                WindowFunction::Rank => peers_start - partition_start + 1,
+                WindowFunction::RowNumber | WindowFunction::Sum(_) => k - partition_start + 1,
+            } as i64;
+            sums[index] = running_sum;
+        }
+        peers_start = peers_end;
+    }
+
+    match summands {
+        Some((column_type, _)) => Ok(OwnedColumn::try_from_scalars(&sums, column_type)?),
+        None => Ok(OwnedColumn::BigInt(numbers)),
+    }
+}
+
+impl<S: Scalar> PostprocessingStep<S> for WindowPostprocessing {
+    /// Apply the window functions to the given `OwnedTable`.
+    ///
+    /// Null values are ignored by `SUM`.
+    fn apply(&self, owned_table: OwnedTable<S>) -> PostprocessingResult<OwnedTable<S>> {
+        let window_columns = self
+            .window_exprs
+            .iter()
+            .map(|(expr, id)| -> PostprocessingResult<_> {
+                let Expression::Window {
+                    function,
+                    partition_by,
+                    order_by,
+                } = expr
+                else {
+                    unreachable!("only window functions are collected")
+                };
+                Ok((
+                    *id,
+                    evaluate_window(&owned_table, function, partition_by, order_by)?,
+                ))
+            })
+            .collect::<PostprocessingResult<Vec<_>>>()?;
+        let target_table =
+            OwnedTable::try_from_iter(owned_table.into_inner().into_iter().chain(window_columns))?;
+        let result = self
+            .remainder_exprs
+            .iter()
+            .map(|aliased_expr| evaluate_aliased_result_expr(&target_table, aliased_expr))
+            .flatten_ok()
+            .process_results(|iter| OwnedTable::try_from_iter(iter))??;
+        Ok(result)
+    }
+}
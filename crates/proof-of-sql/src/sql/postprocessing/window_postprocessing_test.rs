@@ -0,0 +1,161 @@
+use crate::{
+    base::{
+        database::{owned_table_utility::*, OwnedTable},
+        scalar::Curve25519Scalar,
+    },
+    sql::postprocessing::{
+        apply_postprocessing_steps, OwnedTablePostprocessing, PostprocessingError,
+        PostprocessingStep, WindowPostprocessing,
+    },
+};
+use proof_of_sql_parser::{
+    intermediate_ast::{OrderByDirection, WindowFunction},
+    utility::{add, aliased_expr, col, ident, lit, order, window},
+};
+
+#[test]
+fn we_can_make_window_postprocessing() {
+    // SELECT a, ROW_NUMBER() OVER (PARTITION BY a) + 1 AS r, SUM(b) OVER () AS s FROM tab
+    let row_number = window(WindowFunction::RowNumber, &["a"], vec![]);
+    let total = window(WindowFunction::Sum(col("b")), &[], vec![]);
+    let res = WindowPostprocessing::new(vec![
+        aliased_expr(col("a"), "a"),
+        aliased_expr(add(row_number.clone(), lit(1)), "r"),
+        aliased_expr(total.clone(), "s"),
+    ]);
+    assert_eq!(
+        res.window_exprs(),
+        &[
+            (*row_number, ident("__col_window_0")),
+            (*total, ident("__col_window_1")),
+        ]
+    );
+    assert_eq!(
+        res.remainder_exprs(),
+        &[
+            aliased_expr(col("a"), "a"),
+            aliased_expr(add(col("__col_window_0"), lit(1)), "r"),
+            aliased_expr(col("__col_window_1"), "s"),
+        ]
+    );
+}
+
+#[test]
+fn we_can_evaluate_window_functions_over_partitions() {
+    // SELECT c, ROW_NUMBER() OVER w AS rn, RANK() OVER w AS rk, SUM(c) OVER w AS s FROM tab
+    // WINDOW w AS (PARTITION BY a ORDER BY b)
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 1, 1, 2]),
+        bigint("b", [3_i64, 1, 2, 3, 1]),
+        bigint("c", [10_i64, 20, 30, 40, 50]),
+    ]);
+    let over = |function| window(function, &["a"], order("b", OrderByDirection::Asc));
+    let postprocessing = [OwnedTablePostprocessing::new_window(
+        WindowPostprocessing::new(vec![
+            aliased_expr(col("c"), "c"),
+            aliased_expr(over(WindowFunction::RowNumber), "rn"),
+            aliased_expr(over(WindowFunction::Rank), "rk"),
+            aliased_expr(over(WindowFunction::Sum(col("c"))), "s"),
+        ]),
+    )];
+    let expected_table = owned_table([
+        bigint("c", [10_i64, 20, 30, 40, 50]),
+        bigint("rn", [2_i64, 1, 1, 3, 2]),
+        bigint("rk", [2_i64, 1, 1, 2, 1]),
+        bigint("s", [80_i64, 70, 30, 80, 70]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_evaluate_window_functions_in_descending_order_without_partitions() {
+    // SELECT RANK() OVER (ORDER BY b DESC) AS rk, SUM(c) OVER (ORDER BY b DESC) AS s,
+    //     SUM(c) OVER () AS total FROM tab
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        int("b", [1_i32, 3, 2, 3]),
+        decimal75("c", 5, 1, [15_i64, -5, 10, 20]),
+    ]);
+    let postprocessing = [OwnedTablePostprocessing::new_window(
+        WindowPostprocessing::new(vec![
+            aliased_expr(
+                window(
+                    WindowFunction::Rank,
+                    &[],
+                    order("b", OrderByDirection::Desc),
+                ),
+                "rk",
+            ),
+            aliased_expr(
+                window(
+                    WindowFunction::Sum(col("c")),
+                    &[],
+                    order("b", OrderByDirection::Desc),
+                ),
+                "s",
+            ),
+            aliased_expr(window(WindowFunction::Sum(col("c")), &[], vec![]), "total"),
+        ]),
+    )];
+    let expected_table = owned_table([
+        bigint("rk", [4_i64, 1, 3, 1]),
+        decimal75("s", 5, 1, [40_i64, 15, 25, 15]),
+        decimal75("total", 5, 1, [40_i64, 40, 40, 40]),
+    ]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_evaluate_window_functions_on_empty_tables() {
+    let table: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]);
+    let postprocessing = [OwnedTablePostprocessing::new_window(
+        WindowPostprocessing::new(vec![
+            aliased_expr(window(WindowFunction::RowNumber, &["a"], vec![]), "rn"),
+            aliased_expr(
+                window(
+                    WindowFunction::Sum(col("b")),
+                    &["a"],
+                    order("b", OrderByDirection::Asc),
+                ),
+                "s",
+            ),
+        ]),
+    )];
+    let expected_table = owned_table([bigint("rn", [0_i64; 0]), bigint("s", [0_i64; 0])]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_can_sum_nullable_expressions_over_windows() {
+    // SELECT SUM(b) OVER (PARTITION BY a) AS s FROM tab
+    let table: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 1, 2, 2]),
+        bigint("b", [5_i64, 7, 9, 3]),
+        boolean("__b_presence__", [true, false, false, true]),
+    ]);
+    let postprocessing = [OwnedTablePostprocessing::new_window(
+        WindowPostprocessing::new(vec![aliased_expr(
+            window(WindowFunction::Sum(col("b")), &["a"], vec![]),
+            "s",
+        )]),
+    )];
+    let expected_table = owned_table([bigint("s", [5_i64, 5, 3, 3])]);
+    let actual_table = apply_postprocessing_steps(table, &postprocessing).unwrap();
+    assert_eq!(actual_table, expected_table);
+}
+
+#[test]
+fn we_cannot_evaluate_window_functions_over_missing_columns() {
+    let table: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [1_i64, 2])]);
+    let postprocessing = WindowPostprocessing::new(vec![aliased_expr(
+        window(WindowFunction::RowNumber, &["b"], vec![]),
+        "rn",
+    )]);
+    assert!(matches!(
+        postprocessing.apply(table),
+        Err(PostprocessingError::ColumnNotFound { .. })
+    ));
+}
@@ -0,0 +1,408 @@
+use super::{
+    query_proof::{compute_range_length, extend_transcript},
+    CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofCounts, ProofPlan,
+    ProvableQueryResult, QueryData, SumcheckMleEvaluations, SumcheckRandomScalars,
+    VerificationBuilder,
+};
+use crate::{
+    base::{
+        bit::BitDistribution,
+        commitment::{Commitment, CommitmentEvaluationProof},
+        database::{Column, CommitmentAccessor, DataAccessor},
+        math::log2_up,
+        polynomial::{compute_evaluation_vector, CompositePolynomialInfo},
+        proof::{Keccak256Transcript, ProofError, Transcript},
+    },
+    proof_primitive::sumcheck::SumcheckProof,
+    sql::proof::QueryError,
+};
+use alloc::{string::String, vec, vec::Vec};
+use bumpalo::Bump;
+use core::cmp;
+use num_traits::Zero;
+use serde::{Deserialize, Serialize};
+
+/// The proof for a batch of queries.
+///
+/// All queries of a batch are proven with a single transcript, a single sumcheck and a single
+/// evaluation proof, so that the cost of the evaluation proof is shared by the whole batch.
+/// The queries have to read tables that start at the same generator offset, e.g. several
+/// queries against the same table snapshot.
+///
+/// Note: Because the class is deserialized from untrusted data, it
+/// cannot maintain any invariant on its data members; hence, they are
+/// all public so as to allow for easy manipulation for testing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BatchQueryProof<CP: CommitmentEvaluationProof> {
+    /// Bit distributions of all queries
+    pub bit_distributions: Vec<BitDistribution>,
+    /// Lengths of intermediate results that are not part of the query results
+    pub one_evaluation_lengths: Vec<usize>,
+    /// Distinct values of string columns that the verifier needs in the clear
+    pub string_dictionaries: Vec<Vec<String>>,
+    /// Commitments to the intermediate MLEs of the first round
+    pub first_round_commitments: Vec<CP::Commitment>,
+    /// Commitments
+    pub commitments: Vec<CP::Commitment>,
+    /// Sumcheck Proof
+    pub sumcheck_proof: SumcheckProof<CP::Scalar>,
+    /// MLEs used in sumcheck except for the result columns
+    pub pcs_proof_evaluations: Vec<CP::Scalar>,
+    /// Inner product proof of the MLEs' evaluations
+    pub evaluation_proof: CP,
+}
+
+impl<CP: CommitmentEvaluationProof> BatchQueryProof<CP> {
+    /// Create a new `BatchQueryProof` along with the results of the queries, in the order of `exprs`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `exprs` is empty or if the queries don't share a generator offset.
+    #[tracing::instrument(name = "BatchQueryProof::new", level = "debug", skip_all)]
+    pub fn new(
+        exprs: &[&(impl ProofPlan<CP::Commitment> + Serialize)],
+        accessor: &impl DataAccessor<CP::Scalar>,
+        setup: &CP::ProverPublicSetup<'_>,
+    ) -> (Self, Vec<ProvableQueryResult>) {
+        assert!(!exprs.is_empty(), "a batch must contain at least one query");
+        let table_lengths: Vec<_> = exprs.iter().map(|expr| expr.get_length(accessor)).collect();
+        let generator_offset = exprs[0].get_offset(accessor);
+        assert!(
+            exprs
+                .iter()
+                .all(|expr| expr.get_offset(accessor) == generator_offset),
+            "the queries of a batch must share a generator offset"
+        );
+
+        let alloc = Bump::new();
+
+        // Evaluate query results
+        let provable_results: Vec<_> = exprs
+            .iter()
+            .zip(&table_lengths)
+            .map(|(expr, &table_length)| {
+                let result_cols = expr.result_evaluate(table_length, &alloc, accessor);
+                let output_length = result_cols.first().map_or(0, Column::len);
+                ProvableQueryResult::new(output_length as u64, &result_cols)
+            })
+            .collect();
+
+        // Prover First Round
+        let mut first_round_builder = FirstRoundBuilder::new();
+        for expr in exprs {
+            expr.first_round_evaluate(&mut first_round_builder, &alloc, accessor);
+        }
+        let one_evaluation_lengths = first_round_builder.one_evaluation_lengths().to_vec();
+        let string_dictionaries = first_round_builder.string_dictionaries().to_vec();
+        let first_round_commitments =
+            first_round_builder.commit_intermediate_mles(generator_offset, setup);
+
+        // The sumcheck domain has to fit the inputs, the outputs and the intermediate results of all queries.
+        let range_length =
+            compute_batch_range_length(&table_lengths, &provable_results, &one_evaluation_lengths);
+        let num_sumcheck_variables = cmp::max(log2_up(range_length), 1);
+
+        // construct a transcript for the proof
+        let mut transcript: Keccak256Transcript =
+            make_batch_transcript(exprs, &provable_results, &table_lengths, generator_offset);
+        transcript.extend_serialize_as_le(&one_evaluation_lengths);
+        transcript.extend_serialize_as_le(&string_dictionaries);
+        transcript.extend_serialize_as_le(&first_round_commitments);
+
+        // These are the challenges of all queries that the verifier sends to the prover after
+        // the prover sends the results.
+        // Note: the last challenge in the vec is the first one that is consumed.
+        let post_result_challenges =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(first_round_builder.num_post_result_challenges())
+                .collect();
+
+        let mut builder = FinalRoundBuilder::new(
+            table_lengths[0],
+            range_length,
+            num_sumcheck_variables,
+            post_result_challenges,
+        );
+        builder.add_first_round_mles(first_round_builder.into_pcs_proof_mles());
+        for (expr, &table_length) in exprs.iter().zip(&table_lengths) {
+            builder.set_table_length(table_length);
+            expr.final_round_evaluate(&mut builder, &alloc, accessor);
+        }
+
+        // commit to any intermediate MLEs
+        let commitments = builder.commit_intermediate_mles(generator_offset, setup);
+
+        // add the commitments and bit distributions to the proof
+        extend_transcript(&mut transcript, &commitments, builder.bit_distributions());
+
+        // construct the sumcheck polynomial of all queries
+        let num_random_scalars = num_sumcheck_variables + builder.num_sumcheck_subpolynomials();
+        let random_scalars: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(num_random_scalars)
+                .collect();
+        let poly = builder.make_sumcheck_polynomial(&SumcheckRandomScalars::new(
+            &random_scalars,
+            range_length,
+            num_sumcheck_variables,
+        ));
+
+        // create the sumcheck proof
+        let mut evaluation_point = vec![Zero::zero(); poly.num_variables];
+        let sumcheck_proof = SumcheckProof::create(&mut transcript, &mut evaluation_point, &poly);
+
+        // evaluate the MLEs used in sumcheck except for the result columns
+        let mut evaluation_vec = vec![Zero::zero(); range_length];
+        compute_evaluation_vector(&mut evaluation_vec, &evaluation_point);
+        let pcs_proof_evaluations = builder.evaluate_pcs_proof_mles(&evaluation_vec);
+
+        // commit to the MLE evaluations
+        transcript.extend_canonical_serialize_as_le(&pcs_proof_evaluations);
+
+        // fold together the MLEs of all queries -- this is the input to the one evaluation proof of the batch
+        let random_scalars: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(pcs_proof_evaluations.len())
+                .collect();
+        let folded_mle = builder.fold_pcs_proof_mles(&random_scalars);
+
+        let evaluation_proof = CP::new(
+            &mut transcript,
+            &folded_mle,
+            &evaluation_point,
+            generator_offset as u64,
+            setup,
+        );
+
+        let proof = Self {
+            bit_distributions: builder.bit_distributions().to_vec(),
+            one_evaluation_lengths,
+            string_dictionaries,
+            first_round_commitments,
+            commitments,
+            sumcheck_proof,
+            pcs_proof_evaluations,
+            evaluation_proof,
+        };
+        (proof, provable_results)
+    }
+
+    #[tracing::instrument(name = "BatchQueryProof::verify", level = "debug", skip_all, err)]
+    /// Verify a `BatchQueryProof` and return the verified results in the order of `exprs`.
+    /// Note: This does NOT transform the results!
+    pub fn verify(
+        &self,
+        exprs: &[&(impl ProofPlan<CP::Commitment> + Serialize)],
+        accessor: &impl CommitmentAccessor<CP::Commitment>,
+        results: &[ProvableQueryResult],
+        setup: &CP::VerifierPublicSetup<'_>,
+    ) -> Result<Vec<QueryData<CP::Scalar>>, QueryError> {
+        if exprs.is_empty() || exprs.len() != results.len() {
+            Err(ProofError::VerificationError {
+                error: "a batch must contain exactly one result per query",
+            })?;
+        }
+        let table_lengths: Vec<_> = exprs.iter().map(|expr| expr.get_length(accessor)).collect();
+        let generator_offset = exprs[0].get_offset(accessor);
+        if exprs
+            .iter()
+            .any(|expr| expr.get_offset(accessor) != generator_offset)
+        {
+            Err(ProofError::VerificationError {
+                error: "the queries of a batch must share a generator offset",
+            })?;
+        }
+        let range_length =
+            compute_batch_range_length(&table_lengths, results, &self.one_evaluation_lengths);
+        let num_sumcheck_variables = cmp::max(log2_up(range_length), 1);
+
+        // validate bit decompositions
+        for dist in &self.bit_distributions {
+            if !dist.is_valid() {
+                Err(ProofError::VerificationError {
+                    error: "invalid bit distributions",
+                })?;
+            }
+        }
+
+        // count terms of all queries
+        let counts = {
+            let mut builder = CountBuilder::new(&self.bit_distributions);
+            for expr in exprs {
+                expr.count(&mut builder, accessor)?;
+            }
+            builder.counts()
+        }?;
+
+        // verify sizes
+        if !self.validate_sizes(&counts) {
+            Err(ProofError::VerificationError {
+                error: "invalid proof size",
+            })?;
+        }
+
+        // construct a transcript for the proof
+        let mut transcript: Keccak256Transcript =
+            make_batch_transcript(exprs, results, &table_lengths, generator_offset);
+        transcript.extend_serialize_as_le(&self.one_evaluation_lengths);
+        transcript.extend_serialize_as_le(&self.string_dictionaries);
+        transcript.extend_serialize_as_le(&self.first_round_commitments);
+
+        let post_result_challenges =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(counts.post_result_challenges)
+                .collect();
+
+        // add the commitments and bit distributions to the proof
+        extend_transcript(&mut transcript, &self.commitments, &self.bit_distributions);
+
+        // draw the random scalars for sumcheck
+        let num_random_scalars = num_sumcheck_variables + counts.sumcheck_subpolynomials;
+        let random_scalars: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(num_random_scalars)
+                .collect();
+        let sumcheck_random_scalars =
+            SumcheckRandomScalars::new(&random_scalars, range_length, num_sumcheck_variables);
+
+        // verify sumcheck up to the evaluation check
+        let poly_info = CompositePolynomialInfo {
+            // This needs to be at least 2 since `CompositePolynomialBuilder::make_composite_polynomial`
+            // always adds a degree 2 term.
+            max_multiplicands: core::cmp::max(counts.sumcheck_max_multiplicands, 2),
+            num_variables: num_sumcheck_variables,
+        };
+        let subclaim = self.sumcheck_proof.verify_without_evaluation(
+            &mut transcript,
+            poly_info,
+            &Zero::zero(),
+        )?;
+
+        // commit to mle evaluations
+        transcript.extend_canonical_serialize_as_le(&self.pcs_proof_evaluations);
+
+        // draw the random scalars for the evaluation proof
+        let evaluation_random_scalars: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(self.pcs_proof_evaluations.len())
+                .collect();
+
+        // pass over the provable ASTs of all queries to fill in the verification builder
+        let sumcheck_evaluations = SumcheckMleEvaluations::new(
+            table_lengths[0],
+            results[0].table_length(),
+            &subclaim.evaluation_point,
+            &sumcheck_random_scalars,
+            &self.pcs_proof_evaluations,
+        );
+        let mut builder = VerificationBuilder::new(
+            generator_offset,
+            sumcheck_evaluations,
+            &self.bit_distributions,
+            &self.first_round_commitments,
+            &self.one_evaluation_lengths,
+            &self.string_dictionaries,
+            &self.commitments,
+            sumcheck_random_scalars.subpolynomial_multipliers,
+            &evaluation_random_scalars,
+            post_result_challenges,
+        );
+        let mut tables = Vec::with_capacity(exprs.len());
+        for ((expr, result), &input_length) in exprs.iter().zip(results).zip(&table_lengths) {
+            let output_length = result.table_length();
+            builder.mle_evaluations.replace_input_length(input_length);
+            builder.mle_evaluations.replace_output_length(output_length);
+            let column_result_fields = expr.get_column_result_fields();
+            let owned_table_result = result.to_owned_table(&column_result_fields[..])?;
+            let verifier_evaluations =
+                expr.verifier_evaluate(&mut builder, accessor, Some(&owned_table_result))?;
+            // compute and check the evaluation of the result MLEs
+            let result_evaluations = result.evaluate(
+                &subclaim.evaluation_point,
+                output_length,
+                &column_result_fields[..],
+            )?;
+            if verifier_evaluations != result_evaluations {
+                Err(ProofError::VerificationError {
+                    error: "result evaluation check failed",
+                })?;
+            }
+            tables.push(owned_table_result);
+        }
+
+        // perform the evaluation check of the sumcheck polynomial
+        if builder.sumcheck_evaluation() != subclaim.expected_evaluation {
+            Err(ProofError::VerificationError {
+                error: "sumcheck evaluation check failed",
+            })?;
+        }
+
+        // finally, check the MLE evaluations of all queries with the one inner product proof
+        let product = builder.folded_pcs_proof_evaluation();
+        self.evaluation_proof
+            .verify_batched_proof(
+                &mut transcript,
+                builder.pcs_proof_commitments(),
+                builder.inner_product_multipliers(),
+                &product,
+                &subclaim.evaluation_point,
+                generator_offset as u64,
+                range_length,
+                setup,
+            )
+            .map_err(|_e| ProofError::VerificationError {
+                error: "Inner product proof of MLE evaluations failed",
+            })?;
+
+        let verification_hash = transcript.challenge_as_le();
+        Ok(tables
+            .into_iter()
+            .map(|table| QueryData {
+                table,
+                verification_hash,
+            })
+            .collect())
+    }
+
+    fn validate_sizes(&self, counts: &ProofCounts) -> bool {
+        self.first_round_commitments.len() == counts.first_round_mles
+            && self.one_evaluation_lengths.len() == counts.one_evaluation_lengths
+            && self.string_dictionaries.len() == counts.string_dictionaries
+            && self.commitments.len() == counts.intermediate_mles
+            && self.pcs_proof_evaluations.len()
+                == counts.first_round_mles + counts.intermediate_mles + counts.anchored_mles
+    }
+}
+
+/// The length of the sumcheck domain, which has to fit the inputs, the outputs and every
+/// intermediate result of all queries of a batch.
+fn compute_batch_range_length(
+    table_lengths: &[usize],
+    results: &[ProvableQueryResult],
+    one_evaluation_lengths: &[usize],
+) -> usize {
+    table_lengths
+        .iter()
+        .zip(results)
+        .map(|(&table_length, result)| {
+            compute_range_length(table_length, result.table_length(), one_evaluation_lengths)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Creates a transcript with all the public inputs of a batch, i.e. the queries, their results,
+/// the lengths of their input tables and the shared generator offset.
+fn make_batch_transcript<C: Commitment, T: Transcript>(
+    exprs: &[&(impl ProofPlan<C> + Serialize)],
+    results: &[ProvableQueryResult],
+    table_lengths: &[usize],
+    generator_offset: usize,
+) -> T {
+    let mut transcript = T::new();
+    transcript.extend_serialize_as_le(results);
+    transcript.extend_serialize_as_le(exprs);
+    transcript.extend_serialize_as_le(table_lengths);
+    transcript.extend_serialize_as_le(&generator_offset);
+    transcript
+}
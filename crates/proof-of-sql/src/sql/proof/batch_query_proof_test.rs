@@ -0,0 +1,176 @@
+use super::{BatchQueryProof, QueryProof};
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTableTestAccessor, TestAccessor},
+        proof::ProofError,
+    },
+    sql::{
+        proof::QueryError,
+        proof_exprs::test_utility::*,
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+
+fn batch_queries(
+    accessor: &OwnedTableTestAccessor<InnerProductProof>,
+) -> Vec<DynProofPlan<RistrettoPoint>> {
+    let t = "sxt.t".parse().unwrap();
+    let u = "sxt.u".parse().unwrap();
+    vec![
+        // select a, b from sxt.t where a >= 3
+        filter(
+            cols_expr_plan(t, &["a", "b"], accessor),
+            tab(t),
+            gte(column(t, "a", accessor), const_bigint(3)),
+        ),
+        // select b, sum(a) as s, count(*) as __count__ from sxt.t group by b
+        group_by(
+            cols_expr_plan(t, &["b"], accessor),
+            vec![sum_expr(column(t, "a", accessor), "s")],
+            "__count__",
+            tab(t),
+            const_bool(true),
+        ),
+        // select c from sxt.u where c = 'x'
+        filter(
+            cols_expr_plan(u, &["c"], accessor),
+            tab(u),
+            equal(column(u, "c", accessor), const_varchar("x")),
+        ),
+    ]
+}
+
+fn batch_accessor() -> OwnedTableTestAccessor<'static, InnerProductProof> {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        "sxt.t".parse().unwrap(),
+        owned_table([
+            bigint("a", [1_i64, 2, 3, 4, 5]),
+            varchar("b", ["p", "q", "p", "q", "p"]),
+        ]),
+        0,
+        (),
+    );
+    accessor.add_table(
+        "sxt.u".parse().unwrap(),
+        owned_table([varchar("c", ["x", "y", "x"])]),
+        0,
+    );
+    accessor
+}
+
+#[test]
+fn we_can_prove_a_batch_of_queries_with_a_single_proof() {
+    let accessor = batch_accessor();
+    let plans = batch_queries(&accessor);
+    let exprs: Vec<_> = plans.iter().collect();
+    let (proof, results) = BatchQueryProof::<InnerProductProof>::new(&exprs, &accessor, &());
+    assert_eq!(results.len(), 3);
+    let tables: Vec<_> = proof
+        .verify(&exprs, &accessor, &results, &())
+        .unwrap()
+        .into_iter()
+        .map(|data| data.table)
+        .collect();
+    assert_eq!(
+        tables,
+        vec![
+            owned_table([bigint("a", [3_i64, 4, 5]), varchar("b", ["p", "q", "p"])]),
+            owned_table([
+                varchar("b", ["p", "q"]),
+                bigint("s", [9_i64, 6]),
+                bigint("__count__", [3_i64, 2])
+            ]),
+            owned_table([varchar("c", ["x", "x"])]),
+        ]
+    );
+
+    // Each query of the batch has the same result as when it is proven on its own
+    for (expr, table) in plans.iter().zip(&tables) {
+        let (single_proof, single_result) =
+            QueryProof::<InnerProductProof>::new(expr, &accessor, &());
+        let single_table = single_proof
+            .verify(expr, &accessor, &single_result, &())
+            .unwrap()
+            .table;
+        assert_eq!(&single_table, table);
+    }
+}
+
+#[test]
+fn we_cannot_verify_a_batch_with_a_tampered_result() {
+    let accessor = batch_accessor();
+    let plans = batch_queries(&accessor);
+    let exprs: Vec<_> = plans.iter().collect();
+    let (proof, mut results) = BatchQueryProof::<InnerProductProof>::new(&exprs, &accessor, &());
+    let t = "sxt.t".parse().unwrap();
+    let (_, tampered_result) = QueryProof::<InnerProductProof>::new(
+        &filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            gte(column(t, "a", &accessor), const_bigint(4)),
+        ),
+        &accessor,
+        &(),
+    );
+    results[0] = tampered_result;
+    assert!(matches!(
+        proof.verify(&exprs, &accessor, &results, &()),
+        Err(QueryError::ProofError { .. })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_batch_against_different_queries() {
+    let accessor = batch_accessor();
+    let plans = batch_queries(&accessor);
+    let exprs: Vec<_> = plans.iter().collect();
+    let (proof, results) = BatchQueryProof::<InnerProductProof>::new(&exprs, &accessor, &());
+
+    // The queries are part of the transcript, so their order matters
+    let mut swapped_exprs = exprs.clone();
+    swapped_exprs.swap(0, 2);
+    let mut swapped_results = results.clone();
+    swapped_results.swap(0, 2);
+    assert!(matches!(
+        proof.verify(&swapped_exprs, &accessor, &swapped_results, &()),
+        Err(QueryError::ProofError { .. })
+    ));
+
+    // Every query needs exactly one result
+    assert!(matches!(
+        proof.verify(&exprs[..2], &accessor, &results[..2], &()),
+        Err(QueryError::ProofError { .. })
+    ));
+    assert!(matches!(
+        proof.verify(&exprs, &accessor, &results[..2], &()),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError { .. }
+        })
+    ));
+}
+
+#[test]
+fn we_cannot_verify_a_batch_of_queries_with_different_offsets() {
+    let mut accessor = batch_accessor();
+    accessor.add_table(
+        "sxt.v".parse().unwrap(),
+        owned_table([bigint("d", [1_i64, 2])]),
+        3,
+    );
+    let v = "sxt.v".parse().unwrap();
+    let plans = batch_queries(&accessor);
+    let mut exprs: Vec<_> = plans.iter().collect();
+    let (proof, mut results) = BatchQueryProof::<InnerProductProof>::new(&exprs, &accessor, &());
+    let plan_on_v = projection(cols_expr_plan(v, &["d"], &accessor), tab(v));
+    exprs[2] = &plan_on_v;
+    let (_, result) = QueryProof::<InnerProductProof>::new(&plan_on_v, &accessor, &());
+    results[2] = result;
+    assert!(matches!(
+        proof.verify(&exprs, &accessor, &results, &()),
+        Err(QueryError::ProofError {
+            source: ProofError::VerificationError { .. }
+        })
+    ));
+}
@@ -50,6 +50,24 @@ impl<'a> CountBuilder<'a> {
         self.counts.intermediate_mles += cnt;
     }
 
+    /// Adds `cnt` to the number of MLEs that are committed to in the first round of the proof,
+    /// i.e. before the post-result challenges are drawn.
+    pub fn count_first_round_mles(&mut self, cnt: usize) {
+        self.counts.first_round_mles += cnt;
+    }
+
+    /// Adds `cnt` to the number of lengths of intermediate results that the prover sends along
+    /// with the first round commitments.
+    pub fn count_one_evaluation_lengths(&mut self, cnt: usize) {
+        self.counts.one_evaluation_lengths += cnt;
+    }
+
+    /// Adds `cnt` to the number of string dictionaries that the prover sends along with the
+    /// first round commitments.
+    pub fn count_string_dictionaries(&mut self, cnt: usize) {
+        self.counts.string_dictionaries += cnt;
+    }
+
     pub fn count_degree(&mut self, degree: usize) {
         self.counts.sumcheck_max_multiplicands =
             max(self.counts.sumcheck_max_multiplicands, degree);
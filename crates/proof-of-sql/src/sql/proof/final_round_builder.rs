@@ -14,6 +14,7 @@ use num_traits::Zero;
 /// Track components used to form a query's proof
 pub struct FinalRoundBuilder<'a, S: Scalar> {
     table_length: usize,
+    range_length: usize,
     num_sumcheck_variables: usize,
     bit_distributions: Vec<BitDistribution>,
     commitment_descriptor: Vec<CommittableColumn<'a>>,
@@ -32,11 +33,13 @@ pub struct FinalRoundBuilder<'a, S: Scalar> {
 impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
     pub fn new(
         table_length: usize,
+        range_length: usize,
         num_sumcheck_variables: usize,
         post_result_challenges: Vec<S>,
     ) -> Self {
         Self {
             table_length,
+            range_length,
             num_sumcheck_variables,
             bit_distributions: Vec::new(),
             commitment_descriptor: Vec::new(),
@@ -46,10 +49,48 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
         }
     }
 
+    /// Register the MLEs committed to in the first round so that they are evaluated and folded
+    /// together with the MLEs of this round.
+    ///
+    /// The first round MLEs always come first in the list of MLEs that are opened by the
+    /// evaluation proof.
+    ///
+    /// # Panics
+    ///
+    /// Panics if MLEs have already been added to the evaluation proof.
+    pub(super) fn add_first_round_mles(
+        &mut self,
+        first_round_mles: Vec<Box<dyn MultilinearExtension<S> + 'a>>,
+    ) {
+        assert!(self.pcs_proof_mles.is_empty());
+        self.pcs_proof_mles = first_round_mles;
+    }
+
+    /// The length of the table that the expressions of the query are evaluated on
     pub fn table_length(&self) -> usize {
         self.table_length
     }
 
+    /// Make the expressions that are evaluated next refer to a table of length `table_length`.
+    ///
+    /// This is needed when several plans, each with its own input table, share one proof.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `table_length` doesn't fit into the sumcheck domain.
+    pub(crate) fn set_table_length(&mut self, table_length: usize) {
+        assert!(table_length <= self.range_length);
+        self.table_length = table_length;
+    }
+
+    /// The length of the sumcheck domain that is actually used.
+    ///
+    /// This can be larger than [`Self::table_length`] if the query has intermediate results that are
+    /// longer than its input, e.g. joins or range checks.
+    pub fn range_length(&self) -> usize {
+        self.range_length
+    }
+
     pub fn num_sumcheck_variables(&self) -> usize {
         self.num_sumcheck_variables
     }
@@ -161,7 +202,7 @@ impl<'a, S: Scalar> FinalRoundBuilder<'a, S> {
     )]
     pub fn fold_pcs_proof_mles(&self, multipliers: &[S]) -> Vec<S> {
         assert_eq!(multipliers.len(), self.pcs_proof_mles.len());
-        let mut res = vec![Zero::zero(); self.table_length];
+        let mut res = vec![Zero::zero(); self.range_length];
         for (multiplier, evaluator) in multipliers.iter().zip(self.pcs_proof_mles.iter()) {
             evaluator.mul_add(&mut res, multiplier);
         }
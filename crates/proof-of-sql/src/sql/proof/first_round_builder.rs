@@ -1,23 +1,40 @@
+use crate::base::{
+    commitment::{Commitment, CommittableColumn, VecCommitmentExt},
+    polynomial::MultilinearExtension,
+    scalar::Scalar,
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+
 /// Track the result created by a query
-pub struct FirstRoundBuilder {
+pub struct FirstRoundBuilder<'a, S: Scalar> {
     /// The number of challenges used in the proof.
     /// Specifically, these are the challenges that the verifier sends to
     /// the prover after the prover sends the result, but before the prover
     /// send commitments to the intermediate witness columns.
     num_post_result_challenges: usize,
+    /// The lengths of intermediate results whose `chi` evaluations the verifier needs.
+    one_evaluation_lengths: Vec<usize>,
+    /// The distinct values of string columns that the verifier needs in the clear.
+    string_dictionaries: Vec<Vec<String>>,
+    commitment_descriptor: Vec<CommittableColumn<'a>>,
+    pcs_proof_mles: Vec<Box<dyn MultilinearExtension<S> + 'a>>,
 }
 
-impl Default for FirstRoundBuilder {
+impl<'a, S: Scalar> Default for FirstRoundBuilder<'a, S> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl FirstRoundBuilder {
+impl<'a, S: Scalar> FirstRoundBuilder<'a, S> {
     /// Create a new result builder for a table with the given length. For multi table queries, this will likely need to change.
     pub fn new() -> Self {
         Self {
             num_post_result_challenges: 0,
+            one_evaluation_lengths: Vec::new(),
+            string_dictionaries: Vec::new(),
+            commitment_descriptor: Vec::new(),
+            pcs_proof_mles: Vec::new(),
         }
     }
 
@@ -38,4 +55,77 @@ impl FirstRoundBuilder {
     pub fn request_post_result_challenges(&mut self, cnt: usize) {
         self.num_post_result_challenges += cnt;
     }
+
+    /// The lengths of intermediate results whose `chi` evaluations the verifier needs.
+    pub(super) fn one_evaluation_lengths(&self) -> &[usize] {
+        &self.one_evaluation_lengths
+    }
+
+    /// Produce the length of an intermediate result that is not part of the query result,
+    /// e.g. the input of a slice.
+    ///
+    /// The length is sent to the verifier along with the first round commitments and is consumed with
+    /// [`VerificationBuilder::consume_one_evaluation_length`](crate::sql::proof::VerificationBuilder::consume_one_evaluation_length).
+    ///
+    /// Note: this must be matched with the same count in the [`CountBuilder`](crate::sql::proof::CountBuilder).
+    pub fn produce_one_evaluation_length(&mut self, length: usize) {
+        self.one_evaluation_lengths.push(length);
+    }
+
+    /// The distinct values of string columns that the verifier needs in the clear.
+    pub(super) fn string_dictionaries(&self) -> &[Vec<String>] {
+        &self.string_dictionaries
+    }
+
+    /// Produce the distinct values of a string column, e.g. so that the verifier can evaluate a
+    /// `LIKE` pattern on them. This reveals the values to the verifier.
+    ///
+    /// The dictionary is sent to the verifier along with the first round commitments and is consumed with
+    /// [`VerificationBuilder::consume_string_dictionary`](crate::sql::proof::VerificationBuilder::consume_string_dictionary).
+    ///
+    /// Note: this must be matched with the same count in the [`CountBuilder`](crate::sql::proof::CountBuilder).
+    pub fn produce_string_dictionary(&mut self, dictionary: Vec<String>) {
+        self.string_dictionaries.push(dictionary);
+    }
+
+    /// Produce an MLE for an intermediate column that is committed to before the post-result
+    /// challenges are drawn.
+    ///
+    /// Witness columns that the prover is free to choose must be produced here, since the
+    /// constraints that bind them are only sound if the challenges are drawn after the commitment.
+    /// The MLE is consumed in the final round with
+    /// [`VerificationBuilder::consume_first_round_mle`](crate::sql::proof::VerificationBuilder::consume_first_round_mle).
+    ///
+    /// Note: this must be matched with the same count in the [`CountBuilder`](crate::sql::proof::CountBuilder).
+    pub fn produce_intermediate_mle(
+        &mut self,
+        data: impl MultilinearExtension<S> + Into<CommittableColumn<'a>> + Copy + 'a,
+    ) {
+        self.commitment_descriptor.push(data.into());
+        self.pcs_proof_mles.push(Box::new(data));
+    }
+
+    /// Compute commitments of all the first round MLEs
+    #[tracing::instrument(
+        name = "FirstRoundBuilder::commit_intermediate_mles",
+        level = "debug",
+        skip_all
+    )]
+    pub fn commit_intermediate_mles<C: Commitment>(
+        &self,
+        offset_generators: usize,
+        setup: &C::PublicSetup<'_>,
+    ) -> Vec<C> {
+        Vec::from_commitable_columns_with_offset(
+            &self.commitment_descriptor,
+            offset_generators,
+            setup,
+        )
+    }
+
+    /// Hand over the first round MLEs so that they can be evaluated and folded together with the
+    /// MLEs of the final round.
+    pub(super) fn into_pcs_proof_mles(self) -> Vec<Box<dyn MultilinearExtension<S> + 'a>> {
+        self.pcs_proof_mles
+    }
 }
@@ -0,0 +1,585 @@
+use super::{
+    query_proof::{compute_range_length, make_transcript},
+    CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofCounts, ProofPlan,
+    ProvableQueryResult, QueryData, QueryResult, SumcheckMleEvaluations, SumcheckRandomScalars,
+    VerificationBuilder,
+};
+use crate::{
+    base::{
+        database::{BlindingFactorAccessor, Column, CommitmentAccessor, DataAccessor, OwnedTable},
+        map::IndexMap,
+        math::log2_up,
+        polynomial::{compute_evaluation_vector, CompositePolynomialInfo},
+        proof::{Keccak256Transcript, ProofError, Transcript},
+        scalar::{Curve25519Scalar, Scalar as _},
+        slice_ops,
+    },
+    proof_primitive::{
+        hiding::{MaskedSumcheckProof, PolynomialRelationProof},
+        inner_product::{
+            compute_curve25519_blinding_generator, compute_curve25519_generator,
+            compute_curve25519_hiding_commitment, HidingInnerProductProof,
+        },
+    },
+};
+use alloc::{vec, vec::Vec};
+use ark_std::{
+    rand::{CryptoRng, RngCore},
+    UniformRand,
+};
+use bumpalo::Bump;
+use core::cmp;
+use curve25519_dalek::{
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    scalar::Scalar,
+    traits::Identity,
+};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+/// Errors that can occur when creating a [`HidingQueryProof`]
+#[derive(Snafu, Debug)]
+pub enum HidingQueryProofError {
+    /// The query can only be proven with a proof that reveals data beyond the query result.
+    #[snafu(display("the query cannot be proven in zero knowledge: {error}"))]
+    UnsupportedQuery {
+        /// The reason why the query is unsupported
+        error: &'static str,
+    },
+}
+
+/// A zero-knowledge proof for a query, which reveals nothing about the queried tables beyond the query result
+/// and the lengths of intermediate results.
+///
+/// The proof follows [`QueryProof`](super::QueryProof) over Curve25519, but
+/// - the table commitments are hiding commitments, whose blinding factors only the prover knows,
+///   see [`BlindingFactorAccessor`],
+/// - the intermediate MLEs are committed to with hiding commitments,
+/// - the sumcheck proof is a [`MaskedSumcheckProof`], which only yields a commitment to the evaluation
+///   of the sumcheck polynomial,
+/// - the MLE evaluations are only sent as hiding commitments, and the verifier's evaluation checks are
+///   proven on them with a [`PolynomialRelationProof`],
+/// - the MLE evaluations are proven with a [`HidingInnerProductProof`].
+///
+/// Queries whose proof reveals data, i.e. queries that need bit distributions or string dictionaries,
+/// can't be proven with this proof.
+///
+/// Note: Because the class is deserialized from untrusted data, it
+/// cannot maintain any invariant on its data members; hence, they are
+/// all public so as to allow for easy manipulation for testing.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HidingQueryProof {
+    /// Lengths of intermediate results that are not part of the query result
+    pub one_evaluation_lengths: Vec<usize>,
+    /// Hiding commitments to the intermediate MLEs of the first round
+    pub first_round_commitments: Vec<RistrettoPoint>,
+    /// Hiding commitments to the intermediate MLEs of the final round
+    pub commitments: Vec<RistrettoPoint>,
+    /// Masked sumcheck proof
+    pub sumcheck_proof: MaskedSumcheckProof,
+    /// Hiding commitments to the evaluations of the MLEs used in sumcheck except for the result columns
+    pub evaluation_commitments: Vec<CompressedRistretto>,
+    /// Proof that the committed evaluations pass the evaluation checks of the verifier
+    pub relation_proof: PolynomialRelationProof,
+    /// Inner product proof of the committed MLE evaluations
+    pub evaluation_proof: HidingInnerProductProof,
+}
+
+/// Hide `commitment` with a random blinding factor, and return the hiding commitment with its blinding factor.
+fn add_blind(
+    commitment: RistrettoPoint,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> (RistrettoPoint, Curve25519Scalar) {
+    let blind = Curve25519Scalar::rand(rng);
+    (
+        commitment + Scalar::from(&blind) * compute_curve25519_blinding_generator(),
+        blind,
+    )
+}
+
+/// `sumcheck_evaluation + gamma * evaluations[0] + gamma^2 * evaluations[1] + ...`
+fn batch_evaluations(
+    sumcheck_evaluation: Curve25519Scalar,
+    evaluations: &[Curve25519Scalar],
+    gamma: Curve25519Scalar,
+) -> Curve25519Scalar {
+    evaluations
+        .iter()
+        .rev()
+        .fold(Curve25519Scalar::ZERO, |acc, &evaluation| {
+            (acc + evaluation) * gamma
+        })
+        + sumcheck_evaluation
+}
+
+/// Everything the verifier's pass over the proof plan needs besides the MLE evaluations.
+///
+/// Both the prover and the verifier run the pass on MLE evaluations that are not the actual ones,
+/// so that the verifier's evaluation checks can be proven on committed evaluations.
+struct PlanEvaluator<'a, P, A> {
+    expr: &'a P,
+    accessor: &'a A,
+    input_length: usize,
+    output_length: usize,
+    generator_offset: usize,
+    evaluation_point: &'a [Curve25519Scalar],
+    sumcheck_random_scalars: &'a SumcheckRandomScalars<'a, Curve25519Scalar>,
+    first_round_commitments: &'a [RistrettoPoint],
+    one_evaluation_lengths: &'a [usize],
+    commitments: &'a [RistrettoPoint],
+    inner_product_multipliers: &'a [Curve25519Scalar],
+    post_result_challenges: &'a [Curve25519Scalar],
+    result: &'a OwnedTable<Curve25519Scalar>,
+}
+
+/// The outcome of the verifier's pass over the proof plan.
+struct PlanEvaluation {
+    sumcheck_evaluation: Curve25519Scalar,
+    result_evaluations: Vec<Curve25519Scalar>,
+    pcs_proof_commitments: Vec<RistrettoPoint>,
+}
+
+impl<P: ProofPlan<RistrettoPoint>, A: CommitmentAccessor<RistrettoPoint>> PlanEvaluator<'_, P, A> {
+    /// Run the verifier's pass over the proof plan as if `pcs_proof_evaluations` were the MLE evaluations.
+    fn evaluate(
+        &self,
+        pcs_proof_evaluations: &[Curve25519Scalar],
+    ) -> Result<PlanEvaluation, ProofError> {
+        let sumcheck_evaluations = SumcheckMleEvaluations::new(
+            self.input_length,
+            self.output_length,
+            self.evaluation_point,
+            self.sumcheck_random_scalars,
+            pcs_proof_evaluations,
+        );
+        let mut builder = VerificationBuilder::new(
+            self.generator_offset,
+            sumcheck_evaluations,
+            &[],
+            self.first_round_commitments,
+            self.one_evaluation_lengths,
+            &[],
+            self.commitments,
+            self.sumcheck_random_scalars.subpolynomial_multipliers,
+            self.inner_product_multipliers,
+            self.post_result_challenges.to_vec(),
+        );
+        let result_evaluations =
+            self.expr
+                .verifier_evaluate(&mut builder, self.accessor, Some(self.result))?;
+        Ok(PlanEvaluation {
+            sumcheck_evaluation: builder.sumcheck_evaluation(),
+            result_evaluations,
+            pcs_proof_commitments: builder.pcs_proof_commitments().to_vec(),
+        })
+    }
+}
+
+impl HidingQueryProof {
+    /// Create a new `HidingQueryProof`.
+    ///
+    /// The commitments of `accessor` have to be hiding commitments, whose blinding factors are the ones of `accessor`.
+    ///
+    /// `rng` must be a cryptographically secure source of randomness, since all blinding factors are drawn from it.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the result of the query cannot be converted to a table.
+    #[tracing::instrument(name = "HidingQueryProof::new", level = "debug", skip_all)]
+    #[allow(clippy::too_many_lines)]
+    pub fn new(
+        expr: &(impl ProofPlan<RistrettoPoint> + Serialize),
+        accessor: &(impl DataAccessor<Curve25519Scalar>
+              + CommitmentAccessor<RistrettoPoint>
+              + BlindingFactorAccessor<Curve25519Scalar>),
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> Result<(Self, ProvableQueryResult), HidingQueryProofError> {
+        let table_length = expr.get_length(accessor);
+        let generator_offset = expr.get_offset(accessor);
+
+        let alloc = Bump::new();
+
+        // Evaluate query result
+        let result_cols = expr.result_evaluate(table_length, &alloc, accessor);
+        let output_length = result_cols.first().map_or(0, Column::len);
+        let provable_result = ProvableQueryResult::new(output_length as u64, &result_cols);
+
+        // Prover First Round
+        let mut first_round_builder = FirstRoundBuilder::new();
+        expr.first_round_evaluate(&mut first_round_builder, &alloc, accessor);
+        if !first_round_builder.string_dictionaries().is_empty() {
+            return Err(HidingQueryProofError::UnsupportedQuery {
+                error: "string dictionaries reveal the distinct values of a column",
+            });
+        }
+        let one_evaluation_lengths = first_round_builder.one_evaluation_lengths().to_vec();
+        let (first_round_commitments, first_round_blinds): (Vec<_>, Vec<_>) = first_round_builder
+            .commit_intermediate_mles::<RistrettoPoint>(generator_offset, &())
+            .into_iter()
+            .map(|commitment| add_blind(commitment, rng))
+            .unzip();
+
+        let range_length =
+            compute_range_length(table_length, output_length, &one_evaluation_lengths);
+        let num_sumcheck_variables = cmp::max(log2_up(range_length), 1);
+
+        // construct a transcript for the proof
+        let mut transcript: Keccak256Transcript =
+            make_transcript(expr, &provable_result, table_length, generator_offset);
+        transcript.extend_serialize_as_le(&one_evaluation_lengths);
+        transcript.extend_serialize_as_le(&first_round_commitments);
+
+        let post_result_challenges: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(first_round_builder.num_post_result_challenges())
+                .collect();
+
+        let mut builder = FinalRoundBuilder::new(
+            table_length,
+            range_length,
+            num_sumcheck_variables,
+            post_result_challenges.clone(),
+        );
+        builder.add_first_round_mles(first_round_builder.into_pcs_proof_mles());
+        expr.final_round_evaluate(&mut builder, &alloc, accessor);
+        if !builder.bit_distributions().is_empty() {
+            return Err(HidingQueryProofError::UnsupportedQuery {
+                error: "bit distributions reveal the constant bits of a column",
+            });
+        }
+
+        // commit to any intermediate MLEs
+        let (commitments, intermediate_blinds): (Vec<_>, Vec<_>) = builder
+            .commit_intermediate_mles::<RistrettoPoint>(generator_offset, &())
+            .into_iter()
+            .map(|commitment| add_blind(commitment, rng))
+            .unzip();
+        transcript.extend_serialize_as_le(&commitments);
+
+        // construct the sumcheck polynomial
+        let num_random_scalars = num_sumcheck_variables + builder.num_sumcheck_subpolynomials();
+        let random_scalars: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(num_random_scalars)
+                .collect();
+        let sumcheck_random_scalars =
+            SumcheckRandomScalars::new(&random_scalars, range_length, num_sumcheck_variables);
+        let poly = builder.make_sumcheck_polynomial(&sumcheck_random_scalars);
+        let degree = poly.max_multiplicands;
+
+        // create the masked sumcheck proof, which only commits to the evaluation of the sumcheck polynomial
+        let mut evaluation_point = vec![Curve25519Scalar::ZERO; poly.num_variables];
+        let (sumcheck_proof, sumcheck_evaluation_blind) =
+            MaskedSumcheckProof::create(&mut transcript, &mut evaluation_point, &poly, rng);
+
+        // commit to the evaluations of the MLEs used in sumcheck except for the result columns
+        let mut evaluation_vec = vec![Curve25519Scalar::ZERO; range_length];
+        compute_evaluation_vector(&mut evaluation_vec, &evaluation_point);
+        let pcs_proof_evaluations = builder.evaluate_pcs_proof_mles(&evaluation_vec);
+        let (evaluation_commitments, evaluation_blinds): (Vec<_>, Vec<_>) = pcs_proof_evaluations
+            .iter()
+            .map(|&evaluation| {
+                let blind = Curve25519Scalar::rand(rng);
+                (
+                    compute_curve25519_hiding_commitment(&[evaluation.into()], blind.into(), 0)
+                        .compress(),
+                    blind,
+                )
+            })
+            .unzip();
+        transcript.extend_as_le_from_refs(
+            evaluation_commitments
+                .iter()
+                .map(CompressedRistretto::as_bytes),
+        );
+        let inner_product_multipliers: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(pcs_proof_evaluations.len())
+                .collect();
+        let gamma: Curve25519Scalar = transcript.scalar_challenge_as_be();
+
+        // prove the verifier's evaluation checks on the committed evaluations
+        let owned_table_result = provable_result
+            .to_owned_table(&expr.get_column_result_fields())
+            .expect("the prover's own result should be a valid table");
+        let evaluator = PlanEvaluator {
+            expr,
+            accessor,
+            input_length: table_length,
+            output_length,
+            generator_offset,
+            evaluation_point: &evaluation_point,
+            sumcheck_random_scalars: &sumcheck_random_scalars,
+            first_round_commitments: &first_round_commitments,
+            one_evaluation_lengths: &one_evaluation_lengths,
+            commitments: &commitments,
+            inner_product_multipliers: &inner_product_multipliers,
+            post_result_challenges: &post_result_challenges,
+            result: &owned_table_result,
+        };
+        let pcs_proof_commitments = evaluator
+            .evaluate(&pcs_proof_evaluations)
+            .expect("the verifier's pass should succeed on the prover's own evaluations")
+            .pcs_proof_commitments;
+        let relation_proof = PolynomialRelationProof::create(
+            &mut transcript,
+            &pcs_proof_evaluations,
+            &evaluation_blinds,
+            sumcheck_evaluation_blind,
+            degree,
+            |evaluations| {
+                let evaluation = evaluator.evaluate(evaluations).ok()?;
+                Some(batch_evaluations(
+                    evaluation.sumcheck_evaluation,
+                    &evaluation.result_evaluations,
+                    gamma,
+                ))
+            },
+            rng,
+        )
+        .ok_or(HidingQueryProofError::UnsupportedQuery {
+            error:
+                "the evaluation checks of the verifier are not a polynomial in the MLE evaluations",
+        })?;
+
+        // fold the blinding factors of the commitments the same way the verifier folds the commitments
+        let blinds: IndexMap<_, _> = expr
+            .get_column_references()
+            .into_iter()
+            .map(|column| {
+                (
+                    accessor.get_commitment(column).compress(),
+                    accessor.get_blinding_factor(column),
+                )
+            })
+            .chain(
+                first_round_commitments
+                    .iter()
+                    .chain(&commitments)
+                    .map(RistrettoPoint::compress)
+                    .zip(first_round_blinds.into_iter().chain(intermediate_blinds)),
+            )
+            .collect();
+        let folded_blind = pcs_proof_commitments
+            .iter()
+            .zip(&inner_product_multipliers)
+            .map(|(commitment, &multiplier)| {
+                blinds
+                    .get(&commitment.compress())
+                    .map(|&blind| multiplier * blind)
+            })
+            .sum::<Option<Curve25519Scalar>>()
+            .ok_or(HidingQueryProofError::UnsupportedQuery {
+                error: "the blinding factor of a commitment is unknown",
+            })?;
+        let folded_evaluation_blind: Curve25519Scalar = evaluation_blinds
+            .iter()
+            .zip(&inner_product_multipliers)
+            .map(|(&blind, &multiplier)| multiplier * blind)
+            .sum();
+
+        // finally, form the hiding inner product proof of the MLEs' evaluations
+        let folded_mle = builder.fold_pcs_proof_mles(&inner_product_multipliers);
+        let evaluation_proof = transcript.wrap_transcript(|transcript| {
+            HidingInnerProductProof::create(
+                transcript,
+                &slice_ops::slice_cast(&folded_mle),
+                folded_blind.into(),
+                &slice_ops::slice_cast(&evaluation_vec),
+                folded_evaluation_blind.into(),
+                generator_offset as u64,
+                rng,
+            )
+        });
+
+        let proof = Self {
+            one_evaluation_lengths,
+            first_round_commitments,
+            commitments,
+            sumcheck_proof,
+            evaluation_commitments,
+            relation_proof,
+            evaluation_proof,
+        };
+        Ok((proof, provable_result))
+    }
+
+    #[tracing::instrument(name = "HidingQueryProof::verify", level = "debug", skip_all, err)]
+    /// Verify a `HidingQueryProof`. Note: This does NOT transform the result!
+    pub fn verify(
+        &self,
+        expr: &(impl ProofPlan<RistrettoPoint> + Serialize),
+        accessor: &impl CommitmentAccessor<RistrettoPoint>,
+        result: &ProvableQueryResult,
+    ) -> QueryResult<Curve25519Scalar> {
+        let input_length = expr.get_length(accessor);
+        let output_length = result.table_length();
+        let range_length =
+            compute_range_length(input_length, output_length, &self.one_evaluation_lengths);
+        let generator_offset = expr.get_offset(accessor);
+        let num_sumcheck_variables = cmp::max(log2_up(range_length), 1);
+
+        // count terms; the proof has no bit distributions
+        let counts = {
+            let mut builder = CountBuilder::new(&[]);
+            expr.count(&mut builder, accessor)?;
+            builder.counts()
+        }?;
+
+        // verify sizes
+        if !self.validate_sizes(&counts) {
+            Err(ProofError::VerificationError {
+                error: "invalid proof size",
+            })?;
+        }
+
+        // construct a transcript for the proof
+        let mut transcript: Keccak256Transcript =
+            make_transcript(expr, result, input_length, generator_offset);
+        transcript.extend_serialize_as_le(&self.one_evaluation_lengths);
+        transcript.extend_serialize_as_le(&self.first_round_commitments);
+
+        let post_result_challenges: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(counts.post_result_challenges)
+                .collect();
+
+        transcript.extend_serialize_as_le(&self.commitments);
+
+        // draw the random scalars for sumcheck
+        let num_random_scalars = num_sumcheck_variables + counts.sumcheck_subpolynomials;
+        let random_scalars: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(num_random_scalars)
+                .collect();
+        let sumcheck_random_scalars =
+            SumcheckRandomScalars::new(&random_scalars, range_length, num_sumcheck_variables);
+
+        // verify the masked sumcheck up to the evaluation check
+        let degree = cmp::max(counts.sumcheck_max_multiplicands, 2);
+        let poly_info = CompositePolynomialInfo {
+            max_multiplicands: degree,
+            num_variables: num_sumcheck_variables,
+        };
+        let subclaim = self.sumcheck_proof.verify_without_evaluation(
+            &mut transcript,
+            poly_info,
+            &Curve25519Scalar::ZERO,
+        )?;
+
+        // commit to mle evaluations
+        let evaluation_commitments = self
+            .evaluation_commitments
+            .iter()
+            .map(|commitment| {
+                commitment
+                    .decompress()
+                    .ok_or(ProofError::VerificationError {
+                        error: "invalid evaluation commitment",
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        transcript.extend_as_le_from_refs(
+            self.evaluation_commitments
+                .iter()
+                .map(CompressedRistretto::as_bytes),
+        );
+        let inner_product_multipliers: Vec<_> =
+            core::iter::repeat_with(|| transcript.scalar_challenge_as_be())
+                .take(evaluation_commitments.len())
+                .collect();
+        let gamma: Curve25519Scalar = transcript.scalar_challenge_as_be();
+
+        // compute the evaluation of the result MLEs
+        let column_result_fields = expr.get_column_result_fields();
+        let owned_table_result = result.to_owned_table(&column_result_fields[..])?;
+        let result_evaluations = result.evaluate(
+            &subclaim.evaluation_point,
+            output_length,
+            &column_result_fields[..],
+        )?;
+
+        // check that the verifier's pass over the proof plan evaluates the sumcheck polynomial and the result
+        // MLEs to the committed sumcheck evaluation and the actual result evaluations
+        let evaluator = PlanEvaluator {
+            expr,
+            accessor,
+            input_length,
+            output_length,
+            generator_offset,
+            evaluation_point: &subclaim.evaluation_point,
+            sumcheck_random_scalars: &sumcheck_random_scalars,
+            first_round_commitments: &self.first_round_commitments,
+            one_evaluation_lengths: &self.one_evaluation_lengths,
+            commitments: &self.commitments,
+            inner_product_multipliers: &inner_product_multipliers,
+            post_result_challenges: &post_result_challenges,
+            result: &owned_table_result,
+        };
+        let target_commitment = subclaim.expected_evaluation_commitment
+            + Scalar::from(&batch_evaluations(
+                Curve25519Scalar::ZERO,
+                &result_evaluations,
+                gamma,
+            )) * compute_curve25519_generator(0);
+        let mut pcs_proof_commitments = Vec::new();
+        self.relation_proof.verify(
+            &mut transcript,
+            &evaluation_commitments,
+            &target_commitment,
+            degree,
+            |evaluations| {
+                let evaluation = evaluator.evaluate(evaluations)?;
+                if evaluation.result_evaluations.len() != result_evaluations.len() {
+                    Err(ProofError::VerificationError {
+                        error: "result evaluation check failed",
+                    })?;
+                }
+                pcs_proof_commitments = evaluation.pcs_proof_commitments;
+                Ok(batch_evaluations(
+                    evaluation.sumcheck_evaluation,
+                    &evaluation.result_evaluations,
+                    gamma,
+                ))
+            },
+        )?;
+
+        // finally, check the committed MLE evaluations with the hiding inner product proof
+        let fold = |commitments: &[RistrettoPoint]| {
+            commitments.iter().zip(&inner_product_multipliers).fold(
+                RistrettoPoint::identity(),
+                |acc, (commitment, multiplier)| acc + Scalar::from(multiplier) * commitment,
+            )
+        };
+        let mut evaluation_vec = vec![Curve25519Scalar::ZERO; range_length];
+        compute_evaluation_vector(&mut evaluation_vec, &subclaim.evaluation_point);
+        transcript
+            .wrap_transcript(|transcript| {
+                self.evaluation_proof.verify(
+                    transcript,
+                    &fold(&pcs_proof_commitments),
+                    &fold(&evaluation_commitments),
+                    &slice_ops::slice_cast(&evaluation_vec),
+                    generator_offset as u64,
+                )
+            })
+            .map_err(|_e| ProofError::VerificationError {
+                error: "Inner product proof of MLE evaluations failed",
+            })?;
+
+        let verification_hash = transcript.challenge_as_le();
+        Ok(QueryData {
+            table: owned_table_result,
+            verification_hash,
+        })
+    }
+
+    fn validate_sizes(&self, counts: &ProofCounts) -> bool {
+        counts.string_dictionaries == 0
+            && self.first_round_commitments.len() == counts.first_round_mles
+            && self.one_evaluation_lengths.len() == counts.one_evaluation_lengths
+            && self.commitments.len() == counts.intermediate_mles
+            && self.evaluation_commitments.len()
+                == counts.first_round_mles + counts.intermediate_mles + counts.anchored_mles
+    }
+}
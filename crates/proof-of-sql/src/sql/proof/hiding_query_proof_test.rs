@@ -0,0 +1,152 @@
+use super::{HidingQueryProof, HidingQueryProofError, ProvableQueryResult};
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, Column, HidingTableTestAccessor, OwnedTableTestAccessor,
+            TableRef,
+        },
+        scalar::{Curve25519Scalar, Scalar},
+    },
+    proof_primitive::inner_product::{compute_curve25519_generator, InnerProductProof},
+    sql::{
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::{test_utility::*, DynProofPlan},
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+use rand::{rngs::StdRng, SeedableRng};
+
+fn filter_plan(
+    t: TableRef,
+    accessor: &HidingTableTestAccessor,
+    where_clause: DynProofExpr<RistrettoPoint>,
+) -> DynProofPlan<RistrettoPoint> {
+    filter(
+        cols_expr_plan(t, &["a", "b"], accessor),
+        tab(t),
+        where_clause,
+    )
+}
+
+fn test_accessor(t: TableRef, rng: &mut StdRng) -> HidingTableTestAccessor {
+    let data = owned_table([
+        bigint("a", [1_i64, 4, 5, 2, 5]),
+        bigint("b", [1_i64, 2, 3, 4, 5]),
+    ]);
+    HidingTableTestAccessor::new_from_table(t, data, 0, rng)
+}
+
+#[test]
+fn we_can_prove_and_verify_a_query_in_zero_knowledge() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = test_accessor(t, &mut rng);
+    let expr = filter_plan(
+        t,
+        &accessor,
+        equal(column(t, "a", &accessor), const_bigint(5)),
+    );
+    let (proof, result) = HidingQueryProof::new(&expr, &accessor, &mut rng).unwrap();
+    let table = proof.verify(&expr, &accessor, &result).unwrap().table;
+    assert_eq!(
+        table,
+        owned_table([bigint("a", [5_i64, 5]), bigint("b", [3_i64, 5])])
+    );
+}
+
+#[test]
+fn proofs_of_the_same_query_do_not_repeat_any_revealed_evaluations() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = test_accessor(t, &mut rng);
+    let expr = filter_plan(
+        t,
+        &accessor,
+        equal(column(t, "a", &accessor), const_bigint(5)),
+    );
+    let (proof, _) = HidingQueryProof::new(&expr, &accessor, &mut rng).unwrap();
+    let (other_proof, _) = HidingQueryProof::new(&expr, &accessor, &mut rng).unwrap();
+    assert!(proof
+        .evaluation_commitments
+        .iter()
+        .all(|commitment| !other_proof.evaluation_commitments.contains(commitment)));
+    assert!(proof
+        .relation_proof
+        .shifted_values
+        .iter()
+        .all(|value| !other_proof.relation_proof.shifted_values.contains(value)));
+}
+
+#[test]
+fn we_cannot_verify_a_hiding_query_proof_with_a_tampered_result_or_proof() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = test_accessor(t, &mut rng);
+    let expr = filter_plan(
+        t,
+        &accessor,
+        equal(column(t, "a", &accessor), const_bigint(5)),
+    );
+    let (proof, _) = HidingQueryProof::new(&expr, &accessor, &mut rng).unwrap();
+
+    let tampered_result = ProvableQueryResult::new(
+        2,
+        &[
+            Column::<Curve25519Scalar>::BigInt(&[5, 5]),
+            Column::BigInt(&[3, 6]),
+        ],
+    );
+    assert!(proof.verify(&expr, &accessor, &tampered_result).is_err());
+
+    let (_, result) = HidingQueryProof::new(&expr, &accessor, &mut rng).unwrap();
+    let mut tampered_proof = proof.clone();
+    tampered_proof.evaluation_commitments[0] = (tampered_proof.evaluation_commitments[0]
+        .decompress()
+        .unwrap()
+        + compute_curve25519_generator(0))
+    .compress();
+    assert!(tampered_proof.verify(&expr, &accessor, &result).is_err());
+    let mut tampered_proof = proof.clone();
+    tampered_proof.relation_proof.shifted_values[0] += Curve25519Scalar::ONE;
+    assert!(tampered_proof.verify(&expr, &accessor, &result).is_err());
+    assert!(proof.verify(&expr, &accessor, &result).is_ok());
+}
+
+#[test]
+fn we_cannot_verify_a_hiding_query_proof_against_commitments_without_blinding_factors() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = test_accessor(t, &mut rng);
+    let expr = filter_plan(
+        t,
+        &accessor,
+        equal(column(t, "a", &accessor), const_bigint(5)),
+    );
+    let (proof, result) = HidingQueryProof::new(&expr, &accessor, &mut rng).unwrap();
+    let plain_accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+        t,
+        owned_table([
+            bigint("a", [1_i64, 4, 5, 2, 5]),
+            bigint("b", [1_i64, 2, 3, 4, 5]),
+        ]),
+        0,
+        (),
+    );
+    assert!(proof.verify(&expr, &plain_accessor, &result).is_err());
+}
+
+#[test]
+fn we_cannot_prove_a_query_whose_proof_reveals_bit_distributions() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = test_accessor(t, &mut rng);
+    let expr = filter_plan(
+        t,
+        &accessor,
+        lte(column(t, "a", &accessor), const_bigint(4)),
+    );
+    assert!(matches!(
+        HidingQueryProof::new(&expr, &accessor, &mut rng),
+        Err(HidingQueryProofError::UnsupportedQuery { .. })
+    ));
+}
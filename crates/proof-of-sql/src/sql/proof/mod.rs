@@ -45,6 +45,18 @@ pub use query_proof::QueryProof;
 #[cfg(all(test, feature = "blitzar"))]
 mod query_proof_test;
 
+#[cfg(feature = "std")]
+mod hiding_query_proof;
+#[cfg(feature = "std")]
+pub use hiding_query_proof::{HidingQueryProof, HidingQueryProofError};
+#[cfg(all(test, feature = "std"))]
+mod hiding_query_proof_test;
+
+mod batch_query_proof;
+pub use batch_query_proof::BatchQueryProof;
+#[cfg(all(test, feature = "blitzar"))]
+mod batch_query_proof_test;
+
 mod query_result;
 pub use query_result::{QueryData, QueryError, QueryResult};
 
@@ -8,6 +8,15 @@ pub struct ProofCounts {
     pub result_columns: usize,
     pub anchored_mles: usize,
     pub intermediate_mles: usize,
+    /// The number of intermediate MLEs that are committed to before the post-result challenges
+    /// are drawn.
+    pub first_round_mles: usize,
+    /// The number of lengths of intermediate results that the prover sends along with the first
+    /// round commitments.
+    pub one_evaluation_lengths: usize,
+    /// The number of string dictionaries that the prover sends along with the first round
+    /// commitments.
+    pub string_dictionaries: usize,
     pub sumcheck_subpolynomials: usize,
 
     /// The number of challenges used in the proof.
@@ -27,6 +36,9 @@ impl ProofCounts {
         tracing::info!("result_columns = {:?}", self.result_columns);
         tracing::info!("anchored_mles = {:?}", self.anchored_mles);
         tracing::info!("intermediate_mles = {:?}", self.intermediate_mles);
+        tracing::info!("first_round_mles = {:?}", self.first_round_mles);
+        tracing::info!("one_evaluation_lengths = {:?}", self.one_evaluation_lengths);
+        tracing::info!("string_dictionaries = {:?}", self.string_dictionaries);
         tracing::info!(
             "sumcheck_subpolynomials = {:?}",
             self.sumcheck_subpolynomials
@@ -58,7 +58,14 @@ pub trait ProverEvaluate<S: Scalar> {
     ) -> Vec<Column<'a, S>>;
 
     /// Evaluate the query and modify `FirstRoundBuilder` to form the query's proof.
-    fn first_round_evaluate(&self, builder: &mut FirstRoundBuilder);
+    ///
+    /// Intermediate MLEs produced here are committed to before the post-result challenges are drawn.
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<S>,
+    );
 
     /// Evaluate the query and modify `FinalRoundBuilder` to store an intermediate representation
     /// of the query result and track all the components needed to form the query's proof.
@@ -34,6 +34,11 @@ impl ProvableQueryResult {
     pub fn table_length(&self) -> usize {
         self.table_length as usize
     }
+    /// The underlying encoded data of the result
+    #[cfg(feature = "evm")]
+    pub(crate) fn data(&self) -> &[u8] {
+        &self.data
+    }
     /// A mutable reference to the underlying encoded data of the result. Because the struct is deserialized from untrusted data, it
     /// cannot maintain any invariant on its data members; hence, this function is available to allow for easy manipulation for testing.
     #[cfg(test)]
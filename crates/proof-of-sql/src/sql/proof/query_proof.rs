@@ -14,7 +14,7 @@ use crate::{
     proof_primitive::sumcheck::SumcheckProof,
     sql::proof::{FirstRoundBuilder, QueryData},
 };
-use alloc::{vec, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use bumpalo::Bump;
 use core::cmp;
 use num_traits::Zero;
@@ -29,6 +29,12 @@ use serde::{Deserialize, Serialize};
 pub struct QueryProof<CP: CommitmentEvaluationProof> {
     /// Bit distributions
     pub bit_distributions: Vec<BitDistribution>,
+    /// Lengths of intermediate results that are not part of the query result
+    pub one_evaluation_lengths: Vec<usize>,
+    /// Distinct values of string columns that the verifier needs in the clear
+    pub string_dictionaries: Vec<Vec<String>>,
+    /// Commitments to the intermediate MLEs of the first round
+    pub first_round_commitments: Vec<CP::Commitment>,
     /// Commitments
     pub commitments: Vec<CP::Commitment>,
     /// Sumcheck Proof
@@ -48,9 +54,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         setup: &CP::ProverPublicSetup<'_>,
     ) -> (Self, ProvableQueryResult) {
         let table_length = expr.get_length(accessor);
-        let num_sumcheck_variables = cmp::max(log2_up(table_length), 1);
         let generator_offset = expr.get_offset(accessor);
-        assert!(num_sumcheck_variables > 0);
 
         let alloc = Bump::new();
 
@@ -61,11 +65,25 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         // Prover First Round
         let mut first_round_builder = FirstRoundBuilder::new();
-        expr.first_round_evaluate(&mut first_round_builder);
+        expr.first_round_evaluate(&mut first_round_builder, &alloc, accessor);
+        let one_evaluation_lengths = first_round_builder.one_evaluation_lengths().to_vec();
+        let string_dictionaries = first_round_builder.string_dictionaries().to_vec();
+        let first_round_commitments =
+            first_round_builder.commit_intermediate_mles(generator_offset, setup);
+
+        // The sumcheck domain has to fit the input, the output and the intermediate results of the query.
+        // These only differ for queries whose output can be larger than their input, e.g. joins.
+        let range_length =
+            compute_range_length(table_length, output_length, &one_evaluation_lengths);
+        let num_sumcheck_variables = cmp::max(log2_up(range_length), 1);
+        assert!(num_sumcheck_variables > 0);
 
         // construct a transcript for the proof
         let mut transcript: Keccak256Transcript =
             make_transcript(expr, &provable_result, table_length, generator_offset);
+        transcript.extend_serialize_as_le(&one_evaluation_lengths);
+        transcript.extend_serialize_as_le(&string_dictionaries);
+        transcript.extend_serialize_as_le(&first_round_commitments);
 
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
@@ -77,12 +95,16 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                 .take(first_round_builder.num_post_result_challenges())
                 .collect();
 
-        let mut builder =
-            FinalRoundBuilder::new(table_length, num_sumcheck_variables, post_result_challenges);
+        let mut builder = FinalRoundBuilder::new(
+            table_length,
+            range_length,
+            num_sumcheck_variables,
+            post_result_challenges,
+        );
+        builder.add_first_round_mles(first_round_builder.into_pcs_proof_mles());
         expr.final_round_evaluate(&mut builder, &alloc, accessor);
 
         let num_sumcheck_variables = builder.num_sumcheck_variables();
-        let table_length = builder.table_length();
 
         // commit to any intermediate MLEs
         let commitments = builder.commit_intermediate_mles(generator_offset, setup);
@@ -98,7 +120,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                 .collect();
         let poly = builder.make_sumcheck_polynomial(&SumcheckRandomScalars::new(
             &random_scalars,
-            table_length,
+            range_length,
             num_sumcheck_variables,
         ));
 
@@ -107,7 +129,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         let sumcheck_proof = SumcheckProof::create(&mut transcript, &mut evaluation_point, &poly);
 
         // evaluate the MLEs used in sumcheck except for the result columns
-        let mut evaluation_vec = vec![Zero::zero(); table_length];
+        let mut evaluation_vec = vec![Zero::zero(); range_length];
         compute_evaluation_vector(&mut evaluation_vec, &evaluation_point);
         let pcs_proof_evaluations = builder.evaluate_pcs_proof_mles(&evaluation_vec);
 
@@ -133,6 +155,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 
         let proof = Self {
             bit_distributions: builder.bit_distributions().to_vec(),
+            one_evaluation_lengths,
+            string_dictionaries,
+            first_round_commitments,
             commitments,
             sumcheck_proof,
             pcs_proof_evaluations,
@@ -152,8 +177,10 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
     ) -> QueryResult<CP::Scalar> {
         let input_length = expr.get_length(accessor);
         let output_length = result.table_length();
+        let range_length =
+            compute_range_length(input_length, output_length, &self.one_evaluation_lengths);
         let generator_offset = expr.get_offset(accessor);
-        let num_sumcheck_variables = cmp::max(log2_up(input_length), 1);
+        let num_sumcheck_variables = cmp::max(log2_up(range_length), 1);
         assert!(num_sumcheck_variables > 0);
 
         // validate bit decompositions
@@ -182,6 +209,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
         // construct a transcript for the proof
         let mut transcript: Keccak256Transcript =
             make_transcript(expr, result, input_length, generator_offset);
+        transcript.extend_serialize_as_le(&self.one_evaluation_lengths);
+        transcript.extend_serialize_as_le(&self.string_dictionaries);
+        transcript.extend_serialize_as_le(&self.first_round_commitments);
 
         // These are the challenges that will be consumed by the proof
         // Specifically, these are the challenges that the verifier sends to
@@ -203,7 +233,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                 .take(num_random_scalars)
                 .collect();
         let sumcheck_random_scalars =
-            SumcheckRandomScalars::new(&random_scalars, input_length, num_sumcheck_variables);
+            SumcheckRandomScalars::new(&random_scalars, range_length, num_sumcheck_variables);
 
         // verify sumcheck up to the evaluation check
         let poly_info = CompositePolynomialInfo {
@@ -242,6 +272,9 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
             generator_offset,
             sumcheck_evaluations,
             &self.bit_distributions,
+            &self.first_round_commitments,
+            &self.one_evaluation_lengths,
+            &self.string_dictionaries,
             &self.commitments,
             sumcheck_random_scalars.subpolynomial_multipliers,
             &evaluation_random_scalars,
@@ -280,7 +313,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
                 &product,
                 &subclaim.evaluation_point,
                 generator_offset as u64,
-                input_length,
+                range_length,
                 setup,
             )
             .map_err(|_e| ProofError::VerificationError {
@@ -295,11 +328,29 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
     }
 
     fn validate_sizes(&self, counts: &ProofCounts) -> bool {
-        self.commitments.len() == counts.intermediate_mles
-            && self.pcs_proof_evaluations.len() == counts.intermediate_mles + counts.anchored_mles
+        self.first_round_commitments.len() == counts.first_round_mles
+            && self.one_evaluation_lengths.len() == counts.one_evaluation_lengths
+            && self.string_dictionaries.len() == counts.string_dictionaries
+            && self.commitments.len() == counts.intermediate_mles
+            && self.pcs_proof_evaluations.len()
+                == counts.first_round_mles + counts.intermediate_mles + counts.anchored_mles
     }
 }
 
+/// The length of the sumcheck domain, which has to fit the input, the output and every
+/// intermediate result of a query.
+pub(super) fn compute_range_length(
+    input_length: usize,
+    output_length: usize,
+    one_evaluation_lengths: &[usize],
+) -> usize {
+    one_evaluation_lengths
+        .iter()
+        .fold(cmp::max(input_length, output_length), |acc, &length| {
+            cmp::max(acc, length)
+        })
+}
+
 /// Creates a transcript using the Merlin library.
 ///
 /// This function is used to produce a transcript for a proof expression
@@ -323,7 +374,7 @@ impl<CP: CommitmentEvaluationProof> QueryProof<CP> {
 /// This function returns a `merlin::Transcript`. The transcript is a record
 /// of all the operations and data involved in creating a proof.
 /// ```
-fn make_transcript<C: Commitment, T: Transcript>(
+pub(super) fn make_transcript<C: Commitment, T: Transcript>(
     expr: &(impl ProofPlan<C> + Serialize),
     result: &ProvableQueryResult,
     table_length: usize,
@@ -337,7 +388,7 @@ fn make_transcript<C: Commitment, T: Transcript>(
     transcript
 }
 
-fn extend_transcript<C: serde::Serialize>(
+pub(super) fn extend_transcript<C: serde::Serialize>(
     transcript: &mut impl Transcript,
     commitments: &C,
     bit_distributions: &[BitDistribution],
@@ -51,7 +51,13 @@ impl<S: Scalar> ProverEvaluate<S> for TrivialTestProofPlan {
         vec![Column::BigInt(col)]
     }
 
-    fn first_round_evaluate(&self, _builder: &mut FirstRoundBuilder) {}
+    fn first_round_evaluate<'a>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) {
+    }
 
     fn final_round_evaluate<'a>(
         &self,
@@ -208,7 +214,13 @@ impl<S: Scalar> ProverEvaluate<S> for SquareTestProofPlan {
         vec![Column::BigInt(res)]
     }
 
-    fn first_round_evaluate(&self, _builder: &mut FirstRoundBuilder) {}
+    fn first_round_evaluate<'a>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) {
+    }
 
     fn final_round_evaluate<'a>(
         &self,
@@ -390,7 +402,13 @@ impl<S: Scalar> ProverEvaluate<S> for DoubleSquareTestProofPlan {
         vec![Column::BigInt(res)]
     }
 
-    fn first_round_evaluate(&self, _builder: &mut FirstRoundBuilder) {}
+    fn first_round_evaluate<'a>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) {
+    }
 
     fn final_round_evaluate<'a>(
         &self,
@@ -601,7 +619,12 @@ impl<S: Scalar> ProverEvaluate<S> for ChallengeTestProofPlan {
         vec![Column::BigInt(&[9, 25])]
     }
 
-    fn first_round_evaluate(&self, builder: &mut FirstRoundBuilder) {
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) {
         builder.request_post_result_challenges(2);
     }
 
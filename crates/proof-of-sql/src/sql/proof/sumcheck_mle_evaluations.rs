@@ -1,10 +1,12 @@
 use super::SumcheckRandomScalars;
 use crate::base::{
     polynomial::{
-        compute_truncated_lagrange_basis_inner_product, compute_truncated_lagrange_basis_sum,
+        compute_evaluation_vector, compute_truncated_lagrange_basis_inner_product,
+        compute_truncated_lagrange_basis_sum,
     },
     scalar::Scalar,
 };
+use alloc::vec;
 
 /// Evaluations for different MLEs at the random point chosen for sumcheck
 #[derive(Default)]
@@ -32,6 +34,8 @@ pub struct SumcheckMleEvaluations<'a, S: Scalar> {
     pub random_evaluation: S,
     /// The evaluations (at the random point generated by sumcheck) of the mles that are evaluated by the inner product argument. These are batched together and checked by a single IPA.
     pub pcs_proof_evaluations: &'a [S],
+    /// The random point generated by sumcheck.
+    pub evaluation_point: &'a [S],
 }
 
 #[allow(
@@ -48,7 +52,7 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
     pub fn new(
         input_length: usize,
         output_length: usize,
-        evaluation_point: &[S],
+        evaluation_point: &'a [S],
         sumcheck_random_scalars: &SumcheckRandomScalars<S>,
         pcs_proof_evaluations: &'a [S],
     ) -> Self {
@@ -56,9 +60,10 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
             evaluation_point.len(),
             sumcheck_random_scalars.entrywise_point.len()
         );
-        assert_eq!(input_length, sumcheck_random_scalars.table_length);
+        assert!(input_length <= sumcheck_random_scalars.table_length);
+        assert!(output_length <= sumcheck_random_scalars.table_length);
         let random_evaluation = compute_truncated_lagrange_basis_inner_product(
-            input_length,
+            sumcheck_random_scalars.table_length,
             evaluation_point,
             sumcheck_random_scalars.entrywise_point,
         );
@@ -74,6 +79,60 @@ impl<'a, S: Scalar> SumcheckMleEvaluations<'a, S> {
             output_one_evaluation,
             random_evaluation,
             pcs_proof_evaluations,
+            evaluation_point,
         }
     }
+
+    /// Make the output length and its `chi` evaluation refer to an intermediate result of length
+    /// `output_length`, e.g. the input of a slice, and return the previous output length.
+    ///
+    /// Plans evaluate their output with [`Self::output_length`], so this has to be called around the
+    /// verification of a subplan whose output is not the query result.
+    pub fn replace_output_length(&mut self, output_length: usize) -> usize {
+        self.output_one_evaluation = self.chi_evaluation(output_length);
+        core::mem::replace(&mut self.output_length, output_length)
+    }
+
+    /// Make the input length and its `chi` evaluation refer to a table of length `input_length`
+    /// and return the previous input length.
+    ///
+    /// This is needed when several plans, each with its own input table, share one proof.
+    pub fn replace_input_length(&mut self, input_length: usize) -> usize {
+        self.input_one_evaluation = self.chi_evaluation(input_length);
+        core::mem::replace(&mut self.input_length, input_length)
+    }
+
+    /// The evaluation (at the random point generated by sumcheck) of an MLE `{x_i}` where
+    ///     `x_i = 1` if `i < length;`
+    ///         = 0, otherwise
+    ///
+    /// This is needed when a proof involves columns of several different lengths, e.g. the
+    /// two input tables of a join.
+    pub fn chi_evaluation(&self, length: usize) -> S {
+        compute_truncated_lagrange_basis_sum(length, self.evaluation_point)
+    }
+
+    /// The evaluation (at the random point generated by sumcheck) of the MLE `{x_i}` where
+    ///     `x_i = i`
+    /// for every `i` in the sumcheck domain, i.e. the row number.
+    pub fn rho_evaluation(&self) -> S {
+        self.evaluation_point
+            .iter()
+            .rev()
+            .fold(S::ZERO, |acc, &r| acc + acc + r)
+    }
+
+    /// The evaluation (at the random point generated by sumcheck) of the MLE of `values`, which
+    /// the verifier knows in the clear, e.g. a dictionary of strings sent along with the proof.
+    ///
+    /// # Panics
+    /// Panics if `values` does not fit into the sumcheck domain.
+    pub fn evaluate(&self, values: &[S]) -> S {
+        let mut evaluation_vec = vec![S::ZERO; values.len()];
+        compute_evaluation_vector(&mut evaluation_vec, self.evaluation_point);
+        evaluation_vec
+            .iter()
+            .zip(values)
+            .fold(S::ZERO, |acc, (&basis, &value)| acc + basis * value)
+    }
 }
@@ -41,3 +41,25 @@ fn we_can_track_the_evaluation_of_mles_used_within_sumcheck() {
     assert_eq!(evals.input_one_evaluation, expected_eval);
     assert_eq!(evals.output_one_evaluation, expected_eval);
 }
+
+#[test]
+fn we_can_evaluate_the_mle_of_values_known_to_the_verifier() {
+    let evaluation_point = [Curve25519Scalar::from(3u64), Curve25519Scalar::from(5u64)];
+    let evals = SumcheckMleEvaluations {
+        num_sumcheck_variables: 2,
+        evaluation_point: &evaluation_point,
+        ..Default::default()
+    };
+    let values = [
+        Curve25519Scalar::from(7u64),
+        Curve25519Scalar::from(11u64),
+        Curve25519Scalar::from(13u64),
+    ];
+    let expected_eval = values[0]
+        * (Curve25519Scalar::one() - evaluation_point[0])
+        * (Curve25519Scalar::one() - evaluation_point[1])
+        + values[1] * evaluation_point[0] * (Curve25519Scalar::one() - evaluation_point[1])
+        + values[2] * (Curve25519Scalar::one() - evaluation_point[0]) * evaluation_point[1];
+    assert_eq!(evals.evaluate(&values), expected_eval);
+    assert_eq!(evals.evaluate(&[]), Curve25519Scalar::from(0u64));
+}
@@ -35,7 +35,13 @@ impl<S: Scalar> ProverEvaluate<S> for EmptyTestQueryExpr {
         let res: &[_] = alloc.alloc_slice_copy(&zeros);
         vec![Column::BigInt(res); self.columns]
     }
-    fn first_round_evaluate(&self, _builder: &mut FirstRoundBuilder) {}
+    fn first_round_evaluate<'a>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, S>,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) {
+    }
     fn final_round_evaluate<'a>(
         &self,
         builder: &mut FinalRoundBuilder<'a, S>,
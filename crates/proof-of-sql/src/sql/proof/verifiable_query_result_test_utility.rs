@@ -68,13 +68,24 @@ pub fn exercise_verification(
         assert!(res_p.verify(expr, accessor, &()).is_err());
     }
 
+    // try changing first round commitments
+    for i in 0..proof.first_round_commitments.len() {
+        let mut res_p = res.clone();
+        res_p.proof.as_mut().unwrap().first_round_commitments[i] = commit_p;
+        assert!(res_p.verify(expr, accessor, &()).is_err());
+    }
+
     // try changing the offset
     //
     // Note: in the n = 1 case with proof.commmitments all the identity element,
     // the inner product proof isn't dependent on the generators since it simply sends the input
     // vector; hence, changing the offset would have no effect.
     if accessor.get_length(table_ref) > 1
-        || proof.commitments.iter().any(|&c| c != Identity::identity())
+        || proof
+            .commitments
+            .iter()
+            .chain(&proof.first_round_commitments)
+            .any(|&c| c != Identity::identity())
     {
         let offset_generators = accessor.get_offset(table_ref);
         let mut fake_accessor = accessor.clone();
@@ -1,12 +1,15 @@
 use super::{SumcheckMleEvaluations, SumcheckSubpolynomialType};
 use crate::base::{bit::BitDistribution, commitment::Commitment};
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 use num_traits::Zero;
 
 /// Track components used to verify a query's proof
 pub struct VerificationBuilder<'a, C: Commitment> {
     pub mle_evaluations: SumcheckMleEvaluations<'a, C::Scalar>,
     generator_offset: usize,
+    first_round_commitments: &'a [C],
+    one_evaluation_lengths: &'a [usize],
+    string_dictionaries: &'a [Vec<String>],
     intermediate_commitments: &'a [C],
     subpolynomial_multipliers: &'a [C::Scalar],
     inner_product_multipliers: &'a [C::Scalar],
@@ -15,6 +18,9 @@ pub struct VerificationBuilder<'a, C: Commitment> {
     pcs_proof_commitments: Vec<C>,
     folded_pcs_proof_evaluation: C::Scalar,
     consumed_pcs_proof_mles: usize,
+    consumed_first_round_mles: usize,
+    consumed_one_evaluation_lengths: usize,
+    consumed_string_dictionaries: usize,
     consumed_intermediate_mles: usize,
     produced_subpolynomials: usize,
     /// The challenges used in creation of the constraints in the proof.
@@ -32,10 +38,14 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
         clippy::missing_panics_doc,
         reason = "The only possible panic is from the assertion comparing lengths, which is clear from context."
     )]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         generator_offset: usize,
         mle_evaluations: SumcheckMleEvaluations<'a, C::Scalar>,
         bit_distributions: &'a [BitDistribution],
+        first_round_commitments: &'a [C],
+        one_evaluation_lengths: &'a [usize],
+        string_dictionaries: &'a [Vec<String>],
         intermediate_commitments: &'a [C],
         subpolynomial_multipliers: &'a [C::Scalar],
         inner_product_multipliers: &'a [C::Scalar],
@@ -45,17 +55,27 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
             inner_product_multipliers.len(),
             mle_evaluations.pcs_proof_evaluations.len()
         );
+        assert!(first_round_commitments.len() <= inner_product_multipliers.len());
+        // The first round MLEs always come first in the list of MLEs opened by the evaluation proof.
+        let mut pcs_proof_commitments = Vec::with_capacity(inner_product_multipliers.len());
+        pcs_proof_commitments.extend_from_slice(first_round_commitments);
         Self {
             mle_evaluations,
             generator_offset,
             bit_distributions,
+            first_round_commitments,
+            one_evaluation_lengths,
+            string_dictionaries,
             intermediate_commitments,
             subpolynomial_multipliers,
             inner_product_multipliers,
             sumcheck_evaluation: C::Scalar::zero(),
-            pcs_proof_commitments: Vec::with_capacity(inner_product_multipliers.len()),
+            pcs_proof_commitments,
             folded_pcs_proof_evaluation: C::Scalar::zero(),
-            consumed_pcs_proof_mles: 0,
+            consumed_pcs_proof_mles: first_round_commitments.len(),
+            consumed_first_round_mles: 0,
+            consumed_one_evaluation_lengths: 0,
+            consumed_string_dictionaries: 0,
             consumed_intermediate_mles: 0,
             produced_subpolynomials: 0,
             post_result_challenges,
@@ -83,6 +103,46 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
         res
     }
 
+    /// Consume the evaluation of a first round MLE used in sumcheck
+    ///
+    /// A first round MLE is an intermediate MLE that the prover commits to before the post-result
+    /// challenges are drawn.
+    pub fn consume_first_round_mle(&mut self) -> C::Scalar {
+        let index = self.consumed_first_round_mles;
+        let multiplier = self.inner_product_multipliers[index];
+        self.consumed_first_round_mles += 1;
+        let res = self.mle_evaluations.pcs_proof_evaluations[index];
+        self.folded_pcs_proof_evaluation += multiplier * res;
+        res
+    }
+
+    /// Consume the evaluation of a first round MLE used in sumcheck together with its commitment
+    ///
+    /// The commitment lets the MLE be used as an anchored MLE later on, e.g. when it is a column
+    /// of a derived table that another plan reads from.
+    pub fn consume_first_round_mle_and_commitment(&mut self) -> (C::Scalar, C) {
+        let commitment = self.first_round_commitments[self.consumed_first_round_mles].clone();
+        (self.consume_first_round_mle(), commitment)
+    }
+
+    /// Consume the length of an intermediate result that is not part of the query result
+    ///
+    /// See [`FirstRoundBuilder::produce_one_evaluation_length`](crate::sql::proof::FirstRoundBuilder::produce_one_evaluation_length).
+    pub fn consume_one_evaluation_length(&mut self) -> usize {
+        let res = self.one_evaluation_lengths[self.consumed_one_evaluation_lengths];
+        self.consumed_one_evaluation_lengths += 1;
+        res
+    }
+
+    /// Consume the distinct values of a string column
+    ///
+    /// See [`FirstRoundBuilder::produce_string_dictionary`](crate::sql::proof::FirstRoundBuilder::produce_string_dictionary).
+    pub fn consume_string_dictionary(&mut self) -> &'a [String] {
+        let res = &self.string_dictionaries[self.consumed_string_dictionaries];
+        self.consumed_string_dictionaries += 1;
+        res
+    }
+
     /// Consume a bit distribution that describes which bits are constant
     /// and which bits varying in a column of data
     pub fn consume_bit_distribution(&mut self) -> BitDistribution {
@@ -162,6 +222,9 @@ impl<'a, C: Commitment> VerificationBuilder<'a, C> {
     fn completed(&self) -> bool {
         self.bit_distributions.is_empty()
             && self.produced_subpolynomials == self.subpolynomial_multipliers.len()
+            && self.consumed_first_round_mles == self.first_round_commitments.len()
+            && self.consumed_one_evaluation_lengths == self.one_evaluation_lengths.len()
+            && self.consumed_string_dictionaries == self.string_dictionaries.len()
             && self.consumed_intermediate_mles == self.intermediate_commitments.len()
             && self.consumed_pcs_proof_mles == self.mle_evaluations.pcs_proof_evaluations.len()
             && self.post_result_challenges.is_empty()
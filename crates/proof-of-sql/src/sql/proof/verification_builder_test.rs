@@ -18,6 +18,9 @@ fn an_empty_sumcheck_polynomial_evaluates_to_zero() {
         &[][..],
         &[][..],
         &[][..],
+        &[][..],
+        &[][..],
+        &[][..],
         Vec::new(),
     );
     assert_eq!(builder.sumcheck_evaluation(), Curve25519Scalar::zero());
@@ -41,6 +44,9 @@ fn we_build_up_a_sumcheck_polynomial_evaluation_from_subpolynomial_evaluations()
         mle_evaluations,
         &[][..],
         &[][..],
+        &[][..],
+        &[][..],
+        &[][..],
         &subpolynomial_multipliers,
         &[][..],
         Vec::new(),
@@ -82,6 +88,9 @@ fn we_build_up_the_folded_pcs_proof_commitment() {
         0,
         mle_evaluations,
         &[][..],
+        &[][..],
+        &[][..],
+        &[][..],
         &intermediate_commitments,
         &[][..],
         &inner_product_multipliers,
@@ -114,6 +123,9 @@ fn we_can_consume_post_result_challenges_in_proof_builder() {
         &[][..],
         &[][..],
         &[][..],
+        &[][..],
+        &[][..],
+        &[][..],
         vec![
             Curve25519Scalar::from(123),
             Curve25519Scalar::from(456),
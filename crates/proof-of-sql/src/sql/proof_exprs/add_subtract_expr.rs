@@ -9,7 +9,7 @@ use crate::{
         map::IndexSet,
         proof::ProofError,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
 };
 use alloc::boxed::Box;
 use bumpalo::Bump;
@@ -33,6 +33,24 @@ impl<C: Commitment> AddSubtractExpr<C> {
             is_subtract,
         }
     }
+
+    /// The left hand side of the expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn lhs(&self) -> &DynProofExpr<C> {
+        &self.lhs
+    }
+
+    /// The right hand side of the expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn rhs(&self) -> &DynProofExpr<C> {
+        &self.rhs
+    }
+
+    /// Whether the expression is a subtraction rather than an addition
+    #[cfg(feature = "evm")]
+    pub(crate) fn is_subtract(&self) -> bool {
+        self.is_subtract
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for AddSubtractExpr<C> {
@@ -72,6 +90,19 @@ impl<C: Commitment> ProofExpr<C> for AddSubtractExpr<C> {
         ))
     }
 
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.lhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        self.rhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+    }
+
     #[tracing::instrument(
         name = "proofs.sql.ast.add_subtract_expr.prover_evaluate",
         level = "info",
@@ -6,32 +6,66 @@ use crate::{
         map::IndexSet,
         proof::ProofError,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, VerificationBuilder},
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType,
+            VerificationBuilder,
+        },
+        proof_exprs::multiply_columns,
+    },
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, vec};
 use bumpalo::Bump;
+use num_traits::One;
 use proof_of_sql_parser::intermediate_ast::AggregationOperator;
 use serde::{Deserialize, Serialize};
 
 /// Provable aggregate expression
 ///
-/// Currently it doesn't do much since aggregation logic is implemented elsewhere
+/// Currently it doesn't do much since aggregation logic is implemented elsewhere.
+/// If `presence` is provided, `expr` is nullable and its null rows are replaced by zero,
+/// so that they are ignored by `SUM`.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AggregateExpr<C: Commitment> {
     op: AggregationOperator,
     expr: Box<DynProofExpr<C>>,
+    presence: Option<Box<DynProofExpr<C>>>,
 }
 
 impl<C: Commitment> AggregateExpr<C> {
     /// Create a new aggregate expression
     pub fn new(op: AggregationOperator, expr: Box<DynProofExpr<C>>) -> Self {
-        Self { op, expr }
+        Self {
+            op,
+            expr,
+            presence: None,
+        }
+    }
+
+    /// Create a new aggregate expression of a nullable expression
+    pub fn new_nullable(
+        op: AggregationOperator,
+        expr: Box<DynProofExpr<C>>,
+        presence: Box<DynProofExpr<C>>,
+    ) -> Self {
+        Self {
+            op,
+            expr,
+            presence: Some(presence),
+        }
     }
 }
 
 impl<C: Commitment> ProofExpr<C> for AggregateExpr<C> {
     fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
-        self.expr.count(builder)
+        self.expr.count(builder)?;
+        if let Some(presence) = &self.presence {
+            presence.count(builder)?;
+            builder.count_subpolynomials(1);
+            builder.count_intermediate_mles(1);
+            builder.count_degree(3);
+        }
+        Ok(())
     }
 
     fn data_type(&self) -> ColumnType {
@@ -49,7 +83,28 @@ impl<C: Commitment> ProofExpr<C> for AggregateExpr<C> {
         alloc: &'a Bump,
         accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
-        self.expr.result_evaluate(table_length, alloc, accessor)
+        let expr_column = self.expr.result_evaluate(table_length, alloc, accessor);
+        match &self.presence {
+            Some(presence) => {
+                let presence_column = presence.result_evaluate(table_length, alloc, accessor);
+                Column::Scalar(multiply_columns(&expr_column, &presence_column, alloc))
+            }
+            None => expr_column,
+        }
+    }
+
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.expr
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        if let Some(presence) = &self.presence {
+            presence.first_round_evaluate(builder, table_length, alloc, accessor);
+        }
     }
 
     #[tracing::instrument(name = "AggregateExpr::prover_evaluate", level = "debug", skip_all)]
@@ -59,7 +114,30 @@ impl<C: Commitment> ProofExpr<C> for AggregateExpr<C> {
         alloc: &'a Bump,
         accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar> {
-        self.expr.prover_evaluate(builder, alloc, accessor)
+        let expr_column = self.expr.prover_evaluate(builder, alloc, accessor);
+        match &self.presence {
+            Some(presence) => {
+                let presence_column = presence.prover_evaluate(builder, alloc, accessor);
+
+                // expr_or_zero
+                let expr_or_zero = multiply_columns(&expr_column, &presence_column, alloc);
+                builder.produce_intermediate_mle(expr_or_zero);
+
+                // subpolynomial: expr_or_zero - expr * presence
+                builder.produce_sumcheck_subpolynomial(
+                    SumcheckSubpolynomialType::Identity,
+                    vec![
+                        (C::Scalar::one(), vec![Box::new(expr_or_zero)]),
+                        (
+                            -C::Scalar::one(),
+                            vec![Box::new(expr_column), Box::new(presence_column)],
+                        ),
+                    ],
+                );
+                Column::Scalar(expr_or_zero)
+            }
+            None => expr_column,
+        }
     }
 
     fn verifier_evaluate(
@@ -67,10 +145,29 @@ impl<C: Commitment> ProofExpr<C> for AggregateExpr<C> {
         builder: &mut VerificationBuilder<C>,
         accessor: &dyn CommitmentAccessor<C>,
     ) -> Result<C::Scalar, ProofError> {
-        self.expr.verifier_evaluate(builder, accessor)
+        let expr = self.expr.verifier_evaluate(builder, accessor)?;
+        match &self.presence {
+            Some(presence) => {
+                let presence = presence.verifier_evaluate(builder, accessor)?;
+
+                // expr_or_zero
+                let expr_or_zero = builder.consume_intermediate_mle();
+
+                // subpolynomial: expr_or_zero - expr * presence
+                builder.produce_sumcheck_subpolynomial_evaluation(
+                    &SumcheckSubpolynomialType::Identity,
+                    expr_or_zero - expr * presence,
+                );
+                Ok(expr_or_zero)
+            }
+            None => Ok(expr),
+        }
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         self.expr.get_column_references(columns);
+        if let Some(presence) = &self.presence {
+            presence.get_column_references(columns);
+        }
     }
 }
@@ -6,7 +6,10 @@ use crate::{
         map::IndexSet,
         proof::ProofError,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    sql::proof::{
+        CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType,
+        VerificationBuilder,
+    },
 };
 use alloc::{boxed::Box, vec};
 use bumpalo::Bump;
@@ -25,6 +28,18 @@ impl<C: Commitment> AndExpr<C> {
     pub fn new(lhs: Box<DynProofExpr<C>>, rhs: Box<DynProofExpr<C>>) -> Self {
         Self { lhs, rhs }
     }
+
+    /// The left hand side of the expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn lhs(&self) -> &DynProofExpr<C> {
+        &self.lhs
+    }
+
+    /// The right hand side of the expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn rhs(&self) -> &DynProofExpr<C> {
+        &self.rhs
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for AndExpr<C> {
@@ -57,6 +72,19 @@ impl<C: Commitment> ProofExpr<C> for AndExpr<C> {
         Column::Boolean(alloc.alloc_slice_fill_with(table_length, |i| lhs[i] && rhs[i]))
     }
 
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.lhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        self.rhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+    }
+
     #[tracing::instrument(name = "AndExpr::prover_evaluate", level = "debug", skip_all)]
     fn prover_evaluate<'a>(
         &self,
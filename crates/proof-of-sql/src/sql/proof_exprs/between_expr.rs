@@ -0,0 +1,212 @@
+use super::{
+    count_equals_zero, count_or, count_sign_with_range_check, first_round_evaluate_sign,
+    prover_evaluate_equals_zero, prover_evaluate_or, prover_evaluate_sign_with_range_check,
+    result_evaluate_equals_zero, result_evaluate_or, result_evaluate_sign,
+    verifier_evaluate_equals_zero, verifier_evaluate_or, verifier_evaluate_sign_with_range_check,
+    DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType,
+        VerificationBuilder,
+    },
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for
+/// ```ignore
+///     <expr> BETWEEN <low> AND <high>
+/// ```
+/// where `expr` is an integer expression and `low <= high` are integer literals.
+///
+/// Since `low <= high`, `expr` is in `[low, high]` exactly when
+/// `(expr - low) * (expr - high) <= 0`, so a single sign decomposition of the product is enough
+/// to check both bounds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BetweenExpr<C: Commitment> {
+    expr: Box<DynProofExpr<C>>,
+    low: i64,
+    high: i64,
+}
+
+/// The factors `expr - low` and `expr - high` of a `BETWEEN` expression and their product
+struct BetweenFactors<'a, S: Scalar> {
+    above_low: &'a [S],
+    above_high: &'a [S],
+    product: &'a [S],
+}
+
+impl<C: Commitment> BetweenExpr<C> {
+    /// Create a new `BETWEEN` expression
+    ///
+    /// Note: `low` must not be greater than `high`.
+    pub fn new(expr: Box<DynProofExpr<C>>, low: i64, high: i64) -> Self {
+        Self { expr, low, high }
+    }
+
+    /// Compute `expr - low`, `expr - high` and their product
+    fn factors<'a>(
+        &self,
+        alloc: &'a Bump,
+        column: Column<'a, C::Scalar>,
+    ) -> BetweenFactors<'a, C::Scalar> {
+        let values = column.as_scalar(alloc);
+        let low = C::Scalar::from(self.low);
+        let high = C::Scalar::from(self.high);
+        let above_low: &[_] = alloc.alloc_slice_fill_with(values.len(), |i| values[i] - low);
+        let above_high: &[_] = alloc.alloc_slice_fill_with(values.len(), |i| values[i] - high);
+        let product = alloc.alloc_slice_fill_with(values.len(), |i| above_low[i] * above_high[i]);
+        BetweenFactors {
+            above_low,
+            above_high,
+            product,
+        }
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for BetweenExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(1);
+        builder.count_degree(3);
+        count_equals_zero(builder);
+        count_sign_with_range_check(builder)?;
+        count_or(builder);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let product = self.factors(alloc, column).product;
+
+        // product == 0
+        let equals_zero = result_evaluate_equals_zero(table_length, alloc, product);
+
+        // sign(product) == -1
+        let sign = result_evaluate_sign(table_length, alloc, product);
+
+        // (product == 0) || (sign(product) == -1)
+        Column::Boolean(result_evaluate_or(table_length, alloc, equals_zero, sign))
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.expr
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let product = self.factors(alloc, column).product;
+
+        // words of sign(product)
+        first_round_evaluate_sign(builder, alloc, product);
+    }
+
+    #[tracing::instrument(name = "BetweenExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        let BetweenFactors {
+            above_low,
+            above_high,
+            product,
+        } = self.factors(alloc, column);
+
+        // product
+        builder.produce_intermediate_mle(product);
+
+        // subpolynomial: product - (expr - low) * (expr - high)
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(product)]),
+                (
+                    -C::Scalar::ONE,
+                    vec![Box::new(above_low), Box::new(above_high)],
+                ),
+            ],
+        );
+
+        // product == 0
+        let equals_zero = prover_evaluate_equals_zero(builder, alloc, product);
+
+        // sign(product) == -1
+        let sign = prover_evaluate_sign_with_range_check(
+            builder,
+            alloc,
+            product,
+            #[cfg(test)]
+            false,
+        );
+
+        // (product == 0) || (sign(product) == -1)
+        Column::Boolean(prover_evaluate_or(builder, alloc, equals_zero, sign))
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        // The product only characterizes the range if it is not empty.
+        if self.low > self.high {
+            return Err(ProofError::VerificationError {
+                error: "lower bound of BETWEEN is greater than its upper bound",
+            });
+        }
+        let one_eval = builder.mle_evaluations.input_one_evaluation;
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor)?;
+        let above_low_eval = expr_eval - C::Scalar::from(self.low) * one_eval;
+        let above_high_eval = expr_eval - C::Scalar::from(self.high) * one_eval;
+
+        // product
+        let product_eval = builder.consume_intermediate_mle();
+
+        // subpolynomial: product - (expr - low) * (expr - high)
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            product_eval - above_low_eval * above_high_eval,
+        );
+
+        // product == 0
+        let equals_zero = verifier_evaluate_equals_zero(builder, product_eval);
+
+        // sign(product) == -1
+        let sign = verifier_evaluate_sign_with_range_check(builder, product_eval, one_eval)?;
+
+        // (product == 0) || (sign(product) == -1)
+        Ok(verifier_evaluate_or(builder, &equals_zero, &sign))
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
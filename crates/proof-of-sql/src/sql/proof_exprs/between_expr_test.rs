@@ -0,0 +1,104 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef},
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+        proof_plans::test_utility::*,
+    },
+};
+
+fn make_accessor() -> (OwnedTableTestAccessor<'static, InnerProductProof>, TableRef) {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6, 7]),
+        bigint("b", [-5_i64, 0, 3, 10, 11, i64::MIN, i64::MAX]),
+        tinyint("c", [-128_i8, -1, 0, 1, 2, 3, 127]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    (accessor, t)
+}
+
+#[test]
+fn we_can_prove_a_between_query() {
+    let (accessor, t) = make_accessor();
+    let ast = filter(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        tab(t),
+        between(column(t, "b", &accessor), 0, 10),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [2_i64, 3, 4]), bigint("b", [0_i64, 3, 10])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_not_between_query_with_extreme_bounds() {
+    let (accessor, t) = make_accessor();
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        not(between(column(t, "b", &accessor), i64::MIN + 1, i64::MAX)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [6_i64])]));
+}
+
+#[test]
+fn we_can_prove_between_queries_with_a_single_value_and_a_small_integer_column() {
+    let (accessor, t) = make_accessor();
+    let ast = projection(
+        vec![
+            aliased_plan(between(column(t, "a", &accessor), 3, 3), "single"),
+            aliased_plan(between(column(t, "c", &accessor), -1, 200), "tiny"),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("single", [false, false, true, false, false, false, false]),
+        boolean("tiny", [false, true, true, true, true, true, true]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_between_query_on_an_empty_table() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [0_i64; 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        between(column(t, "a", &accessor), -1, 1),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [0_i64; 0])]));
+}
+
+#[test]
+fn we_can_prove_a_between_query_with_a_nonzero_offset() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [-2_i64, 5, 9, 12])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 3, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        between(column(t, "a", &accessor), 5, 9),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [5_i64, 9])]));
+}
@@ -0,0 +1,218 @@
+use super::{DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            try_case_column_types, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+        },
+        map::IndexSet,
+        math::decimal::scale_scalar,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType,
+        VerificationBuilder,
+    },
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for
+/// ```ignore
+///     CASE WHEN <condition> THEN <then_expr> ELSE <else_expr> END
+/// ```
+/// where the result is numeric or boolean. Longer `CASE` expressions nest in `else_expr`.
+///
+/// The condition is a boolean selector column for the branches, so the prover commits to
+/// ```ignore
+///     result = else_expr + condition * (then_expr - else_expr)
+/// ```
+/// with both branches scaled to the scale of the result.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CaseExpr<C: Commitment> {
+    condition: Box<DynProofExpr<C>>,
+    then_expr: Box<DynProofExpr<C>>,
+    else_expr: Box<DynProofExpr<C>>,
+}
+
+impl<C: Commitment> CaseExpr<C> {
+    /// Create a new `CASE` expression
+    pub fn new(
+        condition: Box<DynProofExpr<C>>,
+        then_expr: Box<DynProofExpr<C>>,
+        else_expr: Box<DynProofExpr<C>>,
+    ) -> Self {
+        Self {
+            condition,
+            then_expr,
+            else_expr,
+        }
+    }
+
+    /// The factors that scale `then_expr` and `else_expr` to the scale of the result
+    ///
+    /// # Panics
+    /// Panics if the branches don't have a common type.
+    fn scale_factors(&self) -> (C::Scalar, C::Scalar) {
+        let scale = self.data_type().scale().unwrap_or(0);
+        let scale_factor = |expr: &DynProofExpr<C>| {
+            scale_scalar(
+                C::Scalar::ONE,
+                scale - expr.data_type().scale().unwrap_or(0),
+            )
+            .expect("the result has the largest scale")
+        };
+        (scale_factor(&self.then_expr), scale_factor(&self.else_expr))
+    }
+
+    /// Choose each row of the result from one of the branches
+    ///
+    /// # Panics
+    /// Panics if the condition is not boolean.
+    fn select<'a>(
+        &self,
+        alloc: &'a Bump,
+        condition: Column<'a, C::Scalar>,
+        then_column: Column<'a, C::Scalar>,
+        else_column: Column<'a, C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let selection = condition.as_boolean().expect("condition is not boolean");
+        if let (Column::Boolean(then_values), Column::Boolean(else_values)) =
+            (then_column, else_column)
+        {
+            return Column::Boolean(alloc.alloc_slice_fill_with(selection.len(), |i| {
+                if selection[i] {
+                    then_values[i]
+                } else {
+                    else_values[i]
+                }
+            }));
+        }
+        let (then_factor, else_factor) = self.scale_factors();
+        Column::Scalar(alloc.alloc_slice_fill_with(selection.len(), |i| {
+            if selection[i] {
+                then_column.scalar_at(i).unwrap() * then_factor
+            } else {
+                else_column.scalar_at(i).unwrap() * else_factor
+            }
+        }))
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for CaseExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.condition.count(builder)?;
+        self.then_expr.count(builder)?;
+        self.else_expr.count(builder)?;
+        builder.count_subpolynomials(1);
+        builder.count_intermediate_mles(1);
+        builder.count_degree(3);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        try_case_column_types(self.then_expr.data_type(), self.else_expr.data_type())
+            .expect("Failed to choose between column types")
+    }
+
+    #[tracing::instrument(name = "CaseExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let condition = self
+            .condition
+            .result_evaluate(table_length, alloc, accessor);
+        let then_column = self
+            .then_expr
+            .result_evaluate(table_length, alloc, accessor);
+        let else_column = self
+            .else_expr
+            .result_evaluate(table_length, alloc, accessor);
+        self.select(alloc, condition, then_column, else_column)
+    }
+
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.condition
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        self.then_expr
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        self.else_expr
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+    }
+
+    #[tracing::instrument(name = "CaseExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let condition = self.condition.prover_evaluate(builder, alloc, accessor);
+        let then_column = self.then_expr.prover_evaluate(builder, alloc, accessor);
+        let else_column = self.else_expr.prover_evaluate(builder, alloc, accessor);
+        let (then_factor, else_factor) = self.scale_factors();
+
+        // result
+        let result = self.select(alloc, condition, then_column, else_column);
+        builder.produce_intermediate_mle(result);
+
+        // subpolynomial: result - else - condition * (then - else)
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(result)]),
+                (-else_factor, vec![Box::new(else_column)]),
+                (
+                    -then_factor,
+                    vec![Box::new(condition), Box::new(then_column)],
+                ),
+                (
+                    else_factor,
+                    vec![Box::new(condition), Box::new(else_column)],
+                ),
+            ],
+        );
+        result
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let condition = self.condition.verifier_evaluate(builder, accessor)?;
+        let then_eval = self.then_expr.verifier_evaluate(builder, accessor)?;
+        let else_eval = self.else_expr.verifier_evaluate(builder, accessor)?;
+        let (then_factor, else_factor) = self.scale_factors();
+        let then_eval = then_eval * then_factor;
+        let else_eval = else_eval * else_factor;
+
+        // result
+        let result = builder.consume_intermediate_mle();
+
+        // subpolynomial: result - else - condition * (then - else)
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            result - else_eval - condition * (then_eval - else_eval),
+        );
+
+        Ok(result)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.condition.get_column_references(columns);
+        self.then_expr.get_column_references(columns);
+        self.else_expr.get_column_references(columns);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, ColumnOperationError, OwnedTableTestAccessor},
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+
+// select case when a >= 2 then b else d end as x from sxt.t
+#[test]
+fn we_can_prove_a_case_expression_with_branches_of_different_scales() {
+    let data = owned_table([
+        smallint("a", [1_i16, 2, 3, 4]),
+        int("b", [10_i32, 20, 30, 40]),
+        decimal75("d", 5, 2, [125_i64, -250, 375, 500]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![aliased_plan(
+            case_when(
+                gte(column(t, "a", &accessor), const_smallint(2)),
+                column(t, "b", &accessor),
+                column(t, "d", &accessor),
+            ),
+            "x",
+        )],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([decimal75("x", 12, 2, [125_i64, 2000, 3000, 4000])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where case when a >= 3 then c when a = 1 then true else not c end
+#[test]
+fn we_can_prove_a_filter_by_nested_boolean_case_expressions() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5]),
+        boolean("c", [false, false, true, false, true]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 3, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        case_when(
+            gte(column(t, "a", &accessor), const_bigint(3)),
+            column(t, "c", &accessor),
+            case_when(
+                equal(column(t, "a", &accessor), const_bigint(1)),
+                const_bool(true),
+                not(column(t, "c", &accessor)),
+            ),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1_i64, 2, 3, 5])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a, sum(case when b = 1 then c else 0 end) as sum_c, count(*) as __count__
+// from sxt.t group by a
+#[test]
+fn we_can_prove_a_group_by_with_a_conditional_sum() {
+    let data = owned_table([
+        bigint("a", [1_i64, 1, 2, 2, 2]),
+        bigint("b", [1_i64, 0, 1, 1, 0]),
+        bigint("c", [5_i64, 6, 7, 8, 9]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = group_by(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![sum_expr(
+            case_when(
+                equal(column(t, "b", &accessor), const_bigint(1)),
+                column(t, "c", &accessor),
+                const_bigint(0),
+            ),
+            "sum_c",
+        )],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [1_i64, 2]),
+        bigint("sum_c", [5_i64, 15]),
+        bigint("__count__", [2_i64, 3]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_build_case_expressions_with_invalid_types() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2]),
+        boolean("c", [true, false]),
+        varchar("s", ["x", "y"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+
+    // The condition must be boolean
+    assert!(matches!(
+        DynProofExpr::<RistrettoPoint>::try_new_case(
+            column(t, "a", &accessor),
+            column(t, "a", &accessor),
+            const_bigint(0),
+        ),
+        Err(ConversionError::InvalidDataType { .. })
+    ));
+
+    // The branches must have compatible types
+    assert!(matches!(
+        DynProofExpr::<RistrettoPoint>::try_new_case(
+            column(t, "c", &accessor),
+            column(t, "a", &accessor),
+            column(t, "c", &accessor),
+        ),
+        Err(ConversionError::ColumnOperationError {
+            source: ColumnOperationError::CaseInvalidColumnTypes { .. }
+        })
+    ));
+
+    // Only numeric and boolean results are provable
+    assert!(matches!(
+        DynProofExpr::<RistrettoPoint>::try_new_case(
+            column(t, "c", &accessor),
+            column(t, "s", &accessor),
+            const_varchar("z"),
+        ),
+        Err(ConversionError::Unprovable { .. })
+    ));
+}
@@ -0,0 +1,139 @@
+use super::{DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        map::IndexSet,
+        math::decimal::scale_scalar,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
+};
+use alloc::boxed::Box;
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for
+/// ```ignore
+///     CAST(<expr> AS <data_type>)
+/// ```
+/// where every value of `expr` fits into `data_type` at the same or a larger scale, e.g.
+/// `INT` to `BIGINT`, `BIGINT` to `DECIMAL(21, 2)` or `TIMESTAMP(0)` to `TIMESTAMP(3)`.
+///
+/// The result is `expr` times `10^k` for the difference `k` of the scales, so the verifier
+/// computes its evaluation directly and the prover commits to nothing new.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CastExpr<C: Commitment> {
+    expr: Box<DynProofExpr<C>>,
+    data_type: ColumnType,
+}
+
+impl<C: Commitment> CastExpr<C> {
+    /// Create a new `CAST` expression
+    pub fn new(expr: Box<DynProofExpr<C>>, data_type: ColumnType) -> Self {
+        Self { expr, data_type }
+    }
+
+    /// Whether `CAST` from `from_type` to `to_type` keeps every value, only multiplying it by a
+    /// power of ten
+    pub fn is_lossless(from_type: ColumnType, to_type: ColumnType) -> bool {
+        let from_scale = from_type.scale().unwrap_or(0);
+        let to_scale = to_type.scale().unwrap_or(0);
+        match (from_type, to_type) {
+            (ColumnType::TimestampTZ(..), ColumnType::TimestampTZ(..)) => to_scale >= from_scale,
+            (_, ColumnType::Decimal75(..))
+                if from_type.is_integer() || matches!(from_type, ColumnType::Decimal75(..)) =>
+            {
+                let integer_digits = |column_type: ColumnType, scale: i8| {
+                    i16::from(column_type.precision_value().unwrap_or(0)) - i16::from(scale)
+                };
+                to_scale >= from_scale
+                    && integer_digits(to_type, to_scale) >= integer_digits(from_type, from_scale)
+            }
+            _ if from_type.is_integer() && to_type.is_integer() => {
+                from_type.max_integer_type(&to_type) == Some(to_type)
+            }
+            _ => false,
+        }
+    }
+
+    /// The power of ten that scales `expr` to the scale of the result
+    ///
+    /// # Panics
+    /// Panics if the cast lowers the scale.
+    fn scale(&self) -> i8 {
+        let scale =
+            self.data_type.scale().unwrap_or(0) - self.expr.data_type().scale().unwrap_or(0);
+        assert!(scale >= 0, "a provable cast never lowers the scale");
+        scale
+    }
+
+    /// Rescale the values of `expr`
+    fn cast<'a>(&self, alloc: &'a Bump, column: Column<'a, C::Scalar>) -> Column<'a, C::Scalar> {
+        Column::Scalar(alloc.alloc_slice_copy(&column.to_scalar_with_scaling(self.scale())))
+    }
+
+    /// The factor that scales the evaluation of `expr` to the scale of the result
+    ///
+    /// # Panics
+    /// Panics if the cast lowers the scale.
+    fn scale_factor(&self) -> C::Scalar {
+        scale_scalar(C::Scalar::ONE, self.scale()).expect("a provable cast never lowers the scale")
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for CastExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.data_type
+    }
+
+    #[tracing::instrument(name = "CastExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        self.cast(alloc, column)
+    }
+
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.expr
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+    }
+
+    #[tracing::instrument(name = "CastExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        self.cast(alloc, column)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        Ok(self.expr.verifier_evaluate(builder, accessor)? * self.scale_factor())
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, ColumnOperationError, OwnedTableTestAccessor},
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::{
+    intermediate_ast::DataType,
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+
+// select cast(a as bigint) as x, cast(a as decimal(12, 2)) as y, cast(d as decimal(8, 3)) as z,
+// cast(t as timestamp(3)) as w from sxt.t
+#[test]
+fn we_can_prove_widening_and_rescaling_casts() {
+    let data = owned_table([
+        int("a", [1_i32, -2, 300]),
+        decimal75("d", 5, 2, [125_i64, -250, 375]),
+        timestamptz(
+            "t",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            [0_i64, 1, 2],
+        ),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 2, ());
+    let ast = projection(
+        vec![
+            aliased_plan(cast(column(t, "a", &accessor), DataType::BigInt), "x"),
+            aliased_plan(
+                cast(
+                    column(t, "a", &accessor),
+                    DataType::Decimal {
+                        precision: 12,
+                        scale: 2,
+                    },
+                ),
+                "y",
+            ),
+            aliased_plan(
+                cast(
+                    column(t, "d", &accessor),
+                    DataType::Decimal {
+                        precision: 8,
+                        scale: 3,
+                    },
+                ),
+                "z",
+            ),
+            aliased_plan(
+                cast(
+                    column(t, "t", &accessor),
+                    DataType::Timestamp(PoSQLTimeUnit::Millisecond),
+                ),
+                "w",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("x", [1_i64, -2, 300]),
+        decimal75("y", 12, 2, [100_i64, -200, 30_000]),
+        decimal75("z", 8, 3, [1250_i64, -2500, 3750]),
+        timestamptz(
+            "w",
+            PoSQLTimeUnit::Millisecond,
+            PoSQLTimeZone::Utc,
+            [0_i64, 1000, 2000],
+        ),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where cast(a as decimal(12, 2)) >= d
+#[test]
+fn we_can_prove_a_filter_with_a_cast() {
+    let data = owned_table([
+        smallint("a", [1_i16, 2, 3, 4]),
+        decimal75("d", 5, 2, [125_i64, 150, 300, 350]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        gte(
+            cast(
+                column(t, "a", &accessor),
+                DataType::Decimal {
+                    precision: 12,
+                    scale: 2,
+                },
+            ),
+            column(t, "d", &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([smallint("a", [2_i16, 3, 4])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_only_build_lossless_casts() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2]),
+        decimal75("d", 5, 2, [125_i64, 150]),
+        boolean("c", [true, false]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+
+    // Casting to the same type is a no-op
+    assert_eq!(
+        DynProofExpr::<RistrettoPoint>::try_new_cast(column(t, "a", &accessor), DataType::BigInt)
+            .unwrap(),
+        column(t, "a", &accessor)
+    );
+
+    // A BIGINT may have 19 digits, so it only fits into decimals with 19 digits before the point
+    assert!(DynProofExpr::<RistrettoPoint>::try_new_cast(
+        column(t, "a", &accessor),
+        DataType::Decimal {
+            precision: 21,
+            scale: 2
+        }
+    )
+    .is_ok());
+    for (name, data_type) in [
+        (
+            "a",
+            DataType::Decimal {
+                precision: 20,
+                scale: 2,
+            },
+        ),
+        ("a", DataType::Int),
+        ("d", DataType::BigInt),
+        (
+            "d",
+            DataType::Decimal {
+                precision: 5,
+                scale: 1,
+            },
+        ),
+    ] {
+        assert!(matches!(
+            DynProofExpr::<RistrettoPoint>::try_new_cast(column(t, name, &accessor), data_type),
+            Err(ConversionError::Unprovable { .. })
+        ));
+    }
+
+    // The types must be compatible
+    assert!(matches!(
+        DynProofExpr::<RistrettoPoint>::try_new_cast(column(t, "c", &accessor), DataType::BigInt),
+        Err(ConversionError::ColumnOperationError {
+            source: ColumnOperationError::CastInvalidColumnType { .. }
+        })
+    ));
+}
@@ -6,7 +6,7 @@ use crate::{
         map::IndexSet,
         proof::ProofError,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
 };
 use bumpalo::Bump;
 use core::marker::PhantomData;
@@ -71,6 +71,16 @@ impl<C: Commitment> ProofExpr<C> for ColumnExpr<C> {
         column
     }
 
+    /// A column is committed to by the table, so there is nothing to add to the first round
+    fn first_round_evaluate<'a>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        _table_length: usize,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+    }
+
     /// Given the selected rows (as a slice of booleans), evaluate the column expression and
     /// add the components needed to prove the result
     fn prover_evaluate<'a>(
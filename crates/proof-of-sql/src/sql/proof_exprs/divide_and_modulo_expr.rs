@@ -0,0 +1,405 @@
+use super::{
+    count_equals_zero, count_range_check, count_sign_with_range_check,
+    final_round_evaluate_range_check, first_round_evaluate_range_check, first_round_evaluate_sign,
+    prover_evaluate_equals_zero, prover_evaluate_sign_with_range_check,
+    verifier_evaluate_equals_zero, verifier_evaluate_range_check,
+    verifier_evaluate_sign_with_range_check, word_weight, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType,
+        VerificationBuilder,
+    },
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for
+/// ```ignore
+///     <lhs> / <rhs>
+///     <lhs> % <rhs>
+/// ```
+/// where both operands are integers of at most 64 bits.
+///
+/// Like in SQL, the quotient `q` is rounded toward zero, so the remainder `r` has the sign of
+/// `lhs`. Dividing by zero results in a quotient of zero and a remainder of `lhs`.
+///
+/// The prover commits to `q`, `r`, `|rhs|` and `|r|`, where the absolute values are tied to the
+/// proven signs of `rhs` and `lhs`, and shows
+/// - `lhs = q * rhs + r`,
+/// - `q = 0` if `rhs = 0`,
+/// - `-2^(B-1) <= q < 2^(B+7) - 2^(B-1)` with a range check over one more byte than the result,
+///   where `B` is the bit size of the result, so that the equation holds over the integers and
+///   not only in the scalar field, and
+/// - `0 <= |r| < |rhs|` with two range checks, where the upper bound is dropped if `rhs = 0`.
+///
+/// The extra byte of the quotient makes the smallest value of the result type divided by `-1`
+/// provable. Its quotient `2^(B-1)` is out of range of the result type, so it is reported as an
+/// overflow when the result is decoded, like the integer overflow of postprocessing.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DivideAndModuloExpr<C: Commitment> {
+    lhs: Box<DynProofExpr<C>>,
+    rhs: Box<DynProofExpr<C>>,
+    is_modulo: bool,
+}
+
+/// The witness of a division, see [`DivideAndModuloExpr`]
+struct DivideAndModuloWitness<'a, S: Scalar> {
+    quotient: &'a [S],
+    remainder: &'a [S],
+    abs_rhs: &'a [S],
+    abs_remainder: &'a [S],
+    shifted_quotient: &'a [S],
+    remainder_gap: &'a [S],
+}
+
+impl<C: Commitment> DivideAndModuloExpr<C> {
+    /// Create a new `/` expression, or a new `%` expression if `is_modulo` is set
+    pub fn new(lhs: Box<DynProofExpr<C>>, rhs: Box<DynProofExpr<C>>, is_modulo: bool) -> Self {
+        Self {
+            lhs,
+            rhs,
+            is_modulo,
+        }
+    }
+
+    /// The number of bytes of the result type, which bound `|r|` and `|rhs| - |r| - 1`
+    /// (or `2^B - |r| - 1` if `rhs = 0`)
+    fn num_words(&self) -> usize {
+        self.data_type().byte_size()
+    }
+
+    /// The number of bytes that bound `q + 2^(B-1)`, which is `2^B` for the quotient of the
+    /// smallest value of the result type and `-1`
+    fn num_quotient_words(&self) -> usize {
+        self.num_words() + 1
+    }
+
+    /// `2^(B-1)`, the offset that shifts the quotient into `[0, 2^B)`
+    fn quotient_offset<S: Scalar>(&self) -> S {
+        S::from(1_i128 << (self.data_type().bit_size() - 1))
+    }
+
+    /// Divide the rows of `lhs` by the rows of `rhs`
+    ///
+    /// Values that don't fit into an `i128` can't be the result of an integer expression that is
+    /// in range, so they are treated as zero. This leads to a witness that fails verification.
+    fn witness<'a, S: Scalar>(
+        &self,
+        alloc: &'a Bump,
+        lhs: &[S],
+        rhs: &[S],
+    ) -> DivideAndModuloWitness<'a, S> {
+        let n = lhs.len();
+        let to_i128 = |value: S| TryInto::<i128>::try_into(value).unwrap_or_default();
+        let quotients: &[i128] = alloc.alloc_slice_fill_with(n, |i| {
+            to_i128(lhs[i]).checked_div(to_i128(rhs[i])).unwrap_or(0)
+        });
+        let remainders: &[i128] = alloc.alloc_slice_fill_with(n, |i| {
+            let lhs = to_i128(lhs[i]);
+            lhs.checked_rem(to_i128(rhs[i])).unwrap_or(lhs)
+        });
+        let quotient = alloc.alloc_slice_fill_with(n, |i| S::from(quotients[i]));
+        let remainder = alloc.alloc_slice_fill_with(n, |i| S::from(remainders[i]));
+        let abs_rhs = alloc.alloc_slice_fill_with(n, |i| S::from(to_i128(rhs[i]).saturating_abs()));
+        let abs_remainder =
+            alloc.alloc_slice_fill_with(n, |i| S::from(remainders[i].saturating_abs()));
+        let offset = self.quotient_offset::<S>();
+        let shifted_quotient = alloc.alloc_slice_fill_with(n, |i| quotient[i] + offset);
+        let zero_rhs_offset = word_weight::<S>(self.num_words());
+        let remainder_gap = alloc.alloc_slice_fill_with(n, |i| {
+            let gap = abs_rhs[i] - abs_remainder[i] - S::ONE;
+            if rhs[i] == S::ZERO {
+                gap + zero_rhs_offset
+            } else {
+                gap
+            }
+        });
+        DivideAndModuloWitness {
+            quotient,
+            remainder,
+            abs_rhs,
+            abs_remainder,
+            shifted_quotient,
+            remainder_gap,
+        }
+    }
+
+    /// The column of the result, which is either the quotient or the remainder
+    fn result<'a, S: Scalar>(&self, witness: &DivideAndModuloWitness<'a, S>) -> Column<'a, S> {
+        if self.is_modulo {
+            Column::Scalar(witness.remainder)
+        } else {
+            Column::Scalar(witness.quotient)
+        }
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for DivideAndModuloExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.lhs.count(builder)?;
+        self.rhs.count(builder)?;
+        builder.count_first_round_mles(4);
+        count_sign_with_range_check(builder)?;
+        count_sign_with_range_check(builder)?;
+        count_equals_zero(builder);
+        builder.count_subpolynomials(4);
+        builder.count_degree(3);
+        count_range_check(builder, self.num_quotient_words());
+        for _ in 0..2 {
+            count_range_check(builder, self.num_words());
+        }
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        self.lhs
+            .data_type()
+            .max_integer_type(&self.rhs.data_type())
+            .expect("Operands of a division are integers")
+    }
+
+    #[tracing::instrument(
+        name = "DivideAndModuloExpr::result_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let lhs_column = self.lhs.result_evaluate(table_length, alloc, accessor);
+        let rhs_column = self.rhs.result_evaluate(table_length, alloc, accessor);
+        let witness = self.witness(
+            alloc,
+            lhs_column.as_scalar(alloc),
+            rhs_column.as_scalar(alloc),
+        );
+        self.result(&witness)
+    }
+
+    #[tracing::instrument(
+        name = "DivideAndModuloExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.lhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        self.rhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        let lhs = self
+            .lhs
+            .result_evaluate(table_length, alloc, accessor)
+            .as_scalar(alloc);
+        let rhs = self
+            .rhs
+            .result_evaluate(table_length, alloc, accessor)
+            .as_scalar(alloc);
+        let witness = self.witness(alloc, lhs, rhs);
+        builder.produce_intermediate_mle(witness.quotient);
+        builder.produce_intermediate_mle(witness.remainder);
+        builder.produce_intermediate_mle(witness.abs_rhs);
+        builder.produce_intermediate_mle(witness.abs_remainder);
+
+        // words of sign(lhs) and sign(rhs)
+        first_round_evaluate_sign(builder, alloc, lhs);
+        first_round_evaluate_sign(builder, alloc, rhs);
+
+        first_round_evaluate_range_check(
+            builder,
+            alloc,
+            witness.shifted_quotient,
+            self.num_quotient_words(),
+        );
+        let num_words = self.num_words();
+        first_round_evaluate_range_check(builder, alloc, witness.abs_remainder, num_words);
+        first_round_evaluate_range_check(builder, alloc, witness.remainder_gap, num_words);
+    }
+
+    #[tracing::instrument(
+        name = "DivideAndModuloExpr::prover_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let lhs = self
+            .lhs
+            .prover_evaluate(builder, alloc, accessor)
+            .as_scalar(alloc);
+        let rhs = self
+            .rhs
+            .prover_evaluate(builder, alloc, accessor)
+            .as_scalar(alloc);
+        let witness = self.witness(alloc, lhs, rhs);
+
+        // sign(lhs) and sign(rhs)
+        let lhs_sign = prover_evaluate_sign_with_range_check(
+            builder,
+            alloc,
+            lhs,
+            #[cfg(test)]
+            false,
+        );
+        let rhs_sign = prover_evaluate_sign_with_range_check(
+            builder,
+            alloc,
+            rhs,
+            #[cfg(test)]
+            false,
+        );
+
+        // rhs == 0
+        let rhs_is_zero = prover_evaluate_equals_zero(builder, alloc, rhs);
+
+        // subpolynomial: lhs - q * rhs - r
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(lhs)]),
+                (
+                    -C::Scalar::ONE,
+                    vec![Box::new(witness.quotient), Box::new(rhs)],
+                ),
+                (-C::Scalar::ONE, vec![Box::new(witness.remainder)]),
+            ],
+        );
+
+        // subpolynomial: q * (rhs == 0)
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![(
+                C::Scalar::ONE,
+                vec![Box::new(witness.quotient), Box::new(rhs_is_zero)],
+            )],
+        );
+
+        // subpolynomial: |rhs| - rhs + 2 * rhs * sign(rhs)
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(witness.abs_rhs)]),
+                (-C::Scalar::ONE, vec![Box::new(rhs)]),
+                (C::Scalar::TWO, vec![Box::new(rhs), Box::new(rhs_sign)]),
+            ],
+        );
+
+        // subpolynomial: |r| - r + 2 * r * sign(lhs)
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(witness.abs_remainder)]),
+                (-C::Scalar::ONE, vec![Box::new(witness.remainder)]),
+                (
+                    C::Scalar::TWO,
+                    vec![Box::new(witness.remainder), Box::new(lhs_sign)],
+                ),
+            ],
+        );
+
+        // q + 2^(B-1) is in [0, 2^(B+8)), |r| and |rhs| - |r| - 1 + 2^B * (rhs == 0) are in [0, 2^B)
+        final_round_evaluate_range_check(
+            builder,
+            alloc,
+            witness.shifted_quotient,
+            self.num_quotient_words(),
+        );
+        let num_words = self.num_words();
+        final_round_evaluate_range_check(builder, alloc, witness.abs_remainder, num_words);
+        final_round_evaluate_range_check(builder, alloc, witness.remainder_gap, num_words);
+
+        self.result(&witness)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.input_one_evaluation;
+        let lhs_eval = self.lhs.verifier_evaluate(builder, accessor)?;
+        let rhs_eval = self.rhs.verifier_evaluate(builder, accessor)?;
+        let quotient_eval = builder.consume_first_round_mle();
+        let remainder_eval = builder.consume_first_round_mle();
+        let abs_rhs_eval = builder.consume_first_round_mle();
+        let abs_remainder_eval = builder.consume_first_round_mle();
+
+        // sign(lhs) and sign(rhs)
+        let lhs_sign_eval = verifier_evaluate_sign_with_range_check(builder, lhs_eval, one_eval)?;
+        let rhs_sign_eval = verifier_evaluate_sign_with_range_check(builder, rhs_eval, one_eval)?;
+
+        // rhs == 0
+        let rhs_is_zero_eval = verifier_evaluate_equals_zero(builder, rhs_eval);
+
+        // subpolynomial: lhs - q * rhs - r
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            lhs_eval - quotient_eval * rhs_eval - remainder_eval,
+        );
+
+        // subpolynomial: q * (rhs == 0)
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            quotient_eval * rhs_is_zero_eval,
+        );
+
+        // subpolynomial: |rhs| - rhs + 2 * rhs * sign(rhs)
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            abs_rhs_eval - rhs_eval + C::Scalar::TWO * rhs_eval * rhs_sign_eval,
+        );
+
+        // subpolynomial: |r| - r + 2 * r * sign(lhs)
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            abs_remainder_eval - remainder_eval + C::Scalar::TWO * remainder_eval * lhs_sign_eval,
+        );
+
+        // q + 2^(B-1) is in [0, 2^(B+8)), |r| and |rhs| - |r| - 1 + 2^B * (rhs == 0) are in [0, 2^B)
+        verifier_evaluate_range_check(
+            builder,
+            quotient_eval + self.quotient_offset::<C::Scalar>() * one_eval,
+            self.num_quotient_words(),
+        )?;
+        let num_words = self.num_words();
+        verifier_evaluate_range_check(builder, abs_remainder_eval, num_words)?;
+        verifier_evaluate_range_check(
+            builder,
+            abs_rhs_eval - abs_remainder_eval - one_eval
+                + word_weight::<C::Scalar>(num_words) * rhs_is_zero_eval,
+            num_words,
+        )?;
+
+        Ok(if self.is_modulo {
+            remainder_eval
+        } else {
+            quotient_eval
+        })
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.lhs.get_column_references(columns);
+        self.rhs.get_column_references(columns);
+    }
+}
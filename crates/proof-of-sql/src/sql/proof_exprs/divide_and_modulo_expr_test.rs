@@ -0,0 +1,197 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, ColumnType, OwnedTableTestAccessor},
+    },
+    sql::{
+        parse::ConversionError,
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, DynProofExpr, ProofExpr},
+        proof_plans::test_utility::*,
+    },
+};
+use curve25519_dalek::RistrettoPoint;
+
+// select a / b as q, a % b as r from sxt.t
+#[test]
+fn we_can_prove_integer_division_and_modulo_rounding_toward_zero() {
+    let data = owned_table([
+        bigint(
+            "a",
+            [7_i64, -7, 7, -7, 5, 0, i64::MAX, i64::MIN, 1_000_000_007],
+        ),
+        int("b", [2_i32, 2, -2, -2, 0, 3, 1000, 7, -1000]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(
+                divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+                "q",
+            ),
+            aliased_plan(
+                modulo(column(t, "a", &accessor), column(t, "b", &accessor)),
+                "r",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint(
+            "q",
+            [
+                3_i64,
+                -3,
+                -3,
+                3,
+                0,
+                0,
+                9_223_372_036_854_775,
+                -1_317_624_576_693_539_401,
+                -1_000_000,
+            ],
+        ),
+        bigint("r", [1_i64, -1, 1, -1, 5, 0, 807, -1, 7]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a from sxt.t where a % 3 = 0
+#[test]
+fn we_can_prove_a_filter_with_a_modulo() {
+    let data = owned_table([smallint("a", [1_i16, 2, 3, -3, -4, 6, 9, -10])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 2, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        equal(
+            modulo(column(t, "a", &accessor), const_smallint(3)),
+            const_smallint(0),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([smallint("a", [3_i16, -3, 6, 9])]);
+    assert_eq!(res, expected_res);
+}
+
+// select a, sum(b / c) as ratio, count(*) as __count__ from sxt.t group by a
+#[test]
+fn we_can_prove_a_sum_of_ratios() {
+    let data = owned_table([
+        bigint("a", [1_i64, 1, 2, 2, 2]),
+        bigint("b", [10_i64, 25, -30, 7, 100]),
+        tinyint("c", [3_i8, 5, 4, -2, 10]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = group_by(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![sum_expr(
+            divide(column(t, "b", &accessor), column(t, "c", &accessor)),
+            "ratio",
+        )],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [1_i64, 2]),
+        bigint("ratio", [8_i64, 0]),
+        bigint("__count__", [2_i64, 3]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+// select a / b as q from sxt.t
+#[test]
+fn we_can_prove_a_quotient_that_overflows_but_the_result_is_an_overflow_error() {
+    let data = owned_table([tinyint("a", [-128_i8, 4]), tinyint("b", [-1_i8, 2])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![aliased_plan(
+            divide(column(t, "a", &accessor), column(t, "b", &accessor)),
+            "q",
+        )],
+        tab(t),
+    );
+    // The proof is valid, but the quotient doesn't fit into the result type
+    let verifiable_res = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+    assert!(matches!(
+        verifiable_res.verify(&ast, &accessor, &()),
+        Err(QueryError::Overflow)
+    ));
+}
+
+// select a % b as r from sxt.t
+#[test]
+fn we_can_prove_the_remainder_of_a_quotient_that_overflows() {
+    let data = owned_table([
+        bigint("a", [i64::MIN, i64::MIN, 4]),
+        bigint("b", [-1_i64, 0, 2]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![aliased_plan(
+            modulo(column(t, "a", &accessor), column(t, "b", &accessor)),
+            "r",
+        )],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("r", [0_i64, i64::MIN, 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_only_divide_integers_of_at_most_64_bits() {
+    let data = owned_table([
+        int("a", [1_i32, 2]),
+        smallint("b", [3_i16, 4]),
+        int128("c", [1_i128, 2]),
+        decimal75("d", 5, 2, [125_i64, 150]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+
+    // The result has the larger integer type
+    assert_eq!(
+        DynProofExpr::<RistrettoPoint>::try_new_modulo(
+            column(t, "b", &accessor),
+            column(t, "a", &accessor)
+        )
+        .unwrap()
+        .data_type(),
+        ColumnType::Int
+    );
+
+    for name in ["c", "d"] {
+        assert!(matches!(
+            DynProofExpr::<RistrettoPoint>::try_new_divide(
+                column(t, "a", &accessor),
+                column(t, name, &accessor)
+            ),
+            Err(ConversionError::InvalidDataType { .. })
+        ));
+        assert!(matches!(
+            DynProofExpr::<RistrettoPoint>::try_new_modulo(
+                column(t, name, &accessor),
+                column(t, "a", &accessor)
+            ),
+            Err(ConversionError::InvalidDataType { .. })
+        ));
+    }
+}
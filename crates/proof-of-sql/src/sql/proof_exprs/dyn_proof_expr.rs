@@ -1,23 +1,32 @@
 use super::{
-    AddSubtractExpr, AggregateExpr, AndExpr, ColumnExpr, EqualsExpr, InequalityExpr, LiteralExpr,
+    AddSubtractExpr, AggregateExpr, AndExpr, BetweenExpr, CaseExpr, CastExpr, ColumnExpr,
+    DivideAndModuloExpr, EqualsExpr, InListExpr, InequalityExpr, LikeExpr, LiteralExpr,
     MultiplyExpr, NotExpr, OrExpr, ProofExpr,
 };
 use crate::{
     base::{
         commitment::Commitment,
-        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, LiteralValue},
+        database::{
+            try_case_column_types, try_cast_column_type, Column, ColumnRef, ColumnType,
+            CommitmentAccessor, DataAccessor, LiteralValue,
+        },
         map::IndexSet,
         proof::ProofError,
     },
     sql::{
         parse::{type_check_binary_operation, ConversionError, ConversionResult},
-        proof::{CountBuilder, FinalRoundBuilder, VerificationBuilder},
+        proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
     },
 };
-use alloc::{boxed::Box, string::ToString};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
 use bumpalo::Bump;
 use core::fmt::Debug;
-use proof_of_sql_parser::intermediate_ast::{AggregationOperator, BinaryOperator};
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, BinaryOperator, DataType};
 use serde::{Deserialize, Serialize};
 
 /// Enum of AST column expression types that implement `ProofExpr`. Is itself a `ProofExpr`.
@@ -37,10 +46,22 @@ pub enum DynProofExpr<C: Commitment> {
     Equals(EqualsExpr<C>),
     /// Provable AST expression for an inequality expression
     Inequality(InequalityExpr<C>),
+    /// Provable AST expression for a `BETWEEN` expression
+    Between(BetweenExpr<C>),
+    /// Provable AST expression for an `IN` expression
+    InList(InListExpr<C>),
+    /// Provable AST expression for a `LIKE` expression
+    Like(LikeExpr<C>),
     /// Provable numeric `+` / `-` expression
     AddSubtract(AddSubtractExpr<C>),
     /// Provable numeric `*` expression
     Multiply(MultiplyExpr<C>),
+    /// Provable integer `/` / `%` expression
+    DivideAndModulo(DivideAndModuloExpr<C>),
+    /// Provable `CASE` expression
+    Case(CaseExpr<C>),
+    /// Provable `CAST` expression
+    Cast(CastExpr<C>),
     /// Provable aggregate expression
     Aggregate(AggregateExpr<C>),
 }
@@ -66,6 +87,18 @@ impl<C: Commitment> DynProofExpr<C> {
         expr.check_data_type(ColumnType::Boolean)?;
         Ok(Self::Not(NotExpr::new(Box::new(expr))))
     }
+    /// Create logical NOT expression of a nullable expression, which is `false` for null rows
+    pub fn try_new_nullable_not(
+        expr: DynProofExpr<C>,
+        presence: DynProofExpr<C>,
+    ) -> ConversionResult<Self> {
+        expr.check_data_type(ColumnType::Boolean)?;
+        presence.check_data_type(ColumnType::Boolean)?;
+        Ok(Self::Not(NotExpr::new_nullable(
+            Box::new(expr),
+            Box::new(presence),
+        )))
+    }
     /// Create CONST expression
     pub fn new_literal(value: LiteralValue<C::Scalar>) -> Self {
         Self::Literal(LiteralExpr::new(value))
@@ -109,6 +142,29 @@ impl<C: Commitment> DynProofExpr<C> {
         }
     }
 
+    /// Create a new `BETWEEN` expression of an integer expression with the bounds `low <= high`
+    pub fn try_new_between(expr: DynProofExpr<C>, low: i64, high: i64) -> ConversionResult<Self> {
+        expr.check_small_integer_data_type()?;
+        if low > high {
+            return Err(ConversionError::InvalidExpression {
+                expression: format!("BETWEEN {low} AND {high} has an empty range"),
+            });
+        }
+        Ok(Self::Between(BetweenExpr::new(Box::new(expr), low, high)))
+    }
+
+    /// Create a new `IN` expression of an integer expression
+    pub fn try_new_in_list(expr: DynProofExpr<C>, list: Vec<i64>) -> ConversionResult<Self> {
+        expr.check_small_integer_data_type()?;
+        Ok(Self::InList(InListExpr::new(Box::new(expr), list)))
+    }
+
+    /// Create a new `LIKE` expression
+    pub fn try_new_like(expr: DynProofExpr<C>, pattern: String) -> ConversionResult<Self> {
+        expr.check_data_type(ColumnType::VarChar)?;
+        Ok(Self::Like(LikeExpr::new(Box::new(expr), pattern)))
+    }
+
     /// Create a new add expression
     pub fn try_new_add(lhs: DynProofExpr<C>, rhs: DynProofExpr<C>) -> ConversionResult<Self> {
         let lhs_datatype = lhs.data_type();
@@ -162,11 +218,87 @@ impl<C: Commitment> DynProofExpr<C> {
         }
     }
 
+    /// Create a new divide expression of two integer expressions, which rounds toward zero
+    pub fn try_new_divide(lhs: DynProofExpr<C>, rhs: DynProofExpr<C>) -> ConversionResult<Self> {
+        Self::try_new_divide_and_modulo(lhs, rhs, false)
+    }
+
+    /// Create a new modulo expression of two integer expressions, whose result has the sign
+    /// of `lhs`
+    pub fn try_new_modulo(lhs: DynProofExpr<C>, rhs: DynProofExpr<C>) -> ConversionResult<Self> {
+        Self::try_new_divide_and_modulo(lhs, rhs, true)
+    }
+
+    fn try_new_divide_and_modulo(
+        lhs: DynProofExpr<C>,
+        rhs: DynProofExpr<C>,
+        is_modulo: bool,
+    ) -> ConversionResult<Self> {
+        lhs.check_small_integer_data_type()?;
+        rhs.check_small_integer_data_type()?;
+        Ok(Self::DivideAndModulo(DivideAndModuloExpr::new(
+            Box::new(lhs),
+            Box::new(rhs),
+            is_modulo,
+        )))
+    }
+
+    /// Create a new `CASE WHEN condition THEN then_expr ELSE else_expr END` expression
+    pub fn try_new_case(
+        condition: DynProofExpr<C>,
+        then_expr: DynProofExpr<C>,
+        else_expr: DynProofExpr<C>,
+    ) -> ConversionResult<Self> {
+        condition.check_data_type(ColumnType::Boolean)?;
+        let data_type = try_case_column_types(then_expr.data_type(), else_expr.data_type())?;
+        if !data_type.is_numeric() && data_type != ColumnType::Boolean {
+            return Err(ConversionError::Unprovable {
+                error: format!("CASE with results of type {data_type} is not supported yet"),
+            });
+        }
+        Ok(Self::Case(CaseExpr::new(
+            Box::new(condition),
+            Box::new(then_expr),
+            Box::new(else_expr),
+        )))
+    }
+
+    /// Create a new `CAST(expr AS data_type)` expression
+    ///
+    /// Only casts that keep every value and at most raise its scale are provable.
+    pub fn try_new_cast(expr: DynProofExpr<C>, data_type: DataType) -> ConversionResult<Self> {
+        let from_type = expr.data_type();
+        let to_type = try_cast_column_type(from_type, data_type)?;
+        if from_type == to_type {
+            Ok(expr)
+        } else if CastExpr::<C>::is_lossless(from_type, to_type) {
+            Ok(Self::Cast(CastExpr::new(Box::new(expr), to_type)))
+        } else {
+            Err(ConversionError::Unprovable {
+                error: format!("CAST from {from_type} to {to_type} is not supported yet"),
+            })
+        }
+    }
+
     /// Create a new aggregate expression
     pub fn new_aggregate(op: AggregationOperator, expr: DynProofExpr<C>) -> Self {
         Self::Aggregate(AggregateExpr::new(op, Box::new(expr)))
     }
 
+    /// Create a new aggregate expression of a nullable expression, whose null rows are ignored
+    pub fn try_new_nullable_aggregate(
+        op: AggregationOperator,
+        expr: DynProofExpr<C>,
+        presence: DynProofExpr<C>,
+    ) -> ConversionResult<Self> {
+        presence.check_data_type(ColumnType::Boolean)?;
+        Ok(Self::Aggregate(AggregateExpr::new_nullable(
+            op,
+            Box::new(expr),
+            Box::new(presence),
+        )))
+    }
+
     /// Check that the plan has the correct data type
     fn check_data_type(&self, data_type: ColumnType) -> ConversionResult<()> {
         if self.data_type() == data_type {
@@ -178,6 +310,19 @@ impl<C: Commitment> DynProofExpr<C> {
             })
         }
     }
+
+    /// Checks that the expression is an integer of at most 64 bits
+    fn check_small_integer_data_type(&self) -> ConversionResult<()> {
+        match self.data_type() {
+            ColumnType::TinyInt | ColumnType::SmallInt | ColumnType::Int | ColumnType::BigInt => {
+                Ok(())
+            }
+            actual => Err(ConversionError::InvalidDataType {
+                actual,
+                expected: ColumnType::BigInt,
+            }),
+        }
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for DynProofExpr<C> {
@@ -190,8 +335,14 @@ impl<C: Commitment> ProofExpr<C> for DynProofExpr<C> {
             DynProofExpr::Literal(expr) => ProofExpr::<C>::count(expr, builder),
             DynProofExpr::Equals(expr) => ProofExpr::<C>::count(expr, builder),
             DynProofExpr::Inequality(expr) => ProofExpr::<C>::count(expr, builder),
+            DynProofExpr::Between(expr) => ProofExpr::<C>::count(expr, builder),
+            DynProofExpr::InList(expr) => ProofExpr::<C>::count(expr, builder),
+            DynProofExpr::Like(expr) => ProofExpr::<C>::count(expr, builder),
             DynProofExpr::AddSubtract(expr) => ProofExpr::<C>::count(expr, builder),
             DynProofExpr::Multiply(expr) => ProofExpr::<C>::count(expr, builder),
+            DynProofExpr::DivideAndModulo(expr) => ProofExpr::<C>::count(expr, builder),
+            DynProofExpr::Case(expr) => ProofExpr::<C>::count(expr, builder),
+            DynProofExpr::Cast(expr) => ProofExpr::<C>::count(expr, builder),
             DynProofExpr::Aggregate(expr) => ProofExpr::<C>::count(expr, builder),
         }
     }
@@ -201,13 +352,19 @@ impl<C: Commitment> ProofExpr<C> for DynProofExpr<C> {
             DynProofExpr::Column(expr) => expr.data_type(),
             DynProofExpr::AddSubtract(expr) => expr.data_type(),
             DynProofExpr::Multiply(expr) => expr.data_type(),
+            DynProofExpr::DivideAndModulo(expr) => expr.data_type(),
+            DynProofExpr::Case(expr) => expr.data_type(),
+            DynProofExpr::Cast(expr) => expr.data_type(),
             DynProofExpr::Aggregate(expr) => expr.data_type(),
             DynProofExpr::Literal(expr) => ProofExpr::<C>::data_type(expr),
             DynProofExpr::And(_)
             | DynProofExpr::Or(_)
             | DynProofExpr::Not(_)
             | DynProofExpr::Equals(_)
-            | DynProofExpr::Inequality(_) => ColumnType::Boolean,
+            | DynProofExpr::Inequality(_)
+            | DynProofExpr::Between(_)
+            | DynProofExpr::InList(_)
+            | DynProofExpr::Like(_) => ColumnType::Boolean,
         }
     }
 
@@ -239,18 +396,95 @@ impl<C: Commitment> ProofExpr<C> for DynProofExpr<C> {
             DynProofExpr::Inequality(expr) => {
                 ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
             }
+            DynProofExpr::Between(expr) => {
+                ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            DynProofExpr::InList(expr) => {
+                ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            DynProofExpr::Like(expr) => {
+                ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
             DynProofExpr::AddSubtract(expr) => {
                 ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
             }
             DynProofExpr::Multiply(expr) => {
                 ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
             }
+            DynProofExpr::DivideAndModulo(expr) => {
+                ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            DynProofExpr::Case(expr) => {
+                ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
+            DynProofExpr::Cast(expr) => {
+                ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
+            }
             DynProofExpr::Aggregate(expr) => {
                 ProofExpr::<C>::result_evaluate(expr, table_length, alloc, accessor)
             }
         }
     }
 
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        match self {
+            DynProofExpr::Column(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::And(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Or(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Not(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Literal(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Equals(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Inequality(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Between(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::InList(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Like(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::AddSubtract(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Multiply(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::DivideAndModulo(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Case(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Cast(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+            DynProofExpr::Aggregate(expr) => {
+                ProofExpr::<C>::first_round_evaluate(expr, builder, table_length, alloc, accessor);
+            }
+        }
+    }
+
     fn prover_evaluate<'a>(
         &self,
         builder: &mut FinalRoundBuilder<'a, C::Scalar>,
@@ -279,12 +513,30 @@ impl<C: Commitment> ProofExpr<C> for DynProofExpr<C> {
             DynProofExpr::Inequality(expr) => {
                 ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
+            DynProofExpr::Between(expr) => {
+                ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            DynProofExpr::InList(expr) => {
+                ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            DynProofExpr::Like(expr) => {
+                ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
             DynProofExpr::AddSubtract(expr) => {
                 ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
             DynProofExpr::Multiply(expr) => {
                 ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
+            DynProofExpr::DivideAndModulo(expr) => {
+                ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            DynProofExpr::Case(expr) => {
+                ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
+            DynProofExpr::Cast(expr) => {
+                ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
+            }
             DynProofExpr::Aggregate(expr) => {
                 ProofExpr::<C>::prover_evaluate(expr, builder, alloc, accessor)
             }
@@ -306,8 +558,14 @@ impl<C: Commitment> ProofExpr<C> for DynProofExpr<C> {
             DynProofExpr::Literal(expr) => expr.verifier_evaluate(builder, accessor),
             DynProofExpr::Equals(expr) => expr.verifier_evaluate(builder, accessor),
             DynProofExpr::Inequality(expr) => expr.verifier_evaluate(builder, accessor),
+            DynProofExpr::Between(expr) => expr.verifier_evaluate(builder, accessor),
+            DynProofExpr::InList(expr) => expr.verifier_evaluate(builder, accessor),
+            DynProofExpr::Like(expr) => expr.verifier_evaluate(builder, accessor),
             DynProofExpr::AddSubtract(expr) => expr.verifier_evaluate(builder, accessor),
             DynProofExpr::Multiply(expr) => expr.verifier_evaluate(builder, accessor),
+            DynProofExpr::DivideAndModulo(expr) => expr.verifier_evaluate(builder, accessor),
+            DynProofExpr::Case(expr) => expr.verifier_evaluate(builder, accessor),
+            DynProofExpr::Cast(expr) => expr.verifier_evaluate(builder, accessor),
             DynProofExpr::Aggregate(expr) => expr.verifier_evaluate(builder, accessor),
         }
     }
@@ -321,8 +579,16 @@ impl<C: Commitment> ProofExpr<C> for DynProofExpr<C> {
             DynProofExpr::Literal(expr) => ProofExpr::<C>::get_column_references(expr, columns),
             DynProofExpr::Equals(expr) => ProofExpr::<C>::get_column_references(expr, columns),
             DynProofExpr::Inequality(expr) => ProofExpr::<C>::get_column_references(expr, columns),
+            DynProofExpr::Between(expr) => ProofExpr::<C>::get_column_references(expr, columns),
+            DynProofExpr::InList(expr) => ProofExpr::<C>::get_column_references(expr, columns),
+            DynProofExpr::Like(expr) => ProofExpr::<C>::get_column_references(expr, columns),
             DynProofExpr::AddSubtract(expr) => ProofExpr::<C>::get_column_references(expr, columns),
             DynProofExpr::Multiply(expr) => ProofExpr::<C>::get_column_references(expr, columns),
+            DynProofExpr::DivideAndModulo(expr) => {
+                ProofExpr::<C>::get_column_references(expr, columns);
+            }
+            DynProofExpr::Case(expr) => ProofExpr::<C>::get_column_references(expr, columns),
+            DynProofExpr::Cast(expr) => ProofExpr::<C>::get_column_references(expr, columns),
             DynProofExpr::Aggregate(expr) => ProofExpr::<C>::get_column_references(expr, columns),
         }
     }
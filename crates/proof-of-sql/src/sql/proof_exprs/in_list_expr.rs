@@ -0,0 +1,281 @@
+use super::{
+    count_range_check, final_round_evaluate_range_check, first_round_evaluate_range_check,
+    verifier_evaluate_range_check, DynProofExpr, ProofExpr,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
+        proof_gadgets::{
+            count_membership_check, prover_evaluate_membership_check,
+            verifier_evaluate_membership_check,
+        },
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// The number of bytes of `expr - lower - 1` and `upper - expr - 1`, which are below `2^64` for
+/// every interval containing a 64 bit integer
+const NUM_WORDS: usize = 8;
+
+/// Provable AST expression for
+/// ```ignore
+///     <expr> IN (<value_1>, ..., <value_m>)
+/// ```
+/// where `expr` is an integer expression and the values are integer literals.
+///
+/// The sorted values cut the 64 bit integers into intervals: every value `[v_j, v_j]` and the
+/// gaps `(v_j, v_{j+1})` between them, where `v_0 = i64::MIN - 1` and `v_{m+1} = i64::MAX + 1`.
+/// The verifier knows the table of these intervals. For every row, the prover commits to the
+/// bounds `lower` and `upper` of the interval containing `expr` and to the result, which is
+/// `true` exactly for the values, and shows
+/// - with a membership check that `(lower, upper, result)` is a row of the table and
+/// - with two range checks that `lower + gap <= expr <= upper - gap`, where `gap = 1 - result`.
+///
+/// Unlike a disjunction of equalities, the size of the proof does not depend on the length of the
+/// list.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InListExpr<C: Commitment> {
+    expr: Box<DynProofExpr<C>>,
+    list: Vec<i64>,
+}
+
+/// The witness of an `IN` expression, see [`InListExpr`]
+struct InListWitness<'a, S: Scalar> {
+    matches: &'a [bool],
+    lower: &'a [S],
+    upper: &'a [S],
+    multiplicities: &'a [i64],
+    above_lower: &'a [S],
+    below_upper: &'a [S],
+}
+
+impl<C: Commitment> InListExpr<C> {
+    /// Create a new `IN` expression
+    pub fn new(expr: Box<DynProofExpr<C>>, list: Vec<i64>) -> Self {
+        Self { expr, list }
+    }
+
+    /// The table of intervals as the columns `(lower, upper, is_value)`: the gap below the first
+    /// value, and then every value followed by the gap above it.
+    ///
+    /// The list is sorted here rather than in the constructor, since the verifier can't rely on a
+    /// deserialized expression to be sorted.
+    fn intervals<S: Scalar>(&self) -> (Vec<S>, Vec<S>, Vec<bool>) {
+        let mut values = self.list.clone();
+        values.sort_unstable();
+        values.dedup();
+        let mut lower = Vec::with_capacity(2 * values.len() + 1);
+        let mut upper = Vec::with_capacity(2 * values.len() + 1);
+        let mut is_value = Vec::with_capacity(2 * values.len() + 1);
+        let mut gap_start = S::from(i64::MIN) - S::ONE;
+        for value in values {
+            let value = S::from(value);
+            lower.extend([gap_start, value]);
+            upper.extend([value, value]);
+            is_value.extend([false, true]);
+            gap_start = value;
+        }
+        lower.push(gap_start);
+        upper.push(S::from(i64::MAX) + S::ONE);
+        is_value.push(false);
+        (lower, upper, is_value)
+    }
+
+    /// Find the interval containing each row of `column`
+    fn witness<'a, S: Scalar>(
+        alloc: &'a Bump,
+        column: Column<'a, S>,
+        (lower, upper, is_value): &(Vec<S>, Vec<S>, Vec<bool>),
+    ) -> InListWitness<'a, S> {
+        let values = column.as_scalar(alloc);
+        let multiplicities = alloc.alloc_slice_fill_copy(lower.len(), 0i64);
+        let indexes: Vec<usize> = values
+            .iter()
+            .map(|value| {
+                // The last interval whose lower bound is at most the value
+                let index = lower
+                    .partition_point(|bound| bound.signed_cmp(value).is_le())
+                    .saturating_sub(1);
+                // A value of the list is the lower bound of the gap above it as well
+                let index = if lower[index] == *value && !is_value[index] {
+                    index - 1
+                } else {
+                    index
+                };
+                multiplicities[index] += 1;
+                index
+            })
+            .collect();
+        let multiplicities = &*multiplicities;
+        let matches = alloc.alloc_slice_fill_with(values.len(), |i| is_value[indexes[i]]);
+        let lower = alloc.alloc_slice_fill_with(values.len(), |i| lower[indexes[i]]);
+        let upper = alloc.alloc_slice_fill_with(values.len(), |i| upper[indexes[i]]);
+        let gap = |i: usize| if matches[i] { S::ZERO } else { S::ONE };
+        let above_lower =
+            alloc.alloc_slice_fill_with(values.len(), |i| values[i] - lower[i] - gap(i));
+        let below_upper =
+            alloc.alloc_slice_fill_with(values.len(), |i| upper[i] - values[i] - gap(i));
+        InListWitness {
+            matches,
+            lower,
+            upper,
+            multiplicities,
+            above_lower,
+            below_upper,
+        }
+    }
+}
+
+impl<C: Commitment> ProofExpr<C> for InListExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        builder.count_one_evaluation_lengths(1);
+        builder.count_first_round_mles(4);
+        builder.count_post_result_challenges(2);
+        count_membership_check(builder);
+        count_range_check(builder, NUM_WORDS);
+        count_range_check(builder, NUM_WORDS);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "InListExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        Column::Boolean(Self::witness(alloc, column, &self.intervals()).matches)
+    }
+
+    #[tracing::instrument(name = "InListExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.expr
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let intervals = self.intervals();
+        let witness = Self::witness(alloc, column, &intervals);
+        builder.produce_one_evaluation_length(intervals.0.len());
+        builder.produce_intermediate_mle(witness.matches);
+        builder.produce_intermediate_mle(witness.lower);
+        builder.produce_intermediate_mle(witness.upper);
+        builder.produce_intermediate_mle(witness.multiplicities);
+        builder.request_post_result_challenges(2);
+        first_round_evaluate_range_check(builder, alloc, witness.above_lower, NUM_WORDS);
+        first_round_evaluate_range_check(builder, alloc, witness.below_upper, NUM_WORDS);
+    }
+
+    #[tracing::instrument(name = "InListExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        let intervals = self.intervals();
+        let witness = Self::witness(alloc, column, &intervals);
+        let (table_lower, table_upper, table_is_value) = intervals;
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // (lower[i], upper[i], matches[i]) is one of the intervals
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            column.len(),
+            &[
+                Column::Scalar(witness.lower),
+                Column::Scalar(witness.upper),
+                Column::Boolean(witness.matches),
+            ],
+            table_lower.len(),
+            &[
+                Column::Scalar(alloc.alloc_slice_copy(&table_lower)),
+                Column::Scalar(alloc.alloc_slice_copy(&table_upper)),
+                Column::Boolean(alloc.alloc_slice_copy(&table_is_value)),
+            ],
+            Column::BigInt(witness.multiplicities),
+        );
+
+        // lower[i] + gap[i] <= expr[i] <= upper[i] - gap[i]
+        final_round_evaluate_range_check(builder, alloc, witness.above_lower, NUM_WORDS);
+        final_round_evaluate_range_check(builder, alloc, witness.below_upper, NUM_WORDS);
+
+        Column::Boolean(witness.matches)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let one_eval = builder.mle_evaluations.input_one_evaluation;
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor)?;
+        let (table_lower, table_upper, table_is_value) = self.intervals::<C::Scalar>();
+        if builder.consume_one_evaluation_length() != table_lower.len() {
+            return Err(ProofError::VerificationError {
+                error: "interval table of IN has an invalid length",
+            });
+        }
+        let matches_eval = builder.consume_first_round_mle();
+        let lower_eval = builder.consume_first_round_mle();
+        let upper_eval = builder.consume_first_round_mle();
+        let multiplicity_eval = builder.consume_first_round_mle();
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let table_is_value: Vec<C::Scalar> = table_is_value.into_iter().map(Into::into).collect();
+        let table_lower_eval = builder.mle_evaluations.evaluate(&table_lower);
+        let table_upper_eval = builder.mle_evaluations.evaluate(&table_upper);
+        let table_is_value_eval = builder.mle_evaluations.evaluate(&table_is_value);
+        let table_one_eval = builder.mle_evaluations.chi_evaluation(table_lower.len());
+
+        // (lower[i], upper[i], matches[i]) is one of the intervals
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            one_eval,
+            &[lower_eval, upper_eval, matches_eval],
+            table_one_eval,
+            &[table_lower_eval, table_upper_eval, table_is_value_eval],
+            multiplicity_eval,
+        );
+
+        // lower[i] + gap[i] <= expr[i] <= upper[i] - gap[i]
+        let gap_eval = one_eval - matches_eval;
+        verifier_evaluate_range_check(builder, expr_eval - lower_eval - gap_eval, NUM_WORDS)?;
+        verifier_evaluate_range_check(builder, upper_eval - expr_eval - gap_eval, NUM_WORDS)?;
+
+        Ok(matches_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
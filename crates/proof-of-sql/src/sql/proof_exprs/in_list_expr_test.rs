@@ -0,0 +1,122 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef},
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+        proof_plans::test_utility::*,
+    },
+};
+
+fn make_accessor() -> (OwnedTableTestAccessor<'static, InnerProductProof>, TableRef) {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6, 7]),
+        bigint("b", [-5_i64, 0, 3, 10, 11, i64::MIN, i64::MAX]),
+        smallint("c", [-7_i16, 4, 4, 5, 300, 6, 4]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    (accessor, t)
+}
+
+#[test]
+fn we_can_prove_an_in_list_query() {
+    let (accessor, t) = make_accessor();
+    let ast = filter(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        tab(t),
+        in_list(column(t, "b", &accessor), &[10, -5, 4, 11]),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1_i64, 4, 5]), bigint("b", [-5_i64, 10, 11])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_not_in_list_query_with_extreme_values() {
+    let (accessor, t) = make_accessor();
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        not(in_list(column(t, "b", &accessor), &[i64::MAX, i64::MIN, 0])),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [1_i64, 3, 4, 5])]));
+}
+
+#[test]
+fn we_can_prove_in_list_queries_with_duplicates_and_adjacent_values() {
+    let (accessor, t) = make_accessor();
+    let ast = projection(
+        vec![
+            aliased_plan(
+                in_list(column(t, "c", &accessor), &[5, 4, 5, 6]),
+                "adjacent",
+            ),
+            aliased_plan(in_list(column(t, "a", &accessor), &[100]), "none"),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("adjacent", [false, true, true, true, false, true, true]),
+        boolean("none", [false; 7]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_a_long_list() {
+    let (accessor, t) = make_accessor();
+    let list: Vec<i64> = (0..1000).map(|i| 3 * i).collect();
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        in_list(column(t, "a", &accessor), &list),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [3_i64, 6])]));
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_on_an_empty_table() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [0_i64; 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        in_list(column(t, "a", &accessor), &[1, 2]),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [0_i64; 0])]));
+}
+
+#[test]
+fn we_can_prove_an_in_list_query_with_a_nonzero_offset() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([bigint("a", [-2_i64, 5, 9, 12])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 3, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        in_list(column(t, "a", &accessor), &[12, -2]),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [-2_i64, 12])]));
+}
@@ -1,8 +1,9 @@
 use super::{
-    count_equals_zero, count_or, count_sign, prover_evaluate_equals_zero, prover_evaluate_or,
-    prover_evaluate_sign, result_evaluate_equals_zero, result_evaluate_or, result_evaluate_sign,
+    count_equals_zero, count_or, count_sign_with_range_check, first_round_evaluate_sign,
+    prover_evaluate_equals_zero, prover_evaluate_or, prover_evaluate_sign_with_range_check,
+    result_evaluate_equals_zero, result_evaluate_or, result_evaluate_sign,
     scale_and_add_subtract_eval, scale_and_subtract, verifier_evaluate_equals_zero,
-    verifier_evaluate_or, verifier_evaluate_sign, DynProofExpr, ProofExpr,
+    verifier_evaluate_or, verifier_evaluate_sign_with_range_check, DynProofExpr, ProofExpr,
 };
 use crate::{
     base::{
@@ -11,7 +12,7 @@ use crate::{
         map::IndexSet,
         proof::ProofError,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
 };
 use alloc::boxed::Box;
 use bumpalo::Bump;
@@ -38,6 +39,28 @@ impl<C: Commitment> InequalityExpr<C> {
             treat_column_of_zeros_as_negative: false,
         }
     }
+
+    /// Compute `lhs - rhs` for `<=` and `rhs - lhs` for `>=`, so that the inequality holds
+    /// exactly where the difference is not positive
+    ///
+    /// # Panics
+    /// Panics if the operands can not be scaled to a common scale.
+    fn diff<'a>(
+        &self,
+        alloc: &'a Bump,
+        lhs_column: Column<'a, C::Scalar>,
+        rhs_column: Column<'a, C::Scalar>,
+    ) -> &'a [C::Scalar] {
+        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
+        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
+        if self.is_lte {
+            scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
+                .expect("Failed to scale and subtract")
+        } else {
+            scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
+                .expect("Failed to scale and subtract")
+        }
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for InequalityExpr<C> {
@@ -45,7 +68,7 @@ impl<C: Commitment> ProofExpr<C> for InequalityExpr<C> {
         self.lhs.count(builder)?;
         self.rhs.count(builder)?;
         count_equals_zero(builder);
-        count_sign(builder)?;
+        count_sign_with_range_check(builder)?;
         count_or(builder);
         Ok(())
     }
@@ -63,15 +86,7 @@ impl<C: Commitment> ProofExpr<C> for InequalityExpr<C> {
     ) -> Column<'a, C::Scalar> {
         let lhs_column = self.lhs.result_evaluate(table_length, alloc, accessor);
         let rhs_column = self.rhs.result_evaluate(table_length, alloc, accessor);
-        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
-        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
-        let diff = if self.is_lte {
-            scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
-                .expect("Failed to scale and subtract")
-        } else {
-            scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
-                .expect("Failed to scale and subtract")
-        };
+        let diff = self.diff(alloc, lhs_column, rhs_column);
 
         // diff == 0
         let equals_zero = result_evaluate_equals_zero(table_length, alloc, diff);
@@ -83,6 +98,30 @@ impl<C: Commitment> ProofExpr<C> for InequalityExpr<C> {
         Column::Boolean(result_evaluate_or(table_length, alloc, equals_zero, sign))
     }
 
+    #[tracing::instrument(
+        name = "InequalityExpr::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.lhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        self.rhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        let lhs_column = self.lhs.result_evaluate(table_length, alloc, accessor);
+        let rhs_column = self.rhs.result_evaluate(table_length, alloc, accessor);
+        let diff = self.diff(alloc, lhs_column, rhs_column);
+
+        // words of sign(diff)
+        first_round_evaluate_sign(builder, alloc, diff);
+    }
+
     #[tracing::instrument(name = "InequalityExpr::prover_evaluate", level = "debug", skip_all)]
     fn prover_evaluate<'a>(
         &self,
@@ -92,21 +131,13 @@ impl<C: Commitment> ProofExpr<C> for InequalityExpr<C> {
     ) -> Column<'a, C::Scalar> {
         let lhs_column = self.lhs.prover_evaluate(builder, alloc, accessor);
         let rhs_column = self.rhs.prover_evaluate(builder, alloc, accessor);
-        let lhs_scale = self.lhs.data_type().scale().unwrap_or(0);
-        let rhs_scale = self.rhs.data_type().scale().unwrap_or(0);
-        let diff = if self.is_lte {
-            scale_and_subtract(alloc, lhs_column, rhs_column, lhs_scale, rhs_scale, false)
-                .expect("Failed to scale and subtract")
-        } else {
-            scale_and_subtract(alloc, rhs_column, lhs_column, rhs_scale, lhs_scale, false)
-                .expect("Failed to scale and subtract")
-        };
+        let diff = self.diff(alloc, lhs_column, rhs_column);
 
         // diff == 0
         let equals_zero = prover_evaluate_equals_zero(builder, alloc, diff);
 
         // sign(diff) == -1
-        let sign = prover_evaluate_sign(
+        let sign = prover_evaluate_sign_with_range_check(
             builder,
             alloc,
             diff,
@@ -138,7 +169,7 @@ impl<C: Commitment> ProofExpr<C> for InequalityExpr<C> {
         let equals_zero = verifier_evaluate_equals_zero(builder, diff_eval);
 
         // sign(diff) == -1
-        let sign = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+        let sign = verifier_evaluate_sign_with_range_check(builder, diff_eval, one_eval)?;
 
         // (diff == 0) || (sign(diff) == -1)
         Ok(verifier_evaluate_or(builder, &equals_zero, &sign))
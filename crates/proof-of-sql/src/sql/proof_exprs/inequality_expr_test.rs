@@ -560,6 +560,129 @@ fn we_can_query_random_tables_using_a_non_zero_offset() {
     test_random_tables_with_given_offset(5121);
 }
 
+#[test]
+fn we_can_compare_columns_whose_differences_vary_across_many_bits_with_a_range_check() {
+    let data = owned_table([
+        bigint(
+            "a",
+            [-1_234_567_890_123_i64, 987_654_321, 0, 42, -7, 1 << 40],
+        ),
+        bigint("b", [5_i64, -987_654_321, 0, 1 << 35, -7, 3]),
+        bigint("c", [1_i64, 2, 3, 4, 5, 6]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["c"], &accessor),
+        tab(t),
+        lte(column(t, "a", &accessor), column(t, "b", &accessor)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    // The word table of the range check is sent along with the proof.
+    assert_eq!(
+        verifiable_res
+            .proof
+            .as_ref()
+            .unwrap()
+            .one_evaluation_lengths,
+        vec![256]
+    );
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("c", [1_i64, 3, 4, 5])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_use_several_range_checked_comparisons_in_one_query() {
+    let data = owned_table([
+        bigint(
+            "a",
+            [-1_234_567_890_123_i64, 987_654_321, 0, 42, -7, 1 << 40],
+        ),
+        int128("b", [i128::MIN, 0, i128::MAX, 1 << 100, -(1 << 90), 3]),
+        bigint("c", [1_i64, 2, 3, 4, 5, 6]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = projection(
+        vec![
+            aliased_plan(
+                gte(column(t, "a", &accessor), const_bigint(-1_000_000_000)),
+                "x",
+            ),
+            aliased_plan(lte(column(t, "b", &accessor), const_bigint(42)), "y"),
+            aliased_plan(
+                and(
+                    gte(column(t, "a", &accessor), column(t, "c", &accessor)),
+                    gte(column(t, "b", &accessor), column(t, "a", &accessor)),
+                ),
+                "z",
+            ),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("x", [false, true, true, true, true, true]),
+        boolean("y", [true, true, false, false, true, true]),
+        boolean("z", [false, false, false, true, false, false]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+fn test_random_tables_with_wide_values_and_given_offset(offset: usize) {
+    let dist = Uniform::new(-(1_i64 << 50), 1_i64 << 50);
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    for _ in 0..10 {
+        // Generate random table
+        let n = Uniform::new(1, 21).sample(&mut rng);
+        let data = owned_table([
+            bigint("a", dist.sample_iter(&mut rng).take(n)),
+            bigint("b", dist.sample_iter(&mut rng).take(n)),
+        ]);
+
+        // Create and verify proof
+        let t = "sxt.t".parse().unwrap();
+        let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(
+            t,
+            data.clone(),
+            offset,
+            (),
+        );
+        let ast = filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            gte(column(t, "a", &accessor), column(t, "b", &accessor)),
+        );
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+
+        // Calculate/compare expected result
+        let (expected_a, expected_b): (Vec<_>, Vec<_>) =
+            multizip((data["a"].i64_iter(), data["b"].i64_iter()))
+                .filter(|(a, b)| a >= b)
+                .map(|(a, b)| (*a, *b))
+                .multiunzip();
+        let expected_result = owned_table([bigint("a", expected_a), bigint("b", expected_b)]);
+
+        assert_eq!(expected_result, res);
+    }
+}
+
+#[test]
+fn we_can_query_random_tables_with_wide_values_using_a_zero_offset() {
+    test_random_tables_with_wide_values_and_given_offset(0);
+}
+
+#[test]
+fn we_can_query_random_tables_with_wide_values_using_a_non_zero_offset() {
+    test_random_tables_with_wide_values_and_given_offset(5121);
+}
+
 #[test]
 fn we_can_compute_the_correct_output_of_a_lte_inequality_expr_using_result_evaluate() {
     let data = owned_table([bigint("a", [-1, 9, 1]), bigint("b", [1, 2, 3])]);
@@ -0,0 +1,230 @@
+use super::{DynProofExpr, ProofExpr};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            matches_like_pattern, Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
+        },
+        map::{IndexMap, IndexSet},
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
+        proof_gadgets::{
+            count_membership_check, prover_evaluate_membership_check,
+            verifier_evaluate_membership_check,
+        },
+    },
+};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use bumpalo::Bump;
+use serde::{Deserialize, Serialize};
+
+/// Provable AST expression for
+/// ```ignore
+///     <expr> LIKE <pattern>
+/// ```
+/// where `expr` is a `VarChar` expression and `pattern` is a string literal in which `%` matches
+/// any sequence of characters and `_` matches a single character.
+///
+/// Strings are only committed to by their hashes, so the pattern can't be checked on the
+/// committed data directly. Instead, the prover reveals the distinct values of `expr`, the
+/// dictionary, from which the verifier computes whether each of them matches the pattern.
+/// A membership check of the rows `(expr[i], result[i])` in the rows `(dictionary[j], matches(dictionary[j]))`
+/// then shows that the result is correct.
+///
+/// Note: this reveals every distinct value of `expr` to the verifier, including the ones of rows
+/// that are not in the result, and the proof grows with their number. The planner only builds
+/// this expression if asked to, see `QueryExpr::try_new_revealing_like_dictionaries`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LikeExpr<C: Commitment> {
+    expr: Box<DynProofExpr<C>>,
+    pattern: String,
+}
+
+impl<C: Commitment> LikeExpr<C> {
+    /// Create a new `LIKE` expression
+    pub fn new(expr: Box<DynProofExpr<C>>, pattern: String) -> Self {
+        Self { expr, pattern }
+    }
+
+    /// Evaluate the pattern on every row of the string column `expr`
+    fn matches<'a, S: Scalar>(&self, alloc: &'a Bump, expr: Column<'a, S>) -> &'a [bool] {
+        let strings = string_values(expr);
+        alloc.alloc_slice_fill_with(strings.len(), |i| {
+            matches_like_pattern(strings[i], &self.pattern)
+        })
+    }
+}
+
+/// The strings of a `VarChar` column
+///
+/// # Panics
+/// Panics if the column is not a `VarChar` column, which is ruled out by the type check when the
+/// expression is built.
+fn string_values<S: Scalar>(column: Column<'_, S>) -> &[&str] {
+    match column {
+        Column::VarChar((strings, _)) => strings,
+        _ => panic!("LIKE is only supported on VarChar expressions"),
+    }
+}
+
+/// The distinct strings of a column in order of their first appearance, together with how often
+/// they appear
+fn dictionary<'a>(strings: &[&'a str]) -> IndexMap<&'a str, i64> {
+    let mut dictionary = IndexMap::default();
+    for string in strings {
+        *dictionary.entry(*string).or_insert(0) += 1;
+    }
+    dictionary
+}
+
+impl<C: Commitment> ProofExpr<C> for LikeExpr<C> {
+    fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
+        self.expr.count(builder)?;
+        builder.count_string_dictionaries(1);
+        builder.count_one_evaluation_lengths(1);
+        builder.count_first_round_mles(2);
+        builder.count_post_result_challenges(2);
+        count_membership_check(builder);
+        Ok(())
+    }
+
+    fn data_type(&self) -> ColumnType {
+        ColumnType::Boolean
+    }
+
+    #[tracing::instrument(name = "LikeExpr::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        Column::Boolean(self.matches(alloc, column))
+    }
+
+    #[tracing::instrument(name = "LikeExpr::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.expr
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        let column = self.expr.result_evaluate(table_length, alloc, accessor);
+        let dictionary = dictionary(string_values(column));
+        builder.produce_string_dictionary(
+            dictionary
+                .keys()
+                .map(|string| String::from(*string))
+                .collect(),
+        );
+        builder.produce_one_evaluation_length(dictionary.len());
+        builder.produce_intermediate_mle(self.matches(alloc, column));
+        builder.produce_intermediate_mle(
+            alloc.alloc_slice_fill_iter(dictionary.values().copied()) as &[_]
+        );
+        builder.request_post_result_challenges(2);
+    }
+
+    #[tracing::instrument(name = "LikeExpr::prover_evaluate", level = "debug", skip_all)]
+    fn prover_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Column<'a, C::Scalar> {
+        let column = self.expr.prover_evaluate(builder, alloc, accessor);
+        let matches = self.matches(alloc, column);
+        let dictionary = dictionary(string_values(column));
+        let dictionary_hashes: &[C::Scalar] =
+            alloc.alloc_slice_fill_iter(dictionary.keys().map(|&string| string.into()));
+        let dictionary_matches = alloc.alloc_slice_fill_iter(
+            dictionary
+                .keys()
+                .map(|string| matches_like_pattern(string, &self.pattern)),
+        );
+        let multiplicities = alloc.alloc_slice_fill_iter(dictionary.values().copied());
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // (expr[i], matches[i]) is a row of (dictionary, pattern matches of the dictionary)
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            column.len(),
+            &[column, Column::Boolean(matches)],
+            dictionary.len(),
+            &[
+                Column::Scalar(dictionary_hashes),
+                Column::Boolean(dictionary_matches),
+            ],
+            Column::BigInt(multiplicities),
+        );
+
+        Column::Boolean(matches)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+    ) -> Result<C::Scalar, ProofError> {
+        let expr_eval = self.expr.verifier_evaluate(builder, accessor)?;
+        let dictionary = builder.consume_string_dictionary();
+        if builder.consume_one_evaluation_length() != dictionary.len() {
+            return Err(ProofError::VerificationError {
+                error: "string dictionary of LIKE has an invalid length",
+            });
+        }
+        // The membership check needs the rows of the dictionary to be distinct.
+        if dictionary.iter().collect::<IndexSet<_>>().len() != dictionary.len() {
+            return Err(ProofError::VerificationError {
+                error: "string dictionary of LIKE contains duplicates",
+            });
+        }
+        let matches_eval = builder.consume_first_round_mle();
+        let multiplicity_eval = builder.consume_first_round_mle();
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let dictionary_hashes: Vec<C::Scalar> = dictionary
+            .iter()
+            .map(|string| string.as_str().into())
+            .collect();
+        let dictionary_matches: Vec<C::Scalar> = dictionary
+            .iter()
+            .map(|string| matches_like_pattern(string, &self.pattern).into())
+            .collect();
+        let dictionary_hash_eval = builder.mle_evaluations.evaluate(&dictionary_hashes);
+        let dictionary_match_eval = builder.mle_evaluations.evaluate(&dictionary_matches);
+        let dictionary_one_eval = builder.mle_evaluations.chi_evaluation(dictionary.len());
+        let one_eval = builder.mle_evaluations.input_one_evaluation;
+
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            one_eval,
+            &[expr_eval, matches_eval],
+            dictionary_one_eval,
+            &[dictionary_hash_eval, dictionary_match_eval],
+            multiplicity_eval,
+        );
+
+        Ok(matches_eval)
+    }
+
+    fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
+        self.expr.get_column_references(columns);
+    }
+}
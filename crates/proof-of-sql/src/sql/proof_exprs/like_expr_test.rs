@@ -0,0 +1,147 @@
+use crate::{
+    base::{
+        commitment::InnerProductProof,
+        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TableRef},
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, QueryError, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+        proof_plans::test_utility::*,
+    },
+};
+
+fn make_accessor() -> (OwnedTableTestAccessor<'static, InnerProductProof>, TableRef) {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 3, 4, 5, 6, 7]),
+        varchar("b", ["abc", "abd", "xabc", "ab", "abc", "", "testing123"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    (accessor, t)
+}
+
+#[test]
+fn we_can_prove_a_prefix_like_query() {
+    let (accessor, t) = make_accessor();
+    let ast = filter(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        tab(t),
+        like(column(t, "b", &accessor), "abc%"),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1_i64, 5]), varchar("b", ["abc", "abc"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_not_like_query_with_wildcards() {
+    let (accessor, t) = make_accessor();
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        not(like(column(t, "b", &accessor), "%b_")),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [4_i64, 6, 7])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_several_like_expressions_in_one_query() {
+    let (accessor, t) = make_accessor();
+    let ast = projection(
+        vec![
+            aliased_plan(like(column(t, "b", &accessor), "ab%"), "starts"),
+            aliased_plan(like(column(t, "b", &accessor), "%test%"), "contains"),
+        ],
+        tab(t),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("starts", [true, true, false, true, true, false, false]),
+        boolean("contains", [false, false, false, false, false, false, true]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_like_query_on_an_empty_table() {
+    let data: OwnedTable<Curve25519Scalar> =
+        owned_table([bigint("a", [0_i64; 0]), varchar("b", [""; 0])]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        like(column(t, "b", &accessor), "a%"),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [0_i64; 0])]));
+}
+
+#[test]
+fn we_can_prove_a_like_query_with_a_nonzero_offset() {
+    let data: OwnedTable<Curve25519Scalar> = owned_table([
+        bigint("a", [1_i64, 2, 3]),
+        varchar("b", ["proof", "of", "sql"]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 3, ());
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        like(column(t, "b", &accessor), "%o%"),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [1_i64, 2])]));
+}
+
+#[test]
+fn we_cannot_verify_a_like_query_with_a_tampered_dictionary() {
+    let (accessor, t) = make_accessor();
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        like(column(t, "b", &accessor), "abc%"),
+    );
+    let verifiable_res = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+
+    // Pretending that "xabc" matches
+    let mut tampered_res = verifiable_res.clone();
+    let dictionary = &mut tampered_res.proof.as_mut().unwrap().string_dictionaries[0];
+    let position = dictionary.iter().position(|s| s == "xabc").unwrap();
+    dictionary[position] = "abcx".into();
+    assert!(matches!(
+        tampered_res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError { .. })
+    ));
+
+    // A dictionary with duplicates
+    let mut tampered_res = verifiable_res.clone();
+    let dictionary = &mut tampered_res.proof.as_mut().unwrap().string_dictionaries[0];
+    dictionary[1] = dictionary[0].clone();
+    assert!(matches!(
+        tampered_res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError { .. })
+    ));
+
+    // A dictionary that is missing a value
+    let mut tampered_res = verifiable_res;
+    let proof = tampered_res.proof.as_mut().unwrap();
+    proof.string_dictionaries[0].pop();
+    assert!(matches!(
+        tampered_res.verify(&ast, &accessor, &()),
+        Err(QueryError::ProofError { .. })
+    ));
+}
@@ -7,7 +7,7 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
 };
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
@@ -33,6 +33,12 @@ impl<S: Scalar> LiteralExpr<S> {
     pub fn new(value: LiteralValue<S>) -> Self {
         Self { value }
     }
+
+    /// The value of the literal
+    #[cfg(feature = "evm")]
+    pub(crate) fn value(&self) -> &LiteralValue<S> {
+        &self.value
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for LiteralExpr<C::Scalar> {
@@ -54,6 +60,15 @@ impl<C: Commitment> ProofExpr<C> for LiteralExpr<C::Scalar> {
         Column::from_literal_with_length(&self.value, table_length, alloc)
     }
 
+    fn first_round_evaluate<'a>(
+        &self,
+        _builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        _table_length: usize,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+    }
+
     #[tracing::instrument(name = "LiteralExpr::prover_evaluate", level = "debug", skip_all)]
     fn prover_evaluate<'a>(
         &self,
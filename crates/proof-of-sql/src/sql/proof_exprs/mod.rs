@@ -20,6 +20,11 @@ use multiply_expr::MultiplyExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod multiply_expr_test;
 
+mod divide_and_modulo_expr;
+use divide_and_modulo_expr::DivideAndModuloExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod divide_and_modulo_expr_test;
+
 mod bitwise_verification;
 use bitwise_verification::{
     is_within_acceptable_range, verify_constant_abs_decomposition,
@@ -72,8 +77,37 @@ use equals_expr::{
 #[cfg(all(test, feature = "blitzar"))]
 mod equals_expr_test;
 
+mod between_expr;
+use between_expr::BetweenExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod between_expr_test;
+
+mod in_list_expr;
+use in_list_expr::InListExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod in_list_expr_test;
+
+mod case_expr;
+use case_expr::CaseExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod case_expr_test;
+
+mod cast_expr;
+use cast_expr::CastExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod cast_expr_test;
+
+mod like_expr;
+use like_expr::LikeExpr;
+#[cfg(all(test, feature = "blitzar"))]
+mod like_expr_test;
+
 mod sign_expr;
-use sign_expr::{count_sign, prover_evaluate_sign, result_evaluate_sign, verifier_evaluate_sign};
+pub(crate) use sign_expr::{count_sign, prover_evaluate_sign, verifier_evaluate_sign};
+use sign_expr::{
+    count_sign_with_range_check, first_round_evaluate_sign, prover_evaluate_sign_with_range_check,
+    result_evaluate_sign, verifier_evaluate_sign_with_range_check,
+};
 #[cfg(all(test, feature = "blitzar"))]
 mod sign_expr_test;
 
@@ -88,5 +122,8 @@ pub(crate) use column_expr::ColumnExpr;
 #[cfg(all(test, feature = "blitzar"))]
 mod column_expr_test;
 
-#[allow(dead_code, unused_variables)]
 mod range_check;
+use range_check::{
+    count_range_check, final_round_evaluate_range_check, first_round_evaluate_range_check,
+    verifier_evaluate_range_check, word_weight,
+};
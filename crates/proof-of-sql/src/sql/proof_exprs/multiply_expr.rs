@@ -10,7 +10,10 @@ use crate::{
         proof::ProofError,
     },
     sql::{
-        proof::{CountBuilder, FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType,
+            VerificationBuilder,
+        },
         proof_exprs::multiply_columns,
     },
 };
@@ -31,6 +34,18 @@ impl<C: Commitment> MultiplyExpr<C> {
     pub fn new(lhs: Box<DynProofExpr<C>>, rhs: Box<DynProofExpr<C>>) -> Self {
         Self { lhs, rhs }
     }
+
+    /// The left hand side of the expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn lhs(&self) -> &DynProofExpr<C> {
+        &self.lhs
+    }
+
+    /// The right hand side of the expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn rhs(&self) -> &DynProofExpr<C> {
+        &self.rhs
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for MultiplyExpr<C> {
@@ -62,6 +77,19 @@ impl<C: Commitment> ProofExpr<C> for MultiplyExpr<C> {
         Column::Scalar(scalars)
     }
 
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.lhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        self.rhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+    }
+
     #[tracing::instrument(
         name = "proofs.sql.ast.multiply_expr.prover_evaluate",
         level = "info",
@@ -5,29 +5,77 @@ use crate::{
         database::{Column, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor},
         map::IndexSet,
         proof::ProofError,
+        scalar::Scalar,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
 };
 use alloc::boxed::Box;
 use bumpalo::Bump;
 use serde::{Deserialize, Serialize};
 
 /// Provable logical NOT expression
+///
+/// If `presence` is provided, `expr` is nullable and the result is `presence AND NOT expr`,
+/// i.e. it is `false` for null rows, as long as `expr` is `false` for null rows.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NotExpr<C: Commitment> {
     expr: Box<DynProofExpr<C>>,
+    presence: Option<Box<DynProofExpr<C>>>,
 }
 
 impl<C: Commitment> NotExpr<C> {
     /// Create logical NOT expression
     pub fn new(expr: Box<DynProofExpr<C>>) -> Self {
-        Self { expr }
+        Self {
+            expr,
+            presence: None,
+        }
     }
+
+    /// Create logical NOT expression of a nullable expression
+    pub fn new_nullable(expr: Box<DynProofExpr<C>>, presence: Box<DynProofExpr<C>>) -> Self {
+        Self {
+            expr,
+            presence: Some(presence),
+        }
+    }
+
+    /// The negated expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn expr(&self) -> &DynProofExpr<C> {
+        &self.expr
+    }
+
+    /// Whether the rows of the negated expression are present, if it is nullable
+    #[cfg(feature = "evm")]
+    pub(crate) fn presence(&self) -> Option<&DynProofExpr<C>> {
+        self.presence.as_deref()
+    }
+}
+
+/// Returns `presence AND NOT expr`, where a missing `presence` means that no row is null.
+///
+/// # Panics
+/// Panics if `expr` or `presence` is not boolean.
+fn not_columns<'a, S: Scalar>(
+    alloc: &'a Bump,
+    expr: &Column<'a, S>,
+    presence: Option<&Column<'a, S>>,
+) -> Column<'a, S> {
+    let expr = expr.as_boolean().expect("expr is not boolean");
+    let presence = presence.map(|presence| presence.as_boolean().expect("presence is not boolean"));
+    Column::Boolean(alloc.alloc_slice_fill_with(expr.len(), |i| {
+        presence.map_or(true, |presence| presence[i]) && !expr[i]
+    }))
 }
 
 impl<C: Commitment> ProofExpr<C> for NotExpr<C> {
     fn count(&self, builder: &mut CountBuilder) -> Result<(), ProofError> {
-        self.expr.count(builder)
+        self.expr.count(builder)?;
+        if let Some(presence) = &self.presence {
+            presence.count(builder)?;
+        }
+        Ok(())
     }
 
     fn data_type(&self) -> ColumnType {
@@ -43,8 +91,25 @@ impl<C: Commitment> ProofExpr<C> for NotExpr<C> {
     ) -> Column<'a, C::Scalar> {
         let expr_column: Column<'a, C::Scalar> =
             self.expr.result_evaluate(table_length, alloc, accessor);
-        let expr = expr_column.as_boolean().expect("expr is not boolean");
-        Column::Boolean(alloc.alloc_slice_fill_with(expr.len(), |i| !expr[i]))
+        let presence_column = self
+            .presence
+            .as_ref()
+            .map(|presence| presence.result_evaluate(table_length, alloc, accessor));
+        not_columns(alloc, &expr_column, presence_column.as_ref())
+    }
+
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.expr
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        if let Some(presence) = &self.presence {
+            presence.first_round_evaluate(builder, table_length, alloc, accessor);
+        }
     }
 
     #[tracing::instrument(name = "NotExpr::prover_evaluate", level = "debug", skip_all)]
@@ -56,8 +121,11 @@ impl<C: Commitment> ProofExpr<C> for NotExpr<C> {
     ) -> Column<'a, C::Scalar> {
         let expr_column: Column<'a, C::Scalar> =
             self.expr.prover_evaluate(builder, alloc, accessor);
-        let expr = expr_column.as_boolean().expect("expr is not boolean");
-        Column::Boolean(alloc.alloc_slice_fill_with(expr.len(), |i| !expr[i]))
+        let presence_column = self
+            .presence
+            .as_ref()
+            .map(|presence| presence.prover_evaluate(builder, alloc, accessor));
+        not_columns(alloc, &expr_column, presence_column.as_ref())
     }
 
     fn verifier_evaluate(
@@ -66,10 +134,18 @@ impl<C: Commitment> ProofExpr<C> for NotExpr<C> {
         accessor: &dyn CommitmentAccessor<C>,
     ) -> Result<C::Scalar, ProofError> {
         let eval = self.expr.verifier_evaluate(builder, accessor)?;
-        Ok(builder.mle_evaluations.input_one_evaluation - eval)
+        // Since `expr` is `false` for null rows, `presence AND NOT expr` is just `presence - expr`.
+        let presence_eval = match &self.presence {
+            Some(presence) => presence.verifier_evaluate(builder, accessor)?,
+            None => builder.mle_evaluations.input_one_evaluation,
+        };
+        Ok(presence_eval - eval)
     }
 
     fn get_column_references(&self, columns: &mut IndexSet<ColumnRef>) {
         self.expr.get_column_references(columns);
+        if let Some(presence) = &self.presence {
+            presence.get_column_references(columns);
+        }
     }
 }
@@ -39,6 +39,32 @@ fn we_can_prove_a_not_equals_query_with_a_single_selected_row() {
     assert_eq!(res, expected_res);
 }
 
+#[test]
+fn we_can_prove_a_not_query_on_a_nullable_column() {
+    let data = owned_table([
+        bigint("a", [1_i64, 2, 3]),
+        boolean("b", [true, false, false]),
+        boolean("__b_presence__", [true, true, false]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_from_table(t, data, 0, ());
+    // NOT b is null, and thus not selected, where b is null
+    let ast = filter(
+        cols_expr_plan(t, &["a"], &accessor),
+        tab(t),
+        DynProofExpr::try_new_nullable_not(
+            column(t, "b", &accessor),
+            column(t, "__b_presence__", &accessor),
+        )
+        .unwrap(),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [2])]);
+    assert_eq!(res, expected_res);
+}
+
 fn test_random_tables_with_given_offset(offset: usize) {
     let dist = Uniform::new(-3, 4);
     let mut rng = StdRng::from_seed([0u8; 32]);
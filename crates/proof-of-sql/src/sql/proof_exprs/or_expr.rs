@@ -7,7 +7,10 @@ use crate::{
         proof::ProofError,
         scalar::Scalar,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+    sql::proof::{
+        CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialType,
+        VerificationBuilder,
+    },
 };
 use alloc::{boxed::Box, vec};
 use bumpalo::Bump;
@@ -25,6 +28,18 @@ impl<C: Commitment> OrExpr<C> {
     pub fn new(lhs: Box<DynProofExpr<C>>, rhs: Box<DynProofExpr<C>>) -> Self {
         Self { lhs, rhs }
     }
+
+    /// The left hand side of the expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn lhs(&self) -> &DynProofExpr<C> {
+        &self.lhs
+    }
+
+    /// The right hand side of the expression
+    #[cfg(feature = "evm")]
+    pub(crate) fn rhs(&self) -> &DynProofExpr<C> {
+        &self.rhs
+    }
 }
 
 impl<C: Commitment> ProofExpr<C> for OrExpr<C> {
@@ -55,6 +70,19 @@ impl<C: Commitment> ProofExpr<C> for OrExpr<C> {
         Column::Boolean(result_evaluate_or(table_length, alloc, lhs, rhs))
     }
 
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.lhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+        self.rhs
+            .first_round_evaluate(builder, table_length, alloc, accessor);
+    }
+
     #[tracing::instrument(name = "OrExpr::prover_evaluate", level = "debug", skip_all)]
     fn prover_evaluate<'a>(
         &self,
@@ -5,7 +5,7 @@ use crate::{
         map::IndexSet,
         proof::ProofError,
     },
-    sql::proof::{CountBuilder, FinalRoundBuilder, VerificationBuilder},
+    sql::proof::{CountBuilder, FinalRoundBuilder, FirstRoundBuilder, VerificationBuilder},
 };
 use bumpalo::Bump;
 use core::fmt::Debug;
@@ -28,6 +28,17 @@ pub trait ProofExpr<C: Commitment>: Debug + Send + Sync {
         accessor: &'a dyn DataAccessor<C::Scalar>,
     ) -> Column<'a, C::Scalar>;
 
+    /// Add the witnesses that have to be committed to before any challenge is drawn, e.g. the
+    /// words of a range check, to the [`FirstRoundBuilder`]. Composite expressions forward the
+    /// call to their subexpressions in the order in which they are verified.
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        table_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    );
+
     /// Evaluate the expression, add components needed to prove it, and return thet resulting column
     /// of values
     fn prover_evaluate<'a>(
@@ -1,13 +1,222 @@
-use crate::base::{scalar::Scalar, slice_ops};
-use alloc::vec::Vec;
+//! A range check proves that every value of a column fits into a given number of bytes.
+//!
+//! Each value `v` is decomposed into words `w_0, ..., w_{k-1}` with `v = sum 256^j * w_j`, and a
+//! log-derivative argument shows that every word is one of the 256 possible bytes. With a
+//! challenge `alpha` and the multiplicities `m_t` of each byte `t` among all words, this is
+//! ```text
+//!     sum_i sum_j 1 / (w_j[i] + alpha) = sum_{t < 256} m_t / (t + alpha)
+//! ```
+//! The words and the multiplicities are committed to in the first round, since they have to be
+//! fixed before `alpha` is drawn.
+use crate::{
+    base::{commitment::Commitment, proof::ProofError, scalar::Scalar, slice_ops},
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialTerm,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::prover_evaluate_row_index,
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
 use bytemuck::cast_slice;
+use core::iter::repeat_with;
+
+/// The number of distinct words, i.e. the length of the table that the words are looked up in
+const WORD_TABLE_LENGTH: usize = 256;
+
+/// Count the number of components needed to prove that a column fits into `num_words` bytes
+pub fn count_range_check(builder: &mut CountBuilder, num_words: usize) {
+    builder.count_first_round_mles(num_words + 1);
+    builder.count_one_evaluation_lengths(1);
+    builder.count_post_result_challenges(1);
+    builder.count_intermediate_mles(num_words + 1);
+    builder.count_subpolynomials(num_words + 3);
+    builder.count_degree(3);
+}
+
+/// Commit to the words of the values and the multiplicities of the bytes among them.
+///
+/// The word table has to be part of the sumcheck domain, so its length is sent along with them.
+pub fn first_round_evaluate_range_check<'a, S: Scalar>(
+    builder: &mut FirstRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    values: &[S],
+    num_words: usize,
+) {
+    let (words, byte_counts) = compute_words(alloc, values, num_words);
+    for word in words {
+        builder.produce_intermediate_mle(word);
+    }
+    builder.produce_intermediate_mle(byte_counts);
+    builder.produce_one_evaluation_length(WORD_TABLE_LENGTH);
+    builder.request_post_result_challenges(1);
+}
+
+/// Prove that `0 <= values[i] < 256^num_words` for every row.
+///
+/// Note: [`first_round_evaluate_range_check`] has to be called with the same arguments first.
+pub fn final_round_evaluate_range_check<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    values: &'a [S],
+    num_words: usize,
+) {
+    let n = values.len();
+    let (words, byte_counts) = compute_words(alloc, values, num_words);
+    let alpha = builder.consume_post_result_challenge();
+
+    // 1. inverted_word_j = 1 / (word_j + alpha)
+    let mut inverted_words: Vec<&'a mut [S]> = repeat_with(|| alloc.alloc_slice_fill_default(n))
+        .take(num_words)
+        .collect();
+    get_logarithmic_derivative(&words, alpha, &mut inverted_words);
+    let inverted_words: Vec<&'a [S]> = inverted_words.into_iter().map(|column| &*column).collect();
+    let chi: &[_] = alloc.alloc_slice_fill_copy(n, true);
+    for (&word, &inverted_word) in words.iter().zip(&inverted_words) {
+        builder.produce_intermediate_mle(inverted_word);
+        // inverted_word * (word + alpha) - 1 = 0
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(inverted_word), Box::new(word)]),
+                (alpha, vec![Box::new(inverted_word)]),
+                (-S::one(), vec![Box::new(chi)]),
+            ],
+        );
+    }
+
+    // 2. inverted_table = 1 / (t + alpha)
+    let rho = prover_evaluate_row_index(builder, alloc);
+    let inverted_table: &mut [S] =
+        alloc.alloc_slice_fill_with(WORD_TABLE_LENGTH, |t| rho[t] + alpha);
+    slice_ops::batch_inversion(inverted_table);
+    let inverted_table: &[S] = inverted_table;
+    let table_chi: &[_] = alloc.alloc_slice_fill_copy(WORD_TABLE_LENGTH, true);
+    builder.produce_intermediate_mle(inverted_table);
+    // inverted_table * (rho + alpha) - 1 = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(inverted_table), Box::new(rho)]),
+            (alpha, vec![Box::new(inverted_table)]),
+            (-S::one(), vec![Box::new(table_chi)]),
+        ],
+    );
+
+    // 3. sum inverted_word_j - byte_counts * inverted_table = 0
+    let mut terms: Vec<SumcheckSubpolynomialTerm<S>> = inverted_words
+        .iter()
+        .map(|&inverted_word| (S::one(), vec![Box::new(inverted_word) as Box<_>]))
+        .collect();
+    terms.push((
+        -S::one(),
+        vec![Box::new(byte_counts), Box::new(inverted_table)],
+    ));
+    builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::ZeroSum, terms);
+
+    // 4. values - sum 256^j * word_j = 0
+    let mut terms: Vec<SumcheckSubpolynomialTerm<S>> = vec![(S::one(), vec![Box::new(values)])];
+    for (j, &word) in words.iter().enumerate() {
+        terms.push((-word_weight::<S>(j), vec![Box::new(word)]));
+    }
+    builder.produce_sumcheck_subpolynomial(SumcheckSubpolynomialType::Identity, terms);
+}
+
+/// Verify that `0 <= values[i] < 256^num_words` for every row, where `values_eval` is the
+/// evaluation of the values.
+///
+/// See [`final_round_evaluate_range_check`].
+pub fn verifier_evaluate_range_check<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    values_eval: C::Scalar,
+    num_words: usize,
+) -> Result<(), ProofError> {
+    let word_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+        .take(num_words)
+        .collect();
+    let byte_counts_eval = builder.consume_first_round_mle();
+    if builder.consume_one_evaluation_length() != WORD_TABLE_LENGTH {
+        return Err(ProofError::VerificationError {
+            error: "word table of range check has an invalid length",
+        });
+    }
+    let alpha = builder.consume_post_result_challenge();
+    let one_eval = builder.mle_evaluations.input_one_evaluation;
+
+    // 1. inverted_word_j = 1 / (word_j + alpha)
+    let inverted_word_evals: Vec<_> = repeat_with(|| builder.consume_intermediate_mle())
+        .take(num_words)
+        .collect();
+    for (word_eval, inverted_word_eval) in word_evals.iter().zip(&inverted_word_evals) {
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            *inverted_word_eval * (*word_eval + alpha) - one_eval,
+        );
+    }
+
+    // 2. inverted_table = 1 / (t + alpha)
+    let inverted_table_eval = builder.consume_intermediate_mle();
+    let rho_eval = builder.mle_evaluations.rho_evaluation();
+    let table_one_eval = builder.mle_evaluations.chi_evaluation(WORD_TABLE_LENGTH);
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::Identity,
+        inverted_table_eval * (rho_eval + alpha) - table_one_eval,
+    );
+
+    // 3. sum inverted_word_j - byte_counts * inverted_table = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::ZeroSum,
+        inverted_word_evals.iter().copied().sum::<C::Scalar>()
+            - byte_counts_eval * inverted_table_eval,
+    );
+
+    // 4. values - sum 256^j * word_j = 0
+    let words_eval = word_evals
+        .iter()
+        .enumerate()
+        .map(|(j, word_eval)| word_weight::<C::Scalar>(j) * *word_eval)
+        .sum::<C::Scalar>();
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::Identity,
+        values_eval - words_eval,
+    );
+    Ok(())
+}
+
+/// `256^j` as a scalar
+pub fn word_weight<S: Scalar>(j: usize) -> S {
+    let mut limbs = [0u64; 4];
+    limbs[j / 8] = 1 << (8 * (j % 8));
+    S::from(limbs)
+}
+
+/// Decompose the values into `num_words` columns of words and count the bytes among them
+fn compute_words<'a, S: Scalar>(
+    alloc: &'a Bump,
+    values: &[S],
+    num_words: usize,
+) -> (Vec<&'a [u8]>, &'a [i64]) {
+    let mut word_columns: Vec<&'a mut [u8]> =
+        repeat_with(|| alloc.alloc_slice_fill_copy(values.len(), 0))
+            .take(num_words)
+            .collect();
+    let byte_counts = alloc.alloc_slice_fill_copy(WORD_TABLE_LENGTH, 0);
+    decompose_scalar_to_words(values, &mut word_columns, byte_counts);
+    (
+        word_columns.into_iter().map(|column| &*column).collect(),
+        byte_counts,
+    )
+}
 
 // Decomposes a scalar to requisite words, additionally tracks the total
 // number of occurences of each word for later use in the argument.
+// Only the lowest `word_columns.len()` bytes of each scalar are kept.
 fn decompose_scalar_to_words<'a, S: Scalar + 'a>(
-    scalars: &mut [S],
+    scalars: &[S],
     word_columns: &mut [&mut [u8]],
-    byte_counts: &mut [u64],
+    byte_counts: &mut [i64],
 ) {
     for (i, scalar) in scalars.iter().enumerate() {
         let scalar_array: [u64; 4] = (*scalar).into(); // Convert scalar to u64 array
@@ -15,9 +224,9 @@ fn decompose_scalar_to_words<'a, S: Scalar + 'a>(
         let scalar_bytes = &scalar_bytes_full[..31];
 
         // Populate the columns of the words table with decomposition of scalar:
-        for (byte_index, &byte) in scalar_bytes.iter().enumerate() {
+        for (word_column, &byte) in word_columns.iter_mut().zip(scalar_bytes) {
             // Each column in word_columns is for a specific byte position across all scalars
-            word_columns[byte_index][i] = byte;
+            word_column[i] = byte;
             byte_counts[byte as usize] += 1;
         }
     }
@@ -27,7 +236,7 @@ fn decompose_scalar_to_words<'a, S: Scalar + 'a>(
 // 1 / (word + alpha), which is the modular multiplicative
 // inverse of (word + alpha) in the scalar field.
 fn get_logarithmic_derivative<'a, S: Scalar + 'a>(
-    byte_columns: &[&mut [u8]],
+    byte_columns: &[&[u8]],
     alpha: S,
     inverted_word_columns: &mut [&mut [S]],
 ) {
@@ -48,19 +257,22 @@ fn get_logarithmic_derivative<'a, S: Scalar + 'a>(
 mod tests {
     use crate::{
         base::scalar::{Curve25519Scalar as S, Scalar},
-        sql::proof_exprs::range_check::{decompose_scalar_to_words, get_logarithmic_derivative},
+        sql::proof_exprs::range_check::{
+            compute_words, decompose_scalar_to_words, get_logarithmic_derivative, word_weight,
+        },
     };
+    use bumpalo::Bump;
     use num_traits::Inv;
 
     #[test]
     fn we_can_decompose_small_scalars_to_words() {
-        let mut scalars: Vec<S> = [1, 2, 3, 255, 256, 257].iter().map(S::from).collect();
+        let scalars: Vec<S> = [1, 2, 3, 255, 256, 257].iter().map(S::from).collect();
 
         let mut word_columns = vec![vec![0; scalars.len()]; 31];
         let mut word_slices: Vec<&mut [u8]> = word_columns.iter_mut().map(|c| &mut c[..]).collect();
         let mut byte_counts = vec![0; 256];
 
-        decompose_scalar_to_words(&mut scalars, &mut word_slices, &mut byte_counts);
+        decompose_scalar_to_words(&scalars, &mut word_slices, &mut byte_counts);
 
         let mut expected_word_columns = vec![vec![0; scalars.len()]; 31];
         expected_word_columns[0] = vec![1, 2, 3, 255, 0, 1];
@@ -80,7 +292,7 @@ mod tests {
 
     #[test]
     fn we_can_decompose_large_scalars_to_words() {
-        let mut scalars: Vec<S> = [S::MAX_SIGNED, S::from(u64::MAX), S::from(-1)]
+        let scalars: Vec<S> = [S::MAX_SIGNED, S::from(u64::MAX), S::from(-1)]
             .iter()
             .map(S::from)
             .collect();
@@ -89,7 +301,7 @@ mod tests {
         let mut word_slices: Vec<&mut [u8]> = word_columns.iter_mut().map(|c| &mut c[..]).collect();
         let mut byte_counts = vec![0; 256];
 
-        decompose_scalar_to_words(&mut scalars, &mut word_slices, &mut byte_counts);
+        decompose_scalar_to_words(&scalars, &mut word_slices, &mut byte_counts);
 
         let expected_word_columns = [
             [246, 255, 236],
@@ -150,6 +362,34 @@ mod tests {
         assert_eq!(byte_counts, expected_byte_counts_hardcoded);
     }
 
+    #[test]
+    fn we_can_decompose_scalars_to_fewer_words_than_their_bytes() {
+        let alloc = Bump::new();
+        let scalars: Vec<S> = [1, 258, 65535, 65536].iter().map(S::from).collect();
+
+        let (words, byte_counts) = compute_words(&alloc, &scalars, 2);
+
+        assert_eq!(words, [[1, 2, 255, 0].as_slice(), &[0, 1, 255, 0]]);
+        let mut expected_byte_counts = vec![0; 256];
+        expected_byte_counts[0] = 3;
+        expected_byte_counts[1] = 2;
+        expected_byte_counts[2] = 1;
+        expected_byte_counts[255] = 2;
+        assert_eq!(byte_counts, expected_byte_counts);
+    }
+
+    #[test]
+    fn we_can_compute_word_weights() {
+        assert_eq!(word_weight::<S>(0), S::ONE);
+        assert_eq!(word_weight::<S>(1), S::from(256));
+        assert_eq!(word_weight::<S>(7), S::from(1_u64 << 56));
+        assert_eq!(word_weight::<S>(8), S::from(1_u128 << 64));
+        assert_eq!(
+            word_weight::<S>(17),
+            S::from(1_u128 << 64) * S::from(1_u128 << 72)
+        );
+    }
+
     #[test]
     fn we_can_obtain_logarithmic_derivative_from_small_scalar() {
         let scalars: Vec<S> = [1, 2, 3, 255, 256, 257].iter().map(S::from).collect();
@@ -159,7 +399,7 @@ mod tests {
         word_columns[0] = [1, 2, 3, 255, 0, 1].to_vec();
         word_columns[1] = [0, 0, 0, 0, 1, 1].to_vec();
 
-        let word_slices: Vec<&mut [u8]> = word_columns.iter_mut().map(|c| &mut c[..]).collect();
+        let word_slices: Vec<&[u8]> = word_columns.iter().map(|c| &c[..]).collect();
 
         let alpha = S::from(5);
 
@@ -253,7 +493,7 @@ mod tests {
         // Simulate a verifier challenge, then prepare storage for
         // 1 / (word + alpha)
         let alpha = S::from(5);
-        let word_slices: Vec<&mut [u8]> = word_columns.iter_mut().map(|c| &mut c[..]).collect();
+        let word_slices: Vec<&[u8]> = word_columns.iter().map(|c| &c[..]).collect();
         let mut inverted_word_columns_plus_alpha: Vec<Vec<S>> =
             vec![vec![S::ZERO; scalars.len()]; 31];
         // Convert Vec<Vec<S>> into Vec<&mut [S]> for use in get_logarithmic_derivative
@@ -1,6 +1,7 @@
 use super::{
-    is_within_acceptable_range, verify_constant_abs_decomposition,
-    verify_constant_sign_decomposition,
+    count_range_check, final_round_evaluate_range_check, first_round_evaluate_range_check,
+    is_within_acceptable_range, verifier_evaluate_range_check, verify_constant_abs_decomposition,
+    verify_constant_sign_decomposition, word_weight,
 };
 use crate::{
     base::{
@@ -10,8 +11,8 @@ use crate::{
         scalar::Scalar,
     },
     sql::proof::{
-        CountBuilder, FinalRoundBuilder, SumcheckSubpolynomialTerm, SumcheckSubpolynomialType,
-        VerificationBuilder,
+        CountBuilder, FinalRoundBuilder, FirstRoundBuilder, SumcheckSubpolynomialTerm,
+        SumcheckSubpolynomialType, VerificationBuilder,
     },
 };
 use alloc::{boxed::Box, vec, vec::Vec};
@@ -19,14 +20,41 @@ use bumpalo::Bump;
 
 /// Count the number of components needed to prove a sign decomposition
 pub fn count_sign(builder: &mut CountBuilder) -> Result<(), ProofError> {
+    let dist = consume_acceptable_bit_distribution(builder)?;
+    count_sign_bits(builder, &dist);
+    Ok(())
+}
+
+/// Count the number of components needed to prove a sign decomposition with
+/// [`prover_evaluate_sign_with_range_check`]
+pub fn count_sign_with_range_check(builder: &mut CountBuilder) -> Result<(), ProofError> {
+    let dist = consume_acceptable_bit_distribution(builder)?;
+    match range_check_num_words(&dist) {
+        Some(num_words) => {
+            builder.count_intermediate_mles(1);
+            builder.count_subpolynomials(1);
+            count_range_check(builder, num_words);
+        }
+        None => count_sign_bits(builder, &dist),
+    }
+    Ok(())
+}
+
+fn consume_acceptable_bit_distribution(
+    builder: &mut CountBuilder,
+) -> Result<BitDistribution, ProofError> {
     let dist = builder.consume_bit_distribution()?;
     if !is_within_acceptable_range(&dist) {
         return Err(ProofError::VerificationError {
             error: "bit distribution outside of acceptable range",
         });
     }
+    Ok(dist)
+}
+
+fn count_sign_bits(builder: &mut CountBuilder, dist: &BitDistribution) {
     if dist.num_varying_bits() == 0 {
-        return Ok(());
+        return;
     }
     builder.count_intermediate_mles(dist.num_varying_bits());
     builder.count_subpolynomials(dist.num_varying_bits());
@@ -34,7 +62,19 @@ pub fn count_sign(builder: &mut CountBuilder) -> Result<(), ProofError> {
     if dist.has_varying_sign_bit() && dist.num_varying_bits() > 1 {
         builder.count_subpolynomials(1);
     }
-    Ok(())
+}
+
+/// The number of bytes that the values of a column are range checked to, if proving their sign
+/// with a range check is cheaper than committing to each of their varying bits.
+///
+/// A range check of `k` bytes commits to `2 * k + 2` columns, plus one for the sign bits.
+fn range_check_num_words(dist: &BitDistribution) -> Option<usize> {
+    // `is_valid` guarantees that some absolute bit is set if more than one bit varies.
+    if dist.num_varying_bits() <= 5 || !dist.is_valid() {
+        return None;
+    }
+    let num_words = dist.most_significant_abs_bit() / 8 + 1;
+    (dist.num_varying_bits() > 2 * num_words + 3).then_some(num_words)
 }
 
 /// Compute the sign bit for a column of scalars.
@@ -85,8 +125,64 @@ pub fn prover_evaluate_sign<'a, S: Scalar>(
     expr: &'a [S],
     #[cfg(test)] treat_column_of_zeros_as_negative: bool,
 ) -> &'a [bool] {
-    let table_length = expr.len();
-    // bit_distribution
+    let dist = compute_bit_distribution(
+        expr,
+        #[cfg(test)]
+        treat_column_of_zeros_as_negative,
+    );
+    builder.produce_bit_distribution(dist.clone());
+    prove_sign_bits(builder, alloc, expr, &dist)
+}
+
+/// Commit to the witnesses needed by [`prover_evaluate_sign_with_range_check`].
+pub fn first_round_evaluate_sign<'a, S: Scalar>(
+    builder: &mut FirstRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    expr: &[S],
+) {
+    let dist = BitDistribution::new::<S, _>(expr);
+    if let Some(num_words) = range_check_num_words(&dist) {
+        let (_, shifted_expr) = shift_negative_values(alloc, expr, num_words);
+        first_round_evaluate_range_check(builder, alloc, shifted_expr, num_words);
+    }
+}
+
+/// Prove the sign decomposition for a column of scalars like [`prover_evaluate_sign`], but
+/// with a range check if the values vary across many bits.
+///
+/// If the absolute values are below `256^k`, the sign bits `s1, ..., sn` are correct if and only
+/// if `xi + si * 256^k` is in `[0, 256^k)` for every `i`, which is shown with a range check of
+/// `k` bytes. This is cheaper than committing to every varying bit once more than `2 * k + 3`
+/// bits vary.
+///
+/// Note: [`first_round_evaluate_sign`] has to be called with the same column first.
+pub fn prover_evaluate_sign_with_range_check<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    expr: &'a [S],
+    #[cfg(test)] treat_column_of_zeros_as_negative: bool,
+) -> &'a [bool] {
+    let dist = compute_bit_distribution(
+        expr,
+        #[cfg(test)]
+        treat_column_of_zeros_as_negative,
+    );
+    builder.produce_bit_distribution(dist.clone());
+    match range_check_num_words(&dist) {
+        Some(num_words) => {
+            let (sign, shifted_expr) = shift_negative_values(alloc, expr, num_words);
+            prove_bits_are_binary(builder, &[sign]);
+            final_round_evaluate_range_check(builder, alloc, shifted_expr, num_words);
+            sign
+        }
+        None => prove_sign_bits(builder, alloc, expr, &dist),
+    }
+}
+
+fn compute_bit_distribution<S: Scalar>(
+    expr: &[S],
+    #[cfg(test)] treat_column_of_zeros_as_negative: bool,
+) -> BitDistribution {
     let dist = BitDistribution::new::<S, _>(expr);
     #[cfg(test)]
     let dist = {
@@ -96,7 +192,40 @@ pub fn prover_evaluate_sign<'a, S: Scalar>(
         }
         dist
     };
-    builder.produce_bit_distribution(dist.clone());
+    dist
+}
+
+/// Compute the sign bits `si` of the values and the shifted values `xi + si * 256^num_words`
+fn shift_negative_values<'a, S: Scalar>(
+    alloc: &'a Bump,
+    expr: &[S],
+    num_words: usize,
+) -> (&'a [bool], &'a [S]) {
+    let offset = word_weight::<S>(num_words);
+    let sign = alloc.alloc_slice_fill_with(expr.len(), |i| S::MAX_SIGNED < expr[i]);
+    let shifted_expr =
+        alloc.alloc_slice_fill_with(
+            expr.len(),
+            |i| {
+                if sign[i] {
+                    expr[i] + offset
+                } else {
+                    expr[i]
+                }
+            },
+        );
+    (sign, shifted_expr)
+}
+
+/// # Panics
+/// Panics if `bits.last()` is `None`.
+fn prove_sign_bits<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    expr: &'a [S],
+    dist: &BitDistribution,
+) -> &'a [bool] {
+    let table_length = expr.len();
 
     // handle the constant case
     if dist.num_varying_bits() == 0 {
@@ -104,14 +233,14 @@ pub fn prover_evaluate_sign<'a, S: Scalar>(
     }
 
     // prove that the bits are binary
-    let bits = compute_varying_bit_matrix(alloc, expr, &dist);
+    let bits = compute_varying_bit_matrix(alloc, expr, dist);
     prove_bits_are_binary(builder, &bits);
     if !dist.has_varying_sign_bit() {
         return alloc.alloc_slice_fill_copy(table_length, dist.sign_bit());
     }
 
     if dist.num_varying_bits() > 1 {
-        prove_bit_decomposition(builder, alloc, expr, &bits, &dist);
+        prove_bit_decomposition(builder, alloc, expr, &bits, dist);
     }
 
     // This might panic if `bits.last()` returns `None`.
@@ -131,6 +260,38 @@ pub fn verifier_evaluate_sign<C: Commitment>(
 ) -> Result<C::Scalar, ProofError> {
     // bit_distribution
     let dist = builder.consume_bit_distribution();
+    verify_sign_bits(builder, &dist, eval, one_eval)
+}
+
+/// Verify the sign decomposition for a column of scalars.
+///
+/// See [`prover_evaluate_sign_with_range_check`].
+pub fn verifier_evaluate_sign_with_range_check<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    eval: C::Scalar,
+    one_eval: C::Scalar,
+) -> Result<C::Scalar, ProofError> {
+    let dist = builder.consume_bit_distribution();
+    match range_check_num_words(&dist) {
+        Some(num_words) => {
+            let sign_eval = builder.consume_intermediate_mle();
+            verify_bits_are_binary(builder, &[sign_eval]);
+            let shifted_eval = eval + word_weight::<C::Scalar>(num_words) * sign_eval;
+            verifier_evaluate_range_check(builder, shifted_eval, num_words)?;
+            Ok(sign_eval)
+        }
+        None => verify_sign_bits(builder, &dist, eval, one_eval),
+    }
+}
+
+/// # Panics
+/// Panics if `bit_evals.last()` is `None`.
+fn verify_sign_bits<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    dist: &BitDistribution,
+    eval: C::Scalar,
+    one_eval: C::Scalar,
+) -> Result<C::Scalar, ProofError> {
     let num_varying_bits = dist.num_varying_bits();
 
     // extract evaluations and commitmens of the multilinear extensions for the varying
@@ -146,14 +307,14 @@ pub fn verifier_evaluate_sign<C: Commitment>(
 
     // handle the special case of the sign bit being constant
     if !dist.has_varying_sign_bit() {
-        return verifier_const_sign_evaluate(&dist, eval, one_eval, &bit_evals);
+        return verifier_const_sign_evaluate(dist, eval, one_eval, &bit_evals);
     }
 
     // handle the special case of the absolute part being constant
     if dist.num_varying_bits() == 1 {
-        verify_constant_abs_decomposition(&dist, eval, one_eval, bit_evals[0])?;
+        verify_constant_abs_decomposition(dist, eval, one_eval, bit_evals[0])?;
     } else {
-        verify_bit_decomposition(builder, eval, &bit_evals, &dist);
+        verify_bit_decomposition(builder, eval, one_eval, &bit_evals, dist);
     }
 
     Ok(*bit_evals.last().unwrap())
@@ -245,12 +406,13 @@ fn prove_bit_decomposition<'a, S: Scalar>(
 fn verify_bit_decomposition<C: Commitment>(
     builder: &mut VerificationBuilder<'_, C>,
     expr_eval: C::Scalar,
+    one_eval: C::Scalar,
     bit_evals: &[C::Scalar],
     dist: &BitDistribution,
 ) {
     let mut eval = expr_eval;
     let sign_eval = bit_evals.last().unwrap();
-    let sign_eval = builder.mle_evaluations.input_one_evaluation - C::Scalar::TWO * *sign_eval;
+    let sign_eval = one_eval - C::Scalar::TWO * *sign_eval;
     let mut vary_index = 0;
     eval -= sign_eval * C::Scalar::from(dist.constant_part());
     dist.for_each_abs_varying_bit(|int_index: usize, bit_index: usize| {
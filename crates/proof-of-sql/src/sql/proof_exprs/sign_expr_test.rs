@@ -16,7 +16,7 @@ fn prover_evaluation_generates_the_bit_distribution_of_a_constant_column() {
     let dist = BitDistribution::new::<Curve25519Scalar, _>(&data);
     let alloc = Bump::new();
     let data: Vec<Curve25519Scalar> = data.into_iter().map(Curve25519Scalar::from).collect();
-    let mut builder = FinalRoundBuilder::new(3, 2, Vec::new());
+    let mut builder = FinalRoundBuilder::new(3, 3, 2, Vec::new());
     let sign = prover_evaluate_sign(&mut builder, &alloc, &data, false);
     assert_eq!(sign, [false; 3]);
     assert_eq!(builder.bit_distributions(), [dist]);
@@ -28,7 +28,7 @@ fn prover_evaluation_generates_the_bit_distribution_of_a_negative_constant_colum
     let dist = BitDistribution::new::<Curve25519Scalar, _>(&data);
     let alloc = Bump::new();
     let data: Vec<Curve25519Scalar> = data.into_iter().map(Curve25519Scalar::from).collect();
-    let mut builder = FinalRoundBuilder::new(3, 2, Vec::new());
+    let mut builder = FinalRoundBuilder::new(3, 3, 2, Vec::new());
     let sign = prover_evaluate_sign(&mut builder, &alloc, &data, false);
     assert_eq!(sign, [true; 3]);
     assert_eq!(builder.bit_distributions(), [dist]);
@@ -66,8 +66,18 @@ fn we_can_verify_a_constant_decomposition() {
     );
     let one_eval = sumcheck_evaluations.input_one_evaluation;
 
-    let mut builder: VerificationBuilder<RistrettoPoint> =
-        VerificationBuilder::new(0, sumcheck_evaluations, &dists, &[], &[], &[], Vec::new());
+    let mut builder: VerificationBuilder<RistrettoPoint> = VerificationBuilder::new(
+        0,
+        sumcheck_evaluations,
+        &dists,
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        Vec::new(),
+    );
     let data_eval = (&data).evaluate_at_point(&evaluation_point);
     let eval = verifier_evaluate_sign(&mut builder, data_eval, one_eval).unwrap();
     assert_eq!(eval, Curve25519Scalar::zero());
@@ -90,8 +100,18 @@ fn verification_of_constant_data_fails_if_the_commitment_doesnt_match_the_bit_di
     );
     let one_eval = sumcheck_evaluations.input_one_evaluation;
 
-    let mut builder: VerificationBuilder<RistrettoPoint> =
-        VerificationBuilder::new(0, sumcheck_evaluations, &dists, &[], &[], &[], Vec::new());
+    let mut builder: VerificationBuilder<RistrettoPoint> = VerificationBuilder::new(
+        0,
+        sumcheck_evaluations,
+        &dists,
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        &[],
+        Vec::new(),
+    );
     let data_eval = Curve25519Scalar::from(2) * (&data).evaluate_at_point(&evaluation_point);
     assert!(verifier_evaluate_sign(&mut builder, data_eval, one_eval).is_err());
 }
@@ -4,7 +4,7 @@ use crate::base::{
     database::{ColumnRef, LiteralValue, SchemaAccessor, TableRef},
     math::decimal::Precision,
 };
-use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+use proof_of_sql_parser::intermediate_ast::{AggregationOperator, DataType};
 
 /// # Panics
 /// Panics if:
@@ -50,6 +50,45 @@ pub fn gte<C: Commitment>(left: DynProofExpr<C>, right: DynProofExpr<C>) -> DynP
     DynProofExpr::try_new_inequality(left, right, false).unwrap()
 }
 
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_like()` returns an error.
+pub fn like<C: Commitment>(expr: DynProofExpr<C>, pattern: &str) -> DynProofExpr<C> {
+    DynProofExpr::try_new_like(expr, pattern.into()).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_between()` returns an error.
+pub fn between<C: Commitment>(expr: DynProofExpr<C>, low: i64, high: i64) -> DynProofExpr<C> {
+    DynProofExpr::try_new_between(expr, low, high).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_in_list()` returns an error.
+pub fn in_list<C: Commitment>(expr: DynProofExpr<C>, list: &[i64]) -> DynProofExpr<C> {
+    DynProofExpr::try_new_in_list(expr, list.to_vec()).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_case()` returns an error.
+pub fn case_when<C: Commitment>(
+    condition: DynProofExpr<C>,
+    then_expr: DynProofExpr<C>,
+    else_expr: DynProofExpr<C>,
+) -> DynProofExpr<C> {
+    DynProofExpr::try_new_case(condition, then_expr, else_expr).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_cast()` returns an error.
+pub fn cast<C: Commitment>(expr: DynProofExpr<C>, data_type: DataType) -> DynProofExpr<C> {
+    DynProofExpr::try_new_cast(expr, data_type).unwrap()
+}
+
 /// # Panics
 /// Panics if:
 /// - `DynProofExpr::try_new_not()` returns an error.
@@ -92,6 +131,20 @@ pub fn multiply<C: Commitment>(left: DynProofExpr<C>, right: DynProofExpr<C>) ->
     DynProofExpr::try_new_multiply(left, right).unwrap()
 }
 
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_divide()` returns an error.
+pub fn divide<C: Commitment>(left: DynProofExpr<C>, right: DynProofExpr<C>) -> DynProofExpr<C> {
+    DynProofExpr::try_new_divide(left, right).unwrap()
+}
+
+/// # Panics
+/// Panics if:
+/// - `DynProofExpr::try_new_modulo()` returns an error.
+pub fn modulo<C: Commitment>(left: DynProofExpr<C>, right: DynProofExpr<C>) -> DynProofExpr<C> {
+    DynProofExpr::try_new_modulo(left, right).unwrap()
+}
+
 pub fn const_bool<C: Commitment>(val: bool) -> DynProofExpr<C> {
     DynProofExpr::new_literal(LiteralValue::Boolean(val))
 }
@@ -0,0 +1,138 @@
+use crate::{
+    base::{commitment::Commitment, database::Column, scalar::Scalar, slice_ops},
+    sql::{
+        proof::{CountBuilder, FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_plans::{fold_columns, fold_vals},
+    },
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+
+/// Count the number of components needed to prove a membership check
+pub fn count_membership_check(builder: &mut CountBuilder) {
+    builder.count_intermediate_mles(2);
+    builder.count_subpolynomials(3);
+    builder.count_degree(3);
+}
+
+/// Prove that every row of the candidate columns is a row of the table columns and that the
+/// `i`th row of the table appears exactly `multiplicities[i]` times among the candidates.
+///
+/// Rows are compared as tuples, i.e. the columns are folded together with `beta`. With
+/// `c_fold = alpha + fold(beta, candidates)` and `t_fold = alpha + fold(beta, table)`, this is
+/// the log-derivative identity
+/// ```text
+///     sum_{i < candidate_length} 1 / c_fold[i] = sum_{j < table_length} multiplicities[j] / t_fold[j]
+/// ```
+/// which only holds if the table rows are distinct, so callers need to make sure that this
+/// is the case, e.g. by including a column of row numbers.
+///
+/// Note: every committed column in `candidates`, `table` and `multiplicities` has to be
+/// committed to before `alpha` and `beta` are drawn.
+#[allow(clippy::too_many_arguments)]
+pub fn prover_evaluate_membership_check<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    candidate_length: usize,
+    candidates: &[Column<'a, S>],
+    table_length: usize,
+    table: &[Column<'a, S>],
+    multiplicities: Column<'a, S>,
+) {
+    let n = 1 << builder.num_sumcheck_variables();
+    let candidate_chi = alloc.alloc_slice_fill_copy(candidate_length, true);
+    let table_chi = alloc.alloc_slice_fill_copy(table_length, true);
+
+    let candidate_fold = alloc.alloc_slice_fill_copy(n, alpha);
+    fold_columns(candidate_fold, S::one(), beta, candidates);
+    let table_fold = alloc.alloc_slice_fill_copy(n, alpha);
+    fold_columns(table_fold, S::one(), beta, table);
+
+    let candidate_star = alloc.alloc_slice_copy(&candidate_fold[..candidate_length]);
+    slice_ops::batch_inversion(candidate_star);
+    let table_star = alloc.alloc_slice_copy(&table_fold[..table_length]);
+    slice_ops::batch_inversion(table_star);
+
+    builder.produce_intermediate_mle(candidate_star as &[_]);
+    builder.produce_intermediate_mle(table_star as &[_]);
+
+    // sum candidate_star - table_star * multiplicities = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::ZeroSum,
+        vec![
+            (S::one(), vec![Box::new(candidate_star as &[_])]),
+            (
+                -S::one(),
+                vec![Box::new(table_star as &[_]), Box::new(multiplicities)],
+            ),
+        ],
+    );
+
+    // candidate_fold * candidate_star - candidate_chi = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![
+                    Box::new(candidate_star as &[_]),
+                    Box::new(candidate_fold as &[_]),
+                ],
+            ),
+            (-S::one(), vec![Box::new(candidate_chi as &[_])]),
+        ],
+    );
+
+    // table_fold * table_star - table_chi = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (
+                S::one(),
+                vec![Box::new(table_star as &[_]), Box::new(table_fold as &[_])],
+            ),
+            (-S::one(), vec![Box::new(table_chi as &[_])]),
+        ],
+    );
+}
+
+/// Verify a membership check.
+///
+/// See [`prover_evaluate_membership_check`].
+#[allow(clippy::too_many_arguments)]
+pub fn verifier_evaluate_membership_check<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    alpha: C::Scalar,
+    beta: C::Scalar,
+    candidate_one_eval: C::Scalar,
+    candidate_evals: &[C::Scalar],
+    table_one_eval: C::Scalar,
+    table_evals: &[C::Scalar],
+    multiplicity_eval: C::Scalar,
+) {
+    // The folds are not truncated, so the evaluation of the constant `alpha` is `alpha` itself.
+    let candidate_fold_eval = alpha + fold_vals(beta, candidate_evals);
+    let table_fold_eval = alpha + fold_vals(beta, table_evals);
+    let candidate_star_eval = builder.consume_intermediate_mle();
+    let table_star_eval = builder.consume_intermediate_mle();
+
+    // sum candidate_star - table_star * multiplicities = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::ZeroSum,
+        candidate_star_eval - table_star_eval * multiplicity_eval,
+    );
+
+    // candidate_fold * candidate_star - candidate_chi = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::Identity,
+        candidate_fold_eval * candidate_star_eval - candidate_one_eval,
+    );
+
+    // table_fold * table_star - table_chi = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::Identity,
+        table_fold_eval * table_star_eval - table_one_eval,
+    );
+}
@@ -0,0 +1,24 @@
+//! This module contains subprotocols that are shared between several proof plans.
+mod membership_check;
+pub(crate) use membership_check::{
+    count_membership_check, prover_evaluate_membership_check, verifier_evaluate_membership_check,
+};
+
+mod monotonic;
+pub(crate) use monotonic::{
+    count_strictly_increasing, prover_evaluate_strictly_increasing,
+    verifier_evaluate_strictly_increasing,
+};
+#[cfg(all(test, feature = "blitzar"))]
+mod monotonic_test;
+
+mod row_index;
+pub(crate) use row_index::prover_evaluate_row_index;
+
+mod sorted;
+pub(crate) use sorted::{count_sorted, prover_evaluate_sorted, verifier_evaluate_sorted};
+#[cfg(all(test, feature = "blitzar"))]
+mod sorted_test;
+
+mod shift;
+pub(crate) use shift::shift;
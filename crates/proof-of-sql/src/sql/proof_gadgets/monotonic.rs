@@ -0,0 +1,155 @@
+use super::{
+    count_membership_check, prover_evaluate_membership_check, prover_evaluate_row_index,
+    verifier_evaluate_membership_check,
+};
+use crate::{
+    base::{commitment::Commitment, database::Column, proof::ProofError, scalar::Scalar},
+    sql::{
+        proof::{CountBuilder, FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_exprs::{count_sign, prover_evaluate_sign, verifier_evaluate_sign},
+    },
+};
+use alloc::{boxed::Box, vec};
+use bumpalo::Bump;
+use core::cmp::min;
+use num_traits::{One, Zero};
+
+/// Count the number of components needed to prove that a column is strictly increasing
+pub fn count_strictly_increasing(builder: &mut CountBuilder) -> Result<(), ProofError> {
+    count_membership_check(builder);
+    builder.count_intermediate_mles(1);
+    builder.count_subpolynomials(1);
+    builder.count_degree(3);
+    count_sign(builder)
+}
+
+/// Prove that `column` is strictly increasing.
+///
+/// `shifted_column` must be `column` shifted down by one row, i.e. `shifted_column[i] = column[i - 1]`
+/// for `0 < i < column.len()`. Its first entry is ignored.
+///
+/// The proof works in two steps:
+/// 1. `shifted_column` is shown to be a shift of `column` with a membership check of the rows
+///    `(i, column[i])` for `i < n - 1` in the rows `(i - 1, shifted_column[i])` for `0 < i < n`.
+/// 2. The differences `column[i] - shifted_column[i] - 1` for `0 < i < n` are shown to be
+///    non-negative with a sign decomposition.
+///
+/// Note: `column` and `shifted_column` have to be committed to before `alpha` and `beta` are drawn.
+///
+/// # Panics
+///
+/// Panics if `column` and `shifted_column` have different lengths.
+pub fn prover_evaluate_strictly_increasing<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    column: &'a [S],
+    shifted_column: &'a [S],
+) {
+    let n = column.len();
+    assert_eq!(n, shifted_column.len());
+    let rho = prover_evaluate_row_index(builder, alloc);
+    let rho_minus_one: &[_] = alloc.alloc_slice_fill_with(rho.len(), |i| rho[i] - S::one());
+    let shifted_chi = alloc.alloc_slice_fill_copy(n, true);
+    shifted_chi[..min(1, n)].fill(false);
+
+    // 1. shifted_column is a shift of column
+    prover_evaluate_membership_check(
+        builder,
+        alloc,
+        alpha,
+        beta,
+        n.saturating_sub(1),
+        &[Column::Scalar(rho), Column::Scalar(column)],
+        n,
+        &[
+            Column::Scalar(rho_minus_one),
+            Column::Scalar(shifted_column),
+        ],
+        Column::Boolean(shifted_chi),
+    );
+
+    // 2. column - shifted_column - 1 >= 0
+    let diff: &[_] = alloc.alloc_slice_fill_with(n, |i| {
+        if shifted_chi[i] {
+            column[i] - shifted_column[i] - S::one()
+        } else {
+            S::zero()
+        }
+    });
+    builder.produce_intermediate_mle(diff);
+
+    // diff - shifted_chi * (column - shifted_column - 1) = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(diff)]),
+            (
+                -S::one(),
+                vec![Box::new(shifted_chi as &[_]), Box::new(column)],
+            ),
+            (
+                S::one(),
+                vec![Box::new(shifted_chi as &[_]), Box::new(shifted_column)],
+            ),
+            (S::one(), vec![Box::new(shifted_chi as &[_])]),
+        ],
+    );
+
+    prover_evaluate_sign(
+        builder,
+        alloc,
+        diff,
+        #[cfg(test)]
+        false,
+    );
+}
+
+/// Verify that a column of length `length` is strictly increasing.
+///
+/// See [`prover_evaluate_strictly_increasing`].
+pub fn verifier_evaluate_strictly_increasing<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    alpha: C::Scalar,
+    beta: C::Scalar,
+    length: usize,
+    column_eval: C::Scalar,
+    shifted_column_eval: C::Scalar,
+) -> Result<(), ProofError> {
+    let rho_eval = builder.mle_evaluations.rho_evaluation();
+    let one_eval = builder.mle_evaluations.chi_evaluation(length);
+    let shifted_one_eval = one_eval - builder.mle_evaluations.chi_evaluation(min(1, length));
+    let candidate_one_eval = builder
+        .mle_evaluations
+        .chi_evaluation(length.saturating_sub(1));
+
+    // 1. shifted_column is a shift of column
+    verifier_evaluate_membership_check(
+        builder,
+        alpha,
+        beta,
+        candidate_one_eval,
+        &[rho_eval, column_eval],
+        one_eval,
+        &[rho_eval - C::Scalar::one(), shifted_column_eval],
+        shifted_one_eval,
+    );
+
+    // 2. column - shifted_column - 1 >= 0
+    let diff_eval = builder.consume_intermediate_mle();
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::Identity,
+        diff_eval - shifted_one_eval * (column_eval - shifted_column_eval - C::Scalar::one()),
+    );
+
+    // The sign bits are committed to before the evaluation point is drawn,
+    // so a zero evaluation means that all of them are zero.
+    let sign_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+    if sign_eval != C::Scalar::zero() {
+        return Err(ProofError::VerificationError {
+            error: "column is not strictly increasing",
+        });
+    }
+    Ok(())
+}
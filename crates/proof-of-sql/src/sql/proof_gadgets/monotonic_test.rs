@@ -0,0 +1,180 @@
+use super::{
+    count_strictly_increasing, prover_evaluate_strictly_increasing,
+    verifier_evaluate_strictly_increasing,
+};
+use crate::{
+    base::{
+        commitment::{Commitment, InnerProductProof},
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedTable, OwnedTableTestAccessor,
+        },
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{
+        CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+        VerifiableQueryResult, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use serde::Serialize;
+
+/// A plan that proves that `values` is strictly increasing, using `shifted_values` as the
+/// shifted column so that a dishonest shift can be tested.
+#[derive(Debug, Serialize)]
+struct StrictlyIncreasingTestPlan {
+    values: Vec<i64>,
+    shifted_values: Vec<i64>,
+}
+
+impl StrictlyIncreasingTestPlan {
+    fn new(values: Vec<i64>) -> Self {
+        let shifted_values = core::iter::once(0)
+            .chain(values.iter().copied())
+            .take(values.len())
+            .collect();
+        Self {
+            values,
+            shifted_values,
+        }
+    }
+
+    fn columns<'a, S: Scalar>(&self, alloc: &'a Bump) -> (&'a [S], &'a [S]) {
+        (
+            alloc.alloc_slice_fill_iter(self.values.iter().map(|&v| S::from(v))),
+            alloc.alloc_slice_fill_iter(self.shifted_values.iter().map(|&v| S::from(v))),
+        )
+    }
+}
+
+impl<S: Scalar> ProverEvaluate<S> for StrictlyIncreasingTestPlan {
+    fn result_evaluate<'a>(
+        &self,
+        _input_length: usize,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) -> Vec<Column<'a, S>> {
+        Vec::new()
+    }
+
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) {
+        let (column, shifted_column) = self.columns::<S>(alloc);
+        builder.produce_intermediate_mle(column);
+        builder.produce_intermediate_mle(shifted_column);
+        builder.request_post_result_challenges(2);
+    }
+
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) -> Vec<Column<'a, S>> {
+        let (column, shifted_column) = self.columns::<S>(alloc);
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        prover_evaluate_strictly_increasing(builder, alloc, alpha, beta, column, shifted_column);
+        Vec::new()
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for StrictlyIncreasingTestPlan {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        builder.count_first_round_mles(2);
+        builder.count_post_result_challenges(2);
+        count_strictly_increasing(builder)
+    }
+
+    fn get_length(&self, _accessor: &dyn MetadataAccessor) -> usize {
+        self.values.len()
+    }
+
+    fn get_offset(&self, _accessor: &dyn MetadataAccessor) -> usize {
+        0
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        _accessor: &dyn CommitmentAccessor<C>,
+        _result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let column_eval = builder.consume_first_round_mle();
+        let shifted_column_eval = builder.consume_first_round_mle();
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        verifier_evaluate_strictly_increasing(
+            builder,
+            alpha,
+            beta,
+            self.values.len(),
+            column_eval,
+            shifted_column_eval,
+        )?;
+        Ok(Vec::new())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        Vec::new()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        IndexSet::default()
+    }
+}
+
+fn verify(plan: &StrictlyIncreasingTestPlan) -> bool {
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(plan, &accessor, &());
+    res.verify(plan, &accessor, &()).is_ok()
+}
+
+#[test]
+fn we_can_prove_that_a_column_is_strictly_increasing() {
+    assert!(verify(&StrictlyIncreasingTestPlan::new(vec![])));
+    assert!(verify(&StrictlyIncreasingTestPlan::new(vec![-5])));
+    assert!(verify(&StrictlyIncreasingTestPlan::new(vec![1, 2])));
+    assert!(verify(&StrictlyIncreasingTestPlan::new(vec![
+        -100, -3, 0, 7, 8, 1000
+    ])));
+    assert!(verify(&StrictlyIncreasingTestPlan::new(vec![
+        i64::MIN,
+        0,
+        i64::MAX
+    ])));
+}
+
+#[test]
+fn we_cannot_prove_that_a_column_with_equal_values_is_strictly_increasing() {
+    assert!(!verify(&StrictlyIncreasingTestPlan::new(vec![1, 1])));
+    assert!(!verify(&StrictlyIncreasingTestPlan::new(vec![
+        1, 2, 3, 3, 4
+    ])));
+}
+
+#[test]
+fn we_cannot_prove_that_a_decreasing_column_is_strictly_increasing() {
+    assert!(!verify(&StrictlyIncreasingTestPlan::new(vec![2, 1])));
+    assert!(!verify(&StrictlyIncreasingTestPlan::new(vec![1, 3, 2, 4])));
+}
+
+#[test]
+fn we_cannot_prove_that_a_column_is_strictly_increasing_with_an_invalid_shift() {
+    // The differences to the shifted column are all positive, but it isn't a shift of the column.
+    let plan = StrictlyIncreasingTestPlan {
+        values: vec![3, 1, 2],
+        shifted_values: vec![0, 0, 1],
+    };
+    assert!(!verify(&plan));
+}
@@ -0,0 +1,15 @@
+use crate::{base::scalar::Scalar, sql::proof::FinalRoundBuilder};
+use bumpalo::Bump;
+
+/// Compute the column `{i}` of row numbers, where `i` ranges over the entire sumcheck domain.
+///
+/// Unlike the columns of a table, this column isn't truncated, so the verifier can compute its
+/// evaluation with [`SumcheckMleEvaluations::rho_evaluation`](crate::sql::proof::SumcheckMleEvaluations::rho_evaluation).
+pub fn prover_evaluate_row_index<'a, S: Scalar>(
+    builder: &FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+) -> &'a [S] {
+    alloc.alloc_slice_fill_with(1 << builder.num_sumcheck_variables(), |i| {
+        S::from([i as u64, 0, 0, 0])
+    })
+}
@@ -0,0 +1,19 @@
+use crate::base::scalar::Scalar;
+use bumpalo::Bump;
+
+/// Shift a column down by one row, filling the first row with zero
+///
+/// This computes the shifted columns that [`prover_evaluate_strictly_increasing`](super::prover_evaluate_strictly_increasing)
+/// and [`prover_evaluate_sorted`](super::prover_evaluate_sorted) expect.
+pub fn shift<'a, S: Scalar>(alloc: &'a Bump, values: &[S]) -> &'a [S] {
+    alloc.alloc_slice_fill_with(
+        values.len(),
+        |i| {
+            if i == 0 {
+                S::zero()
+            } else {
+                values[i - 1]
+            }
+        },
+    )
+}
@@ -0,0 +1,293 @@
+use super::{
+    count_membership_check, prover_evaluate_membership_check, prover_evaluate_row_index,
+    verifier_evaluate_membership_check,
+};
+use crate::{
+    base::{
+        commitment::Commitment, database::Column, proof::ProofError, scalar::Scalar, slice_ops,
+    },
+    sql::{
+        proof::{CountBuilder, FinalRoundBuilder, SumcheckSubpolynomialType, VerificationBuilder},
+        proof_exprs::{count_sign, prover_evaluate_sign, verifier_evaluate_sign},
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use core::cmp::min;
+use num_traits::{One, Zero};
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+
+/// The sign that the difference of consecutive keys must have in the given direction
+fn direction_sign<S: Scalar>(direction: OrderByDirection) -> S {
+    match direction {
+        OrderByDirection::Asc => S::one(),
+        OrderByDirection::Desc => -S::one(),
+    }
+}
+
+/// Count the number of components needed to prove that rows are sorted by `num_keys` keys
+pub fn count_sorted(
+    builder: &mut CountBuilder,
+    num_keys: usize,
+    strict: bool,
+) -> Result<(), ProofError> {
+    count_membership_check(builder);
+    for j in 0..num_keys {
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(1);
+        builder.count_degree(3);
+        count_sign(builder)?;
+        if j + 1 < num_keys {
+            builder.count_intermediate_mles(2);
+            builder.count_subpolynomials(2);
+        }
+    }
+    if strict && num_keys > 0 {
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(1);
+    }
+    Ok(())
+}
+
+/// Prove that the rows `(keys[0][i], keys[1][i], ...)` are sorted in lexicographic order, where
+/// the `j`th key is compared in `directions[j]`. Ties are allowed unless `strict` is set.
+///
+/// `shifted_keys[j]` must be `keys[j]` shifted down by one row, i.e. `shifted_keys[j][i] = keys[j][i - 1]`
+/// for `0 < i < n`. Their first entries are ignored.
+///
+/// The proof works in the following steps:
+/// 1. The shifted keys are shown to be a shift of the keys with a membership check of the rows
+///    `(i, keys[0][i], ...)` for `i < n - 1` in the rows `(i - 1, shifted_keys[0][i], ...)` for `0 < i < n`.
+/// 2. With `d_j` the sign of `directions[j]`, the indicators `e_j` of whether all keys before the
+///    `j`th one are equal to the previous row are committed to, where `e_0` is one exactly for `0 < i < n`.
+///    The differences `w_j = e_j * d_j * (keys[j] - shifted_keys[j])` are committed to as well and
+///    `e_{j + 1} = e_j * (w_j == 0)` is shown with an inverse `inv_j` of `w_j`:
+///    ```text
+///        e_{j + 1} - e_j + w_j * inv_j = 0
+///        e_{j + 1} * (keys[j] - shifted_keys[j]) = 0
+///    ```
+/// 3. The differences `w_j` are shown to be non-negative with a sign decomposition.
+/// 4. If `strict` is set, the rows are shown to be distinct with an inverse `inv` of the last
+///    difference `w_{m - 1}`, which is nonzero wherever the previous keys are equal:
+///    ```text
+///        e_{m - 1} - w_{m - 1} * inv = 0
+///    ```
+///    Without keys, the verifier checks that there is at most one row instead.
+///
+/// Note: `keys` and `shifted_keys` have to be committed to before `alpha` and `beta` are drawn,
+/// and the differences of consecutive keys have to fit into the range of a sign decomposition.
+///
+/// # Panics
+///
+/// Panics if the keys, the shifted keys and the directions don't have matching lengths.
+#[allow(clippy::too_many_arguments)]
+pub fn prover_evaluate_sorted<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    alpha: S,
+    beta: S,
+    keys: &[&'a [S]],
+    shifted_keys: &[&'a [S]],
+    directions: &[OrderByDirection],
+    strict: bool,
+) {
+    let num_keys = keys.len();
+    assert_eq!(num_keys, shifted_keys.len());
+    assert_eq!(num_keys, directions.len());
+    let n = keys.first().map_or(0, |key| key.len());
+    let rho = prover_evaluate_row_index(builder, alloc);
+    let rho_minus_one: &[_] = alloc.alloc_slice_fill_with(rho.len(), |i| rho[i] - S::one());
+    let shifted_chi = alloc.alloc_slice_fill_copy(n, true);
+    shifted_chi[..min(1, n)].fill(false);
+
+    // 1. shifted_keys is a shift of keys
+    prover_evaluate_membership_check(
+        builder,
+        alloc,
+        alpha,
+        beta,
+        n.saturating_sub(1),
+        &core::iter::once(Column::Scalar(rho))
+            .chain(keys.iter().map(|&key| Column::Scalar(key)))
+            .collect::<Vec<_>>(),
+        n,
+        &core::iter::once(Column::Scalar(rho_minus_one as &[_]))
+            .chain(shifted_keys.iter().map(|&key| Column::Scalar(key)))
+            .collect::<Vec<_>>(),
+        Column::Boolean(shifted_chi),
+    );
+
+    let mut prefix_equal: &'a [bool] = shifted_chi;
+    for (j, ((&key, &shifted_key), &direction)) in
+        keys.iter().zip(shifted_keys).zip(directions).enumerate()
+    {
+        assert_eq!(n, key.len());
+        assert_eq!(n, shifted_key.len());
+        let sign: S = direction_sign(direction);
+
+        // 2. diff = prefix_equal * sign * (key - shifted_key)
+        let diff: &[_] = alloc.alloc_slice_fill_with(n, |i| {
+            if prefix_equal[i] {
+                sign * (key[i] - shifted_key[i])
+            } else {
+                S::zero()
+            }
+        });
+        builder.produce_intermediate_mle(diff);
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (S::one(), vec![Box::new(diff)]),
+                (-sign, vec![Box::new(prefix_equal), Box::new(key)]),
+                (sign, vec![Box::new(prefix_equal), Box::new(shifted_key)]),
+            ],
+        );
+
+        // 3. diff >= 0
+        prover_evaluate_sign(
+            builder,
+            alloc,
+            diff,
+            #[cfg(test)]
+            false,
+        );
+
+        if j + 1 < num_keys {
+            let diff_inverse = alloc.alloc_slice_copy(diff);
+            slice_ops::batch_inversion(diff_inverse);
+            let next_prefix_equal: &[_] =
+                alloc.alloc_slice_fill_with(n, |i| prefix_equal[i] && diff[i] == S::zero());
+            builder.produce_intermediate_mle(diff_inverse as &[_]);
+            builder.produce_intermediate_mle(next_prefix_equal);
+
+            // next_prefix_equal - prefix_equal + diff * diff_inverse = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(next_prefix_equal)]),
+                    (-S::one(), vec![Box::new(prefix_equal)]),
+                    (
+                        S::one(),
+                        vec![Box::new(diff), Box::new(diff_inverse as &[_])],
+                    ),
+                ],
+            );
+            // next_prefix_equal * (key - shifted_key) = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(next_prefix_equal), Box::new(key)]),
+                    (
+                        -S::one(),
+                        vec![Box::new(next_prefix_equal), Box::new(shifted_key)],
+                    ),
+                ],
+            );
+            prefix_equal = next_prefix_equal;
+        } else if strict {
+            // 4. prefix_equal - diff * diff_inverse = 0
+            let diff_inverse = alloc.alloc_slice_copy(diff);
+            slice_ops::batch_inversion(diff_inverse);
+            builder.produce_intermediate_mle(diff_inverse as &[_]);
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (S::one(), vec![Box::new(prefix_equal)]),
+                    (
+                        -S::one(),
+                        vec![Box::new(diff), Box::new(diff_inverse as &[_])],
+                    ),
+                ],
+            );
+        }
+    }
+}
+
+/// Verify that rows of length `length` are sorted.
+///
+/// See [`prover_evaluate_sorted`].
+#[allow(clippy::too_many_arguments)]
+pub fn verifier_evaluate_sorted<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    alpha: C::Scalar,
+    beta: C::Scalar,
+    length: usize,
+    key_evals: &[C::Scalar],
+    shifted_key_evals: &[C::Scalar],
+    directions: &[OrderByDirection],
+    strict: bool,
+) -> Result<(), ProofError> {
+    let num_keys = key_evals.len();
+    if strict && num_keys == 0 && length > 1 {
+        return Err(ProofError::VerificationError {
+            error: "rows are not distinct",
+        });
+    }
+    let rho_eval = builder.mle_evaluations.rho_evaluation();
+    let one_eval = builder.mle_evaluations.chi_evaluation(length);
+    let shifted_one_eval = one_eval - builder.mle_evaluations.chi_evaluation(min(1, length));
+    let candidate_one_eval = builder
+        .mle_evaluations
+        .chi_evaluation(length.saturating_sub(1));
+
+    // 1. shifted_keys is a shift of keys
+    verifier_evaluate_membership_check(
+        builder,
+        alpha,
+        beta,
+        candidate_one_eval,
+        &[[rho_eval].as_slice(), key_evals].concat(),
+        one_eval,
+        &[[rho_eval - C::Scalar::one()].as_slice(), shifted_key_evals].concat(),
+        shifted_one_eval,
+    );
+
+    let mut prefix_equal_eval = shifted_one_eval;
+    for (j, ((&key_eval, &shifted_key_eval), &direction)) in key_evals
+        .iter()
+        .zip(shifted_key_evals)
+        .zip(directions)
+        .enumerate()
+    {
+        let sign: C::Scalar = direction_sign(direction);
+
+        // 2. diff = prefix_equal * sign * (key - shifted_key)
+        let diff_eval = builder.consume_intermediate_mle();
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            diff_eval - sign * prefix_equal_eval * (key_eval - shifted_key_eval),
+        );
+
+        // 3. diff >= 0
+        // The sign bits are committed to before the evaluation point is drawn,
+        // so a zero evaluation means that all of them are zero.
+        let sign_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+        if sign_eval != C::Scalar::zero() {
+            return Err(ProofError::VerificationError {
+                error: "rows are not sorted",
+            });
+        }
+
+        if j + 1 < num_keys {
+            let diff_inverse_eval = builder.consume_intermediate_mle();
+            let next_prefix_equal_eval = builder.consume_intermediate_mle();
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                next_prefix_equal_eval - prefix_equal_eval + diff_eval * diff_inverse_eval,
+            );
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                next_prefix_equal_eval * (key_eval - shifted_key_eval),
+            );
+            prefix_equal_eval = next_prefix_equal_eval;
+        } else if strict {
+            // 4. the rows are distinct
+            let diff_inverse_eval = builder.consume_intermediate_mle();
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                prefix_equal_eval - diff_eval * diff_inverse_eval,
+            );
+        }
+    }
+    Ok(())
+}
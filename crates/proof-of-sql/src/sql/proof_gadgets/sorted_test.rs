@@ -0,0 +1,275 @@
+use super::{count_sorted, prover_evaluate_sorted, shift, verifier_evaluate_sorted};
+use crate::{
+    base::{
+        commitment::{Commitment, InnerProductProof},
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedTable, OwnedTableTestAccessor,
+        },
+        map::IndexSet,
+        proof::ProofError,
+        scalar::{Curve25519Scalar, Scalar},
+    },
+    sql::proof::{
+        CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+        VerifiableQueryResult, VerificationBuilder,
+    },
+};
+use bumpalo::Bump;
+use core::iter::repeat_with;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection::{self, Asc, Desc};
+use serde::Serialize;
+
+/// A plan that proves that the rows of `keys` are sorted in `directions`, using `shifted_keys`
+/// as the shifted keys so that a dishonest shift can be tested.
+#[derive(Debug, Serialize)]
+struct SortedTestPlan {
+    keys: Vec<Vec<i64>>,
+    shifted_keys: Vec<Vec<i64>>,
+    directions: Vec<OrderByDirection>,
+    strict: bool,
+}
+
+impl SortedTestPlan {
+    fn new(keys: Vec<Vec<i64>>, directions: Vec<OrderByDirection>) -> Self {
+        let shifted_keys = keys
+            .iter()
+            .map(|key| {
+                core::iter::once(0)
+                    .chain(key.iter().copied())
+                    .take(key.len())
+                    .collect()
+            })
+            .collect();
+        Self {
+            keys,
+            shifted_keys,
+            directions,
+            strict: false,
+        }
+    }
+
+    fn new_strict(keys: Vec<Vec<i64>>, directions: Vec<OrderByDirection>) -> Self {
+        Self {
+            strict: true,
+            ..Self::new(keys, directions)
+        }
+    }
+
+    fn length(&self) -> usize {
+        self.keys.first().map_or(0, Vec::len)
+    }
+
+    fn columns<'a, S: Scalar>(alloc: &'a Bump, keys: &[Vec<i64>]) -> Vec<&'a [S]> {
+        keys.iter()
+            .map(|key| &*alloc.alloc_slice_fill_iter(key.iter().map(|&v| S::from(v))))
+            .collect()
+    }
+}
+
+impl<S: Scalar> ProverEvaluate<S> for SortedTestPlan {
+    fn result_evaluate<'a>(
+        &self,
+        _input_length: usize,
+        _alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) -> Vec<Column<'a, S>> {
+        Vec::new()
+    }
+
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) {
+        for column in Self::columns::<S>(alloc, &self.keys)
+            .into_iter()
+            .chain(Self::columns::<S>(alloc, &self.shifted_keys))
+        {
+            builder.produce_intermediate_mle(column);
+        }
+        builder.request_post_result_challenges(2);
+    }
+
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, S>,
+        alloc: &'a Bump,
+        _accessor: &'a dyn DataAccessor<S>,
+    ) -> Vec<Column<'a, S>> {
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        prover_evaluate_sorted(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &Self::columns::<S>(alloc, &self.keys),
+            &Self::columns::<S>(alloc, &self.shifted_keys),
+            &self.directions,
+            self.strict,
+        );
+        Vec::new()
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for SortedTestPlan {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        builder.count_first_round_mles(2 * self.keys.len());
+        builder.count_post_result_challenges(2);
+        count_sorted(builder, self.keys.len(), self.strict)
+    }
+
+    fn get_length(&self, _accessor: &dyn MetadataAccessor) -> usize {
+        self.length()
+    }
+
+    fn get_offset(&self, _accessor: &dyn MetadataAccessor) -> usize {
+        0
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        _accessor: &dyn CommitmentAccessor<C>,
+        _result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let key_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(self.keys.len())
+            .collect();
+        let shifted_key_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(self.keys.len())
+            .collect();
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        verifier_evaluate_sorted(
+            builder,
+            alpha,
+            beta,
+            self.length(),
+            &key_evals,
+            &shifted_key_evals,
+            &self.directions,
+            self.strict,
+        )?;
+        Ok(Vec::new())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        Vec::new()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        IndexSet::default()
+    }
+}
+
+fn verify(plan: &SortedTestPlan) -> bool {
+    let accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    let res = VerifiableQueryResult::<InnerProductProof>::new(plan, &accessor, &());
+    res.verify(plan, &accessor, &()).is_ok()
+}
+
+#[test]
+fn we_can_shift_a_column() {
+    let alloc = Bump::new();
+    let values: Vec<Curve25519Scalar> = [3, 1, 2].into_iter().map(Into::into).collect();
+    let shifted: Vec<_> = [0, 3, 1].into_iter().map(Into::into).collect();
+    assert_eq!(shift(&alloc, &values), shifted);
+    assert!(shift::<Curve25519Scalar>(&alloc, &[]).is_empty());
+}
+
+#[test]
+fn we_can_prove_that_a_column_is_sorted() {
+    assert!(verify(&SortedTestPlan::new(vec![vec![]], vec![Asc])));
+    assert!(verify(&SortedTestPlan::new(vec![vec![-5]], vec![Desc])));
+    assert!(verify(&SortedTestPlan::new(
+        vec![vec![-100, -3, -3, 0, 7, 7, 1000]],
+        vec![Asc]
+    )));
+    assert!(verify(&SortedTestPlan::new(
+        vec![vec![i64::MAX, 0, 0, i64::MIN]],
+        vec![Desc]
+    )));
+}
+
+#[test]
+fn we_cannot_prove_that_an_unsorted_column_is_sorted() {
+    assert!(!verify(&SortedTestPlan::new(vec![vec![2, 1]], vec![Asc])));
+    assert!(!verify(&SortedTestPlan::new(vec![vec![1, 2]], vec![Desc])));
+    assert!(!verify(&SortedTestPlan::new(
+        vec![vec![1, 3, 2, 4]],
+        vec![Asc]
+    )));
+}
+
+#[test]
+fn we_can_prove_that_rows_are_sorted_by_several_keys() {
+    assert!(verify(&SortedTestPlan::new(
+        vec![vec![1, 1, 1, 2, 2, 3], vec![5, 3, 3, 9, -1, 0]],
+        vec![Asc, Desc]
+    )));
+    assert!(verify(&SortedTestPlan::new(
+        vec![
+            vec![3, 3, 3, 3, 1],
+            vec![0, 0, 1, 1, 0],
+            vec![2, 7, -4, -4, -10]
+        ],
+        vec![Desc, Asc, Asc]
+    )));
+}
+
+#[test]
+fn we_cannot_prove_that_rows_are_sorted_if_a_later_key_is_unsorted_within_ties() {
+    assert!(!verify(&SortedTestPlan::new(
+        vec![vec![1, 1, 2], vec![3, 5, 0]],
+        vec![Asc, Desc]
+    )));
+    assert!(!verify(&SortedTestPlan::new(
+        vec![vec![1, 2, 2], vec![0, 5, 4], vec![0, 0, 0]],
+        vec![Asc, Asc, Asc]
+    )));
+}
+
+#[test]
+fn we_cannot_prove_that_rows_are_sorted_with_an_invalid_shift() {
+    // The differences to the shifted keys are all non-negative, but they aren't a shift of the keys.
+    let plan = SortedTestPlan {
+        keys: vec![vec![3, 1, 2]],
+        shifted_keys: vec![vec![0, 0, 1]],
+        directions: vec![Asc],
+        strict: false,
+    };
+    assert!(!verify(&plan));
+}
+
+#[test]
+fn we_can_prove_that_rows_are_strictly_sorted() {
+    assert!(verify(&SortedTestPlan::new_strict(vec![vec![]], vec![Asc])));
+    assert!(verify(&SortedTestPlan::new_strict(vec![], vec![])));
+    assert!(verify(&SortedTestPlan::new_strict(
+        vec![vec![-100, -3, 0, 7, 1000]],
+        vec![Asc]
+    )));
+    assert!(verify(&SortedTestPlan::new_strict(
+        vec![vec![1, 1, 1, 2, 2, 3], vec![5, 3, 2, 9, -1, 0]],
+        vec![Asc, Desc]
+    )));
+}
+
+#[test]
+fn we_cannot_prove_that_rows_with_ties_are_strictly_sorted() {
+    assert!(!verify(&SortedTestPlan::new_strict(
+        vec![vec![-3, 0, 0, 7]],
+        vec![Asc]
+    )));
+    assert!(!verify(&SortedTestPlan::new_strict(
+        vec![vec![1, 1, 2, 2], vec![5, 3, 0, 0]],
+        vec![Asc, Desc]
+    )));
+}
@@ -0,0 +1,329 @@
+use super::{
+    is_sortable_column_type,
+    subquery_util::{
+        evaluate_subquery, final_round_evaluate_subquery, verifier_evaluate_subquery,
+        DerivedTableAccessor,
+    },
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedTable, TableRef,
+        },
+        map::IndexSet,
+        proof::ProofError,
+    },
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            VerificationBuilder,
+        },
+        proof_exprs::ProofExpr,
+        proof_gadgets::{count_sorted, prover_evaluate_sorted, shift, verifier_evaluate_sorted},
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use core::iter::repeat_with;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+use serde::{Deserialize, Serialize};
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     <plan> over (<subquery>) AS <table_ref>
+/// ```
+/// e.g. `SELECT * FROM (SELECT a, COUNT(*) AS n FROM t GROUP BY a) AS d WHERE n > 1`,
+/// where `plan` reads the result of `subquery` as the table `table_ref`.
+///
+/// The subquery is evaluated on its own table. Its result is committed to in the first round,
+/// along with its length, and `plan` reads these commitments as the ones of the columns of
+/// `table_ref`. The proof shows that the committed columns are the result of the subquery.
+///
+/// If the subquery is a `GroupByExec`, its groups are shown to be distinct by proving that its
+/// result is strictly sorted by the group by expressions, so their types need to be sortable,
+/// see [`is_sortable_column_type`]. Any other subquery must not contain a `GroupByExec`,
+/// since it can only be verified along with its result.
+///
+/// Note: the derived table has the offset of the table of the subquery.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DerivedTableExec<C: Commitment> {
+    pub(super) subquery: Box<DynProofPlan<C>>,
+    pub(super) table_ref: TableRef,
+    pub(super) plan: Box<DynProofPlan<C>>,
+}
+
+impl<C: Commitment> DerivedTableExec<C> {
+    /// Creates a new derived table expression.
+    pub fn new(
+        subquery: Box<DynProofPlan<C>>,
+        table_ref: TableRef,
+        plan: Box<DynProofPlan<C>>,
+    ) -> Self {
+        Self {
+            subquery,
+            table_ref,
+            plan,
+        }
+    }
+
+    /// The number of group by expressions if the subquery is a `GroupByExec`
+    fn num_group_by_keys(&self) -> Option<usize> {
+        match self.subquery.as_ref() {
+            DynProofPlan::GroupBy(group_by) => Some(group_by.group_by_exprs.len()),
+            _ => None,
+        }
+    }
+
+    /// The accessor of `plan`, where the derived table consists of `columns`
+    fn derived_accessor<'a>(
+        &self,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+        columns: &[Column<'a, C::Scalar>],
+    ) -> &'a dyn DataAccessor<C::Scalar> {
+        let columns = alloc.alloc_slice_fill_iter(
+            self.subquery
+                .get_column_result_fields()
+                .iter()
+                .map(ColumnField::name)
+                .zip(columns.iter().copied()),
+        );
+        alloc.alloc(DerivedTableAccessor {
+            accessor,
+            table_ref: self.table_ref,
+            length: columns.first().map_or(0, |(_, column)| column.len()),
+            offset: self.subquery.get_offset(&accessor),
+            columns,
+        })
+    }
+}
+
+/// Whether `subquery` can be the subquery of a derived table, see [`DerivedTableExec`].
+pub(crate) fn is_supported_subquery<C: Commitment>(subquery: &DynProofPlan<C>) -> bool {
+    match subquery {
+        DynProofPlan::GroupBy(group_by) => group_by
+            .group_by_exprs
+            .iter()
+            .all(|aliased_expr| is_sortable_column_type(&aliased_expr.expr.data_type())),
+        _ => !subquery.needs_result(),
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for DerivedTableExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        let fields = self.subquery.get_column_result_fields();
+        builder.count_one_evaluation_lengths(1);
+        self.subquery.count(builder, accessor)?;
+        builder.count_first_round_mles(fields.len());
+        if !is_supported_subquery(&self.subquery) {
+            return Err(ProofError::VerificationError {
+                error: "invalid subquery of a derived table",
+            });
+        }
+        if let Some(num_keys) = self.num_group_by_keys() {
+            builder.count_first_round_mles(num_keys);
+            count_sorted(builder, num_keys, true)?;
+            builder.count_post_result_challenges(2);
+        }
+        // The length of the derived table is only known once the proof is verified.
+        // Counting only depends on the offsets of the tables.
+        let derived_accessor = DerivedTableAccessor::<_, ()> {
+            accessor,
+            table_ref: self.table_ref,
+            length: 0,
+            offset: self.subquery.get_offset(accessor),
+            columns: &[],
+        };
+        self.plan.count(builder, &derived_accessor)
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.subquery.get_length(accessor)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.subquery.get_offset(accessor)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let fields = self.subquery.get_column_result_fields();
+        let derived_length = builder.consume_one_evaluation_length();
+
+        // 1. subquery
+        let subquery_evals =
+            verifier_evaluate_subquery(&self.subquery, builder, accessor, derived_length)?;
+
+        // 2. first round witness
+        let (derived_evals, derived_commitments): (Vec<_>, Vec<_>) =
+            repeat_with(|| builder.consume_first_round_mle_and_commitment())
+                .take(fields.len())
+                .unzip();
+        if subquery_evals != derived_evals {
+            return Err(ProofError::VerificationError {
+                error: "committed derived table does not match the subquery",
+            });
+        }
+
+        // 3. the groups of a group by subquery are distinct
+        if let Some(num_keys) = self.num_group_by_keys() {
+            let shifted_key_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+                .take(num_keys)
+                .collect();
+            let alpha = builder.consume_post_result_challenge();
+            let beta = builder.consume_post_result_challenge();
+            verifier_evaluate_sorted(
+                builder,
+                alpha,
+                beta,
+                derived_length,
+                &derived_evals[..num_keys],
+                &shifted_key_evals,
+                &vec![OrderByDirection::Asc; num_keys],
+                true,
+            )?;
+        }
+
+        // 4. plan, evaluated on the derived table
+        let columns: Vec<_> = fields
+            .iter()
+            .map(ColumnField::name)
+            .zip(derived_commitments)
+            .collect();
+        let derived_accessor = DerivedTableAccessor {
+            accessor,
+            table_ref: self.table_ref,
+            length: derived_length,
+            offset: self.subquery.get_offset(&accessor),
+            columns: &columns,
+        };
+        let table_length_of_parent = builder.mle_evaluations.replace_input_length(derived_length);
+        let evals = self
+            .plan
+            .verifier_evaluate(builder, &derived_accessor, result);
+        builder
+            .mle_evaluations
+            .replace_input_length(table_length_of_parent);
+        evals
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.plan.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.subquery
+            .get_column_references()
+            .into_iter()
+            .chain(
+                self.plan
+                    .get_column_references()
+                    .into_iter()
+                    .filter(|column| column.table_ref() != self.table_ref),
+            )
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for DerivedTableExec<C> {
+    #[tracing::instrument(name = "DerivedTableExec::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        _input_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let columns = evaluate_subquery(&self.subquery, alloc, accessor);
+        let derived_length = columns.first().map_or(0, Column::len);
+        let derived_accessor = self.derived_accessor(alloc, accessor, &columns);
+        self.plan
+            .result_evaluate(derived_length, alloc, derived_accessor)
+    }
+
+    #[tracing::instrument(
+        name = "DerivedTableExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // The verifier needs the length of the derived table before it verifies the subquery.
+        let columns = evaluate_subquery(&self.subquery, alloc, accessor);
+        builder.produce_one_evaluation_length(columns.first().map_or(0, Column::len));
+        self.subquery.first_round_evaluate(builder, alloc, accessor);
+        for column in &columns {
+            builder.produce_intermediate_mle(*column);
+        }
+        if let Some(num_keys) = self.num_group_by_keys() {
+            for column in &columns[..num_keys] {
+                builder.produce_intermediate_mle(shift(alloc, column.as_scalar(alloc)));
+            }
+            builder.request_post_result_challenges(2);
+        }
+        let derived_accessor = self.derived_accessor(alloc, accessor, &columns);
+        self.plan
+            .first_round_evaluate(builder, alloc, derived_accessor);
+    }
+
+    #[tracing::instrument(
+        name = "DerivedTableExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        // 1. subquery, evaluated on its own table
+        let columns = final_round_evaluate_subquery(&self.subquery, builder, alloc, accessor);
+        let derived_length = columns.first().map_or(0, Column::len);
+
+        // 2. the groups of a group by subquery are distinct
+        if let Some(num_keys) = self.num_group_by_keys() {
+            let alpha = builder.consume_post_result_challenge();
+            let beta = builder.consume_post_result_challenge();
+            let keys: Vec<_> = columns[..num_keys]
+                .iter()
+                .map(|column| column.as_scalar(alloc))
+                .collect();
+            let shifted_keys: Vec<_> = keys.iter().map(|key| shift(alloc, key)).collect();
+            prover_evaluate_sorted(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                &keys,
+                &shifted_keys,
+                &vec![OrderByDirection::Asc; num_keys],
+                true,
+            );
+        }
+
+        // 3. plan, evaluated on the derived table
+        let table_length_of_parent = builder.table_length();
+        builder.set_table_length(derived_length);
+        let derived_accessor = self.derived_accessor(alloc, accessor, &columns);
+        let output = self
+            .plan
+            .final_round_evaluate(builder, alloc, derived_accessor);
+        builder.set_table_length(table_length_of_parent);
+        output
+    }
+}
@@ -0,0 +1,269 @@
+use super::{is_supported_subquery, test_utility::*};
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, Column, ColumnField, ColumnRef, ColumnType, OwnedTable,
+            OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+        map::IndexSet,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, ProvableQueryResult, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, AliasedDynProofExpr, ColumnExpr, DynProofExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection::Desc;
+
+fn make_accessor(
+    data: OwnedTable<Curve25519Scalar>,
+) -> (OwnedTableTestAccessor<'static, InnerProductProof>, TableRef) {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    (accessor, t)
+}
+
+/// A column of a derived table, which the accessor doesn't know about
+fn derived_column(
+    tab: TableRef,
+    name: &str,
+    column_type: ColumnType,
+) -> DynProofExpr<RistrettoPoint> {
+    DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+        tab,
+        name.parse().unwrap(),
+        column_type,
+    )))
+}
+
+fn derived_cols_expr_plan(
+    tab: TableRef,
+    names: &[&str],
+) -> Vec<AliasedDynProofExpr<RistrettoPoint>> {
+    names
+        .iter()
+        .map(|name| aliased_plan(derived_column(tab, name, ColumnType::BigInt), name))
+        .collect()
+}
+
+fn sample_table() -> OwnedTable<Curve25519Scalar> {
+    owned_table([
+        bigint("a", [3_i64, 1, 2, 3, 2, 3]),
+        bigint("b", [10_i64, 20, 30, 40, 50, 60]),
+        varchar("c", ["x", "y", "x", "z", "y", "x"]),
+    ])
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_column_references() {
+    let (accessor, t) = make_accessor(sample_table());
+    let d = "sxt.d".parse().unwrap();
+    let ast = derived_table(
+        filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            equal(column(t, "c", &accessor), const_varchar("x")),
+        ),
+        d,
+        projection(derived_cols_expr_plan(d, &["b"]), tab(d)),
+    );
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![ColumnField::new("b".parse().unwrap(), ColumnType::BigInt)]
+    );
+    // The columns of the derived table are not columns of the accessor
+    let expected_refs = IndexSet::from_iter([
+        col_ref(t, "a", &accessor),
+        col_ref(t, "b", &accessor),
+        col_ref(t, "c", &accessor),
+    ]);
+    assert_eq!(ast.get_column_references(), expected_refs);
+    assert_eq!(ast.get_length(&accessor), 6);
+    assert_eq!(ast.get_offset(&accessor), 0);
+}
+
+#[test]
+fn we_can_prove_a_filter_over_a_group_by() {
+    let (accessor, t) = make_accessor(sample_table());
+    let d = "sxt.d".parse().unwrap();
+    let ast = derived_table(
+        group_by(
+            cols_expr_plan(t, &["a"], &accessor),
+            vec![sum_expr(column(t, "b", &accessor), "total")],
+            "n",
+            tab(t),
+            const_bool(true),
+        ),
+        d,
+        filter(
+            derived_cols_expr_plan(d, &["a", "total"]),
+            tab(d),
+            gte(derived_column(d, "n", ColumnType::BigInt), const_bigint(2)),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [2_i64, 3]), bigint("total", [80_i64, 110])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_filter_over_a_group_by_if_the_groups_are_not_filtered() {
+    let (accessor, t) = make_accessor(sample_table());
+    let d = "sxt.d".parse().unwrap();
+    let ast = derived_table(
+        group_by(
+            cols_expr_plan(t, &["a"], &accessor),
+            vec![sum_expr(column(t, "b", &accessor), "total")],
+            "n",
+            tab(t),
+            const_bool(true),
+        ),
+        d,
+        filter(
+            derived_cols_expr_plan(d, &["a", "total"]),
+            tab(d),
+            gte(derived_column(d, "n", ColumnType::BigInt), const_bigint(2)),
+        ),
+    );
+    let mut verifiable_res = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+    // Return every group instead of the ones that pass the filter
+    let a = [1_i64, 2, 3];
+    let total = [20_i64, 80, 110];
+    let columns: [Column<Curve25519Scalar>; 2] = [Column::BigInt(&a), Column::BigInt(&total)];
+    verifiable_res.provable_result = Some(ProvableQueryResult::new(3, &columns));
+    assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+}
+
+#[test]
+fn we_can_prove_a_group_by_over_a_filter() {
+    let (accessor, t) = make_accessor(sample_table());
+    let d = "sxt.d".parse().unwrap();
+    let ast = derived_table(
+        filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            gte(column(t, "b", &accessor), const_bigint(30)),
+        ),
+        d,
+        group_by(
+            derived_cols_expr_plan(d, &["a"]),
+            vec![sum_expr(
+                derived_column(d, "b", ColumnType::BigInt),
+                "total",
+            )],
+            "n",
+            tab(d),
+            const_bool(true),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [2_i64, 3]),
+        bigint("total", [80_i64, 100]),
+        bigint("n", [2_i64, 2]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_slice_of_a_sorted_derived_table_of_a_union() {
+    let (accessor, t) = make_accessor(sample_table());
+    let d = "sxt.d".parse().unwrap();
+    let ast = derived_table(
+        union_exec(vec![
+            filter(
+                cols_expr_plan(t, &["a", "b"], &accessor),
+                tab(t),
+                equal(column(t, "a", &accessor), const_bigint(2)),
+            ),
+            projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+        ]),
+        d,
+        slice_exec(
+            sort(
+                projection(derived_cols_expr_plan(d, &["b", "a"]), tab(d)),
+                vec![(0, Desc)],
+            ),
+            1,
+            Some(3),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("b", [50_i64, 50, 40]), bigint("a", [2_i64, 2, 3])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_nested_derived_tables() {
+    let (accessor, t) = make_accessor(sample_table());
+    let d1 = "sxt.d1".parse().unwrap();
+    let d2 = "sxt.d2".parse().unwrap();
+    let ast = derived_table(
+        derived_table(
+            group_by(
+                cols_expr_plan(t, &["a"], &accessor),
+                vec![sum_expr(column(t, "b", &accessor), "total")],
+                "n",
+                tab(t),
+                gte(column(t, "b", &accessor), const_bigint(20)),
+            ),
+            d1,
+            filter(
+                derived_cols_expr_plan(d1, &["a", "total"]),
+                tab(d1),
+                gte(
+                    derived_column(d1, "total", ColumnType::BigInt),
+                    const_bigint(50),
+                ),
+            ),
+        ),
+        d2,
+        projection(derived_cols_expr_plan(d2, &["total"]), tab(d2)),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("total", [80_i64, 100])]));
+}
+
+#[test]
+fn we_can_only_use_subqueries_that_can_be_verified_without_their_result() {
+    let (accessor, t) = make_accessor(sample_table());
+    let group_by_a = || {
+        group_by::<RistrettoPoint>(
+            cols_expr_plan(t, &["a"], &accessor),
+            vec![sum_expr(column(t, "b", &accessor), "total")],
+            "n",
+            tab(t),
+            const_bool(true),
+        )
+    };
+    assert!(is_supported_subquery(&group_by_a()));
+    assert!(is_supported_subquery(&projection::<RistrettoPoint>(
+        cols_expr_plan(t, &["c"], &accessor),
+        tab(t)
+    )));
+    // Strings can't be proven to be strictly sorted
+    assert!(!is_supported_subquery(&group_by::<RistrettoPoint>(
+        cols_expr_plan(t, &["c"], &accessor),
+        vec![],
+        "n",
+        tab(t),
+        const_bool(true),
+    )));
+    // A nested group by needs the query result to be verified
+    assert!(!is_supported_subquery(&union_exec(vec![
+        group_by_a(),
+        group_by_a()
+    ])));
+    assert!(!is_supported_subquery(&sort(group_by_a(), vec![(0, Desc)])));
+}
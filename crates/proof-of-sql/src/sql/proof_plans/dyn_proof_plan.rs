@@ -1,9 +1,12 @@
-use super::{FilterExec, GroupByExec, ProjectionExec};
+use super::{
+    DerivedTableExec, FilterExec, GroupByExec, JoinExec, ProjectionExec, RunningSumExec,
+    ScalarSubqueryExec, SemiJoinExec, SliceExec, SortExec, UnionExec,
+};
 use crate::{
     base::{commitment::Commitment, database::Column, map::IndexSet},
     sql::proof::{ProofPlan, ProverEvaluate},
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use serde::{Deserialize, Serialize};
 
 /// The query plan for proving a query
@@ -29,6 +32,68 @@ pub enum DynProofPlan<C: Commitment> {
     ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table> WHERE <where_clause>
     /// ```
     Filter(FilterExec<C>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_column1>, ..., <result_columnN>
+    ///     FROM <left_table> JOIN <right_table> ON <left_key> = <right_key>
+    /// ```
+    Join(Box<JoinExec<C>>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     <input> ORDER BY <order_by_column1> <direction1>, ..., <order_by_columnN> <directionN>
+    /// ```
+    Sort(Box<SortExec<C>>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     <input> LIMIT <fetch> OFFSET <skip>
+    /// ```
+    Slice(Box<SliceExec<C>>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     <input1> UNION ALL <input2> ... UNION ALL <inputN>
+    /// ```
+    Union(UnionExec<C>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM (<subquery>) AS <alias> ...
+    /// ```
+    DerivedTable(Box<DerivedTableExec<C>>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <input_columns>, SUM(<sum_column>) OVER (PARTITION BY ... ORDER BY ...) AS <alias>, ...
+    ///     FROM <sorted_input>
+    /// ```
+    RunningSum(Box<RunningSumExec<C>>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table>
+    ///     WHERE ... <key> IN (<subquery>) ...
+    /// ```
+    SemiJoin(Box<SemiJoinExec<C>>),
+    /// Provable expressions for queries of the form
+    /// ```ignore
+    ///     SELECT <result_expr1>, ..., <result_exprN> FROM <table>
+    ///     WHERE ... (<subquery>) ...
+    /// ```
+    ScalarSubquery(Box<ScalarSubqueryExec<C>>),
+}
+
+impl<C: Commitment> DynProofPlan<C> {
+    /// Whether the plan contains a `GroupByExec` outside of a subquery, so that it can only be
+    /// verified along with its result, i.e. at the top level of a query plan.
+    pub(crate) fn needs_result(&self) -> bool {
+        match self {
+            DynProofPlan::Projection(_) | DynProofPlan::Filter(_) | DynProofPlan::Join(_) => false,
+            DynProofPlan::GroupBy(_) => true,
+            DynProofPlan::Sort(expr) => expr.input.needs_result(),
+            DynProofPlan::Slice(expr) => expr.input.needs_result(),
+            DynProofPlan::Union(expr) => expr.inputs.iter().any(DynProofPlan::needs_result),
+            DynProofPlan::DerivedTable(expr) => expr.plan.needs_result(),
+            DynProofPlan::RunningSum(expr) => expr.input.needs_result(),
+            DynProofPlan::SemiJoin(expr) => expr.plan.needs_result(),
+            DynProofPlan::ScalarSubquery(expr) => expr.plan.needs_result(),
+        }
+    }
 }
 
 impl<C: Commitment> ProofPlan<C> for DynProofPlan<C> {
@@ -41,6 +106,14 @@ impl<C: Commitment> ProofPlan<C> for DynProofPlan<C> {
             DynProofPlan::Projection(expr) => expr.count(builder, accessor),
             DynProofPlan::GroupBy(expr) => expr.count(builder, accessor),
             DynProofPlan::Filter(expr) => expr.count(builder, accessor),
+            DynProofPlan::Join(expr) => expr.count(builder, accessor),
+            DynProofPlan::Sort(expr) => expr.count(builder, accessor),
+            DynProofPlan::Slice(expr) => expr.count(builder, accessor),
+            DynProofPlan::Union(expr) => expr.count(builder, accessor),
+            DynProofPlan::DerivedTable(expr) => expr.count(builder, accessor),
+            DynProofPlan::RunningSum(expr) => expr.count(builder, accessor),
+            DynProofPlan::SemiJoin(expr) => expr.count(builder, accessor),
+            DynProofPlan::ScalarSubquery(expr) => expr.count(builder, accessor),
         }
     }
 
@@ -49,6 +122,14 @@ impl<C: Commitment> ProofPlan<C> for DynProofPlan<C> {
             DynProofPlan::Projection(expr) => expr.get_length(accessor),
             DynProofPlan::GroupBy(expr) => expr.get_length(accessor),
             DynProofPlan::Filter(expr) => expr.get_length(accessor),
+            DynProofPlan::Join(expr) => expr.get_length(accessor),
+            DynProofPlan::Sort(expr) => expr.get_length(accessor),
+            DynProofPlan::Slice(expr) => expr.get_length(accessor),
+            DynProofPlan::Union(expr) => expr.get_length(accessor),
+            DynProofPlan::DerivedTable(expr) => expr.get_length(accessor),
+            DynProofPlan::RunningSum(expr) => expr.get_length(accessor),
+            DynProofPlan::SemiJoin(expr) => expr.get_length(accessor),
+            DynProofPlan::ScalarSubquery(expr) => expr.get_length(accessor),
         }
     }
 
@@ -57,6 +138,14 @@ impl<C: Commitment> ProofPlan<C> for DynProofPlan<C> {
             DynProofPlan::Projection(expr) => expr.get_offset(accessor),
             DynProofPlan::GroupBy(expr) => expr.get_offset(accessor),
             DynProofPlan::Filter(expr) => expr.get_offset(accessor),
+            DynProofPlan::Join(expr) => expr.get_offset(accessor),
+            DynProofPlan::Sort(expr) => expr.get_offset(accessor),
+            DynProofPlan::Slice(expr) => expr.get_offset(accessor),
+            DynProofPlan::Union(expr) => expr.get_offset(accessor),
+            DynProofPlan::DerivedTable(expr) => expr.get_offset(accessor),
+            DynProofPlan::RunningSum(expr) => expr.get_offset(accessor),
+            DynProofPlan::SemiJoin(expr) => expr.get_offset(accessor),
+            DynProofPlan::ScalarSubquery(expr) => expr.get_offset(accessor),
         }
     }
 
@@ -71,6 +160,14 @@ impl<C: Commitment> ProofPlan<C> for DynProofPlan<C> {
             DynProofPlan::Projection(expr) => expr.verifier_evaluate(builder, accessor, result),
             DynProofPlan::GroupBy(expr) => expr.verifier_evaluate(builder, accessor, result),
             DynProofPlan::Filter(expr) => expr.verifier_evaluate(builder, accessor, result),
+            DynProofPlan::Join(expr) => expr.verifier_evaluate(builder, accessor, result),
+            DynProofPlan::Sort(expr) => expr.verifier_evaluate(builder, accessor, result),
+            DynProofPlan::Slice(expr) => expr.verifier_evaluate(builder, accessor, result),
+            DynProofPlan::Union(expr) => expr.verifier_evaluate(builder, accessor, result),
+            DynProofPlan::DerivedTable(expr) => expr.verifier_evaluate(builder, accessor, result),
+            DynProofPlan::RunningSum(expr) => expr.verifier_evaluate(builder, accessor, result),
+            DynProofPlan::SemiJoin(expr) => expr.verifier_evaluate(builder, accessor, result),
+            DynProofPlan::ScalarSubquery(expr) => expr.verifier_evaluate(builder, accessor, result),
         }
     }
 
@@ -79,6 +176,14 @@ impl<C: Commitment> ProofPlan<C> for DynProofPlan<C> {
             DynProofPlan::Projection(expr) => expr.get_column_result_fields(),
             DynProofPlan::GroupBy(expr) => expr.get_column_result_fields(),
             DynProofPlan::Filter(expr) => expr.get_column_result_fields(),
+            DynProofPlan::Join(expr) => expr.get_column_result_fields(),
+            DynProofPlan::Sort(expr) => expr.get_column_result_fields(),
+            DynProofPlan::Slice(expr) => expr.get_column_result_fields(),
+            DynProofPlan::Union(expr) => expr.get_column_result_fields(),
+            DynProofPlan::DerivedTable(expr) => expr.get_column_result_fields(),
+            DynProofPlan::RunningSum(expr) => expr.get_column_result_fields(),
+            DynProofPlan::SemiJoin(expr) => expr.get_column_result_fields(),
+            DynProofPlan::ScalarSubquery(expr) => expr.get_column_result_fields(),
         }
     }
 
@@ -87,6 +192,14 @@ impl<C: Commitment> ProofPlan<C> for DynProofPlan<C> {
             DynProofPlan::Projection(expr) => expr.get_column_references(),
             DynProofPlan::GroupBy(expr) => expr.get_column_references(),
             DynProofPlan::Filter(expr) => expr.get_column_references(),
+            DynProofPlan::Join(expr) => expr.get_column_references(),
+            DynProofPlan::Sort(expr) => expr.get_column_references(),
+            DynProofPlan::Slice(expr) => expr.get_column_references(),
+            DynProofPlan::Union(expr) => expr.get_column_references(),
+            DynProofPlan::DerivedTable(expr) => expr.get_column_references(),
+            DynProofPlan::RunningSum(expr) => expr.get_column_references(),
+            DynProofPlan::SemiJoin(expr) => expr.get_column_references(),
+            DynProofPlan::ScalarSubquery(expr) => expr.get_column_references(),
         }
     }
 }
@@ -103,14 +216,39 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DynProofPlan<C> {
             DynProofPlan::Projection(expr) => expr.result_evaluate(input_length, alloc, accessor),
             DynProofPlan::GroupBy(expr) => expr.result_evaluate(input_length, alloc, accessor),
             DynProofPlan::Filter(expr) => expr.result_evaluate(input_length, alloc, accessor),
+            DynProofPlan::Join(expr) => expr.result_evaluate(input_length, alloc, accessor),
+            DynProofPlan::Sort(expr) => expr.result_evaluate(input_length, alloc, accessor),
+            DynProofPlan::Slice(expr) => expr.result_evaluate(input_length, alloc, accessor),
+            DynProofPlan::Union(expr) => expr.result_evaluate(input_length, alloc, accessor),
+            DynProofPlan::DerivedTable(expr) => expr.result_evaluate(input_length, alloc, accessor),
+            DynProofPlan::RunningSum(expr) => expr.result_evaluate(input_length, alloc, accessor),
+            DynProofPlan::SemiJoin(expr) => expr.result_evaluate(input_length, alloc, accessor),
+            DynProofPlan::ScalarSubquery(expr) => {
+                expr.result_evaluate(input_length, alloc, accessor)
+            }
         }
     }
 
-    fn first_round_evaluate(&self, builder: &mut crate::sql::proof::FirstRoundBuilder) {
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut crate::sql::proof::FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a bumpalo::Bump,
+        accessor: &'a dyn crate::base::database::DataAccessor<C::Scalar>,
+    ) {
         match self {
-            DynProofPlan::Projection(expr) => expr.first_round_evaluate(builder),
-            DynProofPlan::GroupBy(expr) => expr.first_round_evaluate(builder),
-            DynProofPlan::Filter(expr) => expr.first_round_evaluate(builder),
+            DynProofPlan::Projection(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::GroupBy(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Filter(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Join(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Sort(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Slice(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Union(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::DerivedTable(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::RunningSum(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::SemiJoin(expr) => expr.first_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::ScalarSubquery(expr) => {
+                expr.first_round_evaluate(builder, alloc, accessor);
+            }
         }
     }
 
@@ -125,6 +263,16 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for DynProofPlan<C> {
             DynProofPlan::Projection(expr) => expr.final_round_evaluate(builder, alloc, accessor),
             DynProofPlan::GroupBy(expr) => expr.final_round_evaluate(builder, alloc, accessor),
             DynProofPlan::Filter(expr) => expr.final_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Join(expr) => expr.final_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Sort(expr) => expr.final_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Slice(expr) => expr.final_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::Union(expr) => expr.final_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::DerivedTable(expr) => expr.final_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::RunningSum(expr) => expr.final_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::SemiJoin(expr) => expr.final_round_evaluate(builder, alloc, accessor),
+            DynProofPlan::ScalarSubquery(expr) => {
+                expr.final_round_evaluate(builder, alloc, accessor)
+            }
         }
     }
 }
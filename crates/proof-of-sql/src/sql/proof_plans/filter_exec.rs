@@ -54,6 +54,12 @@ impl<C: Commitment, H: ProverHonestyMarker> OstensibleFilterExec<C, H> {
             phantom: PhantomData,
         }
     }
+
+    /// The expressions of the result columns together with their aliases
+    #[cfg(feature = "evm")]
+    pub(crate) fn aliased_results(&self) -> &[AliasedDynProofExpr<C>] {
+        &self.aliased_results
+    }
 }
 
 impl<C: Commitment, H: ProverHonestyMarker> ProofPlan<C> for OstensibleFilterExec<C, H>
@@ -176,7 +182,22 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for FilterExec<C> {
         filtered_columns
     }
 
-    fn first_round_evaluate(&self, builder: &mut FirstRoundBuilder) {
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let input_length = accessor.get_length(self.table.table_ref);
+        // 1. selection
+        self.where_clause
+            .first_round_evaluate(builder, input_length, alloc, accessor);
+        // 2. columns
+        for aliased_expr in &self.aliased_results {
+            aliased_expr
+                .expr
+                .first_round_evaluate(builder, input_length, alloc, accessor);
+        }
         builder.request_post_result_challenges(2);
     }
 
@@ -277,6 +298,8 @@ pub(super) fn prove_filter<'a, S: Scalar + 'a>(
     let n = builder.table_length();
     let chi = alloc.alloc_slice_fill_copy(n, false);
     chi[..m].fill(true);
+    // The sumcheck domain may be longer than the table, so constant terms have to be restricted to it.
+    let ones: &[_] = alloc.alloc_slice_fill_copy(n, true);
 
     let c_fold = alloc.alloc_slice_fill_copy(n, alpha);
     fold_columns(c_fold, One::one(), beta, c);
@@ -309,7 +332,7 @@ pub(super) fn prove_filter<'a, S: Scalar + 'a>(
                 S::one(),
                 vec![Box::new(c_star as &[_]), Box::new(c_fold as &[_])],
             ),
-            (-S::one(), vec![]),
+            (-S::one(), vec![Box::new(ones)]),
         ],
     );
 
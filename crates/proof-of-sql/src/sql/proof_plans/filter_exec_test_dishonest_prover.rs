@@ -64,7 +64,22 @@ impl ProverEvaluate<Curve25519Scalar> for DishonestFilterExec<RistrettoPoint> {
         filtered_columns
     }
 
-    fn first_round_evaluate(&self, builder: &mut FirstRoundBuilder) {
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, Curve25519Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<Curve25519Scalar>,
+    ) {
+        let input_length = accessor.get_length(self.table.table_ref);
+        // 1. selection
+        self.where_clause
+            .first_round_evaluate(builder, input_length, alloc, accessor);
+        // 2. columns
+        for aliased_expr in &self.aliased_results {
+            aliased_expr
+                .expr
+                .first_round_evaluate(builder, input_length, alloc, accessor);
+        }
         builder.request_post_result_challenges(2);
     }
 
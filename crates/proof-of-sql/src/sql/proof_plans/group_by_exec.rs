@@ -1,10 +1,12 @@
-use super::{fold_columns, fold_vals};
+use super::{fold_columns, fold_vals, is_sortable_column_type};
 use crate::{
     base::{
         commitment::Commitment,
         database::{
+            filter_util::filter_column_by_index,
             group_by_util::{
-                aggregate_columns, compare_indexes_by_owned_columns, AggregatedColumns,
+                aggregate_columns, compare_indexes_by_owned_columns, group_indexes,
+                AggregatedColumns,
             },
             Column, ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor,
             MetadataAccessor, OwnedTable,
@@ -19,32 +21,42 @@ use crate::{
             CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
             SumcheckSubpolynomialType, VerificationBuilder,
         },
-        proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, ProofExpr, TableExpr},
+        proof_exprs::{
+            count_sign, prover_evaluate_sign, verifier_evaluate_sign, AliasedDynProofExpr,
+            DynProofExpr, ProofExpr, TableExpr,
+        },
     },
 };
 use alloc::{boxed::Box, vec, vec::Vec};
 use bumpalo::Bump;
-use core::{iter, iter::repeat_with};
-use num_traits::One;
-use proof_of_sql_parser::Identifier;
+use core::{cmp::Ordering, iter, iter::repeat_with};
+use num_traits::{One, Zero};
+use proof_of_sql_parser::{intermediate_ast::AggregationOperator, Identifier};
 use serde::{Deserialize, Serialize};
 
 /// Provable expressions for queries of the form
 /// ```ignore
-///     SELECT <group_by_expr1>, ..., <group_by_exprM>,
+///     SELECT <group_by_expr1>.expr as <group_by_expr1>.alias, ..., <group_by_exprM>.expr as <group_by_exprM>.alias,
 ///         SUM(<sum_expr1>.expr) as <sum_expr1>.alias, ..., SUM(<sum_exprN>.expr) as <sum_exprN>.alias,
+///         MAX(<min_max_expr1>.expr) as <min_max_expr1>.alias, ..., MIN(<min_max_exprK>.expr) as <min_max_exprK>.alias,
 ///         COUNT(*) as count_alias
 ///     FROM <table>
 ///     WHERE <where_clause>
-///     GROUP BY <group_by_expr1>, ..., <group_by_exprM>
+///     GROUP BY <group_by_expr1>.expr, ..., <group_by_exprM>.expr
 /// ```
 ///
+/// The group by expressions can be arbitrary expressions, e.g. `a + b` or `CAST(a AS BIGINT)`, and not just columns.
 /// Note: if `group_by_exprs` is empty, then the query is equivalent to removing the `GROUP BY` clause.
+/// The `COUNT(*)` column is omitted from the result if `count_alias` is `None`.
+///
+/// The expressions of `MIN` and `MAX` must not be nullable and their types need to be sortable,
+/// see [`is_sortable_column_type`].
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct GroupByExec<C: Commitment> {
-    pub(super) group_by_exprs: Vec<ColumnExpr<C>>,
+    pub(super) group_by_exprs: Vec<AliasedDynProofExpr<C>>,
     pub(super) sum_expr: Vec<AliasedDynProofExpr<C>>,
-    pub(super) count_alias: Identifier,
+    pub(super) min_max_expr: Vec<(AggregationOperator, AliasedDynProofExpr<C>)>,
+    pub(super) count_alias: Option<Identifier>,
     pub(super) table: TableExpr,
     pub(super) where_clause: DynProofExpr<C>,
 }
@@ -52,60 +64,85 @@ pub struct GroupByExec<C: Commitment> {
 impl<C: Commitment> GroupByExec<C> {
     /// Creates a new `group_by` expression.
     pub fn new(
-        group_by_exprs: Vec<ColumnExpr<C>>,
+        group_by_exprs: Vec<AliasedDynProofExpr<C>>,
         sum_expr: Vec<AliasedDynProofExpr<C>>,
-        count_alias: Identifier,
+        min_max_expr: Vec<(AggregationOperator, AliasedDynProofExpr<C>)>,
+        count_alias: Option<Identifier>,
         table: TableExpr,
         where_clause: DynProofExpr<C>,
     ) -> Self {
         Self {
             group_by_exprs,
             sum_expr,
+            min_max_expr,
             count_alias,
             table,
             where_clause,
         }
     }
-}
-
-impl<C: Commitment> ProofPlan<C> for GroupByExec<C> {
-    fn count(
-        &self,
-        builder: &mut CountBuilder,
-        _accessor: &dyn MetadataAccessor,
-    ) -> Result<(), ProofError> {
-        self.where_clause.count(builder)?;
-        for expr in &self.group_by_exprs {
-            expr.count(builder)?;
-            builder.count_intermediate_mles(1);
-        }
-        for aliased_expr in &self.sum_expr {
-            aliased_expr.expr.count(builder)?;
-            builder.count_intermediate_mles(1);
-        }
-        // For the count col
-        builder.count_intermediate_mles(1);
-        builder.count_intermediate_mles(2);
-        builder.count_subpolynomials(3);
-        builder.count_degree(3);
-        builder.count_post_result_challenges(2);
-        Ok(())
-    }
 
-    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
-        accessor.get_length(self.table.table_ref)
+    /// The operators of the `MIN` and `MAX` expressions
+    fn min_max_ops(&self) -> Vec<AggregationOperator> {
+        self.min_max_expr.iter().map(|(op, _)| *op).collect()
     }
 
-    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
-        accessor.get_offset(self.table.table_ref)
+    /// Aggregate the selected rows of the input columns.
+    ///
+    /// Returns the aggregated columns and the extrema of the `MIN` and `MAX` expressions.
+    ///
+    /// # Panics
+    /// Panics if the columns do not have the same length.
+    fn aggregate<'a>(
+        &self,
+        alloc: &'a Bump,
+        group_by_columns: &[Column<'a, C::Scalar>],
+        sum_columns: &[Column<'a, C::Scalar>],
+        min_max_columns: &[Column<'a, C::Scalar>],
+        selection: &[bool],
+    ) -> (
+        AggregatedColumns<'a, C::Scalar>,
+        Vec<MinMaxWitness<'a, C::Scalar>>,
+    ) {
+        let aggregated_columns = aggregate_columns(
+            alloc,
+            group_by_columns,
+            &sum_columns
+                .iter()
+                .copied()
+                .map(Into::into)
+                .collect::<Vec<_>>(),
+            &[],
+            &[],
+            selection,
+        )
+        .expect("columns should be aggregatable");
+        let group_indexes = group_indexes(group_by_columns, selection);
+        let min_max_witnesses = self
+            .min_max_ops()
+            .into_iter()
+            .zip(min_max_columns)
+            .map(|(op, column)| {
+                MinMaxWitness::new(
+                    alloc,
+                    op,
+                    &group_indexes,
+                    aggregated_columns.count_column.len(),
+                    column,
+                )
+            })
+            .collect();
+        (aggregated_columns, min_max_witnesses)
     }
 
-    #[allow(unused_variables)]
-    fn verifier_evaluate(
+    /// Verify the aggregation without checking that the groups are distinct.
+    ///
+    /// The groups are shown to be distinct by checking that the query result is ordered by the
+    /// group by expressions. A plan that reads the result of a `GroupByExec` has to show this
+    /// in another way, e.g. with a proof that the result is strictly sorted.
+    pub(super) fn verifier_evaluate_groups(
         &self,
         builder: &mut VerificationBuilder<C>,
         accessor: &dyn CommitmentAccessor<C>,
-        result: Option<&OwnedTable<C::Scalar>>,
     ) -> Result<Vec<C::Scalar>, ProofError> {
         // 1. selection
         let where_eval = self.where_clause.verifier_evaluate(builder, accessor)?;
@@ -113,14 +150,29 @@ impl<C: Commitment> ProofPlan<C> for GroupByExec<C> {
         let group_by_evals = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.verifier_evaluate(builder, accessor))
+            .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
             .collect::<Result<Vec<_>, _>>()?;
         let aggregate_evals = self
             .sum_expr
             .iter()
             .map(|aliased_expr| aliased_expr.expr.verifier_evaluate(builder, accessor))
             .collect::<Result<Vec<_>, _>>()?;
-        // 3. filtered_columns
+        let min_max_evals = self
+            .min_max_expr
+            .iter()
+            .map(|(_, aliased_expr)| aliased_expr.expr.verifier_evaluate(builder, accessor))
+            .collect::<Result<Vec<_>, _>>()?;
+        // 3. first round witnesses
+        let count_column_eval = builder.consume_first_round_mle();
+        let (row_extrema_evals, is_extremum_evals): (Vec<_>, Vec<_>) = repeat_with(|| {
+            (
+                builder.consume_first_round_mle(),
+                builder.consume_first_round_mle(),
+            )
+        })
+        .take(self.min_max_expr.len())
+        .unzip();
+        // 4. filtered_columns
 
         let group_by_result_columns_evals: Vec<_> =
             repeat_with(|| builder.consume_intermediate_mle())
@@ -129,28 +181,191 @@ impl<C: Commitment> ProofPlan<C> for GroupByExec<C> {
         let sum_result_columns_evals: Vec<_> = repeat_with(|| builder.consume_intermediate_mle())
             .take(self.sum_expr.len())
             .collect();
-        let count_column_eval = builder.consume_intermediate_mle();
+        let min_max_result_columns_evals: Vec<_> =
+            repeat_with(|| builder.consume_intermediate_mle())
+                .take(self.min_max_expr.len())
+                .collect();
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
 
+        // The extremum of a group is treated as part of its key and the extremum witnesses
+        // of each group must sum to one.
+        let output_one_eval = builder.mle_evaluations.output_one_evaluation;
         verify_group_by(
             builder,
             alpha,
             beta,
-            (group_by_evals, aggregate_evals, where_eval),
             (
-                group_by_result_columns_evals.clone(),
-                sum_result_columns_evals.clone(),
+                [group_by_evals, row_extrema_evals.clone()].concat(),
+                [aggregate_evals, is_extremum_evals.clone()].concat(),
+                where_eval,
+            ),
+            (
+                [
+                    group_by_result_columns_evals.clone(),
+                    min_max_result_columns_evals.clone(),
+                ]
+                .concat(),
+                sum_result_columns_evals
+                    .iter()
+                    .copied()
+                    .chain(iter::repeat(output_one_eval).take(self.min_max_expr.len()))
+                    .collect(),
                 count_column_eval,
             ),
         )?;
+        // 5. min and max
+        for (((op, _), min_max_eval), (row_extremum_eval, is_extremum_eval)) in self
+            .min_max_expr
+            .iter()
+            .zip(min_max_evals)
+            .zip(row_extrema_evals.into_iter().zip(is_extremum_evals))
+        {
+            verify_min_max(
+                builder,
+                min_max_sign(*op),
+                where_eval,
+                min_max_eval,
+                row_extremum_eval,
+                is_extremum_eval,
+            )?;
+        }
+        Ok(group_by_result_columns_evals
+            .into_iter()
+            .chain(sum_result_columns_evals)
+            .chain(min_max_result_columns_evals)
+            .chain(self.count_alias.map(|_| count_column_eval))
+            .collect::<Vec<_>>())
+    }
+}
+
+/// The sign of the difference `extremum - value`, which is non-negative for every row of a group
+fn min_max_sign<S: Scalar>(op: AggregationOperator) -> S {
+    match op {
+        AggregationOperator::Min => -S::one(),
+        _ => S::one(),
+    }
+}
+
+/// The witness of a `MIN` or `MAX` expression
+struct MinMaxWitness<'a, S: Scalar> {
+    /// The extremum of each group
+    extrema: Column<'a, S>,
+    /// The extremum of the group of each selected row and zero for the other rows
+    row_extrema: &'a [S],
+    /// Whether a row is the first row of its group whose value is the extremum
+    is_extremum: &'a [bool],
+}
+
+impl<'a, S: Scalar> MinMaxWitness<'a, S> {
+    /// Find the extremum of each group given the group index of each selected row.
+    ///
+    /// # Panics
+    /// Panics if one of the `num_groups` groups has no selected row.
+    fn new(
+        alloc: &'a Bump,
+        op: AggregationOperator,
+        group_indexes: &[Option<usize>],
+        num_groups: usize,
+        column: &Column<'a, S>,
+    ) -> Self {
+        let target = match op {
+            AggregationOperator::Min => Ordering::Less,
+            _ => Ordering::Greater,
+        };
+        let values = column.as_scalar(alloc);
+        let mut extremum_indexes: Vec<Option<usize>> = vec![None; num_groups];
+        for (i, group_index) in group_indexes.iter().enumerate() {
+            if let Some(group_index) = *group_index {
+                let extremum_index = &mut extremum_indexes[group_index];
+                if extremum_index.map_or(true, |j| values[i].signed_cmp(&values[j]) == target) {
+                    *extremum_index = Some(i);
+                }
+            }
+        }
+        let extremum_indexes: Vec<usize> = extremum_indexes
+            .into_iter()
+            .map(|index| index.expect("groups are not empty"))
+            .collect();
+        let is_extremum = alloc.alloc_slice_fill_copy(values.len(), false);
+        for &index in &extremum_indexes {
+            is_extremum[index] = true;
+        }
+        let row_extrema = alloc.alloc_slice_fill_with(values.len(), |i| {
+            group_indexes[i].map_or(S::zero(), |group_index| {
+                values[extremum_indexes[group_index]]
+            })
+        });
+        Self {
+            extrema: filter_column_by_index(alloc, column, &extremum_indexes),
+            row_extrema,
+            is_extremum,
+        }
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for GroupByExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        _accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        self.where_clause.count(builder)?;
+        for aliased_expr in &self.group_by_exprs {
+            aliased_expr.expr.count(builder)?;
+            builder.count_intermediate_mles(1);
+        }
+        for aliased_expr in &self.sum_expr {
+            aliased_expr.expr.count(builder)?;
+            builder.count_intermediate_mles(1);
+        }
+        for (op, aliased_expr) in &self.min_max_expr {
+            if !matches!(op, AggregationOperator::Max | AggregationOperator::Min)
+                || !is_sortable_column_type(&aliased_expr.expr.data_type())
+            {
+                return Err(ProofError::VerificationError {
+                    error: "invalid min or max expression in group by",
+                });
+            }
+            aliased_expr.expr.count(builder)?;
+            builder.count_intermediate_mles(1);
+        }
+        // For the count col and the witnesses of the min and max expressions
+        builder.count_first_round_mles(1 + 2 * self.min_max_expr.len());
+        builder.count_intermediate_mles(2);
+        builder.count_subpolynomials(3);
+        builder.count_degree(3);
+        builder.count_post_result_challenges(2);
+        for _ in &self.min_max_expr {
+            builder.count_intermediate_mles(1);
+            builder.count_subpolynomials(2);
+            count_sign(builder)?;
+        }
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_length(self.table.table_ref)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_offset(self.table.table_ref)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let evals = self.verifier_evaluate_groups(builder, accessor)?;
         match result {
             Some(table) => {
                 let cols = self
                     .group_by_exprs
                     .iter()
-                    .map(|col| table.inner_table().get(&col.column_id()))
+                    .map(|aliased_expr| table.inner_table().get(&aliased_expr.alias))
                     .collect::<Option<Vec<_>>>()
                     .ok_or(ProofError::VerificationError {
                         error: "Result does not all correct group by columns.",
@@ -166,35 +381,33 @@ impl<C: Commitment> ProofPlan<C> for GroupByExec<C> {
             None => todo!("GroupByExec currently only supported at top level of query plan."),
         }
 
-        Ok(group_by_result_columns_evals
-            .into_iter()
-            .chain(sum_result_columns_evals)
-            .chain(iter::once(count_column_eval))
-            .collect::<Vec<_>>())
+        Ok(evals)
     }
 
-    #[allow(clippy::redundant_closure_for_method_calls)]
     fn get_column_result_fields(&self) -> Vec<ColumnField> {
         self.group_by_exprs
             .iter()
-            .map(|col| col.get_column_field())
-            .chain(self.sum_expr.iter().map(|aliased_expr| {
-                ColumnField::new(aliased_expr.alias, aliased_expr.expr.data_type())
-            }))
-            .chain(iter::once(ColumnField::new(
-                self.count_alias,
-                ColumnType::BigInt,
-            )))
+            .chain(&self.sum_expr)
+            .chain(
+                self.min_max_expr
+                    .iter()
+                    .map(|(_, aliased_expr)| aliased_expr),
+            )
+            .map(|aliased_expr| ColumnField::new(aliased_expr.alias, aliased_expr.expr.data_type()))
+            .chain(
+                self.count_alias
+                    .map(|count_alias| ColumnField::new(count_alias, ColumnType::BigInt)),
+            )
             .collect()
     }
 
     fn get_column_references(&self) -> IndexSet<ColumnRef> {
         let mut columns = IndexSet::default();
 
-        for col in &self.group_by_exprs {
-            columns.insert(col.get_column_reference());
+        for aliased_expr in self.group_by_exprs.iter().chain(&self.sum_expr) {
+            aliased_expr.expr.get_column_references(&mut columns);
         }
-        for aliased_expr in &self.sum_expr {
+        for (_, aliased_expr) in &self.min_max_expr {
             aliased_expr.expr.get_column_references(&mut columns);
         }
 
@@ -225,7 +438,11 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExec<C> {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.result_evaluate(input_length, alloc, accessor))
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .result_evaluate(input_length, alloc, accessor)
+            })
             .collect::<Vec<_>>();
         let sum_columns = self
             .sum_expr
@@ -236,23 +453,99 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExec<C> {
                     .result_evaluate(input_length, alloc, accessor)
             })
             .collect::<Vec<_>>();
+        let min_max_columns = self
+            .min_max_expr
+            .iter()
+            .map(|(_, aliased_expr)| {
+                aliased_expr
+                    .expr
+                    .result_evaluate(input_length, alloc, accessor)
+            })
+            .collect::<Vec<_>>();
         // Compute filtered_columns
-        let AggregatedColumns {
-            group_by_columns: group_by_result_columns,
-            sum_columns: sum_result_columns,
-            count_column,
-            ..
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
-            .expect("columns should be aggregatable");
+        // Note that `AggregateExpr` already replaces the null values of the sum columns by zero.
+        let (
+            AggregatedColumns {
+                group_by_columns: group_by_result_columns,
+                sum_columns: sum_result_columns,
+                count_column,
+                ..
+            },
+            min_max_witnesses,
+        ) = self.aggregate(
+            alloc,
+            &group_by_columns,
+            &sum_columns,
+            &min_max_columns,
+            selection,
+        );
         let sum_result_columns_iter = sum_result_columns.iter().map(|col| Column::Scalar(col));
         group_by_result_columns
             .into_iter()
             .chain(sum_result_columns_iter)
-            .chain(iter::once(Column::BigInt(count_column)))
+            .chain(min_max_witnesses.iter().map(|witness| witness.extrema))
+            .chain(self.count_alias.map(|_| Column::BigInt(count_column)))
             .collect::<Vec<_>>()
     }
 
-    fn first_round_evaluate(&self, builder: &mut FirstRoundBuilder) {
+    #[tracing::instrument(name = "GroupByExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let input_length = accessor.get_length(self.table.table_ref);
+        // 0. first round witnesses of the expressions, in the order in which they are verified
+        self.where_clause
+            .first_round_evaluate(builder, input_length, alloc, accessor);
+        for aliased_expr in self.group_by_exprs.iter().chain(&self.sum_expr).chain(
+            self.min_max_expr
+                .iter()
+                .map(|(_, aliased_expr)| aliased_expr),
+        ) {
+            aliased_expr
+                .expr
+                .first_round_evaluate(builder, input_length, alloc, accessor);
+        }
+
+        // 1. selection
+        let selection_column: Column<'a, C::Scalar> =
+            self.where_clause
+                .result_evaluate(input_length, alloc, accessor);
+        let selection = selection_column
+            .as_boolean()
+            .expect("selection is not boolean");
+
+        // 2. columns
+        let group_by_columns = self
+            .group_by_exprs
+            .iter()
+            .map(|aliased_expr| {
+                aliased_expr
+                    .expr
+                    .result_evaluate(input_length, alloc, accessor)
+            })
+            .collect::<Vec<_>>();
+        let min_max_columns = self
+            .min_max_expr
+            .iter()
+            .map(|(_, aliased_expr)| {
+                aliased_expr
+                    .expr
+                    .result_evaluate(input_length, alloc, accessor)
+            })
+            .collect::<Vec<_>>();
+
+        // 3. Commit to the count column and the witnesses of the min and max expressions,
+        // since they are not necessarily part of the result.
+        let (AggregatedColumns { count_column, .. }, min_max_witnesses) =
+            self.aggregate(alloc, &group_by_columns, &[], &min_max_columns, selection);
+        builder.produce_intermediate_mle(count_column);
+        for witness in min_max_witnesses {
+            builder.produce_intermediate_mle(witness.row_extrema);
+            builder.produce_intermediate_mle(witness.is_extremum);
+        }
         builder.request_post_result_challenges(2);
     }
 
@@ -275,50 +568,207 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for GroupByExec<C> {
         let group_by_columns = self
             .group_by_exprs
             .iter()
-            .map(|expr| expr.prover_evaluate(builder, alloc, accessor))
+            .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor))
             .collect::<Vec<_>>();
         let sum_columns = self
             .sum_expr
             .iter()
             .map(|aliased_expr| aliased_expr.expr.prover_evaluate(builder, alloc, accessor))
             .collect::<Vec<_>>();
+        let min_max_columns = self
+            .min_max_expr
+            .iter()
+            .map(|(_, aliased_expr)| aliased_expr.expr.prover_evaluate(builder, alloc, accessor))
+            .collect::<Vec<_>>();
         // 3. Compute filtered_columns
-        let AggregatedColumns {
-            group_by_columns: group_by_result_columns,
-            sum_columns: sum_result_columns,
-            count_column,
-            ..
-        } = aggregate_columns(alloc, &group_by_columns, &sum_columns, &[], &[], selection)
-            .expect("columns should be aggregatable");
+        let (
+            AggregatedColumns {
+                group_by_columns: group_by_result_columns,
+                sum_columns: sum_result_columns,
+                count_column,
+                ..
+            },
+            min_max_witnesses,
+        ) = self.aggregate(
+            alloc,
+            &group_by_columns,
+            &sum_columns,
+            &min_max_columns,
+            selection,
+        );
 
         let alpha = builder.consume_post_result_challenge();
         let beta = builder.consume_post_result_challenge();
 
         // 4. Tally results
         let sum_result_columns_iter = sum_result_columns.iter().map(|col| Column::Scalar(col));
-        let res = group_by_result_columns
+        let min_max_result_columns = min_max_witnesses
+            .iter()
+            .map(|witness| witness.extrema)
+            .collect::<Vec<_>>();
+        let mut res = group_by_result_columns
             .clone()
             .into_iter()
             .chain(sum_result_columns_iter)
-            .chain(core::iter::once(Column::BigInt(count_column)))
+            .chain(min_max_result_columns.iter().copied())
             .collect::<Vec<_>>();
         // 5. Produce MLEs
         res.iter().copied().for_each(|column| {
             builder.produce_intermediate_mle(column);
         });
         // 6. Prove group by
+        // The extremum of a group is treated as part of its key and the extremum witnesses
+        // of each group must sum to one.
+        let ones: &[_] = alloc.alloc_slice_fill_copy(count_column.len(), C::Scalar::one());
         prove_group_by(
             builder,
             alloc,
             alpha,
             beta,
-            (&group_by_columns, &sum_columns, selection),
-            (&group_by_result_columns, &sum_result_columns, count_column),
+            (
+                &group_by_columns
+                    .iter()
+                    .copied()
+                    .chain(
+                        min_max_witnesses
+                            .iter()
+                            .map(|witness| Column::Scalar(witness.row_extrema)),
+                    )
+                    .collect::<Vec<_>>(),
+                &sum_columns
+                    .iter()
+                    .copied()
+                    .chain(
+                        min_max_witnesses
+                            .iter()
+                            .map(|witness| Column::Boolean(witness.is_extremum)),
+                    )
+                    .collect::<Vec<_>>(),
+                selection,
+            ),
+            (
+                &[group_by_result_columns, min_max_result_columns].concat(),
+                &sum_result_columns
+                    .iter()
+                    .copied()
+                    .chain(iter::repeat(ones).take(self.min_max_expr.len()))
+                    .collect::<Vec<_>>(),
+                count_column,
+            ),
         );
+        // 7. Prove min and max
+        for (((op, _), min_max_column), witness) in self
+            .min_max_expr
+            .iter()
+            .zip(&min_max_columns)
+            .zip(&min_max_witnesses)
+        {
+            prove_min_max(
+                builder,
+                alloc,
+                min_max_sign(*op),
+                selection,
+                min_max_column.as_scalar(alloc),
+                witness,
+            );
+        }
+        if self.count_alias.is_some() {
+            res.push(Column::BigInt(count_column));
+        }
         res
     }
 }
 
+/// Verify that the values of the selected rows are bounded by the extremum of their group
+/// and that the extremum is attained by the witness row.
+///
+/// See [`prove_min_max`].
+fn verify_min_max<C: Commitment>(
+    builder: &mut VerificationBuilder<C>,
+    sign: C::Scalar,
+    sel_in_eval: C::Scalar,
+    value_eval: C::Scalar,
+    row_extremum_eval: C::Scalar,
+    is_extremum_eval: C::Scalar,
+) -> Result<(), ProofError> {
+    let one_eval = builder.mle_evaluations.input_one_evaluation;
+
+    // diff - sign * sel_in * row_extremum + sign * sel_in * value = 0
+    let diff_eval = builder.consume_intermediate_mle();
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::Identity,
+        diff_eval - sign * sel_in_eval * row_extremum_eval + sign * sel_in_eval * value_eval,
+    );
+
+    // sign(diff) != -1
+    let sign_eval = verifier_evaluate_sign(builder, diff_eval, one_eval)?;
+    if sign_eval != C::Scalar::zero() {
+        return Err(ProofError::VerificationError {
+            error: "values are not bounded by the minimum or maximum of their group",
+        });
+    }
+
+    // is_extremum * row_extremum - is_extremum * value = 0
+    builder.produce_sumcheck_subpolynomial_evaluation(
+        &SumcheckSubpolynomialType::Identity,
+        is_extremum_eval * row_extremum_eval - is_extremum_eval * value_eval,
+    );
+    Ok(())
+}
+
+/// Prove that the values of the selected rows are bounded by the extremum of their group
+/// and that the extremum is attained by the witness row.
+///
+/// The group by argument already shows that `row_extremum` is constant on each group and that
+/// `is_extremum` sums to one on each group.
+fn prove_min_max<'a, S: Scalar>(
+    builder: &mut FinalRoundBuilder<'a, S>,
+    alloc: &'a Bump,
+    sign: S,
+    sel_in: &'a [bool],
+    values: &'a [S],
+    witness: &MinMaxWitness<'a, S>,
+) {
+    let row_extrema = witness.row_extrema;
+    let is_extremum = witness.is_extremum;
+
+    // diff = sign * sel_in * (row_extremum - value)
+    let diff: &[_] = alloc.alloc_slice_fill_with(values.len(), |i| {
+        if sel_in[i] {
+            sign * (row_extrema[i] - values[i])
+        } else {
+            S::zero()
+        }
+    });
+    builder.produce_intermediate_mle(diff);
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(diff)]),
+            (-sign, vec![Box::new(sel_in), Box::new(row_extrema)]),
+            (sign, vec![Box::new(sel_in), Box::new(values)]),
+        ],
+    );
+
+    // sign(diff) != -1
+    prover_evaluate_sign(
+        builder,
+        alloc,
+        diff,
+        #[cfg(test)]
+        false,
+    );
+
+    // is_extremum * row_extremum - is_extremum * value = 0
+    builder.produce_sumcheck_subpolynomial(
+        SumcheckSubpolynomialType::Identity,
+        vec![
+            (S::one(), vec![Box::new(is_extremum), Box::new(row_extrema)]),
+            (-S::one(), vec![Box::new(is_extremum), Box::new(values)]),
+        ],
+    );
+}
+
 #[allow(clippy::unnecessary_wraps)]
 fn verify_group_by<C: Commitment>(
     builder: &mut VerificationBuilder<C>,
@@ -376,6 +826,8 @@ pub fn prove_group_by<'a, S: Scalar>(
 ) {
     let n = builder.table_length();
     let m_out = count_out.len();
+    // The sumcheck domain may be longer than the table, so constant terms have to be restricted to it.
+    let ones: &[_] = alloc.alloc_slice_fill_copy(n, true);
 
     // g_in_fold = alpha + sum beta^j * g_in[j]
     let g_in_fold = alloc.alloc_slice_fill_copy(n, alpha);
@@ -436,7 +888,7 @@ pub fn prove_group_by<'a, S: Scalar>(
                 S::one(),
                 vec![Box::new(g_in_star as &[_]), Box::new(g_in_fold as &[_])],
             ),
-            (-S::one(), vec![]),
+            (-S::one(), vec![Box::new(ones)]),
         ],
     );
 
@@ -451,7 +903,7 @@ pub fn prove_group_by<'a, S: Scalar>(
                     Box::new(g_out_bar_fold as &[_]),
                 ],
             ),
-            (-S::one(), vec![]),
+            (-S::one(), vec![Box::new(ones)]),
         ],
     );
 }
@@ -7,9 +7,10 @@ use crate::{
     },
     sql::{
         proof::{exercise_verification, VerifiableQueryResult},
-        proof_exprs::test_utility::*,
+        proof_exprs::{test_utility::*, AliasedDynProofExpr, DynProofExpr},
     },
 };
+use proof_of_sql_parser::intermediate_ast::AggregationOperator;
 
 /// `select a, sum(c) as sum_c, count(*) as __count__ from sxt.t where b = 99 group by a`
 #[test]
@@ -23,7 +24,7 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = group_by(
-        cols_expr(t, &["a"], &accessor),
+        cols_expr_plan(t, &["a"], &accessor),
         vec![sum_expr(column(t, "c", &accessor), "sum_c")],
         "__count__",
         tab(t),
@@ -40,6 +41,186 @@ fn we_can_prove_a_simple_group_by_with_bigint_columns() {
     assert_eq!(res, expected);
 }
 
+/// `select a + b as a_plus_b, sum(c) as sum_c, count(*) as __count__ from sxt.t group by a + b`
+#[test]
+fn we_can_prove_a_group_by_on_a_computed_expression() {
+    let data = owned_table([
+        bigint("a", [1, 2, 0, 1, 3]),
+        bigint("b", [2, 1, 3, 0, 0]),
+        bigint("c", [101, 102, 103, 104, 105]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        vec![aliased_plan(
+            add(column(t, "a", &accessor), column(t, "b", &accessor)),
+            "a_plus_b",
+        )],
+        vec![sum_expr(column(t, "c", &accessor), "sum_c")],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a_plus_b", [1, 3]),
+        bigint("sum_c", [104, 101 + 102 + 103 + 105]),
+        bigint("__count__", [1, 4]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, sum(c) as sum_c, count(*) as __count__ from sxt.t group by a`, where `c` is nullable
+#[test]
+fn we_can_prove_a_group_by_with_a_sum_of_a_nullable_column() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2]),
+        bigint("c", [101, 0, 103, 104, 0]),
+        boolean("__c_presence__", [true, false, true, true, false]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![AliasedDynProofExpr {
+            expr: DynProofExpr::try_new_nullable_aggregate(
+                AggregationOperator::Sum,
+                column(t, "c", &accessor),
+                column(t, "__c_presence__", &accessor),
+            )
+            .unwrap(),
+            alias: "sum_c".parse().unwrap(),
+        }],
+        "__count__",
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2]),
+        bigint("sum_c", [101 + 104, 103]),
+        bigint("__count__", [2, 3]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, max(c) as max_c, count(*) as __count__ from sxt.t where b = 99 group by a`
+#[test]
+fn we_can_prove_a_group_by_with_a_max() {
+    let data = owned_table([
+        bigint("a", [1, 2, 2, 1, 2, 3, 1]),
+        bigint("b", [99, 99, 99, 99, 0, 99, 99]),
+        bigint("c", [-101, 102, 103, -104, 105, 0, -103]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by_with_min_max(
+        cols_expr_plan(t, &["a"], &accessor),
+        vec![],
+        vec![(
+            AggregationOperator::Max,
+            aliased_plan(column(t, "c", &accessor), "max_c"),
+        )],
+        Some("__count__"),
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("max_c", [-101, 103, 0]),
+        bigint("__count__", [3, 2, 1]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select a, b, sum(c) as sum_c, min(c) as min_c, max(c - b) as max_d from sxt.t group by a, b`
+#[test]
+fn we_can_prove_a_group_by_with_sums_mins_and_maxes_and_no_count() {
+    let data = owned_table([
+        varchar("a", ["x", "y", "x", "x", "y", "x", "y"]),
+        int("b", [1, 1, 2, 1, 1, 2, 1]),
+        bigint("c", [5, 5, -7, 2, 9, 7, 5]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by_with_min_max(
+        cols_expr_plan(t, &["a", "b"], &accessor),
+        vec![sum_expr(column(t, "c", &accessor), "sum_c")],
+        vec![
+            (
+                AggregationOperator::Min,
+                aliased_plan(column(t, "c", &accessor), "min_c"),
+            ),
+            (
+                AggregationOperator::Max,
+                aliased_plan(
+                    subtract(column(t, "c", &accessor), column(t, "b", &accessor)),
+                    "max_d",
+                ),
+            ),
+        ],
+        None,
+        tab(t),
+        const_bool(true),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([
+        varchar("a", ["x", "x", "y"]),
+        int("b", [1, 2, 1]),
+        bigint("sum_c", [7, 0, 19]),
+        bigint("min_c", [2, -7, 5]),
+        bigint("max_d", [4, 5, 8]),
+    ]);
+    assert_eq!(res, expected);
+}
+
+/// `select min(c) as min_c, max(c) as max_c from sxt.t where b = 99`
+#[test]
+fn we_can_prove_a_min_and_max_without_group_by_columns() {
+    let data = owned_table([
+        bigint("b", [99, 99, 0, 99]),
+        smallint("c", [3_i16, -4, -9, 1]),
+    ]);
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    let expr = group_by_with_min_max(
+        vec![],
+        vec![],
+        vec![
+            (
+                AggregationOperator::Min,
+                aliased_plan(column(t, "c", &accessor), "min_c"),
+            ),
+            (
+                AggregationOperator::Max,
+                aliased_plan(column(t, "c", &accessor), "max_c"),
+            ),
+        ],
+        None,
+        tab(t),
+        equal(column(t, "b", &accessor), const_int128(99)),
+    );
+    let res = VerifiableQueryResult::new(&expr, &accessor, &());
+    exercise_verification(&res, &expr, &accessor, t);
+    let res = res.verify(&expr, &accessor, &()).unwrap().table;
+    let expected = owned_table([smallint("min_c", [-4_i16]), smallint("max_c", [3_i16])]);
+    assert_eq!(res, expected);
+}
+
 /// `select a, sum(c * 2 + 1) as sum_c, count(*) as __count__ from sxt.t where b = 99 group by a`
 #[test]
 fn we_can_prove_a_group_by_with_bigint_columns() {
@@ -52,7 +233,7 @@ fn we_can_prove_a_group_by_with_bigint_columns() {
     let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
     accessor.add_table(t, data, 0);
     let expr = group_by(
-        cols_expr(t, &["a"], &accessor),
+        cols_expr_plan(t, &["a"], &accessor),
         vec![sum_expr(
             add(
                 multiply(column(t, "c", &accessor), const_bigint(2)),
@@ -159,7 +340,7 @@ fn we_can_prove_a_complex_group_by_query_with_many_columns() {
     //  FROM sxt.t WHERE int128_filter = 1020 AND varchar_filter = 'f2'
     //  GROUP BY scalar_group, int128_group, bigint_group
     let expr = group_by(
-        cols_expr(
+        cols_expr_plan(
             t,
             &["scalar_group", "int128_group", "bigint_group"],
             &accessor,
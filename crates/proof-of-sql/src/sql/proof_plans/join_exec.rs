@@ -0,0 +1,590 @@
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            filter_util::filter_column_by_index,
+            join_util::{compute_join_indexes, join_key_values},
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedTable,
+        },
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            VerificationBuilder,
+        },
+        proof_exprs::{ColumnExpr, ProofExpr, TableExpr},
+        proof_gadgets::{
+            count_membership_check, count_strictly_increasing, prover_evaluate_membership_check,
+            prover_evaluate_row_index, prover_evaluate_strictly_increasing, shift,
+            verifier_evaluate_membership_check, verifier_evaluate_strictly_increasing,
+        },
+    },
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+use bumpalo::Bump;
+use core::iter::repeat_with;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+
+/// The number of MLEs that a join commits to in the first round
+const NUM_FIRST_ROUND_MLES: usize = 10;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <result_column1>, ..., <result_columnN>
+///     FROM <left_table> JOIN <right_table> ON <left_key> = <right_key>
+/// ```
+///
+/// The rows of the result are ordered by their row in the left table and then by their row in
+/// the right table, i.e. in the order that a nested loop join over the left table produces.
+///
+/// Note: both tables need to have the same offset and the keys need to be integers of the same type.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct JoinExec<C: Commitment> {
+    pub(super) left_table: TableExpr,
+    pub(super) right_table: TableExpr,
+    pub(super) left_key: ColumnExpr<C>,
+    pub(super) right_key: ColumnExpr<C>,
+    pub(super) aliased_results: Vec<(ColumnExpr<C>, Identifier)>,
+}
+
+impl<C: Commitment> JoinExec<C> {
+    /// Creates a new join expression.
+    pub fn new(
+        left_table: TableExpr,
+        right_table: TableExpr,
+        left_key: ColumnExpr<C>,
+        right_key: ColumnExpr<C>,
+        aliased_results: Vec<(ColumnExpr<C>, Identifier)>,
+    ) -> Self {
+        Self {
+            left_table,
+            right_table,
+            left_key,
+            right_key,
+            aliased_results,
+        }
+    }
+
+    /// Whether a result column is taken from the left table
+    fn is_left_column(&self, column: &ColumnExpr<C>) -> bool {
+        column.get_column_reference().table_ref() == self.left_table.table_ref
+    }
+
+    /// Compute the witness of the join from the keys of the two tables
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys are not integers, which is checked when the plan is built.
+    fn join_witness<'a>(
+        &self,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> JoinWitness<'a, C::Scalar> {
+        let key_values = |key: &ColumnExpr<C>| {
+            join_key_values(&accessor.get_column(key.get_column_reference()))
+                .expect("join keys must be integers")
+        };
+        JoinWitness::new(
+            alloc,
+            &key_values(&self.left_key),
+            &key_values(&self.right_key),
+        )
+    }
+
+    /// Compute the result columns from the columns of the two tables
+    fn result_columns<'a>(
+        &self,
+        alloc: &'a Bump,
+        witness: &JoinWitness<'a, C::Scalar>,
+        columns: &[Column<'a, C::Scalar>],
+    ) -> Vec<Column<'a, C::Scalar>> {
+        self.aliased_results
+            .iter()
+            .zip(columns)
+            .map(|((column_expr, _), column)| {
+                let indexes = if self.is_left_column(column_expr) {
+                    &witness.left_indexes
+                } else {
+                    &witness.right_indexes
+                };
+                filter_column_by_index(alloc, column, indexes)
+            })
+            .collect()
+    }
+
+    /// Split the result columns, or their evaluations, into the ones taken from the left table
+    /// and the ones taken from the right table
+    fn split_by_table<T: Copy>(&self, values: &[T]) -> (Vec<T>, Vec<T>) {
+        let (left, right): (Vec<_>, Vec<_>) = self
+            .aliased_results
+            .iter()
+            .zip(values)
+            .partition(|((column_expr, _), _)| self.is_left_column(column_expr));
+        (
+            left.into_iter().map(|(_, &value)| value).collect(),
+            right.into_iter().map(|(_, &value)| value).collect(),
+        )
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for JoinExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        if accessor.get_offset(self.left_table.table_ref)
+            != accessor.get_offset(self.right_table.table_ref)
+        {
+            return Err(ProofError::VerificationError {
+                error: "joined tables must have the same offset",
+            });
+        }
+        builder.count_first_round_mles(NUM_FIRST_ROUND_MLES);
+        self.left_key.count(builder)?;
+        self.right_key.count(builder)?;
+        for (column_expr, _) in &self.aliased_results {
+            column_expr.count(builder)?;
+            builder.count_intermediate_mles(1);
+        }
+        // The rows of the result are rows of the left and the right table
+        count_membership_check(builder);
+        count_membership_check(builder);
+        // The multiplicities are the number of matching rows in the other table
+        count_membership_check(builder);
+        count_membership_check(builder);
+        // The keys are distinct and the result is sorted
+        count_strictly_increasing(builder)?;
+        count_strictly_increasing(builder)?;
+        builder.count_post_result_challenges(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_length(self.left_table.table_ref)
+            + accessor.get_length(self.right_table.table_ref)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        accessor.get_offset(self.left_table.table_ref)
+    }
+
+    #[allow(clippy::similar_names)]
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        _result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let left_length = accessor.get_length(self.left_table.table_ref);
+        let right_length = accessor.get_length(self.right_table.table_ref);
+        let output_length = builder.mle_evaluations.output_length;
+
+        // 1. first round witness
+        let left_indexes_eval = builder.consume_first_round_mle();
+        let right_indexes_eval = builder.consume_first_round_mle();
+        let output_keys_eval = builder.consume_first_round_mle();
+        let left_multiplicities_eval = builder.consume_first_round_mle();
+        let right_multiplicities_eval = builder.consume_first_round_mle();
+        let distinct_keys_eval = builder.consume_first_round_mle();
+        let right_key_counts_eval = builder.consume_first_round_mle();
+        let left_key_counts_eval = builder.consume_first_round_mle();
+        let shifted_distinct_keys_eval = builder.consume_first_round_mle();
+        let shifted_row_pairs_eval = builder.consume_first_round_mle();
+
+        // 2. keys and columns
+        let left_key_eval = self.left_key.verifier_evaluate(builder, accessor)?;
+        let right_key_eval = self.right_key.verifier_evaluate(builder, accessor)?;
+        let column_evals = self
+            .aliased_results
+            .iter()
+            .map(|(column_expr, _)| column_expr.verifier_evaluate(builder, accessor))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // 3. result columns
+        let result_column_evals: Vec<_> = repeat_with(|| builder.consume_intermediate_mle())
+            .take(self.aliased_results.len())
+            .collect();
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let output_one_eval = builder.mle_evaluations.chi_evaluation(output_length);
+        let left_one_eval = builder.mle_evaluations.chi_evaluation(left_length);
+        let right_one_eval = builder.mle_evaluations.chi_evaluation(right_length);
+        let distinct_keys_one_eval = builder
+            .mle_evaluations
+            .chi_evaluation(left_length + right_length);
+        let rho_eval = builder.mle_evaluations.rho_evaluation();
+        let (left_column_evals, right_column_evals) = self.split_by_table(&column_evals);
+        let (left_result_evals, right_result_evals) = self.split_by_table(&result_column_evals);
+
+        // 4. the rows of the result are rows of the left and the right table
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            output_one_eval,
+            &[
+                [left_indexes_eval, output_keys_eval].as_slice(),
+                &left_result_evals,
+            ]
+            .concat(),
+            left_one_eval,
+            &[[rho_eval, left_key_eval].as_slice(), &left_column_evals].concat(),
+            left_multiplicities_eval,
+        );
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            output_one_eval,
+            &[
+                [right_indexes_eval, output_keys_eval].as_slice(),
+                &right_result_evals,
+            ]
+            .concat(),
+            right_one_eval,
+            &[[rho_eval, right_key_eval].as_slice(), &right_column_evals].concat(),
+            right_multiplicities_eval,
+        );
+
+        // 5. the multiplicities of the left table are the number of matching rows in the right table
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            right_one_eval,
+            &[right_key_eval],
+            distinct_keys_one_eval,
+            &[distinct_keys_eval],
+            right_key_counts_eval,
+        );
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            left_one_eval,
+            &[left_key_eval, left_multiplicities_eval],
+            distinct_keys_one_eval,
+            &[distinct_keys_eval, right_key_counts_eval],
+            left_key_counts_eval,
+        );
+
+        // 6. the distinct keys are distinct and the rows of the result are sorted and distinct
+        verifier_evaluate_strictly_increasing(
+            builder,
+            alpha,
+            beta,
+            left_length + right_length,
+            distinct_keys_eval,
+            shifted_distinct_keys_eval,
+        )?;
+        verifier_evaluate_strictly_increasing(
+            builder,
+            alpha,
+            beta,
+            output_length,
+            left_indexes_eval * C::Scalar::from([right_length as u64, 0, 0, 0])
+                + right_indexes_eval,
+            shifted_row_pairs_eval,
+        )?;
+
+        Ok(result_column_evals)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.aliased_results
+            .iter()
+            .map(|(column_expr, alias)| ColumnField::new(*alias, column_expr.data_type()))
+            .collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let mut columns = IndexSet::default();
+        self.left_key.get_column_references(&mut columns);
+        self.right_key.get_column_references(&mut columns);
+        for (column_expr, _) in &self.aliased_results {
+            column_expr.get_column_references(&mut columns);
+        }
+        columns
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for JoinExec<C> {
+    #[tracing::instrument(name = "JoinExec::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        _input_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let witness = self.join_witness(alloc, accessor);
+        let columns: Vec<_> = self
+            .aliased_results
+            .iter()
+            .map(|(column_expr, _)| accessor.get_column(column_expr.get_column_reference()))
+            .collect();
+        self.result_columns(alloc, &witness, &columns)
+    }
+
+    #[tracing::instrument(name = "JoinExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let witness = self.join_witness(alloc, accessor);
+        builder.produce_intermediate_mle(witness.left_index_column);
+        builder.produce_intermediate_mle(witness.right_index_column);
+        builder.produce_intermediate_mle(witness.output_keys);
+        builder.produce_intermediate_mle(witness.left_multiplicities);
+        builder.produce_intermediate_mle(witness.right_multiplicities);
+        builder.produce_intermediate_mle(witness.distinct_keys);
+        builder.produce_intermediate_mle(witness.right_key_counts);
+        builder.produce_intermediate_mle(witness.left_key_counts);
+        builder.produce_intermediate_mle(witness.shifted_distinct_keys);
+        builder.produce_intermediate_mle(witness.shifted_row_pairs);
+        builder.request_post_result_challenges(2);
+    }
+
+    #[tracing::instrument(name = "JoinExec::final_round_evaluate", level = "debug", skip_all)]
+    #[allow(clippy::similar_names)]
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let witness = self.join_witness(alloc, accessor);
+        let left_length = accessor.get_length(self.left_table.table_ref);
+        let right_length = accessor.get_length(self.right_table.table_ref);
+        let output_length = witness.left_indexes.len();
+
+        // 1. keys and columns
+        let left_key = self.left_key.prover_evaluate(builder, alloc, accessor);
+        let right_key = self.right_key.prover_evaluate(builder, alloc, accessor);
+        let columns: Vec<_> = self
+            .aliased_results
+            .iter()
+            .map(|(column_expr, _)| column_expr.prover_evaluate(builder, alloc, accessor))
+            .collect();
+
+        // 2. result columns
+        let result_columns = self.result_columns(alloc, &witness, &columns);
+        for column in &result_columns {
+            builder.produce_intermediate_mle(*column);
+        }
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let rho = Column::Scalar(prover_evaluate_row_index(builder, alloc));
+        let (left_columns, right_columns) = self.split_by_table(&columns);
+        let (left_results, right_results) = self.split_by_table(&result_columns);
+
+        // 3. the rows of the result are rows of the left and the right table
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            output_length,
+            &[
+                [witness.left_index_column, witness.output_keys].as_slice(),
+                &left_results,
+            ]
+            .concat(),
+            left_length,
+            &[[rho, left_key].as_slice(), &left_columns].concat(),
+            witness.left_multiplicities,
+        );
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            output_length,
+            &[
+                [witness.right_index_column, witness.output_keys].as_slice(),
+                &right_results,
+            ]
+            .concat(),
+            right_length,
+            &[[rho, right_key].as_slice(), &right_columns].concat(),
+            witness.right_multiplicities,
+        );
+
+        // 4. the multiplicities of the left table are the number of matching rows in the right table
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            right_length,
+            &[right_key],
+            left_length + right_length,
+            &[witness.distinct_keys],
+            witness.right_key_counts,
+        );
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            left_length,
+            &[left_key, witness.left_multiplicities],
+            left_length + right_length,
+            &[witness.distinct_keys, witness.right_key_counts],
+            witness.left_key_counts,
+        );
+
+        // 5. the distinct keys are distinct and the rows of the result are sorted and distinct
+        prover_evaluate_strictly_increasing(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            witness.distinct_key_values,
+            witness.shifted_distinct_key_values,
+        );
+        prover_evaluate_strictly_increasing(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            witness.row_pairs,
+            witness.shifted_row_pair_values,
+        );
+
+        result_columns
+    }
+}
+
+/// The columns that the prover commits to in the first round of a join proof, along with
+/// the values needed to compute them.
+struct JoinWitness<'a, S: Scalar> {
+    /// The row of the left table that each row of the result comes from
+    left_indexes: Vec<usize>,
+    /// The row of the right table that each row of the result comes from
+    right_indexes: Vec<usize>,
+    /// `left_indexes` as a column
+    left_index_column: Column<'a, S>,
+    /// `right_indexes` as a column
+    right_index_column: Column<'a, S>,
+    /// The key of each row of the result
+    output_keys: Column<'a, S>,
+    /// The number of rows of the result that each row of the left table appears in
+    left_multiplicities: Column<'a, S>,
+    /// The number of rows of the result that each row of the right table appears in
+    right_multiplicities: Column<'a, S>,
+    /// The distinct keys of both tables in increasing order, padded with larger values
+    /// to the total number of rows of both tables
+    distinct_key_values: &'a [S],
+    /// `distinct_key_values` as a column
+    distinct_keys: Column<'a, S>,
+    /// The number of rows of the right table with each of the distinct keys
+    right_key_counts: Column<'a, S>,
+    /// The number of rows of the left table with each of the distinct keys
+    left_key_counts: Column<'a, S>,
+    /// `distinct_key_values` shifted down by one row
+    shifted_distinct_key_values: &'a [S],
+    /// `shifted_distinct_key_values` as a column
+    shifted_distinct_keys: Column<'a, S>,
+    /// `left_index * right_length + right_index` for each row of the result.
+    ///
+    /// This is not committed to, since the verifier can compute it from the indexes.
+    row_pairs: &'a [S],
+    /// `row_pairs` shifted down by one row
+    shifted_row_pair_values: &'a [S],
+    /// `shifted_row_pair_values` as a column
+    shifted_row_pairs: Column<'a, S>,
+}
+
+impl<'a, S: Scalar> JoinWitness<'a, S> {
+    #[allow(clippy::cast_possible_wrap)]
+    fn new(alloc: &'a Bump, left_keys: &[i128], right_keys: &[i128]) -> Self {
+        let (left_indexes, right_indexes) = compute_join_indexes(left_keys, right_keys);
+        let right_length = right_keys.len();
+        let distinct_keys_length = left_keys.len() + right_keys.len();
+
+        let left_index_column: &[_] =
+            alloc.alloc_slice_fill_iter(left_indexes.iter().map(|&i| i as i64));
+        let right_index_column: &[_] =
+            alloc.alloc_slice_fill_iter(right_indexes.iter().map(|&j| j as i64));
+        let output_keys: &[_] =
+            alloc.alloc_slice_fill_iter(left_indexes.iter().map(|&i| S::from(left_keys[i])));
+
+        let left_multiplicities = alloc.alloc_slice_fill_copy(left_keys.len(), 0_i64);
+        for &i in &left_indexes {
+            left_multiplicities[i] += 1;
+        }
+        let right_multiplicities = alloc.alloc_slice_fill_copy(right_keys.len(), 0_i64);
+        for &j in &right_indexes {
+            right_multiplicities[j] += 1;
+        }
+
+        // (left count, right count) for every key
+        let mut key_counts: BTreeMap<i128, (i64, i64)> = BTreeMap::new();
+        for key in left_keys {
+            key_counts.entry(*key).or_default().0 += 1;
+        }
+        for key in right_keys {
+            key_counts.entry(*key).or_default().1 += 1;
+        }
+        let last_key = key_counts
+            .keys()
+            .last()
+            .map_or(S::zero(), |&key| S::from(key));
+        let distinct_key_values: Vec<_> = key_counts
+            .keys()
+            .map(|&key| S::from(key))
+            .chain(
+                (1..=(distinct_keys_length - key_counts.len()) as u64)
+                    .map(|k| last_key + S::from([k, 0, 0, 0])),
+            )
+            .collect();
+        let distinct_key_values: &[_] = alloc.alloc_slice_copy(&distinct_key_values);
+        let left_key_counts = alloc.alloc_slice_fill_copy(distinct_keys_length, 0_i64);
+        let right_key_counts = alloc.alloc_slice_fill_copy(distinct_keys_length, 0_i64);
+        for (u, (left_count, right_count)) in key_counts.values().enumerate() {
+            left_key_counts[u] = *left_count;
+            right_key_counts[u] = *right_count;
+        }
+
+        let row_pairs: &[_] = alloc.alloc_slice_fill_iter(
+            left_indexes
+                .iter()
+                .zip(&right_indexes)
+                .map(|(&i, &j)| S::from([(i * right_length + j) as u64, 0, 0, 0])),
+        );
+
+        let shifted_distinct_key_values = shift(alloc, distinct_key_values);
+        let shifted_row_pair_values = shift(alloc, row_pairs);
+
+        Self {
+            left_indexes,
+            right_indexes,
+            left_index_column: Column::BigInt(left_index_column),
+            right_index_column: Column::BigInt(right_index_column),
+            output_keys: Column::Scalar(output_keys),
+            left_multiplicities: Column::BigInt(left_multiplicities),
+            right_multiplicities: Column::BigInt(right_multiplicities),
+            distinct_key_values,
+            distinct_keys: Column::Scalar(distinct_key_values),
+            right_key_counts: Column::BigInt(right_key_counts),
+            left_key_counts: Column::BigInt(left_key_counts),
+            shifted_distinct_key_values,
+            shifted_distinct_keys: Column::Scalar(shifted_distinct_key_values),
+            row_pairs,
+            shifted_row_pair_values,
+            shifted_row_pairs: Column::Scalar(shifted_row_pair_values),
+        }
+    }
+}
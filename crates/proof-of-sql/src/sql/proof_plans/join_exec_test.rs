@@ -0,0 +1,293 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TestAccessor,
+        },
+        map::IndexSet,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, ColumnExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+
+fn make_accessor(
+    left: OwnedTable<Curve25519Scalar>,
+    right: OwnedTable<Curve25519Scalar>,
+) -> (
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+    TableRef,
+    TableRef,
+) {
+    let left_ref = "sxt.left".parse().unwrap();
+    let right_ref = "sxt.right".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(left_ref, left, 0);
+    accessor.add_table(right_ref, right, 0);
+    (accessor, left_ref, right_ref)
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_column_references() {
+    let (accessor, l, r) = make_accessor(
+        owned_table([bigint("k", [1_i64]), varchar("a", ["x"])]),
+        owned_table([bigint("k", [1_i64]), int128("b", [2_i128])]),
+    );
+    let ast = join::<RistrettoPoint>(
+        tab(l),
+        tab(r),
+        ColumnExpr::new(col_ref(l, "k", &accessor)),
+        ColumnExpr::new(col_ref(r, "k", &accessor)),
+        vec![
+            aliased_col(r, "b", "b", &accessor),
+            aliased_col(l, "a", "a", &accessor),
+            aliased_col(l, "k", "key", &accessor),
+        ],
+    );
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![
+            ColumnField::new("b".parse().unwrap(), ColumnType::Int128),
+            ColumnField::new("a".parse().unwrap(), ColumnType::VarChar),
+            ColumnField::new("key".parse().unwrap(), ColumnType::BigInt),
+        ]
+    );
+    let expected_refs = IndexSet::from_iter([
+        col_ref(l, "k", &accessor),
+        col_ref(r, "k", &accessor),
+        col_ref(r, "b", &accessor),
+        col_ref(l, "a", &accessor),
+    ]);
+    assert_eq!(ast.get_column_references(), expected_refs);
+    assert_eq!(ast.get_length(&accessor), 2);
+}
+
+#[test]
+fn we_can_prove_a_join_with_one_to_one_matches() {
+    let (accessor, l, r) = make_accessor(
+        owned_table([
+            bigint("id", [1_i64, 2, 3, 4]),
+            varchar("name", ["a", "b", "c", "d"]),
+        ]),
+        owned_table([
+            bigint("user_id", [3_i64, 1, 5]),
+            bigint("amount", [30_i64, 10, 50]),
+        ]),
+    );
+    let ast = join(
+        tab(l),
+        tab(r),
+        ColumnExpr::new(col_ref(l, "id", &accessor)),
+        ColumnExpr::new(col_ref(r, "user_id", &accessor)),
+        vec![
+            aliased_col(l, "name", "name", &accessor),
+            aliased_col(r, "amount", "amount", &accessor),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, l);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("name", ["a", "c"]), bigint("amount", [10_i64, 30])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_join_with_many_to_many_matches() {
+    let (accessor, l, r) = make_accessor(
+        owned_table([
+            int("k", [2, 1, 2, 7, 1]),
+            bigint("a", [10_i64, 11, 12, 13, 14]),
+        ]),
+        owned_table([
+            int("k", [1, 2, 2, 1, 3]),
+            bigint("b", [20_i64, 21, 22, 23, 24]),
+        ]),
+    );
+    let ast = join(
+        tab(l),
+        tab(r),
+        ColumnExpr::new(col_ref(l, "k", &accessor)),
+        ColumnExpr::new(col_ref(r, "k", &accessor)),
+        vec![
+            aliased_col(l, "k", "k", &accessor),
+            aliased_col(l, "a", "a", &accessor),
+            aliased_col(r, "b", "b", &accessor),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, l);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("k", [2, 2, 1, 1, 2, 2, 1, 1]),
+        bigint("a", [10_i64, 10, 11, 11, 12, 12, 14, 14]),
+        bigint("b", [21_i64, 22, 20, 23, 21, 22, 20, 23]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_join_whose_result_is_larger_than_both_tables() {
+    let (accessor, l, r) = make_accessor(
+        owned_table([bigint("k", [5_i64, 5, 5]), bigint("a", [1_i64, 2, 3])]),
+        owned_table([bigint("k", [5_i64, 5, 5]), bigint("b", [4_i64, 5, 6])]),
+    );
+    let ast = join(
+        tab(l),
+        tab(r),
+        ColumnExpr::new(col_ref(l, "k", &accessor)),
+        ColumnExpr::new(col_ref(r, "k", &accessor)),
+        vec![
+            aliased_col(l, "a", "a", &accessor),
+            aliased_col(r, "b", "b", &accessor),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, l);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        bigint("a", [1_i64, 1, 1, 2, 2, 2, 3, 3, 3]),
+        bigint("b", [4_i64, 5, 6, 4, 5, 6, 4, 5, 6]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_join_on_extreme_keys() {
+    let (accessor, l, r) = make_accessor(
+        owned_table([
+            int128("k", [i128::MAX, i128::MIN, 0, -1]),
+            bigint("a", [1_i64, 2, 3, 4]),
+        ]),
+        owned_table([
+            int128("k", [i128::MIN, -1, i128::MAX, 1]),
+            bigint("b", [5_i64, 6, 7, 8]),
+        ]),
+    );
+    let ast = join(
+        tab(l),
+        tab(r),
+        ColumnExpr::new(col_ref(l, "k", &accessor)),
+        ColumnExpr::new(col_ref(r, "k", &accessor)),
+        vec![
+            aliased_col(l, "a", "a", &accessor),
+            aliased_col(r, "b", "b", &accessor),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, l);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1_i64, 2, 4]), bigint("b", [7_i64, 5, 6])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_join_without_matches() {
+    let (accessor, l, r) = make_accessor(
+        owned_table([bigint("k", [1_i64, 2]), bigint("a", [1_i64, 2])]),
+        owned_table([bigint("k", [3_i64, 4, 5]), bigint("b", [3_i64, 4, 5])]),
+    );
+    let ast = join(
+        tab(l),
+        tab(r),
+        ColumnExpr::new(col_ref(l, "k", &accessor)),
+        ColumnExpr::new(col_ref(r, "k", &accessor)),
+        vec![
+            aliased_col(l, "a", "a", &accessor),
+            aliased_col(r, "b", "b", &accessor),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, l);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_join_with_an_empty_table() {
+    let (accessor, l, r) = make_accessor(
+        owned_table([bigint("k", [0_i64; 0]), bigint("a", [0_i64; 0])]),
+        owned_table([bigint("k", [3_i64, 4, 5]), bigint("b", [3_i64, 4, 5])]),
+    );
+    let ast = join(
+        tab(l),
+        tab(r),
+        ColumnExpr::new(col_ref(l, "k", &accessor)),
+        ColumnExpr::new(col_ref(r, "k", &accessor)),
+        vec![
+            aliased_col(l, "a", "a", &accessor),
+            aliased_col(r, "b", "b", &accessor),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, l);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_join_with_offsets() {
+    let left_ref = "sxt.left".parse().unwrap();
+    let right_ref = "sxt.right".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        left_ref,
+        owned_table([bigint("k", [1_i64, 2, 3]), bigint("a", [1_i64, 2, 3])]),
+        3,
+    );
+    accessor.add_table(
+        right_ref,
+        owned_table([bigint("k", [3_i64, 2]), bigint("b", [4_i64, 5])]),
+        3,
+    );
+    let ast = join(
+        tab(left_ref),
+        tab(right_ref),
+        ColumnExpr::new(col_ref(left_ref, "k", &accessor)),
+        ColumnExpr::new(col_ref(right_ref, "k", &accessor)),
+        vec![
+            aliased_col(left_ref, "a", "a", &accessor),
+            aliased_col(right_ref, "b", "b", &accessor),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, left_ref);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [2_i64, 3]), bigint("b", [5_i64, 4])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_join_of_tables_with_different_offsets() {
+    let left_ref = "sxt.left".parse().unwrap();
+    let right_ref = "sxt.right".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        left_ref,
+        owned_table([bigint("k", [1_i64, 2]), bigint("a", [1_i64, 2])]),
+        0,
+    );
+    accessor.add_table(
+        right_ref,
+        owned_table([bigint("k", [2_i64, 1]), bigint("b", [4_i64, 5])]),
+        1,
+    );
+    let ast = join::<RistrettoPoint>(
+        tab(left_ref),
+        tab(right_ref),
+        ColumnExpr::new(col_ref(left_ref, "k", &accessor)),
+        ColumnExpr::new(col_ref(right_ref, "k", &accessor)),
+        vec![
+            aliased_col(left_ref, "a", "a", &accessor),
+            aliased_col(right_ref, "b", "b", &accessor),
+        ],
+    );
+    let verifiable_res = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+    assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+}
@@ -27,5 +27,47 @@ pub(crate) use group_by_exec::GroupByExec;
 #[cfg(all(test, feature = "blitzar"))]
 mod group_by_exec_test;
 
+mod join_exec;
+pub(crate) use join_exec::JoinExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod join_exec_test;
+
+mod sort_exec;
+pub(crate) use sort_exec::{is_sortable_column_type, SortExec};
+#[cfg(all(test, feature = "blitzar"))]
+mod sort_exec_test;
+
+mod slice_exec;
+pub(crate) use slice_exec::SliceExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod slice_exec_test;
+
+mod union_exec;
+pub(crate) use union_exec::UnionExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod union_exec_test;
+
+mod subquery_util;
+
+mod derived_table_exec;
+pub(crate) use derived_table_exec::{is_supported_subquery, DerivedTableExec};
+#[cfg(all(test, feature = "blitzar"))]
+mod derived_table_exec_test;
+
+mod running_sum_exec;
+pub(crate) use running_sum_exec::RunningSumExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod running_sum_exec_test;
+
+mod semi_join_exec;
+pub(crate) use semi_join_exec::SemiJoinExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod semi_join_exec_test;
+
+mod scalar_subquery_exec;
+pub(crate) use scalar_subquery_exec::ScalarSubqueryExec;
+#[cfg(all(test, feature = "blitzar"))]
+mod scalar_subquery_exec_test;
+
 mod dyn_proof_plan;
 pub use dyn_proof_plan::DynProofPlan;
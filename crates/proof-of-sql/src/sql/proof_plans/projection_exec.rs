@@ -39,6 +39,12 @@ impl<C: Commitment> ProjectionExec<C> {
             table,
         }
     }
+
+    /// The expressions of the result columns together with their aliases
+    #[cfg(feature = "evm")]
+    pub(crate) fn aliased_results(&self) -> &[AliasedDynProofExpr<C>] {
+        &self.aliased_results
+    }
 }
 
 impl<C: Commitment> ProofPlan<C> for ProjectionExec<C> {
@@ -114,7 +120,19 @@ impl<C: Commitment> ProverEvaluate<C::Scalar> for ProjectionExec<C> {
         columns
     }
 
-    fn first_round_evaluate(&self, _builder: &mut FirstRoundBuilder) {}
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        let input_length = accessor.get_length(self.table.table_ref);
+        for aliased_expr in &self.aliased_results {
+            aliased_expr
+                .expr
+                .first_round_evaluate(builder, input_length, alloc, accessor);
+        }
+    }
 
     #[tracing::instrument(
         name = "ProjectionExec::final_round_evaluate",
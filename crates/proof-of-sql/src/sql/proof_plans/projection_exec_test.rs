@@ -168,7 +168,7 @@ fn we_can_get_an_empty_result_from_a_basic_projection_on_an_empty_table_using_re
     let result_cols = expr.result_evaluate(0, &alloc, &accessor);
     let output_length = result_cols.first().map_or(0, Column::len) as u64;
     let mut builder = FirstRoundBuilder::new();
-    expr.first_round_evaluate(&mut builder);
+    expr.first_round_evaluate(&mut builder, &alloc, &accessor);
     let fields = &[
         ColumnField::new("b".parse().unwrap(), ColumnType::BigInt),
         ColumnField::new("c".parse().unwrap(), ColumnType::Int128),
@@ -209,7 +209,7 @@ fn we_can_get_no_columns_from_a_basic_projection_with_no_selected_columns_using_
     let result_cols = expr.result_evaluate(5, &alloc, &accessor);
     let output_length = result_cols.first().map_or(0, Column::len) as u64;
     let mut builder = FirstRoundBuilder::new();
-    expr.first_round_evaluate(&mut builder);
+    expr.first_round_evaluate(&mut builder, &alloc, &accessor);
     let fields = &[];
     let res: OwnedTable<Curve25519Scalar> =
         ProvableQueryResult::new(output_length as u64, &result_cols)
@@ -247,7 +247,7 @@ fn we_can_get_the_correct_result_from_a_basic_projection_using_result_evaluate()
     let result_cols = expr.result_evaluate(5, &alloc, &accessor);
     let output_length = result_cols.first().map_or(0, Column::len) as u64;
     let mut builder = FirstRoundBuilder::new();
-    expr.first_round_evaluate(&mut builder);
+    expr.first_round_evaluate(&mut builder, &alloc, &accessor);
     let fields = &[
         ColumnField::new("b".parse().unwrap(), ColumnType::BigInt),
         ColumnField::new("prod".parse().unwrap(), ColumnType::Int128),
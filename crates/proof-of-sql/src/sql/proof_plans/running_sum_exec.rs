@@ -0,0 +1,584 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedColumn, OwnedTable,
+        },
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_gadgets::{
+            count_membership_check, prover_evaluate_membership_check, prover_evaluate_row_index,
+            shift, verifier_evaluate_membership_check,
+        },
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use core::{cmp::min, iter::repeat_with};
+use num_traits::One;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     SELECT <input_column1>, ..., <input_columnM>,
+///         SUM(<sum_column1>) OVER (PARTITION BY <partition_by_column1>, ... ORDER BY <order_by_column1>, ...) AS <alias1>,
+///         ...
+///     FROM <input>
+/// ```
+///
+/// The input has to be sorted by the partition keys and then by the order keys, e.g. by a [`SortExec`](super::SortExec).
+/// Partitions are the runs of rows with equal partition keys, and rows of a partition with equal
+/// order keys are peers. The running sum of a row is the sum over its partition up to the row and its peers.
+/// The result has the columns of the input followed by the running sums.
+///
+/// The proof works in the following steps, where `shift` shifts a column down by one row:
+/// 1. The keys, the partition sums `p` and the running sums `s` are committed to in the first round,
+///    together with their shifts, which are shown to be shifts with a membership check as in
+///    [`prover_evaluate_sorted`](crate::sql::proof_gadgets::prover_evaluate_sorted).
+/// 2. The indicators `e_j` of whether the first `j` keys are equal to the previous row are shown as in
+///    [`prover_evaluate_sorted`](crate::sql::proof_gadgets::prover_evaluate_sorted), so that `e_P` with `P`
+///    partition keys marks rows in the same partition as the previous row and `e_K` with `K` keys
+///    marks peers of the previous row.
+/// 3. With `shifted_chi` being one exactly for `0 < i < n` and `last` being one exactly for `i = n - 1`,
+///    the sums of the column `x` are shown with
+///    ```text
+///        p - e_P * shift(p) - x = 0
+///        shifted_chi * (shift(s) - shift(p)) - e_K * (s - shift(p)) = 0
+///        last * (s - p) = 0
+///    ```
+///
+/// Note: the keys and the summed columns are indexes of the input columns, and the summed columns
+/// have to be numeric.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunningSumExec<C: Commitment> {
+    pub(super) input: Box<DynProofPlan<C>>,
+    pub(super) partition_by: Vec<usize>,
+    pub(super) order_by: Vec<usize>,
+    pub(super) sums: Vec<(usize, Identifier)>,
+}
+
+/// The witness of the running sums of the rows of the input
+struct RunningSumWitness<'a, S: Scalar> {
+    /// The keys as scalars
+    keys: Vec<&'a [S]>,
+    /// The sums of each summed column over the partition up to each row
+    partition_sums: Vec<&'a [S]>,
+    /// The running sums of each summed column
+    running_sums: Vec<&'a [S]>,
+}
+
+impl<C: Commitment> RunningSumExec<C> {
+    /// Creates a new running sum expression.
+    pub fn new(
+        input: Box<DynProofPlan<C>>,
+        partition_by: Vec<usize>,
+        order_by: Vec<usize>,
+        sums: Vec<(usize, Identifier)>,
+    ) -> Self {
+        Self {
+            input,
+            partition_by,
+            order_by,
+            sums,
+        }
+    }
+
+    /// The indexes of the keys, starting with the partition keys
+    fn key_indexes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.partition_by.iter().chain(&self.order_by).copied()
+    }
+
+    /// Compute the running sums of the sorted input columns
+    fn witness<'a>(
+        &self,
+        alloc: &'a Bump,
+        columns: &[Column<'a, C::Scalar>],
+    ) -> RunningSumWitness<'a, C::Scalar> {
+        let n = columns.first().map_or(0, Column::len);
+        let keys: Vec<_> = self
+            .key_indexes()
+            .map(|index| columns[index].as_scalar(alloc))
+            .collect();
+        let is_equal_to_previous =
+            |i: usize, keys: &[&[C::Scalar]]| i > 0 && keys.iter().all(|key| key[i] == key[i - 1]);
+        let (partition_keys, _) = keys.split_at(self.partition_by.len());
+        let (partition_sums, running_sums) = self
+            .sums
+            .iter()
+            .map(|&(index, _)| {
+                let values = columns[index].as_scalar(alloc);
+                let partition_sums: &mut [_] = alloc.alloc_slice_copy(values);
+                for i in 1..n {
+                    if is_equal_to_previous(i, partition_keys) {
+                        let previous = partition_sums[i - 1];
+                        partition_sums[i] += previous;
+                    }
+                }
+                // The running sum of a row is the partition sum of its last peer
+                let running_sums: &mut [_] = alloc.alloc_slice_copy(partition_sums);
+                for i in (1..n).rev() {
+                    if is_equal_to_previous(i, &keys) {
+                        running_sums[i - 1] = running_sums[i];
+                    }
+                }
+                (&*partition_sums, &*running_sums)
+            })
+            .unzip();
+        RunningSumWitness {
+            keys,
+            partition_sums,
+            running_sums,
+        }
+    }
+
+    /// Convert the running sums into columns of the types of the summed columns
+    ///
+    /// # Panics
+    ///
+    /// Panics if a running sum doesn't fit into the type of its summed column.
+    fn sum_columns<'a>(
+        &self,
+        alloc: &'a Bump,
+        running_sums: &[&'a [C::Scalar]],
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let fields = self.input.get_column_result_fields();
+        self.sums
+            .iter()
+            .zip(running_sums)
+            .map(|(&(index, _), running_sum)| {
+                let owned_column =
+                    OwnedColumn::try_from_scalars(running_sum, fields[index].data_type())
+                        .expect("running sums should fit into the type of their column");
+                Column::from_owned_column(alloc.alloc(owned_column), alloc)
+            })
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for RunningSumExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        self.input.count(builder, accessor)?;
+        let fields = self.input.get_column_result_fields();
+        let num_keys = self.partition_by.len() + self.order_by.len();
+        if self.key_indexes().any(|index| index >= fields.len())
+            || self.sums.iter().any(|&(index, _)| {
+                fields
+                    .get(index)
+                    .map_or(true, |field| !field.data_type().is_numeric())
+            })
+        {
+            return Err(ProofError::VerificationError {
+                error: "invalid running sums",
+            });
+        }
+        builder.count_first_round_mles(2 * num_keys + 4 * self.sums.len());
+        // The shifts are shifts
+        count_membership_check(builder);
+        // The indicators of equal keys
+        builder.count_intermediate_mles(3 * num_keys);
+        builder.count_subpolynomials(3 * num_keys);
+        // The sums
+        builder.count_subpolynomials(3 * self.sums.len());
+        builder.count_degree(3);
+        builder.count_post_result_challenges(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.input.get_length(accessor)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.input.get_offset(accessor)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        _result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let num_keys = self.partition_by.len() + self.order_by.len();
+        let output_length = builder.mle_evaluations.output_length;
+
+        // 1. input
+        let input_evals = self.input.verifier_evaluate(builder, accessor, None)?;
+
+        // 2. first round witness
+        let key_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(num_keys)
+            .collect();
+        let shifted_key_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(num_keys)
+            .collect();
+        let sum_evals: Vec<_> = repeat_with(|| {
+            [
+                builder.consume_first_round_mle(),
+                builder.consume_first_round_mle(),
+                builder.consume_first_round_mle(),
+                builder.consume_first_round_mle(),
+            ]
+        })
+        .take(self.sums.len())
+        .collect();
+        if self
+            .key_indexes()
+            .zip(&key_evals)
+            .any(|(index, key_eval)| input_evals[index] != *key_eval)
+        {
+            return Err(ProofError::VerificationError {
+                error: "committed keys of running sums do not match their input",
+            });
+        }
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 3. the shifts are shifts
+        let rho_eval = builder.mle_evaluations.rho_evaluation();
+        let one_eval = builder.mle_evaluations.chi_evaluation(output_length);
+        let shifted_one_eval = one_eval
+            - builder
+                .mle_evaluations
+                .chi_evaluation(min(1, output_length));
+        let candidate_one_eval = builder
+            .mle_evaluations
+            .chi_evaluation(output_length.saturating_sub(1));
+        let last_eval = one_eval - candidate_one_eval;
+        let candidate_evals: Vec<_> = [rho_eval]
+            .into_iter()
+            .chain(key_evals.iter().copied())
+            .chain(sum_evals.iter().flat_map(|&[p, _, s, _]| [p, s]))
+            .collect();
+        let table_evals: Vec<_> = [rho_eval - C::Scalar::one()]
+            .into_iter()
+            .chain(shifted_key_evals.iter().copied())
+            .chain(
+                sum_evals
+                    .iter()
+                    .flat_map(|&[_, shifted_p, _, shifted_s]| [shifted_p, shifted_s]),
+            )
+            .collect();
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            candidate_one_eval,
+            &candidate_evals,
+            one_eval,
+            &table_evals,
+            shifted_one_eval,
+        );
+
+        // 4. the indicators of equal keys
+        let mut prefix_equal_evals = vec![shifted_one_eval];
+        for (&key_eval, &shifted_key_eval) in key_evals.iter().zip(&shifted_key_evals) {
+            let prefix_equal_eval = prefix_equal_evals[prefix_equal_evals.len() - 1];
+            let diff_eval = builder.consume_intermediate_mle();
+            let diff_inverse_eval = builder.consume_intermediate_mle();
+            let next_prefix_equal_eval = builder.consume_intermediate_mle();
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                diff_eval - prefix_equal_eval * (key_eval - shifted_key_eval),
+            );
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                next_prefix_equal_eval - prefix_equal_eval + diff_eval * diff_inverse_eval,
+            );
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                next_prefix_equal_eval * (key_eval - shifted_key_eval),
+            );
+            prefix_equal_evals.push(next_prefix_equal_eval);
+        }
+        let partition_eval = prefix_equal_evals[self.partition_by.len()];
+        let peer_eval = prefix_equal_evals[num_keys];
+
+        // 5. the sums
+        for (&(index, _), &[p_eval, shifted_p_eval, s_eval, shifted_s_eval]) in
+            self.sums.iter().zip(&sum_evals)
+        {
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                p_eval - partition_eval * shifted_p_eval - input_evals[index],
+            );
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                shifted_one_eval * (shifted_s_eval - shifted_p_eval)
+                    - peer_eval * (s_eval - shifted_p_eval),
+            );
+            builder.produce_sumcheck_subpolynomial_evaluation(
+                &SumcheckSubpolynomialType::Identity,
+                last_eval * (s_eval - p_eval),
+            );
+        }
+
+        Ok(input_evals
+            .into_iter()
+            .chain(sum_evals.iter().map(|&[_, _, s_eval, _]| s_eval))
+            .collect())
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        let input_fields = self.input.get_column_result_fields();
+        let sum_fields = self
+            .sums
+            .iter()
+            .map(|&(index, alias)| ColumnField::new(alias, input_fields[index].data_type()))
+            .collect::<Vec<_>>();
+        input_fields.into_iter().chain(sum_fields).collect()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for RunningSumExec<C> {
+    #[tracing::instrument(name = "RunningSumExec::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        input_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let input = self.input.result_evaluate(input_length, alloc, accessor);
+        let witness = self.witness(alloc, &input);
+        let sum_columns = self.sum_columns(alloc, &witness.running_sums);
+        input.into_iter().chain(sum_columns).collect()
+    }
+
+    #[tracing::instrument(
+        name = "RunningSumExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.input.first_round_evaluate(builder, alloc, accessor);
+        let input_length = self.input.get_length(&accessor);
+        let input = self.input.result_evaluate(input_length, alloc, accessor);
+        let witness = self.witness(alloc, &input);
+        for index in self.key_indexes() {
+            builder.produce_intermediate_mle(input[index]);
+        }
+        for &key in &witness.keys {
+            builder.produce_intermediate_mle(shift(alloc, key));
+        }
+        for (&partition_sum, &running_sum) in
+            witness.partition_sums.iter().zip(&witness.running_sums)
+        {
+            builder.produce_intermediate_mle(partition_sum);
+            builder.produce_intermediate_mle(shift(alloc, partition_sum));
+            builder.produce_intermediate_mle(running_sum);
+            builder.produce_intermediate_mle(shift(alloc, running_sum));
+        }
+        builder.request_post_result_challenges(2);
+    }
+
+    #[tracing::instrument(
+        name = "RunningSumExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        // 1. input
+        let input = self.input.final_round_evaluate(builder, alloc, accessor);
+        let n = input.first().map_or(0, Column::len);
+        let witness = self.witness(alloc, &input);
+        let shifted_keys: Vec<_> = witness.keys.iter().map(|key| shift(alloc, key)).collect();
+        let shifted_partition_sums: Vec<_> = witness
+            .partition_sums
+            .iter()
+            .map(|partition_sum| shift(alloc, partition_sum))
+            .collect();
+        let shifted_running_sums: Vec<_> = witness
+            .running_sums
+            .iter()
+            .map(|running_sum| shift(alloc, running_sum))
+            .collect();
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 2. the shifts are shifts
+        let rho = prover_evaluate_row_index(builder, alloc);
+        let rho_minus_one: &[_] =
+            alloc.alloc_slice_fill_with(rho.len(), |i| rho[i] - C::Scalar::one());
+        let shifted_chi = alloc.alloc_slice_fill_copy(n, true);
+        shifted_chi[..min(1, n)].fill(false);
+        let shifted_chi: &[_] = shifted_chi;
+        let candidates: Vec<_> = [Column::Scalar(rho)]
+            .into_iter()
+            .chain(witness.keys.iter().map(|&key| Column::Scalar(key)))
+            .chain(
+                witness
+                    .partition_sums
+                    .iter()
+                    .zip(&witness.running_sums)
+                    .flat_map(|(&p, &s)| [Column::Scalar(p), Column::Scalar(s)]),
+            )
+            .collect();
+        let table: Vec<_> = [Column::Scalar(rho_minus_one)]
+            .into_iter()
+            .chain(shifted_keys.iter().map(|&key| Column::Scalar(key)))
+            .chain(
+                shifted_partition_sums
+                    .iter()
+                    .zip(&shifted_running_sums)
+                    .flat_map(|(&p, &s)| [Column::Scalar(p), Column::Scalar(s)]),
+            )
+            .collect();
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            n.saturating_sub(1),
+            &candidates,
+            n,
+            &table,
+            Column::Boolean(shifted_chi),
+        );
+
+        // 3. the indicators of equal keys
+        let mut prefixes_equal = vec![shifted_chi];
+        for (&key, &shifted_key) in witness.keys.iter().zip(&shifted_keys) {
+            let prefix_equal = prefixes_equal[prefixes_equal.len() - 1];
+            let diff: &[_] = alloc.alloc_slice_fill_with(n, |i| {
+                if prefix_equal[i] {
+                    key[i] - shifted_key[i]
+                } else {
+                    C::Scalar::ZERO
+                }
+            });
+            let diff_inverse = alloc.alloc_slice_copy(diff);
+            slice_ops::batch_inversion(diff_inverse);
+            let next_prefix_equal: &[_] =
+                alloc.alloc_slice_fill_with(n, |i| prefix_equal[i] && diff[i] == C::Scalar::ZERO);
+            builder.produce_intermediate_mle(diff);
+            builder.produce_intermediate_mle(diff_inverse as &[_]);
+            builder.produce_intermediate_mle(next_prefix_equal);
+
+            // diff - prefix_equal * (key - shifted_key) = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(diff)]),
+                    (
+                        -C::Scalar::one(),
+                        vec![Box::new(prefix_equal), Box::new(key)],
+                    ),
+                    (
+                        C::Scalar::one(),
+                        vec![Box::new(prefix_equal), Box::new(shifted_key)],
+                    ),
+                ],
+            );
+            // next_prefix_equal - prefix_equal + diff * diff_inverse = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(next_prefix_equal)]),
+                    (-C::Scalar::one(), vec![Box::new(prefix_equal)]),
+                    (
+                        C::Scalar::one(),
+                        vec![Box::new(diff), Box::new(diff_inverse as &[_])],
+                    ),
+                ],
+            );
+            // next_prefix_equal * (key - shifted_key) = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (
+                        C::Scalar::one(),
+                        vec![Box::new(next_prefix_equal), Box::new(key)],
+                    ),
+                    (
+                        -C::Scalar::one(),
+                        vec![Box::new(next_prefix_equal), Box::new(shifted_key)],
+                    ),
+                ],
+            );
+            prefixes_equal.push(next_prefix_equal);
+        }
+        let partition_equal = prefixes_equal[self.partition_by.len()];
+        let peer = prefixes_equal[witness.keys.len()];
+
+        // 4. the sums
+        let last: &[_] = alloc.alloc_slice_fill_with(n, |i| i + 1 == n);
+        for (((&(index, _), &p), &s), (&shifted_p, &shifted_s)) in self
+            .sums
+            .iter()
+            .zip(&witness.partition_sums)
+            .zip(&witness.running_sums)
+            .zip(shifted_partition_sums.iter().zip(&shifted_running_sums))
+        {
+            let x = input[index].as_scalar(alloc);
+            // p - partition_equal * shifted_p - x = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(p)]),
+                    (
+                        -C::Scalar::one(),
+                        vec![Box::new(partition_equal), Box::new(shifted_p)],
+                    ),
+                    (-C::Scalar::one(), vec![Box::new(x)]),
+                ],
+            );
+            // shifted_chi * (shifted_s - shifted_p) - peer * (s - shifted_p) = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (
+                        C::Scalar::one(),
+                        vec![Box::new(shifted_chi), Box::new(shifted_s)],
+                    ),
+                    (
+                        -C::Scalar::one(),
+                        vec![Box::new(shifted_chi), Box::new(shifted_p)],
+                    ),
+                    (-C::Scalar::one(), vec![Box::new(peer), Box::new(s)]),
+                    (C::Scalar::one(), vec![Box::new(peer), Box::new(shifted_p)]),
+                ],
+            );
+            // last * (s - p) = 0
+            builder.produce_sumcheck_subpolynomial(
+                SumcheckSubpolynomialType::Identity,
+                vec![
+                    (C::Scalar::one(), vec![Box::new(last), Box::new(s)]),
+                    (-C::Scalar::one(), vec![Box::new(last), Box::new(p)]),
+                ],
+            );
+        }
+
+        let sum_columns = self.sum_columns(alloc, &witness.running_sums);
+        input.into_iter().chain(sum_columns).collect()
+    }
+}
@@ -0,0 +1,194 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TestAccessor,
+        },
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, CountBuilder, ProofPlan, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection::{Asc, Desc};
+
+fn make_accessor(
+    data: OwnedTable<Curve25519Scalar>,
+) -> (OwnedTableTestAccessor<'static, InnerProductProof>, TableRef) {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    (accessor, t)
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_column_references() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [1_i64, 2]),
+        varchar("b", ["x", "y"]),
+        int128("c", [3_i128, 4]),
+    ]));
+    let ast = running_sum::<RistrettoPoint>(
+        projection(cols_expr_plan(t, &["a", "b", "c"], &accessor), tab(t)),
+        vec![1],
+        vec![0],
+        &[(2, "s")],
+    );
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![
+            ColumnField::new("a".parse().unwrap(), ColumnType::BigInt),
+            ColumnField::new("b".parse().unwrap(), ColumnType::VarChar),
+            ColumnField::new("c".parse().unwrap(), ColumnType::Int128),
+            ColumnField::new("s".parse().unwrap(), ColumnType::Int128),
+        ]
+    );
+    let expected_refs = IndexSet::from_iter([
+        col_ref(t, "a", &accessor),
+        col_ref(t, "b", &accessor),
+        col_ref(t, "c", &accessor),
+    ]);
+    assert_eq!(ast.get_column_references(), expected_refs);
+    assert_eq!(ast.get_length(&accessor), 2);
+}
+
+#[test]
+fn we_cannot_count_running_sums_of_invalid_columns() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [1_i64, 2]),
+        varchar("b", ["x", "y"]),
+    ]));
+    for (partition_by, sums) in [
+        (vec![], [(1, "s")]),
+        (vec![2], [(0, "s")]),
+        (vec![0], [(3, "s")]),
+    ] {
+        let ast = running_sum::<RistrettoPoint>(
+            projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+            partition_by,
+            vec![],
+            &sums,
+        );
+        assert!(matches!(
+            ast.count(&mut CountBuilder::new(&[]), &accessor),
+            Err(ProofError::VerificationError { .. })
+        ));
+    }
+}
+
+#[test]
+fn we_can_prove_running_sums_over_partitions_of_a_sorted_filter() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [1_i64, 2, 1, 1, 2, 3]),
+        bigint("b", [3_i64, 1, 2, 3, 1, 5]),
+        bigint("c", [10_i64, 20, 30, 40, 50, 60]),
+        decimal75("d", 10, 2, [-150_i64, 20, 20, -150, 300, 0]),
+        boolean("e", [true, true, true, true, true, false]),
+    ]));
+    let ast = running_sum(
+        sort(
+            filter(
+                cols_expr_plan(t, &["a", "b", "c", "d"], &accessor),
+                tab(t),
+                column(t, "e", &accessor),
+            ),
+            vec![(0, Asc), (1, Asc)],
+        ),
+        vec![0],
+        vec![1],
+        &[(2, "sum_c"), (3, "sum_d")],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    // Rows with equal keys are peers, so they have the same running sum
+    let expected_res = owned_table([
+        bigint("a", [1_i64, 1, 1, 2, 2]),
+        bigint("b", [2_i64, 3, 3, 1, 1]),
+        bigint("c", [30_i64, 10, 40, 20, 50]),
+        decimal75("d", 10, 2, [20_i64, -150, -150, 20, 300]),
+        bigint("sum_c", [30_i64, 80, 80, 70, 70]),
+        decimal75("sum_d", 10, 2, [20_i64, -280, -280, 320, 320]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_running_sums_in_descending_order_without_partitions() {
+    let (accessor, t) = make_accessor(owned_table([
+        int("b", [1_i32, 3, 2, 3]),
+        bigint("c", [15_i64, -5, 10, 20]),
+    ]));
+    let ast = running_sum(
+        sort(
+            projection(cols_expr_plan(t, &["b", "c"], &accessor), tab(t)),
+            vec![(0, Desc)],
+        ),
+        vec![],
+        vec![0],
+        &[(1, "s")],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        int("b", [3_i32, 3, 2, 1]),
+        bigint("c", [-5_i64, 20, 10, 15]),
+        bigint("s", [15_i64, 15, 25, 40]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_sum_over_the_whole_input() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [5_i64, -2, 7]),
+        bigint("c", [1_i64, 0, 1]),
+    ]));
+    let ast = running_sum(
+        filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            equal(column(t, "c", &accessor), const_bigint(1)),
+        ),
+        vec![],
+        vec![],
+        &[(0, "total")],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [5_i64, 7]), bigint("total", [12_i64, 12])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_running_sums_of_an_empty_input() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [5_i64, -2, 7]),
+        bigint("c", [1_i64, 1, 1]),
+    ]));
+    let ast = running_sum(
+        filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            equal(column(t, "c", &accessor), const_bigint(0)),
+        ),
+        vec![0],
+        vec![],
+        &[(0, "s")],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(
+        res,
+        owned_table([bigint("a", [0_i64; 0]), bigint("s", [0_i64; 0])])
+    );
+}
@@ -0,0 +1,357 @@
+use super::{
+    is_supported_subquery,
+    subquery_util::{
+        evaluate_subquery, final_round_evaluate_subquery, verifier_evaluate_subquery,
+        DerivedTableAccessor,
+    },
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            filter_util::filter_column_by_index, presence_column_id, Column, ColumnField,
+            ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedColumn,
+            OwnedTable, TableRef,
+        },
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            VerificationBuilder,
+        },
+        proof_gadgets::{
+            count_membership_check, prover_evaluate_membership_check,
+            verifier_evaluate_membership_check,
+        },
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use bumpalo::Bump;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     <plan> WHERE ... (<subquery>) ...
+/// ```
+/// e.g. `SELECT a FROM t WHERE b > (SELECT MAX(b) FROM u)`,
+/// where `plan` reads the value of `subquery` as the column `alias` of `table_ref`.
+///
+/// The subquery has to return at most one row and its length is sent along with the proof.
+/// The value of the subquery is broadcast to every row of the table. If the subquery has no
+/// rows, its value is null, so that the column has a presence column that is false everywhere.
+/// Both columns are committed to in the first round and `plan` reads these commitments.
+///
+/// Note: the subquery must return a single column, which is not a string, and must either be a
+/// `GroupByExec` or not contain one. Its table needs to have the same offset as `table_ref`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScalarSubqueryExec<C: Commitment> {
+    pub(super) subquery: Box<DynProofPlan<C>>,
+    pub(super) table_ref: TableRef,
+    pub(super) alias: Identifier,
+    pub(super) plan: Box<DynProofPlan<C>>,
+}
+
+impl<C: Commitment> ScalarSubqueryExec<C> {
+    /// Creates a new scalar subquery expression.
+    pub fn new(
+        subquery: Box<DynProofPlan<C>>,
+        table_ref: TableRef,
+        alias: Identifier,
+        plan: Box<DynProofPlan<C>>,
+    ) -> Self {
+        Self {
+            subquery,
+            table_ref,
+            alias,
+            plan,
+        }
+    }
+
+    /// The identifier of the presence column of `alias`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alias` is too long to have a presence column, which is checked when the plan is built.
+    fn presence_id(&self) -> Identifier {
+        presence_column_id(self.alias).expect("the alias of a scalar subquery should be short")
+    }
+
+    /// The value of the subquery for each of the `table_length` rows of the table,
+    /// along with whether it is not null
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subquery has more than one row or its column is a string column.
+    fn broadcast<'a>(
+        alloc: &'a Bump,
+        value: Column<'a, C::Scalar>,
+        table_length: usize,
+    ) -> (Column<'a, C::Scalar>, Column<'a, C::Scalar>) {
+        assert!(
+            value.len() <= 1,
+            "a scalar subquery must return at most one row"
+        );
+        let is_present = value.len() == 1;
+        let values = if is_present {
+            filter_column_by_index(alloc, &value, &vec![0; table_length])
+        } else {
+            let nulls = OwnedColumn::try_from_scalars(
+                &vec![C::Scalar::ZERO; table_length],
+                value.column_type(),
+            )
+            .expect("the value of a scalar subquery should not be a string");
+            Column::from_owned_column(alloc.alloc(nulls), alloc)
+        };
+        let presence = Column::Boolean(alloc.alloc_slice_fill_copy(table_length, is_present));
+        (values, presence)
+    }
+
+    /// The accessor of `plan`, where the table has the column `alias` and its presence column
+    fn scalar_subquery_accessor<'a>(
+        &self,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+        values: Column<'a, C::Scalar>,
+        presence: Column<'a, C::Scalar>,
+    ) -> &'a dyn DataAccessor<C::Scalar> {
+        alloc.alloc(DerivedTableAccessor {
+            accessor,
+            table_ref: self.table_ref,
+            length: accessor.get_length(self.table_ref),
+            offset: accessor.get_offset(self.table_ref),
+            columns: alloc
+                .alloc_slice_copy(&[(self.alias, values), (self.presence_id(), presence)]),
+        })
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for ScalarSubqueryExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        builder.count_one_evaluation_lengths(1);
+        self.subquery.count(builder, accessor)?;
+        let fields = self.subquery.get_column_result_fields();
+        if !is_supported_subquery(&self.subquery)
+            || fields.len() != 1
+            || fields[0].data_type() == ColumnType::VarChar
+            || presence_column_id(self.alias).is_none()
+        {
+            return Err(ProofError::VerificationError {
+                error: "invalid scalar subquery",
+            });
+        }
+        if self.subquery.get_offset(accessor) != accessor.get_offset(self.table_ref) {
+            return Err(ProofError::VerificationError {
+                error: "a scalar subquery must have the offset of the table",
+            });
+        }
+        // The values and their presence
+        builder.count_first_round_mles(2);
+        // The values are the value of the subquery
+        count_membership_check(builder);
+        builder.count_post_result_challenges(2);
+        self.plan.count(builder, accessor)
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.plan
+            .get_length(accessor)
+            .max(self.subquery.get_length(accessor))
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.plan.get_offset(accessor)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let table_length = accessor.get_length(self.table_ref);
+        let subquery_length = builder.consume_one_evaluation_length();
+        if subquery_length > 1 {
+            return Err(ProofError::VerificationError {
+                error: "a scalar subquery must return at most one row",
+            });
+        }
+
+        // 1. subquery
+        let subquery_evals =
+            verifier_evaluate_subquery(&self.subquery, builder, accessor, subquery_length)?;
+
+        // 2. first round witness
+        let (values_eval, values_commitment) = builder.consume_first_round_mle_and_commitment();
+        let (presence_eval, presence_commitment) = builder.consume_first_round_mle_and_commitment();
+
+        // 3. the values are present exactly if the subquery has a row
+        let present_length = table_length * subquery_length;
+        let present_one_eval = builder.mle_evaluations.chi_evaluation(present_length);
+        if presence_eval != present_one_eval {
+            return Err(ProofError::VerificationError {
+                error: "the presence of a scalar subquery does not match its length",
+            });
+        }
+
+        // 4. every present value is the value of the subquery
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let subquery_one_eval = builder.mle_evaluations.chi_evaluation(subquery_length);
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            present_one_eval,
+            &[values_eval],
+            subquery_one_eval,
+            &subquery_evals,
+            C::Scalar::from([table_length as u64, 0, 0, 0]) * subquery_one_eval,
+        );
+
+        // 5. plan, evaluated on the table with the value of the subquery
+        let columns = [
+            (self.alias, values_commitment),
+            (self.presence_id(), presence_commitment),
+        ];
+        let scalar_subquery_accessor = DerivedTableAccessor {
+            accessor,
+            table_ref: self.table_ref,
+            length: table_length,
+            offset: accessor.get_offset(self.table_ref),
+            columns: &columns,
+        };
+        let table_length_of_parent = builder.mle_evaluations.replace_input_length(table_length);
+        let evals = self
+            .plan
+            .verifier_evaluate(builder, &scalar_subquery_accessor, result);
+        builder
+            .mle_evaluations
+            .replace_input_length(table_length_of_parent);
+        evals
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.plan.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let presence_id = self.presence_id();
+        let mut columns = self.subquery.get_column_references();
+        columns.extend(
+            self.plan
+                .get_column_references()
+                .into_iter()
+                .filter(|column| {
+                    column.table_ref() != self.table_ref
+                        || (column.column_id() != self.alias && column.column_id() != presence_id)
+                }),
+        );
+        columns
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for ScalarSubqueryExec<C> {
+    #[tracing::instrument(
+        name = "ScalarSubqueryExec::result_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn result_evaluate<'a>(
+        &self,
+        _input_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let subquery_columns = evaluate_subquery(&self.subquery, alloc, accessor);
+        let table_length = accessor.get_length(self.table_ref);
+        let (values, presence) = Self::broadcast(alloc, subquery_columns[0], table_length);
+        let scalar_subquery_accessor =
+            self.scalar_subquery_accessor(alloc, accessor, values, presence);
+        self.plan
+            .result_evaluate(table_length, alloc, scalar_subquery_accessor)
+    }
+
+    #[tracing::instrument(
+        name = "ScalarSubqueryExec::first_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // The verifier needs the length of the subquery result before it verifies the subquery.
+        let subquery_columns = evaluate_subquery(&self.subquery, alloc, accessor);
+        builder.produce_one_evaluation_length(subquery_columns[0].len());
+        self.subquery.first_round_evaluate(builder, alloc, accessor);
+        let table_length = accessor.get_length(self.table_ref);
+        let (values, presence) = Self::broadcast(alloc, subquery_columns[0], table_length);
+        builder.produce_intermediate_mle(values);
+        builder.produce_intermediate_mle(presence);
+        builder.request_post_result_challenges(2);
+        let scalar_subquery_accessor =
+            self.scalar_subquery_accessor(alloc, accessor, values, presence);
+        self.plan
+            .first_round_evaluate(builder, alloc, scalar_subquery_accessor);
+    }
+
+    #[tracing::instrument(
+        name = "ScalarSubqueryExec::final_round_evaluate",
+        level = "debug",
+        skip_all
+    )]
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        // 1. subquery, evaluated on its own table
+        let subquery_columns =
+            final_round_evaluate_subquery(&self.subquery, builder, alloc, accessor);
+        let value = subquery_columns[0];
+        let table_length = accessor.get_length(self.table_ref);
+        let (values, presence) = Self::broadcast(alloc, value, table_length);
+
+        // 2. every present value is the value of the subquery
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+        let multiplicities = Column::BigInt(alloc.alloc_slice_fill_copy(
+            value.len(),
+            i64::try_from(table_length).expect("table length should fit into an i64"),
+        ));
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            table_length * value.len(),
+            &[values],
+            value.len(),
+            &[value],
+            multiplicities,
+        );
+
+        // 3. plan, evaluated on the table with the value of the subquery
+        let table_length_of_parent = builder.table_length();
+        builder.set_table_length(table_length);
+        let scalar_subquery_accessor =
+            self.scalar_subquery_accessor(alloc, accessor, values, presence);
+        let output = self
+            .plan
+            .final_round_evaluate(builder, alloc, scalar_subquery_accessor);
+        builder.set_table_length(table_length_of_parent);
+        output
+    }
+}
@@ -0,0 +1,205 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, presence_column_id, ColumnField, ColumnRef, ColumnType,
+            OwnedTable, OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+        map::IndexSet,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, ColumnExpr, DynProofExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::intermediate_ast::AggregationOperator;
+
+fn make_accessor(
+    data: OwnedTable<Curve25519Scalar>,
+    other: OwnedTable<Curve25519Scalar>,
+) -> (
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+    TableRef,
+    TableRef,
+) {
+    let t = "sxt.t".parse().unwrap();
+    let u = "sxt.u".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    accessor.add_table(u, other, 0);
+    (accessor, t, u)
+}
+
+/// The hidden column that holds the value of the subquery
+fn value(t: TableRef) -> DynProofExpr<RistrettoPoint> {
+    DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+        t,
+        "__col_subquery_0".parse().unwrap(),
+        ColumnType::BigInt,
+    )))
+}
+
+/// Whether the value of the subquery is not null
+fn is_present(t: TableRef) -> DynProofExpr<RistrettoPoint> {
+    DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+        t,
+        presence_column_id("__col_subquery_0".parse().unwrap()).unwrap(),
+        ColumnType::Boolean,
+    )))
+}
+
+/// `b >= <subquery>`, which is false if the subquery is null
+fn b_gte_value(
+    t: TableRef,
+    accessor: &OwnedTableTestAccessor<'static, InnerProductProof>,
+) -> DynProofExpr<RistrettoPoint> {
+    and(gte(column(t, "b", accessor), value(t)), is_present(t))
+}
+
+fn sample_tables() -> (OwnedTable<Curve25519Scalar>, OwnedTable<Curve25519Scalar>) {
+    (
+        owned_table([
+            bigint("a", [3_i64, 1, 2, 3, 5, 4]),
+            bigint("b", [10_i64, 20, 30, 40, 50, 60]),
+        ]),
+        owned_table([bigint("k", [5_i64, 3, 7]), bigint("v", [15_i64, 45, 30])]),
+    )
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_column_references() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = scalar_subquery(
+        filter(
+            cols_expr_plan(u, &["v"], &accessor),
+            tab(u),
+            equal(column(u, "k", &accessor), const_bigint(3)),
+        ),
+        t,
+        "__col_subquery_0",
+        filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            b_gte_value(t, &accessor),
+        ),
+    );
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![ColumnField::new("a".parse().unwrap(), ColumnType::BigInt)]
+    );
+    // The hidden columns are not columns of the accessor
+    let expected_refs = IndexSet::from_iter([
+        col_ref(u, "v", &accessor),
+        col_ref(u, "k", &accessor),
+        col_ref(t, "a", &accessor),
+        col_ref(t, "b", &accessor),
+    ]);
+    assert_eq!(ast.get_column_references(), expected_refs);
+    assert_eq!(ast.get_length(&accessor), 6);
+    assert_eq!(ast.get_offset(&accessor), 0);
+}
+
+#[test]
+fn we_can_prove_a_filter_on_the_value_of_a_subquery() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = scalar_subquery(
+        filter(
+            cols_expr_plan(u, &["v"], &accessor),
+            tab(u),
+            equal(column(u, "k", &accessor), const_bigint(3)),
+        ),
+        t,
+        "__col_subquery_0",
+        filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            b_gte_value(t, &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [5_i64, 4]), bigint("b", [50_i64, 60])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_filter_on_the_value_of_an_aggregation() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = scalar_subquery(
+        group_by_with_min_max(
+            vec![],
+            vec![],
+            vec![(
+                AggregationOperator::Max,
+                aliased_plan(column(u, "v", &accessor), "max_v"),
+            )],
+            None,
+            tab(u),
+            const_bool(true),
+        ),
+        t,
+        "__col_subquery_0",
+        group_by(
+            vec![],
+            vec![sum_expr(column(t, "b", &accessor), "total")],
+            "n",
+            tab(t),
+            b_gte_value(t, &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("total", [110_i64]), bigint("n", [2_i64])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_filter_on_the_value_of_an_empty_subquery_as_null() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = scalar_subquery(
+        filter(
+            cols_expr_plan(u, &["v"], &accessor),
+            tab(u),
+            equal(column(u, "k", &accessor), const_bigint(4)),
+        ),
+        t,
+        "__col_subquery_0",
+        filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            b_gte_value(t, &accessor),
+        ),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+#[should_panic(expected = "a scalar subquery must return at most one row")]
+fn we_cannot_prove_a_subquery_with_more_than_one_row() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = scalar_subquery(
+        projection(cols_expr_plan(u, &["v"], &accessor), tab(u)),
+        t,
+        "__col_subquery_0",
+        filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            b_gte_value(t, &accessor),
+        ),
+    );
+    VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+}
@@ -0,0 +1,504 @@
+use super::{
+    is_supported_subquery,
+    subquery_util::{
+        evaluate_subquery, final_round_evaluate_subquery, verifier_evaluate_subquery,
+        DerivedTableAccessor,
+    },
+    DynProofPlan,
+};
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            join_util::join_key_values, Column, ColumnField, ColumnRef, ColumnType,
+            CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedTable, TableRef,
+        },
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Scalar,
+        slice_ops,
+    },
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            SumcheckSubpolynomialType, VerificationBuilder,
+        },
+        proof_exprs::{ColumnExpr, ProofExpr},
+        proof_gadgets::{
+            count_membership_check, count_strictly_increasing, prover_evaluate_membership_check,
+            prover_evaluate_strictly_increasing, shift, verifier_evaluate_membership_check,
+            verifier_evaluate_strictly_increasing,
+        },
+    },
+};
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+use bumpalo::Bump;
+use proof_of_sql_parser::Identifier;
+use serde::{Deserialize, Serialize};
+
+/// The number of MLEs that a semi-join commits to in the first round
+const NUM_FIRST_ROUND_MLES: usize = 6;
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     <plan> WHERE ... <key> IN (<subquery>) ...
+/// ```
+/// e.g. `SELECT a FROM t WHERE b IN (SELECT c FROM u WHERE d > 1)`,
+/// where `plan` reads whether the key of each row is a result of `subquery` as the boolean
+/// column `alias` of the table of `key`.
+///
+/// The subquery is evaluated on its own table and its length is sent along with the proof.
+/// The column `alias` is committed to in the first round and `plan` reads this commitment.
+/// The proof shows that a row of the column is true exactly if its key is a result of the
+/// subquery, using the distinct keys of both the table and the subquery result.
+///
+/// Note: the subquery must return a single column and must either be a `GroupByExec` or not
+/// contain one. Its table needs to have the same offset as the table of `key`, and the keys
+/// need to be integers or timestamps of the same type.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SemiJoinExec<C: Commitment> {
+    pub(super) subquery: Box<DynProofPlan<C>>,
+    pub(super) key: ColumnExpr<C>,
+    pub(super) alias: Identifier,
+    pub(super) plan: Box<DynProofPlan<C>>,
+}
+
+impl<C: Commitment> SemiJoinExec<C> {
+    /// Creates a new semi-join expression.
+    pub fn new(
+        subquery: Box<DynProofPlan<C>>,
+        key: ColumnExpr<C>,
+        alias: Identifier,
+        plan: Box<DynProofPlan<C>>,
+    ) -> Self {
+        Self {
+            subquery,
+            key,
+            alias,
+            plan,
+        }
+    }
+
+    /// The table of the key, which `plan` reads the column `alias` of
+    fn table_ref(&self) -> TableRef {
+        self.key.get_column_reference().table_ref()
+    }
+
+    /// Compute the witness of the semi-join from the keys of the table and the subquery
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys are not integers, which is checked when the plan is built.
+    fn semi_join_witness<'a>(
+        alloc: &'a Bump,
+        keys: &Column<'a, C::Scalar>,
+        subquery_keys: &Column<'a, C::Scalar>,
+    ) -> SemiJoinWitness<'a, C::Scalar> {
+        let key_values =
+            |column| join_key_values(column).expect("keys of a semi-join must be integers");
+        SemiJoinWitness::new(alloc, &key_values(keys), &key_values(subquery_keys))
+    }
+
+    /// The accessor of `plan`, where the table of the key has the column `alias`
+    fn semi_join_accessor<'a>(
+        &self,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+        is_in: Column<'a, C::Scalar>,
+    ) -> &'a dyn DataAccessor<C::Scalar> {
+        let table_ref = self.table_ref();
+        alloc.alloc(DerivedTableAccessor {
+            accessor,
+            table_ref,
+            length: accessor.get_length(table_ref),
+            offset: accessor.get_offset(table_ref),
+            columns: alloc.alloc_slice_copy(&[(self.alias, is_in)]),
+        })
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for SemiJoinExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        builder.count_one_evaluation_lengths(1);
+        self.subquery.count(builder, accessor)?;
+        if !is_supported_subquery(&self.subquery) {
+            return Err(ProofError::VerificationError {
+                error: "invalid subquery of a semi-join",
+            });
+        }
+        let key_type = self.key.data_type();
+        let fields = self.subquery.get_column_result_fields();
+        if fields.len() != 1
+            || fields[0].data_type() != key_type
+            || !(key_type.is_integer() || matches!(key_type, ColumnType::TimestampTZ(_, _)))
+        {
+            return Err(ProofError::VerificationError {
+                error: "the keys of a semi-join must be integers or timestamps of the same type",
+            });
+        }
+        if self.subquery.get_offset(accessor) != accessor.get_offset(self.table_ref()) {
+            return Err(ProofError::VerificationError {
+                error: "the subquery of a semi-join must have the offset of the table",
+            });
+        }
+        builder.count_first_round_mles(NUM_FIRST_ROUND_MLES);
+        self.key.count(builder)?;
+        // The inverses of the key counts of the subquery
+        builder.count_intermediate_mles(1);
+        builder.count_subpolynomials(2);
+        builder.count_degree(3);
+        // The keys of the table and the subquery are among the distinct keys
+        count_membership_check(builder);
+        count_membership_check(builder);
+        // The distinct keys are distinct
+        count_strictly_increasing(builder)?;
+        builder.count_post_result_challenges(2);
+        self.plan.count(builder, accessor)
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        // The distinct keys have one row for each row of the table and of the subquery result
+        self.plan.get_length(accessor) + self.subquery.get_length(accessor)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.plan.get_offset(accessor)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let table_ref = self.table_ref();
+        let table_length = accessor.get_length(table_ref);
+        let subquery_length = builder.consume_one_evaluation_length();
+
+        // 1. subquery
+        let subquery_evals =
+            verifier_evaluate_subquery(&self.subquery, builder, accessor, subquery_length)?;
+
+        // 2. first round witness
+        let (is_in_eval, is_in_commitment) = builder.consume_first_round_mle_and_commitment();
+        let distinct_keys_eval = builder.consume_first_round_mle();
+        let shifted_distinct_keys_eval = builder.consume_first_round_mle();
+        let subquery_key_counts_eval = builder.consume_first_round_mle();
+        let key_counts_eval = builder.consume_first_round_mle();
+        let is_subquery_key_eval = builder.consume_first_round_mle();
+
+        // 3. key
+        let key_eval = self.key.verifier_evaluate(builder, accessor)?;
+
+        // 4. a distinct key is a key of the subquery exactly if its count is not zero
+        let inverses_eval = builder.consume_intermediate_mle();
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            is_subquery_key_eval - subquery_key_counts_eval * inverses_eval,
+        );
+        builder.produce_sumcheck_subpolynomial_evaluation(
+            &SumcheckSubpolynomialType::Identity,
+            subquery_key_counts_eval - subquery_key_counts_eval * is_subquery_key_eval,
+        );
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        let table_one_eval = builder.mle_evaluations.chi_evaluation(table_length);
+        let subquery_one_eval = builder.mle_evaluations.chi_evaluation(subquery_length);
+        let distinct_keys_one_eval = builder
+            .mle_evaluations
+            .chi_evaluation(table_length + subquery_length);
+
+        // 5. the keys of the subquery and of the table are distinct keys,
+        // and a row is in the semi-join exactly if its distinct key is a key of the subquery
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            subquery_one_eval,
+            &subquery_evals,
+            distinct_keys_one_eval,
+            &[distinct_keys_eval],
+            subquery_key_counts_eval,
+        );
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            table_one_eval,
+            &[key_eval, is_in_eval],
+            distinct_keys_one_eval,
+            &[distinct_keys_eval, is_subquery_key_eval],
+            key_counts_eval,
+        );
+
+        // 6. the distinct keys are distinct
+        verifier_evaluate_strictly_increasing(
+            builder,
+            alpha,
+            beta,
+            table_length + subquery_length,
+            distinct_keys_eval,
+            shifted_distinct_keys_eval,
+        )?;
+
+        // 7. plan, evaluated on the table with the column of the semi-join
+        let columns = [(self.alias, is_in_commitment)];
+        let semi_join_accessor = DerivedTableAccessor {
+            accessor,
+            table_ref,
+            length: table_length,
+            offset: accessor.get_offset(table_ref),
+            columns: &columns,
+        };
+        let table_length_of_parent = builder.mle_evaluations.replace_input_length(table_length);
+        let evals = self
+            .plan
+            .verifier_evaluate(builder, &semi_join_accessor, result);
+        builder
+            .mle_evaluations
+            .replace_input_length(table_length_of_parent);
+        evals
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.plan.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        let table_ref = self.table_ref();
+        let mut columns = self.subquery.get_column_references();
+        self.key.get_column_references(&mut columns);
+        columns.extend(
+            self.plan
+                .get_column_references()
+                .into_iter()
+                .filter(|column| {
+                    column.table_ref() != table_ref || column.column_id() != self.alias
+                }),
+        );
+        columns
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for SemiJoinExec<C> {
+    #[tracing::instrument(name = "SemiJoinExec::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        _input_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let subquery_columns = evaluate_subquery(&self.subquery, alloc, accessor);
+        let keys = accessor.get_column(self.key.get_column_reference());
+        let witness = Self::semi_join_witness(alloc, &keys, &subquery_columns[0]);
+        let semi_join_accessor = self.semi_join_accessor(alloc, accessor, witness.is_in);
+        self.plan
+            .result_evaluate(keys.len(), alloc, semi_join_accessor)
+    }
+
+    #[tracing::instrument(name = "SemiJoinExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // The verifier needs the length of the subquery result before it verifies the subquery.
+        let subquery_columns = evaluate_subquery(&self.subquery, alloc, accessor);
+        builder.produce_one_evaluation_length(subquery_columns[0].len());
+        self.subquery.first_round_evaluate(builder, alloc, accessor);
+        let keys = accessor.get_column(self.key.get_column_reference());
+        let witness = Self::semi_join_witness(alloc, &keys, &subquery_columns[0]);
+        builder.produce_intermediate_mle(witness.is_in);
+        builder.produce_intermediate_mle(witness.distinct_keys);
+        builder.produce_intermediate_mle(witness.shifted_distinct_keys);
+        builder.produce_intermediate_mle(witness.subquery_key_counts);
+        builder.produce_intermediate_mle(witness.key_counts);
+        builder.produce_intermediate_mle(witness.is_subquery_key);
+        builder.request_post_result_challenges(2);
+        let semi_join_accessor = self.semi_join_accessor(alloc, accessor, witness.is_in);
+        self.plan
+            .first_round_evaluate(builder, alloc, semi_join_accessor);
+    }
+
+    #[tracing::instrument(name = "SemiJoinExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        // 1. subquery, evaluated on its own table
+        let subquery_columns =
+            final_round_evaluate_subquery(&self.subquery, builder, alloc, accessor);
+        let subquery_keys = subquery_columns[0];
+
+        // 2. key
+        let keys = self.key.prover_evaluate(builder, alloc, accessor);
+        let witness = Self::semi_join_witness(alloc, &keys, &subquery_keys);
+        let table_length = keys.len();
+        let distinct_keys_length = table_length + subquery_keys.len();
+
+        // 3. a distinct key is a key of the subquery exactly if its count is not zero
+        builder.produce_intermediate_mle(Column::Scalar(witness.subquery_key_count_inverses));
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(witness.is_subquery_key)]),
+                (
+                    -C::Scalar::ONE,
+                    vec![
+                        Box::new(witness.subquery_key_counts),
+                        Box::new(witness.subquery_key_count_inverses as &[_]),
+                    ],
+                ),
+            ],
+        );
+        builder.produce_sumcheck_subpolynomial(
+            SumcheckSubpolynomialType::Identity,
+            vec![
+                (C::Scalar::ONE, vec![Box::new(witness.subquery_key_counts)]),
+                (
+                    -C::Scalar::ONE,
+                    vec![
+                        Box::new(witness.subquery_key_counts),
+                        Box::new(witness.is_subquery_key),
+                    ],
+                ),
+            ],
+        );
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 4. the keys of the subquery and of the table are distinct keys,
+        // and a row is in the semi-join exactly if its distinct key is a key of the subquery
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            subquery_keys.len(),
+            &[subquery_keys],
+            distinct_keys_length,
+            &[witness.distinct_keys],
+            witness.subquery_key_counts,
+        );
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            table_length,
+            &[keys, witness.is_in],
+            distinct_keys_length,
+            &[witness.distinct_keys, witness.is_subquery_key],
+            witness.key_counts,
+        );
+
+        // 5. the distinct keys are distinct
+        prover_evaluate_strictly_increasing(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            witness.distinct_key_values,
+            witness.shifted_distinct_key_values,
+        );
+
+        // 6. plan, evaluated on the table with the column of the semi-join
+        let table_length_of_parent = builder.table_length();
+        builder.set_table_length(table_length);
+        let semi_join_accessor = self.semi_join_accessor(alloc, accessor, witness.is_in);
+        let output = self
+            .plan
+            .final_round_evaluate(builder, alloc, semi_join_accessor);
+        builder.set_table_length(table_length_of_parent);
+        output
+    }
+}
+
+/// The columns that the prover commits to in a semi-join proof, along with the values needed
+/// to compute them.
+struct SemiJoinWitness<'a, S: Scalar> {
+    /// Whether the key of each row of the table is a key of the subquery
+    is_in: Column<'a, S>,
+    /// The distinct keys of the table and the subquery in increasing order, padded with larger
+    /// values to the total number of rows of both
+    distinct_key_values: &'a [S],
+    /// `distinct_key_values` as a column
+    distinct_keys: Column<'a, S>,
+    /// `distinct_key_values` shifted down by one row
+    shifted_distinct_key_values: &'a [S],
+    /// `shifted_distinct_key_values` as a column
+    shifted_distinct_keys: Column<'a, S>,
+    /// The number of rows of the subquery with each of the distinct keys
+    subquery_key_counts: Column<'a, S>,
+    /// The number of rows of the table with each of the distinct keys
+    key_counts: Column<'a, S>,
+    /// Whether each of the distinct keys is a key of the subquery
+    is_subquery_key: Column<'a, S>,
+    /// The inverses of `subquery_key_counts`, where zero is left as it is
+    subquery_key_count_inverses: &'a [S],
+}
+
+impl<'a, S: Scalar> SemiJoinWitness<'a, S> {
+    fn new(alloc: &'a Bump, keys: &[i128], subquery_keys: &[i128]) -> Self {
+        let distinct_keys_length = keys.len() + subquery_keys.len();
+
+        // (subquery count, table count) for every key
+        let mut key_counts: BTreeMap<i128, (i64, i64)> = BTreeMap::new();
+        for key in subquery_keys {
+            key_counts.entry(*key).or_default().0 += 1;
+        }
+        for key in keys {
+            key_counts.entry(*key).or_default().1 += 1;
+        }
+        let is_in: &[_] = alloc.alloc_slice_fill_iter(keys.iter().map(|key| key_counts[key].0 > 0));
+
+        let last_key = key_counts
+            .keys()
+            .last()
+            .map_or(S::zero(), |&key| S::from(key));
+        let distinct_key_values: Vec<_> = key_counts
+            .keys()
+            .map(|&key| S::from(key))
+            .chain(
+                (1..=(distinct_keys_length - key_counts.len()) as u64)
+                    .map(|k| last_key + S::from([k, 0, 0, 0])),
+            )
+            .collect();
+        let distinct_key_values: &[_] = alloc.alloc_slice_copy(&distinct_key_values);
+        let subquery_key_counts = alloc.alloc_slice_fill_copy(distinct_keys_length, 0_i64);
+        let table_key_counts = alloc.alloc_slice_fill_copy(distinct_keys_length, 0_i64);
+        let is_subquery_key = alloc.alloc_slice_fill_copy(distinct_keys_length, false);
+        for (u, (subquery_count, table_count)) in key_counts.values().enumerate() {
+            subquery_key_counts[u] = *subquery_count;
+            table_key_counts[u] = *table_count;
+            is_subquery_key[u] = *subquery_count > 0;
+        }
+        let subquery_key_count_inverses =
+            alloc.alloc_slice_fill_iter(subquery_key_counts.iter().map(|&count| S::from(count)));
+        slice_ops::batch_inversion(subquery_key_count_inverses);
+
+        let shifted_distinct_key_values = shift(alloc, distinct_key_values);
+
+        Self {
+            is_in: Column::Boolean(is_in),
+            distinct_key_values,
+            distinct_keys: Column::Scalar(distinct_key_values),
+            shifted_distinct_key_values,
+            shifted_distinct_keys: Column::Scalar(shifted_distinct_key_values),
+            subquery_key_counts: Column::BigInt(subquery_key_counts),
+            key_counts: Column::BigInt(table_key_counts),
+            is_subquery_key: Column::Boolean(is_subquery_key),
+            subquery_key_count_inverses,
+        }
+    }
+}
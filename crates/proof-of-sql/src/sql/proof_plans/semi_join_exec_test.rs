@@ -0,0 +1,175 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnRef, ColumnType, OwnedTable,
+            OwnedTableTestAccessor, TableRef, TestAccessor,
+        },
+        map::IndexSet,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, ColumnExpr, DynProofExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+
+fn make_accessor(
+    data: OwnedTable<Curve25519Scalar>,
+    other: OwnedTable<Curve25519Scalar>,
+) -> (
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+    TableRef,
+    TableRef,
+) {
+    let t = "sxt.t".parse().unwrap();
+    let u = "sxt.u".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    accessor.add_table(u, other, 0);
+    (accessor, t, u)
+}
+
+/// The hidden column that is true for the rows whose key is in the subquery
+fn is_in(t: TableRef) -> DynProofExpr<RistrettoPoint> {
+    DynProofExpr::Column(ColumnExpr::new(ColumnRef::new(
+        t,
+        "__col_subquery_0".parse().unwrap(),
+        ColumnType::Boolean,
+    )))
+}
+
+fn sample_tables() -> (OwnedTable<Curve25519Scalar>, OwnedTable<Curve25519Scalar>) {
+    (
+        owned_table([
+            bigint("a", [3_i64, 1, 2, 3, 5, 4]),
+            bigint("b", [10_i64, 20, 30, 40, 50, 60]),
+        ]),
+        owned_table([bigint("k", [5_i64, 3, 7, 3]), bigint("v", [1_i64, 2, 3, 4])]),
+    )
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_column_references() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = semi_join(
+        projection(cols_expr_plan(u, &["k"], &accessor), tab(u)),
+        "a",
+        t,
+        "__col_subquery_0",
+        filter(cols_expr_plan(t, &["b"], &accessor), tab(t), is_in(t)),
+        &accessor,
+    );
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![ColumnField::new("b".parse().unwrap(), ColumnType::BigInt)]
+    );
+    // The hidden column is not a column of the accessor
+    let expected_refs = IndexSet::from_iter([
+        col_ref(u, "k", &accessor),
+        col_ref(t, "a", &accessor),
+        col_ref(t, "b", &accessor),
+    ]);
+    assert_eq!(ast.get_column_references(), expected_refs);
+    assert_eq!(ast.get_length(&accessor), 10);
+    assert_eq!(ast.get_offset(&accessor), 0);
+}
+
+#[test]
+fn we_can_prove_a_filter_on_keys_in_a_subquery() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = semi_join(
+        filter(
+            cols_expr_plan(u, &["k"], &accessor),
+            tab(u),
+            gte(column(u, "v", &accessor), const_bigint(2)),
+        ),
+        "a",
+        t,
+        "__col_subquery_0",
+        filter(cols_expr_plan(t, &["a", "b"], &accessor), tab(t), is_in(t)),
+        &accessor,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [3_i64, 3]), bigint("b", [10_i64, 40])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_filter_on_keys_not_in_a_group_by_subquery() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = semi_join(
+        group_by_with_min_max(
+            cols_expr_plan(u, &["k"], &accessor),
+            vec![],
+            vec![],
+            None,
+            tab(u),
+            const_bool(true),
+        ),
+        "a",
+        t,
+        "__col_subquery_0",
+        filter(
+            cols_expr_plan(t, &["a", "b"], &accessor),
+            tab(t),
+            not(is_in(t)),
+        ),
+        &accessor,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [1_i64, 2, 4]), bigint("b", [20_i64, 30, 60])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_filter_on_keys_in_an_empty_subquery() {
+    let (data, other) = sample_tables();
+    let (accessor, t, u) = make_accessor(data, other);
+    let ast = semi_join(
+        filter(
+            cols_expr_plan(u, &["k"], &accessor),
+            tab(u),
+            const_bool(false),
+        ),
+        "a",
+        t,
+        "__col_subquery_0",
+        filter(cols_expr_plan(t, &["a", "b"], &accessor), tab(t), is_in(t)),
+        &accessor,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [0_i64; 0]), bigint("b", [0_i64; 0])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_filter_on_keys_in_a_subquery_over_a_larger_table() {
+    let (accessor, t, u) = make_accessor(
+        owned_table([bigint("a", [2_i64, -1]), bigint("b", [10_i64, 20])]),
+        owned_table([bigint("k", [-1_i64, 0, 1, -1, 3, 4, 5, 6, 7])]),
+    );
+    let ast = semi_join(
+        projection(cols_expr_plan(u, &["k"], &accessor), tab(u)),
+        "a",
+        t,
+        "__col_subquery_0",
+        filter(cols_expr_plan(t, &["b"], &accessor), tab(t), is_in(t)),
+        &accessor,
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("b", [20_i64])]));
+}
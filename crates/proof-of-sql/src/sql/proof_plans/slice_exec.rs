@@ -0,0 +1,247 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            filter_util::filter_column_by_index, Column, ColumnField, ColumnRef,
+            CommitmentAccessor, DataAccessor, MetadataAccessor, OwnedTable,
+        },
+        map::IndexSet,
+        proof::ProofError,
+    },
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            VerificationBuilder,
+        },
+        proof_gadgets::{
+            count_membership_check, prover_evaluate_membership_check, prover_evaluate_row_index,
+            verifier_evaluate_membership_check,
+        },
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use core::{cmp::min, iter::repeat_with};
+use serde::{Deserialize, Serialize};
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     <input> LIMIT <fetch> OFFSET <skip>
+/// ```
+///
+/// The result consists of the rows `[skip, skip + fetch)` of the input. If `fetch` is `None`,
+/// every row from `skip` on is returned.
+///
+/// The proof shows that the `i`th row of the result is the `(skip + i)`th row of the input with a
+/// membership check of the rows `(i + skip, result[i])` in the rows `(j, input[j])`, where
+/// exactly the rows of the input that are in the window have multiplicity one.
+/// Both the input and the result are committed to in the first round, since the
+/// input of the slice is not part of the query result. The length of the input is sent along
+/// with them.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SliceExec<C: Commitment> {
+    pub(super) input: Box<DynProofPlan<C>>,
+    pub(super) skip: usize,
+    pub(super) fetch: Option<usize>,
+}
+
+impl<C: Commitment> SliceExec<C> {
+    /// Creates a new slice expression.
+    pub fn new(input: Box<DynProofPlan<C>>, skip: usize, fetch: Option<usize>) -> Self {
+        Self { input, skip, fetch }
+    }
+
+    /// The range of rows of an input of length `input_length` that are in the result
+    fn window(&self, input_length: usize) -> (usize, usize) {
+        let start = min(self.skip, input_length);
+        let end = self.fetch.map_or(input_length, |fetch| {
+            min(start.saturating_add(fetch), input_length)
+        });
+        (start, end)
+    }
+
+    /// Slice the input columns
+    fn slice_columns<'a>(
+        &self,
+        alloc: &'a Bump,
+        columns: &[Column<'a, C::Scalar>],
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let (start, end) = self.window(columns.first().map_or(0, Column::len));
+        let indexes: Vec<_> = (start..end).collect();
+        columns
+            .iter()
+            .map(|column| filter_column_by_index(alloc, column, &indexes))
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for SliceExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        builder.count_one_evaluation_lengths(1);
+        self.input.count(builder, accessor)?;
+        builder.count_first_round_mles(2 * self.get_column_result_fields().len());
+        count_membership_check(builder);
+        builder.count_post_result_challenges(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.input.get_length(accessor)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.input.get_offset(accessor)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        _result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let num_columns = self.get_column_result_fields().len();
+        let output_length = builder.mle_evaluations.output_length;
+        let input_length = builder.consume_one_evaluation_length();
+
+        // 1. input
+        // The output of the input is not the query result, so its length has to be swapped in.
+        let output_length_of_parent = builder.mle_evaluations.replace_output_length(input_length);
+        let input_evals = self.input.verifier_evaluate(builder, accessor, None);
+        builder
+            .mle_evaluations
+            .replace_output_length(output_length_of_parent);
+        let input_evals = input_evals?;
+
+        let (start, end) = self.window(input_length);
+        if output_length != end - start {
+            return Err(ProofError::VerificationError {
+                error: "length of the result of slice is invalid",
+            });
+        }
+
+        // 2. first round witness
+        let committed_input_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(num_columns)
+            .collect();
+        let output_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(num_columns)
+            .collect();
+        if input_evals != committed_input_evals {
+            return Err(ProofError::VerificationError {
+                error: "committed input of slice does not match its input",
+            });
+        }
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 3. the ith row of the result is the (skip + i)th row of the input
+        let rho_eval = builder.mle_evaluations.rho_evaluation();
+        let output_one_eval = builder.mle_evaluations.chi_evaluation(output_length);
+        let input_one_eval = builder.mle_evaluations.chi_evaluation(input_length);
+        let window_eval = builder.mle_evaluations.chi_evaluation(end)
+            - builder.mle_evaluations.chi_evaluation(start);
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            output_one_eval,
+            &[
+                [rho_eval + C::Scalar::from([start as u64, 0, 0, 0])].as_slice(),
+                &output_evals,
+            ]
+            .concat(),
+            input_one_eval,
+            &[[rho_eval].as_slice(), &committed_input_evals].concat(),
+            window_eval,
+        );
+
+        Ok(output_evals)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for SliceExec<C> {
+    #[tracing::instrument(name = "SliceExec::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        input_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let input = self.input.result_evaluate(input_length, alloc, accessor);
+        self.slice_columns(alloc, &input)
+    }
+
+    #[tracing::instrument(name = "SliceExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // The verifier needs the length of the input before it verifies the input.
+        let input_length = self.input.get_length(&accessor);
+        let input = self.input.result_evaluate(input_length, alloc, accessor);
+        builder.produce_one_evaluation_length(input.first().map_or(0, Column::len));
+        self.input.first_round_evaluate(builder, alloc, accessor);
+        let output = self.slice_columns(alloc, &input);
+        for column in input.iter().chain(&output) {
+            builder.produce_intermediate_mle(*column);
+        }
+        builder.request_post_result_challenges(2);
+    }
+
+    #[tracing::instrument(name = "SliceExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        // 1. input
+        let input = self.input.final_round_evaluate(builder, alloc, accessor);
+        let input_length = input.first().map_or(0, Column::len);
+        let output = self.slice_columns(alloc, &input);
+        let (start, end) = self.window(input_length);
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 2. the ith row of the result is the (skip + i)th row of the input
+        let rho = prover_evaluate_row_index(builder, alloc);
+        let shifted_rho: &[_] = alloc.alloc_slice_fill_with(rho.len(), |i| {
+            rho[i] + C::Scalar::from([start as u64, 0, 0, 0])
+        });
+        let window = alloc.alloc_slice_fill_with(input_length, |i| (start..end).contains(&i));
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            end - start,
+            &core::iter::once(Column::Scalar(shifted_rho))
+                .chain(output.iter().copied())
+                .collect::<Vec<_>>(),
+            input_length,
+            &core::iter::once(Column::Scalar(rho))
+                .chain(input.iter().copied())
+                .collect::<Vec<_>>(),
+            Column::Boolean(window),
+        );
+
+        output
+    }
+}
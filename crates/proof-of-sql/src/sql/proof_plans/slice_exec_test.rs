@@ -0,0 +1,199 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TestAccessor,
+        },
+        map::IndexSet,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, ColumnExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection::{Asc, Desc};
+
+fn make_accessor(
+    data: OwnedTable<Curve25519Scalar>,
+) -> (OwnedTableTestAccessor<'static, InnerProductProof>, TableRef) {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    (accessor, t)
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_column_references() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [1_i64, 2]),
+        varchar("b", ["x", "y"]),
+        bigint("c", [3_i64, 4]),
+    ]));
+    let ast = slice_exec::<RistrettoPoint>(
+        filter(
+            cols_expr_plan(t, &["b", "a"], &accessor),
+            tab(t),
+            equal(column(t, "c", &accessor), const_bigint(3)),
+        ),
+        1,
+        Some(2),
+    );
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![
+            ColumnField::new("b".parse().unwrap(), ColumnType::VarChar),
+            ColumnField::new("a".parse().unwrap(), ColumnType::BigInt),
+        ]
+    );
+    let expected_refs = IndexSet::from_iter([
+        col_ref(t, "b", &accessor),
+        col_ref(t, "a", &accessor),
+        col_ref(t, "c", &accessor),
+    ]);
+    assert_eq!(ast.get_column_references(), expected_refs);
+    assert_eq!(ast.get_length(&accessor), 2);
+}
+
+#[test]
+fn we_can_prove_a_slice_of_a_filter() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [5_i64, -2, 7, 5, 0, -2, 9]),
+        varchar("b", ["a", "b", "c", "d", "e", "f", "g"]),
+        bigint("c", [1_i64, 1, 1, 0, 1, 1, 1]),
+    ]));
+    let input = || {
+        filter(
+            cols_expr_plan(t, &["b", "a"], &accessor),
+            tab(t),
+            equal(column(t, "c", &accessor), const_bigint(1)),
+        )
+    };
+    for (skip, fetch, expected_res) in [
+        (
+            1,
+            Some(3),
+            owned_table([varchar("b", ["b", "c", "e"]), bigint("a", [-2_i64, 7, 0])]),
+        ),
+        (
+            4,
+            None,
+            owned_table([varchar("b", ["f", "g"]), bigint("a", [-2_i64, 9])]),
+        ),
+        (
+            0,
+            Some(100),
+            owned_table([
+                varchar("b", ["a", "b", "c", "e", "f", "g"]),
+                bigint("a", [5_i64, -2, 7, 0, -2, 9]),
+            ]),
+        ),
+        (
+            2,
+            Some(0),
+            owned_table([varchar("b", [""; 0]), bigint("a", [0_i64; 0])]),
+        ),
+        (
+            6,
+            Some(2),
+            owned_table([varchar("b", [""; 0]), bigint("a", [0_i64; 0])]),
+        ),
+    ] {
+        let ast = slice_exec(input(), skip, fetch);
+        let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+        exercise_verification(&verifiable_res, &ast, &accessor, t);
+        let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+        assert_eq!(res, expected_res);
+    }
+}
+
+#[test]
+fn we_can_prove_a_slice_of_a_sort() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [5_i64, -2, 7, 5, 0, -2, 9]),
+        varchar("b", ["a", "b", "c", "d", "e", "f", "g"]),
+    ]));
+    let ast = slice_exec(
+        sort(
+            projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+            vec![(0, Desc)],
+        ),
+        1,
+        Some(3),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [7_i64, 5, 5]), varchar("b", ["c", "a", "d"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_slice_of_a_slice_of_a_sorted_filter() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [5_i64, -2, 7, 5, 0, -2, 9, 4]),
+        bigint("c", [1_i64, 1, 1, 0, 1, 1, 1, 1]),
+    ]));
+    let ast = slice_exec(
+        slice_exec(
+            sort(
+                filter(
+                    cols_expr_plan(t, &["a"], &accessor),
+                    tab(t),
+                    equal(column(t, "c", &accessor), const_bigint(1)),
+                ),
+                vec![(0, Asc)],
+            ),
+            2,
+            None,
+        ),
+        1,
+        Some(2),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [4_i64, 5])]));
+}
+
+#[test]
+fn we_can_prove_a_slice_of_a_join() {
+    let l = "sxt.left".parse().unwrap();
+    let r = "sxt.right".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        l,
+        owned_table([bigint("id", [1_i64, 2]), varchar("name", ["a", "b"])]),
+        0,
+    );
+    accessor.add_table(
+        r,
+        owned_table([
+            bigint("user_id", [1_i64, 1, 2, 1, 2]),
+            bigint("amount", [10_i64, 20, 30, 40, 50]),
+        ]),
+        0,
+    );
+    let ast = slice_exec(
+        join(
+            tab(l),
+            tab(r),
+            ColumnExpr::new(col_ref(l, "id", &accessor)),
+            ColumnExpr::new(col_ref(r, "user_id", &accessor)),
+            vec![
+                aliased_col(l, "name", "name", &accessor),
+                aliased_col(r, "amount", "amount", &accessor),
+            ],
+        ),
+        3,
+        Some(10),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, l);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([varchar("name", ["b", "b"]), bigint("amount", [30_i64, 50])]);
+    assert_eq!(res, expected_res);
+}
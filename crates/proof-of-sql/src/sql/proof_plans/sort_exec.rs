@@ -0,0 +1,301 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            compare_indexes_by_columns_with_direction, filter_util::filter_column_by_index, Column,
+            ColumnField, ColumnRef, ColumnType, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedTable,
+        },
+        map::IndexSet,
+        proof::ProofError,
+    },
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            VerificationBuilder,
+        },
+        proof_gadgets::{
+            count_membership_check, count_sorted, prover_evaluate_membership_check,
+            prover_evaluate_sorted, shift, verifier_evaluate_membership_check,
+            verifier_evaluate_sorted,
+        },
+    },
+};
+use alloc::{boxed::Box, vec::Vec};
+use bumpalo::Bump;
+use core::iter::repeat_with;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection;
+use serde::{Deserialize, Serialize};
+
+/// The largest precision of a decimal column that can be sorted.
+///
+/// The differences of consecutive keys have to fit into the range of a sign decomposition.
+const MAX_SORTABLE_DECIMAL_PRECISION: u8 = 38;
+
+/// Whether a provable sort can use a column of type `column_type` as a key.
+///
+/// Strings and scalars can't be sorted, since their scalar values don't preserve their order.
+pub(crate) fn is_sortable_column_type(column_type: &ColumnType) -> bool {
+    match column_type {
+        ColumnType::Boolean
+        | ColumnType::TinyInt
+        | ColumnType::SmallInt
+        | ColumnType::Int
+        | ColumnType::BigInt
+        | ColumnType::Int128
+        | ColumnType::TimestampTZ(_, _) => true,
+        ColumnType::Decimal75(precision, _) => precision.value() <= MAX_SORTABLE_DECIMAL_PRECISION,
+        ColumnType::VarChar | ColumnType::Scalar => false,
+    }
+}
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     <input> ORDER BY <order_by_column1> <direction1>, ..., <order_by_columnN> <directionN>
+/// ```
+///
+/// The result has the same columns as the input. The proof does not constrain the order of rows
+/// with equal keys, so a verified result may list them in any order.
+///
+/// The proof shows that the result is a permutation of the input and that it is sorted by the keys.
+/// Both the input and the result are committed to in the first round, since the
+/// input of the sort is not part of the query result.
+///
+/// Note: the keys are indexes of the input columns and their types need to be sortable,
+/// see [`is_sortable_column_type`].
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SortExec<C: Commitment> {
+    pub(super) input: Box<DynProofPlan<C>>,
+    pub(super) order_by: Vec<(usize, OrderByDirection)>,
+}
+
+impl<C: Commitment> SortExec<C> {
+    /// Creates a new sort expression.
+    pub fn new(input: Box<DynProofPlan<C>>, order_by: Vec<(usize, OrderByDirection)>) -> Self {
+        Self { input, order_by }
+    }
+
+    /// The directions of the keys
+    fn directions(&self) -> Vec<OrderByDirection> {
+        self.order_by
+            .iter()
+            .map(|(_, direction)| *direction)
+            .collect()
+    }
+
+    /// Sort the input columns by the keys
+    fn sort_columns<'a>(
+        &self,
+        alloc: &'a Bump,
+        columns: &[Column<'a, C::Scalar>],
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let order_by_pairs: Vec<_> = self
+            .order_by
+            .iter()
+            .map(|&(index, direction)| (columns[index], direction))
+            .collect();
+        let mut indexes: Vec<_> = (0..columns.first().map_or(0, Column::len)).collect();
+        indexes.sort_by(|&i, &j| compare_indexes_by_columns_with_direction(&order_by_pairs, i, j));
+        columns
+            .iter()
+            .map(|column| filter_column_by_index(alloc, column, &indexes))
+            .collect()
+    }
+
+    /// The keys of the sorted columns as scalars
+    fn keys<'a>(&self, alloc: &'a Bump, columns: &[Column<'a, C::Scalar>]) -> Vec<&'a [C::Scalar]> {
+        self.order_by
+            .iter()
+            .map(|&(index, _)| columns[index].as_scalar(alloc))
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for SortExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        self.input.count(builder, accessor)?;
+        let fields = self.input.get_column_result_fields();
+        if self.order_by.iter().any(|&(index, _)| {
+            fields
+                .get(index)
+                .map_or(true, |field| !is_sortable_column_type(&field.data_type()))
+        }) {
+            return Err(ProofError::VerificationError {
+                error: "invalid sort keys",
+            });
+        }
+        builder.count_first_round_mles(2 * fields.len() + self.order_by.len());
+        // The result is a permutation of the input
+        count_membership_check(builder);
+        // The result is sorted
+        count_sorted(builder, self.order_by.len(), false)?;
+        builder.count_post_result_challenges(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.input.get_length(accessor)
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.input.get_offset(accessor)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        _result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let num_columns = self.get_column_result_fields().len();
+        let output_length = builder.mle_evaluations.output_length;
+
+        // 1. input
+        let input_evals = self.input.verifier_evaluate(builder, accessor, None)?;
+
+        // 2. first round witness
+        let committed_input_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(num_columns)
+            .collect();
+        let output_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(num_columns)
+            .collect();
+        let shifted_key_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(self.order_by.len())
+            .collect();
+        if input_evals != committed_input_evals {
+            return Err(ProofError::VerificationError {
+                error: "committed input of sort does not match its input",
+            });
+        }
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 3. the result is a permutation of the input
+        let one_eval = builder.mle_evaluations.chi_evaluation(output_length);
+        verifier_evaluate_membership_check(
+            builder,
+            alpha,
+            beta,
+            one_eval,
+            &output_evals,
+            one_eval,
+            &committed_input_evals,
+            one_eval,
+        );
+
+        // 4. the result is sorted
+        let key_evals: Vec<_> = self
+            .order_by
+            .iter()
+            .map(|&(index, _)| output_evals[index])
+            .collect();
+        verifier_evaluate_sorted(
+            builder,
+            alpha,
+            beta,
+            output_length,
+            &key_evals,
+            &shifted_key_evals,
+            &self.directions(),
+            false,
+        )?;
+
+        Ok(output_evals)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.input.get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.input.get_column_references()
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for SortExec<C> {
+    #[tracing::instrument(name = "SortExec::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        input_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let input = self.input.result_evaluate(input_length, alloc, accessor);
+        self.sort_columns(alloc, &input)
+    }
+
+    #[tracing::instrument(name = "SortExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        self.input.first_round_evaluate(builder, alloc, accessor);
+        let input_length = self.input.get_length(&accessor);
+        let input = self.input.result_evaluate(input_length, alloc, accessor);
+        let output = self.sort_columns(alloc, &input);
+        for column in input.iter().chain(&output) {
+            builder.produce_intermediate_mle(*column);
+        }
+        for key in self.keys(alloc, &output) {
+            builder.produce_intermediate_mle(shift(alloc, key));
+        }
+        builder.request_post_result_challenges(2);
+    }
+
+    #[tracing::instrument(name = "SortExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        // 1. input
+        let input = self.input.final_round_evaluate(builder, alloc, accessor);
+        let output = self.sort_columns(alloc, &input);
+        let output_length = input.first().map_or(0, Column::len);
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 2. the result is a permutation of the input
+        // The input rows need not be distinct, but with all multiplicities equal to one,
+        // the membership check shows that the rows are equal as multisets.
+        let chi = alloc.alloc_slice_fill_copy(output_length, true);
+        prover_evaluate_membership_check(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            output_length,
+            &output,
+            output_length,
+            &input,
+            Column::Boolean(chi),
+        );
+
+        // 3. the result is sorted
+        let keys = self.keys(alloc, &output);
+        let shifted_keys: Vec<_> = keys.iter().map(|key| shift(alloc, key)).collect();
+        prover_evaluate_sorted(
+            builder,
+            alloc,
+            alpha,
+            beta,
+            &keys,
+            &shifted_keys,
+            &self.directions(),
+            false,
+        );
+
+        output
+    }
+}
@@ -0,0 +1,209 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TestAccessor,
+        },
+        map::IndexSet,
+        proof::ProofError,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, CountBuilder, ProofPlan, VerifiableQueryResult},
+        proof_exprs::{test_utility::*, ColumnExpr},
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::{
+    intermediate_ast::OrderByDirection::{Asc, Desc},
+    posql_time::{PoSQLTimeUnit, PoSQLTimeZone},
+};
+
+fn make_accessor(
+    data: OwnedTable<Curve25519Scalar>,
+) -> (OwnedTableTestAccessor<'static, InnerProductProof>, TableRef) {
+    let t = "sxt.t".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t, data, 0);
+    (accessor, t)
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_column_references() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [1_i64, 2]),
+        varchar("b", ["x", "y"]),
+        bigint("c", [3_i64, 4]),
+    ]));
+    let ast = sort::<RistrettoPoint>(
+        filter(
+            cols_expr_plan(t, &["b", "a"], &accessor),
+            tab(t),
+            equal(column(t, "c", &accessor), const_bigint(3)),
+        ),
+        vec![(1, Desc)],
+    );
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![
+            ColumnField::new("b".parse().unwrap(), ColumnType::VarChar),
+            ColumnField::new("a".parse().unwrap(), ColumnType::BigInt),
+        ]
+    );
+    let expected_refs = IndexSet::from_iter([
+        col_ref(t, "b", &accessor),
+        col_ref(t, "a", &accessor),
+        col_ref(t, "c", &accessor),
+    ]);
+    assert_eq!(ast.get_column_references(), expected_refs);
+    assert_eq!(ast.get_length(&accessor), 2);
+}
+
+#[test]
+fn we_cannot_count_a_sort_with_invalid_keys() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [1_i64, 2]),
+        varchar("b", ["x", "y"]),
+    ]));
+    for order_by in [vec![(1, Asc)], vec![(0, Asc), (2, Desc)]] {
+        let ast = sort::<RistrettoPoint>(
+            projection(cols_expr_plan(t, &["a", "b"], &accessor), tab(t)),
+            order_by,
+        );
+        assert!(matches!(
+            ast.count(&mut CountBuilder::new(&[]), &accessor),
+            Err(ProofError::VerificationError { .. })
+        ));
+    }
+}
+
+#[test]
+fn we_can_prove_a_sort_of_a_filter_by_one_key() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [5_i64, -2, 7, 5, 0, -2, 9]),
+        varchar("b", ["a", "b", "c", "d", "e", "f", "g"]),
+        bigint("c", [1_i64, 1, 1, 1, 0, 1, 1]),
+    ]));
+    let ast = sort(
+        filter(
+            cols_expr_plan(t, &["b", "a"], &accessor),
+            tab(t),
+            equal(column(t, "c", &accessor), const_bigint(1)),
+        ),
+        vec![(1, Asc)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    // The honest prover keeps rows with equal keys in the order of the input
+    let expected_res = owned_table([
+        varchar("b", ["b", "f", "a", "d", "c", "g"]),
+        bigint("a", [-2_i64, -2, 5, 5, 7, 9]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_sort_by_several_keys_of_different_types() {
+    let (accessor, t) = make_accessor(owned_table([
+        boolean("a", [true, false, true, true, false, true]),
+        decimal75("b", 10, 2, [-150_i64, 20, 20, -150, 300, 20]),
+        int128("c", [i128::MIN, 3, i128::MAX, 5, 0, -7]),
+        timestamptz(
+            "d",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            [10, 20, 30, 40, 50, 60],
+        ),
+        smallint("e", [1_i16, 2, 3, 4, 5, 6]),
+    ]));
+    let ast = sort(
+        projection(
+            cols_expr_plan(t, &["a", "b", "c", "d", "e"], &accessor),
+            tab(t),
+        ),
+        vec![(0, Desc), (1, Asc), (2, Desc)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        boolean("a", [true, true, true, true, false, false]),
+        decimal75("b", 10, 2, [-150_i64, -150, 20, 20, 20, 300]),
+        int128("c", [5, i128::MIN, i128::MAX, -7, 3, 0]),
+        timestamptz(
+            "d",
+            PoSQLTimeUnit::Second,
+            PoSQLTimeZone::Utc,
+            [40, 10, 30, 60, 20, 50],
+        ),
+        smallint("e", [4_i16, 1, 3, 6, 2, 5]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_sort_with_an_empty_result() {
+    let (accessor, t) = make_accessor(owned_table([
+        bigint("a", [5_i64, -2, 7]),
+        bigint("c", [1_i64, 1, 1]),
+    ]));
+    let ast = sort(
+        filter(
+            cols_expr_plan(t, &["a"], &accessor),
+            tab(t),
+            equal(column(t, "c", &accessor), const_bigint(0)),
+        ),
+        vec![(0, Desc)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [0_i64; 0])]));
+}
+
+#[test]
+fn we_can_prove_a_sort_of_a_join() {
+    let l = "sxt.left".parse().unwrap();
+    let r = "sxt.right".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        l,
+        owned_table([
+            bigint("id", [1_i64, 2, 3]),
+            varchar("name", ["a", "b", "c"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        r,
+        owned_table([
+            bigint("user_id", [3_i64, 1, 3, 2]),
+            bigint("amount", [30_i64, 10, 50, 20]),
+        ]),
+        0,
+    );
+    let ast = sort(
+        join(
+            tab(l),
+            tab(r),
+            ColumnExpr::new(col_ref(l, "id", &accessor)),
+            ColumnExpr::new(col_ref(r, "user_id", &accessor)),
+            vec![
+                aliased_col(l, "name", "name", &accessor),
+                aliased_col(r, "amount", "amount", &accessor),
+            ],
+        ),
+        vec![(1, Desc)],
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, l);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        varchar("name", ["c", "c", "b", "a"]),
+        bigint("amount", [50_i64, 30, 20, 10]),
+    ]);
+    assert_eq!(res, expected_res);
+}
@@ -0,0 +1,161 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            Column, ColumnField, ColumnRef, CommitmentAccessor, DataAccessor, MetadataAccessor,
+            OwnedColumn, TableRef,
+        },
+        proof::ProofError,
+        scalar::Scalar,
+    },
+    sql::proof::{FinalRoundBuilder, ProofPlan, ProverEvaluate, VerificationBuilder},
+};
+use alloc::vec::Vec;
+use bumpalo::Bump;
+use proof_of_sql_parser::Identifier;
+
+/// Evaluate a subquery on its own table and type its columns like its result fields
+///
+/// # Panics
+///
+/// Panics if an aggregate of the subquery doesn't fit into its type.
+pub(super) fn evaluate_subquery<'a, C: Commitment>(
+    subquery: &DynProofPlan<C>,
+    alloc: &'a Bump,
+    accessor: &'a dyn DataAccessor<C::Scalar>,
+) -> Vec<Column<'a, C::Scalar>> {
+    let columns = subquery.result_evaluate(subquery.get_length(&accessor), alloc, accessor);
+    type_columns(alloc, &subquery.get_column_result_fields(), columns)
+}
+
+/// Prove a subquery on its own table and return its typed columns.
+///
+/// The table length of the parent plan is restored afterwards.
+pub(super) fn final_round_evaluate_subquery<'a, C: Commitment>(
+    subquery: &DynProofPlan<C>,
+    builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+    alloc: &'a Bump,
+    accessor: &'a dyn DataAccessor<C::Scalar>,
+) -> Vec<Column<'a, C::Scalar>> {
+    let table_length_of_parent = builder.table_length();
+    builder.set_table_length(subquery.get_length(&accessor));
+    let columns = subquery.final_round_evaluate(builder, alloc, accessor);
+    builder.set_table_length(table_length_of_parent);
+    type_columns(alloc, &subquery.get_column_result_fields(), columns)
+}
+
+/// Verify a subquery whose output of length `output_length` is not the query result
+/// and return the evaluations of its columns.
+///
+/// The subquery is evaluated on its own table, so both the input and the output length
+/// are swapped in and restored afterwards. A `GroupByExec` is verified without checking
+/// that its groups are distinct, which is up to the caller.
+pub(super) fn verifier_evaluate_subquery<C: Commitment>(
+    subquery: &DynProofPlan<C>,
+    builder: &mut VerificationBuilder<C>,
+    accessor: &dyn CommitmentAccessor<C>,
+    output_length: usize,
+) -> Result<Vec<C::Scalar>, ProofError> {
+    let table_length_of_parent = builder
+        .mle_evaluations
+        .replace_input_length(subquery.get_length(&accessor));
+    let output_length_of_parent = builder.mle_evaluations.replace_output_length(output_length);
+    let evals = match subquery {
+        DynProofPlan::GroupBy(group_by) => group_by.verifier_evaluate_groups(builder, accessor),
+        subquery => subquery.verifier_evaluate(builder, accessor, None),
+    };
+    builder
+        .mle_evaluations
+        .replace_output_length(output_length_of_parent);
+    builder
+        .mle_evaluations
+        .replace_input_length(table_length_of_parent);
+    evals
+}
+
+/// Some plans, e.g. `GroupByExec`, evaluate results as scalars.
+/// Plans that read the result of a subquery need them to have the types of the result fields instead.
+///
+/// # Panics
+///
+/// Panics if a scalar doesn't fit into the type of its field.
+fn type_columns<'a, S: Scalar>(
+    alloc: &'a Bump,
+    fields: &[ColumnField],
+    columns: Vec<Column<'a, S>>,
+) -> Vec<Column<'a, S>> {
+    columns
+        .into_iter()
+        .zip(fields)
+        .map(|(column, field)| match column {
+            Column::Scalar(scalars) if field.data_type() != column.column_type() => {
+                let owned_column = OwnedColumn::try_from_scalars(scalars, field.data_type())
+                    .expect("aggregates should fit into their type");
+                Column::from_owned_column(alloc.alloc(owned_column), alloc)
+            }
+            _ => column,
+        })
+        .collect()
+}
+
+/// An accessor where the table `table_ref` has the given columns in addition to the ones of
+/// `accessor`, e.g. a derived table or the hidden columns of a subquery in a WHERE clause
+pub(super) struct DerivedTableAccessor<'a, A: ?Sized, T> {
+    pub(super) accessor: &'a A,
+    pub(super) table_ref: TableRef,
+    pub(super) length: usize,
+    pub(super) offset: usize,
+    pub(super) columns: &'a [(Identifier, T)],
+}
+
+impl<A: MetadataAccessor + ?Sized, T> DerivedTableAccessor<'_, A, T> {
+    fn get_derived(&self, column: ColumnRef) -> Option<&T> {
+        (column.table_ref() == self.table_ref)
+            .then(|| {
+                self.columns
+                    .iter()
+                    .find(|(id, _)| *id == column.column_id())
+                    .map(|(_, value)| value)
+            })
+            .flatten()
+    }
+}
+
+impl<A: MetadataAccessor + ?Sized, T> MetadataAccessor for DerivedTableAccessor<'_, A, T> {
+    fn get_length(&self, table_ref: TableRef) -> usize {
+        if table_ref == self.table_ref {
+            self.length
+        } else {
+            self.accessor.get_length(table_ref)
+        }
+    }
+
+    fn get_offset(&self, table_ref: TableRef) -> usize {
+        if table_ref == self.table_ref {
+            self.offset
+        } else {
+            self.accessor.get_offset(table_ref)
+        }
+    }
+}
+
+impl<'a, S: Scalar> DataAccessor<S>
+    for DerivedTableAccessor<'a, dyn DataAccessor<S> + 'a, Column<'a, S>>
+{
+    fn get_column(&self, column: ColumnRef) -> Column<S> {
+        self.get_derived(column)
+            .copied()
+            .unwrap_or_else(|| self.accessor.get_column(column))
+    }
+}
+
+impl<'a, C: Commitment> CommitmentAccessor<C>
+    for DerivedTableAccessor<'a, dyn CommitmentAccessor<C> + 'a, C>
+{
+    fn get_commitment(&self, column: ColumnRef) -> C {
+        self.get_derived(column)
+            .cloned()
+            .unwrap_or_else(|| self.accessor.get_commitment(column))
+    }
+}
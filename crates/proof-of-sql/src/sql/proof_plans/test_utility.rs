@@ -1,7 +1,20 @@
-use super::{DynProofPlan, FilterExec, GroupByExec, ProjectionExec};
+use super::{
+    DerivedTableExec, DynProofPlan, FilterExec, GroupByExec, JoinExec, ProjectionExec,
+    RunningSumExec, ScalarSubqueryExec, SemiJoinExec, SliceExec, SortExec, UnionExec,
+};
 use crate::{
-    base::commitment::Commitment,
-    sql::proof_exprs::{AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr},
+    base::{
+        commitment::Commitment,
+        database::{SchemaAccessor, TableRef},
+    },
+    sql::proof_exprs::{
+        test_utility::col_ref, AliasedDynProofExpr, ColumnExpr, DynProofExpr, TableExpr,
+    },
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use proof_of_sql_parser::{
+    intermediate_ast::{AggregationOperator, OrderByDirection},
+    Identifier,
 };
 
 pub fn projection<C: Commitment>(
@@ -23,7 +36,7 @@ pub fn filter<C: Commitment>(
 ///
 /// Will panic if `count_alias` cannot be parsed as a valid identifier.
 pub fn group_by<C: Commitment>(
-    group_by_exprs: Vec<ColumnExpr<C>>,
+    group_by_exprs: Vec<AliasedDynProofExpr<C>>,
     sum_expr: Vec<AliasedDynProofExpr<C>>,
     count_alias: &str,
     table: TableExpr,
@@ -32,8 +45,147 @@ pub fn group_by<C: Commitment>(
     DynProofPlan::GroupBy(GroupByExec::new(
         group_by_exprs,
         sum_expr,
-        count_alias.parse().unwrap(),
+        vec![],
+        Some(count_alias.parse().unwrap()),
         table,
         where_clause,
     ))
 }
+
+/// # Panics
+///
+/// Will panic if `count_alias` cannot be parsed as a valid identifier.
+pub fn group_by_with_min_max<C: Commitment>(
+    group_by_exprs: Vec<AliasedDynProofExpr<C>>,
+    sum_expr: Vec<AliasedDynProofExpr<C>>,
+    min_max_expr: Vec<(AggregationOperator, AliasedDynProofExpr<C>)>,
+    count_alias: Option<&str>,
+    table: TableExpr,
+    where_clause: DynProofExpr<C>,
+) -> DynProofPlan<C> {
+    DynProofPlan::GroupBy(GroupByExec::new(
+        group_by_exprs,
+        sum_expr,
+        min_max_expr,
+        count_alias.map(|count_alias| count_alias.parse().unwrap()),
+        table,
+        where_clause,
+    ))
+}
+
+/// # Panics
+///
+/// Will panic if `alias` cannot be parsed as a valid identifier or if the column doesn't exist.
+pub fn aliased_col<C: Commitment>(
+    tab: TableRef,
+    name: &str,
+    alias: &str,
+    accessor: &impl SchemaAccessor,
+) -> (ColumnExpr<C>, Identifier) {
+    (
+        ColumnExpr::new(col_ref(tab, name, accessor)),
+        alias.parse().unwrap(),
+    )
+}
+
+pub fn join<C: Commitment>(
+    left_table: TableExpr,
+    right_table: TableExpr,
+    left_key: ColumnExpr<C>,
+    right_key: ColumnExpr<C>,
+    aliased_results: Vec<(ColumnExpr<C>, Identifier)>,
+) -> DynProofPlan<C> {
+    DynProofPlan::Join(Box::new(JoinExec::new(
+        left_table,
+        right_table,
+        left_key,
+        right_key,
+        aliased_results,
+    )))
+}
+
+pub fn sort<C: Commitment>(
+    input: DynProofPlan<C>,
+    order_by: Vec<(usize, OrderByDirection)>,
+) -> DynProofPlan<C> {
+    DynProofPlan::Sort(Box::new(SortExec::new(Box::new(input), order_by)))
+}
+
+pub fn slice_exec<C: Commitment>(
+    input: DynProofPlan<C>,
+    skip: usize,
+    fetch: Option<usize>,
+) -> DynProofPlan<C> {
+    DynProofPlan::Slice(Box::new(SliceExec::new(Box::new(input), skip, fetch)))
+}
+
+pub fn union_exec<C: Commitment>(inputs: Vec<DynProofPlan<C>>) -> DynProofPlan<C> {
+    DynProofPlan::Union(UnionExec::new(inputs))
+}
+
+pub fn derived_table<C: Commitment>(
+    subquery: DynProofPlan<C>,
+    table_ref: TableRef,
+    plan: DynProofPlan<C>,
+) -> DynProofPlan<C> {
+    DynProofPlan::DerivedTable(Box::new(DerivedTableExec::new(
+        Box::new(subquery),
+        table_ref,
+        Box::new(plan),
+    )))
+}
+
+/// # Panics
+///
+/// Will panic if an alias cannot be parsed as a valid identifier.
+pub fn running_sum<C: Commitment>(
+    input: DynProofPlan<C>,
+    partition_by: Vec<usize>,
+    order_by: Vec<usize>,
+    sums: &[(usize, &str)],
+) -> DynProofPlan<C> {
+    DynProofPlan::RunningSum(Box::new(RunningSumExec::new(
+        Box::new(input),
+        partition_by,
+        order_by,
+        sums.iter()
+            .map(|&(index, alias)| (index, alias.parse().unwrap()))
+            .collect(),
+    )))
+}
+
+/// # Panics
+///
+/// Will panic if the key or the alias cannot be parsed as a valid identifier.
+pub fn semi_join<C: Commitment>(
+    subquery: DynProofPlan<C>,
+    key: &str,
+    table: TableRef,
+    alias: &str,
+    plan: DynProofPlan<C>,
+    accessor: &impl SchemaAccessor,
+) -> DynProofPlan<C> {
+    DynProofPlan::SemiJoin(Box::new(SemiJoinExec::new(
+        Box::new(subquery),
+        ColumnExpr::new(col_ref(table, key, accessor)),
+        alias.parse().unwrap(),
+        Box::new(plan),
+    )))
+}
+
+/// # Panics
+///
+/// Will panic if the alias cannot be parsed as a valid identifier.
+pub fn scalar_subquery<C: Commitment>(
+    subquery: DynProofPlan<C>,
+    table_ref: TableRef,
+    alias: &str,
+    plan: DynProofPlan<C>,
+) -> DynProofPlan<C> {
+    DynProofPlan::ScalarSubquery(Box::new(ScalarSubqueryExec::new(
+        Box::new(subquery),
+        table_ref,
+        alias.parse().unwrap(),
+        Box::new(plan),
+    )))
+}
@@ -0,0 +1,356 @@
+use super::DynProofPlan;
+use crate::{
+    base::{
+        commitment::Commitment,
+        database::{
+            union_util::concat_columns, Column, ColumnField, ColumnRef, CommitmentAccessor,
+            DataAccessor, MetadataAccessor, OwnedTable,
+        },
+        map::IndexSet,
+        proof::ProofError,
+    },
+    sql::{
+        proof::{
+            CountBuilder, FinalRoundBuilder, FirstRoundBuilder, ProofPlan, ProverEvaluate,
+            VerificationBuilder,
+        },
+        proof_gadgets::{
+            count_membership_check, prover_evaluate_membership_check, prover_evaluate_row_index,
+            verifier_evaluate_membership_check,
+        },
+    },
+};
+use alloc::vec::Vec;
+use bumpalo::Bump;
+use core::iter::repeat_with;
+use serde::{Deserialize, Serialize};
+
+/// Provable expressions for queries of the form
+/// ```ignore
+///     <input1> UNION ALL <input2> ... UNION ALL <inputN>
+/// ```
+///
+/// The result consists of the rows of the first input, followed by the rows of the second input
+/// and so on. The result columns are named after the ones of the first input.
+///
+/// Each input is evaluated on its own table, so that it is verified against the commitments of
+/// that table. The proof shows that the rows of the `i`th input are the rows of the result from
+/// `start_i`, the total length of the previous inputs, on with a membership check of the rows
+/// `(j + start_i, input_i[j])` in the rows `(k, result[k])`, where exactly the rows of the result
+/// in the window of the `i`th input have multiplicity one.
+/// The inputs and the result are committed to in the first round, since the inputs are not part
+/// of the query result. The lengths of the inputs are sent along with them.
+///
+/// Note: the tables of all inputs need to have the same offset and the inputs need to have
+/// results of the same types.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnionExec<C: Commitment> {
+    pub(super) inputs: Vec<DynProofPlan<C>>,
+}
+
+impl<C: Commitment> UnionExec<C> {
+    /// Creates a new union expression.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than two inputs.
+    pub fn new(inputs: Vec<DynProofPlan<C>>) -> Self {
+        assert!(inputs.len() >= 2, "a union needs at least two inputs");
+        Self { inputs }
+    }
+
+    /// The range of rows of the result that each input of the given length is in
+    fn windows(input_lengths: &[usize]) -> Vec<(usize, usize)> {
+        input_lengths
+            .iter()
+            .scan(0, |start, &length| {
+                let window = (*start, *start + length);
+                *start += length;
+                Some(window)
+            })
+            .collect()
+    }
+
+    /// Evaluate the inputs, each on its own table
+    fn evaluate_inputs<'a>(
+        &self,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Vec<Column<'a, C::Scalar>>> {
+        self.inputs
+            .iter()
+            .map(|input| input.result_evaluate(input.get_length(&accessor), alloc, accessor))
+            .collect()
+    }
+
+    /// Concatenate the columns of the inputs
+    fn union_columns<'a>(
+        &self,
+        alloc: &'a Bump,
+        inputs: &[Vec<Column<'a, C::Scalar>>],
+    ) -> Vec<Column<'a, C::Scalar>> {
+        (0..self.get_column_result_fields().len())
+            .map(|i| {
+                let columns: Vec<_> = inputs.iter().map(|input| input[i]).collect();
+                concat_columns(alloc, &columns)
+            })
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProofPlan<C> for UnionExec<C> {
+    fn count(
+        &self,
+        builder: &mut CountBuilder,
+        accessor: &dyn MetadataAccessor,
+    ) -> Result<(), ProofError> {
+        let offset = self.get_offset(accessor);
+        if self
+            .inputs
+            .iter()
+            .any(|input| input.get_offset(accessor) != offset)
+        {
+            return Err(ProofError::VerificationError {
+                error: "inputs of a union must have the same offset",
+            });
+        }
+        builder.count_one_evaluation_lengths(self.inputs.len());
+        for input in &self.inputs {
+            input.count(builder, accessor)?;
+        }
+        builder.count_first_round_mles(
+            (self.inputs.len() + 1) * self.get_column_result_fields().len(),
+        );
+        for _ in &self.inputs {
+            count_membership_check(builder);
+        }
+        builder.count_post_result_challenges(2);
+        Ok(())
+    }
+
+    fn get_length(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.inputs
+            .iter()
+            .map(|input| input.get_length(accessor))
+            .sum()
+    }
+
+    fn get_offset(&self, accessor: &dyn MetadataAccessor) -> usize {
+        self.inputs[0].get_offset(accessor)
+    }
+
+    fn verifier_evaluate(
+        &self,
+        builder: &mut VerificationBuilder<C>,
+        accessor: &dyn CommitmentAccessor<C>,
+        result: Option<&OwnedTable<C::Scalar>>,
+    ) -> Result<Vec<C::Scalar>, ProofError> {
+        let num_columns = self.get_column_result_fields().len();
+        let output_length = builder.mle_evaluations.output_length;
+        let input_lengths: Vec<_> = repeat_with(|| builder.consume_one_evaluation_length())
+            .take(self.inputs.len())
+            .collect();
+        if output_length != input_lengths.iter().sum::<usize>() {
+            return Err(ProofError::VerificationError {
+                error: "length of the result of union is invalid",
+            });
+        }
+
+        // 1. inputs
+        // Each input is evaluated on its own table and its output is not the query result,
+        // so both lengths have to be swapped in.
+        // If the union is the query result, each input gets the rows of it in its window,
+        // since some plans e.g. `GroupByExec` need their result to be verified.
+        let input_results = result
+            .map(|table| {
+                Self::windows(&input_lengths)
+                    .into_iter()
+                    .zip(&self.inputs)
+                    .map(|((start, end), input)| {
+                        OwnedTable::try_from_iter(
+                            input
+                                .get_column_result_fields()
+                                .iter()
+                                .zip(table.inner_table().values())
+                                .map(|(field, column)| (field.name(), column.slice(start, end))),
+                        )
+                        .map_err(|_| ProofError::VerificationError {
+                            error: "result of union does not match its inputs",
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+        let mut input_evals = Vec::with_capacity(self.inputs.len());
+        for (i, (input, &input_length)) in self.inputs.iter().zip(&input_lengths).enumerate() {
+            let table_length_of_parent = builder
+                .mle_evaluations
+                .replace_input_length(input.get_length(&accessor));
+            let output_length_of_parent =
+                builder.mle_evaluations.replace_output_length(input_length);
+            let input_result = input_results
+                .as_ref()
+                .map(|input_results| &input_results[i]);
+            let evals = input.verifier_evaluate(builder, accessor, input_result);
+            builder
+                .mle_evaluations
+                .replace_output_length(output_length_of_parent);
+            builder
+                .mle_evaluations
+                .replace_input_length(table_length_of_parent);
+            input_evals.push(evals?);
+        }
+
+        // 2. first round witness
+        for evals in &input_evals {
+            let committed_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+                .take(num_columns)
+                .collect();
+            if *evals != committed_evals {
+                return Err(ProofError::VerificationError {
+                    error: "committed input of union does not match its input",
+                });
+            }
+        }
+        let output_evals: Vec<_> = repeat_with(|| builder.consume_first_round_mle())
+            .take(num_columns)
+            .collect();
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 3. the rows of the ith input are the rows of the result in its window
+        let rho_eval = builder.mle_evaluations.rho_evaluation();
+        let output_one_eval = builder.mle_evaluations.chi_evaluation(output_length);
+        for ((evals, &input_length), (start, end)) in input_evals
+            .iter()
+            .zip(&input_lengths)
+            .zip(Self::windows(&input_lengths))
+        {
+            let input_one_eval = builder.mle_evaluations.chi_evaluation(input_length);
+            let window_eval = builder.mle_evaluations.chi_evaluation(end)
+                - builder.mle_evaluations.chi_evaluation(start);
+            verifier_evaluate_membership_check(
+                builder,
+                alpha,
+                beta,
+                input_one_eval,
+                &[
+                    [rho_eval + C::Scalar::from([start as u64, 0, 0, 0])].as_slice(),
+                    evals,
+                ]
+                .concat(),
+                output_one_eval,
+                &[[rho_eval].as_slice(), &output_evals].concat(),
+                window_eval,
+            );
+        }
+
+        Ok(output_evals)
+    }
+
+    fn get_column_result_fields(&self) -> Vec<ColumnField> {
+        self.inputs[0].get_column_result_fields()
+    }
+
+    fn get_column_references(&self) -> IndexSet<ColumnRef> {
+        self.inputs
+            .iter()
+            .flat_map(ProofPlan::get_column_references)
+            .collect()
+    }
+}
+
+impl<C: Commitment> ProverEvaluate<C::Scalar> for UnionExec<C> {
+    #[tracing::instrument(name = "UnionExec::result_evaluate", level = "debug", skip_all)]
+    fn result_evaluate<'a>(
+        &self,
+        _input_length: usize,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        let inputs = self.evaluate_inputs(alloc, accessor);
+        self.union_columns(alloc, &inputs)
+    }
+
+    #[tracing::instrument(name = "UnionExec::first_round_evaluate", level = "debug", skip_all)]
+    fn first_round_evaluate<'a>(
+        &self,
+        builder: &mut FirstRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) {
+        // The verifier needs the lengths of the inputs before it verifies them.
+        let inputs = self.evaluate_inputs(alloc, accessor);
+        for input in &inputs {
+            builder.produce_one_evaluation_length(input.first().map_or(0, Column::len));
+        }
+        for input in &self.inputs {
+            input.first_round_evaluate(builder, alloc, accessor);
+        }
+        let output = self.union_columns(alloc, &inputs);
+        for column in inputs.iter().flatten().chain(&output) {
+            builder.produce_intermediate_mle(*column);
+        }
+        builder.request_post_result_challenges(2);
+    }
+
+    #[tracing::instrument(name = "UnionExec::final_round_evaluate", level = "debug", skip_all)]
+    fn final_round_evaluate<'a>(
+        &self,
+        builder: &mut FinalRoundBuilder<'a, C::Scalar>,
+        alloc: &'a Bump,
+        accessor: &'a dyn DataAccessor<C::Scalar>,
+    ) -> Vec<Column<'a, C::Scalar>> {
+        // 1. inputs, each evaluated on its own table
+        let table_length_of_parent = builder.table_length();
+        let inputs: Vec<_> = self
+            .inputs
+            .iter()
+            .map(|input| {
+                builder.set_table_length(input.get_length(&accessor));
+                input.final_round_evaluate(builder, alloc, accessor)
+            })
+            .collect();
+        builder.set_table_length(table_length_of_parent);
+        let input_lengths: Vec<_> = inputs
+            .iter()
+            .map(|input| input.first().map_or(0, Column::len))
+            .collect();
+        let output = self.union_columns(alloc, &inputs);
+        let output_length = input_lengths.iter().sum();
+
+        let alpha = builder.consume_post_result_challenge();
+        let beta = builder.consume_post_result_challenge();
+
+        // 2. the rows of the ith input are the rows of the result in its window
+        let rho = prover_evaluate_row_index(builder, alloc);
+        for ((input, &input_length), (start, end)) in inputs
+            .iter()
+            .zip(&input_lengths)
+            .zip(Self::windows(&input_lengths))
+        {
+            let shifted_rho: &[_] = alloc.alloc_slice_fill_with(rho.len(), |i| {
+                rho[i] + C::Scalar::from([start as u64, 0, 0, 0])
+            });
+            let window = alloc.alloc_slice_fill_with(output_length, |i| (start..end).contains(&i));
+            prover_evaluate_membership_check(
+                builder,
+                alloc,
+                alpha,
+                beta,
+                input_length,
+                &core::iter::once(Column::Scalar(shifted_rho))
+                    .chain(input.iter().copied())
+                    .collect::<Vec<_>>(),
+                output_length,
+                &core::iter::once(Column::Scalar(rho))
+                    .chain(output.iter().copied())
+                    .collect::<Vec<_>>(),
+                Column::Boolean(window),
+            );
+        }
+
+        output
+    }
+}
@@ -0,0 +1,214 @@
+use super::test_utility::*;
+use crate::{
+    base::{
+        database::{
+            owned_table_utility::*, ColumnField, ColumnType, OwnedTable, OwnedTableTestAccessor,
+            TableRef, TestAccessor,
+        },
+        map::IndexSet,
+        scalar::Curve25519Scalar,
+    },
+    sql::{
+        proof::{exercise_verification, ProofPlan, VerifiableQueryResult},
+        proof_exprs::test_utility::*,
+    },
+};
+use blitzar::proof::InnerProductProof;
+use curve25519_dalek::RistrettoPoint;
+use proof_of_sql_parser::intermediate_ast::OrderByDirection::Desc;
+
+fn make_accessor(
+    data_1: OwnedTable<Curve25519Scalar>,
+    data_2: OwnedTable<Curve25519Scalar>,
+) -> (
+    OwnedTableTestAccessor<'static, InnerProductProof>,
+    TableRef,
+    TableRef,
+) {
+    let t1 = "sxt.tx_2024_01".parse().unwrap();
+    let t2 = "sxt.tx_2024_02".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t1, data_1, 0);
+    accessor.add_table(t2, data_2, 0);
+    (accessor, t1, t2)
+}
+
+#[test]
+fn we_can_correctly_fetch_the_query_result_schema_and_column_references() {
+    let (accessor, t1, t2) = make_accessor(
+        owned_table([bigint("a", [1_i64, 2]), varchar("b", ["x", "y"])]),
+        owned_table([
+            bigint("a", [3_i64, 4, 5]),
+            varchar("b", ["z", "w", "v"]),
+            bigint("c", [6_i64, 7, 8]),
+        ]),
+    );
+    let ast = union_exec::<RistrettoPoint>(vec![
+        projection(cols_expr_plan(t1, &["a", "b"], &accessor), tab(t1)),
+        filter(
+            vec![
+                aliased_plan(column(t2, "c", &accessor), "c"),
+                aliased_plan(column(t2, "b", &accessor), "d"),
+            ],
+            tab(t2),
+            equal(column(t2, "a", &accessor), const_bigint(3)),
+        ),
+    ]);
+    assert_eq!(
+        ast.get_column_result_fields(),
+        vec![
+            ColumnField::new("a".parse().unwrap(), ColumnType::BigInt),
+            ColumnField::new("b".parse().unwrap(), ColumnType::VarChar),
+        ]
+    );
+    let expected_refs = IndexSet::from_iter([
+        col_ref(t1, "a", &accessor),
+        col_ref(t1, "b", &accessor),
+        col_ref(t2, "c", &accessor),
+        col_ref(t2, "b", &accessor),
+        col_ref(t2, "a", &accessor),
+    ]);
+    assert_eq!(ast.get_column_references(), expected_refs);
+    assert_eq!(ast.get_length(&accessor), 5);
+    assert_eq!(ast.get_offset(&accessor), 0);
+}
+
+#[test]
+fn we_can_prove_a_union_of_filters_on_different_tables() {
+    let (accessor, t1, t2) = make_accessor(
+        owned_table([
+            bigint("a", [5_i64, -2, 7, 5]),
+            varchar("b", ["a", "b", "c", "d"]),
+        ]),
+        owned_table([bigint("a", [1_i64, 0, 3]), varchar("b", ["e", "f", "g"])]),
+    );
+    let ast = union_exec(vec![
+        filter(
+            cols_expr_plan(t1, &["b", "a"], &accessor),
+            tab(t1),
+            equal(column(t1, "a", &accessor), const_bigint(5)),
+        ),
+        filter(
+            cols_expr_plan(t2, &["b", "a"], &accessor),
+            tab(t2),
+            gte(column(t2, "a", &accessor), const_bigint(1)),
+        ),
+    ]);
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t1);
+    exercise_verification(&verifiable_res, &ast, &accessor, t2);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        varchar("b", ["a", "d", "e", "g"]),
+        bigint("a", [5_i64, 5, 1, 3]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_union_of_more_than_two_inputs_with_empty_ones() {
+    let (accessor, t1, t2) = make_accessor(
+        owned_table([bigint("a", [1_i64, 2, 3])]),
+        owned_table([bigint("a", [4_i64, 5])]),
+    );
+    let ast = union_exec(vec![
+        filter(
+            cols_expr_plan(t1, &["a"], &accessor),
+            tab(t1),
+            const_bool(false),
+        ),
+        projection(cols_expr_plan(t2, &["a"], &accessor), tab(t2)),
+        filter(
+            cols_expr_plan(t1, &["a"], &accessor),
+            tab(t1),
+            gte(column(t1, "a", &accessor), const_bigint(2)),
+        ),
+        filter(
+            cols_expr_plan(t2, &["a"], &accessor),
+            tab(t2),
+            const_bool(false),
+        ),
+    ]);
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t1);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    assert_eq!(res, owned_table([bigint("a", [4_i64, 5, 2, 3])]));
+}
+
+#[test]
+fn we_can_prove_a_union_of_group_bys_on_different_tables() {
+    let (accessor, t1, t2) = make_accessor(
+        owned_table([
+            varchar("wallet", ["x", "y", "x", "z"]),
+            bigint("amount", [10_i64, 20, 30, 40]),
+        ]),
+        owned_table([
+            varchar("wallet", ["y", "y", "x"]),
+            bigint("amount", [50_i64, 60, 70]),
+        ]),
+    );
+    let ast = union_exec(vec![
+        group_by(
+            cols_expr_plan(t1, &["wallet"], &accessor),
+            vec![sum_expr(column(t1, "amount", &accessor), "total")],
+            "num",
+            tab(t1),
+            const_bool(true),
+        ),
+        group_by(
+            cols_expr_plan(t2, &["wallet"], &accessor),
+            vec![sum_expr(column(t2, "amount", &accessor), "total")],
+            "num",
+            tab(t2),
+            const_bool(true),
+        ),
+    ]);
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t1);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([
+        varchar("wallet", ["x", "y", "z", "x", "y"]),
+        bigint("total", [40_i64, 20, 40, 70, 110]),
+        bigint("num", [2_i64, 1, 1, 1, 2]),
+    ]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_can_prove_a_slice_of_a_sorted_union() {
+    let (accessor, t1, t2) = make_accessor(
+        owned_table([bigint("a", [5_i64, -2, 7]), varchar("b", ["a", "b", "c"])]),
+        owned_table([bigint("a", [0_i64, 9]), varchar("b", ["d", "e"])]),
+    );
+    let ast = slice_exec(
+        sort(
+            union_exec(vec![
+                projection(cols_expr_plan(t1, &["a", "b"], &accessor), tab(t1)),
+                projection(cols_expr_plan(t2, &["a", "b"], &accessor), tab(t2)),
+            ]),
+            vec![(0, Desc)],
+        ),
+        1,
+        Some(3),
+    );
+    let verifiable_res = VerifiableQueryResult::new(&ast, &accessor, &());
+    exercise_verification(&verifiable_res, &ast, &accessor, t2);
+    let res = verifiable_res.verify(&ast, &accessor, &()).unwrap().table;
+    let expected_res = owned_table([bigint("a", [7_i64, 5, 0]), varchar("b", ["c", "a", "d"])]);
+    assert_eq!(res, expected_res);
+}
+
+#[test]
+fn we_cannot_verify_a_union_of_tables_with_different_offsets() {
+    let t1 = "sxt.tx_2024_01".parse().unwrap();
+    let t2 = "sxt.tx_2024_02".parse().unwrap();
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(t1, owned_table([bigint("a", [1_i64, 2])]), 0);
+    accessor.add_table(t2, owned_table([bigint("a", [3_i64, 4])]), 2);
+    let ast = union_exec(vec![
+        projection(cols_expr_plan(t1, &["a"], &accessor), tab(t1)),
+        projection(cols_expr_plan(t2, &["a"], &accessor), tab(t2)),
+    ]);
+    let verifiable_res = VerifiableQueryResult::<InnerProductProof>::new(&ast, &accessor, &());
+    assert!(verifiable_res.verify(&ast, &accessor, &()).is_err());
+}
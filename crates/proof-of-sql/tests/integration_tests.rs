@@ -6,7 +6,10 @@ use curve25519_dalek::RistrettoPoint;
 use proof_of_sql::base::commitment::InnerProductProof;
 use proof_of_sql::{
     base::{
-        database::{owned_table_utility::*, OwnedTable, OwnedTableTestAccessor, TestAccessor},
+        database::{
+            owned_table_utility::*, HidingTableTestAccessor, OwnedTable, OwnedTableTestAccessor,
+            TestAccessor,
+        },
         scalar::Curve25519Scalar,
     },
     proof_primitive::dory::{
@@ -16,9 +19,10 @@ use proof_of_sql::{
     sql::{
         parse::{ConversionError, QueryExpr},
         postprocessing::apply_postprocessing_steps,
-        proof::{QueryError, QueryProof},
+        proof::{BatchQueryProof, HidingQueryProof, QueryError, QueryProof},
     },
 };
+use rand::{rngs::StdRng, SeedableRng};
 
 #[test]
 #[cfg(feature = "blitzar")]
@@ -45,6 +49,31 @@ fn we_can_prove_a_minimal_filter_query_with_curve25519() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+fn we_can_prove_a_minimal_filter_query_in_zero_knowledge() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let accessor = HidingTableTestAccessor::new_from_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 5, 3, 5]), bigint("b", [1, 2, 3, 4])]),
+        0,
+        &mut rng,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT b FROM table WHERE a = 5".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        HidingQueryProof::new(query.proof_expr(), &accessor, &mut rng).unwrap();
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result)
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("b", [2, 4])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 #[test]
 fn we_can_prove_a_minimal_filter_query_with_dory() {
     let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
@@ -81,6 +110,50 @@ fn we_can_prove_a_minimal_filter_query_with_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+fn we_can_prove_a_batch_of_queries_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1_i64, 2, 3, 2, 1]),
+            varchar("b", ["x", "y", "z", "w", "x"]),
+        ]),
+        0,
+    );
+    let queries = [
+        "SELECT b FROM table WHERE a = 2",
+        "SELECT a, count(*) AS c FROM table GROUP BY a",
+    ]
+    .map(|sql| {
+        QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap()
+    });
+    let exprs: Vec<_> = queries.iter().map(QueryExpr::proof_expr).collect();
+    let (proof, serialized_results) =
+        BatchQueryProof::<DoryEvaluationProof>::new(&exprs, &accessor, &dory_prover_setup);
+    let owned_table_results: Vec<_> = proof
+        .verify(&exprs, &accessor, &serialized_results, &dory_verifier_setup)
+        .unwrap()
+        .into_iter()
+        .zip(&queries)
+        .map(|(data, query)| {
+            apply_postprocessing_steps(data.table, query.postprocessing()).unwrap()
+        })
+        .collect();
+    let expected_results = vec![
+        owned_table([varchar("b", ["y", "w"])]),
+        owned_table([bigint("a", [1_i64, 2, 3]), bigint("c", [2_i64, 2, 1])]),
+    ];
+    assert_eq!(owned_table_results, expected_results);
+}
+
 #[test]
 fn we_can_prove_a_minimal_filter_query_with_dynamic_dory() {
     let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
@@ -599,6 +672,42 @@ fn we_can_prove_a_basic_group_by_query_with_curve25519() {
     assert_eq!(owned_table_result, expected_result);
 }
 
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_group_by_query_with_a_count_of_a_nullable_column_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2]),
+            bigint("b", [1, 0, 4, 0, 3]),
+            boolean("__b_presence__", [true, false, true, false, false]),
+        ]),
+        0,
+    );
+    // Null rows are not counted
+    let query = QueryExpr::try_new(
+        "SELECT a, count(b) as n, count(*) as c FROM table group by a"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("n", [1, 1, 0]),
+        bigint("c", [2, 2, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
 #[test]
 #[cfg(feature = "blitzar")]
 fn we_can_prove_a_cat_group_by_query_with_curve25519() {
@@ -787,32 +896,82 @@ fn we_can_prove_a_basic_group_by_query_with_dory() {
     assert_eq!(owned_table_result, expected_result);
 }
 
-// Overflow checks
 #[test]
-#[cfg(feature = "blitzar")]
-fn we_can_prove_a_query_with_overflow_with_curve25519() {
-    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+fn we_can_prove_a_group_by_query_with_a_having_clause_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
     accessor.add_table(
         "sxt.table".parse().unwrap(),
-        owned_table([smallint("a", [i16::MAX]), smallint("b", [1_i16])]),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2, 3]),
+            bigint("b", [1, 0, 4, 2, 3, 7]),
+        ]),
         0,
     );
+    // The HAVING expression only depends on the results, so both the group by
+    // and the filter of its groups are proven
     let query = QueryExpr::try_new(
-        "SELECT a + b as c from table".parse().unwrap(),
+        "SELECT a, sum(b) as s, count(*) as n FROM table group by a having count(*) >= 2 and sum(b) > 2"
+            .parse()
+            .unwrap(),
         "sxt".parse().unwrap(),
         &accessor,
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
-    assert!(matches!(
-        proof.verify(query.proof_expr(), &accessor, &serialized_result, &()),
-        Err(QueryError::Overflow)
-    ));
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<_> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        bigint("a", [2, 3]),
+        bigint("s", [7, 9]),
+        bigint("n", [2, 2]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+
+    // The HAVING expression needs an aggregation that isn't a result
+    let query = QueryExpr::try_new(
+        "SELECT a FROM table group by a having max(b) <= 4"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<_> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([bigint("a", [1, 2])]);
+    assert_eq!(transformed_result, expected_result);
 }
 
 #[test]
-fn we_can_prove_a_query_with_overflow_with_dory() {
+fn we_can_prove_distinct_queries_with_dory() {
     let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
     let prover_setup = ProverSetup::from(&public_parameters);
     let verifier_setup = VerifierSetup::from(&public_parameters);
@@ -823,43 +982,87 @@ fn we_can_prove_a_query_with_overflow_with_dory() {
         OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
     accessor.add_table(
         "sxt.table".parse().unwrap(),
-        owned_table([bigint("a", [i64::MIN]), smallint("b", [1_i16])]),
+        owned_table([
+            bigint("day", [1, 2, 1, 1, 2, 3, 2]),
+            varchar("wallet", ["x", "y", "x", "z", "x", "w", "y"]),
+            boolean(
+                "__wallet_presence__",
+                [true, true, true, true, true, false, true],
+            ),
+        ]),
         0,
     );
+    // The distinct wallets of each day are proven and then counted
     let query = QueryExpr::try_new(
-        "SELECT a - b as c from table".parse().unwrap(),
+        "SELECT day, count(distinct wallet) as wallets FROM table group by day order by day"
+            .parse()
+            .unwrap(),
         "sxt".parse().unwrap(),
         &accessor,
     )
     .unwrap();
     let (proof, serialized_result) =
         QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
-    assert!(matches!(
-        proof.verify(
+    let owned_table_result = proof
+        .verify(
             query.proof_expr(),
             &accessor,
             &serialized_result,
-            &dory_verifier_setup
-        ),
-        Err(QueryError::Overflow)
-    ));
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<_> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([bigint("day", [1, 2, 3]), bigint("wallets", [2, 2, 0])]);
+    assert_eq!(transformed_result, expected_result);
+
+    let query = QueryExpr::try_new(
+        "SELECT DISTINCT day FROM table where wallet = 'x'"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<_> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([bigint("day", [1, 2])]);
+    assert_eq!(transformed_result, expected_result);
 }
 
 #[test]
-#[cfg(feature = "blitzar")]
-fn we_can_perform_arithmetic_and_conditional_operations_on_tinyint() {
-    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+fn we_can_prove_a_group_by_query_with_a_max_and_a_min_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
     accessor.add_table(
         "sxt.table".parse().unwrap(),
         owned_table([
-            tinyint("a", [3_i8, 5, 2, 1]),
-            tinyint("b", [2_i8, 1, 3, 4]),
-            tinyint("c", [1_i8, 4, 5, 2]),
+            bigint("a", [1, 1, 2, 3, 2, 1]),
+            bigint("b", [1, 0, 4, 2, 3, -5]),
+            bigint("c", [-2, 2, 1, 0, 1, 3]),
         ]),
         0,
     );
     let query = QueryExpr::try_new(
-        "SELECT a*b+b+c as result FROM table WHERE a>b OR c=4"
+        "SELECT a, MAX(b) as max_b, MIN(b + c) as min_d FROM table WHERE c >= 0 group by a"
             .parse()
             .unwrap(),
         "sxt".parse().unwrap(),
@@ -867,11 +1070,938 @@ fn we_can_perform_arithmetic_and_conditional_operations_on_tinyint() {
     )
     .unwrap();
     let (proof, serialized_result) =
-        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
     let owned_table_result = proof
-        .verify(query.proof_expr(), &accessor, &serialized_result, &())
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
         .unwrap()
         .table;
-    let expected_result = owned_table([tinyint("result", [9_i8, 10])]);
+    let expected_result = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("max_b", [0, 4, 2]),
+        bigint("min_d", [-2, 4, 2]),
+    ]);
     assert_eq!(owned_table_result, expected_result);
 }
+
+#[test]
+fn we_can_prove_a_group_by_query_with_arithmetic_on_the_aggregations_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [1, 1, 2, 3, 2]), bigint("b", [1, 0, 4, 2, 3])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT 2 * a as d, sum(b) + 1 as s, count(b) as e FROM table group by a"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        bigint("d", [2, 4, 6]),
+        bigint("s", [2, 8, 3]),
+        bigint("e", [2, 2, 1]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+// Overflow checks
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_query_with_overflow_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([smallint("a", [i16::MAX]), smallint("b", [1_i16])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a + b as c from table".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    assert!(matches!(
+        proof.verify(query.proof_expr(), &accessor, &serialized_result, &()),
+        Err(QueryError::Overflow)
+    ));
+}
+
+#[test]
+fn we_can_prove_a_query_with_overflow_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [i64::MIN]), smallint("b", [1_i16])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a - b as c from table".parse().unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    assert!(matches!(
+        proof.verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup
+        ),
+        Err(QueryError::Overflow)
+    ));
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_perform_arithmetic_and_conditional_operations_on_tinyint() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            tinyint("a", [3_i8, 5, 2, 1]),
+            tinyint("b", [2_i8, 1, 3, 4]),
+            tinyint("c", [1_i8, 4, 5, 2]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a*b+b+c as result FROM table WHERE a>b OR c=4"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([tinyint("result", [9_i8, 10])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_join_query_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.users".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3]),
+            varchar("name", ["alice", "bob", "carol"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.orders".parse().unwrap(),
+        owned_table([
+            bigint("user_id", [3, 1, 3, 4]),
+            bigint("amount", [5, 7, 2, 9]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT users.name, amount FROM users JOIN orders ON users.id = orders.user_id ORDER BY amount"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result, &())
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<Curve25519Scalar> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        varchar("name", ["carol", "carol", "alice"]),
+        bigint("amount", [2, 5, 7]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_join_query_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.users".parse().unwrap(),
+        owned_table([
+            bigint("id", [1, 2, 3]),
+            varchar("name", ["alice", "bob", "carol"]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.orders".parse().unwrap(),
+        owned_table([
+            bigint("user_id", [3, 1, 3, 4]),
+            bigint("amount", [5, 7, 2, 9]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT name, amount FROM users JOIN orders ON id = user_id"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        varchar("name", ["alice", "carol", "carol"]),
+        bigint("amount", [7, 5, 2]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_order_by_query_with_a_limit_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [3, -1, 4, 1, 5, 9, 2, 6]),
+            bigint("b", [1, 0, 1, 1, 0, 1, 0, 1]),
+            varchar("c", ["t", "h", "r", "e", "e", "o", "n", "e"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT c, a FROM table WHERE b = 1 ORDER BY a DESC LIMIT 2"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    // Both the sort and the limit are proven, so only the first two rows are returned.
+    let expected_result = owned_table([varchar("c", ["o", "e"]), bigint("a", [9, 6])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_like_query_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5]),
+            varchar("name", ["abc", "test_abc", "abcdef", "xyz", "abtest"]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new_revealing_like_dictionaries(
+        "SELECT a FROM table WHERE name LIKE 'abc%' OR name NOT LIKE '%test%'"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [1, 3, 4])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_an_in_list_and_between_query_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4, 5]),
+            int("b", [-20, 7, 12, 0, 1000]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a FROM table WHERE a IN (1, 3, 5, 7) AND b NOT BETWEEN 0 AND 100"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("a", [1, 5])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_group_by_query_with_a_conditional_sum_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 1, 2, 3, 2]),
+            bigint("b", [1, 0, 4, 2, 3]),
+            bigint("c", [5, 2, 7, 9, 1]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a, sum(CASE WHEN b >= 2 THEN c ELSE 0 END) as d, count(*) as e FROM table group by a"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([
+        bigint("a", [1, 2, 3]),
+        bigint("d", [0, 8, 9]),
+        bigint("e", [2, 2, 1]),
+    ]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_with_casts_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([
+            bigint("a", [1, 2, 3, 4]),
+            decimal75("b", 5, 2, [150, 150, 300, 500]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT CAST(a AS DECIMAL(21, 2)) as c FROM table WHERE a::decimal(21, 2) >= b"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([decimal75("c", 21, 2, [200, 300])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+#[cfg(feature = "blitzar")]
+fn we_can_prove_a_query_with_integer_division_and_modulo_with_curve25519() {
+    let mut accessor = OwnedTableTestAccessor::<InnerProductProof>::new_empty_with_setup(());
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [7, -7, 10, 12, -9]), int("b", [2, 2, 0, -5, 3])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a / b as q, a % b as r FROM table WHERE a % 3 <> 0"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<InnerProductProof>::new(query.proof_expr(), &accessor, &());
+    let owned_table_result = proof
+        .verify(query.proof_expr(), &accessor, &serialized_result, &())
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("q", [3, -3, 0]), bigint("r", [1, -1, 10])]);
+    assert_eq!(owned_table_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_query_with_integer_division_and_modulo_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.table".parse().unwrap(),
+        owned_table([bigint("a", [7, -7, 10, 12, -9]), int("b", [2, 2, 0, -5, 3])]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT a / b as q, a % b as r FROM table WHERE a % 3 <> 0"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let expected_result = owned_table([bigint("q", [3, -3, 0]), bigint("r", [1, -1, 10])]);
+    assert_eq!(owned_table_result, expected_result);
+
+    // Dividing by zero in postprocessing gives the same result as in the proof
+    let query = QueryExpr::try_new(
+        "SELECT sum(a) / sum(b) as q, sum(a) % sum(b) as r FROM table WHERE b = 0"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<_> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([bigint("q", [0]), bigint("r", [10])]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_a_union_of_tables_with_dory() {
+    let public_parameters = PublicParameters::test_rand(4, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.tx_2024_01".parse().unwrap(),
+        owned_table([
+            bigint("day", [1, 2, 2, 3]),
+            varchar("wallet", ["x", "y", "x", "z"]),
+            bigint("amount", [10, 20, 30, 40]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.tx_2024_02".parse().unwrap(),
+        owned_table([
+            bigint("day", [1, 1, 3]),
+            varchar("wallet", ["y", "y", "x"]),
+            bigint("amount", [50, 60, 70]),
+        ]),
+        0,
+    );
+    let query = QueryExpr::try_new(
+        "SELECT wallet, amount FROM tx_2024_01 WHERE amount > 15 \
+            UNION ALL SELECT wallet, amount FROM tx_2024_02 WHERE day = 1 \
+            ORDER BY amount DESC LIMIT 3"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<_> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        varchar("wallet", ["y", "y", "z"]),
+        bigint("amount", [60, 50, 40]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+
+    // The aggregation of each month is proven
+    let query = QueryExpr::try_new(
+        "SELECT wallet, sum(amount) as total, count(*) as num FROM tx_2024_01 GROUP BY wallet \
+            UNION ALL SELECT wallet, sum(amount) as total, count(*) as num FROM tx_2024_02 \
+            GROUP BY wallet ORDER BY total DESC, wallet LIMIT 4"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<_> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        varchar("wallet", ["y", "x", "x", "z"]),
+        bigint("total", [110, 70, 40, 40]),
+        bigint("num", [2, 1, 2, 1]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+
+    // The aggregation over both months is proven by reading the union as a derived table
+    let query = QueryExpr::try_new(
+        "SELECT wallet, sum(amount) as total, count(*) as num FROM \
+            (SELECT wallet, amount FROM tx_2024_01 UNION ALL SELECT wallet, amount FROM tx_2024_02) \
+            AS tx GROUP BY wallet ORDER BY wallet"
+            .parse()
+            .unwrap(),
+        "sxt".parse().unwrap(),
+        &accessor,
+    )
+    .unwrap();
+    let (proof, serialized_result) =
+        QueryProof::<DoryEvaluationProof>::new(query.proof_expr(), &accessor, &dory_prover_setup);
+    let owned_table_result = proof
+        .verify(
+            query.proof_expr(),
+            &accessor,
+            &serialized_result,
+            &dory_verifier_setup,
+        )
+        .unwrap()
+        .table;
+    let transformed_result: OwnedTable<_> =
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap();
+    let expected_result = owned_table([
+        varchar("wallet", ["x", "y", "z"]),
+        bigint("total", [110, 130, 40]),
+        bigint("num", [3, 3, 1]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_queries_over_derived_tables_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.tx".parse().unwrap(),
+        owned_table([
+            bigint("day", [3, 1, 2, 3, 2, 3]),
+            varchar("wallet", ["x", "y", "x", "z", "y", "x"]),
+            bigint("amount", [10, 20, 30, 40, 50, 60]),
+        ]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap()
+    };
+
+    // A filter over the result of an aggregation
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT day, total FROM \
+            (SELECT day, sum(amount) AS total, count(*) AS n FROM tx GROUP BY day) AS g \
+            WHERE n > 1",
+    );
+    let expected_result = owned_table([bigint("day", [2, 3]), bigint("total", [80, 110])]);
+    assert_eq!(transformed_result, expected_result);
+
+    // An aggregation over the result of a filter
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT wallet, count(*) AS n FROM \
+            (SELECT wallet, amount FROM tx WHERE amount > 15) big GROUP BY wallet",
+    );
+    let expected_result = owned_table([varchar("wallet", ["x", "y", "z"]), bigint("n", [2, 2, 1])]);
+    assert_eq!(transformed_result, expected_result);
+
+    // Nested derived tables with a proven sort and slice
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT * FROM (SELECT * FROM (SELECT day, amount FROM tx WHERE day >= 2) AS a \
+            WHERE amount < 60) AS b ORDER BY amount DESC LIMIT 2",
+    );
+    let expected_result = owned_table([bigint("day", [2, 3]), bigint("amount", [50, 40])]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_queries_with_common_table_expressions_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.tx".parse().unwrap(),
+        owned_table([
+            bigint("day", [3, 1, 2, 3, 2, 3]),
+            varchar("wallet", ["x", "y", "x", "z", "y", "x"]),
+            bigint("amount", [10, 20, 30, 40, 50, 60]),
+        ]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap()
+    };
+
+    // A common table expression referenced twice
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "WITH big AS (SELECT wallet, amount FROM tx WHERE amount > 15) \
+            SELECT * FROM big WHERE wallet = 'x' UNION ALL SELECT * FROM big WHERE amount > 45 \
+            ORDER BY amount",
+    );
+    let expected_result = owned_table([
+        varchar("wallet", ["x", "y", "x", "x"]),
+        bigint("amount", [30, 50, 60, 60]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+
+    // A common table expression over the result of an aggregation in another one
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "WITH daily AS (SELECT day, sum(amount) AS total, count(*) AS n FROM tx GROUP BY day), \
+            busy AS (SELECT day, total FROM daily WHERE n > 1) \
+            SELECT day, total FROM busy ORDER BY total DESC",
+    );
+    let expected_result = owned_table([bigint("day", [3, 2]), bigint("total", [110, 80])]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_queries_with_window_functions_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.tx".parse().unwrap(),
+        owned_table([
+            bigint("day", [3, 1, 2, 3, 2, 3]),
+            varchar("wallet", ["x", "y", "x", "z", "y", "x"]),
+            bigint("amount", [10, 20, 30, 40, 50, 60]),
+        ]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap()
+    };
+
+    // A running sum is proven
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT day, amount, SUM(amount) OVER (PARTITION BY day ORDER BY amount) AS running \
+            FROM tx",
+    );
+    let expected_result = owned_table([
+        bigint("day", [1, 2, 2, 3, 3, 3]),
+        bigint("amount", [20, 30, 50, 10, 40, 60]),
+        bigint("running", [20, 30, 80, 10, 50, 110]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+
+    // Other window functions are evaluated in postprocessing
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT wallet, ROW_NUMBER() OVER (PARTITION BY wallet ORDER BY day DESC) AS rn, \
+            RANK() OVER (ORDER BY day) AS rk FROM tx",
+    );
+    let expected_result = owned_table([
+        varchar("wallet", ["x", "y", "x", "z", "y", "x"]),
+        bigint("rn", [1, 2, 3, 1, 1, 2]),
+        bigint("rk", [4, 1, 2, 4, 2, 4]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+}
+
+#[test]
+fn we_can_prove_queries_with_subqueries_in_the_where_clause_with_dory() {
+    let public_parameters = PublicParameters::test_rand(5, &mut test_rng());
+    let prover_setup = ProverSetup::from(&public_parameters);
+    let verifier_setup = VerifierSetup::from(&public_parameters);
+    let dory_prover_setup = DoryProverPublicSetup::new(&prover_setup, 3);
+    let dory_verifier_setup = DoryVerifierPublicSetup::new(&verifier_setup, 3);
+
+    let mut accessor =
+        OwnedTableTestAccessor::<DoryEvaluationProof>::new_empty_with_setup(dory_prover_setup);
+    accessor.add_table(
+        "sxt.tx".parse().unwrap(),
+        owned_table([
+            bigint("day", [3, 1, 2, 3, 2, 3]),
+            varchar("wallet", ["x", "y", "x", "z", "y", "x"]),
+            bigint("amount", [10, 20, 30, 40, 50, 60]),
+        ]),
+        0,
+    );
+    accessor.add_table(
+        "sxt.limits".parse().unwrap(),
+        owned_table([bigint("day", [2, 3, 4]), bigint("cap", [35, 25, 5])]),
+        0,
+    );
+    let prove_and_verify = |sql: &str| {
+        let query =
+            QueryExpr::try_new(sql.parse().unwrap(), "sxt".parse().unwrap(), &accessor).unwrap();
+        let (proof, serialized_result) = QueryProof::<DoryEvaluationProof>::new(
+            query.proof_expr(),
+            &accessor,
+            &dory_prover_setup,
+        );
+        let owned_table_result = proof
+            .verify(
+                query.proof_expr(),
+                &accessor,
+                &serialized_result,
+                &dory_verifier_setup,
+            )
+            .unwrap()
+            .table;
+        apply_postprocessing_steps(owned_table_result, query.postprocessing()).unwrap()
+    };
+
+    // An IN subquery is proven as a semi-join
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT wallet, amount FROM tx WHERE day IN (SELECT day FROM limits WHERE cap >= 20)",
+    );
+    let expected_result = owned_table([
+        varchar("wallet", ["x", "x", "z", "y", "x"]),
+        bigint("amount", [10, 30, 40, 50, 60]),
+    ]);
+    assert_eq!(transformed_result, expected_result);
+
+    // A scalar subquery is proven once and compared to every row
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT wallet, SUM(amount) AS total FROM tx \
+            WHERE amount > (SELECT MIN(cap) AS m FROM limits) \
+            AND day NOT IN (SELECT day FROM limits) GROUP BY wallet",
+    );
+    let expected_result = owned_table([varchar("wallet", ["y"]), bigint("total", [20])]);
+    assert_eq!(transformed_result, expected_result);
+
+    // The value of an empty scalar subquery is null
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT day FROM tx WHERE amount >= (SELECT cap FROM limits WHERE day = 1)",
+    );
+    assert_eq!(transformed_result, owned_table([bigint("day", [0; 0])]));
+
+    // The rows above the average are found through the sum and count of a subquery
+    let transformed_result: OwnedTable<_> = prove_and_verify(
+        "SELECT day, amount FROM tx WHERE amount * (SELECT COUNT(amount) AS n FROM tx) \
+            > (SELECT SUM(amount) AS total FROM tx)",
+    );
+    let expected_result = owned_table([bigint("day", [3, 2, 3]), bigint("amount", [40, 50, 60])]);
+    assert_eq!(transformed_result, expected_result);
+}